@@ -376,6 +376,10 @@ pub enum ScriptCategory {
     Vesting {
         label: &'static str,
     },
+    /// On-chain auction contract holding lots (and usually the standing bid)
+    Auction {
+        label: &'static str,
+    },
 }
 
 impl PartialEq for ScriptCategory {
@@ -423,6 +427,9 @@ impl PartialEq for ScriptCategory {
             (ScriptCategory::Vesting { label: l1 }, ScriptCategory::Vesting { label: l2 }) => {
                 l1 == l2
             }
+            (ScriptCategory::Auction { label: l1 }, ScriptCategory::Auction { label: l2 }) => {
+                l1 == l2
+            }
             _ => false,
         }
     }
@@ -452,6 +459,9 @@ impl fmt::Display for ScriptCategory {
             ScriptCategory::Vesting { label } => {
                 write!(f, "{label} vesting")
             }
+            ScriptCategory::Auction { label } => {
+                write!(f, "{label} auction")
+            }
         }
     }
 }
@@ -527,6 +537,14 @@ pub trait AddressLookup: Send + Sync {
         )
     }
 
+    /// Check if the address belongs to a known auction contract.
+    fn is_any_auction_address(&self, address: &str) -> bool {
+        matches!(
+            self.lookup(address),
+            Some(AddressCategory::Script(ScriptCategory::Auction { .. }))
+        )
+    }
+
     /// Get the fee calculation function for a marketplace address.
     fn get_marketplace_fee_calculation(&self, address: &str) -> Option<FeeCalculationFn> {
         match self.lookup(address) {
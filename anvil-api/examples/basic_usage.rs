@@ -1,4 +1,4 @@
-use anvil_api::{AnvilClient, CollectionAssetsRequest, SaleType};
+use anvil_api::{AnvilClient, CollectionAssetsRequest, PolicyId, SaleType};
 use dotenv::dotenv;
 use std::env;
 use tracing::info;
@@ -20,11 +20,10 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         }
     };
 
-    let request = CollectionAssetsRequest::new(
-        "b3dab69f7e6100849434fb1781e34bd12a916557f6231b8d2629b6f6".to_string(),
-    )
-    .with_limit(20)
-    .with_sale_type(SaleType::All);
+    let policy_id: PolicyId = "b3dab69f7e6100849434fb1781e34bd12a916557f6231b8d2629b6f6".parse()?;
+    let request = CollectionAssetsRequest::new(&policy_id)
+        .with_limit(20)
+        .with_sale_type(SaleType::All);
 
     match client.get_collection_assets(&request).await {
         Ok(response) => {
@@ -1,4 +1,4 @@
-use anvil_api::AnvilClient;
+use anvil_api::{AnvilClient, PolicyId};
 use dotenv::dotenv;
 use std::env;
 
@@ -20,14 +20,14 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     let client = AnvilClient::new().with_api_key(&api_key);
 
     // Use Blackflag policy ID
-    let policy_id = "b3dab69f7e6100849434fb1781e34bd12a916557f6231b8d2629b6f6";
+    let policy_id: PolicyId = "b3dab69f7e6100849434fb1781e34bd12a916557f6231b8d2629b6f6".parse()?;
 
     println!("🏴‍☠️ Collection Details Utility Example");
     println!("=====================================");
     println!("Policy ID: {}", policy_id);
     println!();
 
-    match client.get_collection_details(policy_id).await {
+    match client.get_collection_details(&policy_id).await {
         Ok(collection) => {
             println!("✅ Collection Details Retrieved:");
             println!("   📛 Name: {}", collection.name);
@@ -1,4 +1,4 @@
-use anvil_api::{AnvilClient, CollectionAssetsRequest, OrderBy};
+use anvil_api::{AnvilClient, CollectionAssetsRequest, OrderBy, PolicyId};
 use dotenv::dotenv;
 use std::env;
 
@@ -20,14 +20,14 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     let client = AnvilClient::new().with_api_key(&api_key);
 
     // Use Blackflag policy ID
-    let policy_id = "b3dab69f7e6100849434fb1781e34bd12a916557f6231b8d2629b6f6";
+    let policy_id: PolicyId = "b3dab69f7e6100849434fb1781e34bd12a916557f6231b8d2629b6f6".parse()?;
 
     println!("🔍 Testing Combined Free Text + Trait Filtering");
     println!("==============================================");
 
     // Test 1: Get baseline - all "Pirate" assets to see trait distribution
     println!("\n📋 Test 1: Get all 'Pirate' assets (baseline)");
-    let baseline_request = CollectionAssetsRequest::for_listed_assets(policy_id, Some(20))
+    let baseline_request = CollectionAssetsRequest::for_listed_assets(&policy_id, Some(20))
         .with_search_term("Pirate")
         .with_order_by(OrderBy::PriceAsc);
 
@@ -61,7 +61,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
 
     // Test 2: Combine free text "Pirate" + specific rank filter
     println!("\n📋 Test 2: 'Pirate' + Rank='Captain' (combined search)");
-    let combined_request = CollectionAssetsRequest::for_listed_assets(policy_id, Some(10))
+    let combined_request = CollectionAssetsRequest::for_listed_assets(&policy_id, Some(10))
         .with_search_term("Pirate")
         .with_trait("Rank", "Captain")
         .with_order_by(OrderBy::PriceAsc);
@@ -96,7 +96,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
 
     // Test 3: Try a different rank that might be more common
     println!("\n📋 Test 3: 'Pirate' + Rank='Quartermaster' (different rank)");
-    let combined_request2 = CollectionAssetsRequest::for_listed_assets(policy_id, Some(10))
+    let combined_request2 = CollectionAssetsRequest::for_listed_assets(&policy_id, Some(10))
         .with_search_term("Pirate")
         .with_trait("Rank", "Quartermaster")
         .with_order_by(OrderBy::PriceAsc);
@@ -127,7 +127,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
 
     // Test 4: Multiple trait filters + free text
     println!("\n📋 Test 4: '86' + Multiple traits (Rank + Background)");
-    let multi_trait_request = CollectionAssetsRequest::new(policy_id)
+    let multi_trait_request = CollectionAssetsRequest::new(&policy_id)
         .with_search_term("86")
         .with_trait("Rank", "Swab")
         .with_trait("Background", "Rosy Tide")
@@ -166,7 +166,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
 
     // Test 5: Just trait filter without free text (for comparison)
     println!("\n📋 Test 5: Just Rank='Quartermaster' (no free text for comparison)");
-    let trait_only_request = CollectionAssetsRequest::for_listed_assets(policy_id, Some(10))
+    let trait_only_request = CollectionAssetsRequest::for_listed_assets(&policy_id, Some(10))
         .with_trait("Rank", "Quartermaster")
         .with_order_by(OrderBy::PriceAsc);
 
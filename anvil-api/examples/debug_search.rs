@@ -1,4 +1,4 @@
-use anvil_api::{AnvilClient, CollectionAssetsRequest, OrderBy, SaleType};
+use anvil_api::{AnvilClient, CollectionAssetsRequest, OrderBy, PolicyId, SaleType};
 use dotenv::dotenv;
 use std::env;
 
@@ -20,14 +20,14 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     let client = AnvilClient::new().with_api_key(&api_key);
 
     // Use Blackflag policy ID
-    let policy_id = "b3dab69f7e6100849434fb1781e34bd12a916557f6231b8d2629b6f6";
+    let policy_id: PolicyId = "b3dab69f7e6100849434fb1781e34bd12a916557f6231b8d2629b6f6".parse()?;
 
     println!("🔍 Debug Full Text Search");
     println!("========================");
 
     // Test 1: Basic search without term (baseline)
     println!("\n📋 Test 1: No search term (get any 3 listed assets)");
-    let request = CollectionAssetsRequest::for_listed_assets(policy_id, Some(3))
+    let request = CollectionAssetsRequest::for_listed_assets(&policy_id, Some(3))
         .with_order_by(OrderBy::PriceAsc);
 
     match client.get_collection_assets(&request).await {
@@ -45,7 +45,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
 
     // Test 2: Search with a very generic term
     println!("\n📋 Test 2: Search for 'Pirate' (should match many)");
-    let request = CollectionAssetsRequest::for_listed_assets(policy_id, Some(10))
+    let request = CollectionAssetsRequest::for_listed_assets(&policy_id, Some(10))
         .with_search_term("Pirate")
         .with_order_by(OrderBy::PriceAsc);
 
@@ -72,7 +72,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
 
     // Test 3: Search for a number from asset names
     println!("\n📋 Test 3: Search for '1000' (asset numbers)");
-    let request = CollectionAssetsRequest::for_listed_assets(policy_id, Some(5))
+    let request = CollectionAssetsRequest::for_listed_assets(&policy_id, Some(5))
         .with_search_term("1000")
         .with_order_by(OrderBy::PriceAsc);
 
@@ -91,7 +91,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
 
     // Test 4: Search for 'Luffy' in ALL assets (not just listed)
     println!("\n📋 Test 4: Search for 'Luffy' in ALL assets (including unlisted)");
-    let request = CollectionAssetsRequest::new(policy_id)
+    let request = CollectionAssetsRequest::new(&policy_id)
         .with_limit(10)
         .with_search_term("Luffy")
         .with_sale_type(SaleType::All); // Search all assets, not just listed
@@ -123,7 +123,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
 
     // Test 5: Try searching for a rank value (this should return 0 as search is name-only)
     println!("\n📋 Test 5: Search for 'Swab' (rank value - should return 0)");
-    let request = CollectionAssetsRequest::for_listed_assets(policy_id, Some(5))
+    let request = CollectionAssetsRequest::for_listed_assets(&policy_id, Some(5))
         .with_search_term("Swab")
         .with_order_by(OrderBy::PriceAsc);
 
@@ -148,7 +148,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
 
     // Test 6: Try a search term that should definitely not exist
     println!("\n📋 Test 6: Search for 'Nonexistent' (should return 0)");
-    let request = CollectionAssetsRequest::for_listed_assets(policy_id, Some(5))
+    let request = CollectionAssetsRequest::for_listed_assets(&policy_id, Some(5))
         .with_search_term("Nonexistent12345")
         .with_order_by(OrderBy::PriceAsc);
 
@@ -1,4 +1,4 @@
-use anvil_api::AnvilClient;
+use anvil_api::{AnvilClient, PolicyId};
 use dotenv::dotenv;
 use std::env;
 
@@ -20,7 +20,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     let client = AnvilClient::new().with_api_key(&api_key);
 
     // Use Blackflag policy ID
-    let policy_id = "b3dab69f7e6100849434fb1781e34bd12a916557f6231b8d2629b6f6";
+    let policy_id: PolicyId = "b3dab69f7e6100849434fb1781e34bd12a916557f6231b8d2629b6f6".parse()?;
 
     println!("💰 Floor Assets Utility Example");
     println!("===============================");
@@ -28,7 +28,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     println!();
 
     // Test getting top 10 floor assets
-    match client.get_floor(policy_id, 10).await {
+    match client.get_floor(&policy_id, 10).await {
         Ok(floor_assets) => {
             println!("✅ Found {} floor assets:", floor_assets.len());
             println!();
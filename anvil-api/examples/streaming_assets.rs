@@ -1,4 +1,4 @@
-use anvil_api::{AnvilClient, CollectionAssetsRequest, OrderBy, SaleType};
+use anvil_api::{AnvilClient, CollectionAssetsRequest, OrderBy, PolicyId, SaleType};
 use dotenv::dotenv;
 use futures::StreamExt; // for .next(), .take(), etc.
 use std::env;
@@ -21,7 +21,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     let client = AnvilClient::new().with_api_key(&api_key);
 
     // Use Blackflag policy ID to test pagination with many assets
-    let policy_id = "812197d5f4cdd9ebb05d40e259c181982d4b3d8c2505b1a7ad800bdc";
+    let policy_id: PolicyId = "812197d5f4cdd9ebb05d40e259c181982d4b3d8c2505b1a7ad800bdc".parse()?;
 
     println!("🌊 Asset Streaming Examples");
     println!("===========================");
@@ -32,7 +32,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     println!("📋 Example 1: Stream floor assets (listed only)");
     println!("-----------------------------------------------");
 
-    let floor_request = CollectionAssetsRequest::for_listed_assets(policy_id, Some(5))
+    let floor_request = CollectionAssetsRequest::for_listed_assets(&policy_id, Some(5))
         .with_order_by(OrderBy::PriceAsc);
 
     let floor_stream = client.stream_assets(floor_request);
@@ -83,7 +83,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     println!("📋 Example 2: Stream ALL assets (both listed and unlisted)");
     println!("---------------------------------------------------------");
 
-    let all_request = CollectionAssetsRequest::new(policy_id)
+    let all_request = CollectionAssetsRequest::new(&policy_id)
         .with_sale_type(SaleType::All) // Include both listed and unlisted
         .with_limit(10); // Small page size to demonstrate pagination
 
@@ -1,4 +1,4 @@
-use anvil_api::{AnvilClient, CollectionAssetsRequest, OrderBy, SaleType};
+use anvil_api::{AnvilClient, CollectionAssetsRequest, OrderBy, PolicyId, SaleType};
 use dotenv::dotenv;
 use std::env;
 
@@ -20,7 +20,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     let client = AnvilClient::new().with_api_key(&api_key);
 
     // Use Blackflag policy ID for the example
-    let policy_id = "b3dab69f7e6100849434fb1781e34bd12a916557f6231b8d2629b6f6";
+    let policy_id: PolicyId = "b3dab69f7e6100849434fb1781e34bd12a916557f6231b8d2629b6f6".parse()?;
 
     println!("🔍 Free Text Search Example with Anvil API");
     println!("==========================================");
@@ -32,7 +32,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     println!("--------------------------------------------------------");
 
     // First try searching only listed assets
-    let request = CollectionAssetsRequest::for_listed_assets(policy_id, Some(10))
+    let request = CollectionAssetsRequest::for_listed_assets(&policy_id, Some(10))
         .with_search_term("Luffy")
         .with_order_by(OrderBy::PriceAsc);
 
@@ -62,7 +62,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                 println!();
 
                 // Search all assets if no listed ones found
-                let all_request = CollectionAssetsRequest::new(policy_id)
+                let all_request = CollectionAssetsRequest::new(&policy_id)
                     .with_limit(10)
                     .with_search_term("Luffy")
                     .with_sale_type(SaleType::All);
@@ -104,7 +104,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     println!("📋 Example 2: Search for 'Captain' (pirate theme)");
     println!("------------------------------------------------");
 
-    let request = CollectionAssetsRequest::for_listed_assets(policy_id, Some(8))
+    let request = CollectionAssetsRequest::for_listed_assets(&policy_id, Some(8))
         .with_search_term("Captain")
         .with_order_by(OrderBy::PriceAsc);
 
@@ -145,7 +145,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     println!("📋 Example 3: Search 'Navigator' + under 5 ADA");
     println!("----------------------------------------------");
 
-    let request = CollectionAssetsRequest::for_listed_assets(policy_id, Some(5))
+    let request = CollectionAssetsRequest::for_listed_assets(&policy_id, Some(5))
         .with_search_term("Navigator")
         .with_price_range(None, Some(5_000_000)) // Max 5 ADA
         .with_order_by(OrderBy::PriceAsc);
@@ -186,7 +186,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     println!("📋 Example 4: Search 'Reef' + specific rank");
     println!("-------------------------------------------");
 
-    let request = CollectionAssetsRequest::for_listed_assets(policy_id, Some(5))
+    let request = CollectionAssetsRequest::for_listed_assets(&policy_id, Some(5))
         .with_search_term("Reef") // Should match "Lost Reef" background
         .with_trait("Rank", "Quartermaster")
         .with_order_by(OrderBy::PriceAsc);
@@ -1,4 +1,4 @@
-use anvil_api::{AnvilClient, CollectionAssetsRequest, OrderBy};
+use anvil_api::{AnvilClient, CollectionAssetsRequest, OrderBy, PolicyId};
 use dotenv::dotenv;
 use std::env;
 
@@ -20,7 +20,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     let client = AnvilClient::new().with_api_key(&api_key);
 
     // Use Blackflag policy ID for the example
-    let policy_id = "b3dab69f7e6100849434fb1781e34bd12a916557f6231b8d2629b6f6";
+    let policy_id: PolicyId = "b3dab69f7e6100849434fb1781e34bd12a916557f6231b8d2629b6f6".parse()?;
 
     println!("🔍 Trait Filtering Example with Anvil API");
     println!("=========================================");
@@ -31,7 +31,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     println!("📋 Example 1: Single trait filter (Rank = Swab)");
     println!("-----------------------------------------------");
 
-    let request = CollectionAssetsRequest::for_listed_assets(policy_id, Some(5))
+    let request = CollectionAssetsRequest::for_listed_assets(&policy_id, Some(5))
         .with_trait("Rank", "Swab")
         .with_order_by(OrderBy::PriceAsc);
 
@@ -70,7 +70,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
 
     let traits = vec![("Rank", "Swab"), ("Background", "Golden Mirage")];
 
-    let request = CollectionAssetsRequest::for_listed_assets(policy_id, Some(3))
+    let request = CollectionAssetsRequest::for_listed_assets(&policy_id, Some(3))
         .with_traits(traits)
         .with_order_by(OrderBy::PriceAsc);
 
@@ -109,7 +109,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     println!("📋 Example 3: Price range + trait filtering (Rank = Swab, under 10 ADA)");
     println!("-----------------------------------------------------------------------");
 
-    let request = CollectionAssetsRequest::for_listed_assets(policy_id, Some(5))
+    let request = CollectionAssetsRequest::for_listed_assets(&policy_id, Some(5))
         .with_trait("Rank", "Swab")
         .with_price_range(None, Some(10_000_000)) // Max 10 ADA (10M lovelace)
         .with_order_by(OrderBy::PriceAsc);
@@ -0,0 +1,241 @@
+//! HMAC request signing
+//!
+//! Signed requests carry the key id in `X-Api-Key` plus three headers:
+//!
+//! - `X-Anvil-Timestamp` — unix seconds when the request was signed
+//! - `X-Anvil-Content-Sha256` — hex SHA-256 of the request body (empty for GET)
+//! - `X-Anvil-Signature` — hex HMAC-SHA256 over the canonical string
+//!
+//! The canonical string is `{timestamp}\n{METHOD}\n{path_and_query}\n{content_sha256}`.
+//! Anvil rejects timestamps outside its skew window, so the signer tracks an
+//! offset against the server clock, learned from the `Date` header of a
+//! rejected request.
+
+use hmac::{Hmac, Mac};
+use sha2::{Digest, Sha256};
+use std::fmt;
+use std::sync::atomic::{AtomicI64, Ordering};
+use std::sync::Arc;
+
+pub const API_KEY_HEADER: &str = "X-Api-Key";
+pub const TIMESTAMP_HEADER: &str = "X-Anvil-Timestamp";
+pub const CONTENT_SHA256_HEADER: &str = "X-Anvil-Content-Sha256";
+pub const SIGNATURE_HEADER: &str = "X-Anvil-Signature";
+
+/// Skew Anvil tolerates between the signing timestamp and its own clock
+pub const DEFAULT_MAX_SKEW_SECS: u64 = 300;
+
+/// Signs requests with a shared secret
+///
+/// Clones share the learned clock offset.
+#[derive(Clone)]
+pub struct HmacSigner {
+    key_id: String,
+    secret: Vec<u8>,
+    clock_offset_secs: Arc<AtomicI64>,
+}
+
+impl fmt::Debug for HmacSigner {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("HmacSigner")
+            .field("key_id", &self.key_id)
+            .field("secret", &"<redacted>")
+            .field("clock_offset_secs", &self.clock_offset())
+            .finish()
+    }
+}
+
+impl HmacSigner {
+    pub fn new(key_id: &str, secret: &str) -> Self {
+        Self {
+            key_id: key_id.to_string(),
+            secret: secret.as_bytes().to_vec(),
+            clock_offset_secs: Arc::new(AtomicI64::new(0)),
+        }
+    }
+
+    /// Seconds added to the local clock when signing
+    pub fn clock_offset(&self) -> i64 {
+        self.clock_offset_secs.load(Ordering::Relaxed)
+    }
+
+    /// Align signing timestamps with the server clock
+    ///
+    /// Returns `true` if the offset changed by more than a second, i.e. a
+    /// retry with a fresh signature is worthwhile.
+    pub fn sync_clock(&self, server_unix_secs: i64) -> bool {
+        let offset = server_unix_secs - now_unix_secs();
+        let previous = self.clock_offset_secs.swap(offset, Ordering::Relaxed);
+        (offset - previous).abs() > 1
+    }
+
+    /// Sign a request at the current (offset-adjusted) time
+    pub fn sign(
+        &self,
+        method: &str,
+        path_and_query: &str,
+        body: &[u8],
+    ) -> Vec<(&'static str, String)> {
+        self.sign_at(
+            now_unix_secs() + self.clock_offset(),
+            method,
+            path_and_query,
+            body,
+        )
+    }
+
+    /// Sign a request with an explicit timestamp
+    pub fn sign_at(
+        &self,
+        timestamp: i64,
+        method: &str,
+        path_and_query: &str,
+        body: &[u8],
+    ) -> Vec<(&'static str, String)> {
+        let content_sha256 = hex::encode(Sha256::digest(body));
+        let signature = signature(
+            &self.secret,
+            timestamp,
+            method,
+            path_and_query,
+            &content_sha256,
+        );
+
+        vec![
+            (API_KEY_HEADER, self.key_id.clone()),
+            (TIMESTAMP_HEADER, timestamp.to_string()),
+            (CONTENT_SHA256_HEADER, content_sha256),
+            (SIGNATURE_HEADER, signature),
+        ]
+    }
+}
+
+fn signature(
+    secret: &[u8],
+    timestamp: i64,
+    method: &str,
+    path_and_query: &str,
+    content_sha256: &str,
+) -> String {
+    let mut mac = Hmac::<Sha256>::new_from_slice(secret).expect("HMAC accepts any key length");
+    mac.update(
+        format!(
+            "{timestamp}\n{}\n{path_and_query}\n{content_sha256}",
+            method.to_ascii_uppercase()
+        )
+        .as_bytes(),
+    );
+    hex::encode(mac.finalize().into_bytes())
+}
+
+/// Why a signature failed verification
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SignatureError {
+    /// Timestamp is further than the allowed skew from `now`
+    Expired { skew_secs: u64 },
+    /// Body doesn't match the signed digest
+    DigestMismatch,
+    /// Signature doesn't match the canonical string
+    BadSignature,
+}
+
+impl fmt::Display for SignatureError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SignatureError::Expired { skew_secs } => {
+                write!(f, "Signature timestamp is {skew_secs}s from server time")
+            }
+            SignatureError::DigestMismatch => write!(f, "Body does not match content digest"),
+            SignatureError::BadSignature => write!(f, "Signature does not match"),
+        }
+    }
+}
+
+impl std::error::Error for SignatureError {}
+
+/// Verify a signed request the way Anvil does (useful for tests and mocks)
+#[allow(clippy::too_many_arguments)]
+pub fn verify_signature(
+    secret: &str,
+    method: &str,
+    path_and_query: &str,
+    body: &[u8],
+    timestamp: i64,
+    content_sha256: &str,
+    signature_hex: &str,
+    now_unix_secs: i64,
+) -> Result<(), SignatureError> {
+    let skew_secs = now_unix_secs.abs_diff(timestamp);
+    if skew_secs > DEFAULT_MAX_SKEW_SECS {
+        return Err(SignatureError::Expired { skew_secs });
+    }
+
+    if hex::encode(Sha256::digest(body)) != content_sha256.to_ascii_lowercase() {
+        return Err(SignatureError::DigestMismatch);
+    }
+
+    let expected = signature(
+        secret.as_bytes(),
+        timestamp,
+        method,
+        path_and_query,
+        content_sha256,
+    );
+    let matches = expected.len() == signature_hex.len()
+        && expected
+            .bytes()
+            .zip(signature_hex.to_ascii_lowercase().bytes())
+            .fold(0u8, |acc, (a, b)| acc | (a ^ b))
+            == 0;
+    if matches {
+        Ok(())
+    } else {
+        Err(SignatureError::BadSignature)
+    }
+}
+
+/// Parse an IMF-fixdate `Date` header (`Sun, 06 Nov 1994 08:49:37 GMT`)
+pub(crate) fn parse_http_date(value: &str) -> Option<i64> {
+    let parts: Vec<&str> = value.split_whitespace().collect();
+    let [_, day, month, year, time, "GMT"] = parts.as_slice() else {
+        return None;
+    };
+
+    let day: i64 = day.parse().ok()?;
+    let month = [
+        "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+    ]
+    .iter()
+    .position(|m| m == month)? as i64
+        + 1;
+    let year: i64 = year.parse().ok()?;
+
+    let mut hms = time.split(':').map(|p| p.parse::<i64>().ok());
+    let (h, m, s) = (hms.next()??, hms.next()??, hms.next()??);
+
+    Some(days_from_civil(year, month, day) * 86_400 + h * 3_600 + m * 60 + s)
+}
+
+/// Days since 1970-01-01 for a proleptic Gregorian date
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let month_index = (month + 9) % 12;
+    let day_of_year = (153 * month_index + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
+
+#[cfg(target_arch = "wasm32")]
+fn now_unix_secs() -> i64 {
+    (js_sys::Date::now() / 1000.0) as i64
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn now_unix_secs() -> i64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs() as i64)
+        .unwrap_or_default()
+}
@@ -1,14 +1,38 @@
-use crate::{error::AnvilError, types::*};
+use crate::{
+    auth::{parse_http_date, HmacSigner},
+    drift::{self, DecodeMode},
+    error::AnvilError,
+    types::*,
+};
 use async_stream::stream;
+use cardano_assets::{CollectionDetails, Network, PolicyId};
 use futures::Stream;
-use http_client::HttpClient;
-use tracing::debug;
+use http_client::{BaseClient, HttpClient, HttpError, Query, ResponseDetails};
+use serde::de::DeserializeOwned;
+use std::collections::HashSet;
+use tracing::{debug, warn};
 
 const BASE_URL: &str = "https://prod.api.ada-anvil.app";
+const BASE_URL_PREPROD: &str = "https://preprod.api.ada-anvil.app";
+const BASE_URL_PREVIEW: &str = "https://preview.api.ada-anvil.app";
+
+/// Default number of collection requests in flight for
+/// [`AnvilClient::get_collections_bulk`]
+pub const BULK_CONCURRENCY: usize = 8;
+
+/// Anvil API base URL for a network
+pub fn base_url_for(network: Network) -> &'static str {
+    match network {
+        Network::Mainnet => BASE_URL,
+        Network::Preprod => BASE_URL_PREPROD,
+        Network::Preview => BASE_URL_PREVIEW,
+    }
+}
 
 pub struct AnvilClient {
-    http_client: HttpClient,
-    base_url: String,
+    api: BaseClient,
+    signer: Option<HmacSigner>,
+    decode_mode: DecodeMode,
 }
 
 impl Default for AnvilClient {
@@ -20,57 +44,197 @@ impl Default for AnvilClient {
 impl AnvilClient {
     pub fn new() -> Self {
         Self {
-            http_client: HttpClient::new().with_user_agent("anvil-api-client/0.1.0"),
-            base_url: BASE_URL.to_string(),
+            api: BaseClient::with_client(
+                BASE_URL,
+                HttpClient::new().with_user_agent("anvil-api-client/0.1.0"),
+            ),
+            signer: None,
+            decode_mode: DecodeMode::default(),
         }
     }
 
+    /// Create a client pointed at the given network's Anvil deployment
+    pub fn for_network(network: Network) -> Self {
+        Self::new().with_base_url(base_url_for(network))
+    }
+
     pub fn with_base_url(mut self, base_url: &str) -> Self {
-        self.base_url = base_url.to_string();
+        self.api = self.api.with_base_url(base_url);
+        self
+    }
+
+    /// Use a preconfigured [`HttpClient`] (e.g. one replaying test fixtures).
+    ///
+    /// Replaces the default client, so call before [`with_api_key`](Self::with_api_key).
+    pub fn with_http_client(mut self, http_client: HttpClient) -> Self {
+        self.api = self.api.with_http_client(http_client);
         self
     }
 
     pub fn with_api_key(self, api_key: &str) -> Self {
         Self {
-            http_client: self.http_client.with_header("X-Api-Key", api_key),
-            base_url: self.base_url,
+            api: self.api.with_header("X-Api-Key", api_key),
+            ..self
         }
     }
 
+    /// Sign every request with HMAC-SHA256 instead of sending a bare API key
+    ///
+    /// `key_id` is sent as `X-Api-Key`; `secret` never leaves the client. See
+    /// [`auth`](crate::auth) for the signed headers. If Anvil rejects a request
+    /// because the local clock has drifted, the signer aligns with the server's
+    /// `Date` header and the request is retried once.
+    pub fn with_hmac_signing(mut self, key_id: &str, secret: &str) -> Self {
+        self.signer = Some(HmacSigner::new(key_id, secret));
+        self
+    }
+
+    /// How to treat response fields the client doesn't know about
+    ///
+    /// Defaults to [`DecodeMode::Lenient`]; see [`SchemaDrift`](crate::SchemaDrift).
+    pub fn with_decode_mode(mut self, decode_mode: DecodeMode) -> Self {
+        self.decode_mode = decode_mode;
+        self
+    }
+
     /// Get collection details by extracting metadata from a sample asset
     /// This is a convenience method that fetches a single asset to get collection metadata
     pub async fn get_collection_details(
         &self,
-        policy_id: &str,
-    ) -> Result<cardano_assets::CollectionDetails, AnvilError> {
+        policy_id: &PolicyId,
+    ) -> Result<CollectionDetails, AnvilError> {
+        match self.fetch_collection_details(policy_id, None).await? {
+            CollectionFetch::Fresh { details, .. } => Ok(details),
+            CollectionFetch::NotModified => Err(unexpected_not_modified(policy_id)),
+        }
+    }
+
+    /// Collection details for many policies, e.g. a watchlist.
+    ///
+    /// Anvil has no multi-collection endpoint, so this issues one request per
+    /// (deduplicated) policy with at most [`BULK_CONCURRENCY`] in flight.
+    /// Policies in `cache` are requested with `If-None-Match`; a `304` reuses
+    /// the cached details. The cache is updated with every fresh response
+    /// that carries an `ETag`.
+    pub async fn get_collections_bulk(
+        &self,
+        policy_ids: &[PolicyId],
+        cache: &mut CollectionCache,
+    ) -> BulkCollections {
+        self.get_collections_bulk_with_concurrency(policy_ids, cache, BULK_CONCURRENCY)
+            .await
+    }
+
+    /// [`get_collections_bulk`](Self::get_collections_bulk) with an explicit concurrency limit
+    pub async fn get_collections_bulk_with_concurrency(
+        &self,
+        policy_ids: &[PolicyId],
+        cache: &mut CollectionCache,
+        concurrency: usize,
+    ) -> BulkCollections {
+        let mut seen = HashSet::new();
+        let requests: Vec<(&PolicyId, Option<String>)> = policy_ids
+            .iter()
+            .filter(|policy_id| seen.insert(*policy_id))
+            .map(|policy_id| (policy_id, cache.etag(policy_id).map(str::to_string)))
+            .collect();
+
+        let fetches = requests.into_iter().map(|(policy_id, etag)| async move {
+            let result = self
+                .fetch_collection_details(policy_id, etag.as_deref())
+                .await;
+            (policy_id, result)
+        });
+        let results = worker_utils::join_bounded(fetches, concurrency).await;
+
+        let mut output = BulkCollections::default();
+        for (policy_id, result) in results {
+            match result {
+                Ok(CollectionFetch::Fresh { details, etag }) => {
+                    match etag {
+                        Some(etag) => {
+                            cache.entries.insert(
+                                policy_id.clone(),
+                                CachedCollection {
+                                    etag,
+                                    details: details.clone(),
+                                },
+                            );
+                        }
+                        None => {
+                            cache.entries.remove(policy_id);
+                        }
+                    }
+                    output.collections.insert(policy_id.clone(), details);
+                }
+                Ok(CollectionFetch::NotModified) => match cache.entries.get(policy_id) {
+                    Some(entry) => {
+                        output
+                            .collections
+                            .insert(policy_id.clone(), entry.details.clone());
+                        output.not_modified.push(policy_id.clone());
+                    }
+                    None => {
+                        let err = unexpected_not_modified(policy_id);
+                        warn!("Failed to fetch collection {policy_id}: {err}");
+                        output.failed.push((policy_id.clone(), err));
+                    }
+                },
+                Err(err) => {
+                    warn!("Failed to fetch collection {policy_id}: {err}");
+                    output.failed.push((policy_id.clone(), err));
+                }
+            }
+        }
+
+        output
+    }
+
+    /// Collection details from a single-asset page, conditional on `etag`
+    async fn fetch_collection_details(
+        &self,
+        policy_id: &PolicyId,
+        etag: Option<&str>,
+    ) -> Result<CollectionFetch, AnvilError> {
         debug!("Fetching collection details for policy_id: {}", policy_id);
 
         // Get a single asset to extract collection metadata
         let request = CollectionAssetsRequest::new(policy_id).with_limit(1);
-
-        let response = self.get_collection_assets(&request).await?;
-
-        if response.results.is_empty() {
-            return Err(AnvilError::InvalidInput(format!(
-                "No assets found for policy ID: {}",
-                policy_id
-            )));
-        }
+        let headers: Vec<(&str, &str)> = etag.map(|e| ("If-None-Match", e)).into_iter().collect();
+
+        let response = match self
+            .collection_assets_with_headers(&request, &headers)
+            .await
+        {
+            Ok(response) => response,
+            Err(AnvilError::Http(HttpError::Status { code: 304, .. })) if etag.is_some() => {
+                return Ok(CollectionFetch::NotModified)
+            }
+            Err(e) => return Err(e),
+        };
+        let etag = response.get_header("etag").cloned();
 
         // Extract collection details from the first asset
-        let first_asset = &response.results[0];
+        let first_asset = response.data.results.into_iter().next().ok_or_else(|| {
+            AnvilError::NotFound(format!("No assets found for policy ID: {}", policy_id))
+        })?;
 
-        first_asset.collection.clone().ok_or_else(|| {
-            AnvilError::InvalidInput(format!(
+        let details = first_asset.collection.ok_or_else(|| {
+            AnvilError::NotFound(format!(
                 "No collection details found for policy ID: {}",
                 policy_id
             ))
-        })
+        })?;
+        Ok(CollectionFetch::Fresh { details, etag })
     }
 
     /// Get floor assets - returns the cheapest listed assets in price ascending order
     /// This is a convenience method for getting floor price listings
-    pub async fn get_floor(&self, policy_id: &str, count: u32) -> Result<Vec<Asset>, AnvilError> {
+    pub async fn get_floor(
+        &self,
+        policy_id: &PolicyId,
+        count: u32,
+    ) -> Result<Vec<Asset>, AnvilError> {
         debug!(
             "Fetching {} floor assets for policy_id: {}",
             count, policy_id
@@ -162,19 +326,18 @@ impl AnvilClient {
         &self,
         request: &CollectionAssetsRequest,
     ) -> Result<CollectionAssetsResponse, AnvilError> {
-        if request.policy_id.trim().is_empty() {
-            return Err(AnvilError::InvalidInput(
-                "Policy ID cannot be empty".to_string(),
-            ));
-        }
+        Ok(self
+            .collection_assets_with_headers(request, &[])
+            .await?
+            .data)
+    }
 
-        // Collect all string values first to avoid borrow checker issues
-        let limit_str = request.limit.as_ref().map(|l| l.to_string());
-        let min_price_str = request.min_price.as_ref().map(|p| p.to_string());
-        let max_price_str = request.max_price.as_ref().map(|p| p.to_string());
-        let min_rarity_str = request.min_rarity.as_ref().map(|r| r.to_string());
-        let max_rarity_str = request.max_rarity.as_ref().map(|r| r.to_string());
-        let cursor_json = request.cursor.clone();
+    /// `get-collection-assets` with extra request headers, keeping the response headers
+    async fn collection_assets_with_headers(
+        &self,
+        request: &CollectionAssetsRequest,
+        headers: &[(&str, &str)],
+    ) -> Result<ResponseDetails<CollectionAssetsResponse>, AnvilError> {
         let properties_json = request
             .properties
             .as_ref()
@@ -185,98 +348,146 @@ impl AnvilClient {
                 AnvilError::InvalidInput(format!("Failed to serialize properties: {}", e))
             })?;
 
-        // Build query parameters
-        let mut query_params = vec![("policyId", request.policy_id.as_str())];
+        let mut query = Query::new()
+            .push("policyId", &request.policy_id)
+            .push_opt("limit", request.limit.as_ref());
 
-        if let Some(ref limit_str) = limit_str {
-            query_params.push(("limit", limit_str.as_str()));
-        }
-
-        if let Some(ref cursor_json) = cursor_json {
+        if let Some(cursor_json) = &request.cursor {
             // When cursor is present, it contains the query state
             // Only send policyId, limit, and cursor
-            query_params.push(("cursor", cursor_json.as_str()));
+            query = query.push("cursor", cursor_json);
         } else {
             // Only add other parameters when there's no cursor (initial request)
+            let order_by = request.order_by.as_ref().map(|order_by| match order_by {
+                OrderBy::PriceAsc => "priceAsc",
+                OrderBy::PriceDesc => "priceDesc",
+                OrderBy::NameAsc => "nameAsc",
+                OrderBy::IdxAsc => "idxAsc",
+                OrderBy::RecentlyListed => "recentlyListed",
+                OrderBy::RarityAsc => "rarityAsc",
+                OrderBy::RecentlyMinted => "recentlyMinted",
+            });
+            let listing_type =
+                request
+                    .listing_type
+                    .as_ref()
+                    .map(|listing_type| match listing_type {
+                        ListingType::JpgStore => "jpgstore",
+                        ListingType::Wayup => "wayup",
+                        ListingType::SpaceBudz => "spacebudz",
+                    });
+            let sale_type = request.sale_type.as_ref().map(|sale_type| match sale_type {
+                SaleType::All => "all",
+                SaleType::ListedOnly => "listedOnly",
+                SaleType::Bundles => "bundles",
+            });
+
+            query = query
+                .push_opt("minPrice", request.min_price.as_ref())
+                .push_opt("maxPrice", request.max_price.as_ref())
+                .push_opt("minRarity", request.min_rarity.as_ref())
+                .push_opt("maxRarity", request.max_rarity.as_ref())
+                .push_opt("orderBy", order_by)
+                .push_opt("term", request.term.as_ref())
+                .push_opt("listingType", listing_type)
+                .push_opt("saleType", sale_type)
+                .push_opt("properties", properties_json);
+        }
 
-            if let Some(ref min_price_str) = min_price_str {
-                query_params.push(("minPrice", min_price_str.as_str()));
-            }
-
-            if let Some(ref max_price_str) = max_price_str {
-                query_params.push(("maxPrice", max_price_str.as_str()));
-            }
-
-            if let Some(ref min_rarity_str) = min_rarity_str {
-                query_params.push(("minRarity", min_rarity_str.as_str()));
-            }
-
-            if let Some(ref max_rarity_str) = max_rarity_str {
-                query_params.push(("maxRarity", max_rarity_str.as_str()));
-            }
-
-            if let Some(order_by) = &request.order_by {
-                let order_by_str = match order_by {
-                    OrderBy::PriceAsc => "priceAsc",
-                    OrderBy::PriceDesc => "priceDesc",
-                    OrderBy::NameAsc => "nameAsc",
-                    OrderBy::IdxAsc => "idxAsc",
-                    OrderBy::RecentlyListed => "recentlyListed",
-                    OrderBy::RarityAsc => "rarityAsc",
-                    OrderBy::RecentlyMinted => "recentlyMinted",
-                };
-                query_params.push(("orderBy", order_by_str));
-            }
-
-            if let Some(term) = &request.term {
-                query_params.push(("term", term.as_str()));
-            }
+        let response = self
+            .get_with_headers(
+                &format!("/marketplace/api/get-collection-assets?{query}"),
+                headers,
+            )
+            .await?;
 
-            if let Some(listing_type) = &request.listing_type {
-                let listing_type_str = match listing_type {
-                    ListingType::JpgStore => "jpgstore",
-                    ListingType::Wayup => "wayup",
-                    ListingType::SpaceBudz => "spacebudz",
-                };
-                query_params.push(("listingType", listing_type_str));
-            }
+        Ok(ResponseDetails {
+            data: drift::check(self.decode_mode, "get-collection-assets", response.data)?,
+            ..response
+        })
+    }
 
-            if let Some(sale_type) = &request.sale_type {
-                let sale_type_str = match sale_type {
-                    SaleType::All => "all",
-                    SaleType::ListedOnly => "listedOnly",
-                    SaleType::Bundles => "bundles",
-                };
-                query_params.push(("saleType", sale_type_str));
-            }
+    /// GET `path_and_query` relative to the base URL, signing if configured
+    async fn get<R: DeserializeOwned>(&self, path_and_query: &str) -> Result<R, AnvilError> {
+        Ok(self.get_with_headers(path_and_query, &[]).await?.data)
+    }
 
-            if let Some(ref properties_json) = properties_json {
-                query_params.push(("properties", properties_json.as_str()));
+    /// [`get`](Self::get) with extra request headers, keeping the response headers
+    async fn get_with_headers<R: DeserializeOwned>(
+        &self,
+        path_and_query: &str,
+        headers: &[(&str, &str)],
+    ) -> Result<ResponseDetails<R>, AnvilError> {
+        let url = self.api.url(path_and_query);
+        let http_client = headers
+            .iter()
+            .fold(self.api.client().clone(), |client, (name, value)| {
+                client.with_header(name, value)
+            });
+
+        let Some(signer) = &self.signer else {
+            // Fetch with details so error statuses keep their headers (e.g. Retry-After)
+            return Ok(http_client.get_with_details::<R>(&url).await?);
+        };
+
+        let signed_get = || {
+            let client = signer
+                .sign("GET", path_and_query, &[])
+                .into_iter()
+                .fold(http_client.clone(), |client, (name, value)| {
+                    client.with_header(name, &value)
+                });
+            let url = url.clone();
+            async move { client.get_with_details::<R>(&url).await }
+        };
+
+        match signed_get().await {
+            Ok(response) => Ok(response),
+            Err(HttpError::Status {
+                code: 401,
+                headers,
+                body,
+            }) => {
+                let server_time = headers
+                    .iter()
+                    .find(|(k, _)| k.eq_ignore_ascii_case("date"))
+                    .and_then(|(_, v)| parse_http_date(v));
+
+                match server_time {
+                    Some(server_time) if signer.sync_clock(server_time) => {
+                        debug!(
+                            "Signed request rejected, retrying with clock offset {}s",
+                            signer.clock_offset()
+                        );
+                        Ok(signed_get().await?)
+                    }
+                    _ => Err(HttpError::Status {
+                        code: 401,
+                        headers,
+                        body,
+                    }
+                    .into()),
+                }
             }
+            Err(e) => Err(e.into()),
         }
+    }
+}
 
-        let query_string = query_params
-            .iter()
-            .map(|(key, value)| {
-                format!(
-                    "{}={}",
-                    urlencoding::encode(key),
-                    urlencoding::encode(value)
-                )
-            })
-            .collect::<Vec<_>>()
-            .join("&");
-
-        let url = format!(
-            "{}/marketplace/api/get-collection-assets?{}",
-            self.base_url, query_string
-        );
-
-        let response = self
-            .http_client
-            .get::<CollectionAssetsResponse>(&url)
-            .await?;
+/// Outcome of a conditional collection details request
+enum CollectionFetch {
+    Fresh {
+        details: CollectionDetails,
+        etag: Option<String>,
+    },
+    NotModified,
+}
 
-        Ok(response)
-    }
+/// A `304` for a policy with no cached details to reuse
+fn unexpected_not_modified(policy_id: &PolicyId) -> AnvilError {
+    AnvilError::Http(HttpError::Status {
+        code: 304,
+        headers: Default::default(),
+        body: format!("Not Modified for {policy_id} without cached details"),
+    })
 }
@@ -0,0 +1,108 @@
+//! Response schema drift detection
+//!
+//! Anvil changes response shapes without notice. Response types capture
+//! fields they don't know about in `raw_extra` instead of dropping them, and
+//! the client checks every decoded response according to its [`DecodeMode`]:
+//! lenient mode logs the offending keys and carries on, strict mode fails the
+//! request with [`AnvilError::SchemaDrift`].
+//!
+//! Only `get-collection-assets` is checked; it is the one endpoint the client
+//! calls, with [`get_collection_details`](crate::AnvilClient::get_collection_details),
+//! [`get_floor`](crate::AnvilClient::get_floor) and the asset streams built on
+//! it. Nested [`CollectionDetails`](cardano_assets::CollectionDetails) is
+//! shared with other crates and isn't checked.
+
+use serde_json::{Map, Value};
+use tracing::warn;
+
+use crate::{AnvilError, Asset, AssetMedia, CollectionAssetsResponse, Listing};
+
+/// How the client treats response fields it doesn't recognise
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum DecodeMode {
+    /// Fail the request with [`AnvilError::SchemaDrift`] (tests, canaries)
+    Strict,
+    /// Keep unknown fields in `raw_extra` and log a warning
+    #[default]
+    Lenient,
+}
+
+/// A response type that captures unknown fields
+pub trait SchemaDrift {
+    /// Add the path of every unknown field to `out`, each prefixed with `prefix`
+    fn collect_unknown_fields(&self, prefix: &str, out: &mut Vec<String>);
+
+    /// Sorted, de-duplicated paths of unknown fields, e.g. `results[].listing.fees`
+    fn unknown_fields(&self) -> Vec<String> {
+        let mut fields = Vec::new();
+        self.collect_unknown_fields("", &mut fields);
+        fields.sort();
+        fields.dedup();
+        fields
+    }
+}
+
+fn extra_keys(extra: &Map<String, Value>, prefix: &str, out: &mut Vec<String>) {
+    out.extend(extra.keys().map(|key| format!("{prefix}{key}")));
+}
+
+impl SchemaDrift for CollectionAssetsResponse {
+    fn collect_unknown_fields(&self, prefix: &str, out: &mut Vec<String>) {
+        extra_keys(&self.raw_extra, prefix, out);
+        let prefix = format!("{prefix}results[].");
+        for asset in &self.results {
+            asset.collect_unknown_fields(&prefix, out);
+        }
+    }
+}
+
+impl SchemaDrift for Asset {
+    fn collect_unknown_fields(&self, prefix: &str, out: &mut Vec<String>) {
+        extra_keys(&self.raw_extra, prefix, out);
+        if let Some(media) = &self.media {
+            media.collect_unknown_fields(&format!("{prefix}media."), out);
+        }
+        if let Some(listing) = &self.listing {
+            listing.collect_unknown_fields(&format!("{prefix}listing."), out);
+        }
+    }
+}
+
+impl SchemaDrift for AssetMedia {
+    fn collect_unknown_fields(&self, prefix: &str, out: &mut Vec<String>) {
+        extra_keys(&self.raw_extra, prefix, out);
+    }
+}
+
+impl SchemaDrift for Listing {
+    fn collect_unknown_fields(&self, prefix: &str, out: &mut Vec<String>) {
+        extra_keys(&self.raw_extra, prefix, out);
+    }
+}
+
+/// Apply `mode` to a decoded response from `endpoint`
+pub(crate) fn check<R: SchemaDrift>(
+    mode: DecodeMode,
+    endpoint: &str,
+    response: R,
+) -> Result<R, AnvilError> {
+    let fields = response.unknown_fields();
+    if fields.is_empty() {
+        return Ok(response);
+    }
+
+    match mode {
+        DecodeMode::Strict => Err(AnvilError::SchemaDrift {
+            endpoint: endpoint.to_string(),
+            fields,
+        }),
+        DecodeMode::Lenient => {
+            warn!(
+                "Anvil schema drift in {}: unknown fields {}",
+                endpoint,
+                fields.join(", ")
+            );
+            Ok(response)
+        }
+    }
+}
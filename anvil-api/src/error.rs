@@ -1,27 +1,143 @@
+use http_client::HttpError;
+use serde::Deserialize;
+use std::collections::HashMap;
 use std::fmt;
 
+/// Errors returned by [`AnvilClient`](crate::AnvilClient)
+///
+/// HTTP failures are classified by status code so callers can decide whether
+/// to retry, back off, or fall back to another source; see
+/// [`is_retriable`](Self::is_retriable).
 #[derive(Debug)]
 pub enum AnvilError {
-    Http(http_client::HttpError),
+    /// The requested resource does not exist (404)
+    NotFound(String),
+    /// Too many requests (429); `retry_after` is in seconds when the API provides it
+    RateLimited {
+        retry_after: Option<u64>,
+    },
+    /// Missing or rejected API key (401/403)
+    Unauthorized(String),
+    /// The API rejected the request parameters (400/422)
+    Validation {
+        message: String,
+        field_errors: Vec<FieldError>,
+    },
+    /// Network failure, timeout, or 5xx response
+    Transient(String),
+    /// Any other HTTP failure that doesn't fit the categories above
+    Http(HttpError),
     Serialization(serde_json::Error),
+    /// Rejected client-side before any request was sent
     InvalidInput(String),
+    /// The response had fields the client doesn't know, in
+    /// [`DecodeMode::Strict`](crate::DecodeMode::Strict)
+    SchemaDrift {
+        endpoint: String,
+        fields: Vec<String>,
+    },
+}
+
+/// A single parameter the API rejected
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FieldError {
+    pub field: String,
+    pub message: String,
+}
+
+impl AnvilError {
+    /// Whether retrying the same request may succeed
+    ///
+    /// Only rate limits and transient failures are retriable; everything else
+    /// will fail the same way until the request or credentials change.
+    pub fn is_retriable(&self) -> bool {
+        matches!(
+            self,
+            AnvilError::RateLimited { .. } | AnvilError::Transient(_)
+        )
+    }
+
+    /// Seconds to wait before retrying, if the API asked for a delay
+    pub fn retry_after_seconds(&self) -> Option<u64> {
+        match self {
+            AnvilError::RateLimited { retry_after } => *retry_after,
+            _ => None,
+        }
+    }
+
+    /// Classify a non-2xx response from the Anvil API
+    fn from_status(status_code: u16, headers: &HashMap<String, String>, body: String) -> Self {
+        match status_code {
+            404 => AnvilError::NotFound(error_message(&body).unwrap_or(body)),
+            429 => AnvilError::RateLimited {
+                retry_after: headers
+                    .iter()
+                    .find(|(k, _)| k.eq_ignore_ascii_case("retry-after"))
+                    .and_then(|(_, v)| v.trim().parse().ok()),
+            },
+            401 | 403 => AnvilError::Unauthorized(error_message(&body).unwrap_or(body)),
+            400 | 422 => {
+                let field_errors = field_errors(&body);
+                AnvilError::Validation {
+                    message: error_message(&body).unwrap_or(body),
+                    field_errors,
+                }
+            }
+            408 | 500..=599 => AnvilError::Transient(format!("status {status_code}: {body}")),
+            _ => AnvilError::Http(HttpError::Status {
+                code: status_code,
+                headers: headers.clone(),
+                body,
+            }),
+        }
+    }
 }
 
 impl fmt::Display for AnvilError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
+            AnvilError::NotFound(msg) => write!(f, "Not found: {msg}"),
+            AnvilError::RateLimited {
+                retry_after: Some(secs),
+            } => write!(f, "Rate limited, retry after {secs}s"),
+            AnvilError::RateLimited { retry_after: None } => write!(f, "Rate limited"),
+            AnvilError::Unauthorized(msg) => write!(f, "Unauthorized: {msg}"),
+            AnvilError::Validation {
+                message,
+                field_errors,
+            } => {
+                write!(f, "Validation error: {message}")?;
+                for err in field_errors {
+                    write!(f, "; {}: {}", err.field, err.message)?;
+                }
+                Ok(())
+            }
+            AnvilError::Transient(msg) => write!(f, "Transient error: {msg}"),
             AnvilError::Http(err) => write!(f, "HTTP error: {err}"),
             AnvilError::Serialization(err) => write!(f, "Serialization error: {err}"),
             AnvilError::InvalidInput(msg) => write!(f, "Invalid input: {msg}"),
+            AnvilError::SchemaDrift { endpoint, fields } => write!(
+                f,
+                "Schema drift in {endpoint}: unknown fields {}",
+                fields.join(", ")
+            ),
         }
     }
 }
 
 impl std::error::Error for AnvilError {}
 
-impl From<http_client::HttpError> for AnvilError {
-    fn from(err: http_client::HttpError) -> Self {
-        AnvilError::Http(err)
+impl From<HttpError> for AnvilError {
+    fn from(err: HttpError) -> Self {
+        match err {
+            HttpError::Status {
+                code,
+                headers,
+                body,
+            } => AnvilError::from_status(code, &headers, body),
+            HttpError::Timeout(_) | HttpError::Network(_) => AnvilError::Transient(err.to_string()),
+            other => AnvilError::Http(other),
+        }
     }
 }
 
@@ -30,3 +146,56 @@ impl From<serde_json::Error> for AnvilError {
         AnvilError::Serialization(err)
     }
 }
+
+/// Error body shapes seen from the Anvil API and its zod-based validation
+#[derive(Deserialize)]
+struct ErrorBody {
+    #[serde(default)]
+    message: Option<String>,
+    #[serde(default)]
+    error: Option<String>,
+    #[serde(default, alias = "issues")]
+    errors: Vec<ErrorIssue>,
+}
+
+#[derive(Deserialize)]
+struct ErrorIssue {
+    #[serde(default, alias = "field")]
+    path: Option<serde_json::Value>,
+    #[serde(default)]
+    message: String,
+}
+
+fn error_message(body: &str) -> Option<String> {
+    let parsed = serde_json::from_str::<ErrorBody>(body).ok()?;
+    parsed.message.or(parsed.error)
+}
+
+fn field_errors(body: &str) -> Vec<FieldError> {
+    let Ok(parsed) = serde_json::from_str::<ErrorBody>(body) else {
+        return Vec::new();
+    };
+
+    parsed
+        .errors
+        .into_iter()
+        .map(|issue| {
+            let field = match issue.path {
+                Some(serde_json::Value::String(s)) => s,
+                Some(serde_json::Value::Array(parts)) => parts
+                    .iter()
+                    .map(|p| match p {
+                        serde_json::Value::String(s) => s.clone(),
+                        other => other.to_string(),
+                    })
+                    .collect::<Vec<_>>()
+                    .join("."),
+                _ => String::new(),
+            };
+            FieldError {
+                field,
+                message: issue.message,
+            }
+        })
+        .collect()
+}
@@ -0,0 +1,84 @@
+//! Sale economics: marketplace fee, royalty and seller proceeds
+//!
+//! The marketplace fee comes from [`marketplace_fee`] for the listing's
+//! marketplace, and the royalty from the collection's `royaltyPct`, which
+//! Anvil sends as a fraction (`0.07`).
+
+use cardano_assets::Marketplace;
+use serde::{Deserialize, Serialize};
+
+use crate::Asset;
+
+/// Marketplace fee assumed for marketplaces without a known rate
+pub const DEFAULT_MARKETPLACE_FEE_PCT: f64 = 0.02;
+
+/// Fee a marketplace takes from a sale, as a fraction of the price
+pub fn marketplace_fee(marketplace: &Marketplace) -> f64 {
+    match marketplace {
+        Marketplace::JpgStore => 0.02,
+        Marketplace::Wayup => 0.02,
+        Marketplace::SpaceBudz | Marketplace::Unknown(_) => DEFAULT_MARKETPLACE_FEE_PCT,
+    }
+}
+
+/// Fee rates applied to a sale, as fractions of the price
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct FeeRates {
+    pub marketplace: f64,
+    pub royalty: f64,
+}
+
+/// Where a sale's price goes, in lovelace
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProceedsEstimate {
+    pub price: u64,
+    pub marketplace_fee: u64,
+    pub royalty: u64,
+    /// What the seller receives after fees
+    pub seller: u64,
+}
+
+impl FeeRates {
+    pub fn estimate_proceeds(&self, price: u64) -> ProceedsEstimate {
+        let marketplace_fee = share(price, self.marketplace);
+        let royalty = share(price, self.royalty);
+        ProceedsEstimate {
+            price,
+            marketplace_fee,
+            royalty,
+            seller: price
+                .saturating_sub(marketplace_fee)
+                .saturating_sub(royalty),
+        }
+    }
+}
+
+impl Asset {
+    /// Fee rates for selling this asset on its listed marketplace
+    pub fn fee_rates(&self) -> FeeRates {
+        let marketplace = self
+            .listing
+            .as_ref()
+            .map_or(DEFAULT_MARKETPLACE_FEE_PCT, |l| {
+                marketplace_fee(&l.marketplace)
+            });
+        let royalty = self
+            .collection
+            .as_ref()
+            .map_or(0.0, |c| c.royalty_percentage.clamp(0.0, 1.0));
+        FeeRates {
+            marketplace,
+            royalty,
+        }
+    }
+
+    /// Seller proceeds if this asset sells for `price` lovelace
+    pub fn estimate_proceeds(&self, price: u64) -> ProceedsEstimate {
+        self.fee_rates().estimate_proceeds(price)
+    }
+}
+
+/// Rounded to the lovelace, absorbing Anvil's f32 rates (`0.05999999865889549`)
+fn share(price: u64, rate: f64) -> u64 {
+    (price as f64 * rate).round() as u64
+}
@@ -1,12 +1,19 @@
+pub mod auth;
 mod client;
+mod drift;
 mod error;
+mod fees;
 mod types;
 
 #[cfg(test)]
 mod test;
 
-pub use client::AnvilClient;
-pub use error::AnvilError;
+pub use auth::HmacSigner;
+pub use cardano_assets::{Network, PolicyId};
+pub use client::{base_url_for, AnvilClient, BULK_CONCURRENCY};
+pub use drift::{DecodeMode, SchemaDrift};
+pub use error::{AnvilError, FieldError};
+pub use fees::{marketplace_fee, FeeRates, ProceedsEstimate, DEFAULT_MARKETPLACE_FEE_PCT};
 pub use types::*;
 
 // Re-export Stream trait for convenience
@@ -1,6 +1,7 @@
 #[cfg(test)]
 mod tests {
     use crate::*;
+    use cardano_assets::{Marketplace, PolicyId};
     use dotenv::dotenv;
 
     use std::env;
@@ -34,6 +35,178 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_fixtures_have_no_schema_drift() {
+        // All fixtures are get-collection-assets responses, the only endpoint
+        // the client calls
+        for fixture in [
+            test_case!("response_blackflag.json"),
+            test_case!("response_nikeverse.json"),
+            test_case!("response_toolheads.json"),
+        ] {
+            let response: CollectionAssetsResponse = serde_json::from_str(fixture).unwrap();
+            assert_eq!(response.unknown_fields(), Vec::<String>::new());
+        }
+    }
+
+    #[test]
+    fn test_schema_drift_modes() {
+        let mut json: serde_json::Value =
+            serde_json::from_str(test_case!("response_toolheads.json")).unwrap();
+        json["took"] = 12.into();
+        let results = json["results"].as_array_mut().unwrap();
+        results[0]["badges"] = serde_json::json!(["og"]);
+        let listed = results
+            .iter_mut()
+            .find(|asset| !asset["listing"].is_null())
+            .unwrap();
+        listed["listing"]["fees"] = 1_000_000.into();
+        listed["badges"] = serde_json::json!([]);
+
+        let response: CollectionAssetsResponse = serde_json::from_value(json).unwrap();
+        assert_eq!(response.raw_extra["took"], 12);
+        assert_eq!(
+            response.unknown_fields(),
+            vec!["results[].badges", "results[].listing.fees", "took"]
+        );
+
+        // Unknown fields survive a round trip
+        let reencoded = serde_json::to_value(&response).unwrap();
+        assert_eq!(reencoded["took"], 12);
+
+        let lenient = crate::drift::check(DecodeMode::Lenient, "test", response.clone());
+        assert!(lenient.is_ok());
+
+        match crate::drift::check(DecodeMode::Strict, "test", response) {
+            Err(AnvilError::SchemaDrift { endpoint, fields }) => {
+                assert_eq!(endpoint, "test");
+                assert_eq!(fields.len(), 3);
+            }
+            other => panic!("expected schema drift, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_estimate_proceeds() {
+        let response: CollectionAssetsResponse =
+            serde_json::from_str(test_case!("response_toolheads.json")).unwrap();
+        let mut asset = response
+            .results
+            .into_iter()
+            .find(|asset| asset.listing.is_some())
+            .unwrap();
+
+        // Toolheads royalty is 6%, sent as 0.05999999865889549
+        assert_eq!(
+            asset.estimate_proceeds(40_000_000),
+            ProceedsEstimate {
+                price: 40_000_000,
+                marketplace_fee: 800_000,
+                royalty: 2_400_000,
+                seller: 36_800_000,
+            }
+        );
+
+        // The fee follows the listing's marketplace
+        asset.listing.as_mut().unwrap().marketplace = Marketplace::Wayup;
+        assert_eq!(asset.fee_rates().marketplace, 0.02);
+        asset.listing.as_mut().unwrap().marketplace = Marketplace::Unknown("other".into());
+        assert_eq!(asset.fee_rates().marketplace, DEFAULT_MARKETPLACE_FEE_PCT);
+
+        asset.listing = None;
+        asset.collection = None;
+        assert_eq!(
+            asset.fee_rates(),
+            FeeRates {
+                marketplace: DEFAULT_MARKETPLACE_FEE_PCT,
+                royalty: 0.0,
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn test_get_collection_assets_replay() {
+        // Replays resources/fixtures/anvil; refresh with HTTP_FIXTURES=record
+        let client = AnvilClient::new().with_http_client(test_utils::fixture_client!("anvil"));
+        let toolheads: PolicyId = "285c0b8e91ba323da4ca083c9db837e111dafbf3143ece4d03eba8f4"
+            .parse()
+            .unwrap();
+        let request = CollectionAssetsRequest::new(&toolheads).with_limit(1);
+
+        let response = client.get_collection_assets(&request).await.unwrap();
+        assert_eq!(response.count, 1);
+        assert_eq!(response.results.len(), 1);
+        assert_eq!(response.results[0].name, "Toolhead #1880");
+    }
+
+    #[tokio::test]
+    async fn test_get_collections_bulk_partial_results() {
+        let client = AnvilClient::new().with_http_client(test_utils::fixture_client!("anvil"));
+        let toolheads: PolicyId = "285c0b8e91ba323da4ca083c9db837e111dafbf3143ece4d03eba8f4"
+            .parse()
+            .unwrap();
+        // No fixture recorded for this policy, so its request fails
+        let missing: PolicyId = "b3dab69f7e6100849434fb1781e34bd12a916557f6231b8d2629b6f6"
+            .parse()
+            .unwrap();
+
+        let mut cache = CollectionCache::default();
+        let bulk = client
+            .get_collections_bulk(
+                &[toolheads.clone(), missing.clone(), toolheads.clone()],
+                &mut cache,
+            )
+            .await;
+
+        assert_eq!(bulk.collections.len(), 1);
+        assert_eq!(bulk.collections[&toolheads].name, "Toolheads");
+        assert!(bulk.not_modified.is_empty());
+        assert_eq!(bulk.failed.len(), 1);
+        assert_eq!(bulk.failed[0].0, missing);
+        // The fixture response has no ETag, so nothing is cached
+        assert!(cache.entries.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_not_modified_without_cache_entry_is_an_error() {
+        // Fixture answers 304 Not Modified for this policy
+        let client = AnvilClient::new().with_http_client(test_utils::fixture_client!("anvil"));
+        let nikeverse: PolicyId = "de79250af8caffc7a64645d86939159f665d4107c3f198562007bf32"
+            .parse()
+            .unwrap();
+
+        match client.get_collection_details(&nikeverse).await {
+            Err(AnvilError::Http(http_client::HttpError::Status { code: 304, .. })) => {}
+            other => panic!("expected a 304 error, got {other:?}"),
+        }
+
+        let mut cache = CollectionCache::default();
+        let bulk = client
+            .get_collections_bulk(&[nikeverse.clone()], &mut cache)
+            .await;
+        assert!(bulk.collections.is_empty());
+        assert_eq!(bulk.failed.len(), 1);
+        assert_eq!(bulk.failed[0].0, nikeverse);
+
+        // With a cached entry the 304 reuses it
+        let response: CollectionAssetsResponse =
+            serde_json::from_str(test_case!("response_nikeverse.json")).unwrap();
+        let details = response.results[0].collection.clone().unwrap();
+        cache.entries.insert(
+            nikeverse.clone(),
+            CachedCollection {
+                etag: "W/\"1f4-nikeverse\"".to_string(),
+                details,
+            },
+        );
+        let bulk = client
+            .get_collections_bulk(&[nikeverse.clone()], &mut cache)
+            .await;
+        assert!(bulk.failed.is_empty());
+        assert_eq!(bulk.not_modified, vec![nikeverse.clone()]);
+        assert!(bulk.collections.contains_key(&nikeverse));
+    }
+
     #[ignore]
     #[tokio::test]
     async fn test_get_collection_assets_integration() {
@@ -44,7 +217,7 @@ mod tests {
             "b3dab69f7e6100849434fb1781e34bd12a916557f6231b8d2629b6f6".to_string()
         });
 
-        let request = CollectionAssetsRequest::new(&policy_id)
+        let request = CollectionAssetsRequest::new(&policy_id.parse().unwrap())
             .with_limit(5)
             .with_sale_type(SaleType::All);
 
@@ -86,9 +259,10 @@ mod tests {
         // Example: Filter for assets with Rank = Swab
         let rank_filter = PropertyFilter::new("Rank", "Swab");
 
-        let request = CollectionAssetsRequest::for_listed_assets(&policy_id, Some(10))
-            .with_properties(vec![rank_filter])
-            .with_order_by(OrderBy::PriceAsc);
+        let request =
+            CollectionAssetsRequest::for_listed_assets(&policy_id.parse().unwrap(), Some(10))
+                .with_properties(vec![rank_filter])
+                .with_order_by(OrderBy::PriceAsc);
 
         match client.get_collection_assets(&request).await {
             Ok(response) => {
@@ -141,9 +315,10 @@ mod tests {
         });
 
         // Simpler way to filter by traits using convenience method
-        let request = CollectionAssetsRequest::for_listed_assets(&policy_id, Some(5))
-            .with_trait("Rank", "Swab")
-            .with_order_by(OrderBy::PriceAsc);
+        let request =
+            CollectionAssetsRequest::for_listed_assets(&policy_id.parse().unwrap(), Some(5))
+                .with_trait("Rank", "Swab")
+                .with_order_by(OrderBy::PriceAsc);
 
         match client.get_collection_assets(&request).await {
             Ok(response) => {
@@ -182,9 +357,10 @@ mod tests {
         // Example of filtering by multiple traits at once
         let traits = vec![("Rank", "Swab"), ("Background", "Lost Reef")];
 
-        let request = CollectionAssetsRequest::for_listed_assets(&policy_id, Some(5))
-            .with_traits(traits)
-            .with_order_by(OrderBy::PriceAsc);
+        let request =
+            CollectionAssetsRequest::for_listed_assets(&policy_id.parse().unwrap(), Some(5))
+                .with_traits(traits)
+                .with_order_by(OrderBy::PriceAsc);
 
         match client.get_collection_assets(&request).await {
             Ok(response) => {
@@ -209,9 +385,12 @@ mod tests {
         test_utils::init_test_tracing();
 
         let client = AnvilClient::from_env();
-        let policy_id = env::var("TEST_POLICY_ID").unwrap_or_else(|_| {
-            "b3dab69f7e6100849434fb1781e34bd12a916557f6231b8d2629b6f6".to_string()
-        });
+        let policy_id: PolicyId = env::var("TEST_POLICY_ID")
+            .unwrap_or_else(|_| {
+                "b3dab69f7e6100849434fb1781e34bd12a916557f6231b8d2629b6f6".to_string()
+            })
+            .parse()
+            .expect("TEST_POLICY_ID should be a policy id");
 
         match client.get_collection_details(&policy_id).await {
             Ok(collection) => {
@@ -259,9 +438,12 @@ mod tests {
         test_utils::init_test_tracing();
 
         let client = AnvilClient::from_env();
-        let policy_id = env::var("TEST_POLICY_ID").unwrap_or_else(|_| {
-            "b3dab69f7e6100849434fb1781e34bd12a916557f6231b8d2629b6f6".to_string()
-        });
+        let policy_id: PolicyId = env::var("TEST_POLICY_ID")
+            .unwrap_or_else(|_| {
+                "b3dab69f7e6100849434fb1781e34bd12a916557f6231b8d2629b6f6".to_string()
+            })
+            .parse()
+            .expect("TEST_POLICY_ID should be a policy id");
 
         match client.get_floor(&policy_id, 5).await {
             Ok(floor_assets) => {
@@ -313,8 +495,12 @@ mod tests {
 
     #[test]
     fn test_collection_assets_request_serialization() {
+        let policy_id: PolicyId = "b3dab69f7e6100849434fb1781e34bd12a916557f6231b8d2629b6f6"
+            .parse()
+            .unwrap();
+
         // Test that search term is properly serialized
-        let request = CollectionAssetsRequest::for_listed_assets("test_policy", Some(5))
+        let request = CollectionAssetsRequest::for_listed_assets(&policy_id, Some(5))
             .with_search_term("Luffy");
 
         let serialized = serde_json::to_string(&request).expect("Should serialize");
@@ -324,7 +510,7 @@ mod tests {
         );
 
         // Test without search term
-        let request_no_term = CollectionAssetsRequest::for_listed_assets("test_policy", Some(5));
+        let request_no_term = CollectionAssetsRequest::for_listed_assets(&policy_id, Some(5));
         let serialized_no_term = serde_json::to_string(&request_no_term).expect("Should serialize");
         assert!(
             !serialized_no_term.contains("term"),
@@ -338,7 +524,7 @@ mod tests {
         );
 
         // Test with order_by
-        let request_with_order = CollectionAssetsRequest::for_listed_assets("test_policy", Some(5))
+        let request_with_order = CollectionAssetsRequest::for_listed_assets(&policy_id, Some(5))
             .with_order_by(OrderBy::PriceAsc);
         let serialized_with_order =
             serde_json::to_string(&request_with_order).expect("Should serialize");
@@ -347,4 +533,155 @@ mod tests {
             "Should contain orderBy when set"
         );
     }
+
+    fn status_error(status_code: u16, headers: &[(&str, &str)], body: &str) -> AnvilError {
+        http_client::HttpError::Status {
+            code: status_code,
+            headers: headers
+                .iter()
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect(),
+            body: body.to_string(),
+        }
+        .into()
+    }
+
+    #[test]
+    fn test_error_classification() {
+        let err = status_error(429, &[("retry-after", "30")], "");
+        assert!(matches!(
+            err,
+            AnvilError::RateLimited {
+                retry_after: Some(30)
+            }
+        ));
+        assert!(err.is_retriable());
+        assert_eq!(err.retry_after_seconds(), Some(30));
+
+        let err = status_error(503, &[], "upstream unavailable");
+        assert!(matches!(err, AnvilError::Transient(_)));
+        assert!(err.is_retriable());
+
+        let err = status_error(404, &[], r#"{"message":"Collection not found"}"#);
+        assert!(matches!(&err, AnvilError::NotFound(msg) if msg == "Collection not found"));
+        assert!(!err.is_retriable());
+
+        let err = status_error(401, &[], "");
+        assert!(matches!(err, AnvilError::Unauthorized(_)));
+        assert!(!err.is_retriable());
+
+        let err = status_error(418, &[], "");
+        assert!(matches!(err, AnvilError::Http(_)));
+        assert!(!err.is_retriable());
+    }
+
+    #[test]
+    fn test_validation_field_errors() {
+        let body = r#"{
+            "message": "Invalid query",
+            "issues": [
+                {"path": ["limit"], "message": "Number must be less than or equal to 100"},
+                {"path": ["properties", 0, "key"], "message": "Required"}
+            ]
+        }"#;
+
+        match status_error(400, &[], body) {
+            AnvilError::Validation {
+                message,
+                field_errors,
+            } => {
+                assert_eq!(message, "Invalid query");
+                assert_eq!(
+                    field_errors,
+                    vec![
+                        FieldError {
+                            field: "limit".to_string(),
+                            message: "Number must be less than or equal to 100".to_string(),
+                        },
+                        FieldError {
+                            field: "properties.0.key".to_string(),
+                            message: "Required".to_string(),
+                        },
+                    ]
+                );
+            }
+            other => panic!("expected validation error, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_hmac_signature_roundtrip() {
+        use crate::auth::*;
+
+        let signer = HmacSigner::new("key-1", "s3cret");
+        let path = "/marketplace/api/get-collection-assets?policyId=abc&limit=1";
+        let body = br#"{"policyId":"abc"}"#;
+        let headers = signer.sign_at(1_700_000_000, "post", path, body);
+        let header = |name: &str| {
+            headers
+                .iter()
+                .find(|(k, _)| *k == name)
+                .map(|(_, v)| v.clone())
+                .unwrap()
+        };
+
+        assert_eq!(header(API_KEY_HEADER), "key-1");
+        assert_eq!(header(TIMESTAMP_HEADER), "1700000000");
+        let digest = header(CONTENT_SHA256_HEADER);
+        let signature = header(SIGNATURE_HEADER);
+        assert_eq!(signature.len(), 64);
+        assert!(!format!("{signer:?}").contains("s3cret"));
+
+        let verify = |secret: &str, body: &[u8], now: i64| {
+            verify_signature(
+                secret,
+                "POST",
+                path,
+                body,
+                1_700_000_000,
+                &digest,
+                &signature,
+                now,
+            )
+        };
+        assert_eq!(verify("s3cret", body, 1_700_000_000 + 299), Ok(()));
+        assert_eq!(verify("s3cret", body, 1_700_000_000 - 300), Ok(()));
+        assert_eq!(
+            verify("s3cret", body, 1_700_000_000 + 301),
+            Err(SignatureError::Expired { skew_secs: 301 })
+        );
+        assert_eq!(
+            verify("s3cret", b"{}", 1_700_000_000),
+            Err(SignatureError::DigestMismatch)
+        );
+        assert_eq!(
+            verify("other", body, 1_700_000_000),
+            Err(SignatureError::BadSignature)
+        );
+    }
+
+    #[test]
+    fn test_hmac_clock_sync() {
+        use crate::auth::*;
+
+        // RFC 9110 example date
+        assert_eq!(
+            parse_http_date("Sun, 06 Nov 1994 08:49:37 GMT"),
+            Some(784_111_777)
+        );
+        assert_eq!(parse_http_date("Sunday, 06-Nov-94 08:49:37 GMT"), None);
+
+        let signer = HmacSigner::new("key-1", "s3cret");
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_secs() as i64;
+
+        // Server is ten minutes ahead: worth a retry, and clones share the offset
+        let clone = signer.clone();
+        assert!(signer.sync_clock(now + 600));
+        assert!((clone.clock_offset() - 600).abs() <= 1);
+        // Same offset again: nothing to gain from retrying
+        assert!(!signer.sync_clock(now + 600));
+    }
 }
@@ -1,7 +1,10 @@
 use std::collections::HashMap;
 
-use cardano_assets::{AssetId, CollectionDetails, Marketplace};
+use cardano_assets::{AssetId, CollectionDetails, Marketplace, PolicyId};
 use serde::{Deserialize, Deserializer, Serialize};
+use serde_json::{Map, Value};
+
+use crate::AnvilError;
 
 /// Custom deserializer for attributes that handles both null and missing values
 fn deserialize_attributes<'de, D>(deserializer: D) -> Result<HashMap<String, String>, D::Error>
@@ -14,7 +17,7 @@ where
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CollectionAssetsRequest {
     #[serde(rename = "policyId")]
-    pub policy_id: String,
+    pub policy_id: PolicyId,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub limit: Option<u32>,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -101,9 +104,9 @@ impl From<Marketplace> for ListingType {
 
 impl CollectionAssetsRequest {
     /// Create a basic request with only policy_id
-    pub fn new(policy_id: impl Into<String>) -> Self {
+    pub fn new(policy_id: &PolicyId) -> Self {
         Self {
-            policy_id: policy_id.into(),
+            policy_id: policy_id.clone(),
             limit: None,
             cursor: None,
             min_price: None,
@@ -119,9 +122,9 @@ impl CollectionAssetsRequest {
     }
 
     /// Create request for listed assets only with price ordering
-    pub fn for_listed_assets(policy_id: impl Into<String>, limit: Option<u32>) -> Self {
+    pub fn for_listed_assets(policy_id: &PolicyId, limit: Option<u32>) -> Self {
         Self {
-            policy_id: policy_id.into(),
+            policy_id: policy_id.clone(),
             limit,
             cursor: None,
             min_price: None,
@@ -241,6 +244,9 @@ pub struct CollectionAssetsResponse {
     pub page_state: Option<PageState>,
     pub count: u32,
     pub results: Vec<Asset>,
+    /// Fields Anvil sent that this type doesn't know about yet
+    #[serde(flatten, default, skip_serializing_if = "Map::is_empty")]
+    pub raw_extra: Map<String, Value>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -254,16 +260,18 @@ pub struct PageState {
 #[serde(rename_all = "camelCase")]
 pub struct Asset {
     pub unit: AssetId,
-    pub policy_id: String,
+    pub policy_id: PolicyId,
     pub owner_stake_keyhash: Option<String>,
     #[serde(default)]
     pub is_script: bool,
     pub quantity: u32,
+    pub asset_name: Option<String>,
     pub name: String,
     pub name_idx: Option<u32>,
     pub image: Option<String>,
     pub media: Option<AssetMedia>,
-    // pub label: Option<u32>,
+    /// CIP-67 label, when the asset name carries one
+    pub label: Option<u32>,
     #[serde(default)]
     pub version: AssetVersion,
     pub last_update_tx_hash: String,
@@ -272,6 +280,9 @@ pub struct Asset {
     pub listing: Option<Listing>,
     pub collection: Option<CollectionDetails>,
     pub rarity: Option<u32>,
+    /// Fields Anvil sent that this type doesn't know about yet
+    #[serde(flatten, default, skip_serializing_if = "Map::is_empty")]
+    pub raw_extra: Map<String, Value>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
@@ -286,6 +297,9 @@ pub enum AssetVersion {
 pub struct AssetMedia {
     pub src: String,
     pub blur: String,
+    /// Fields Anvil sent that this type doesn't know about yet
+    #[serde(flatten, default, skip_serializing_if = "Map::is_empty")]
+    pub raw_extra: Map<String, Value>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -293,6 +307,7 @@ pub struct AssetMedia {
 pub struct Listing {
     #[serde(with = "wasm_safe_serde::u64_required")]
     pub price: u64,
+    pub price_currency: Option<String>,
     pub tx_hash_index: String,
     pub script_hash: String,
     pub bundle_size: Option<u32>,
@@ -300,4 +315,41 @@ pub struct Listing {
     #[serde(alias = "type", default)]
     pub marketplace: Marketplace,
     pub version: String,
+    /// Fields Anvil sent that this type doesn't know about yet
+    #[serde(flatten, default, skip_serializing_if = "Map::is_empty")]
+    pub raw_extra: Map<String, Value>,
+}
+
+/// Collection details remembered between [`get_collections_bulk`] runs
+///
+/// Serializable so a worker can keep it in KV between invocations. Entries
+/// are only stored for responses that carried an `ETag`.
+///
+/// [`get_collections_bulk`]: crate::AnvilClient::get_collections_bulk
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CollectionCache {
+    pub entries: HashMap<PolicyId, CachedCollection>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CachedCollection {
+    pub etag: String,
+    pub details: CollectionDetails,
+}
+
+impl CollectionCache {
+    pub fn etag(&self, policy_id: &PolicyId) -> Option<&str> {
+        self.entries.get(policy_id).map(|entry| entry.etag.as_str())
+    }
+}
+
+/// Result of [`get_collections_bulk`](crate::AnvilClient::get_collections_bulk)
+///
+/// One policy failing doesn't fail the batch; its error is kept in `failed`.
+#[derive(Debug, Default)]
+pub struct BulkCollections {
+    pub collections: HashMap<PolicyId, CollectionDetails>,
+    /// Policies served from the cache after a `304 Not Modified`
+    pub not_modified: Vec<PolicyId>,
+    pub failed: Vec<(PolicyId, AnvilError)>,
 }
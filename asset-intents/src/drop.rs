@@ -14,6 +14,8 @@ use utoipa::ToSchema;
 /// - `{"type": "tip", "token": "ADA", "amount": 100.0}`
 /// - `{"type": "wallet_send", "asset_id": {...}, "amount": 1}`
 ///
+/// plus any [`DropSchedule`] fields that are set.
+///
 /// # Example
 ///
 /// ```
@@ -40,6 +42,8 @@ pub enum Drop {
         token: String,
         /// Amount to tip
         amount: f64,
+        #[serde(flatten)]
+        schedule: DropSchedule,
     },
     /// Direct asset transfer via wallet service (e.g., cnft.dev)
     WalletSend {
@@ -47,36 +51,131 @@ pub enum Drop {
         asset_id: AssetId,
         /// Quantity to transfer
         amount: u64,
+        #[serde(flatten)]
+        schedule: DropSchedule,
     },
 }
 
+/// When a [`Drop`] can be delivered or claimed, as unix timestamps in seconds
+///
+/// The default is no window: delivered straight away, never expires.
+///
+/// ```
+/// use asset_intents::{Drop, DropSchedule};
+///
+/// let drop = Drop::tip("ADA", 100.0).with_schedule(
+///     DropSchedule::default()
+///         .deliver_after(1_700_000_000)
+///         .expires_at(1_700_086_400)
+///         .claim_required(true),
+/// );
+/// assert!(!drop.is_claimable(1_699_999_999));
+/// assert!(drop.is_claimable(1_700_000_000));
+/// assert!(!drop.is_claimable(1_700_086_400));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+pub struct DropSchedule {
+    /// Hold the drop until this time
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub deliver_after: Option<u64>,
+    /// The drop lapses if not delivered or claimed before this time
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expires_at: Option<u64>,
+    /// Deliver only once the winner claims it
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub claim_required: bool,
+}
+
+impl DropSchedule {
+    pub fn deliver_after(mut self, timestamp: u64) -> Self {
+        self.deliver_after = Some(timestamp);
+        self
+    }
+
+    pub fn expires_at(mut self, timestamp: u64) -> Self {
+        self.expires_at = Some(timestamp);
+        self
+    }
+
+    pub fn claim_required(mut self, claim_required: bool) -> Self {
+        self.claim_required = claim_required;
+        self
+    }
+
+    /// Whether the window has closed at `now`
+    pub fn is_expired(&self, now: u64) -> bool {
+        self.expires_at.is_some_and(|expires_at| now >= expires_at)
+    }
+
+    /// Whether the window is open at `now`
+    pub fn is_open(&self, now: u64) -> bool {
+        self.deliver_after.is_none_or(|after| now >= after) && !self.is_expired(now)
+    }
+}
+
 impl Drop {
     /// Create a new tip drop
     pub fn tip(token: impl Into<String>, amount: f64) -> Self {
         Drop::Tip {
             token: token.into(),
             amount,
+            schedule: DropSchedule::default(),
         }
     }
 
     /// Create a new wallet send drop
     pub fn wallet_send(asset_id: AssetId, amount: u64) -> Self {
-        Drop::WalletSend { asset_id, amount }
+        Drop::WalletSend {
+            asset_id,
+            amount,
+            schedule: DropSchedule::default(),
+        }
     }
 
     /// Create a wallet send drop for a single NFT
     pub fn wallet_send_single(asset_id: AssetId) -> Self {
-        Drop::WalletSend {
-            asset_id,
-            amount: 1,
+        Self::wallet_send(asset_id, 1)
+    }
+
+    /// Replace the drop's delivery window
+    pub fn with_schedule(mut self, schedule: DropSchedule) -> Self {
+        *self.schedule_mut() = schedule;
+        self
+    }
+
+    pub fn schedule(&self) -> &DropSchedule {
+        match self {
+            Drop::Tip { schedule, .. } | Drop::WalletSend { schedule, .. } => schedule,
         }
     }
 
+    pub fn schedule_mut(&mut self) -> &mut DropSchedule {
+        match self {
+            Drop::Tip { schedule, .. } | Drop::WalletSend { schedule, .. } => schedule,
+        }
+    }
+
+    /// Whether the winner can claim the drop at `now`: it needs a claim and
+    /// its window is open
+    pub fn is_claimable(&self, now: u64) -> bool {
+        let schedule = self.schedule();
+        schedule.claim_required && schedule.is_open(now)
+    }
+
+    /// Whether the drop should be sent unprompted at `now`
+    pub fn is_deliverable(&self, now: u64) -> bool {
+        let schedule = self.schedule();
+        !schedule.claim_required && schedule.is_open(now)
+    }
+
     /// Get a human-readable description of the drop
     pub fn description(&self) -> String {
         match self {
-            Drop::Tip { token, amount } => format!("{} {}", amount, token),
-            Drop::WalletSend { asset_id, amount } => {
+            Drop::Tip { token, amount, .. } => format!("{} {}", amount, token),
+            Drop::WalletSend {
+                asset_id, amount, ..
+            } => {
                 format!("{} x {}", amount, asset_id.delimited(":"))
             }
         }
@@ -133,12 +232,51 @@ mod tests {
         assert!(json.contains("\"amount\":1"));
     }
 
+    #[test]
+    fn test_schedule() {
+        let schedule = DropSchedule::default().deliver_after(100).expires_at(200);
+        let drop = Drop::wallet_send_single(test_asset_id()).with_schedule(schedule.clone());
+        assert!(!drop.is_deliverable(99));
+        assert!(drop.is_deliverable(100));
+        assert!(!drop.is_deliverable(200));
+        assert!(!drop.is_claimable(150));
+
+        let claimed = drop.with_schedule(schedule.claim_required(true));
+        assert!(claimed.is_claimable(150));
+        assert!(!claimed.is_deliverable(150));
+        assert!(claimed.schedule().is_expired(200));
+
+        let unscheduled = Drop::tip("ADA", 1.0);
+        assert!(unscheduled.is_deliverable(0));
+        assert!(!unscheduled.is_claimable(0));
+    }
+
+    #[test]
+    fn test_schedule_serde() {
+        // Stored configs without schedule fields keep round-tripping unchanged
+        let json = r#"{"type":"tip","token":"ADA","amount":100.0}"#;
+        let drop: Drop = serde_json::from_str(json).unwrap();
+        assert_eq!(drop.schedule(), &DropSchedule::default());
+        assert_eq!(serde_json::to_string(&drop).unwrap(), json);
+
+        let drop = Drop::tip("ADA", 100.0).with_schedule(
+            DropSchedule::default()
+                .expires_at(1_700_000_000)
+                .claim_required(true),
+        );
+        let json = serde_json::to_string(&drop).unwrap();
+        assert!(json.contains("\"expires_at\":1700000000"));
+        assert!(json.contains("\"claim_required\":true"));
+        assert!(!json.contains("deliver_after"));
+        assert_eq!(serde_json::from_str::<Drop>(&json).unwrap(), drop);
+    }
+
     #[test]
     fn test_deserialization() {
         let json = r#"{"type":"tip","token":"ADA","amount":100.0}"#;
         let drop: Drop = serde_json::from_str(json).unwrap();
         assert!(drop.is_tip());
-        if let Drop::Tip { token, amount } = drop {
+        if let Drop::Tip { token, amount, .. } = drop {
             assert_eq!(token, "ADA");
             assert_eq!(amount, 100.0);
         }
@@ -0,0 +1,297 @@
+//! Min-UTxO and fee estimation
+//!
+//! Offline estimates for validating transfer intents before they are handed to
+//! a wallet service. The size model mirrors the Babbage/Conway CBOR encoding of
+//! a transaction output, so results track the ledger to within a few bytes;
+//! callers building the actual transaction should still use exact values.
+
+use std::collections::BTreeMap;
+use std::fmt;
+
+use cardano_assets::AssetId;
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "openapi")]
+use utoipa::ToSchema;
+
+/// Fixed per-UTxO overhead added to the serialized output size (Babbage+)
+const UTXO_OVERHEAD: u64 = 160;
+
+/// Typical base address (payment + stake credential): `5839` + 57 bytes
+const ADDRESS_SIZE: u64 = 59;
+
+/// Transaction input: `82 5820 [32 byte hash] [index]`
+const INPUT_SIZE: u64 = 43;
+
+/// One vkey witness: vkey (34) + signature (66) + array tag
+const VKEY_WITNESS_SIZE: u64 = 101;
+
+/// Body map, witness set, validity flag and auxiliary data overhead
+const TX_OVERHEAD: u64 = 20;
+
+/// Protocol parameters needed for min-UTxO and fee estimation
+///
+/// Defaults to current mainnet values.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+pub struct FeeParams {
+    /// `coinsPerUTxOByte`
+    pub coins_per_utxo_byte: u64,
+    /// Linear fee coefficient (`minFeeA`, lovelace per byte)
+    pub min_fee_a: u64,
+    /// Linear fee constant (`minFeeB`, lovelace)
+    pub min_fee_b: u64,
+}
+
+impl Default for FeeParams {
+    fn default() -> Self {
+        Self {
+            coins_per_utxo_byte: 4310,
+            min_fee_a: 44,
+            min_fee_b: 155_381,
+        }
+    }
+}
+
+/// Lovelace needed to submit a transfer
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+pub struct TransferEstimate {
+    /// Min-ADA that must accompany the assets in the recipient output
+    pub min_utxo: u64,
+    /// Estimated network fee
+    pub fee: u64,
+}
+
+impl TransferEstimate {
+    /// Total lovelace the sender must cover
+    #[must_use]
+    pub fn total(&self) -> u64 {
+        self.min_utxo + self.fee
+    }
+}
+
+/// Errors from validating an intent against estimated costs
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FeeError {
+    /// A transfer with no assets and no ADA
+    EmptyTransfer,
+    /// Transfers of zero quantity are rejected by the ledger
+    ZeroAmount(AssetId),
+    /// The same asset is listed more than once in a transfer
+    DuplicateAsset(AssetId),
+    /// The lovelace attached to an output is below its min-UTxO
+    InsufficientLovelace { required: u64, provided: u64 },
+}
+
+impl fmt::Display for FeeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FeeError::EmptyTransfer => write!(f, "Transfer has no assets or ADA"),
+            FeeError::ZeroAmount(asset_id) => {
+                write!(f, "Transfer amount for {} must be non-zero", asset_id)
+            }
+            FeeError::DuplicateAsset(asset_id) => {
+                write!(f, "Transfer lists {} more than once", asset_id)
+            }
+            FeeError::InsufficientLovelace { required, provided } => write!(
+                f,
+                "Output needs at least {} lovelace, got {}",
+                required, provided
+            ),
+        }
+    }
+}
+
+impl std::error::Error for FeeError {}
+
+/// Size of a CBOR unsigned integer (header + payload)
+fn cbor_uint_size(value: u64) -> u64 {
+    match value {
+        0..=23 => 1,
+        24..=0xFF => 2,
+        0x100..=0xFFFF => 3,
+        0x1_0000..=0xFFFF_FFFF => 5,
+        _ => 9,
+    }
+}
+
+/// Size of a CBOR byte string / map header for `len` items
+fn cbor_header_size(len: u64) -> u64 {
+    cbor_uint_size(len)
+}
+
+/// Serialized size of a post-Alonzo output holding `assets` and `lovelace`
+///
+/// Quantities for the same asset are summed, matching how the ledger merges
+/// duplicate entries in a multi-asset value.
+fn output_size(assets: &[(AssetId, u64)], lovelace: u64) -> u64 {
+    let mut policies: BTreeMap<&str, BTreeMap<&str, u64>> = BTreeMap::new();
+    for (asset_id, quantity) in assets {
+        *policies
+            .entry(&asset_id.policy_id)
+            .or_default()
+            .entry(&asset_id.asset_name_hex)
+            .or_default() += quantity;
+    }
+
+    let value_size = if policies.is_empty() {
+        cbor_uint_size(lovelace)
+    } else {
+        // 82 [lovelace, multiasset]
+        let mut size = 1 + cbor_uint_size(lovelace) + cbor_header_size(policies.len() as u64);
+        for names in policies.values() {
+            // 581c + 28 byte policy id
+            size += 30 + cbor_header_size(names.len() as u64);
+            for (name_hex, quantity) in names {
+                let name_len = (name_hex.len() / 2) as u64;
+                size += cbor_header_size(name_len) + name_len + cbor_uint_size(*quantity);
+            }
+        }
+        size
+    };
+
+    // Map header + key 0 + address + key 1 + value
+    1 + 1 + ADDRESS_SIZE + 1 + value_size
+}
+
+/// Estimate the min-ADA required for an output holding `assets`
+///
+/// Uses mainnet [`FeeParams`]. See [`estimate_min_utxo_with`].
+///
+/// # Example
+///
+/// ```
+/// use asset_intents::{estimate_min_utxo, AssetId};
+///
+/// let nft = AssetId::new_unchecked(
+///     "b3dab69f7e6100849434fb1781e34bd12a916557f6231b8d2629b6f6".to_string(),
+///     "50697261746531303836".to_string(),
+/// );
+///
+/// let min_ada = estimate_min_utxo(&[(nft, 1)]);
+/// assert!(min_ada > 1_000_000 && min_ada < 1_500_000);
+/// ```
+#[must_use]
+pub fn estimate_min_utxo(assets: &[(AssetId, u64)]) -> u64 {
+    estimate_min_utxo_with(&FeeParams::default(), assets)
+}
+
+/// Estimate the min-ADA required for an output holding `assets`
+///
+/// Babbage formula: `(160 + |serialized_output|) * coinsPerUTxOByte`. The
+/// lovelace field is itself part of the output, so the size is recomputed
+/// once with the candidate amount in place.
+#[must_use]
+pub fn estimate_min_utxo_with(params: &FeeParams, assets: &[(AssetId, u64)]) -> u64 {
+    let first = (UTXO_OVERHEAD + output_size(assets, 0)) * params.coins_per_utxo_byte;
+    (UTXO_OVERHEAD + output_size(assets, first)) * params.coins_per_utxo_byte
+}
+
+/// Estimate the fee for a simple transfer: `num_inputs` wallet inputs, one
+/// recipient output holding `assets` and one ADA-only change output, signed
+/// by a single key
+///
+/// Fee is `minFeeA * size + minFeeB`. The size is an upper-leaning estimate;
+/// wallet services may come in slightly under it.
+#[must_use]
+pub fn estimate_transfer_fee(
+    params: &FeeParams,
+    assets: &[(AssetId, u64)],
+    num_inputs: u64,
+) -> u64 {
+    let num_inputs = num_inputs.max(1);
+    let min_utxo = estimate_min_utxo_with(params, assets);
+
+    // Fee and change amounts are unknown until balanced; assume 5-byte uints
+    let body = TX_OVERHEAD
+        + num_inputs * INPUT_SIZE
+        + output_size(assets, min_utxo)
+        + output_size(&[], u64::from(u32::MAX))
+        // Key 2 + fee
+        + 1
+        + cbor_uint_size(u64::from(u32::MAX));
+    let witnesses = VKEY_WITNESS_SIZE;
+
+    params.min_fee_a * (body + witnesses) + params.min_fee_b
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const POLICY: &str = "b3dab69f7e6100849434fb1781e34bd12a916557f6231b8d2629b6f6";
+
+    fn asset(name_hex: &str) -> AssetId {
+        AssetId::new_unchecked(POLICY.to_string(), name_hex.to_string())
+    }
+
+    #[test]
+    fn test_cbor_uint_size() {
+        assert_eq!(cbor_uint_size(0), 1);
+        assert_eq!(cbor_uint_size(23), 1);
+        assert_eq!(cbor_uint_size(24), 2);
+        assert_eq!(cbor_uint_size(256), 3);
+        assert_eq!(cbor_uint_size(1_000_000), 5);
+        assert_eq!(cbor_uint_size(u64::MAX), 9);
+    }
+
+    /// An ADA-only output with a base address is 67 bytes: ~0.98 ADA
+    #[test]
+    fn test_ada_only_min_utxo() {
+        assert_eq!(estimate_min_utxo(&[]), (160 + 67) * 4310);
+    }
+
+    /// Single 10-byte-name NFT: output is 1 + 60 + 1 + (1 + 5 + 1 + 30 + 1 + 11 + 1)
+    #[test]
+    fn test_single_nft_min_utxo() {
+        let min = estimate_min_utxo(&[(asset("50697261746531303836"), 1)]);
+        assert_eq!(min, (160 + 112) * 4310);
+    }
+
+    #[test]
+    fn test_min_utxo_grows_with_assets_and_quantity() {
+        let one = estimate_min_utxo(&[(asset("01"), 1)]);
+        let big_qty = estimate_min_utxo(&[(asset("01"), 1_000_000_000)]);
+        let two = estimate_min_utxo(&[(asset("01"), 1), (asset("02"), 1)]);
+        assert!(big_qty > one);
+        assert!(two > one);
+
+        // Duplicate entries merge into one
+        let merged = estimate_min_utxo(&[(asset("01"), 1), (asset("01"), 1)]);
+        assert_eq!(merged, estimate_min_utxo(&[(asset("01"), 2)]));
+    }
+
+    #[test]
+    fn test_min_utxo_respects_params() {
+        let params = FeeParams {
+            coins_per_utxo_byte: 1,
+            ..FeeParams::default()
+        };
+        assert_eq!(estimate_min_utxo_with(&params, &[]), 160 + 64);
+    }
+
+    #[test]
+    fn test_transfer_fee_in_expected_range() {
+        let params = FeeParams::default();
+        let fee = estimate_transfer_fee(&params, &[(asset("50697261746531303836"), 1)], 1);
+        // Simple one-input NFT sends land around 0.17-0.18 ADA
+        assert!((165_000..185_000).contains(&fee), "fee was {fee}");
+
+        let more_inputs = estimate_transfer_fee(&params, &[(asset("01"), 1)], 3);
+        let fewer_inputs = estimate_transfer_fee(&params, &[(asset("01"), 1)], 1);
+        assert_eq!(
+            more_inputs - fewer_inputs,
+            2 * INPUT_SIZE * params.min_fee_a
+        );
+    }
+
+    #[test]
+    fn test_transfer_estimate_total() {
+        let estimate = TransferEstimate {
+            min_utxo: 1_000_000,
+            fee: 170_000,
+        };
+        assert_eq!(estimate.total(), 1_170_000);
+    }
+}
@@ -5,9 +5,14 @@
 //! This crate provides the core types for expressing intentions to move assets:
 //!
 //! - [`TipIntent`] - Fungible token tips via tipping services (e.g., FarmBot ctip)
-//! - [`TransferIntent`] - Direct asset transfers via wallet services (e.g., cnft.dev)
+//! - [`TransferIntent`] - Direct transfers of one or more assets (plus optional ADA)
+//!   via wallet services (e.g., cnft.dev)
 //! - [`Drop`] - A reward/prize that can be either a tip or wallet send
 //!
+//! [`estimate_min_utxo`] and [`estimate_transfer_fee`] estimate the min-ADA and
+//! network fee a transfer needs, so [`TransferIntent::validate`] can catch
+//! underfunded outputs before submission.
+//!
 //! # Example
 //!
 //! ```
@@ -33,14 +38,20 @@
 //! ```
 
 mod drop;
+mod fees;
 mod tip;
 mod token_amount;
 mod transfer;
 
-pub use drop::Drop;
+pub use drop::{Drop, DropSchedule};
+pub use fees::{
+    estimate_min_utxo, estimate_min_utxo_with, estimate_transfer_fee, FeeError, FeeParams,
+    TransferEstimate,
+};
 pub use tip::TipIntent;
 pub use token_amount::{format_number, TokenAmount};
-pub use transfer::TransferIntent;
+pub use transfer::{TransferAsset, TransferIntent};
 
-// Re-export AssetId for convenience
+// Re-export AssetId and Decimal for convenience
 pub use cardano_assets::AssetId;
+pub use wasm_safe_serde::Decimal;
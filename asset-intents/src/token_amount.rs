@@ -3,6 +3,7 @@
 //! Provides human-readable formatting for large token amounts.
 
 use std::fmt;
+use wasm_safe_serde::{Decimal, DecimalError};
 
 /// A token amount with human-readable formatting.
 ///
@@ -33,6 +34,27 @@ impl TokenAmount {
         }
     }
 
+    /// Create a token amount from a lossless [`Decimal`]
+    ///
+    /// The decimal is converted to `f64` for formatting, so this is intended
+    /// for display rather than further arithmetic.
+    pub fn from_decimal(amount: &Decimal, token: impl Into<String>) -> Self {
+        Self::new(amount.to_f64(), token)
+    }
+
+    /// Convert the amount to a [`Decimal`] with `decimals` fractional digits
+    ///
+    /// ```
+    /// use asset_intents::TokenAmount;
+    ///
+    /// let amount = TokenAmount::new(1.1, "USDM").to_decimal(6).unwrap();
+    /// assert_eq!(amount.to_string(), "1.100000");
+    /// assert_eq!(amount.to_base_units(6), Some(1_100_000));
+    /// ```
+    pub fn to_decimal(&self, decimals: u32) -> Result<Decimal, DecimalError> {
+        Decimal::from_f64(self.amount, decimals)
+    }
+
     /// Format the amount with appropriate suffix (K, M, B, T)
     pub fn format_amount(&self) -> String {
         format_number(self.amount)
@@ -153,4 +175,15 @@ mod tests {
             "100M BANK"
         );
     }
+
+    #[test]
+    fn test_token_amount_decimal_conversion() {
+        let decimal = Decimal::from_base_units(2_500_000_000_000, 6);
+        let amount = TokenAmount::from_decimal(&decimal, "SNEK");
+        assert_eq!(amount.to_string(), "2.5M SNEK");
+
+        let back = amount.to_decimal(6).unwrap();
+        assert_eq!(back, decimal);
+        assert_eq!(back.to_base_units(6), Some(2_500_000_000_000));
+    }
 }
@@ -1,14 +1,30 @@
 //! Transfer intent for direct asset transfers
 
+use crate::fees::{
+    estimate_min_utxo_with, estimate_transfer_fee, FeeError, FeeParams, TransferEstimate,
+};
 use cardano_assets::AssetId;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
 
 #[cfg(feature = "openapi")]
 use utoipa::ToSchema;
 
+/// One asset and quantity within a [`TransferIntent`]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+pub struct TransferAsset {
+    /// The asset to transfer
+    pub asset_id: AssetId,
+    /// Quantity to transfer
+    pub amount: u64,
+}
+
 /// Intent to transfer assets directly to a wallet
 ///
-/// Used for NFT transfers, token sends, etc. via services like cnft.dev
+/// Used for NFT transfers, token sends and prize bundles (e.g. an NFT plus
+/// fungible tokens and some ADA) via services like cnft.dev. All assets go to
+/// the recipient in a single output.
 ///
 /// # Example
 ///
@@ -25,21 +41,54 @@ use utoipa::ToSchema;
 /// let transfer = TransferIntent::new(asset_id.clone(), 1);
 ///
 /// // Transfer 100 fungible tokens
-/// let transfer = TransferIntent::new(asset_id, 100);
+/// let transfer = TransferIntent::new(asset_id.clone(), 100);
+///
+/// // Prize bundle: the NFT plus 5 ADA
+/// let bundle = TransferIntent::single(asset_id).with_lovelace(5_000_000);
+/// assert_eq!(bundle.describe(), "1 x Pirate1086 + 5 ADA");
 /// ```
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[cfg_attr(feature = "openapi", derive(ToSchema))]
+#[serde(from = "TransferIntentRepr")]
 pub struct TransferIntent {
-    /// The asset to transfer
-    pub asset_id: AssetId,
-    /// Quantity to transfer
-    pub amount: u64,
+    /// Assets to transfer, in display order
+    pub assets: Vec<TransferAsset>,
+    /// ADA to send alongside the assets, in lovelace
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub lovelace: Option<u64>,
+}
+
+/// Accepts the single-asset shape stored before bundles existed
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum TransferIntentRepr {
+    Bundle {
+        assets: Vec<TransferAsset>,
+        #[serde(default)]
+        lovelace: Option<u64>,
+    },
+    Single {
+        asset_id: AssetId,
+        amount: u64,
+    },
+}
+
+impl From<TransferIntentRepr> for TransferIntent {
+    fn from(repr: TransferIntentRepr) -> Self {
+        match repr {
+            TransferIntentRepr::Bundle { assets, lovelace } => Self { assets, lovelace },
+            TransferIntentRepr::Single { asset_id, amount } => Self::new(asset_id, amount),
+        }
+    }
 }
 
 impl TransferIntent {
     /// Create a new transfer intent
     pub fn new(asset_id: AssetId, amount: u64) -> Self {
-        Self { asset_id, amount }
+        Self {
+            assets: vec![TransferAsset { asset_id, amount }],
+            lovelace: None,
+        }
     }
 
     /// Create a transfer intent for a single NFT
@@ -47,9 +96,132 @@ impl TransferIntent {
         Self::new(asset_id, 1)
     }
 
-    /// Get a human-readable description (e.g., "1 x policy:asset")
+    /// Create a transfer intent for several assets
+    pub fn bundle(assets: impl IntoIterator<Item = (AssetId, u64)>) -> Self {
+        Self {
+            assets: assets
+                .into_iter()
+                .map(|(asset_id, amount)| TransferAsset { asset_id, amount })
+                .collect(),
+            lovelace: None,
+        }
+    }
+
+    /// Add an asset to the transfer
+    #[must_use]
+    pub fn with_asset(mut self, asset_id: AssetId, amount: u64) -> Self {
+        self.assets.push(TransferAsset { asset_id, amount });
+        self
+    }
+
+    /// Send `lovelace` alongside the assets
+    #[must_use]
+    pub fn with_lovelace(mut self, lovelace: u64) -> Self {
+        self.lovelace = Some(lovelace);
+        self
+    }
+
+    /// Assets and quantities, in the shape the fee estimators take
+    pub fn asset_amounts(&self) -> Vec<(AssetId, u64)> {
+        self.assets
+            .iter()
+            .map(|entry| (entry.asset_id.clone(), entry.amount))
+            .collect()
+    }
+
+    /// Get a technical description (e.g., "1 x policy:asset, 5000000 lovelace")
     pub fn description(&self) -> String {
-        format!("{} x {}", self.amount, self.asset_id.delimited(":"))
+        let mut parts: Vec<String> = self
+            .assets
+            .iter()
+            .map(|entry| format!("{} x {}", entry.amount, entry.asset_id.delimited(":")))
+            .collect();
+        if let Some(lovelace) = self.lovelace {
+            parts.push(format!("{lovelace} lovelace"));
+        }
+        parts.join(", ")
+    }
+
+    /// Human-readable summary for confirmation embeds, e.g.
+    /// "1 x Pirate1086, 500 x SNEK + 5 ADA"
+    pub fn describe(&self) -> String {
+        let assets = self
+            .assets
+            .iter()
+            .map(|entry| format!("{} x {}", entry.amount, entry.asset_id.display_name()))
+            .collect::<Vec<_>>()
+            .join(", ");
+        match self.lovelace {
+            Some(lovelace) if assets.is_empty() => format_ada(lovelace),
+            Some(lovelace) => format!("{assets} + {}", format_ada(lovelace)),
+            None => assets,
+        }
+    }
+
+    /// Estimate the min-ADA and fee for sending this transfer from a single
+    /// wallet input
+    pub fn estimate(&self, params: &FeeParams) -> TransferEstimate {
+        let assets = self.asset_amounts();
+        TransferEstimate {
+            min_utxo: estimate_min_utxo_with(params, &assets),
+            fee: estimate_transfer_fee(params, &assets, 1),
+        }
+    }
+
+    /// Lovelace the recipient output must carry: the requested ADA, or the
+    /// min-UTxO if that is higher
+    pub fn output_lovelace(&self, params: &FeeParams) -> u64 {
+        self.estimate(params)
+            .min_utxo
+            .max(self.lovelace.unwrap_or(0))
+    }
+
+    /// Check the transfer can be submitted with `lovelace` attached to the
+    /// recipient output
+    ///
+    /// Rejects empty transfers, zero quantities and assets listed twice.
+    /// Returns the estimate on success so callers can reserve the fee too.
+    pub fn validate(
+        &self,
+        lovelace: u64,
+        params: &FeeParams,
+    ) -> Result<TransferEstimate, FeeError> {
+        if self.assets.is_empty() && self.lovelace.unwrap_or(0) == 0 {
+            return Err(FeeError::EmptyTransfer);
+        }
+
+        let mut seen = BTreeSet::new();
+        for entry in &self.assets {
+            if entry.amount == 0 {
+                return Err(FeeError::ZeroAmount(entry.asset_id.clone()));
+            }
+            if !seen.insert(&entry.asset_id) {
+                return Err(FeeError::DuplicateAsset(entry.asset_id.clone()));
+            }
+        }
+
+        let estimate = self.estimate(params);
+        let required = estimate.min_utxo.max(self.lovelace.unwrap_or(0));
+        if lovelace < required {
+            return Err(FeeError::InsufficientLovelace {
+                required,
+                provided: lovelace,
+            });
+        }
+
+        Ok(estimate)
+    }
+}
+
+/// Format lovelace as ADA, dropping trailing zeros (e.g. "1.5 ADA")
+fn format_ada(lovelace: u64) -> String {
+    let whole = lovelace / 1_000_000;
+    let fraction = lovelace % 1_000_000;
+    if fraction == 0 {
+        format!("{whole} ADA")
+    } else {
+        let fraction = format!("{fraction:06}");
+        format!("{whole}.{} ADA", fraction.trim_end_matches('0'))
     }
 }
 
@@ -67,13 +239,14 @@ mod tests {
     #[test]
     fn test_transfer_intent_creation() {
         let transfer = TransferIntent::new(test_asset_id(), 5);
-        assert_eq!(transfer.amount, 5);
+        assert_eq!(transfer.assets[0].amount, 5);
     }
 
     #[test]
     fn test_single_nft_transfer() {
         let transfer = TransferIntent::single(test_asset_id());
-        assert_eq!(transfer.amount, 1);
+        assert_eq!(transfer.assets.len(), 1);
+        assert_eq!(transfer.assets[0].amount, 1);
     }
 
     #[test]
@@ -81,4 +254,102 @@ mod tests {
         let transfer = TransferIntent::new(test_asset_id(), 3);
         assert!(transfer.description().contains("3 x"));
     }
+
+    #[test]
+    fn test_transfer_validation() {
+        let params = FeeParams::default();
+        let transfer = TransferIntent::single(test_asset_id());
+        let estimate = transfer.estimate(&params);
+
+        assert_eq!(transfer.validate(estimate.min_utxo, &params), Ok(estimate));
+        assert_eq!(
+            transfer.validate(1_000_000, &params),
+            Err(FeeError::InsufficientLovelace {
+                required: estimate.min_utxo,
+                provided: 1_000_000,
+            })
+        );
+        assert_eq!(
+            TransferIntent::new(test_asset_id(), 0).validate(2_000_000, &params),
+            Err(FeeError::ZeroAmount(test_asset_id()))
+        );
+    }
+
+    fn token_id() -> AssetId {
+        AssetId::new_unchecked(
+            "279c909f348e533da5808898f87f9a14bb2c3dfbbacccd631d927a3f".to_string(),
+            "534e454b".to_string(),
+        )
+    }
+
+    #[test]
+    fn test_bundle_describe() {
+        let bundle = TransferIntent::single(test_asset_id())
+            .with_asset(token_id(), 500)
+            .with_lovelace(1_500_000);
+        assert_eq!(bundle.describe(), "1 x Pirate1086, 500 x SNEK + 1.5 ADA");
+        assert!(bundle.description().ends_with(", 1500000 lovelace"));
+
+        let ada_only = TransferIntent::bundle([]).with_lovelace(25_000_000);
+        assert_eq!(ada_only.describe(), "25 ADA");
+    }
+
+    #[test]
+    fn test_bundle_validation() {
+        let params = FeeParams::default();
+        let bundle = TransferIntent::bundle([(test_asset_id(), 1), (token_id(), 500)]);
+        let estimate = bundle.estimate(&params);
+        assert!(
+            estimate.min_utxo
+                > TransferIntent::single(test_asset_id())
+                    .estimate(&params)
+                    .min_utxo
+        );
+        assert_eq!(bundle.validate(estimate.min_utxo, &params), Ok(estimate));
+
+        // Requested ADA above min-UTxO must be attached in full
+        let with_ada = bundle.clone().with_lovelace(10_000_000);
+        assert_eq!(with_ada.output_lovelace(&params), 10_000_000);
+        assert_eq!(
+            with_ada.validate(estimate.min_utxo, &params),
+            Err(FeeError::InsufficientLovelace {
+                required: 10_000_000,
+                provided: estimate.min_utxo,
+            })
+        );
+
+        assert_eq!(
+            bundle
+                .with_asset(token_id(), 1)
+                .validate(5_000_000, &params),
+            Err(FeeError::DuplicateAsset(token_id()))
+        );
+        assert_eq!(
+            TransferIntent::bundle([]).validate(5_000_000, &params),
+            Err(FeeError::EmptyTransfer)
+        );
+    }
+
+    #[test]
+    fn test_serialization_round_trip_and_legacy_shape() {
+        let bundle = TransferIntent::bundle([(test_asset_id(), 1), (token_id(), 500)])
+            .with_lovelace(2_000_000);
+        let json = serde_json::to_string(&bundle).unwrap();
+        assert_eq!(
+            serde_json::from_str::<TransferIntent>(&json).unwrap(),
+            bundle
+        );
+
+        let single = serde_json::to_string(&TransferIntent::single(test_asset_id())).unwrap();
+        assert!(!single.contains("lovelace"));
+
+        let legacy = format!(
+            r#"{{"asset_id":{},"amount":3}}"#,
+            serde_json::to_string(&test_asset_id()).unwrap()
+        );
+        assert_eq!(
+            serde_json::from_str::<TransferIntent>(&legacy).unwrap(),
+            TransferIntent::new(test_asset_id(), 3)
+        );
+    }
 }
@@ -0,0 +1,146 @@
+//! Chunked scoring for spreading large collections across queue messages.
+//!
+//! Workers can't score a 50k-token collection within a single invocation, so
+//! the work is split into three steps:
+//!
+//! 1. Build the [`Collection`] stats once and store them (they serialize).
+//! 2. Fan out one message per [`chunk_ranges`] entry; each calls
+//!    [`score_chunk`] on its slice of tokens and stores the [`ScoreChunk`].
+//! 3. Once every chunk is in, [`rank_chunks`] merges and ranks them.
+//!
+//! ```
+//! use asset_rarity::{build_collection, chunk_ranges, rank_chunks, score_chunk};
+//! use asset_rarity::{Attribute, MagicEdenScorer, Scorer, Token};
+//!
+//! let tokens: Vec<Token> = (0..10)
+//!     .map(|i| Token::new(format!("{i}"), vec![Attribute::new("hat", format!("{}", i % 3))]))
+//!     .collect();
+//!
+//! let collection = build_collection(&tokens);
+//! let chunks: Vec<_> = chunk_ranges(tokens.len(), 4)
+//!     .into_iter()
+//!     .enumerate()
+//!     .map(|(i, range)| score_chunk(&MagicEdenScorer, &collection, i, &tokens[range]))
+//!     .collect();
+//!
+//! let ranked = rank_chunks(chunks, MagicEdenScorer.lower_is_rarer());
+//! assert_eq!(ranked.len(), 10);
+//! ```
+
+use std::collections::BTreeMap;
+use std::ops::Range;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{ranker, Collection, RankedToken, Scorer, Token};
+
+/// Scores for one chunk of a collection, as produced by a single worker.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScoreChunk {
+    /// Index of the chunk within [`chunk_ranges`]
+    pub chunk: usize,
+    /// `(token_id, score)` pairs
+    pub scores: Vec<(String, f64)>,
+}
+
+/// Split `total` tokens into contiguous ranges of at most `chunk_size`.
+///
+/// A `chunk_size` of zero is treated as one.
+pub fn chunk_ranges(total: usize, chunk_size: usize) -> Vec<Range<usize>> {
+    let chunk_size = chunk_size.max(1);
+    (0..total)
+        .step_by(chunk_size)
+        .map(|start| start..(start + chunk_size).min(total))
+        .collect()
+}
+
+/// Score one chunk of tokens against the full collection stats.
+///
+/// `collection` must be built from the *whole* collection, not just `tokens`,
+/// or trait frequencies will be wrong.
+pub fn score_chunk(
+    scorer: &dyn Scorer,
+    collection: &Collection,
+    chunk: usize,
+    tokens: &[Token],
+) -> ScoreChunk {
+    ScoreChunk {
+        chunk,
+        scores: scorer.score(collection, tokens),
+    }
+}
+
+/// Merge scored chunks and rank the whole collection.
+///
+/// Chunks may arrive in any order. A chunk index seen more than once (e.g. a
+/// redelivered queue message) only counts once, with the last copy winning.
+pub fn rank_chunks(
+    chunks: impl IntoIterator<Item = ScoreChunk>,
+    lower_is_rarer: bool,
+) -> Vec<RankedToken> {
+    let by_index: BTreeMap<usize, Vec<(String, f64)>> = chunks
+        .into_iter()
+        .map(|chunk| (chunk.chunk, chunk.scores))
+        .collect();
+
+    ranker::rank(by_index.into_values().flatten().collect(), lower_is_rarer)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{build_collection, score_and_rank, Attribute, ICScorer, MagicEdenScorer};
+
+    fn sample_tokens(n: usize) -> Vec<Token> {
+        (0..n)
+            .map(|i| {
+                Token::new(
+                    format!("{i}"),
+                    vec![
+                        Attribute::new("hat", format!("hat_{}", i % 7)),
+                        Attribute::new("body", format!("body_{}", i % 13)),
+                        Attribute::new("eyes", format!("eyes_{}", (i * 31) % 17)),
+                    ],
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_chunk_ranges() {
+        assert_eq!(chunk_ranges(10, 4), vec![0..4, 4..8, 8..10]);
+        assert_eq!(chunk_ranges(8, 4), vec![0..4, 4..8]);
+        assert_eq!(chunk_ranges(3, 0), vec![0..1, 1..2, 2..3]);
+        assert!(chunk_ranges(0, 4).is_empty());
+    }
+
+    #[test]
+    fn test_chunked_matches_score_and_rank() {
+        let tokens = sample_tokens(250);
+        let collection = build_collection(&tokens);
+
+        for scorer in [&MagicEdenScorer as &dyn Scorer, &ICScorer] {
+            let mut chunks: Vec<ScoreChunk> = chunk_ranges(tokens.len(), 64)
+                .into_iter()
+                .enumerate()
+                .map(|(i, range)| score_chunk(scorer, &collection, i, &tokens[range]))
+                .collect();
+            // Out-of-order arrival and a redelivered chunk
+            chunks.reverse();
+            chunks.push(chunks[1].clone());
+
+            let chunked = rank_chunks(chunks, scorer.lower_is_rarer());
+            let direct = score_and_rank(scorer, &tokens);
+
+            assert_eq!(chunked.len(), direct.len());
+            let ranks: BTreeMap<_, _> = direct.iter().map(|t| (&t.id, t.rank)).collect();
+            for token in &chunked {
+                assert_eq!(
+                    ranks[&token.id], token.rank,
+                    "rank mismatch for {}",
+                    token.id
+                );
+            }
+        }
+    }
+}
@@ -1,9 +1,14 @@
 use std::collections::BTreeMap;
 
-use crate::Token;
+use serde::{Deserialize, Serialize};
+
+use crate::{ScoringConfig, Token};
 
 /// Precomputed collection-level statistics for rarity scoring.
-#[derive(Debug)]
+///
+/// Serializable so the stats can be built once and shared with the queue
+/// messages that score individual chunks (see [`score_chunk`](crate::score_chunk)).
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Collection {
     pub total_supply: usize,
     /// trait_type -> max times it appears on any single token.
@@ -12,7 +17,23 @@ pub struct Collection {
     /// `slot_index` is 0 for single-occurrence traits.
     /// For duplicate trait_types (e.g. multiple "Outfit" entries), each
     /// occurrence gets its own slot index (sorted alphabetically).
+    #[serde(with = "slot_frequencies")]
     pub frequencies: BTreeMap<(String, usize), BTreeMap<String, usize>>,
+    /// Whether missing slots are scored as null markers; see
+    /// [`ScoringConfig::treat_missing_as_null`]
+    #[serde(default = "default_treat_missing_as_null")]
+    pub treat_missing_as_null: bool,
+    /// Whether each token's trait count is scored as the extra
+    /// [`TRAIT_COUNT`] trait; see [`ScoringConfig::include_trait_count`]
+    #[serde(default)]
+    pub include_trait_count: bool,
+}
+
+/// Key of the synthetic trait-count slot in [`Collection::frequencies`]
+pub const TRAIT_COUNT: &str = "__trait_count";
+
+fn default_treat_missing_as_null() -> bool {
+    true
 }
 
 impl Collection {
@@ -35,95 +56,234 @@ impl Collection {
     }
 }
 
+/// JSON object keys must be strings, so the `(trait_type, slot_index)` keyed
+/// map is (de)serialized as a list of entries instead.
+mod slot_frequencies {
+    use std::collections::BTreeMap;
+
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    #[derive(Serialize, Deserialize)]
+    struct SlotEntry {
+        trait_type: String,
+        slot: usize,
+        values: BTreeMap<String, usize>,
+    }
+
+    pub fn serialize<S: Serializer>(
+        frequencies: &BTreeMap<(String, usize), BTreeMap<String, usize>>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(
+            frequencies
+                .iter()
+                .map(|((trait_type, slot), values)| SlotEntry {
+                    trait_type: trait_type.clone(),
+                    slot: *slot,
+                    values: values.clone(),
+                }),
+        )
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<BTreeMap<(String, usize), BTreeMap<String, usize>>, D::Error> {
+        let entries = Vec::<SlotEntry>::deserialize(deserializer)?;
+        Ok(entries
+            .into_iter()
+            .map(|e| ((e.trait_type, e.slot), e.values))
+            .collect())
+    }
+}
+
 /// Build collection statistics from a list of tokens.
 ///
 /// Handles duplicate `trait_type` entries by detecting the collection "shape"
 /// (max occurrences per trait_type) and assigning slot indices. Tokens with
 /// fewer occurrences than the max get null markers for missing slots.
 pub fn build_collection(tokens: &[Token]) -> Collection {
-    let total_supply = tokens.len();
-
-    // Step 1: Determine collection shape — max count per trait_type
-    let mut shape: BTreeMap<String, usize> = BTreeMap::new();
-    for token in tokens {
-        let mut token_trait_counts: BTreeMap<String, usize> = BTreeMap::new();
-        for attr in &token.attributes {
-            *token_trait_counts
-                .entry(attr.trait_type.clone())
-                .or_insert(0) += 1;
-        }
-        for (trait_type, count) in &token_trait_counts {
-            let max = shape.entry(trait_type.clone()).or_insert(0);
-            if *count > *max {
-                *max = *count;
-            }
-        }
-    }
+    build_collection_with(tokens, &ScoringConfig::default())
+}
 
-    // Step 2: Build frequency maps with slot indices.
-    // For each token, sort its values per trait_type alphabetically so that
-    // slot assignment is deterministic. Then pad with null markers.
-    let mut frequencies: BTreeMap<(String, usize), BTreeMap<String, usize>> = BTreeMap::new();
+/// Build collection statistics, scoring only the traits `config` allows.
+///
+/// Unscored traits are left out of the shape entirely, so scorers ignore
+/// them too.
+pub fn build_collection_with(tokens: &[Token], config: &ScoringConfig) -> Collection {
+    let mut builder = CollectionBuilder::new(config.clone());
+    builder.extend(tokens);
+    builder.finish()
+}
+
+/// Builds [`Collection`] stats one token at a time.
+///
+/// Only value counts are kept, never the tokens themselves, so a collection
+/// can be ingested page by page from storage. The builder serializes, so
+/// ingestion can also be spread across invocations. The result is the same
+/// as [`build_collection_with`] over all the tokens.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CollectionBuilder {
+    config: ScoringConfig,
+    total_supply: usize,
+    /// `(trait_type, slot_index)` -> value -> count, for values actually
+    /// present (no null markers yet)
+    #[serde(with = "slot_frequencies")]
+    values: BTreeMap<(String, usize), BTreeMap<String, usize>>,
+    /// trait_type -> occurrences on a token -> tokens with that many
+    occurrences: BTreeMap<String, BTreeMap<usize, usize>>,
+    /// Scored trait count -> tokens with that many
+    trait_counts: BTreeMap<usize, usize>,
+}
 
-    // Initialize all slots
-    for (trait_type, max_count) in &shape {
-        for slot_idx in 0..*max_count {
-            frequencies.insert((trait_type.clone(), slot_idx), BTreeMap::new());
+impl CollectionBuilder {
+    pub fn new(config: ScoringConfig) -> Self {
+        Self {
+            config,
+            total_supply: 0,
+            values: BTreeMap::new(),
+            occurrences: BTreeMap::new(),
+            trait_counts: BTreeMap::new(),
         }
     }
 
-    for token in tokens {
-        // Group this token's values by trait_type, sorted
-        let mut token_values: BTreeMap<String, Vec<String>> = BTreeMap::new();
-        for attr in &token.attributes {
+    /// Tokens ingested so far
+    pub fn total_supply(&self) -> usize {
+        self.total_supply
+    }
+
+    /// Add one token's attributes to the stats
+    pub fn add_token(&mut self, token: &Token) {
+        self.total_supply += 1;
+
+        // Values sorted per trait_type, so slot assignment is deterministic
+        let mut token_values: BTreeMap<&str, Vec<&str>> = BTreeMap::new();
+        for attr in scored_attributes(token, &self.config) {
             token_values
-                .entry(attr.trait_type.clone())
+                .entry(&attr.trait_type)
                 .or_default()
-                .push(attr.value.clone());
+                .push(&attr.value);
         }
 
-        // For each trait_type in the shape, assign values to slots
-        for (trait_type, max_count) in &shape {
-            let values = token_values.get(trait_type);
-            let mut sorted_values: Vec<String> = values
-                .map(|v| {
-                    let mut s = v.clone();
-                    s.sort();
-                    s
-                })
-                .unwrap_or_default();
-
-            // Pad with unique null markers for missing slots
-            let present_count = sorted_values.len();
-            for i in 0..(*max_count - present_count) {
-                // Each missing slot gets a unique null marker so they don't
-                // share probability — per Magic Eden spec
-                sorted_values.push(format!("__null_{i}"));
+        let mut trait_count = 0;
+        for (trait_type, mut values) in token_values {
+            values.sort();
+            trait_count += values.len();
+            *self
+                .occurrences
+                .entry(trait_type.to_string())
+                .or_default()
+                .entry(values.len())
+                .or_insert(0) += 1;
+            for (slot_idx, value) in values.into_iter().enumerate() {
+                *self
+                    .values
+                    .entry((trait_type.to_string(), slot_idx))
+                    .or_default()
+                    .entry(value.to_string())
+                    .or_insert(0) += 1;
             }
+        }
 
-            for (slot_idx, value) in sorted_values.iter().enumerate() {
-                *frequencies
-                    .get_mut(&(trait_type.clone(), slot_idx))
-                    .unwrap()
-                    .entry(value.clone())
-                    .or_insert(0) += 1;
+        if self.config.include_trait_count {
+            *self.trait_counts.entry(trait_count).or_insert(0) += 1;
+        }
+    }
+
+    /// Finalize the stats.
+    ///
+    /// The collection shape is only known once every token has been seen, so
+    /// null markers for missing slots are filled in here.
+    pub fn finish(self) -> Collection {
+        let shape: BTreeMap<String, usize> = self
+            .occurrences
+            .iter()
+            .filter_map(|(trait_type, counts)| {
+                counts
+                    .keys()
+                    .next_back()
+                    .map(|max| (trait_type.clone(), *max))
+            })
+            .collect();
+
+        let mut frequencies = self.values;
+
+        if self.config.treat_missing_as_null {
+            for (trait_type, counts) in &self.occurrences {
+                let max_count = shape[trait_type];
+                let holders: usize = counts.values().sum();
+                let without = self.total_supply - holders;
+
+                // A token with `k` of `max_count` values gets `__null_i`
+                // markers in slots `k..max_count` — per Magic Eden spec
+                let padded = std::iter::once((0, without))
+                    .chain(counts.iter().map(|(k, n)| (*k, *n)))
+                    .filter(|(k, n)| *n > 0 && *k < max_count);
+                for (present, tokens) in padded {
+                    for i in 0..(max_count - present) {
+                        *frequencies
+                            .entry((trait_type.clone(), present + i))
+                            .or_default()
+                            .entry(format!("__null_{i}"))
+                            .or_insert(0) += tokens;
+                    }
+                }
             }
         }
+
+        if self.config.include_trait_count {
+            frequencies.insert(
+                (TRAIT_COUNT.to_string(), 0),
+                self.trait_counts
+                    .into_iter()
+                    .map(|(count, tokens)| (count.to_string(), tokens))
+                    .collect(),
+            );
+        }
+
+        Collection {
+            total_supply: self.total_supply,
+            shape,
+            frequencies,
+            treat_missing_as_null: self.config.treat_missing_as_null,
+            include_trait_count: self.config.include_trait_count,
+        }
     }
+}
 
-    Collection {
-        total_supply,
-        shape,
-        frequencies,
+impl<'a> Extend<&'a Token> for CollectionBuilder {
+    fn extend<I: IntoIterator<Item = &'a Token>>(&mut self, tokens: I) {
+        for token in tokens {
+            self.add_token(token);
+        }
     }
 }
 
+impl Extend<Token> for CollectionBuilder {
+    fn extend<I: IntoIterator<Item = Token>>(&mut self, tokens: I) {
+        for token in tokens {
+            self.add_token(&token);
+        }
+    }
+}
+
+fn scored_attributes<'a>(
+    token: &'a Token,
+    config: &'a ScoringConfig,
+) -> impl Iterator<Item = &'a crate::Attribute> {
+    token
+        .attributes
+        .iter()
+        .filter(|attr| config.is_scored(&attr.trait_type))
+}
+
 /// Get the normalized attribute list for a token against a collection shape.
-/// Returns `(trait_type, slot_index, value)` tuples, padded with null markers.
+/// Returns `(trait_type, slot_index, value)` tuples, padded with null markers
+/// unless the collection was built without them.
 pub fn normalize_token_attributes(
     token: &Token,
-    shape: &BTreeMap<String, usize>,
+    collection: &Collection,
 ) -> Vec<(String, usize, String)> {
+    let shape = &collection.shape;
     let mut token_values: BTreeMap<String, Vec<String>> = BTreeMap::new();
     for attr in &token.attributes {
         token_values
@@ -144,16 +304,30 @@ pub fn normalize_token_attributes(
             })
             .unwrap_or_default();
 
-        let present_count = sorted_values.len();
-        for i in 0..(*max_count - present_count) {
-            sorted_values.push(format!("__null_{i}"));
+        if collection.treat_missing_as_null {
+            let present_count = sorted_values.len();
+            for i in 0..max_count.saturating_sub(present_count) {
+                sorted_values.push(format!("__null_{i}"));
+            }
         }
 
+        // A token from outside the collection may hold more values than the
+        // shape allows; the extras are scored against the last slot
         for (slot_idx, value) in sorted_values.iter().enumerate() {
+            let slot_idx = slot_idx.min(max_count.saturating_sub(1));
             result.push((trait_type.clone(), slot_idx, value.clone()));
         }
     }
 
+    if collection.include_trait_count {
+        let count = token
+            .attributes
+            .iter()
+            .filter(|attr| shape.contains_key(&attr.trait_type))
+            .count();
+        result.push((TRAIT_COUNT.to_string(), 0, count.to_string()));
+    }
+
     result
 }
 
@@ -220,4 +394,26 @@ mod tests {
         assert_eq!(col.count_for_value("special", 0, "__null_0"), 1);
         assert_eq!(col.count_for_value("special", 0, "true"), 1);
     }
+
+    #[test]
+    fn test_collection_serde_roundtrip() {
+        let tokens = vec![
+            Token::new(
+                "1",
+                vec![
+                    Attribute::new("outfit", "jeans"),
+                    Attribute::new("outfit", "tee"),
+                ],
+            ),
+            Token::new("2", vec![Attribute::new("outfit", "shorts")]),
+        ];
+        let col = build_collection(&tokens);
+
+        let json = serde_json::to_string(&col).unwrap();
+        let restored: Collection = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(restored.total_supply, 2);
+        assert_eq!(restored.shape, col.shape);
+        assert_eq!(restored.frequencies, col.frequencies);
+    }
 }
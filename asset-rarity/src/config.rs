@@ -0,0 +1,286 @@
+use std::collections::BTreeSet;
+
+use serde::{Deserialize, Serialize};
+
+/// Which traits count towards rarity, and how missing traits are scored.
+///
+/// Projects often carry meta-traits (e.g. "Kingdom Score", "Votes") that
+/// change over time or aren't part of the art; excluding them keeps them out
+/// of the collection stats entirely, so they can't shift any token's score.
+///
+/// ```
+/// use asset_rarity::{score_and_rank_with, Attribute, MagicEdenScorer, ScoringConfig, Token};
+///
+/// let config = ScoringConfig::default().exclude("Kingdom Score");
+/// let tokens = vec![
+///     Token::new("1", vec![Attribute::new("hat", "red"), Attribute::new("Kingdom Score", "97")]),
+///     Token::new("2", vec![Attribute::new("hat", "red"), Attribute::new("Kingdom Score", "12")]),
+/// ];
+///
+/// let ranked = score_and_rank_with(&MagicEdenScorer, &tokens, &config);
+/// assert_eq!(ranked[0].score, ranked[1].score);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ScoringConfig {
+    /// Trait types ignored for scoring
+    #[serde(default)]
+    pub excluded_traits: BTreeSet<String>,
+    /// If non-empty, only these trait types are scored (before exclusions)
+    #[serde(default)]
+    pub included_traits: BTreeSet<String>,
+    /// Score a token lacking a trait type as holding a "null" value for it
+    /// (Magic Eden / OpenRarity behaviour). When `false`, absent traits
+    /// contribute nothing to the token's score.
+    #[serde(default = "default_treat_missing_as_null")]
+    pub treat_missing_as_null: bool,
+    /// Score each token's number of scored traits as one more trait, as
+    /// Magic Eden does when a collection enables "trait count"
+    #[serde(default)]
+    pub include_trait_count: bool,
+}
+
+fn default_treat_missing_as_null() -> bool {
+    true
+}
+
+impl Default for ScoringConfig {
+    fn default() -> Self {
+        Self {
+            excluded_traits: BTreeSet::new(),
+            included_traits: BTreeSet::new(),
+            treat_missing_as_null: default_treat_missing_as_null(),
+            include_trait_count: false,
+        }
+    }
+}
+
+impl ScoringConfig {
+    pub fn exclude(mut self, trait_type: impl Into<String>) -> Self {
+        self.excluded_traits.insert(trait_type.into());
+        self
+    }
+
+    pub fn include(mut self, trait_type: impl Into<String>) -> Self {
+        self.included_traits.insert(trait_type.into());
+        self
+    }
+
+    pub fn treat_missing_as_null(mut self, treat_missing_as_null: bool) -> Self {
+        self.treat_missing_as_null = treat_missing_as_null;
+        self
+    }
+
+    pub fn include_trait_count(mut self, include_trait_count: bool) -> Self {
+        self.include_trait_count = include_trait_count;
+        self
+    }
+
+    /// Whether `trait_type` counts towards rarity
+    pub fn is_scored(&self, trait_type: &str) -> bool {
+        (self.included_traits.is_empty() || self.included_traits.contains(trait_type))
+            && !self.excluded_traits.contains(trait_type)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        score_and_rank, score_and_rank_with, Attribute, ICScorer, MagicEdenScorer, Scorer, Token,
+    };
+
+    fn tokens(with_meta: bool) -> Vec<Token> {
+        (0..20)
+            .map(|i| {
+                let mut attributes = vec![
+                    Attribute::new("hat", format!("hat_{}", i % 4)),
+                    Attribute::new("body", if i == 7 { "gold" } else { "plain" }),
+                ];
+                if with_meta {
+                    // Unique per token: would dominate rarity if scored
+                    attributes.push(Attribute::new("Kingdom Score", format!("{}", i * 13)));
+                    if i % 3 == 0 {
+                        attributes.push(Attribute::new("Votes", "yes"));
+                    }
+                }
+                Token::new(format!("{i}"), attributes)
+            })
+            .collect()
+    }
+
+    fn scores(ranked: Vec<crate::RankedToken>) -> Vec<(String, f64)> {
+        let mut scores: Vec<_> = ranked.into_iter().map(|t| (t.id, t.score)).collect();
+        scores.sort_by(|a, b| a.0.cmp(&b.0));
+        scores
+    }
+
+    #[test]
+    fn test_excluded_traits_do_not_affect_scores() {
+        let config = ScoringConfig::default()
+            .exclude("Kingdom Score")
+            .exclude("Votes");
+        let scorers: [&dyn Scorer; 2] = [&MagicEdenScorer, &ICScorer];
+
+        for scorer in scorers {
+            let excluded = scores(score_and_rank_with(scorer, &tokens(true), &config));
+            let without_meta = scores(score_and_rank(scorer, &tokens(false)));
+            assert_eq!(excluded.len(), without_meta.len());
+            for ((id_a, a), (id_b, b)) in excluded.iter().zip(&without_meta) {
+                assert_eq!(id_a, id_b);
+                approx::assert_relative_eq!(*a, *b, epsilon = 1e-12);
+            }
+        }
+    }
+
+    #[test]
+    fn test_included_traits_allow_list() {
+        let config = ScoringConfig::default().include("hat").include("body");
+        assert!(config.is_scored("hat"));
+        assert!(!config.is_scored("Votes"));
+        assert!(!config.clone().exclude("hat").is_scored("hat"));
+
+        let included = scores(score_and_rank_with(
+            &MagicEdenScorer,
+            &tokens(true),
+            &config,
+        ));
+        let without_meta = scores(score_and_rank(&MagicEdenScorer, &tokens(false)));
+        for ((_, a), (_, b)) in included.iter().zip(&without_meta) {
+            approx::assert_relative_eq!(*a, *b, epsilon = 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_missing_traits_without_nulls() {
+        // 7 of 20 tokens have "Votes"
+        let tokens = tokens(true);
+        let config = ScoringConfig::default()
+            .exclude("Kingdom Score")
+            .treat_missing_as_null(false);
+
+        let ranked = scores(score_and_rank_with(&MagicEdenScorer, &tokens, &config));
+        let with_votes = ranked.iter().find(|(id, _)| id == "0").unwrap().1;
+        let without_votes = ranked.iter().find(|(id, _)| id == "4").unwrap().1;
+        // Token 4 shares token 0's hat and body but skips the "Votes" factor
+        approx::assert_relative_eq!(with_votes, without_votes * 7.0 / 20.0, epsilon = 1e-12);
+    }
+
+    #[test]
+    fn test_config_serde_defaults() {
+        let config: ScoringConfig =
+            serde_json::from_str(r#"{"excluded_traits":["Votes"]}"#).unwrap();
+        assert!(config.treat_missing_as_null);
+        assert!(!config.include_trait_count);
+        assert!(!config.is_scored("Votes"));
+    }
+
+    #[test]
+    fn test_trait_count_parity() {
+        // Expected ranks follow Magic Eden's formula by hand: with trait
+        // count on, token 7 (the only one with a single trait) overtakes 4
+        let tokens: Vec<Token> = [
+            ("1", vec![("hat", "red"), ("body", "plain")]),
+            ("2", vec![("hat", "red"), ("body", "plain")]),
+            ("3", vec![("hat", "red"), ("body", "plain"), ("pet", "cat")]),
+            (
+                "4",
+                vec![("hat", "blue"), ("body", "plain"), ("pet", "cat")],
+            ),
+            ("5", vec![("hat", "blue"), ("body", "gold"), ("pet", "dog")]),
+            ("6", vec![("hat", "red"), ("body", "gold")]),
+            ("7", vec![("hat", "red")]),
+        ]
+        .into_iter()
+        .map(|(id, attributes)| {
+            let attributes = attributes
+                .into_iter()
+                .map(|(trait_type, value)| Attribute::new(trait_type, value))
+                .collect();
+            Token::new(id, attributes)
+        })
+        .collect();
+        let ranks = |ranked: Vec<crate::RankedToken>| -> Vec<(String, usize)> {
+            ranked.into_iter().map(|t| (t.id, t.rank)).take(3).collect()
+        };
+        let expected = |ids: [&str; 3]| -> Vec<(String, usize)> {
+            ids.iter()
+                .zip(1..)
+                .map(|(id, rank)| (id.to_string(), rank))
+                .collect()
+        };
+
+        let with_count = ScoringConfig::default().include_trait_count(true);
+        let scorers: [&dyn Scorer; 2] = [&MagicEdenScorer, &ICScorer];
+        for scorer in scorers {
+            let plain = ranks(score_and_rank(scorer, &tokens));
+            assert_eq!(plain, expected(["5", "4", "7"]), "{}", scorer.name());
+            let counted = ranks(score_and_rank_with(scorer, &tokens, &with_count));
+            assert_eq!(counted, expected(["5", "7", "4"]), "{}", scorer.name());
+        }
+
+        // Each Magic Eden score gains a factor of P(trait count)
+        let plain = scores(score_and_rank(&MagicEdenScorer, &tokens));
+        let counted = scores(score_and_rank_with(&MagicEdenScorer, &tokens, &with_count));
+        let count_share = [2.0, 2.0, 3.0, 3.0, 3.0, 2.0, 1.0].map(|n| n / 7.0);
+        for (((_, a), (_, b)), share) in plain.iter().zip(&counted).zip(count_share) {
+            approx::assert_relative_eq!(*b, *a * share, epsilon = 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_trait_count_parity_black_flag() {
+        // Black Flag's 2000 tokens, traits taken from the cnft.tools dump
+        // with "None" values dropped. Expected ranks follow Magic Eden's
+        // statistical formula, computed outside this crate
+        #[derive(serde::Deserialize)]
+        struct Fixture {
+            traits: Vec<String>,
+            tokens: Vec<(String, Vec<Option<String>>, usize, usize)>,
+        }
+        let fixture: Fixture =
+            serde_json::from_str(test_utils::test_case!("black_flag.json")).unwrap();
+        let tokens: Vec<Token> = fixture
+            .tokens
+            .iter()
+            .map(|(id, values, ..)| {
+                let attributes = fixture
+                    .traits
+                    .iter()
+                    .zip(values)
+                    .filter_map(|(trait_type, value)| {
+                        value
+                            .as_ref()
+                            .map(|value| Attribute::new(trait_type, value))
+                    })
+                    .collect();
+                Token::new(id, attributes)
+            })
+            .collect();
+        let expected = |with_count: bool| -> Vec<(String, usize)> {
+            let mut ranks: Vec<_> = fixture
+                .tokens
+                .iter()
+                .map(|(id, _, plain, counted)| {
+                    (id.clone(), if with_count { *counted } else { *plain })
+                })
+                .collect();
+            ranks.sort();
+            ranks
+        };
+        let actual = |ranked: Vec<crate::RankedToken>| -> Vec<(String, usize)> {
+            let mut ranks: Vec<_> = ranked.into_iter().map(|t| (t.id, t.rank)).collect();
+            ranks.sort();
+            ranks
+        };
+
+        let with_count = ScoringConfig::default().include_trait_count(true);
+        assert_eq!(
+            actual(score_and_rank(&MagicEdenScorer, &tokens)),
+            expected(false)
+        );
+        assert_eq!(
+            actual(score_and_rank_with(&MagicEdenScorer, &tokens, &with_count)),
+            expected(true)
+        );
+    }
+}
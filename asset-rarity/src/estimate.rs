@@ -0,0 +1,197 @@
+//! Single-token rarity without the full token list.
+//!
+//! Notification workers often hold only a collection's trait value counts
+//! and the one token being announced. With the `cardano-assets` feature,
+//! [`Collection::from_summary`] turns a `TraitSummarySorted` into collection
+//! stats, and [`score_token_against`] scores the token against them.
+//!
+//! ```ignore
+//! use asset_rarity::{score_token_against, Collection, MagicEdenScorer, Token};
+//!
+//! let collection = Collection::from_summary(&summary, total_supply);
+//! let token = Token::from_traits(asset_name, &asset.traits);
+//! let score = score_token_against(&MagicEdenScorer, &collection, &token);
+//! ```
+//!
+//! A summary only has value counts, so the result is an estimate: every
+//! trait is treated as a single slot (multi-valued traits are pooled), and
+//! tokens lacking a trait are inferred from `total_supply`. Scores are
+//! comparable with each other, not with a ranking built from the tokens.
+
+use crate::{Collection, Scorer, Token};
+
+/// Score one token against precomputed collection stats.
+///
+/// The token doesn't need to have been part of the collection the stats
+/// were built from.
+pub fn score_token_against(scorer: &dyn Scorer, collection: &Collection, token: &Token) -> f64 {
+    scorer
+        .score(collection, std::slice::from_ref(token))
+        .first()
+        .map(|(_, score)| *score)
+        .unwrap_or_default()
+}
+
+#[cfg(feature = "cardano-assets")]
+mod summary {
+    use std::collections::BTreeMap;
+
+    use cardano_assets::{TraitSummarySorted, Traits};
+
+    use crate::{Attribute, Collection, ScoringConfig, Token};
+
+    impl Collection {
+        /// Estimate collection stats from trait value counts
+        pub fn from_summary(summary: &TraitSummarySorted, total_supply: usize) -> Self {
+            Self::from_summary_with(summary, total_supply, &ScoringConfig::default())
+        }
+
+        /// [`from_summary`](Self::from_summary), keeping only the traits
+        /// `config` allows
+        pub fn from_summary_with(
+            summary: &TraitSummarySorted,
+            total_supply: usize,
+            config: &ScoringConfig,
+        ) -> Self {
+            let mut shape = BTreeMap::new();
+            let mut frequencies = BTreeMap::new();
+
+            for (trait_type, value_counts) in &summary.traits {
+                if !config.is_scored(trait_type) {
+                    continue;
+                }
+                let mut values: BTreeMap<String, usize> = value_counts
+                    .iter()
+                    .filter(|v| v.count > 0)
+                    .map(|v| (v.value.clone(), v.count as usize))
+                    .collect();
+                let counted: usize = values.values().sum();
+                if config.treat_missing_as_null && counted < total_supply {
+                    values.insert("__null_0".to_string(), total_supply - counted);
+                }
+
+                shape.insert(trait_type.clone(), 1);
+                frequencies.insert((trait_type.clone(), 0), values);
+            }
+
+            Self {
+                total_supply,
+                shape,
+                frequencies,
+                treat_missing_as_null: config.treat_missing_as_null,
+                // A summary doesn't say how many traits each token has
+                include_trait_count: false,
+            }
+        }
+    }
+
+    impl Token {
+        /// A token from a Cardano asset's traits
+        pub fn from_traits(id: impl Into<String>, traits: &Traits) -> Self {
+            let attributes = traits
+                .iter()
+                .flat_map(|(name, values)| values.iter().map(|v| Attribute::new(name, v)))
+                .collect();
+            Self::new(id, attributes)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{build_collection, score_and_rank, Attribute, ICScorer, MagicEdenScorer};
+
+    fn tokens() -> Vec<Token> {
+        (0..20)
+            .map(|i| {
+                let mut attributes = vec![Attribute::new("hat", format!("hat_{}", i % 4))];
+                if i % 5 == 0 {
+                    attributes.push(Attribute::new("special", "laser eyes"));
+                }
+                Token::new(format!("{i}"), attributes)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_score_token_against_matches_batch() {
+        let tokens = tokens();
+        let collection = build_collection(&tokens);
+        let ranked = score_and_rank(&MagicEdenScorer, &tokens);
+        for token in &tokens {
+            let expected = ranked.iter().find(|r| r.id == token.id).unwrap().score;
+            let score = score_token_against(&MagicEdenScorer, &collection, token);
+            approx::assert_relative_eq!(score, expected);
+        }
+    }
+
+    #[cfg(feature = "cardano-assets")]
+    mod summary {
+        use super::*;
+        use cardano_assets::{TraitSummarySorted, Traits};
+        use std::collections::HashMap;
+
+        fn summary_of(tokens: &[Token]) -> TraitSummarySorted {
+            let mut counts: HashMap<&str, HashMap<&str, u32>> = HashMap::new();
+            for attr in tokens.iter().flat_map(|t| &t.attributes) {
+                *counts
+                    .entry(attr.trait_type.as_str())
+                    .or_default()
+                    .entry(attr.value.as_str())
+                    .or_default() += 1;
+            }
+            let traits: HashMap<_, Vec<_>> = counts
+                .into_iter()
+                .map(|(name, values)| {
+                    let values = values
+                        .into_iter()
+                        .map(|(v, c)| serde_json::json!({"v": v, "c": c}))
+                        .collect();
+                    (name, values)
+                })
+                .collect();
+            serde_json::from_value(serde_json::json!({"traits": traits, "count": tokens.len()}))
+                .unwrap()
+        }
+
+        #[test]
+        fn test_from_summary_matches_single_valued_collection() {
+            let tokens = tokens();
+            let built = build_collection(&tokens);
+            let estimated = Collection::from_summary(&summary_of(&tokens), tokens.len());
+            assert_eq!(estimated.shape, built.shape);
+            assert_eq!(estimated.frequencies, built.frequencies);
+
+            for scorer in [&MagicEdenScorer as &dyn Scorer, &ICScorer] {
+                for token in &tokens {
+                    approx::assert_relative_eq!(
+                        score_token_against(scorer, &estimated, token),
+                        score_token_against(scorer, &built, token)
+                    );
+                }
+            }
+        }
+
+        #[test]
+        fn test_multi_valued_token_against_summary() {
+            let mut tokens = tokens();
+            tokens.push(Token::new(
+                "crew",
+                vec![
+                    Attribute::new("hat", "hat_0"),
+                    Attribute::new("hat", "hat_1"),
+                ],
+            ));
+            let collection = Collection::from_summary(&summary_of(&tokens), tokens.len());
+
+            let traits = Traits::from_map(HashMap::from([(
+                "hat".to_string(),
+                vec!["hat_0".to_string(), "hat_1".to_string()],
+            )]));
+            let token = Token::from_traits("crew", &traits);
+            let score = score_token_against(&MagicEdenScorer, &collection, &token);
+            assert!(score > 0.0 && score < 1.0);
+        }
+    }
+}
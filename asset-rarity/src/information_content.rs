@@ -30,7 +30,7 @@ impl ICScorer {
     /// Calculate the IC score for a single token (before entropy normalization).
     fn token_ic_score(&self, collection: &Collection, token: &Token) -> f64 {
         let total = collection.total_supply as f64;
-        let normalized = normalize_token_attributes(token, &collection.shape);
+        let normalized = normalize_token_attributes(token, collection);
 
         let mut ic = 0.0;
         for (trait_type, slot_idx, value) in &normalized {
@@ -17,15 +17,43 @@
 //! let ranked = score_and_rank(&MagicEdenScorer, &tokens);
 //! assert_eq!(ranked[0].rank, 1); // rarest token
 //! ```
+//!
+//! # Checking against published ranks
+//! [`verify_against`] compares a ranking with one published by a marketplace
+//! and reports correlation and the largest mismatches.
+//!
+//! # Large collections
+//! With the `parallel` feature (native only), [`score_and_rank`] spreads
+//! scoring across a rayon thread pool. On Workers, use the [`chunked`] APIs
+//! ([`score_chunk`] / [`rank_chunks`]) to split scoring across queue messages.
+//! When even the token list won't fit in memory, the [`streaming`] APIs
+//! ([`CollectionBuilder`] / [`score_stream`]) read the tokens in two passes.
+//!
+//! # Single tokens
+//! [`score_token_against`] scores one token against stored [`Collection`]
+//! stats. With the `cardano-assets` feature, `Collection::from_summary`
+//! estimates those stats from a trait summary when the tokens aren't at hand.
 
+pub mod chunked;
 mod collection;
+mod config;
+mod estimate;
 mod information_content;
 mod magic_eden;
 mod ranker;
+pub mod streaming;
+mod verify;
 
-pub use collection::{build_collection, Collection};
+pub use chunked::{chunk_ranges, rank_chunks, score_chunk, ScoreChunk};
+pub use collection::{
+    build_collection, build_collection_with, Collection, CollectionBuilder, TRAIT_COUNT,
+};
+pub use config::ScoringConfig;
+pub use estimate::score_token_against;
 pub use information_content::ICScorer;
 pub use magic_eden::MagicEdenScorer;
+pub use streaming::{score_stream, ScoreStream};
+pub use verify::{verify_against, RankMismatch, RankVerification};
 
 /// A single trait_type/value attribute (Solana Metaplex format).
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -73,7 +101,9 @@ pub struct RankedToken {
 }
 
 /// Pluggable scoring algorithm.
-pub trait Scorer {
+///
+/// Scorers must be `Sync` so chunks can be scored from several threads.
+pub trait Scorer: Sync {
     /// Score all tokens against collection statistics.
     /// Returns `(token_id, score)` pairs.
     fn score(&self, collection: &Collection, tokens: &[Token]) -> Vec<(String, f64)>;
@@ -88,8 +118,43 @@ pub trait Scorer {
 }
 
 /// Build collection stats and score+rank all tokens with the given algorithm.
+///
+/// With the `parallel` feature on native targets, collections of at least
+/// [`PARALLEL_THRESHOLD`] tokens are scored in chunks across threads.
 pub fn score_and_rank(scorer: &dyn Scorer, tokens: &[Token]) -> Vec<RankedToken> {
-    let collection = build_collection(tokens);
-    let scores = scorer.score(&collection, tokens);
+    score_and_rank_with(scorer, tokens, &ScoringConfig::default())
+}
+
+/// [`score_and_rank`] scoring only the traits `config` allows.
+pub fn score_and_rank_with(
+    scorer: &dyn Scorer,
+    tokens: &[Token],
+    config: &ScoringConfig,
+) -> Vec<RankedToken> {
+    let collection = build_collection_with(tokens, config);
+    let scores = score_all(scorer, &collection, tokens);
     ranker::rank(scores, scorer.lower_is_rarer())
 }
+
+/// Collection size at which [`score_and_rank`] switches to parallel scoring.
+pub const PARALLEL_THRESHOLD: usize = 4096;
+
+#[cfg(all(feature = "parallel", not(target_arch = "wasm32")))]
+fn score_all(scorer: &dyn Scorer, collection: &Collection, tokens: &[Token]) -> Vec<(String, f64)> {
+    use rayon::prelude::*;
+
+    if tokens.len() < PARALLEL_THRESHOLD {
+        return scorer.score(collection, tokens);
+    }
+
+    let chunk_size = tokens.len().div_ceil(rayon::current_num_threads()).max(1);
+    tokens
+        .par_chunks(chunk_size)
+        .flat_map_iter(|chunk| scorer.score(collection, chunk))
+        .collect()
+}
+
+#[cfg(not(all(feature = "parallel", not(target_arch = "wasm32"))))]
+fn score_all(scorer: &dyn Scorer, collection: &Collection, tokens: &[Token]) -> Vec<(String, f64)> {
+    scorer.score(collection, tokens)
+}
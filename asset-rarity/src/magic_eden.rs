@@ -16,7 +16,7 @@ impl Scorer for MagicEdenScorer {
         tokens
             .iter()
             .map(|token| {
-                let normalized = normalize_token_attributes(token, &collection.shape);
+                let normalized = normalize_token_attributes(token, collection);
                 let score = normalized
                     .iter()
                     .map(|(trait_type, slot_idx, value)| {
@@ -0,0 +1,171 @@
+//! Two-pass scoring that never holds the whole collection in memory.
+//!
+//! A Worker can't load 50k tokens at once, but it can read them page by page
+//! from R2 or KV — twice:
+//!
+//! 1. Feed every token into a [`CollectionBuilder`], which keeps only value
+//!    counts, then [`finish`](CollectionBuilder::finish) it into a
+//!    [`Collection`].
+//! 2. Read the tokens again and pass them through [`score_stream`], which
+//!    scores them one at a time as they arrive.
+//!
+//! Ranking still needs every score, but only `(token_id, score)` pairs —
+//! collect them into a [`ScoreChunk`](crate::ScoreChunk) and hand it to
+//! [`rank_chunks`](crate::rank_chunks).
+//!
+//! ```
+//! use asset_rarity::{score_stream, Attribute, CollectionBuilder, MagicEdenScorer, Token};
+//!
+//! // Stands in for a paged read from storage
+//! let pages = || {
+//!     (0..10).map(|i| Token::new(format!("{i}"), vec![Attribute::new("hat", format!("{}", i % 3))]))
+//! };
+//!
+//! let mut builder = CollectionBuilder::default();
+//! builder.extend(pages());
+//! let collection = builder.finish();
+//!
+//! let scores: Vec<_> = score_stream(&MagicEdenScorer, &collection, pages()).collect();
+//! assert_eq!(scores.len(), 10);
+//! ```
+
+use crate::{Collection, Scorer, Token};
+
+/// Scores tokens one at a time as they are pulled from the inner iterator.
+///
+/// Created by [`score_stream`].
+pub struct ScoreStream<'a, I> {
+    scorer: &'a dyn Scorer,
+    collection: &'a Collection,
+    tokens: I,
+}
+
+/// Score `tokens` lazily against finished collection stats, yielding
+/// `(token_id, score)` pairs in input order.
+///
+/// `collection` must cover the whole collection; see
+/// [`score_chunk`](crate::score_chunk).
+pub fn score_stream<'a, I>(
+    scorer: &'a dyn Scorer,
+    collection: &'a Collection,
+    tokens: I,
+) -> ScoreStream<'a, I::IntoIter>
+where
+    I: IntoIterator<Item = Token>,
+{
+    ScoreStream {
+        scorer,
+        collection,
+        tokens: tokens.into_iter(),
+    }
+}
+
+impl<I: Iterator<Item = Token>> Iterator for ScoreStream<'_, I> {
+    type Item = (String, f64);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let token = self.tokens.next()?;
+        self.scorer
+            .score(self.collection, std::slice::from_ref(&token))
+            .pop()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.tokens.size_hint()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use super::*;
+    use crate::{
+        build_collection_with, rank_chunks, score_and_rank_with, Attribute, CollectionBuilder,
+        ICScorer, MagicEdenScorer, ScoreChunk, ScoringConfig,
+    };
+
+    fn sample_tokens(n: usize) -> impl Iterator<Item = Token> {
+        (0..n).map(|i| {
+            let mut attributes = vec![
+                Attribute::new("hat", format!("hat_{}", i % 7)),
+                Attribute::new("eyes", format!("eyes_{}", (i * 31) % 17)),
+            ];
+            // Uneven multi-valued and missing traits exercise null padding
+            for j in 0..(i % 4) {
+                attributes.push(Attribute::new("outfit", format!("outfit_{}", (i + j) % 5)));
+            }
+            if i % 9 == 0 {
+                attributes.push(Attribute::new("special", "true"));
+            }
+            Token::new(format!("{i}"), attributes)
+        })
+    }
+
+    #[test]
+    fn test_builder_matches_build_collection() {
+        let tokens: Vec<Token> = sample_tokens(200).collect();
+        let configs = [
+            ScoringConfig::default(),
+            ScoringConfig::default().treat_missing_as_null(false),
+            ScoringConfig::default()
+                .include_trait_count(true)
+                .exclude("special"),
+        ];
+
+        for config in configs {
+            let mut builder = CollectionBuilder::new(config.clone());
+            builder.extend(sample_tokens(200));
+            let streamed = builder.finish();
+            let direct = build_collection_with(&tokens, &config);
+
+            assert_eq!(streamed.total_supply, direct.total_supply);
+            assert_eq!(streamed.shape, direct.shape);
+            assert_eq!(streamed.frequencies, direct.frequencies);
+        }
+    }
+
+    #[test]
+    fn test_builder_resumes_after_serde() {
+        let mut builder = CollectionBuilder::default();
+        builder.extend(sample_tokens(50));
+
+        let json = serde_json::to_string(&builder).unwrap();
+        let mut builder: CollectionBuilder = serde_json::from_str(&json).unwrap();
+        builder.extend(sample_tokens(100).skip(50));
+        assert_eq!(builder.total_supply(), 100);
+
+        let mut whole = CollectionBuilder::default();
+        whole.extend(sample_tokens(100));
+        assert_eq!(builder.finish().frequencies, whole.finish().frequencies);
+    }
+
+    #[test]
+    fn test_streamed_ranks_match_score_and_rank() {
+        let tokens: Vec<Token> = sample_tokens(200).collect();
+        let config = ScoringConfig::default();
+
+        let mut builder = CollectionBuilder::new(config.clone());
+        builder.extend(sample_tokens(200));
+        let collection = builder.finish();
+
+        for scorer in [&MagicEdenScorer as &dyn Scorer, &ICScorer] {
+            let chunk = ScoreChunk {
+                chunk: 0,
+                scores: score_stream(scorer, &collection, sample_tokens(200)).collect(),
+            };
+            let streamed = rank_chunks([chunk], scorer.lower_is_rarer());
+            let direct = score_and_rank_with(scorer, &tokens, &config);
+
+            assert_eq!(streamed.len(), direct.len());
+            let ranks: BTreeMap<_, _> = direct.iter().map(|t| (&t.id, t.rank)).collect();
+            for token in &streamed {
+                assert_eq!(
+                    ranks[&token.id], token.rank,
+                    "rank mismatch for {}",
+                    token.id
+                );
+            }
+        }
+    }
+}
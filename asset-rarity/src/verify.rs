@@ -0,0 +1,249 @@
+//! Compare computed rankings with ranks published elsewhere.
+//!
+//! Marketplaces (cnft.tools, Anvil) publish their own ranks, and for some
+//! collections they disagree with ours — different trait handling, missing
+//! metadata, or a deliberately curated ranking. [`verify_against`] lines the
+//! two up by token id and reports how closely they agree, so the choice of
+//! ranking source can be made per collection.
+//!
+//! ```
+//! use asset_rarity::{score_and_rank, verify_against, Attribute, MagicEdenScorer, Token};
+//!
+//! let tokens = vec![
+//!     Token::new("1", vec![Attribute::new("hat", "red")]),
+//!     Token::new("2", vec![Attribute::new("hat", "red")]),
+//!     Token::new("3", vec![Attribute::new("hat", "gold")]),
+//! ];
+//! let ranked = score_and_rank(&MagicEdenScorer, &tokens);
+//!
+//! let published: Vec<(String, usize)> = ranked.iter().map(|t| (t.id.clone(), t.rank)).collect();
+//! let report = verify_against(&ranked, &published, 10);
+//! assert!(report.spearman.unwrap() > 0.999);
+//! assert!(report.mismatches.is_empty());
+//! ```
+
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::RankedToken;
+
+/// A token ranked differently by the two sources
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RankMismatch {
+    pub id: String,
+    pub ours: usize,
+    pub published: usize,
+}
+
+impl RankMismatch {
+    /// Published rank minus ours; positive when we rank the token rarer
+    pub fn delta(&self) -> i64 {
+        self.published as i64 - self.ours as i64
+    }
+}
+
+/// Agreement between our ranking and a published one
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RankVerification {
+    /// Tokens ranked by both sources
+    pub compared: usize,
+    /// Tokens only we ranked
+    pub missing_published: usize,
+    /// Published ids we have no rank for
+    pub unknown_published: usize,
+    /// Tokens given the same rank by both
+    pub exact_matches: usize,
+    /// Spearman rank correlation (1.0 = identical order), `None` with fewer
+    /// than two compared tokens or a constant ranking
+    pub spearman: Option<f64>,
+    pub mean_abs_delta: f64,
+    pub median_abs_delta: f64,
+    /// Share of our top 10% that is also in the published top 10%
+    pub top_decile_overlap: f64,
+    /// Largest disagreements first
+    pub mismatches: Vec<RankMismatch>,
+}
+
+/// Compare `ranked` with `published` `(token_id, rank)` pairs, keeping the
+/// `max_mismatches` largest disagreements.
+pub fn verify_against<S: AsRef<str>>(
+    ranked: &[RankedToken],
+    published: &[(S, usize)],
+    max_mismatches: usize,
+) -> RankVerification {
+    let published: HashMap<&str, usize> = published
+        .iter()
+        .map(|(id, rank)| (id.as_ref(), *rank))
+        .collect();
+
+    let pairs: Vec<(&str, usize, usize)> = ranked
+        .iter()
+        .filter_map(|token| {
+            published
+                .get(token.id.as_str())
+                .map(|&theirs| (token.id.as_str(), token.rank, theirs))
+        })
+        .collect();
+
+    let compared = pairs.len();
+    let mut abs_deltas: Vec<usize> = pairs
+        .iter()
+        .map(|(_, ours, theirs)| ours.abs_diff(*theirs))
+        .collect();
+    abs_deltas.sort_unstable();
+
+    let mut mismatches: Vec<RankMismatch> = pairs
+        .iter()
+        .filter(|(_, ours, theirs)| ours != theirs)
+        .map(|(id, ours, theirs)| RankMismatch {
+            id: id.to_string(),
+            ours: *ours,
+            published: *theirs,
+        })
+        .collect();
+    mismatches.sort_by(|a, b| {
+        b.delta()
+            .abs()
+            .cmp(&a.delta().abs())
+            .then_with(|| a.ours.cmp(&b.ours))
+    });
+    mismatches.truncate(max_mismatches);
+
+    RankVerification {
+        compared,
+        missing_published: ranked.len().saturating_sub(compared),
+        unknown_published: published.len().saturating_sub(compared),
+        exact_matches: abs_deltas.iter().take_while(|d| **d == 0).count(),
+        spearman: spearman(&pairs),
+        mean_abs_delta: mean(&abs_deltas),
+        median_abs_delta: median(&abs_deltas),
+        top_decile_overlap: top_decile_overlap(&pairs),
+        mismatches,
+    }
+}
+
+/// Pearson correlation of the two rank columns, which handles tied ranks
+fn spearman(pairs: &[(&str, usize, usize)]) -> Option<f64> {
+    if pairs.len() < 2 {
+        return None;
+    }
+    let n = pairs.len() as f64;
+    let mean_ours = pairs.iter().map(|p| p.1 as f64).sum::<f64>() / n;
+    let mean_theirs = pairs.iter().map(|p| p.2 as f64).sum::<f64>() / n;
+
+    let (mut cov, mut var_ours, mut var_theirs) = (0.0, 0.0, 0.0);
+    for (_, ours, theirs) in pairs {
+        let a = *ours as f64 - mean_ours;
+        let b = *theirs as f64 - mean_theirs;
+        cov += a * b;
+        var_ours += a * a;
+        var_theirs += b * b;
+    }
+
+    if var_ours == 0.0 || var_theirs == 0.0 {
+        return None;
+    }
+    Some(cov / (var_ours * var_theirs).sqrt())
+}
+
+fn top_decile_overlap(pairs: &[(&str, usize, usize)]) -> f64 {
+    let top = pairs.len().div_ceil(10);
+    if top == 0 {
+        return 0.0;
+    }
+    let top_ids = |rank: fn(&(&str, usize, usize)) -> usize| {
+        let mut sorted: Vec<_> = pairs.iter().collect();
+        sorted.sort_by_key(|p| rank(p));
+        sorted
+            .into_iter()
+            .take(top)
+            .map(|p| p.0)
+            .collect::<Vec<_>>()
+    };
+    let ours = top_ids(|p| p.1);
+    let theirs = top_ids(|p| p.2);
+    ours.iter().filter(|id| theirs.contains(id)).count() as f64 / top as f64
+}
+
+fn mean(values: &[usize]) -> f64 {
+    if values.is_empty() {
+        return 0.0;
+    }
+    values.iter().sum::<usize>() as f64 / values.len() as f64
+}
+
+/// Median of sorted `values`
+fn median(values: &[usize]) -> f64 {
+    match values.len() {
+        0 => 0.0,
+        n if n % 2 == 1 => values[n / 2] as f64,
+        n => (values[n / 2 - 1] + values[n / 2]) as f64 / 2.0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+
+    fn ranked(ranks: &[(&str, usize)]) -> Vec<RankedToken> {
+        ranks
+            .iter()
+            .map(|(id, rank)| RankedToken {
+                id: id.to_string(),
+                score: *rank as f64,
+                rank: *rank,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_identical_rankings() {
+        let ours = ranked(&[("a", 1), ("b", 2), ("c", 3)]);
+        let report = verify_against(&ours, &[("a", 1), ("b", 2), ("c", 3)], 5);
+        assert_eq!(report.compared, 3);
+        assert_eq!(report.exact_matches, 3);
+        assert_relative_eq!(report.spearman.unwrap(), 1.0);
+        assert_eq!(report.mean_abs_delta, 0.0);
+        assert_eq!(report.top_decile_overlap, 1.0);
+        assert!(report.mismatches.is_empty());
+    }
+
+    #[test]
+    fn test_reversed_with_missing_tokens() {
+        let ours = ranked(&[("a", 1), ("b", 2), ("c", 3), ("d", 4), ("e", 5)]);
+        let published = vec![
+            ("a".to_string(), 4),
+            ("b".to_string(), 3),
+            ("c".to_string(), 2),
+            ("d".to_string(), 1),
+            ("z".to_string(), 5),
+        ];
+        let report = verify_against(&ours, &published, 2);
+
+        assert_eq!(report.compared, 4);
+        assert_eq!(report.missing_published, 1);
+        assert_eq!(report.unknown_published, 1);
+        assert_eq!(report.exact_matches, 0);
+        assert_relative_eq!(report.spearman.unwrap(), -1.0);
+        assert_eq!(report.mean_abs_delta, 2.0);
+        assert_eq!(report.median_abs_delta, 2.0);
+        assert_eq!(report.top_decile_overlap, 0.0);
+
+        // Largest first, ties broken by our rank
+        assert_eq!(report.mismatches.len(), 2);
+        assert_eq!(report.mismatches[0].id, "a");
+        assert_eq!(report.mismatches[0].delta(), 3);
+        assert_eq!(report.mismatches[1].id, "d");
+        assert_eq!(report.mismatches[1].delta(), -3);
+    }
+
+    #[test]
+    fn test_too_few_tokens() {
+        let ours = ranked(&[("a", 1)]);
+        let report = verify_against(&ours, &[("a", 7)], 5);
+        assert_eq!(report.spearman, None);
+        assert_eq!(report.mismatches[0].delta(), 6);
+    }
+}
@@ -10,6 +10,9 @@ use crate::{Asset, MetadataKind};
 /// Cardano policy ID length in hex characters (28 bytes = 56 hex chars)
 const POLICY_ID_LENGTH: usize = 56;
 
+/// Default character limit for [`AssetId::display_name`]
+pub const DISPLAY_NAME_MAX_CHARS: usize = 64;
+
 /// A compound asset identifier representing an on-chain Cardano native token
 ///
 /// Combines policy_id and asset_name_hex into a unified type that can
@@ -51,7 +54,6 @@ const POLICY_ID_LENGTH: usize = 56;
 /// assert!(AssetId::new("policy_id".to_string(), "".to_string()).is_err());
 /// ```
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
-#[cfg_attr(feature = "openapi", derive(ToSchema))]
 pub struct AssetId {
     /// Policy ID as 56-character hex string (28 bytes)
     pub policy_id: String,
@@ -142,6 +144,66 @@ impl AssetId {
         Self::new_unchecked(self.policy_id.clone(), stripped_hex)
     }
 
+    /// The CIP-67 label prefixing the asset name, if any (100, 222, 333, ...)
+    ///
+    /// Only prefixes with a valid checksum count, so a name that happens to
+    /// start with zero bytes isn't mistaken for a labelled one.
+    pub fn cip67_label(&self) -> Option<u16> {
+        let prefix = hex::decode(self.asset_name_hex.get(..8)?).ok()?;
+        let [0, hi, lo, 0] = [
+            prefix[0] >> 4,
+            (prefix[0] << 4) | (prefix[1] >> 4),
+            (prefix[1] << 4) | (prefix[2] >> 4),
+            prefix[3] & 0x0f,
+        ] else {
+            return None;
+        };
+        let checksum = (prefix[2] << 4) | (prefix[3] >> 4);
+        (crc8(&[hi, lo]) == checksum).then_some(u16::from_be_bytes([hi, lo]))
+    }
+
+    /// Asset name for notifications and UI, at most
+    /// [`DISPLAY_NAME_MAX_CHARS`] characters
+    ///
+    /// See [`Self::display_name_with_limit`].
+    pub fn display_name(&self) -> String {
+        self.display_name_with_limit(DISPLAY_NAME_MAX_CHARS)
+    }
+
+    /// Asset name for notifications and UI
+    ///
+    /// Strips any CIP-67 label, decodes UTF-8 with lossy replacement,
+    /// escapes control characters and truncates to `max_chars` (including a
+    /// trailing `…`). Names with no decodable text at all are shown as hex.
+    pub fn display_name_with_limit(&self, max_chars: usize) -> String {
+        let name_hex = match self.cip67_label() {
+            Some(_) => &self.asset_name_hex[8..],
+            None => self.asset_name_hex.as_str(),
+        };
+
+        let name = match hex::decode(name_hex) {
+            Ok(bytes) => {
+                let decoded = String::from_utf8_lossy(&bytes);
+                if decoded.chars().all(|c| c == char::REPLACEMENT_CHARACTER) {
+                    name_hex.to_string()
+                } else {
+                    let mut escaped = String::with_capacity(decoded.len());
+                    for c in decoded.chars() {
+                        if c.is_control() {
+                            escaped.extend(c.escape_default());
+                        } else {
+                            escaped.push(c);
+                        }
+                    }
+                    escaped
+                }
+            }
+            Err(_) => name_hex.to_string(),
+        };
+
+        truncate_with_ellipsis(name, max_chars)
+    }
+
     /// Create AssetId from hex-encoded asset name
     pub fn from_hex_name(policy_id: String, asset_name_hex: String) -> Result<Self, AssetIdError> {
         Self::new(policy_id, asset_name_hex)
@@ -331,6 +393,43 @@ impl AssetId {
     }
 }
 
+/// Documented as the `{policy_id, asset_name_hex}` object it serializes to in
+/// JSON; the string forms are only accepted on input
+#[cfg(feature = "openapi")]
+impl utoipa::PartialSchema for AssetId {
+    fn schema() -> utoipa::openapi::RefOr<utoipa::openapi::schema::Schema> {
+        use utoipa::openapi::schema::Type;
+        use utoipa::openapi::ObjectBuilder;
+
+        ObjectBuilder::new()
+            .schema_type(Type::Object)
+            .property(
+                "policy_id",
+                ObjectBuilder::new()
+                    .schema_type(Type::String)
+                    .description(Some("Policy ID, 56 hex chars"))
+                    .min_length(Some(POLICY_ID_LENGTH))
+                    .max_length(Some(POLICY_ID_LENGTH)),
+            )
+            .required("policy_id")
+            .property(
+                "asset_name_hex",
+                ObjectBuilder::new()
+                    .schema_type(Type::String)
+                    .description(Some("Hex-encoded asset name")),
+            )
+            .required("asset_name_hex")
+            .examples([serde_json::json!({
+                "policy_id": "b3dab69f7e6100849434fb1781e34bd12a916557f6231b8d2629b6f6",
+                "asset_name_hex": "50697261746531303836",
+            })])
+            .into()
+    }
+}
+
+#[cfg(feature = "openapi")]
+impl ToSchema for AssetId {}
+
 impl TryFrom<&str> for AssetId {
     type Error = AssetIdError;
 
@@ -422,7 +521,8 @@ impl From<AssetId> for String {
     }
 }
 
-/// Serialize as concatenated string by default for backward compatibility
+/// Serialize as `{policy_id, asset_name_hex}` in human-readable formats and
+/// as the concatenated string in binary ones
 impl Serialize for AssetId {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
@@ -478,6 +578,28 @@ impl<'de> Deserialize<'de> for AssetId {
     }
 }
 
+/// CRC-8 (polynomial 0x07) as used by CIP-67 label checksums
+fn crc8(bytes: &[u8]) -> u8 {
+    bytes.iter().fold(0u8, |crc, byte| {
+        (0..8).fold(crc ^ byte, |crc, _| {
+            if crc & 0x80 != 0 {
+                (crc << 1) ^ 0x07
+            } else {
+                crc << 1
+            }
+        })
+    })
+}
+
+fn truncate_with_ellipsis(value: String, max_chars: usize) -> String {
+    if value.chars().count() <= max_chars {
+        return value;
+    }
+    let mut truncated: String = value.chars().take(max_chars.saturating_sub(1)).collect();
+    truncated.push('…');
+    truncated
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -762,6 +884,38 @@ mod tests {
     }
 
     // CIP-14 fingerprint tests (using test vectors with non-empty asset names)
+    #[test]
+    fn test_cip67_label() {
+        let name = |hex: &str| AssetId::new_unchecked(TEST_POLICY_ID.to_string(), hex.to_string());
+
+        assert_eq!(name("000643b04d4432393230").cip67_label(), Some(100));
+        assert_eq!(name("000de1404d4432393230").cip67_label(), Some(222));
+        assert_eq!(name("0014df10484f534b59").cip67_label(), Some(333));
+        // Bad checksum
+        assert_eq!(name("000de1504d4432393230").cip67_label(), None);
+        assert_eq!(name(TEST_ASSET_NAME_HEX).cip67_label(), None);
+        assert_eq!(name("0000").cip67_label(), None);
+    }
+
+    #[test]
+    fn test_display_name() {
+        let name = |hex: &str| AssetId::new_unchecked(TEST_POLICY_ID.to_string(), hex.to_string());
+
+        assert_eq!(name(TEST_ASSET_NAME_HEX).display_name(), "Pirate1086");
+        assert_eq!(name("000de1404d4432393230").display_name(), "MD2920");
+        assert_eq!(name("0014df10484f534b59").display_name(), "HOSKY");
+        // Invalid UTF-8 replaced, control characters escaped
+        assert_eq!(name("50697261746580").display_name(), "Pirate\u{fffd}");
+        assert_eq!(name("4869210a").display_name(), "Hi!\\n");
+        // Nothing decodable falls back to hex
+        assert_eq!(name("ff").display_name(), "ff");
+        assert_eq!(name("").display_name(), "");
+
+        let long = name(&hex::encode("Pirate".repeat(20)));
+        assert_eq!(long.display_name_with_limit(10), "PiratePir…");
+        assert_eq!(long.display_name().chars().count(), DISPLAY_NAME_MAX_CHARS);
+    }
+
     #[cfg(feature = "cip14")]
     mod cip14_tests {
         use super::*;
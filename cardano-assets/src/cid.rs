@@ -8,8 +8,9 @@
 //! This module is pure (no chain dependencies) and is always compiled; the
 //! CIP-68 datum decoder that feeds it lives behind the `cip68` feature.
 
-use crate::{Asset, AssetFile, AssetMetadata, AssetMetadata68, PrimitiveOrList};
+use crate::{raw, Asset, AssetFile, AssetMetadata, AssetMetadata68, PrimitiveOrList};
 use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
 use std::collections::HashSet;
 
 #[cfg(feature = "openapi")]
@@ -45,11 +46,20 @@ impl AssetMetadata {
     /// non-IPFS URLs are skipped — this is an IPFS preservation index.
     #[must_use]
     pub fn extract_cids(&self) -> Vec<ExtractedCid> {
-        let (image, files) = self.image_and_files();
+        let (image, files) = match self.image_and_files() {
+            Some((image, files)) => (
+                Some(image.dechunked()),
+                Cow::Borrowed(files.as_deref().unwrap_or_default()),
+            ),
+            None => {
+                let value = self.raw().unwrap_or(&serde_json::Value::Null);
+                (raw::image(value), Cow::Owned(raw::files(value)))
+            }
+        };
         let mut seen = HashSet::new();
         let mut out = Vec::new();
 
-        if let Some(cid) = cid_from_url(&image.dechunked()) {
+        if let Some(cid) = image.as_deref().and_then(cid_from_url) {
             if seen.insert(cid.clone()) {
                 out.push(ExtractedCid {
                     cid,
@@ -59,16 +69,14 @@ impl AssetMetadata {
             }
         }
 
-        if let Some(files) = files {
-            for file in files {
-                if let Some(cid) = cid_from_url(&file.get_src()) {
-                    if seen.insert(cid.clone()) {
-                        out.push(ExtractedCid {
-                            cid,
-                            role: CidRole::File,
-                            media_type: Some(file.media_type().to_owned()),
-                        });
-                    }
+        for file in files.iter() {
+            if let Some(cid) = cid_from_url(&file.get_src()) {
+                if seen.insert(cid.clone()) {
+                    out.push(ExtractedCid {
+                        cid,
+                        role: CidRole::File,
+                        media_type: Some(file.media_type().to_owned()),
+                    });
                 }
             }
         }
@@ -76,9 +84,10 @@ impl AssetMetadata {
         out
     }
 
-    /// The `image` and `files` fields, which every variant carries.
-    fn image_and_files(&self) -> (&PrimitiveOrList<String>, &Option<Vec<AssetFile>>) {
-        match self {
+    /// The `image` and `files` fields, which every variant but
+    /// [`AssetMetadata::Raw`] carries.
+    fn image_and_files(&self) -> Option<(&PrimitiveOrList<String>, &Option<Vec<AssetFile>>)> {
+        let fields = match self {
             AssetMetadata::Attributed { image, files, .. }
             | AssetMetadata::Flattened { image, files, .. }
             | AssetMetadata::FlattenedMixed { image, files, .. }
@@ -87,7 +96,9 @@ impl AssetMetadata {
             | AssetMetadata::AttributeArray { image, files, .. }
             | AssetMetadata::UnsignedAlgorithms { image, files, .. }
             | AssetMetadata::Untitled { image, files, .. } => (image, files),
-        }
+            AssetMetadata::Raw { .. } => return None,
+        };
+        Some(fields)
     }
 }
 
@@ -11,7 +11,7 @@
 //!
 //! Behind the `cip68` feature; pulls in `pallas-codec` / `pallas-primitives`.
 
-use crate::{AssetMetadata, AssetMetadata68, NftPurpose};
+use crate::{AssetMetadata, AssetMetadata68, NftPurpose, TokenInfo};
 use pallas_primitives::{BigInt, PlutusData};
 use serde_json::{Map, Number, Value};
 use std::fmt;
@@ -30,7 +30,9 @@ pub enum Cip68Error {
     WrongConstructor,
     /// The constructor carried no fields — no metadata map present.
     EmptyDatum,
-    /// The metadata map did not match any known `AssetMetadata` shape.
+    /// The metadata was not a map (anything else decodes, as
+    /// `AssetMetadata::Raw` at worst), or for fungible tokens did not match
+    /// the `TokenInfo` shape.
     Metadata(serde_json::Error),
 }
 
@@ -59,6 +61,27 @@ impl std::error::Error for Cip68Error {}
 /// [`AssetMetadata68::purpose`] is always [`NftPurpose::ReferenceNft`] —
 /// CIP-68 metadata datums only ever live on the reference token.
 pub fn decode_cip68_datum(datum_cbor: &[u8]) -> Result<AssetMetadata68, Cip68Error> {
+    let (metadata, version) = datum_metadata_json(datum_cbor)?;
+    let metadata: AssetMetadata = serde_json::from_value(metadata).map_err(Cip68Error::Metadata)?;
+
+    Ok(AssetMetadata68 {
+        purpose: NftPurpose::ReferenceNft,
+        version,
+        metadata,
+    })
+}
+
+/// Decode the reference datum of a CIP-68 `333` fungible token into a
+/// [`TokenInfo`]. The datum has the same
+/// `Constr 0 [metadata_map, version, extra?]` shape as NFT datums.
+pub fn decode_cip68_ft_datum(datum_cbor: &[u8]) -> Result<TokenInfo, Cip68Error> {
+    let (metadata, _version) = datum_metadata_json(datum_cbor)?;
+    TokenInfo::from_cip68_metadata(metadata).map_err(Cip68Error::Metadata)
+}
+
+/// Unwrap `Constr 0 [metadata_map, version, extra?]` into the metadata map
+/// rendered as JSON, and the version (defaulting to `1`).
+fn datum_metadata_json(datum_cbor: &[u8]) -> Result<(Value, u32), Cip68Error> {
     let plutus: PlutusData =
         pallas_codec::minicbor::decode(datum_cbor).map_err(|e| Cip68Error::Cbor(e.to_string()))?;
 
@@ -74,14 +97,7 @@ pub fn decode_cip68_datum(datum_cbor: &[u8]) -> Result<AssetMetadata68, Cip68Err
     let metadata_pd = fields.first().ok_or(Cip68Error::EmptyDatum)?;
     let version = fields.get(1).and_then(plutus_as_u32).unwrap_or(1);
 
-    let metadata: AssetMetadata =
-        serde_json::from_value(plutus_to_json(metadata_pd)).map_err(Cip68Error::Metadata)?;
-
-    Ok(AssetMetadata68 {
-        purpose: NftPurpose::ReferenceNft,
-        version,
-        metadata,
-    })
+    Ok((plutus_to_json(metadata_pd), version))
 }
 
 /// Read a `PlutusData` integer as a `u32`, if it fits.
@@ -1,4 +1,7 @@
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+use crate::{AssetId, PolicyId, TxHash};
 
 #[cfg(feature = "openapi")]
 use utoipa::ToSchema;
@@ -56,6 +59,7 @@ impl std::fmt::Display for Marketplace {
 
 /// Collection social media links
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
 pub struct CollectionSocials {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub discord: Option<String>,
@@ -66,22 +70,267 @@ pub struct CollectionSocials {
 }
 
 /// Collection information for a CNFT collection
+///
+/// Accepts both snake_case and the camelCase field names used by marketplace
+/// APIs, and serializes as snake_case:
+///
+/// ```
+/// use cardano_assets::CollectionDetails;
+///
+/// let json = r#"{
+///     "policyId": "b3dab69f7e6100849434fb1781e34bd12a916557f6231b8d2629b6f6",
+///     "name": "Pirates",
+///     "handle": "pirates",
+///     "description": null,
+///     "royaltyAddress": null,
+///     "royaltyPct": 5.0,
+///     "image": "ipfs://QmPirates",
+///     "banner": null,
+///     "socials": { "twitter": "https://x.com/pirates" }
+/// }"#;
+///
+/// let details: CollectionDetails = serde_json::from_str(json).unwrap();
+/// assert_eq!(details.royalty_percentage, 5.0);
+///
+/// let out = serde_json::to_value(&details).unwrap();
+/// assert_eq!(out["policy_id"], "b3dab69f7e6100849434fb1781e34bd12a916557f6231b8d2629b6f6");
+/// assert!(out["socials"].get("discord").is_none());
+/// ```
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
 pub struct CollectionDetails {
     #[serde(alias = "policyId")]
-    pub policy_id: String,
+    pub policy_id: PolicyId,
     pub name: String,
     pub handle: Option<String>,
     pub description: Option<String>,
     #[serde(alias = "royaltyAddress")]
     pub royalty_address: Option<String>,
     #[serde(alias = "royaltyPct")]
+    #[cfg_attr(feature = "serde_compat", serde(alias = "royaltyPercentage"))]
     pub royalty_percentage: f64,
     pub image: Option<String>,
     pub banner: Option<String>,
     pub socials: Option<CollectionSocials>,
 }
 
+/// How a linked collection relates to its parent
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum LinkKind {
+    /// One asset per parent asset, e.g. a parrot for every pirate
+    Companion,
+    /// Derivative art of the parent, not paired asset by asset
+    Derivative,
+    /// Dropped to holders of the parent
+    Airdrop,
+}
+
+/// How a child asset's name follows from its parent's
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+#[serde(tag = "rule", rename_all = "snake_case")]
+pub enum CompanionNaming {
+    /// Assets aren't paired
+    #[default]
+    Unpaired,
+    /// The child has the parent's asset name
+    SameName,
+    /// Numbered names with one `{n}` placeholder each, e.g. `"Pirate{n}"` →
+    /// `"Parrot{n}"`; the digits carry over verbatim, zero padding included
+    Numbered { parent: String, child: String },
+    /// Parent asset name (hex) → child asset name (hex)
+    Explicit { names: BTreeMap<String, String> },
+}
+
+impl CompanionNaming {
+    /// The child asset name (hex) paired with `parent_hex`
+    pub fn child_name_hex(&self, parent_hex: &str) -> Option<String> {
+        match self {
+            Self::Unpaired => None,
+            Self::SameName => Some(parent_hex.to_string()),
+            Self::Numbered { parent, child } => renumber(parent, child, parent_hex),
+            Self::Explicit { names } => names.get(parent_hex).cloned(),
+        }
+    }
+
+    /// The parent asset name (hex) paired with `child_hex`
+    pub fn parent_name_hex(&self, child_hex: &str) -> Option<String> {
+        match self {
+            Self::Unpaired => None,
+            Self::SameName => Some(child_hex.to_string()),
+            Self::Numbered { parent, child } => renumber(child, parent, child_hex),
+            Self::Explicit { names } => names
+                .iter()
+                .find(|(_, child)| child.as_str() == child_hex)
+                .map(|(parent, _)| parent.clone()),
+        }
+    }
+}
+
+/// Move the number in `name_hex`, matched against `from`, into `to`
+fn renumber(from: &str, to: &str, name_hex: &str) -> Option<String> {
+    let name = String::from_utf8(hex::decode(name_hex).ok()?).ok()?;
+    let (prefix, suffix) = from.split_once("{n}")?;
+    let digits = name.strip_prefix(prefix)?.strip_suffix(suffix)?;
+    if digits.is_empty() || !digits.bytes().all(|b| b.is_ascii_digit()) || !to.contains("{n}") {
+        return None;
+    }
+    Some(hex::encode(to.replacen("{n}", digits, 1)))
+}
+
+/// Where an airdropped collection came from
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+pub struct AirdropProvenance {
+    /// Slot the parent holder snapshot was taken at
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub snapshot_slot: Option<u64>,
+    /// Transactions that sent the drop
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tx_hashes: Vec<TxHash>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub note: Option<String>,
+}
+
+/// A parent collection and one collection derived from it
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+pub struct CollectionLink {
+    pub parent: PolicyId,
+    pub child: PolicyId,
+    pub kind: LinkKind,
+    #[serde(default)]
+    pub naming: CompanionNaming,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub airdrop: Option<AirdropProvenance>,
+}
+
+impl CollectionLink {
+    pub fn new(parent: PolicyId, child: PolicyId, kind: LinkKind) -> Self {
+        Self {
+            parent,
+            child,
+            kind,
+            naming: CompanionNaming::default(),
+            airdrop: None,
+        }
+    }
+
+    pub fn with_naming(mut self, naming: CompanionNaming) -> Self {
+        self.naming = naming;
+        self
+    }
+
+    pub fn with_airdrop(mut self, airdrop: AirdropProvenance) -> Self {
+        self.airdrop = Some(airdrop);
+        self
+    }
+
+    /// The child asset paired with `asset`, if it's from the parent policy
+    pub fn child_asset(&self, asset: &AssetId) -> Option<AssetId> {
+        if self.parent != asset.policy_id {
+            return None;
+        }
+        let name = self.naming.child_name_hex(&asset.asset_name_hex)?;
+        Some(AssetId::new_unchecked(self.child.to_string(), name))
+    }
+
+    /// The parent asset paired with `asset`, if it's from the child policy
+    pub fn parent_asset(&self, asset: &AssetId) -> Option<AssetId> {
+        if self.child != asset.policy_id {
+            return None;
+        }
+        let name = self.naming.parent_name_hex(&asset.asset_name_hex)?;
+        Some(AssetId::new_unchecked(self.parent.to_string(), name))
+    }
+}
+
+/// Every known parent → child collection link
+///
+/// ```
+/// use cardano_assets::{AssetId, CollectionLink, CollectionLinks, CompanionNaming, LinkKind, PolicyId};
+///
+/// let pirates = PolicyId::new_unchecked("b3dab69f7e6100849434fb1781e34bd12a916557f6231b8d2629b6f6");
+/// let parrots = PolicyId::new_unchecked("c3dab69f7e6100849434fb1781e34bd12a916557f6231b8d2629b6f6");
+///
+/// let mut links = CollectionLinks::new();
+/// links.add(
+///     CollectionLink::new(pirates.clone(), parrots.clone(), LinkKind::Companion).with_naming(
+///         CompanionNaming::Numbered {
+///             parent: "Pirate{n}".to_string(),
+///             child: "Parrot{n}".to_string(),
+///         },
+///     ),
+/// );
+///
+/// let pirate = AssetId::from_utf8_name(pirates.to_string(), "Pirate84".to_string()).unwrap();
+/// let parrot = links.companion_of(&pirate, &parrots).unwrap();
+/// assert_eq!(parrot.asset_name(), "Parrot84");
+/// assert_eq!(links.parent_asset_of(&parrot), Some(pirate));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+#[serde(transparent)]
+pub struct CollectionLinks {
+    links: Vec<CollectionLink>,
+}
+
+impl CollectionLinks {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add `link`, replacing any existing link between the same two policies
+    pub fn add(&mut self, link: CollectionLink) {
+        self.links
+            .retain(|l| !(l.parent == link.parent && l.child == link.child));
+        self.links.push(link);
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &CollectionLink> {
+        self.links.iter()
+    }
+
+    /// Links from `policy` to collections derived from it
+    pub fn children_of<'a>(
+        &'a self,
+        policy: &'a PolicyId,
+    ) -> impl Iterator<Item = &'a CollectionLink> + 'a {
+        self.links.iter().filter(move |l| &l.parent == policy)
+    }
+
+    /// Links from collections `policy` derives from
+    pub fn parents_of<'a>(
+        &'a self,
+        policy: &'a PolicyId,
+    ) -> impl Iterator<Item = &'a CollectionLink> + 'a {
+        self.links.iter().filter(move |l| &l.child == policy)
+    }
+
+    /// The asset paired with `asset` in the `child` collection
+    pub fn companion_of(&self, asset: &AssetId, child: &PolicyId) -> Option<AssetId> {
+        self.links
+            .iter()
+            .filter(|l| &l.child == child)
+            .find_map(|l| l.child_asset(asset))
+    }
+
+    /// Every asset paired with `asset` across its child collections
+    pub fn companions(&self, asset: &AssetId) -> Vec<AssetId> {
+        self.links
+            .iter()
+            .filter_map(|l| l.child_asset(asset))
+            .collect()
+    }
+
+    /// The parent asset `asset` was paired with
+    pub fn parent_asset_of(&self, asset: &AssetId) -> Option<AssetId> {
+        self.links.iter().find_map(|l| l.parent_asset(asset))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -144,4 +393,88 @@ mod tests {
         assert_eq!(Marketplace::Wayup.to_string(), "wayup");
         assert_eq!(Marketplace::Unknown("foo".to_string()).to_string(), "foo");
     }
+
+    fn policy(c: char) -> PolicyId {
+        PolicyId::new_unchecked(c.to_string().repeat(56))
+    }
+
+    fn asset(policy: &PolicyId, name: &str) -> AssetId {
+        AssetId::new_unchecked(policy.to_string(), hex::encode(name))
+    }
+
+    #[test]
+    fn test_companion_naming() {
+        let numbered = CompanionNaming::Numbered {
+            parent: "Pirate #{n}".to_string(),
+            child: "Parrot{n}".to_string(),
+        };
+        assert_eq!(
+            numbered.child_name_hex(&hex::encode("Pirate #0084")),
+            Some(hex::encode("Parrot0084"))
+        );
+        assert_eq!(
+            numbered.parent_name_hex(&hex::encode("Parrot0084")),
+            Some(hex::encode("Pirate #0084"))
+        );
+        assert_eq!(numbered.child_name_hex(&hex::encode("Pirate #")), None);
+        assert_eq!(numbered.child_name_hex(&hex::encode("Pirate #8a")), None);
+        assert_eq!(numbered.child_name_hex(&hex::encode("Captain84")), None);
+
+        let explicit = CompanionNaming::Explicit {
+            names: BTreeMap::from([("aa".to_string(), "bb".to_string())]),
+        };
+        assert_eq!(explicit.child_name_hex("aa").as_deref(), Some("bb"));
+        assert_eq!(explicit.parent_name_hex("bb").as_deref(), Some("aa"));
+        assert_eq!(CompanionNaming::Unpaired.child_name_hex("aa"), None);
+    }
+
+    #[test]
+    fn test_collection_links() {
+        let (pirates, parrots, maps, art) = (policy('a'), policy('b'), policy('c'), policy('d'));
+        let mut links = CollectionLinks::new();
+        links.add(CollectionLink::new(
+            pirates.clone(),
+            parrots.clone(),
+            LinkKind::Companion,
+        ));
+        links.add(
+            CollectionLink::new(pirates.clone(), parrots.clone(), LinkKind::Companion)
+                .with_naming(CompanionNaming::SameName),
+        );
+        links.add(
+            CollectionLink::new(pirates.clone(), maps.clone(), LinkKind::Airdrop).with_airdrop(
+                AirdropProvenance {
+                    snapshot_slot: Some(120_000_000),
+                    ..Default::default()
+                },
+            ),
+        );
+        links.add(CollectionLink::new(
+            pirates.clone(),
+            art.clone(),
+            LinkKind::Derivative,
+        ));
+
+        // Re-adding a link replaces it
+        assert_eq!(links.iter().count(), 3);
+        assert_eq!(links.children_of(&pirates).count(), 3);
+        assert_eq!(
+            links.parents_of(&maps).next().unwrap().kind,
+            LinkKind::Airdrop
+        );
+
+        let pirate = asset(&pirates, "Pirate84");
+        let parrot = asset(&parrots, "Pirate84");
+        assert_eq!(links.companion_of(&pirate, &parrots), Some(parrot.clone()));
+        assert_eq!(links.companion_of(&pirate, &maps), None);
+        assert_eq!(links.companions(&pirate), [parrot.clone()]);
+        assert_eq!(links.parent_asset_of(&parrot), Some(pirate));
+        assert_eq!(links.parent_asset_of(&asset(&art, "Pirate84")), None);
+
+        let json = serde_json::to_string(&links).unwrap();
+        assert_eq!(
+            serde_json::from_str::<CollectionLinks>(&json).unwrap(),
+            links
+        );
+    }
 }
@@ -0,0 +1,164 @@
+//! Stable content hashes for [`Asset`] and [`AssetV2`].
+//!
+//! Two workers holding the same asset should agree on whether it changed
+//! without shipping the whole structure around. `content_hash()` is the hex
+//! Blake2b-256 of the asset's [`canonical_json`]: its usual serialization
+//! with object keys sorted, no whitespace, and each trait's values sorted
+//! (the order values arrive in from metadata carries no meaning).
+//!
+//! Everything that serializes is covered, including `rarity_rank` and
+//! `tags`, so a re-rank or a listing also changes the hash.
+
+use blake2::digest::{Update, VariableOutput};
+use blake2::Blake2bVar;
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::{Asset, AssetV2};
+
+/// Compact JSON of `value` with object keys sorted at every level
+pub fn canonical_json(value: &Value) -> String {
+    let mut out = String::new();
+    write_canonical(value, &mut out);
+    out
+}
+
+fn write_canonical(value: &Value, out: &mut String) {
+    match value {
+        Value::Object(fields) => {
+            let mut keys: Vec<&String> = fields.keys().collect();
+            keys.sort();
+            out.push('{');
+            for (i, key) in keys.into_iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                out.push_str(&Value::String(key.clone()).to_string());
+                out.push(':');
+                write_canonical(&fields[key], out);
+            }
+            out.push('}');
+        }
+        Value::Array(items) => {
+            out.push('[');
+            for (i, item) in items.iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                write_canonical(item, out);
+            }
+            out.push(']');
+        }
+        scalar => out.push_str(&scalar.to_string()),
+    }
+}
+
+fn hash_with_sorted_traits(asset: &impl Serialize) -> String {
+    let mut value = serde_json::to_value(asset).expect("assets serialize to JSON");
+    if let Some(Value::Object(traits)) = value.get_mut("traits") {
+        for values in traits.values_mut() {
+            if let Value::Array(values) = values {
+                values.sort_by(|a, b| a.as_str().cmp(&b.as_str()));
+            }
+        }
+    }
+
+    let mut hasher = Blake2bVar::new(32).expect("valid output size");
+    hasher.update(canonical_json(&value).as_bytes());
+    let mut out = [0u8; 32];
+    hasher
+        .finalize_variable(&mut out)
+        .expect("output buffer matches");
+    hex::encode(out)
+}
+
+impl Asset {
+    /// Hex Blake2b-256 of this asset's canonical JSON
+    #[must_use]
+    pub fn content_hash(&self) -> String {
+        hash_with_sorted_traits(self)
+    }
+}
+
+impl AssetV2 {
+    /// Hex Blake2b-256 of this asset's canonical JSON
+    #[must_use]
+    pub fn content_hash(&self) -> String {
+        hash_with_sorted_traits(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Traits;
+    use serde_json::json;
+    use std::collections::HashMap;
+
+    fn asset(traits: &[(&str, &[&str])]) -> Asset {
+        Asset {
+            name: "Pirate #376".to_string(),
+            image: "ipfs://QmSfqtMhjqeU6cncYWpMXcoQQVrzxsaap2SgRzmhkvXZC9".to_string(),
+            media_type: Some("image/png".to_string()),
+            traits: Traits::from_map(
+                traits
+                    .iter()
+                    .map(|(k, v)| (k.to_string(), v.iter().map(|s| s.to_string()).collect()))
+                    .collect::<HashMap<_, _>>(),
+            ),
+            rarity_rank: Some(59),
+            quantity: 1,
+            tags: vec![],
+        }
+    }
+
+    #[test]
+    fn test_canonical_json_sorts_keys() {
+        let value = json!({ "b": [{ "z": 1, "a": null }], "a": "x\"y" });
+        assert_eq!(
+            canonical_json(&value),
+            r#"{"a":"x\"y","b":[{"a":null,"z":1}]}"#
+        );
+    }
+
+    #[test]
+    fn test_content_hash_ignores_order() {
+        let a = asset(&[
+            ("Eyes", &["Focus"]),
+            ("Tattoos", &["Anchor", "Rose"]),
+            ("Skin", &["Inked"]),
+        ]);
+        let b = asset(&[
+            ("Skin", &["Inked"]),
+            ("Tattoos", &["Rose", "Anchor"]),
+            ("Eyes", &["Focus"]),
+        ]);
+        assert_eq!(a.content_hash(), b.content_hash());
+        assert_eq!(a.content_hash().len(), 64);
+
+        let c = asset(&[
+            ("Eyes", &["Focus"]),
+            ("Tattoos", &["Anchor"]),
+            ("Skin", &["Inked"]),
+        ]);
+        assert_ne!(a.content_hash(), c.content_hash());
+
+        let mut reranked = a.clone();
+        reranked.rarity_rank = Some(60);
+        assert_ne!(a.content_hash(), reranked.content_hash());
+    }
+
+    #[test]
+    fn test_content_hash_is_pinned() {
+        // Changing this value invalidates every stored hash
+        let a = asset(&[("Eyes", &["Focus"])]);
+        assert_eq!(
+            canonical_json(&serde_json::to_value(&a).unwrap()),
+            r#"{"image":"ipfs://QmSfqtMhjqeU6cncYWpMXcoQQVrzxsaap2SgRzmhkvXZC9","media_type":"image/png","name":"Pirate #376","rarity_rank":59,"traits":{"Eyes":["Focus"]}}"#
+        );
+        assert_eq!(
+            a.content_hash(),
+            "41c99fae00f7087ca40fdd3b96ccf450b1be6fe52d66d883b11cf4041f1ff5a7"
+        );
+    }
+}
@@ -178,6 +178,7 @@ impl AssetEnvelope {
             media_type,
             traits,
             rarity_rank: None,
+            quantity: 1,
             tags: vec![],
         }
     }
@@ -0,0 +1,471 @@
+//! Trait-based image layering — which art layer each trait value maps to.
+//!
+//! Derivative and companion drops generate images by stacking one layer per
+//! trait value. A [`LayerComposition`] is the descriptor shared by the art
+//! pipeline and the metadata side: layers are keyed by trait name/value and
+//! carry a z-index and blend rule. Rendering is out of scope; this module only
+//! resolves and checks the stack.
+//!
+//! Ordering is deterministic: every trait owns exactly one z-index, distinct
+//! traits never share one, and [`LayerComposition::layers_for`] sorts by it.
+//! [`LayerComposition::validate`] checks those rules, that every layer names
+//! a trait and value of a [`TraitSummary`], and that every value of a layered
+//! trait without a fallback has a layer.
+//!
+//! [`TraitSummary`]: crate::TraitSummary
+
+use crate::{TraitSummary, TraitSummarySorted, Traits};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+
+#[cfg(feature = "openapi")]
+use utoipa::ToSchema;
+
+/// How a layer is composited onto the layers below it
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum BlendMode {
+    /// Source-over alpha compositing
+    #[default]
+    Normal,
+    Multiply,
+    Screen,
+    Overlay,
+}
+
+/// One image layer, selected by a trait value
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+pub struct Layer {
+    /// Trait name this layer belongs to (e.g. `"Background"`)
+    pub trait_name: String,
+    /// Trait value that selects this layer; `None` is the trait's fallback,
+    /// used for any value without its own layer
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub value: Option<String>,
+    /// Stacking order, lowest drawn first
+    pub z_index: i32,
+    #[serde(default)]
+    pub blend: BlendMode,
+    /// Opacity in percent (0-100)
+    #[serde(default = "default_opacity")]
+    pub opacity: u8,
+    /// Image source for the art pipeline (path, URL or CID)
+    pub source: String,
+}
+
+fn default_opacity() -> u8 {
+    100
+}
+
+impl Layer {
+    /// Opaque, normally blended layer for a single trait value
+    #[must_use]
+    pub fn new(
+        trait_name: impl Into<String>,
+        value: impl Into<String>,
+        z_index: i32,
+        source: impl Into<String>,
+    ) -> Self {
+        Self {
+            trait_name: trait_name.into(),
+            value: Some(value.into()),
+            z_index,
+            blend: BlendMode::Normal,
+            opacity: 100,
+            source: source.into(),
+        }
+    }
+
+    /// Fallback layer for every value of `trait_name` without its own layer
+    #[must_use]
+    pub fn fallback(
+        trait_name: impl Into<String>,
+        z_index: i32,
+        source: impl Into<String>,
+    ) -> Self {
+        Self {
+            value: None,
+            ..Self::new(trait_name, String::new(), z_index, source)
+        }
+    }
+
+    #[must_use]
+    pub fn with_blend(mut self, blend: BlendMode) -> Self {
+        self.blend = blend;
+        self
+    }
+
+    #[must_use]
+    pub fn with_opacity(mut self, opacity: u8) -> Self {
+        self.opacity = opacity;
+        self
+    }
+}
+
+/// Ordered layer descriptor for a generated collection
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+pub struct LayerComposition {
+    pub layers: Vec<Layer>,
+}
+
+/// A problem found by [`LayerComposition::validate`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LayerIssue {
+    /// Two layers share the same trait name and value
+    DuplicateLayer {
+        trait_name: String,
+        value: Option<String>,
+    },
+    /// A trait's layers use more than one z-index
+    InconsistentZIndex { trait_name: String },
+    /// Two traits share a z-index, so their order is ambiguous
+    ZIndexConflict { z_index: i32, traits: Vec<String> },
+    /// Opacity above 100
+    InvalidOpacity { trait_name: String, opacity: u8 },
+    /// A layer references a trait the collection doesn't have
+    UnknownTrait { trait_name: String },
+    /// A layer references a value the collection doesn't have
+    UnknownValue { trait_name: String, value: String },
+    /// A value in the collection has no layer (and its trait no fallback)
+    MissingLayer { trait_name: String, value: String },
+}
+
+impl fmt::Display for LayerIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LayerIssue::DuplicateLayer { trait_name, value } => match value {
+                Some(value) => write!(f, "Duplicate layer for {trait_name}={value}"),
+                None => write!(f, "Duplicate fallback layer for {trait_name}"),
+            },
+            LayerIssue::InconsistentZIndex { trait_name } => {
+                write!(f, "Layers for {trait_name} use more than one z-index")
+            }
+            LayerIssue::ZIndexConflict { z_index, traits } => {
+                write!(f, "z-index {z_index} is shared by {}", traits.join(", "))
+            }
+            LayerIssue::InvalidOpacity {
+                trait_name,
+                opacity,
+            } => write!(f, "Layer for {trait_name} has opacity {opacity} (max 100)"),
+            LayerIssue::UnknownTrait { trait_name } => {
+                write!(f, "Trait {trait_name} is not in the collection")
+            }
+            LayerIssue::UnknownValue { trait_name, value } => {
+                write!(f, "Value {trait_name}={value} is not in the collection")
+            }
+            LayerIssue::MissingLayer { trait_name, value } => {
+                write!(f, "No layer for {trait_name}={value}")
+            }
+        }
+    }
+}
+
+impl LayerComposition {
+    #[must_use]
+    pub fn new(layers: Vec<Layer>) -> Self {
+        Self { layers }
+    }
+
+    /// Layers to stack for an asset, bottom first
+    ///
+    /// Each trait value picks its exact layer, falling back to the trait's
+    /// fallback layer. Traits with no layers (or values with neither) are
+    /// skipped — [`validate`](Self::validate) is what catches gaps.
+    #[must_use]
+    pub fn layers_for(&self, traits: &Traits) -> Vec<&Layer> {
+        let mut selected: Vec<&Layer> = Vec::new();
+
+        for (trait_name, values) in traits.iter() {
+            let fallback = self
+                .layers
+                .iter()
+                .find(|l| &l.trait_name == trait_name && l.value.is_none());
+
+            for value in values {
+                let exact = self
+                    .layers
+                    .iter()
+                    .find(|l| &l.trait_name == trait_name && l.value.as_ref() == Some(value));
+
+                if let Some(layer) = exact.or(fallback) {
+                    if !selected.contains(&layer) {
+                        selected.push(layer);
+                    }
+                }
+            }
+        }
+
+        // Trait iteration order is unspecified; ties within a trait (multi
+        // values) fall back to declaration order
+        selected.sort_by_key(|l| {
+            let position = self
+                .layers
+                .iter()
+                .position(|x| std::ptr::eq(x, *l))
+                .unwrap_or(usize::MAX);
+            (l.z_index, position)
+        });
+        selected
+    }
+
+    /// Check the composition against a collection's trait summary
+    ///
+    /// Beyond the structural rules, only layered traits are compared: a
+    /// summary trait with no layers at all is not reported, and a trait with
+    /// a fallback layer isn't checked for values lacking their own layer.
+    pub fn validate(&self, summary: &TraitSummary) -> Result<(), Vec<LayerIssue>> {
+        let known = summary
+            .traits
+            .iter()
+            .map(|(name, values)| (name.as_str(), values.keys().map(String::as_str).collect()))
+            .collect();
+        self.validate_against(&known)
+    }
+
+    /// [`validate`](Self::validate) against a stored [`TraitSummarySorted`]
+    pub fn validate_sorted(&self, summary: &TraitSummarySorted) -> Result<(), Vec<LayerIssue>> {
+        let known = summary
+            .traits
+            .iter()
+            .map(|(name, values)| {
+                (
+                    name.as_str(),
+                    values.iter().map(|v| v.value.as_str()).collect(),
+                )
+            })
+            .collect();
+        self.validate_against(&known)
+    }
+
+    fn validate_against(
+        &self,
+        known: &BTreeMap<&str, BTreeSet<&str>>,
+    ) -> Result<(), Vec<LayerIssue>> {
+        let mut issues = Vec::new();
+        self.check_structure(&mut issues);
+
+        let mut layered: BTreeMap<&str, (bool, BTreeSet<&str>)> = BTreeMap::new();
+        for layer in &self.layers {
+            let entry = layered.entry(layer.trait_name.as_str()).or_default();
+            match &layer.value {
+                Some(value) => {
+                    entry.1.insert(value.as_str());
+                }
+                None => entry.0 = true,
+            }
+        }
+
+        for (trait_name, (has_fallback, values)) in &layered {
+            let Some(known_values) = known.get(trait_name) else {
+                issues.push(LayerIssue::UnknownTrait {
+                    trait_name: trait_name.to_string(),
+                });
+                continue;
+            };
+
+            for value in values.difference(known_values) {
+                issues.push(LayerIssue::UnknownValue {
+                    trait_name: trait_name.to_string(),
+                    value: value.to_string(),
+                });
+            }
+
+            if !has_fallback {
+                for value in known_values.difference(values) {
+                    issues.push(LayerIssue::MissingLayer {
+                        trait_name: trait_name.to_string(),
+                        value: value.to_string(),
+                    });
+                }
+            }
+        }
+
+        if issues.is_empty() {
+            Ok(())
+        } else {
+            Err(issues)
+        }
+    }
+
+    /// Duplicate layers, z-index rules and opacity range
+    fn check_structure(&self, issues: &mut Vec<LayerIssue>) {
+        let mut seen = BTreeSet::new();
+        let mut z_by_trait: BTreeMap<&str, BTreeSet<i32>> = BTreeMap::new();
+
+        for layer in &self.layers {
+            if !seen.insert((layer.trait_name.as_str(), layer.value.as_deref())) {
+                issues.push(LayerIssue::DuplicateLayer {
+                    trait_name: layer.trait_name.clone(),
+                    value: layer.value.clone(),
+                });
+            }
+            if layer.opacity > 100 {
+                issues.push(LayerIssue::InvalidOpacity {
+                    trait_name: layer.trait_name.clone(),
+                    opacity: layer.opacity,
+                });
+            }
+            z_by_trait
+                .entry(layer.trait_name.as_str())
+                .or_default()
+                .insert(layer.z_index);
+        }
+
+        let mut traits_by_z: BTreeMap<i32, Vec<String>> = BTreeMap::new();
+        for (trait_name, z_indexes) in &z_by_trait {
+            if z_indexes.len() > 1 {
+                issues.push(LayerIssue::InconsistentZIndex {
+                    trait_name: trait_name.to_string(),
+                });
+            }
+            for z_index in z_indexes {
+                traits_by_z
+                    .entry(*z_index)
+                    .or_default()
+                    .push(trait_name.to_string());
+            }
+        }
+
+        for (z_index, traits) in traits_by_z {
+            if traits.len() > 1 {
+                issues.push(LayerIssue::ZIndexConflict { z_index, traits });
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Asset;
+
+    fn asset(traits: &[(&str, &str)]) -> Asset {
+        let mut asset_traits = Traits::new();
+        for (name, value) in traits {
+            asset_traits.insert_single(name.to_string(), value.to_string());
+        }
+        Asset {
+            name: "Test".to_string(),
+            image: String::new(),
+            media_type: None,
+            traits: asset_traits,
+            rarity_rank: None,
+            quantity: 1,
+            tags: vec![],
+        }
+    }
+
+    fn summary() -> TraitSummary {
+        let mut summary = TraitSummary::default();
+        summary.add_asset(&asset(&[("Background", "Blue"), ("Hat", "Tricorn")]));
+        summary.add_asset(&asset(&[("Background", "Red"), ("Hat", "Bandana")]));
+        summary
+    }
+
+    fn composition() -> LayerComposition {
+        LayerComposition::new(vec![
+            Layer::new("Hat", "Tricorn", 10, "hat/tricorn.png"),
+            Layer::new("Hat", "Bandana", 10, "hat/bandana.png").with_blend(BlendMode::Multiply),
+            Layer::fallback("Background", 0, "bg/plain.png"),
+            Layer::new("Background", "Red", 0, "bg/red.png"),
+        ])
+    }
+
+    #[test]
+    fn test_layers_for_orders_by_z_index() {
+        let composition = composition();
+
+        let layers =
+            composition.layers_for(&asset(&[("Hat", "Tricorn"), ("Background", "Red")]).traits);
+        let sources: Vec<_> = layers.iter().map(|l| l.source.as_str()).collect();
+        assert_eq!(sources, vec!["bg/red.png", "hat/tricorn.png"]);
+
+        // Blue has no layer of its own and uses the fallback
+        let layers = composition.layers_for(&asset(&[("Background", "Blue")]).traits);
+        assert_eq!(layers[0].source, "bg/plain.png");
+
+        // Traits without layers are ignored
+        let layers = composition.layers_for(&asset(&[("Eyes", "Laser")]).traits);
+        assert!(layers.is_empty());
+    }
+
+    #[test]
+    fn test_validate_matching_composition() {
+        assert_eq!(composition().validate(&summary()), Ok(()));
+        assert_eq!(
+            composition().validate_sorted(&TraitSummarySorted::from(summary())),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn test_validate_reports_drift() {
+        let composition = LayerComposition::new(vec![
+            Layer::new("Hat", "Tricorn", 10, "a.png"),
+            Layer::new("Hat", "Crown", 10, "b.png"),
+            Layer::new("Eyes", "Laser", 20, "c.png"),
+        ]);
+
+        let issues = composition.validate(&summary()).unwrap_err();
+        assert_eq!(
+            issues,
+            vec![
+                LayerIssue::UnknownTrait {
+                    trait_name: "Eyes".into()
+                },
+                LayerIssue::UnknownValue {
+                    trait_name: "Hat".into(),
+                    value: "Crown".into()
+                },
+                LayerIssue::MissingLayer {
+                    trait_name: "Hat".into(),
+                    value: "Bandana".into()
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_validate_structure() {
+        let composition = LayerComposition::new(vec![
+            Layer::new("Background", "Blue", 0, "a.png"),
+            Layer::new("Background", "Blue", 0, "b.png"),
+            Layer::new("Background", "Red", 5, "c.png"),
+            Layer::fallback("Hat", 5, "d.png").with_opacity(120),
+        ]);
+
+        let issues = composition.validate(&summary()).unwrap_err();
+        assert!(issues.contains(&LayerIssue::DuplicateLayer {
+            trait_name: "Background".into(),
+            value: Some("Blue".into()),
+        }));
+        assert!(issues.contains(&LayerIssue::InconsistentZIndex {
+            trait_name: "Background".into()
+        }));
+        assert!(issues.contains(&LayerIssue::ZIndexConflict {
+            z_index: 5,
+            traits: vec!["Background".into(), "Hat".into()],
+        }));
+        assert!(issues.contains(&LayerIssue::InvalidOpacity {
+            trait_name: "Hat".into(),
+            opacity: 120
+        }));
+    }
+
+    #[test]
+    fn test_serde_defaults() {
+        let layer: Layer = serde_json::from_str(
+            r#"{"trait_name":"Hat","value":"Tricorn","z_index":3,"source":"hat.png"}"#,
+        )
+        .unwrap();
+        assert_eq!(layer.blend, BlendMode::Normal);
+        assert_eq!(layer.opacity, 100);
+
+        let fallback = serde_json::to_value(Layer::fallback("Hat", 3, "hat.png")).unwrap();
+        assert!(fallback.get("value").is_none());
+    }
+}
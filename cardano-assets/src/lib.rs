@@ -16,14 +16,32 @@ pub mod cip25;
 #[cfg(feature = "cip68")]
 pub mod cip68;
 pub mod collection;
+#[cfg(feature = "content-hash")]
+pub mod content_hash;
 pub mod extract;
 #[cfg(feature = "cip14")]
 pub mod fingerprint;
+pub mod layering;
+pub mod marketplace;
+pub mod network;
+pub mod normalize;
+pub mod overrides;
 pub mod policy_id;
+pub mod provenance;
+pub mod raffle;
+pub mod raw;
 pub mod resolver;
+#[cfg(feature = "serde_compat")]
+pub mod serde_compat;
+#[cfg(feature = "snapshot")]
+pub mod snapshot;
+pub mod socials;
+pub mod summary;
 pub mod supply;
 #[cfg(feature = "tag-datum")]
 pub mod tag_datum;
+pub mod timeline;
+pub mod token_info;
 pub mod token_type;
 pub mod traits;
 pub mod tx_hash;
@@ -31,6 +49,7 @@ pub mod utxo;
 
 #[cfg(feature = "tag-datum")]
 pub use tag_datum::UtxoTagDatum;
+pub use token_info::{RegistryEntry, RegistryProperty, TokenInfo, TokenInfoSource};
 pub use token_type::TokenType;
 
 #[cfg(feature = "utxorpc")]
@@ -41,7 +60,7 @@ pub use cid::{cid_v0_to_v1, normalize_cid, CidRole, ExtractedCid};
 #[cfg(feature = "cip25")]
 pub use cip25::{cip25_metadata_json, cip25_metadata_value, decode_cip25_metadata};
 #[cfg(feature = "cip68")]
-pub use cip68::{decode_cip68_datum, Cip68Error};
+pub use cip68::{decode_cip68_datum, decode_cip68_ft_datum, Cip68Error};
 pub use collection::*;
 pub use extract::{
     asset_from_metadata_json, asset_from_metadata_value, extract_traits, AssetEnvelope,
@@ -49,9 +68,24 @@ pub use extract::{
 };
 #[cfg(feature = "cip14")]
 pub use fingerprint::{Fingerprint, FingerprintError};
+pub use layering::{BlendMode, Layer, LayerComposition, LayerIssue};
+pub use marketplace::{marketplace_urls, MarketplaceUrls};
+pub use network::{Network, NetworkError};
+pub use normalize::{CaseStyle, MergedTraitValue, NormalizationReport, TraitNormalization};
+pub use overrides::{AssetOverride, Overrides, OVERRIDES_KV_PREFIX};
 pub use policy_id::{PolicyId, PolicyIdError};
+pub use provenance::{Holding, OwnershipEvent, OwnershipKind, Provenance, SLOTS_PER_DAY};
+pub use raffle::{RaffleHolding, RaffleWeights, RarityBonus, WeightEntry, WeightTable};
 pub use resolver::*;
-pub use supply::MintSupply;
+#[cfg(feature = "snapshot")]
+pub use snapshot::{
+    ChunkInfo, CollectionSnapshot, RankEntry, SnapshotError, SnapshotExport, SnapshotHeader,
+    SnapshotManifest, SnapshotReader, SnapshotWriter,
+};
+pub use socials::Socials;
+pub use summary::TraitSummaryError;
+pub use supply::{AssetSupply, MintEvent, MintSupply, PolicySupply, SupplyChange, SupplyLedger};
+pub use timeline::{EpochPoint, EpochTimeline, TraitMintSpan};
 pub use traits::*;
 pub use tx_hash::*;
 pub use utxo::*;
@@ -316,6 +350,14 @@ pub enum AssetMetadata {
         #[serde(flatten)]
         extra: HashMap<String, serde_json::Value>,
     },
+    // Any other metadata object, kept as-is so the asset isn't dropped;
+    // see the `raw` module for the best-effort `Asset` conversion.
+    // Must stay last — untagged variants are tried in order.
+    Raw {
+        #[serde(flatten)]
+        #[cfg_attr(feature = "openapi", schema(value_type = HashMap<String, serde_json::Value>))]
+        value: serde_json::Value,
+    },
 }
 
 /// Fungible token metadata keys — if any of these appear in the metadata,
@@ -339,6 +381,7 @@ impl AssetMetadata {
             AssetMetadata::Untitled { extra, .. } => {
                 FT_SIGNAL_KEYS.iter().any(|k| extra.contains_key(*k))
             }
+            AssetMetadata::Raw { value } => FT_SIGNAL_KEYS.iter().any(|k| value.get(k).is_some()),
             // Other variants are structured enough that they wouldn't contain
             // these stray fields — they're definitively NFTs.
             _ => false,
@@ -639,14 +682,40 @@ impl<'de> Deserialize<'de> for AssetTag {
 pub struct Asset {
     pub name: String,
     pub image: String,
+    #[cfg_attr(feature = "serde_compat", serde(alias = "mediaType"))]
     pub media_type: Option<String>,
     pub traits: Traits,
     #[serde(skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "serde_compat", serde(alias = "rarityRank"))]
     pub rarity_rank: Option<u32>,
+    /// Units minted under this asset id; above 1 for semi-fungibles.
+    /// Metadata doesn't say, so conversions from it assume 1.
+    #[serde(
+        default = "single_quantity",
+        skip_serializing_if = "is_single_quantity"
+    )]
+    pub quantity: u64,
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub tags: Vec<AssetTag>,
 }
 
+fn single_quantity() -> u64 {
+    1
+}
+
+fn is_single_quantity(quantity: &u64) -> bool {
+    *quantity == 1
+}
+
+impl Asset {
+    /// Set the units minted, e.g. from an indexer's total supply
+    #[must_use]
+    pub fn with_quantity(mut self, quantity: u64) -> Self {
+        self.quantity = quantity;
+        self
+    }
+}
+
 /// Asset with explicit ID - enhanced version for marketplace and API operations
 #[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
 #[cfg_attr(feature = "openapi", derive(ToSchema))]
@@ -657,12 +726,20 @@ pub struct AssetV2 {
     pub name: String,
     /// Asset image URL
     pub image: String,
+    #[cfg_attr(feature = "serde_compat", serde(alias = "mediaType"))]
     pub media_type: Option<String>,
     /// Asset traits/attributes
     pub traits: Traits,
     /// Rarity rank if available
     #[serde(skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "serde_compat", serde(alias = "rarityRank"))]
     pub rarity_rank: Option<u32>,
+    /// Units minted under this asset id; see [`Asset::quantity`]
+    #[serde(
+        default = "single_quantity",
+        skip_serializing_if = "is_single_quantity"
+    )]
+    pub quantity: u64,
     /// Asset tags (rarity, on_sale, etc.)
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub tags: Vec<AssetTag>,
@@ -676,6 +753,7 @@ impl From<AssetV2> for Asset {
             media_type: asset_v2.media_type,
             traits: asset_v2.traits,
             rarity_rank: asset_v2.rarity_rank,
+            quantity: asset_v2.quantity,
             tags: asset_v2.tags,
         }
     }
@@ -691,11 +769,12 @@ impl AssetV2 {
             media_type: asset.media_type,
             traits: asset.traits,
             rarity_rank: asset.rarity_rank,
+            quantity: asset.quantity,
             tags: asset.tags,
         }
     }
 
-    /// Create a new AssetV2 with all fields
+    /// Create a new AssetV2 of a single unit; see [`Self::with_quantity`]
     pub fn new(
         id: AssetId,
         name: String,
@@ -712,9 +791,17 @@ impl AssetV2 {
             media_type,
             traits,
             rarity_rank,
+            quantity: 1,
             tags,
         }
     }
+
+    /// Set the units minted; see [`Asset::with_quantity`]
+    #[must_use]
+    pub fn with_quantity(mut self, quantity: u64) -> Self {
+        self.quantity = quantity;
+        self
+    }
 }
 
 impl AssetMetadata {
@@ -786,6 +873,7 @@ impl AssetMetadata {
 
                 None
             }
+            AssetMetadata::Raw { value } => raw::media_type(value),
         }
     }
 }
@@ -812,6 +900,7 @@ impl From<AssetMetadata> for Asset {
                     media_type: extracted_media_type,
                     traits: merged_traits,
                     rarity_rank: None,
+                    quantity: 1,
                     tags: vec![],
                 }
             }
@@ -826,6 +915,7 @@ impl From<AssetMetadata> for Asset {
                 media_type: extracted_media_type,
                 traits,
                 rarity_rank: None,
+                quantity: 1,
                 tags: vec![],
             },
             AssetMetadata::ColonDelimitedAttributes {
@@ -861,6 +951,7 @@ impl From<AssetMetadata> for Asset {
                     media_type: extracted_media_type,
                     traits,
                     rarity_rank: None,
+                    quantity: 1,
                     tags: vec![],
                 }
             }
@@ -883,6 +974,7 @@ impl From<AssetMetadata> for Asset {
                     media_type: extracted_media_type,
                     traits,
                     rarity_rank: None,
+                    quantity: 1,
                     tags: vec![],
                 }
             }
@@ -903,6 +995,7 @@ impl From<AssetMetadata> for Asset {
                     media_type: extracted_media_type,
                     traits,
                     rarity_rank: None,
+                    quantity: 1,
                     tags: vec![],
                 }
             }
@@ -921,6 +1014,7 @@ impl From<AssetMetadata> for Asset {
                     media_type: extracted_media_type,
                     traits,
                     rarity_rank: None,
+                    quantity: 1,
                     tags: vec![],
                 }
             }
@@ -943,6 +1037,7 @@ impl From<AssetMetadata> for Asset {
                     media_type: extracted_media_type,
                     traits,
                     rarity_rank: None,
+                    quantity: 1,
                     tags: vec![],
                 }
             }
@@ -954,8 +1049,10 @@ impl From<AssetMetadata> for Asset {
                 media_type: extracted_media_type,
                 traits: Traits::new(),
                 rarity_rank: None,
+                quantity: 1,
                 tags: vec![],
             },
+            AssetMetadata::Raw { value } => raw::asset_from_raw(value, extracted_media_type),
         }
     }
 }
@@ -1116,28 +1213,136 @@ impl AssetWithId {
 pub struct TraitSummary {
     /// Map of trait name → (value → occurrence count)
     traits: HashMap<String, HashMap<String, u32>>,
-    /// Total number of assets processed
+    /// Total number of assets processed, or units for
+    /// [`QuantityWeighting::PerUnit`]
     count: u32,
+    /// Counts before [`Overrides`] were applied, once any asset was added
+    /// with [`TraitSummary::add_asset_with_overrides`]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    original_traits: Option<HashMap<String, HashMap<String, u32>>>,
+    /// Number of assets changed by overrides
+    #[serde(skip_serializing_if = "is_zero")]
+    overridden: u32,
+}
+
+fn is_zero(n: &u32) -> bool {
+    *n == 0
+}
+
+fn count_traits(counts: &mut HashMap<String, HashMap<String, u32>>, traits: &Traits, weight: u32) {
+    for (trait_name, trait_values) in traits {
+        let counter = counts.entry(trait_name.clone()).or_default();
+        for val in trait_values {
+            let count = counter.entry(val.clone()).or_insert(0);
+            *count = count.saturating_add(weight);
+        }
+    }
+}
+
+/// How semi-fungible assets (quantity above 1) are counted in a
+/// [`TraitSummary`], and so in rarity estimated from it
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum QuantityWeighting {
+    /// One count per asset id, whatever its quantity
+    #[default]
+    PerAsset,
+    /// One count per unit minted, so a 500-edition asset weighs 500
+    PerUnit,
+}
+
+impl QuantityWeighting {
+    /// Count `asset` contributes
+    #[must_use]
+    pub fn weight(&self, asset: &Asset) -> u32 {
+        match self {
+            Self::PerAsset => 1,
+            Self::PerUnit => u32::try_from(asset.quantity).unwrap_or(u32::MAX),
+        }
+    }
 }
 
 impl TraitSummary {
     /// Adds an asset's traits to the summary, handling both single and multi-valued traits.
+    ///
+    /// Counts the asset once regardless of its quantity; see
+    /// [`add_asset_weighted`](Self::add_asset_weighted).
     pub fn add_asset(&mut self, asset: &Asset) {
-        for (trait_name, trait_values) in &asset.traits {
-            // Ensure an entry for this trait name
-            let counter = self.traits.entry(trait_name.clone()).or_default();
-
-            // trait_values is now Vec<String>, so iterate over all values
-            for val in trait_values {
-                counter
-                    .entry(val.clone())
-                    .and_modify(|c| *c += 1)
-                    .or_insert(1);
-            }
+        self.add_asset_weighted(asset, QuantityWeighting::PerAsset);
+    }
+
+    /// Adds an asset's traits, counted per `weighting`
+    pub fn add_asset_weighted(&mut self, asset: &Asset, weighting: QuantityWeighting) {
+        let weight = weighting.weight(asset);
+        count_traits(&mut self.traits, &asset.traits, weight);
+        if let Some(original) = &mut self.original_traits {
+            count_traits(original, &asset.traits, weight);
         }
 
         // Increment the total asset count
-        self.count += 1;
+        self.count = self.count.saturating_add(weight);
+    }
+
+    /// Adds an asset's traits as corrected by `overrides`, keeping the
+    /// uncorrected counts alongside for comparison.
+    pub fn add_asset_with_overrides(
+        &mut self,
+        asset: &Asset,
+        asset_name_hex: &str,
+        overrides: &Overrides,
+    ) {
+        self.add_asset_with_overrides_weighted(
+            asset,
+            asset_name_hex,
+            overrides,
+            QuantityWeighting::PerAsset,
+        );
+    }
+
+    /// [`add_asset_with_overrides`](Self::add_asset_with_overrides), counted
+    /// per `weighting`
+    pub fn add_asset_with_overrides_weighted(
+        &mut self,
+        asset: &Asset,
+        asset_name_hex: &str,
+        overrides: &Overrides,
+        weighting: QuantityWeighting,
+    ) {
+        let weight = weighting.weight(asset);
+        // Assets added so far were counted as-is
+        let original = self
+            .original_traits
+            .get_or_insert_with(|| self.traits.clone());
+        count_traits(original, &asset.traits, weight);
+
+        let corrected = overrides.apply_traits(&asset.traits, asset_name_hex);
+        if corrected != asset.traits {
+            self.overridden += 1;
+        }
+        count_traits(&mut self.traits, &corrected, weight);
+        self.count = self.count.saturating_add(weight);
+    }
+
+    /// Trait counts before overrides, if any asset was added with them.
+    /// These are never normalized.
+    #[must_use]
+    pub fn original_traits(&self) -> Option<&HashMap<String, HashMap<String, u32>>> {
+        self.original_traits.as_ref()
+    }
+
+    /// Number of assets whose traits were changed by overrides
+    #[must_use]
+    pub fn overridden_assets(&self) -> u32 {
+        self.overridden
+    }
+
+    /// Merge value counts that canonicalize to the same value under `config`.
+    ///
+    /// Returns a report of every canonical value that absorbed differing raw
+    /// values, so callers can surface (or persist as aliases) what was merged.
+    pub fn normalize(&mut self, config: &TraitNormalization) -> NormalizationReport {
+        let (traits, report) = normalize::normalize_counts(&self.traits, config);
+        self.traits = traits;
+        report
     }
 }
 
@@ -1231,6 +1436,52 @@ pub fn get_asset_tags(asset: &CnftAsset, max_rarity: Option<u32>) -> Vec<AssetTa
     tags
 }
 
+#[cfg(feature = "cnft_tools")]
+impl AssetV2 {
+    /// Convert a cnft.tools asset. Its id is only the display name, so the
+    /// policy comes from the caller; `max_rarity` adds the rarity tag as in
+    /// [`get_asset_tags`]. `"None"` trait values are dropped.
+    #[must_use]
+    pub fn from_cnft(policy_id: &PolicyId, asset: &CnftAsset, max_rarity: Option<u32>) -> Self {
+        let mut traits = Traits::new();
+        for (key, values) in &asset.traits {
+            let values: Vec<String> = values
+                .iter()
+                .filter(|v| !v.is_empty() && !v.eq_ignore_ascii_case("none"))
+                .cloned()
+                .collect();
+            if !values.is_empty() {
+                traits.insert_vec(key.clone(), values);
+            }
+        }
+
+        let image = match asset.icon_url.as_deref() {
+            Some(url) if url.contains("://") => url.to_string(),
+            Some(cid) if !cid.is_empty() => format!("ipfs://{cid}"),
+            _ => String::new(),
+        };
+
+        Self {
+            id: AssetId::new_unchecked(policy_id.as_str().to_string(), asset.encoded_name.clone()),
+            name: asset.name.clone(),
+            image,
+            media_type: None,
+            traits,
+            // cnft.tools reports unranked assets as 0
+            rarity_rank: (asset.rarity_rank > 0).then_some(asset.rarity_rank),
+            quantity: 1,
+            tags: get_asset_tags(asset, max_rarity),
+        }
+    }
+}
+
+#[cfg(feature = "cnft_tools")]
+impl From<(&PolicyId, &CnftAsset)> for AssetV2 {
+    fn from((policy_id, asset): (&PolicyId, &CnftAsset)) -> Self {
+        Self::from_cnft(policy_id, asset, None)
+    }
+}
+
 /// Merge extra metadata fields into traits, filtering out known metadata fields
 fn merge_extra_fields_into_traits(
     mut traits: Traits,
@@ -1294,6 +1545,31 @@ mod tests {
     use super::*;
     use test_utils::test_case;
 
+    #[test]
+    fn test_quantity_round_trip() {
+        let metadata: AssetMetadata =
+            serde_json::from_str(test_case!("bankcard2500.json")).unwrap();
+        let single = Asset::from(metadata);
+        assert_eq!(single.quantity, 1);
+        let json = serde_json::to_value(&single).unwrap();
+        assert!(json.get("quantity").is_none());
+
+        let editions = single.with_quantity(25);
+        let json = serde_json::to_string(&editions).unwrap();
+        let back: Asset = serde_json::from_str(&json).unwrap();
+        assert_eq!(back.quantity, 25);
+
+        let v2 = AssetV2::with_id(
+            back,
+            AssetId::new_unchecked(
+                "b3dab69f7e6100849434fb1781e34bd12a916557f6231b8d2629b6f6".to_string(),
+                "42616e6b636172643031".to_string(),
+            ),
+        );
+        assert_eq!(v2.quantity, 25);
+        assert_eq!(Asset::from(v2).quantity, 25);
+    }
+
     #[test]
     fn test_deserialize() {
         match serde_json::from_str::<Asset>(test_case!("5069726174653834.json")) {
@@ -1855,4 +2131,80 @@ mod tests {
             serde_json::json!(["val1", "val2"])
         );
     }
+
+    #[cfg(feature = "openapi")]
+    #[test]
+    fn test_openapi_schemas_match_wire_format() {
+        use utoipa::PartialSchema;
+
+        // The schema's properties must be exactly the keys AssetId writes
+        let schema = serde_json::to_value(AssetId::schema()).unwrap();
+        let wire = serde_json::to_value(AssetId::new_unchecked(
+            "b3dab69f7e6100849434fb1781e34bd12a916557f6231b8d2629b6f6".to_string(),
+            "50697261746531303836".to_string(),
+        ))
+        .unwrap();
+        assert_eq!(schema["type"], "object");
+        let mut properties: Vec<_> = schema["properties"].as_object().unwrap().keys().collect();
+        let mut keys: Vec<_> = wire.as_object().unwrap().keys().collect();
+        properties.sort();
+        keys.sort();
+        assert_eq!(properties, keys);
+        assert_eq!(schema["required"].as_array().unwrap().len(), keys.len());
+        assert_eq!(schema["examples"][0], wire);
+
+        let details = serde_json::to_value(CollectionDetails::schema()).unwrap();
+        assert!(details["properties"].get("policy_id").is_some());
+        assert!(details["properties"].get("royalty_percentage").is_some());
+
+        let v2 = serde_json::to_value(AssetV2::schema()).unwrap();
+        assert!(v2["properties"].get("id").is_some());
+
+        let sorted = serde_json::to_value(TraitSummarySorted::schema()).unwrap();
+        assert_eq!(sorted["properties"]["traits"]["type"], "object");
+    }
+
+    #[cfg(feature = "cnft_tools")]
+    #[test]
+    fn test_asset_v2_from_cnft() {
+        let asset: CnftAsset = serde_json::from_value(serde_json::json!({
+            "onSale": true,
+            "assetName": "KingDanielNavagio",
+            "assetID": "KingDanielNavagio",
+            "name": "King Daniel Navagio",
+            "iconurl": "QmeAiBuHYKHeTm7zSwJsFQeN5nhpjeSqsViC1bZLeRC9jd",
+            "hat": "Kings Crown",
+            "tattoo": "None",
+            "earrings": "none",
+            "rarityRank": "1",
+            "encodedName": "4b696e6744616e69656c4e61766167696f",
+            "ownerStakeKey": "stake1u90rj0nevs6jt4xwzm7m5gqml64z7fdygqrk3296tt08d9g9zj0va"
+        }))
+        .unwrap();
+        let policy_id =
+            PolicyId::new("b3dab69f7e6100849434fb1781e34bd12a916557f6231b8d2629b6f6").unwrap();
+
+        let v2 = AssetV2::from_cnft(&policy_id, &asset, Some(10_000));
+        assert_eq!(
+            v2.id.concatenated(),
+            "b3dab69f7e6100849434fb1781e34bd12a916557f6231b8d2629b6f64b696e6744616e69656c4e61766167696f"
+        );
+        assert_eq!(v2.name, "King Daniel Navagio");
+        assert_eq!(
+            v2.image,
+            "ipfs://QmeAiBuHYKHeTm7zSwJsFQeN5nhpjeSqsViC1bZLeRC9jd"
+        );
+        assert_eq!(v2.traits.get("hat"), Some(&vec!["Kings Crown".to_string()]));
+        assert!(v2.traits.get("tattoo").is_none());
+        assert!(v2.traits.get("earrings").is_none());
+        assert_eq!(v2.rarity_rank, Some(1));
+        assert_eq!(
+            v2.tags,
+            vec![AssetTag::OnSale, AssetTag::Rarity(AssetRarity::Legendary)]
+        );
+
+        let plain = AssetV2::from((&policy_id, &asset));
+        assert_eq!(plain.tags, vec![AssetTag::OnSale]);
+        assert_eq!(plain.traits, v2.traits);
+    }
 }
@@ -0,0 +1,125 @@
+//! Canonical marketplace and explorer links for an asset.
+//!
+//! Every embed links the same way, and a site changing its URL scheme is
+//! fixed here once:
+//!
+//! ```
+//! use cardano_assets::{marketplace_urls, AssetId};
+//!
+//! let id = AssetId::parse_concatenated(
+//!     "b3dab69f7e6100849434fb1781e34bd12a916557f6231b8d2629b6f650697261746531",
+//! )
+//! .unwrap();
+//! let urls = marketplace_urls(&id);
+//! assert_eq!(
+//!     urls.jpg_store,
+//!     "https://www.jpg.store/asset/b3dab69f7e6100849434fb1781e34bd12a916557f6231b8d2629b6f650697261746531"
+//! );
+//! ```
+//!
+//! pool.pm and cexplorer address assets by their CIP-14 fingerprint. With the
+//! `cip14` feature disabled they link to the policy page instead.
+
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "openapi")]
+use utoipa::ToSchema;
+
+use crate::AssetId;
+
+/// Links to one asset across marketplaces and explorers
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+pub struct MarketplaceUrls {
+    pub jpg_store: String,
+    pub wayup: String,
+    pub pool_pm: String,
+    pub cexplorer: String,
+}
+
+/// Build the canonical links for `asset_id`
+#[must_use]
+pub fn marketplace_urls(asset_id: &AssetId) -> MarketplaceUrls {
+    let policy_id = asset_id.policy_id();
+    let (pool_pm, cexplorer) = match fingerprint(asset_id) {
+        Some(fingerprint) => (
+            format!("https://pool.pm/{fingerprint}"),
+            format!("https://cexplorer.io/asset/{fingerprint}"),
+        ),
+        None => (
+            format!("https://pool.pm/policy/{policy_id}"),
+            format!("https://cexplorer.io/policy/{policy_id}"),
+        ),
+    };
+
+    MarketplaceUrls {
+        jpg_store: format!("https://www.jpg.store/asset/{}", asset_id.concatenated()),
+        wayup: format!(
+            "https://www.wayup.io/collection/{policy_id}/asset/{}",
+            asset_id.asset_name_hex()
+        ),
+        pool_pm,
+        cexplorer,
+    }
+}
+
+#[cfg(feature = "cip14")]
+fn fingerprint(asset_id: &AssetId) -> Option<String> {
+    asset_id.fingerprint().ok()
+}
+
+#[cfg(not(feature = "cip14"))]
+fn fingerprint(_asset_id: &AssetId) -> Option<String> {
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const POLICY_ID: &str = "7eae28af2208be856f7a119668ae52a49b73725e326dc16579dcc373";
+
+    fn asset_id() -> AssetId {
+        // CIP-14 test vector (empty asset name)
+        AssetId::new_unchecked(POLICY_ID.to_string(), String::new())
+    }
+
+    #[test]
+    fn test_marketplace_links() {
+        let id = AssetId::new_unchecked(POLICY_ID.to_string(), "50697261746531".to_string());
+        let urls = marketplace_urls(&id);
+        assert_eq!(
+            urls.jpg_store,
+            format!("https://www.jpg.store/asset/{POLICY_ID}50697261746531")
+        );
+        assert_eq!(
+            urls.wayup,
+            format!("https://www.wayup.io/collection/{POLICY_ID}/asset/50697261746531")
+        );
+    }
+
+    #[cfg(feature = "cip14")]
+    #[test]
+    fn test_explorer_links_use_fingerprint() {
+        let urls = marketplace_urls(&asset_id());
+        assert_eq!(
+            urls.pool_pm,
+            "https://pool.pm/asset1rjklcrnsdzqp65wjgrg55sy9723kw09mlgvlc3"
+        );
+        assert_eq!(
+            urls.cexplorer,
+            "https://cexplorer.io/asset/asset1rjklcrnsdzqp65wjgrg55sy9723kw09mlgvlc3"
+        );
+    }
+
+    #[cfg(not(feature = "cip14"))]
+    #[test]
+    fn test_explorer_links_fall_back_to_policy() {
+        let urls = marketplace_urls(&asset_id());
+        assert_eq!(urls.pool_pm, format!("https://pool.pm/policy/{POLICY_ID}"));
+        assert_eq!(
+            urls.cexplorer,
+            format!("https://cexplorer.io/policy/{POLICY_ID}")
+        );
+    }
+}
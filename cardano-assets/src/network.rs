@@ -0,0 +1,294 @@
+//! Cardano network selection shared by the API clients.
+//!
+//! Clients (`maestro`, `anvil-api`, ...) take a [`Network`] to pick their base
+//! URL, so a staging bot can run the whole stack against preprod by changing a
+//! single value. Address helpers here check bech32 prefixes so a mainnet
+//! address can't slip into a testnet flow (or vice versa).
+
+use std::fmt;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "openapi")]
+use utoipa::ToSchema;
+
+/// A Cardano network.
+///
+/// Parses from the bare name (`"preprod"`) or the CAIP-2 style chain id used
+/// in worker config (`"cardano:preprod"`). `"testnet"` is accepted as an alias
+/// for preprod, matching the existing `cardano:testnet` config values.
+///
+/// # Examples
+///
+/// ```
+/// use cardano_assets::Network;
+///
+/// let network: Network = "cardano:preprod".parse().unwrap();
+/// assert_eq!(network, Network::Preprod);
+/// assert_eq!(network.network_id(), 0);
+/// assert!(network.is_address_for("addr_test1vpu5vlrf4xkxv2qpwngf6cjhtw542ayty80v8dyr49rf5eg57c2qv"));
+/// assert!(!Network::Mainnet.is_address_for("addr_test1vpu5vlrf4xkxv2qpwngf6cjhtw542ayty80v8dyr49rf5eg57c2qv"));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+#[serde(rename_all = "lowercase")]
+pub enum Network {
+    #[default]
+    Mainnet,
+    Preprod,
+    Preview,
+}
+
+impl Network {
+    pub const ALL: [Network; 3] = [Network::Mainnet, Network::Preprod, Network::Preview];
+
+    /// Network id carried in the address header (1 = mainnet, 0 = any testnet)
+    #[must_use]
+    pub fn network_id(&self) -> u8 {
+        match self {
+            Network::Mainnet => 1,
+            Network::Preprod | Network::Preview => 0,
+        }
+    }
+
+    #[must_use]
+    pub fn is_mainnet(&self) -> bool {
+        matches!(self, Network::Mainnet)
+    }
+
+    #[must_use]
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Network::Mainnet => "mainnet",
+            Network::Preprod => "preprod",
+            Network::Preview => "preview",
+        }
+    }
+
+    /// Chain id in the `cardano:<network>` form used by worker config
+    #[must_use]
+    pub fn chain_id(&self) -> &'static str {
+        match self {
+            Network::Mainnet => "cardano:mainnet",
+            Network::Preprod => "cardano:preprod",
+            Network::Preview => "cardano:preview",
+        }
+    }
+
+    /// Bech32 prefix for payment addresses (`addr` / `addr_test`)
+    #[must_use]
+    pub fn address_prefix(&self) -> &'static str {
+        if self.is_mainnet() {
+            "addr"
+        } else {
+            "addr_test"
+        }
+    }
+
+    /// Bech32 prefix for stake (reward) addresses (`stake` / `stake_test`)
+    #[must_use]
+    pub fn stake_address_prefix(&self) -> &'static str {
+        if self.is_mainnet() {
+            "stake"
+        } else {
+            "stake_test"
+        }
+    }
+
+    /// Whether a bech32 payment or stake address belongs to this network.
+    ///
+    /// Only the human-readable prefix is checked — preprod and preview share
+    /// prefixes, so either testnet accepts the other's addresses.
+    #[must_use]
+    pub fn is_address_for(&self, address: &str) -> bool {
+        match address_hrp(address) {
+            Some(hrp) => hrp == self.address_prefix() || hrp == self.stake_address_prefix(),
+            None => false,
+        }
+    }
+
+    /// Like [`is_address_for`](Self::is_address_for) but explains the mismatch
+    pub fn validate_address(&self, address: &str) -> Result<(), NetworkError> {
+        let hrp = address_hrp(address)
+            .ok_or_else(|| NetworkError::InvalidAddress(address.to_string()))?;
+
+        if self.is_address_for(address) {
+            Ok(())
+        } else {
+            Err(NetworkError::AddressMismatch {
+                expected: *self,
+                prefix: hrp.to_string(),
+            })
+        }
+    }
+
+    /// Infer mainnet vs testnet from a bech32 address.
+    ///
+    /// Testnet addresses resolve to [`Network::Preprod`], since the prefix
+    /// can't distinguish preprod from preview.
+    pub fn from_address(address: &str) -> Result<Self, NetworkError> {
+        match address_hrp(address) {
+            Some("addr" | "stake") => Ok(Network::Mainnet),
+            Some("addr_test" | "stake_test") => Ok(Network::Preprod),
+            _ => Err(NetworkError::InvalidAddress(address.to_string())),
+        }
+    }
+
+    /// Unix time (seconds) at which epoch 0 began
+    #[must_use]
+    pub fn system_start_secs(&self) -> u64 {
+        match self {
+            Network::Mainnet => 1_506_203_091,
+            Network::Preprod => 1_654_041_600,
+            Network::Preview => 1_666_656_000,
+        }
+    }
+
+    /// Epoch length in seconds: five days, except one day on preview.
+    ///
+    /// Byron epochs were the same length as Shelley ones on every network, so
+    /// this holds for the whole chain.
+    #[must_use]
+    pub fn epoch_length_secs(&self) -> u64 {
+        match self {
+            Network::Mainnet | Network::Preprod => 432_000,
+            Network::Preview => 86_400,
+        }
+    }
+
+    /// Epoch containing a unix timestamp (seconds). Times before the network
+    /// started fall in epoch 0.
+    #[must_use]
+    pub fn epoch_at(&self, unix_secs: u64) -> u64 {
+        unix_secs.saturating_sub(self.system_start_secs()) / self.epoch_length_secs()
+    }
+}
+
+/// Human-readable part of a bech32 string (everything before the last `1`)
+fn address_hrp(address: &str) -> Option<&str> {
+    let (hrp, data) = address.rsplit_once('1')?;
+    if hrp.is_empty() || data.is_empty() {
+        return None;
+    }
+    Some(hrp)
+}
+
+impl fmt::Display for Network {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for Network {
+    type Err = NetworkError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let name = s.strip_prefix("cardano:").unwrap_or(s);
+        match name.to_ascii_lowercase().as_str() {
+            "mainnet" => Ok(Network::Mainnet),
+            "preprod" | "testnet" => Ok(Network::Preprod),
+            "preview" => Ok(Network::Preview),
+            _ => Err(NetworkError::Unknown(s.to_string())),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum NetworkError {
+    /// Not a recognised network name
+    Unknown(String),
+    /// Not a bech32 address
+    InvalidAddress(String),
+    /// Address prefix belongs to a different network
+    AddressMismatch { expected: Network, prefix: String },
+}
+
+impl fmt::Display for NetworkError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            NetworkError::Unknown(s) => write!(f, "Unknown Cardano network: {s}"),
+            NetworkError::InvalidAddress(s) => write!(f, "Not a bech32 address: {s}"),
+            NetworkError::AddressMismatch { expected, prefix } => write!(
+                f,
+                "Address prefix '{prefix}' is not valid on {expected} (expected '{}' or '{}')",
+                expected.address_prefix(),
+                expected.stake_address_prefix()
+            ),
+        }
+    }
+}
+
+impl std::error::Error for NetworkError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MAINNET_ADDR: &str = "addr1w999n67e47he8y0v36hjtzluargwu25zw94f6lqnm82aqqsg4xkcp";
+    const TESTNET_ADDR: &str = "addr_test1vpu5vlrf4xkxv2qpwngf6cjhtw542ayty80v8dyr49rf5eg57c2qv";
+
+    #[test]
+    fn test_parse_network() {
+        assert_eq!("mainnet".parse::<Network>().unwrap(), Network::Mainnet);
+        assert_eq!(
+            "cardano:preview".parse::<Network>().unwrap(),
+            Network::Preview
+        );
+        assert_eq!(
+            "cardano:testnet".parse::<Network>().unwrap(),
+            Network::Preprod
+        );
+        assert!("sanchonet".parse::<Network>().is_err());
+
+        for network in Network::ALL {
+            assert_eq!(network.chain_id().parse::<Network>().unwrap(), network);
+            assert_eq!(network.to_string().parse::<Network>().unwrap(), network);
+        }
+    }
+
+    #[test]
+    fn test_serde_lowercase() {
+        assert_eq!(
+            serde_json::to_string(&Network::Preprod).unwrap(),
+            "\"preprod\""
+        );
+        assert_eq!(
+            serde_json::from_str::<Network>("\"preview\"").unwrap(),
+            Network::Preview
+        );
+    }
+
+    #[test]
+    fn test_address_validation() {
+        assert!(Network::Mainnet.validate_address(MAINNET_ADDR).is_ok());
+        assert!(Network::Preview.validate_address(TESTNET_ADDR).is_ok());
+        assert!(Network::Mainnet.is_address_for("stake1uxyz"));
+        assert!(Network::Preprod.is_address_for("stake_test1uxyz"));
+
+        assert_eq!(
+            Network::Mainnet.validate_address(TESTNET_ADDR),
+            Err(NetworkError::AddressMismatch {
+                expected: Network::Mainnet,
+                prefix: "addr_test".to_string(),
+            })
+        );
+        assert!(matches!(
+            Network::Preprod.validate_address("not-an-address"),
+            Err(NetworkError::InvalidAddress(_))
+        ));
+
+        assert_eq!(Network::from_address(MAINNET_ADDR), Ok(Network::Mainnet));
+        assert_eq!(Network::from_address(TESTNET_ADDR), Ok(Network::Preprod));
+    }
+
+    #[test]
+    fn test_epoch_at() {
+        // Shelley hard fork: first slot of mainnet epoch 208
+        assert_eq!(Network::Mainnet.epoch_at(1_596_059_091), 208);
+        assert_eq!(Network::Mainnet.epoch_at(1_596_059_090), 207);
+        assert_eq!(Network::Mainnet.epoch_at(0), 0);
+        assert_eq!(Network::Preprod.epoch_at(1_655_769_600), 4);
+        assert_eq!(Network::Preview.epoch_at(1_666_656_000 + 86_400 * 3 + 1), 3);
+    }
+}
@@ -0,0 +1,393 @@
+//! Trait value normalization — canonicalize messy on-chain trait values.
+//!
+//! Collections routinely mix `"Laser Eyes Blue"`, `"laser eyes blue"` and
+//! `"Laser Eyes Blue "` for the same trait, which splits [`TraitSummary`]
+//! counts and skews rarity. [`TraitNormalization`] describes a pipeline of
+//! text clean-ups applied in a fixed order:
+//!
+//! 1. Unicode NFC (composed form, so `e` + combining accent == `é`)
+//! 2. Trim leading/trailing whitespace
+//! 3. Collapse internal whitespace runs to a single space
+//! 4. Case style ([`CaseStyle`])
+//! 5. Alias lookup (matched case-insensitively against the cleaned value)
+//!
+//! Apply it to a single asset with [`Traits::normalize`], or merge the
+//! counts of an already-built summary with [`TraitSummary::normalize`], which
+//! returns a [`NormalizationReport`] of which raw values were merged.
+//!
+//! [`TraitSummary`]: crate::TraitSummary
+//! [`TraitSummary::normalize`]: crate::TraitSummary::normalize
+
+use crate::Traits;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use unicode_normalization::UnicodeNormalization;
+
+#[cfg(feature = "openapi")]
+use utoipa::ToSchema;
+
+/// How letter case is canonicalized
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum CaseStyle {
+    /// Leave case untouched (case variants stay distinct)
+    #[default]
+    Preserve,
+    /// `laser eyes blue`
+    Lower,
+    /// `Laser Eyes Blue` — first letter of each word upper, the rest lower
+    Title,
+}
+
+/// Configuration for the trait normalization pipeline
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+pub struct TraitNormalization {
+    pub trim: bool,
+    pub collapse_whitespace: bool,
+    pub unicode_nfc: bool,
+    pub case: CaseStyle,
+    /// Also clean trait names (trim/whitespace/NFC only — case is never changed)
+    pub normalize_keys: bool,
+    /// Value aliases applied to every trait, keyed by lowercased source value
+    #[serde(default)]
+    pub aliases: HashMap<String, String>,
+    /// Value aliases scoped to a single trait name, checked before `aliases`
+    #[serde(default)]
+    pub trait_aliases: HashMap<String, HashMap<String, String>>,
+}
+
+impl Default for TraitNormalization {
+    fn default() -> Self {
+        Self {
+            trim: true,
+            collapse_whitespace: true,
+            unicode_nfc: true,
+            case: CaseStyle::Preserve,
+            normalize_keys: true,
+            aliases: HashMap::new(),
+            trait_aliases: HashMap::new(),
+        }
+    }
+}
+
+impl TraitNormalization {
+    /// Default pipeline (NFC, trim, collapse whitespace) without case changes
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    #[must_use]
+    pub fn with_case(mut self, case: CaseStyle) -> Self {
+        self.case = case;
+        self
+    }
+
+    /// Map `from` to `to` for every trait (matched case-insensitively)
+    #[must_use]
+    pub fn with_alias(mut self, from: &str, to: &str) -> Self {
+        self.aliases.insert(alias_key(from), to.to_string());
+        self
+    }
+
+    /// Map `from` to `to` only for the trait named `trait_name`
+    #[must_use]
+    pub fn with_trait_alias(mut self, trait_name: &str, from: &str, to: &str) -> Self {
+        self.trait_aliases
+            .entry(trait_name.to_string())
+            .or_default()
+            .insert(alias_key(from), to.to_string());
+        self
+    }
+
+    /// Canonicalize a trait name
+    #[must_use]
+    pub fn normalize_key(&self, key: &str) -> String {
+        if self.normalize_keys {
+            self.clean_text(key)
+        } else {
+            key.to_string()
+        }
+    }
+
+    /// Canonicalize a single value of the trait named `trait_name`
+    #[must_use]
+    pub fn normalize_value(&self, trait_name: &str, value: &str) -> String {
+        let cleaned = self.clean_text(value);
+        let cased = match self.case {
+            CaseStyle::Preserve => cleaned,
+            CaseStyle::Lower => cleaned.to_lowercase(),
+            CaseStyle::Title => title_case(&cleaned),
+        };
+
+        let lookup = alias_key(&cased);
+        self.trait_aliases
+            .get(trait_name)
+            .and_then(|aliases| aliases.get(&lookup))
+            .or_else(|| self.aliases.get(&lookup))
+            .cloned()
+            .unwrap_or(cased)
+    }
+
+    fn clean_text(&self, input: &str) -> String {
+        let mut text: String = if self.unicode_nfc {
+            input.nfc().collect()
+        } else {
+            input.to_string()
+        };
+        if self.trim {
+            text = text.trim().to_string();
+        }
+        if self.collapse_whitespace {
+            text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+        }
+        text
+    }
+}
+
+fn alias_key(value: &str) -> String {
+    value
+        .nfc()
+        .collect::<String>()
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .to_lowercase()
+}
+
+fn title_case(value: &str) -> String {
+    value
+        .split(' ')
+        .map(|word| {
+            let mut chars = word.chars();
+            match chars.next() {
+                Some(first) => first
+                    .to_uppercase()
+                    .chain(chars.flat_map(char::to_lowercase))
+                    .collect(),
+                None => String::new(),
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// A canonical trait value that absorbed one or more differing raw values
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+pub struct MergedTraitValue {
+    pub trait_name: String,
+    pub canonical: String,
+    /// Raw values (as seen before normalization) folded into `canonical`, sorted
+    pub variants: Vec<String>,
+    /// Combined occurrence count after merging
+    pub count: u32,
+}
+
+/// Summary of what a normalization pass changed
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+pub struct NormalizationReport {
+    /// Canonical values that differ from, or combine, their raw inputs
+    pub merged: Vec<MergedTraitValue>,
+}
+
+impl NormalizationReport {
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.merged.is_empty()
+    }
+
+    /// Number of raw values that were rewritten or folded away
+    #[must_use]
+    pub fn rewritten_values(&self) -> usize {
+        self.merged
+            .iter()
+            .map(|m| m.variants.iter().filter(|v| **v != m.canonical).count())
+            .sum()
+    }
+}
+
+/// Normalize a trait → (value → count) table, returning the merged table and
+/// a report of every canonical value built from differing raw values.
+pub(crate) fn normalize_counts(
+    counts: &HashMap<String, HashMap<String, u32>>,
+    config: &TraitNormalization,
+) -> (HashMap<String, HashMap<String, u32>>, NormalizationReport) {
+    // (key, canonical) -> (raw variants, total)
+    let mut merged: BTreeMap<(String, String), (BTreeSet<String>, u32)> = BTreeMap::new();
+
+    for (raw_key, values) in counts {
+        let key = config.normalize_key(raw_key);
+        for (raw_value, count) in values {
+            let canonical = config.normalize_value(&key, raw_value);
+            let entry = merged.entry((key.clone(), canonical)).or_default();
+            entry.0.insert(raw_value.clone());
+            entry.1 += count;
+        }
+    }
+
+    let mut output: HashMap<String, HashMap<String, u32>> = HashMap::new();
+    let mut report = NormalizationReport::default();
+
+    for ((key, canonical), (variants, count)) in merged {
+        let changed = variants.len() > 1 || !variants.contains(&canonical);
+        if changed {
+            report.merged.push(MergedTraitValue {
+                trait_name: key.clone(),
+                canonical: canonical.clone(),
+                variants: variants.into_iter().collect(),
+                count,
+            });
+        }
+        output.entry(key).or_default().insert(canonical, count);
+    }
+
+    (output, report)
+}
+
+impl Traits {
+    /// Return a copy with every trait name and value run through `config`.
+    ///
+    /// Values that collapse to the same canonical form within one trait are
+    /// deduplicated (first occurrence wins the position), and traits whose
+    /// names collapse together have their values combined.
+    #[must_use]
+    pub fn normalize(&self, config: &TraitNormalization) -> Traits {
+        let mut map: HashMap<String, Vec<String>> = HashMap::new();
+
+        for (raw_key, values) in self.iter() {
+            let key = config.normalize_key(raw_key);
+            let target = map.entry(key.clone()).or_default();
+            for value in values {
+                let canonical = config.normalize_value(&key, value);
+                if !canonical.is_empty() && !target.contains(&canonical) {
+                    target.push(canonical);
+                }
+            }
+        }
+
+        Traits::from_map(map)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Asset, TraitSummary};
+
+    fn traits(pairs: &[(&str, &[&str])]) -> Traits {
+        Traits::from_map(
+            pairs
+                .iter()
+                .map(|(k, v)| (k.to_string(), v.iter().map(|s| s.to_string()).collect()))
+                .collect(),
+        )
+    }
+
+    #[test]
+    fn test_default_pipeline_cleans_whitespace_and_nfc() {
+        let config = TraitNormalization::default();
+        assert_eq!(
+            config.normalize_value("Eyes", "  Laser   Eyes\tBlue "),
+            "Laser Eyes Blue"
+        );
+        // "e" + combining acute accent composes to "é"
+        assert_eq!(
+            config.normalize_value("Hat", "Beret Cafe\u{301}"),
+            "Beret Café"
+        );
+        // Case is preserved by default
+        assert_eq!(config.normalize_value("Eyes", "laser eyes"), "laser eyes");
+    }
+
+    #[test]
+    fn test_case_styles() {
+        let title = TraitNormalization::new().with_case(CaseStyle::Title);
+        assert_eq!(
+            title.normalize_value("Eyes", "laser EYES blue"),
+            "Laser Eyes Blue"
+        );
+
+        let lower = TraitNormalization::new().with_case(CaseStyle::Lower);
+        assert_eq!(
+            lower.normalize_value("Eyes", "Laser Eyes Blue"),
+            "laser eyes blue"
+        );
+    }
+
+    #[test]
+    fn test_aliases_scoped_and_global() {
+        let config = TraitNormalization::new()
+            .with_alias("N/A", "None")
+            .with_trait_alias("Background", "lt blue", "Light Blue");
+
+        assert_eq!(config.normalize_value("Hat", "n/a"), "None");
+        assert_eq!(
+            config.normalize_value("Background", "LT  Blue"),
+            "Light Blue"
+        );
+        assert_eq!(config.normalize_value("Hat", "lt blue"), "lt blue");
+    }
+
+    #[test]
+    fn test_traits_normalize_dedupes_values_and_keys() {
+        let config = TraitNormalization::new().with_case(CaseStyle::Title);
+        let raw = traits(&[
+            ("Accessories", &["gold chain", "Gold Chain ", "Earring"]),
+            ("Eyes ", &["laser eyes blue"]),
+        ]);
+
+        let normalized = raw.normalize(&config);
+        assert_eq!(
+            normalized.get("Accessories"),
+            Some(&vec!["Gold Chain".to_string(), "Earring".to_string()])
+        );
+        assert_eq!(
+            normalized.get("Eyes"),
+            Some(&vec!["Laser Eyes Blue".to_string()])
+        );
+        assert!(!normalized.contains_key("Eyes "));
+    }
+
+    #[test]
+    fn test_trait_summary_normalize_merges_counts() {
+        let mut summary = TraitSummary::default();
+        for value in [
+            "Laser Eyes Blue",
+            "laser eyes blue",
+            "Laser Eyes Blue ",
+            "Sleepy",
+        ] {
+            summary.add_asset(&Asset {
+                name: "Test".to_string(),
+                image: String::new(),
+                media_type: None,
+                traits: traits(&[("Eyes", &[value])]),
+                rarity_rank: None,
+                quantity: 1,
+                tags: vec![],
+            });
+        }
+
+        let report = summary.normalize(&TraitNormalization::new().with_case(CaseStyle::Title));
+
+        assert_eq!(report.merged.len(), 1);
+        let merged = &report.merged[0];
+        assert_eq!(merged.trait_name, "Eyes");
+        assert_eq!(merged.canonical, "Laser Eyes Blue");
+        assert_eq!(merged.count, 3);
+        assert_eq!(
+            merged.variants,
+            vec!["Laser Eyes Blue", "Laser Eyes Blue ", "laser eyes blue"]
+        );
+        assert_eq!(report.rewritten_values(), 2);
+
+        let sorted = crate::TraitSummarySorted::from(summary);
+        let eyes = &sorted.traits["Eyes"];
+        assert_eq!(eyes.len(), 2);
+        assert_eq!(eyes.iter().map(|v| v.count).sum::<u32>(), 4);
+    }
+}
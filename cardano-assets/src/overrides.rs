@@ -0,0 +1,376 @@
+//! Collection metadata overrides — corrections layered over on-chain metadata.
+//!
+//! On-chain metadata is immutable, but projects regularly ship typos, swapped
+//! images or a trait key that changed spelling halfway through the mint.
+//! [`Overrides`] holds the corrections for one policy and is applied to
+//! [`Asset`]/[`AssetV2`] on read, so the stored metadata stays untouched and
+//! a correction can be edited or withdrawn at any time.
+//!
+//! Corrections are applied in a fixed order:
+//!
+//! 1. Trait key renames (`"Backgrund"` → `"Background"`), merging values if
+//!    the new key already exists
+//! 2. Trait value fixes, scoped to a (renamed) trait key
+//! 3. Per-asset corrections: name, image, traits removed, traits replaced
+//!
+//! The whole layer serializes to JSON and is stored in KV under
+//! [`Overrides::kv_key`]. Use [`TraitSummary::add_asset_with_overrides`] to
+//! build a summary that reports counts before and after the corrections.
+//!
+//! [`TraitSummary::add_asset_with_overrides`]: crate::TraitSummary::add_asset_with_overrides
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+use crate::{Asset, AssetV2, PolicyId, Traits};
+
+#[cfg(feature = "openapi")]
+use utoipa::ToSchema;
+
+/// KV key prefix for stored [`Overrides`]
+pub const OVERRIDES_KV_PREFIX: &str = "overrides:";
+
+/// Corrections for a single asset
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+pub struct AssetOverride {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub image: Option<String>,
+    /// Trait keys dropped from the asset
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub remove_traits: Vec<String>,
+    /// Trait values replaced wholesale (added if the key is missing)
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub set_traits: BTreeMap<String, Vec<String>>,
+}
+
+/// Metadata corrections for one policy
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+pub struct Overrides {
+    pub policy_id: PolicyId,
+    /// Trait key → replacement key, applied to every asset
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub trait_renames: BTreeMap<String, String>,
+    /// Trait key → (value → replacement value), applied to every asset
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub value_fixes: BTreeMap<String, BTreeMap<String, String>>,
+    /// Asset name (hex) → corrections for that asset only
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub assets: BTreeMap<String, AssetOverride>,
+}
+
+impl Overrides {
+    pub fn new(policy_id: PolicyId) -> Self {
+        Self {
+            policy_id,
+            trait_renames: BTreeMap::new(),
+            value_fixes: BTreeMap::new(),
+            assets: BTreeMap::new(),
+        }
+    }
+
+    /// KV key the overrides for `policy_id` are stored under
+    #[must_use]
+    pub fn kv_key(policy_id: &PolicyId) -> String {
+        format!("{OVERRIDES_KV_PREFIX}{policy_id}")
+    }
+
+    pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+
+    pub fn to_json(&self) -> Result<String, serde_json::Error> {
+        serde_json::to_string(self)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.trait_renames.is_empty() && self.value_fixes.is_empty() && self.assets.is_empty()
+    }
+
+    /// Corrections registered for a single asset
+    #[must_use]
+    pub fn asset(&self, asset_name_hex: &str) -> Option<&AssetOverride> {
+        self.assets.get(asset_name_hex)
+    }
+
+    /// Apply the corrections to `asset`, whose name is `asset_name_hex`.
+    ///
+    /// Returns `true` if anything changed.
+    pub fn apply(&self, asset: &mut Asset, asset_name_hex: &str) -> bool {
+        self.apply_fields(
+            &mut asset.name,
+            &mut asset.image,
+            &mut asset.traits,
+            asset_name_hex,
+        )
+    }
+
+    /// Apply the corrections to `asset`. Assets under another policy are left
+    /// untouched.
+    ///
+    /// Returns `true` if anything changed.
+    pub fn apply_v2(&self, asset: &mut AssetV2) -> bool {
+        if self.policy_id != asset.id.policy_id() {
+            return false;
+        }
+        let asset_name_hex = asset.id.asset_name_hex().to_string();
+        self.apply_fields(
+            &mut asset.name,
+            &mut asset.image,
+            &mut asset.traits,
+            &asset_name_hex,
+        )
+    }
+
+    /// Apply only the trait corrections, returning the corrected traits
+    #[must_use]
+    pub fn apply_traits(&self, traits: &Traits, asset_name_hex: &str) -> Traits {
+        let mut traits = traits.clone();
+        self.correct_traits(&mut traits, self.asset(asset_name_hex));
+        traits
+    }
+
+    fn apply_fields(
+        &self,
+        name: &mut String,
+        image: &mut String,
+        traits: &mut Traits,
+        asset_name_hex: &str,
+    ) -> bool {
+        let asset_override = self.asset(asset_name_hex);
+        let mut changed = self.correct_traits(traits, asset_override);
+
+        if let Some(asset_override) = asset_override {
+            for (field, replacement) in
+                [(name, &asset_override.name), (image, &asset_override.image)]
+            {
+                if let Some(replacement) = replacement {
+                    if *field != *replacement {
+                        field.clone_from(replacement);
+                        changed = true;
+                    }
+                }
+            }
+        }
+
+        changed
+    }
+
+    fn correct_traits(&self, traits: &mut Traits, asset_override: Option<&AssetOverride>) -> bool {
+        let original = traits.clone();
+        let map = traits.inner_mut();
+
+        for (from, to) in &self.trait_renames {
+            if let Some(values) = map.remove(from) {
+                map.entry(to.clone()).or_default().extend(values);
+            }
+        }
+
+        for (key, fixes) in &self.value_fixes {
+            if let Some(values) = map.get_mut(key) {
+                for value in values.iter_mut() {
+                    if let Some(fixed) = fixes.get(value.as_str()) {
+                        value.clone_from(fixed);
+                    }
+                }
+            }
+        }
+
+        if let Some(asset_override) = asset_override {
+            for key in &asset_override.remove_traits {
+                map.remove(key);
+            }
+            for (key, values) in &asset_override.set_traits {
+                map.insert(key.clone(), values.clone());
+            }
+        }
+
+        // Renames and fixes can fold two values into one
+        for values in map.values_mut() {
+            let mut seen = Vec::with_capacity(values.len());
+            values.retain(|value| {
+                if seen.contains(value) {
+                    false
+                } else {
+                    seen.push(value.clone());
+                    true
+                }
+            });
+        }
+
+        *traits != original
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{AssetId, QuantityWeighting, TraitSummary};
+
+    const POLICY: &str = "b3dab69f7e6100849434fb1781e34bd12a916557f6231b8d2629b6f6";
+
+    fn asset(name: &str, pairs: &[(&str, &str)]) -> Asset {
+        let mut traits = Traits::new();
+        for (key, value) in pairs {
+            traits.insert_single(key.to_string(), value.to_string());
+        }
+        Asset {
+            name: name.to_string(),
+            image: "ipfs://QmOld".to_string(),
+            media_type: None,
+            traits,
+            rarity_rank: None,
+            quantity: 1,
+            tags: vec![],
+        }
+    }
+
+    fn overrides() -> Overrides {
+        let mut overrides = Overrides::new(POLICY.parse().unwrap());
+        overrides
+            .trait_renames
+            .insert("Backgrund".to_string(), "Background".to_string());
+        overrides.value_fixes.insert(
+            "Background".to_string(),
+            BTreeMap::from([("Gld".to_string(), "Gold".to_string())]),
+        );
+        overrides.assets.insert(
+            hex::encode("Pirate2"),
+            AssetOverride {
+                name: Some("Pirate #2".to_string()),
+                image: Some("ipfs://QmNew".to_string()),
+                remove_traits: vec!["Debug".to_string()],
+                set_traits: BTreeMap::from([("Hat".to_string(), vec!["Crown".to_string()])]),
+            },
+        );
+        overrides
+    }
+
+    #[test]
+    fn applies_renames_fixes_and_asset_corrections() {
+        let overrides = overrides();
+
+        let mut first = asset("Pirate1", &[("Backgrund", "Gld")]);
+        assert!(overrides.apply(&mut first, &hex::encode("Pirate1")));
+        assert_eq!(
+            first.traits.get("Background"),
+            Some(&vec!["Gold".to_string()])
+        );
+        assert!(!first.traits.contains_key("Backgrund"));
+        assert_eq!(first.name, "Pirate1");
+
+        let mut second = asset("Pirate2", &[("Background", "Blue"), ("Debug", "1")]);
+        assert!(overrides.apply(&mut second, &hex::encode("Pirate2")));
+        assert_eq!(second.name, "Pirate #2");
+        assert_eq!(second.image, "ipfs://QmNew");
+        assert!(!second.traits.contains_key("Debug"));
+        assert_eq!(second.traits.get("Hat"), Some(&vec!["Crown".to_string()]));
+
+        let mut untouched = asset("Pirate3", &[("Background", "Blue")]);
+        assert!(!overrides.apply(&mut untouched, &hex::encode("Pirate3")));
+    }
+
+    #[test]
+    fn rename_into_existing_key_merges_values() {
+        let overrides = overrides();
+        let mut traits = Traits::new();
+        traits.insert_single("Backgrund".to_string(), "Gld".to_string());
+        traits.insert_single("Background".to_string(), "Gold".to_string());
+
+        let corrected = overrides.apply_traits(&traits, "");
+        assert_eq!(corrected.get("Background"), Some(&vec!["Gold".to_string()]));
+        assert_eq!(corrected.inner().len(), 1);
+    }
+
+    #[test]
+    fn apply_v2_checks_policy() {
+        let overrides = overrides();
+        let base = asset("Pirate2", &[("Backgrund", "Gld")]);
+
+        let mut other = AssetV2::with_id(
+            base.clone(),
+            AssetId::new_unchecked("00".repeat(28), hex::encode("Pirate2")),
+        );
+        assert!(!overrides.apply_v2(&mut other));
+
+        let mut ours = AssetV2::with_id(
+            base,
+            AssetId::new_unchecked(POLICY.to_string(), hex::encode("Pirate2")),
+        );
+        assert!(overrides.apply_v2(&mut ours));
+        assert_eq!(ours.name, "Pirate #2");
+    }
+
+    #[test]
+    fn json_round_trip() {
+        let overrides = overrides();
+        let json = overrides.to_json().unwrap();
+        assert_eq!(Overrides::from_json(&json).unwrap(), overrides);
+
+        let empty = Overrides::from_json(&format!(r#"{{"policy_id":"{POLICY}"}}"#)).unwrap();
+        assert!(empty.is_empty());
+        assert_eq!(
+            Overrides::kv_key(&POLICY.parse().unwrap()),
+            format!("overrides:{POLICY}")
+        );
+    }
+
+    #[test]
+    fn summary_reports_original_and_overridden_counts() {
+        let overrides = overrides();
+        let mut summary = TraitSummary::default();
+        summary.add_asset(&asset("Pirate0", &[("Background", "Gold")]));
+        summary.add_asset_with_overrides(
+            &asset("Pirate1", &[("Backgrund", "Gld")]),
+            &hex::encode("Pirate1"),
+            &overrides,
+        );
+        summary.add_asset_with_overrides(
+            &asset("Pirate3", &[("Background", "Blue")]),
+            &hex::encode("Pirate3"),
+            &overrides,
+        );
+
+        assert_eq!(summary.count, 3);
+        assert_eq!(summary.overridden_assets(), 1);
+        assert_eq!(summary.traits["Background"]["Gold"], 2);
+        let original = summary.original_traits().unwrap();
+        assert_eq!(original["Background"]["Gold"], 1);
+        assert_eq!(original["Backgrund"]["Gld"], 1);
+        assert_eq!(original["Background"]["Blue"], 1);
+    }
+
+    #[test]
+    fn weighted_overrides_count_units() {
+        let overrides = overrides();
+        let edition = asset("Pirate1", &[("Backgrund", "Gld")]).with_quantity(5);
+        let name_hex = hex::encode("Pirate1");
+
+        let mut summary = TraitSummary::default();
+        summary.add_asset_with_overrides_weighted(
+            &edition,
+            &name_hex,
+            &overrides,
+            QuantityWeighting::PerUnit,
+        );
+        assert_eq!(summary.count, 5);
+        assert_eq!(summary.overridden_assets(), 1);
+        assert_eq!(summary.traits["Background"]["Gold"], 5);
+        assert_eq!(summary.original_traits().unwrap()["Backgrund"]["Gld"], 5);
+
+        summary
+            .remove_asset_with_overrides_weighted(
+                &edition,
+                &name_hex,
+                &overrides,
+                QuantityWeighting::PerUnit,
+            )
+            .unwrap();
+        assert_eq!(summary.count, 0);
+        assert_eq!(summary.overridden_assets(), 0);
+        assert!(summary.traits.is_empty());
+    }
+}
@@ -10,7 +10,13 @@
 //! time from raw asset names, tx hashes, or arbitrary 56-char hex.
 //! Validation runs once on construction; downstream code can rely
 //! on the format.
+//!
+//! The most common mix-up — passing an asset unit (policy id + asset name
+//! hex) where a policy id is expected — is rejected with
+//! [`PolicyIdError::AssetUnit`]; use [`PolicyId::from_unit`] to take the
+//! policy half deliberately.
 
+use std::borrow::Borrow;
 use std::fmt;
 use std::str::FromStr;
 
@@ -22,6 +28,13 @@ use utoipa::ToSchema;
 /// 28 bytes = 56 lowercase hex characters.
 pub(crate) const POLICY_ID_HEX_LEN: usize = 56;
 
+/// CIP-5 hrp for script hashes, which is what a policy id is.
+#[cfg(feature = "cip14")]
+pub(crate) const POLICY_ID_HRP: &str = "script";
+
+/// Asset names are at most 32 bytes (64 hex characters).
+const MAX_ASSET_NAME_HEX_LEN: usize = 64;
+
 /// Cardano policy ID — the script hash that identifies a minting
 /// policy. Always 56 lowercase hex characters.
 ///
@@ -42,6 +55,12 @@ pub(crate) const POLICY_ID_HEX_LEN: usize = 56;
 /// // Wrong length — rejected.
 /// assert!(PolicyId::new("abc").is_err());
 ///
+/// // An asset unit is not a policy id…
+/// let unit = "b3dab69f7e6100849434fb1781e34bd12a916557f6231b8d2629b6f650697261746531303836";
+/// assert!(PolicyId::new(unit).is_err());
+/// // …unless the policy half is asked for.
+/// assert_eq!(PolicyId::from_unit(unit).unwrap(), p);
+///
 /// // Non-hex characters — rejected.
 /// assert!(PolicyId::new(&"z".repeat(56)).is_err());
 /// ```
@@ -51,13 +70,34 @@ pub struct PolicyId(String);
 
 impl PolicyId {
     /// Construct a `PolicyId`, validating length (56) and that all
-    /// characters are lowercase ASCII hex digits.
+    /// characters are ASCII hex digits. Uppercase hex is lowercased.
     pub fn new(s: impl Into<String>) -> Result<Self, PolicyIdError> {
-        let s = s.into();
+        let mut s = s.into();
         Self::validate(&s)?;
+        s.make_ascii_lowercase();
         Ok(Self(s))
     }
 
+    /// Policy half of an asset unit (`policy_id ++ asset_name_hex`, with or
+    /// without a `.` separator). A bare policy id is accepted too.
+    pub fn from_unit(unit: &str) -> Result<Self, PolicyIdError> {
+        let unit = unit.trim();
+        if unit.len() < POLICY_ID_HEX_LEN || !unit.is_char_boundary(POLICY_ID_HEX_LEN) {
+            return Err(PolicyIdError::InvalidLength {
+                expected: POLICY_ID_HEX_LEN,
+                actual: unit.len(),
+            });
+        }
+        let (policy, asset_name) = unit.split_at(POLICY_ID_HEX_LEN);
+        let asset_name = asset_name.strip_prefix('.').unwrap_or(asset_name);
+        if asset_name.len() > MAX_ASSET_NAME_HEX_LEN
+            || !asset_name.chars().all(|c| c.is_ascii_hexdigit())
+        {
+            return Err(PolicyIdError::InvalidFormat);
+        }
+        Self::new(policy)
+    }
+
     /// Construct without validation. Caller must ensure the string
     /// is 56 lowercase hex characters; otherwise downstream
     /// behaviour is undefined (most consumers re-validate, some
@@ -90,7 +130,49 @@ impl PolicyId {
         self.0
     }
 
+    /// CIP-5 bech32 form, e.g. `script1…`.
+    #[cfg(feature = "cip14")]
+    pub fn to_bech32(&self) -> String {
+        use bech32::{Bech32, Hrp};
+
+        let bytes = self.as_bytes().expect("validated policy id");
+        let hrp = Hrp::parse(POLICY_ID_HRP).expect("valid hrp");
+        bech32::encode::<Bech32>(hrp, &bytes).expect("28 bytes fit in bech32")
+    }
+
+    /// Parse the CIP-5 bech32 form, verifying the checksum and hrp.
+    #[cfg(feature = "cip14")]
+    pub fn from_bech32(s: &str) -> Result<Self, PolicyIdError> {
+        let (hrp, data) = bech32::decode(s.trim()).map_err(|_| PolicyIdError::InvalidBech32)?;
+        if hrp.as_str() != POLICY_ID_HRP {
+            return Err(PolicyIdError::InvalidBech32);
+        }
+        if data.len() != POLICY_ID_HEX_LEN / 2 {
+            return Err(PolicyIdError::InvalidLength {
+                expected: POLICY_ID_HEX_LEN,
+                actual: data.len() * 2,
+            });
+        }
+        Ok(Self(hex::encode(data)))
+    }
+
+    /// Hex, or bech32 when the `cip14` feature is enabled.
+    pub fn parse_any(s: &str) -> Result<Self, PolicyIdError> {
+        let s = s.trim();
+        #[cfg(feature = "cip14")]
+        if s.starts_with(POLICY_ID_HRP) && s.len() > POLICY_ID_HRP.len() + 1 {
+            return Self::from_bech32(s);
+        }
+        Self::new(s)
+    }
+
     fn validate(s: &str) -> Result<(), PolicyIdError> {
+        let looks_like_unit = s.len() > POLICY_ID_HEX_LEN
+            && s.len() <= POLICY_ID_HEX_LEN + MAX_ASSET_NAME_HEX_LEN + 1
+            && s.chars().all(|c| c.is_ascii_hexdigit() || c == '.');
+        if looks_like_unit && PolicyId::from_unit(s).is_ok() {
+            return Err(PolicyIdError::AssetUnit);
+        }
         if s.len() != POLICY_ID_HEX_LEN {
             return Err(PolicyIdError::InvalidLength {
                 expected: POLICY_ID_HEX_LEN,
@@ -123,6 +205,56 @@ impl AsRef<str> for PolicyId {
     }
 }
 
+impl Borrow<str> for PolicyId {
+    fn borrow(&self) -> &str {
+        &self.0
+    }
+}
+
+impl PartialEq<str> for PolicyId {
+    fn eq(&self, other: &str) -> bool {
+        self.0 == other
+    }
+}
+
+impl PartialEq<&str> for PolicyId {
+    fn eq(&self, other: &&str) -> bool {
+        self.0 == *other
+    }
+}
+
+impl PartialEq<String> for PolicyId {
+    fn eq(&self, other: &String) -> bool {
+        &self.0 == other
+    }
+}
+
+impl From<PolicyId> for String {
+    fn from(policy_id: PolicyId) -> Self {
+        policy_id.0
+    }
+}
+
+impl From<&PolicyId> for String {
+    fn from(policy_id: &PolicyId) -> Self {
+        policy_id.0.clone()
+    }
+}
+
+impl TryFrom<String> for PolicyId {
+    type Error = PolicyIdError;
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        Self::new(s)
+    }
+}
+
+impl TryFrom<&str> for PolicyId {
+    type Error = PolicyIdError;
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        Self::new(s)
+    }
+}
+
 impl Serialize for PolicyId {
     fn serialize<S: Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
         s.serialize_str(&self.0)
@@ -139,8 +271,15 @@ impl<'de> Deserialize<'de> for PolicyId {
 #[derive(Debug, Clone, PartialEq, Eq)]
 #[cfg_attr(feature = "openapi", derive(ToSchema))]
 pub enum PolicyIdError {
-    InvalidLength { expected: usize, actual: usize },
+    InvalidLength {
+        expected: usize,
+        actual: usize,
+    },
     InvalidFormat,
+    /// A full asset unit (policy id + asset name) was given
+    AssetUnit,
+    /// Not `script1…` bech32, or the checksum doesn't match
+    InvalidBech32,
 }
 
 impl fmt::Display for PolicyIdError {
@@ -152,9 +291,12 @@ impl fmt::Display for PolicyIdError {
                     "Invalid policy ID length: expected {expected}, got {actual}"
                 )
             }
-            Self::InvalidFormat => {
-                f.write_str("Invalid policy ID format: must be lowercase hexadecimal")
-            }
+            Self::InvalidFormat => f.write_str("Invalid policy ID format: must be hexadecimal"),
+            Self::AssetUnit => f.write_str(
+                "Invalid policy ID: got an asset unit (policy ID + asset name); \
+                 use PolicyId::from_unit for the policy half",
+            ),
+            Self::InvalidBech32 => f.write_str("Invalid policy ID: not bech32 with hrp \"script\""),
         }
     }
 }
@@ -221,6 +363,49 @@ mod tests {
         assert_eq!(p.as_str(), known_good());
     }
 
+    #[test]
+    fn lowercases_and_compares_with_str() {
+        let p = PolicyId::new(known_good().to_uppercase()).unwrap();
+        assert_eq!(p, known_good());
+        assert_eq!(String::from(&p), known_good());
+    }
+
+    #[test]
+    fn asset_unit_is_not_a_policy_id() {
+        let unit = format!("{}50697261746531303836", known_good());
+        assert_eq!(PolicyId::new(&unit), Err(PolicyIdError::AssetUnit));
+        assert_eq!(
+            PolicyId::new(format!("{}.50697261746531303836", known_good())),
+            Err(PolicyIdError::AssetUnit)
+        );
+
+        let p = PolicyId::from_unit(&unit).unwrap();
+        assert_eq!(p.as_str(), known_good());
+        assert_eq!(PolicyId::from_unit(known_good()).unwrap(), p);
+        assert!(PolicyId::from_unit("abc").is_err());
+        assert!(PolicyId::from_unit(&format!("{}zz", known_good())).is_err());
+    }
+
+    #[cfg(feature = "cip14")]
+    #[test]
+    fn bech32_round_trip() {
+        let p = PolicyId::new(known_good()).unwrap();
+        let bech = p.to_bech32();
+        assert!(bech.starts_with("script1"));
+        assert_eq!(PolicyId::from_bech32(&bech).unwrap(), p);
+        assert_eq!(PolicyId::parse_any(&bech).unwrap(), p);
+
+        // Flipping a character breaks the checksum
+        let mut corrupted = bech.into_bytes();
+        let last = corrupted.len() - 1;
+        corrupted[last] = if corrupted[last] == b'q' { b'p' } else { b'q' };
+        let corrupted = String::from_utf8(corrupted).unwrap();
+        assert_eq!(
+            PolicyId::from_bech32(&corrupted),
+            Err(PolicyIdError::InvalidBech32)
+        );
+    }
+
     #[test]
     fn display_matches_inner() {
         let p = PolicyId::new(known_good()).unwrap();
@@ -0,0 +1,308 @@
+//! Asset provenance — the ownership chain of a single asset.
+//!
+//! [`Provenance`] is built from [`OwnershipEvent`]s pulled from whichever
+//! indexer is at hand (maestro asset transactions, utxorpc, ...), and answers
+//! holder-duration questions such as "has the current holder held since slot
+//! N" for role gating.
+//!
+//! Owners are opaque strings. Use stake addresses where possible so moving an
+//! asset between addresses of the same wallet isn't treated as a sale;
+//! consecutive events for the same owner are merged into one holding.
+//!
+//! ```
+//! use cardano_assets::{AssetId, OwnershipEvent, Provenance, TxHash, SLOTS_PER_DAY};
+//!
+//! let asset = AssetId::new_unchecked(
+//!     "b3dab69f7e6100849434fb1781e34bd12a916557f6231b8d2629b6f6".to_string(),
+//!     "50697261746531303836".to_string(),
+//! );
+//! let tx = |n: u8| TxHash::new_unchecked(format!("{n:064x}"));
+//!
+//! let provenance = Provenance::from_events(
+//!     asset,
+//!     vec![
+//!         OwnershipEvent::mint(tx(1), 1_000, "stake1minter"),
+//!         OwnershipEvent::transfer(tx(2), 5_000, "stake1holder"),
+//!     ],
+//! );
+//!
+//! let now = 5_000 + 200 * SLOTS_PER_DAY;
+//! assert_eq!(provenance.current_holder(), Some("stake1holder"));
+//! assert!(provenance.held_since(now - 180 * SLOTS_PER_DAY));
+//! ```
+
+use serde::{Deserialize, Serialize};
+
+use crate::{AssetId, TxHash};
+
+#[cfg(feature = "openapi")]
+use utoipa::ToSchema;
+
+/// Mainnet slots are one second long (since Shelley)
+pub const SLOTS_PER_DAY: u64 = 86_400;
+
+/// What moved the asset
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum OwnershipKind {
+    Mint,
+    Transfer,
+    Burn,
+}
+
+/// A single change of ownership, as reported by an indexer
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+pub struct OwnershipEvent {
+    pub kind: OwnershipKind,
+    pub tx_hash: TxHash,
+    pub slot: u64,
+    /// Receiving owner; `None` for burns
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub owner: Option<String>,
+}
+
+impl OwnershipEvent {
+    pub fn mint(tx_hash: TxHash, slot: u64, owner: impl Into<String>) -> Self {
+        Self {
+            kind: OwnershipKind::Mint,
+            tx_hash,
+            slot,
+            owner: Some(owner.into()),
+        }
+    }
+
+    pub fn transfer(tx_hash: TxHash, slot: u64, owner: impl Into<String>) -> Self {
+        Self {
+            kind: OwnershipKind::Transfer,
+            tx_hash,
+            slot,
+            owner: Some(owner.into()),
+        }
+    }
+
+    pub fn burn(tx_hash: TxHash, slot: u64) -> Self {
+        Self {
+            kind: OwnershipKind::Burn,
+            tx_hash,
+            slot,
+            owner: None,
+        }
+    }
+}
+
+/// A continuous period of ownership
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+pub struct Holding {
+    pub owner: String,
+    /// Transaction that delivered the asset to `owner`
+    pub tx_hash: TxHash,
+    /// Slot `owner` acquired the asset
+    pub acquired_slot: u64,
+    /// Slot `owner` gave the asset up; `None` while still held
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub released_slot: Option<u64>,
+}
+
+/// Ownership chain of one asset, oldest holding first
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+pub struct Provenance {
+    pub asset_id: AssetId,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mint_tx: Option<TxHash>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub burn_tx: Option<TxHash>,
+    pub holdings: Vec<Holding>,
+}
+
+impl Provenance {
+    /// Build the chain from indexer events in any order
+    ///
+    /// Events are sorted by slot (stable, so same-slot events keep their
+    /// input order). Events after a burn are ignored unless the asset is
+    /// minted again.
+    pub fn from_events(
+        asset_id: AssetId,
+        events: impl IntoIterator<Item = OwnershipEvent>,
+    ) -> Self {
+        let mut events: Vec<OwnershipEvent> = events.into_iter().collect();
+        events.sort_by_key(|e| e.slot);
+
+        let mut provenance = Self {
+            asset_id,
+            mint_tx: None,
+            burn_tx: None,
+            holdings: Vec::new(),
+        };
+        for event in events {
+            provenance.push(event);
+        }
+        provenance
+    }
+
+    /// Append an event in chain order
+    pub fn push(&mut self, event: OwnershipEvent) {
+        if self.burn_tx.is_some() && event.kind != OwnershipKind::Mint {
+            return;
+        }
+
+        match event.kind {
+            OwnershipKind::Mint => {
+                self.mint_tx.get_or_insert_with(|| event.tx_hash.clone());
+                self.burn_tx = None;
+            }
+            OwnershipKind::Burn => {
+                self.release(event.slot);
+                self.burn_tx = Some(event.tx_hash);
+                return;
+            }
+            OwnershipKind::Transfer => {}
+        }
+
+        let Some(owner) = event.owner else {
+            return;
+        };
+        if self.current_holder() == Some(owner.as_str()) {
+            return;
+        }
+
+        self.release(event.slot);
+        self.holdings.push(Holding {
+            owner,
+            tx_hash: event.tx_hash,
+            acquired_slot: event.slot,
+            released_slot: None,
+        });
+    }
+
+    fn release(&mut self, slot: u64) {
+        if let Some(current) = self.holdings.last_mut() {
+            current.released_slot.get_or_insert(slot);
+        }
+    }
+
+    /// Whether the asset has been burned (and not reminted)
+    #[must_use]
+    pub fn is_burned(&self) -> bool {
+        self.burn_tx.is_some()
+    }
+
+    /// The holding in progress, if the asset exists and has an owner
+    #[must_use]
+    pub fn current(&self) -> Option<&Holding> {
+        self.holdings
+            .last()
+            .filter(|holding| holding.released_slot.is_none())
+    }
+
+    #[must_use]
+    pub fn current_holder(&self) -> Option<&str> {
+        self.current().map(|holding| holding.owner.as_str())
+    }
+
+    /// The current holder has held the asset since at or before `slot`
+    #[must_use]
+    pub fn held_since(&self, slot: u64) -> bool {
+        self.current()
+            .is_some_and(|holding| holding.acquired_slot <= slot)
+    }
+
+    /// Slots the current holder has held the asset as of `now_slot`
+    #[must_use]
+    pub fn holding_duration(&self, now_slot: u64) -> Option<u64> {
+        self.current()
+            .map(|holding| now_slot.saturating_sub(holding.acquired_slot))
+    }
+
+    /// Up to `n` owners before the current one, most recent first
+    ///
+    /// Once burned there is no current holder, so the last holder counts as
+    /// previous.
+    #[must_use]
+    pub fn previous_owners(&self, n: usize) -> Vec<&str> {
+        let skip = usize::from(self.current().is_some());
+        self.holdings
+            .iter()
+            .rev()
+            .skip(skip)
+            .take(n)
+            .map(|holding| holding.owner.as_str())
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn asset() -> AssetId {
+        AssetId::new_unchecked(
+            "b3dab69f7e6100849434fb1781e34bd12a916557f6231b8d2629b6f6".to_string(),
+            "50697261746531303836".to_string(),
+        )
+    }
+
+    fn tx(n: u8) -> TxHash {
+        TxHash::new_unchecked(format!("{n:064x}"))
+    }
+
+    #[test]
+    fn test_chain_from_unordered_events() {
+        let provenance = Provenance::from_events(
+            asset(),
+            vec![
+                OwnershipEvent::transfer(tx(3), 300, "carol"),
+                OwnershipEvent::mint(tx(1), 100, "alice"),
+                OwnershipEvent::transfer(tx(2), 200, "bob"),
+                // Moved between two addresses of the same wallet
+                OwnershipEvent::transfer(tx(4), 400, "carol"),
+            ],
+        );
+
+        assert_eq!(provenance.mint_tx, Some(tx(1)));
+        assert_eq!(provenance.holdings.len(), 3);
+        assert_eq!(provenance.holdings[0].released_slot, Some(200));
+        assert_eq!(provenance.current_holder(), Some("carol"));
+        assert_eq!(provenance.current().unwrap().tx_hash, tx(3));
+
+        assert!(provenance.held_since(300));
+        assert!(provenance.held_since(1_000));
+        assert!(!provenance.held_since(299));
+        assert_eq!(provenance.holding_duration(1_300), Some(1_000));
+
+        assert_eq!(provenance.previous_owners(1), vec!["bob"]);
+        assert_eq!(provenance.previous_owners(5), vec!["bob", "alice"]);
+    }
+
+    #[test]
+    fn test_burn_ends_ownership() {
+        let provenance = Provenance::from_events(
+            asset(),
+            vec![
+                OwnershipEvent::mint(tx(1), 100, "alice"),
+                OwnershipEvent::burn(tx(2), 200),
+                OwnershipEvent::transfer(tx(3), 300, "bob"),
+            ],
+        );
+
+        assert!(provenance.is_burned());
+        assert_eq!(provenance.current_holder(), None);
+        assert!(!provenance.held_since(1_000));
+        assert_eq!(provenance.previous_owners(1), vec!["alice"]);
+    }
+
+    #[test]
+    fn test_serde_roundtrip() {
+        let provenance =
+            Provenance::from_events(asset(), vec![OwnershipEvent::mint(tx(1), 100, "alice")]);
+        let json = serde_json::to_value(&provenance).unwrap();
+        assert!(json.get("burn_tx").is_none());
+        assert!(json["holdings"][0].get("released_slot").is_none());
+
+        let parsed: Provenance = serde_json::from_value(json).unwrap();
+        assert_eq!(parsed, provenance);
+    }
+}
@@ -0,0 +1,408 @@
+//! Holder raffles weighted from an ownership snapshot.
+//!
+//! A snapshot (from an indexer, cnft.tools, ...) is flattened into
+//! [`RaffleHolding`]s, and [`RaffleWeights`] turns those into a
+//! [`WeightTable`]: one row per stake key with its asset count, rarity bonus
+//! and final entries. The table is sorted by stake key and serializes as-is,
+//! so it can be published before the draw and anyone can re-run
+//! [`WeightTable::draw`] with the same seed (e.g. a block hash) to check the
+//! winners.
+//!
+//! ```
+//! use cardano_assets::{AssetId, AssetRarity, RaffleHolding, RaffleWeights};
+//!
+//! let asset = |n: u8| AssetId::new_unchecked("ab".repeat(28), format!("{n:02x}"));
+//! let holdings = vec![
+//!     RaffleHolding::new("stake1alice", asset(1)).with_rarity_rank(1),
+//!     RaffleHolding::new("stake1alice", asset(2)).with_rarity_rank(80),
+//!     RaffleHolding::new("stake1bob", asset(3)).with_rarity_rank(100),
+//! ];
+//!
+//! let table = RaffleWeights::new()
+//!     .per_asset(10)
+//!     .rarity_bonus(AssetRarity::Legendary, 50)
+//!     .max_entries(100)
+//!     .build(holdings);
+//!
+//! assert_eq!(table.entries_for("stake1alice"), 70);
+//! assert_eq!(table.entries_for("stake1bob"), 10);
+//!
+//! let winners = table.draw(b"block hash", 1);
+//! assert_eq!(winners, table.draw(b"block hash", 1));
+//! ```
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "openapi")]
+use utoipa::ToSchema;
+
+use crate::{AssetId, AssetRarity};
+
+/// One asset held by one stake key at snapshot time
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+pub struct RaffleHolding {
+    pub stake_key: String,
+    pub asset_id: AssetId,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rarity_rank: Option<u32>,
+}
+
+impl RaffleHolding {
+    pub fn new(stake_key: impl Into<String>, asset_id: AssetId) -> Self {
+        Self {
+            stake_key: stake_key.into(),
+            asset_id,
+            rarity_rank: None,
+        }
+    }
+
+    /// Rank 0 means unranked, matching cnft.tools
+    pub fn with_rarity_rank(mut self, rank: u32) -> Self {
+        self.rarity_rank = (rank > 0).then_some(rank);
+        self
+    }
+}
+
+/// Rules for turning holdings into raffle entries
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+pub struct RaffleWeights {
+    per_asset: u64,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    rarity_bonus: Vec<RarityBonus>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    max_rank: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    max_entries: Option<u64>,
+}
+
+impl Default for RaffleWeights {
+    fn default() -> Self {
+        Self {
+            per_asset: 1,
+            rarity_bonus: Vec::new(),
+            max_rank: None,
+            max_entries: None,
+        }
+    }
+}
+
+impl RaffleWeights {
+    /// One entry per asset, no bonuses, no cap
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn per_asset(mut self, weight: u64) -> Self {
+        self.per_asset = weight;
+        self
+    }
+
+    /// Extra entries for each asset in `tier`, on top of `per_asset`
+    pub fn rarity_bonus(mut self, tier: AssetRarity, bonus: u64) -> Self {
+        self.rarity_bonus.retain(|existing| existing.tier != tier);
+        self.rarity_bonus.push(RarityBonus { tier, bonus });
+        self
+    }
+
+    /// Collection size used to bucket ranks into tiers
+    ///
+    /// Defaults to the highest rank among the holdings, which undercounts
+    /// when the snapshot doesn't cover the whole collection.
+    pub fn max_rank(mut self, max_rank: u32) -> Self {
+        self.max_rank = Some(max_rank);
+        self
+    }
+
+    /// Cap on entries per stake key
+    pub fn max_entries(mut self, max_entries: u64) -> Self {
+        self.max_entries = Some(max_entries);
+        self
+    }
+
+    /// Compute the weight table
+    ///
+    /// An asset reported more than once counts for the last holder listed.
+    pub fn build(&self, holdings: impl IntoIterator<Item = RaffleHolding>) -> WeightTable {
+        let mut by_asset: BTreeMap<AssetId, RaffleHolding> = BTreeMap::new();
+        for holding in holdings {
+            by_asset.insert(holding.asset_id.clone(), holding);
+        }
+
+        let max_rank = self.max_rank.or_else(|| {
+            by_asset
+                .values()
+                .filter_map(|holding| holding.rarity_rank)
+                .max()
+        });
+
+        let mut rows: BTreeMap<String, WeightEntry> = BTreeMap::new();
+        for holding in by_asset.into_values() {
+            let bonus = match (holding.rarity_rank, max_rank) {
+                (Some(rank), Some(max_rank)) if max_rank > 0 => {
+                    self.bonus_for(&AssetRarity::from_rank(rank, max_rank))
+                }
+                _ => 0,
+            };
+            let row = rows
+                .entry(holding.stake_key.clone())
+                .or_insert_with(|| WeightEntry::empty(holding.stake_key));
+            row.assets += 1;
+            row.base = row.base.saturating_add(self.per_asset);
+            row.bonus = row.bonus.saturating_add(bonus);
+        }
+
+        let entries: Vec<WeightEntry> = rows
+            .into_values()
+            .map(|mut row| {
+                let uncapped = row.base.saturating_add(row.bonus);
+                row.entries = self.max_entries.map_or(uncapped, |max| uncapped.min(max));
+                row
+            })
+            .collect();
+        let total_entries = entries
+            .iter()
+            .fold(0u64, |total, row| total.saturating_add(row.entries));
+
+        WeightTable {
+            rules: self.clone(),
+            entries,
+            total_entries,
+        }
+    }
+
+    fn bonus_for(&self, tier: &AssetRarity) -> u64 {
+        self.rarity_bonus
+            .iter()
+            .find(|rule| rule.tier == *tier)
+            .map_or(0, |rule| rule.bonus)
+    }
+}
+
+/// Extra entries per asset in a rarity tier
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+pub struct RarityBonus {
+    pub tier: AssetRarity,
+    pub bonus: u64,
+}
+
+/// One stake key's row in a [`WeightTable`]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+pub struct WeightEntry {
+    pub stake_key: String,
+    pub assets: u32,
+    /// `assets * per_asset`
+    pub base: u64,
+    /// Sum of rarity bonuses
+    pub bonus: u64,
+    /// `base + bonus`, capped at `max_entries`
+    pub entries: u64,
+}
+
+impl WeightEntry {
+    fn empty(stake_key: String) -> Self {
+        Self {
+            stake_key,
+            assets: 0,
+            base: 0,
+            bonus: 0,
+            entries: 0,
+        }
+    }
+}
+
+/// Per-stake-key entries, sorted by stake key, with the rules that produced them
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+pub struct WeightTable {
+    pub rules: RaffleWeights,
+    pub entries: Vec<WeightEntry>,
+    pub total_entries: u64,
+}
+
+impl WeightTable {
+    pub fn get(&self, stake_key: &str) -> Option<&WeightEntry> {
+        self.entries
+            .binary_search_by(|row| row.stake_key.as_str().cmp(stake_key))
+            .ok()
+            .map(|i| &self.entries[i])
+    }
+
+    /// Entries held by `stake_key`, 0 if absent
+    pub fn entries_for(&self, stake_key: &str) -> u64 {
+        self.get(stake_key).map_or(0, |row| row.entries)
+    }
+
+    /// Draw up to `count` distinct winners, weighted by entries
+    ///
+    /// The same table and seed always give the same winners in the same
+    /// order. Stake keys with no entries never win.
+    pub fn draw(&self, seed: &[u8], count: usize) -> Vec<String> {
+        let mut rng = SplitMix64::from_seed(seed);
+        let mut remaining: Vec<&WeightEntry> =
+            self.entries.iter().filter(|row| row.entries > 0).collect();
+        let mut total: u64 = remaining.iter().map(|row| row.entries).sum();
+        let mut winners = Vec::with_capacity(count.min(remaining.len()));
+
+        while winners.len() < count && total > 0 {
+            let mut ticket = rng.below(total);
+            let index = remaining
+                .iter()
+                .position(|row| {
+                    if ticket < row.entries {
+                        true
+                    } else {
+                        ticket -= row.entries;
+                        false
+                    }
+                })
+                .expect("ticket is below the total");
+            let winner = remaining.remove(index);
+            total -= winner.entries;
+            winners.push(winner.stake_key.clone());
+        }
+        winners
+    }
+}
+
+/// splitmix64, seeded with the FNV-1a hash of the seed bytes
+///
+/// Not cryptographic; the seed is expected to come from somewhere public and
+/// unpredictable at snapshot time, such as a future block hash.
+struct SplitMix64(u64);
+
+impl SplitMix64 {
+    fn from_seed(seed: &[u8]) -> Self {
+        let hash = seed.iter().fold(0xcbf2_9ce4_8422_2325u64, |hash, byte| {
+            (hash ^ u64::from(*byte)).wrapping_mul(0x0000_0100_0000_01b3)
+        });
+        Self(hash)
+    }
+
+    fn next(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// Uniform in `0..bound` (rejection sampling, no modulo bias)
+    fn below(&mut self, bound: u64) -> u64 {
+        let zone = u64::MAX - u64::MAX % bound;
+        loop {
+            let value = self.next();
+            if value < zone {
+                return value % bound;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn asset(n: u32) -> AssetId {
+        AssetId::new_unchecked("ab".repeat(28), format!("{n:04x}"))
+    }
+
+    fn snapshot() -> Vec<RaffleHolding> {
+        vec![
+            RaffleHolding::new("stake1carol", asset(1)).with_rarity_rank(1),
+            RaffleHolding::new("stake1alice", asset(2)).with_rarity_rank(30),
+            RaffleHolding::new("stake1alice", asset(3)).with_rarity_rank(90),
+            RaffleHolding::new("stake1bob", asset(4)),
+            RaffleHolding::new("stake1bob", asset(5)).with_rarity_rank(100),
+        ]
+    }
+
+    #[test]
+    fn test_weight_table() {
+        let table = RaffleWeights::new()
+            .per_asset(10)
+            .rarity_bonus(AssetRarity::Legendary, 100)
+            .rarity_bonus(AssetRarity::Rare, 5)
+            .max_entries(50)
+            .build(snapshot());
+
+        let keys: Vec<&str> = table.entries.iter().map(|r| r.stake_key.as_str()).collect();
+        assert_eq!(keys, ["stake1alice", "stake1bob", "stake1carol"]);
+
+        let alice = table.get("stake1alice").unwrap();
+        assert_eq!((alice.assets, alice.base, alice.bonus), (2, 20, 5));
+        assert_eq!(alice.entries, 25);
+
+        let carol = table.get("stake1carol").unwrap();
+        assert_eq!((carol.base, carol.bonus, carol.entries), (10, 100, 50));
+
+        assert_eq!(table.entries_for("stake1bob"), 20);
+        assert_eq!(table.entries_for("stake1nobody"), 0);
+        assert_eq!(table.total_entries, 95);
+    }
+
+    #[test]
+    fn test_duplicate_assets_count_once() {
+        let mut holdings = snapshot();
+        holdings.push(RaffleHolding::new("stake1bob", asset(1)));
+        let table = RaffleWeights::new().build(holdings);
+        assert_eq!(table.get("stake1carol"), None);
+        assert_eq!(table.entries_for("stake1bob"), 3);
+    }
+
+    #[test]
+    fn test_build_is_order_independent() {
+        let weights = RaffleWeights::new().rarity_bonus(AssetRarity::Legendary, 3);
+        let mut reversed = snapshot();
+        reversed.reverse();
+        assert_eq!(weights.build(snapshot()), weights.build(reversed));
+    }
+
+    #[test]
+    fn test_draw_is_deterministic_and_distinct() {
+        let table = RaffleWeights::new().per_asset(3).build(snapshot());
+
+        let winners = table.draw(b"seed", 3);
+        assert_eq!(winners, table.draw(b"seed", 3));
+        assert_eq!(winners.len(), 3);
+        let mut unique = winners.clone();
+        unique.sort();
+        unique.dedup();
+        assert_eq!(unique.len(), 3);
+
+        assert_eq!(table.draw(b"seed", 10).len(), 3);
+        assert!(table.draw(b"seed", 0).is_empty());
+    }
+
+    #[test]
+    fn test_draw_follows_weights() {
+        let mut holdings: Vec<RaffleHolding> = (1..=9)
+            .map(|n| RaffleHolding::new("stake1whale", asset(n)))
+            .collect();
+        holdings.push(RaffleHolding::new("stake1minnow", asset(10)));
+        let table = RaffleWeights::new().build(holdings);
+
+        let whale_wins = (0u32..1000)
+            .filter(|seed| table.draw(&seed.to_le_bytes(), 1) == ["stake1whale"])
+            .count();
+        assert!((850..950).contains(&whale_wins), "{whale_wins}");
+
+        let zero = RaffleWeights::new().per_asset(0).build(snapshot());
+        assert!(zero.draw(b"seed", 1).is_empty());
+    }
+
+    #[test]
+    fn test_table_round_trips() {
+        let table = RaffleWeights::new()
+            .rarity_bonus(AssetRarity::Epic, 2)
+            .max_rank(1000)
+            .build(snapshot());
+        let json = serde_json::to_string(&table).unwrap();
+        assert_eq!(serde_json::from_str::<WeightTable>(&json).unwrap(), table);
+    }
+}
@@ -0,0 +1,185 @@
+//! Metadata in no known shape.
+//!
+//! [`AssetMetadata::Raw`] catches any metadata object the typed variants
+//! reject, so an asset from an unusual collection is kept rather than
+//! skipped. Converting it to an [`Asset`] is best effort: the name and
+//! image are looked up under the keys collections commonly use, and traits
+//! come from the shape-based [`extract_traits`](crate::extract::extract_traits).
+
+use std::collections::HashMap;
+
+use serde_json::Value;
+
+use crate::extract::extract_traits;
+use crate::{Asset, AssetFile, AssetMetadata, PrimitiveOrList, Traits};
+
+/// Keys tried, in order, for the display name
+const NAME_KEYS: &[&str] = &["name", "Name", "title", "Title", "asset_name", "assetName"];
+
+/// Keys tried, in order, for the image URL
+const IMAGE_KEYS: &[&str] = &[
+    "image",
+    "Image",
+    "img",
+    "image_url",
+    "imageUrl",
+    "thumbnail",
+];
+
+/// Keys tried, in order, for the image media type
+const MEDIA_TYPE_KEYS: &[&str] = &["mediaType", "media_type", "mediatype", "MediaType"];
+
+impl AssetMetadata {
+    /// The untouched metadata, if it matched no known shape
+    pub fn raw(&self) -> Option<&Value> {
+        match self {
+            AssetMetadata::Raw { value } => Some(value),
+            _ => None,
+        }
+    }
+}
+
+/// First of `keys` holding a non-empty string, or a chunked string array
+pub(crate) fn string_field(value: &Value, keys: &[&str]) -> Option<String> {
+    keys.iter().find_map(|key| {
+        let text = match value.get(*key)? {
+            Value::String(s) => s.trim().to_string(),
+            Value::Number(n) => n.to_string(),
+            chunks @ Value::Array(_) => {
+                serde_json::from_value::<PrimitiveOrList<String>>(chunks.clone())
+                    .ok()?
+                    .dechunked()
+            }
+            _ => return None,
+        };
+        (!text.is_empty()).then_some(text)
+    })
+}
+
+/// The `files` entries that parse; malformed ones are skipped
+pub(crate) fn files(value: &Value) -> Vec<AssetFile> {
+    value
+        .get("files")
+        .and_then(Value::as_array)
+        .map(|files| {
+            files
+                .iter()
+                .filter_map(|file| serde_json::from_value(file.clone()).ok())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// The image URL, falling back to the first image file
+pub(crate) fn image(value: &Value) -> Option<String> {
+    string_field(value, IMAGE_KEYS).or_else(|| {
+        let files = files(value);
+        files
+            .iter()
+            .find(|file| file.media_type().starts_with("image/"))
+            .or(files.first())
+            .map(AssetFile::get_src)
+    })
+}
+
+/// The top-level media type, falling back to the file matching `image`
+pub(crate) fn media_type(value: &Value) -> Option<String> {
+    string_field(value, MEDIA_TYPE_KEYS).or_else(|| {
+        let image = image(value)?;
+        files(value)
+            .iter()
+            .find(|file| file.get_src() == image)
+            .map(|file| file.media_type().to_string())
+    })
+}
+
+/// Best-effort [`Asset`] from metadata in no known shape.
+///
+/// The name is left empty when none is found, as for
+/// [`AssetMetadata::Untitled`]; callers fill it in from the asset name.
+pub(crate) fn asset_from_raw(value: Value, media_type: Option<String>) -> Asset {
+    let name = string_field(&value, NAME_KEYS).unwrap_or_default();
+    let image = image(&value).unwrap_or_default();
+    let traits = match value {
+        Value::Object(fields) => {
+            // Whatever was read as the name, image or media type isn't a trait
+            let rest: HashMap<String, Value> = fields
+                .into_iter()
+                .filter(|(key, _)| {
+                    ![NAME_KEYS, IMAGE_KEYS, MEDIA_TYPE_KEYS]
+                        .iter()
+                        .any(|keys| keys.contains(&key.as_str()))
+                })
+                .collect();
+            extract_traits(&rest)
+        }
+        _ => Traits::new(),
+    };
+
+    Asset {
+        name,
+        image,
+        media_type,
+        traits,
+        rarity_rank: None,
+        quantity: 1,
+        tags: vec![],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unknown_shape_falls_back_to_raw() {
+        // `name` is a number and `image` sits under a non-standard key, so
+        // no typed variant matches
+        let json = r#"{
+            "name": 42,
+            "img": ["ipfs://QmXAybY8AvnNfsEgiZFxoKre1ujf", "f5PxzU21tUuSVBEwkD"],
+            "files": [{"mediaType": "image/png", "src": "ipfs://QmXAybY8AvnNfsEgiZFxoKre1ujff5PxzU21tUuSVBEwkD"}],
+            "attributes": {"Background": "Teal", "Eyes": "Laser"}
+        }"#;
+        let metadata: AssetMetadata = serde_json::from_str(json).unwrap();
+        assert!(metadata.raw().is_some());
+
+        let asset = Asset::from(metadata);
+        assert_eq!(asset.name, "42");
+        assert_eq!(
+            asset.image,
+            "ipfs://QmXAybY8AvnNfsEgiZFxoKre1ujff5PxzU21tUuSVBEwkD"
+        );
+        assert_eq!(asset.media_type.as_deref(), Some("image/png"));
+        assert_eq!(
+            asset.traits.get_single("Background").as_deref(),
+            Some("Teal")
+        );
+        assert_eq!(asset.traits.get_single("Eyes").as_deref(), Some("Laser"));
+    }
+
+    #[test]
+    fn test_raw_without_name_or_image() {
+        let metadata: AssetMetadata =
+            serde_json::from_str(r#"{"files": [{"mediaType": "video/mp4", "src": "ar://clip"}]}"#)
+                .unwrap();
+        let value = metadata.raw().cloned().unwrap();
+
+        let asset = Asset::from(metadata);
+        assert_eq!(asset.name, "");
+        assert_eq!(asset.image, "ar://clip");
+        assert_eq!(asset.media_type.as_deref(), Some("video/mp4"));
+
+        // Round-trips as the original document
+        let metadata: AssetMetadata = serde_json::from_value(value.clone()).unwrap();
+        assert_eq!(serde_json::to_value(&metadata).unwrap(), value);
+    }
+
+    #[test]
+    fn test_known_shapes_are_not_raw() {
+        let metadata: AssetMetadata =
+            serde_json::from_str(r#"{"name": "Snek #1", "image": "ipfs://Qm1", "hat": "red"}"#)
+                .unwrap();
+        assert!(metadata.raw().is_none());
+    }
+}
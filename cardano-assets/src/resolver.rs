@@ -1,8 +1,12 @@
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
+#[cfg(feature = "openapi")]
+use utoipa::ToSchema;
+
 /// Classification of a Cardano native token's fungibility.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
 #[serde(rename_all = "snake_case")]
 pub enum TokenType {
     /// Non-fungible token (quantity = 1, unique asset name).
@@ -28,6 +32,7 @@ impl TokenType {
 
 /// Source that verified a policy's identity.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
 #[serde(rename_all = "snake_case")]
 pub enum VerificationSource {
     /// Cardano Token Registry (CIP-26).
@@ -53,6 +58,7 @@ impl VerificationSource {
 
 /// Warning/safety tag applied to a policy.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
 #[serde(rename_all = "snake_case")]
 pub enum PolicyTag {
     /// Known rugpull.
@@ -90,6 +96,7 @@ impl PolicyTag {
 /// that any UI or business logic can use without needing async access
 /// to collection APIs.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
 pub struct ResolvedPolicy {
     /// Human-readable name (e.g., "SpaceBudz", "HOSKY", "Black Flag").
     pub name: String,
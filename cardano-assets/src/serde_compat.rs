@@ -0,0 +1,189 @@
+//! camelCase JSON for TypeScript consumers.
+//!
+//! The shared types serialize with snake_case field names (as Rust names
+//! them); anvil's API and most of our frontends use camelCase. With the
+//! `serde_compat` feature:
+//!
+//! - the externally consumed types here and in `tx_insights` accept their
+//!   multi-word fields under either name when deserializing;
+//! - [`CamelCase`] serializes any of them with camelCase field names.
+//!
+//! Only field names change. Enum tags and values (`"type": "offer_create"`,
+//! `"on_sale"`) stay snake_case, and the keys of data maps (trait names under
+//! `traits`, addresses under `counterparties`) are left as they are.
+//!
+//! ```
+//! use cardano_assets::serde_compat::CamelCase;
+//! use cardano_assets::{Asset, Traits};
+//!
+//! let asset = Asset {
+//!     name: "Pirate #1".to_string(),
+//!     image: "ipfs://QmPirate".to_string(),
+//!     media_type: Some("image/png".to_string()),
+//!     traits: Traits::new(),
+//!     rarity_rank: Some(12),
+//!     quantity: 1,
+//!     tags: vec![],
+//! };
+//!
+//! let camel = serde_json::to_value(CamelCase(&asset)).unwrap();
+//! assert_eq!(camel["mediaType"], "image/png");
+//! assert_eq!(camel["rarityRank"], 12);
+//!
+//! // Either shape deserializes
+//! let back: Asset = serde_json::from_value(camel).unwrap();
+//! assert_eq!(back.rarity_rank, Some(12));
+//! ```
+
+use serde::{Serialize, Serializer};
+use serde_json::{Map, Value};
+
+/// Fields whose values are maps keyed by data rather than field names
+pub const DATA_MAP_FIELDS: &[&str] = &["traits", "counterparties"];
+
+/// Serializes the wrapped value with camelCase field names
+#[derive(Debug, Clone, Copy)]
+pub struct CamelCase<T>(pub T);
+
+impl<T: Serialize> Serialize for CamelCase<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let value = serde_json::to_value(&self.0).map_err(serde::ser::Error::custom)?;
+        to_camel_case(value).serialize(serializer)
+    }
+}
+
+/// `price_lovelace` -> `priceLovelace`
+pub fn camel_case_key(key: &str) -> String {
+    let mut out = String::with_capacity(key.len());
+    let mut upper = false;
+    for c in key.chars() {
+        if c == '_' && !out.is_empty() {
+            upper = true;
+        } else if upper {
+            out.extend(c.to_uppercase());
+            upper = false;
+        } else {
+            out.push(c);
+        }
+    }
+    out
+}
+
+/// Rename the field names of every object in `value` to camelCase, leaving
+/// the keys of [`DATA_MAP_FIELDS`] maps alone
+pub fn to_camel_case(value: Value) -> Value {
+    match value {
+        Value::Object(fields) => Value::Object(
+            fields
+                .into_iter()
+                .map(|(key, value)| {
+                    let value = match value {
+                        // Keep the data keys, but entries may be structs
+                        Value::Object(entries) if DATA_MAP_FIELDS.contains(&key.as_str()) => {
+                            Value::Object(
+                                entries
+                                    .into_iter()
+                                    .map(|(k, v)| (k, to_camel_case(v)))
+                                    .collect::<Map<_, _>>(),
+                            )
+                        }
+                        value => to_camel_case(value),
+                    };
+                    (camel_case_key(&key), value)
+                })
+                .collect(),
+        ),
+        Value::Array(items) => Value::Array(items.into_iter().map(to_camel_case).collect()),
+        other => other,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Asset, AssetTag, CollectionDetails, Traits};
+    use serde_json::json;
+
+    fn asset() -> Asset {
+        let mut traits = Traits::new();
+        traits.insert_single("background_color".to_string(), "Sea Blue".to_string());
+        Asset {
+            name: "Pirate #1".to_string(),
+            image: "ipfs://QmPirate".to_string(),
+            media_type: Some("image/png".to_string()),
+            traits,
+            rarity_rank: Some(12),
+            quantity: 1,
+            tags: vec![AssetTag::OnSale],
+        }
+    }
+
+    #[test]
+    fn test_camel_case_key() {
+        assert_eq!(camel_case_key("price_lovelace"), "priceLovelace");
+        assert_eq!(
+            camel_case_key("price_per_asset_lovelace"),
+            "pricePerAssetLovelace"
+        );
+        assert_eq!(camel_case_key("name"), "name");
+        assert_eq!(camel_case_key("_private"), "_private");
+    }
+
+    #[test]
+    fn test_asset_shapes() {
+        let snake = json!({
+            "name": "Pirate #1",
+            "image": "ipfs://QmPirate",
+            "media_type": "image/png",
+            "traits": { "background_color": ["Sea Blue"] },
+            "rarity_rank": 12,
+            "tags": ["on_sale"]
+        });
+        let camel = json!({
+            "name": "Pirate #1",
+            "image": "ipfs://QmPirate",
+            "mediaType": "image/png",
+            "traits": { "background_color": ["Sea Blue"] },
+            "rarityRank": 12,
+            "tags": ["on_sale"]
+        });
+
+        assert_eq!(serde_json::to_value(asset()).unwrap(), snake);
+        assert_eq!(serde_json::to_value(CamelCase(asset())).unwrap(), camel);
+
+        for shape in [snake, camel] {
+            let asset: Asset = serde_json::from_value(shape).unwrap();
+            assert_eq!(asset.media_type.as_deref(), Some("image/png"));
+            assert_eq!(asset.rarity_rank, Some(12));
+            assert_eq!(
+                asset.traits.get_single("background_color").as_deref(),
+                Some("Sea Blue")
+            );
+        }
+    }
+
+    #[test]
+    fn test_collection_details_camel_case() {
+        let details: CollectionDetails = serde_json::from_value(json!({
+            "policyId": "b3dab69f7e6100849434fb1781e34bd12a916557f6231b8d2629b6f6",
+            "name": "Pirates",
+            "handle": null,
+            "description": null,
+            "royaltyAddress": null,
+            "royaltyPercentage": 5.0,
+            "image": null,
+            "banner": null,
+            "socials": null
+        }))
+        .unwrap();
+        assert_eq!(details.royalty_percentage, 5.0);
+
+        let camel = serde_json::to_value(CamelCase(&details)).unwrap();
+        assert_eq!(
+            camel["policyId"],
+            "b3dab69f7e6100849434fb1781e34bd12a916557f6231b8d2629b6f6"
+        );
+        assert_eq!(camel["royaltyPercentage"], 5.0);
+        assert!(camel.get("royalty_percentage").is_none());
+    }
+}
@@ -0,0 +1,599 @@
+//! Versioned collection snapshot format for backup/restore between environments
+//!
+//! A snapshot is stored as three kinds of object, suited to R2 (or any blob
+//! store) without holding the whole collection in memory:
+//!
+//! - a JSON **header** with the [`CollectionDetails`], [`TraitSummarySorted`]
+//!   and rankings
+//! - **chunks** of assets as newline-delimited [`AssetV2`] JSON
+//! - a JSON [`SnapshotManifest`] listing the Blake2b-256 hash of the header
+//!   and every chunk, plus a content hash over all of them
+//!
+//! [`SnapshotWriter`] emits chunks as assets are pushed (e.g. while paging an
+//! API); write the manifest last so a half-uploaded snapshot is never loadable.
+//! [`SnapshotReader`] verifies each part against the manifest as it's read.
+//!
+//! ```
+//! use cardano_assets::{CollectionDetails, CollectionSnapshot, TraitSummary};
+//!
+//! let collection: CollectionDetails = serde_json::from_str(r#"{
+//!     "policy_id": "b3dab69f7e6100849434fb1781e34bd12a916557f6231b8d2629b6f6",
+//!     "name": "Pirates",
+//!     "royalty_percentage": 5.0
+//! }"#).unwrap();
+//!
+//! let snapshot = CollectionSnapshot {
+//!     collection,
+//!     assets: vec![],
+//!     trait_summary: TraitSummary::default().into(),
+//!     rankings: vec![],
+//! };
+//!
+//! let export = snapshot.export(1_000).unwrap();
+//! let restored =
+//!     CollectionSnapshot::load(&export.manifest, &export.header, &export.chunks).unwrap();
+//! assert_eq!(restored.collection.name, "Pirates");
+//! ```
+
+use std::fmt;
+
+use blake2::digest::{Update, VariableOutput};
+use blake2::Blake2bVar;
+use serde::{Deserialize, Serialize};
+
+use crate::{AssetId, AssetV2, CollectionDetails, PolicyId, TraitSummarySorted};
+
+/// Format version written by this crate
+pub const SNAPSHOT_VERSION: u32 = 1;
+
+/// Assets per chunk when the caller has no preference
+pub const DEFAULT_CHUNK_SIZE: usize = 1_000;
+
+/// Rarity ranking of one asset
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RankEntry {
+    pub asset_id: AssetId,
+    pub rank: u32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub score: Option<f64>,
+}
+
+/// Everything in a snapshot except the assets
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SnapshotHeader {
+    pub version: u32,
+    pub collection: CollectionDetails,
+    pub trait_summary: TraitSummarySorted,
+    #[serde(default)]
+    pub rankings: Vec<RankEntry>,
+}
+
+/// Integrity record for one chunk
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChunkInfo {
+    pub asset_count: u32,
+    pub bytes: u64,
+    /// Hex Blake2b-256 of the chunk bytes
+    pub hash: String,
+}
+
+/// Index of a stored snapshot
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SnapshotManifest {
+    pub version: u32,
+    pub policy_id: PolicyId,
+    pub asset_count: u64,
+    /// Hex Blake2b-256 of the header bytes
+    pub header_hash: String,
+    pub chunks: Vec<ChunkInfo>,
+    /// Hex Blake2b-256 over the header hash and every chunk hash, in order;
+    /// identical snapshots share a content hash
+    pub content_hash: String,
+}
+
+impl SnapshotManifest {
+    /// Object key for chunk `index` under `prefix`, e.g. `snapshots/abc/chunk-00003.ndjson`
+    #[must_use]
+    pub fn chunk_key(prefix: &str, index: usize) -> String {
+        format!("{}/chunk-{index:05}.ndjson", prefix.trim_end_matches('/'))
+    }
+
+    /// Object key for the header under `prefix`
+    #[must_use]
+    pub fn header_key(prefix: &str) -> String {
+        format!("{}/header.json", prefix.trim_end_matches('/'))
+    }
+
+    /// Object key for the manifest under `prefix`
+    #[must_use]
+    pub fn manifest_key(prefix: &str) -> String {
+        format!("{}/manifest.json", prefix.trim_end_matches('/'))
+    }
+
+    fn expected_content_hash(&self) -> String {
+        content_hash(&self.header_hash, &self.chunks)
+    }
+}
+
+/// Why a snapshot couldn't be written or loaded
+#[derive(Debug)]
+pub enum SnapshotError {
+    UnsupportedVersion(u32),
+    /// `part` is `"header"` or `"chunk N"`
+    HashMismatch {
+        part: String,
+        expected: String,
+        actual: String,
+    },
+    /// Manifest's content hash doesn't cover its own part hashes
+    ContentHashMismatch,
+    /// A chunk or the manifest disagrees on how many assets there are
+    CountMismatch {
+        part: String,
+        expected: u64,
+        actual: u64,
+    },
+    /// Fewer or more chunks than the manifest lists
+    ChunkCount {
+        expected: usize,
+        actual: usize,
+    },
+    /// Header collection doesn't match the manifest's policy id
+    PolicyMismatch {
+        expected: PolicyId,
+        actual: PolicyId,
+    },
+    Json(serde_json::Error),
+}
+
+impl fmt::Display for SnapshotError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnsupportedVersion(v) => write!(f, "Unsupported snapshot version {v}"),
+            Self::HashMismatch {
+                part,
+                expected,
+                actual,
+            } => write!(f, "Snapshot {part} hash {actual} does not match {expected}"),
+            Self::ContentHashMismatch => f.write_str("Snapshot manifest content hash is invalid"),
+            Self::CountMismatch {
+                part,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "Snapshot {part} has {actual} assets, expected {expected}"
+            ),
+            Self::ChunkCount { expected, actual } => {
+                write!(f, "Snapshot has {actual} chunks, expected {expected}")
+            }
+            Self::PolicyMismatch { expected, actual } => {
+                write!(
+                    f,
+                    "Snapshot header is for policy {actual}, expected {expected}"
+                )
+            }
+            Self::Json(e) => write!(f, "Snapshot JSON error: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for SnapshotError {}
+
+impl From<serde_json::Error> for SnapshotError {
+    fn from(e: serde_json::Error) -> Self {
+        Self::Json(e)
+    }
+}
+
+/// A whole collection, held in memory
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CollectionSnapshot {
+    pub collection: CollectionDetails,
+    pub assets: Vec<AssetV2>,
+    pub trait_summary: TraitSummarySorted,
+    #[serde(default)]
+    pub rankings: Vec<RankEntry>,
+}
+
+/// Serialized parts of a snapshot, ready to store
+#[derive(Debug, Clone)]
+pub struct SnapshotExport {
+    pub manifest: SnapshotManifest,
+    pub header: Vec<u8>,
+    pub chunks: Vec<Vec<u8>>,
+}
+
+impl CollectionSnapshot {
+    /// Serialize into header, chunks of `chunk_size` assets, and manifest
+    pub fn export(&self, chunk_size: usize) -> Result<SnapshotExport, SnapshotError> {
+        let mut writer = SnapshotWriter::new(
+            self.collection.clone(),
+            self.trait_summary.clone(),
+            self.rankings.clone(),
+            chunk_size,
+        )?;
+
+        let mut chunks = Vec::new();
+        for asset in &self.assets {
+            if let Some(chunk) = writer.push(asset)? {
+                chunks.push(chunk);
+            }
+        }
+
+        let header = writer.header().to_vec();
+        let (last, manifest) = writer.finish();
+        chunks.extend(last);
+
+        Ok(SnapshotExport {
+            manifest,
+            header,
+            chunks,
+        })
+    }
+
+    /// Rebuild a snapshot, verifying every part against the manifest
+    pub fn load<C: AsRef<[u8]>>(
+        manifest: &SnapshotManifest,
+        header: &[u8],
+        chunks: impl IntoIterator<Item = C>,
+    ) -> Result<Self, SnapshotError> {
+        let mut reader = SnapshotReader::new(manifest.clone(), header)?;
+
+        let mut assets = Vec::with_capacity(manifest.asset_count as usize);
+        for chunk in chunks {
+            assets.extend(reader.read_chunk(chunk.as_ref())?);
+        }
+        let header = reader.finish()?;
+
+        Ok(Self {
+            collection: header.collection,
+            assets,
+            trait_summary: header.trait_summary,
+            rankings: header.rankings,
+        })
+    }
+}
+
+/// Builds a snapshot incrementally, emitting each chunk once it fills
+pub struct SnapshotWriter {
+    policy_id: PolicyId,
+    header: Vec<u8>,
+    header_hash: String,
+    chunk_size: usize,
+    buffer: Vec<u8>,
+    buffered: u32,
+    chunks: Vec<ChunkInfo>,
+    asset_count: u64,
+}
+
+impl SnapshotWriter {
+    pub fn new(
+        collection: CollectionDetails,
+        trait_summary: TraitSummarySorted,
+        rankings: Vec<RankEntry>,
+        chunk_size: usize,
+    ) -> Result<Self, SnapshotError> {
+        let policy_id = collection.policy_id.clone();
+        let header = serde_json::to_vec(&SnapshotHeader {
+            version: SNAPSHOT_VERSION,
+            collection,
+            trait_summary,
+            rankings,
+        })?;
+        let header_hash = blake2b_256_hex(&header);
+
+        Ok(Self {
+            policy_id,
+            header,
+            header_hash,
+            chunk_size: chunk_size.max(1),
+            buffer: Vec::new(),
+            buffered: 0,
+            chunks: Vec::new(),
+            asset_count: 0,
+        })
+    }
+
+    /// Header bytes to store alongside the chunks
+    #[must_use]
+    pub fn header(&self) -> &[u8] {
+        &self.header
+    }
+
+    /// Add an asset; returns a full chunk to store when one completes
+    pub fn push(&mut self, asset: &AssetV2) -> Result<Option<Vec<u8>>, SnapshotError> {
+        serde_json::to_writer(&mut self.buffer, asset)?;
+        self.buffer.push(b'\n');
+        self.buffered += 1;
+        self.asset_count += 1;
+
+        if self.buffered as usize >= self.chunk_size {
+            return Ok(Some(self.flush()));
+        }
+        Ok(None)
+    }
+
+    /// Flush the final partial chunk (if any) and produce the manifest
+    #[must_use]
+    pub fn finish(mut self) -> (Option<Vec<u8>>, SnapshotManifest) {
+        let last = (self.buffered > 0).then(|| self.flush());
+        let manifest = SnapshotManifest {
+            version: SNAPSHOT_VERSION,
+            policy_id: self.policy_id,
+            asset_count: self.asset_count,
+            content_hash: content_hash(&self.header_hash, &self.chunks),
+            header_hash: self.header_hash,
+            chunks: self.chunks,
+        };
+        (last, manifest)
+    }
+
+    fn flush(&mut self) -> Vec<u8> {
+        let chunk = std::mem::take(&mut self.buffer);
+        self.chunks.push(ChunkInfo {
+            asset_count: self.buffered,
+            bytes: chunk.len() as u64,
+            hash: blake2b_256_hex(&chunk),
+        });
+        self.buffered = 0;
+        chunk
+    }
+}
+
+/// Reads a stored snapshot chunk by chunk, verifying each against the manifest
+pub struct SnapshotReader {
+    manifest: SnapshotManifest,
+    header: SnapshotHeader,
+    chunks_read: usize,
+    assets_read: u64,
+}
+
+impl SnapshotReader {
+    /// Check the manifest and header; chunks follow via [`read_chunk`](Self::read_chunk)
+    pub fn new(manifest: SnapshotManifest, header: &[u8]) -> Result<Self, SnapshotError> {
+        if manifest.version != SNAPSHOT_VERSION {
+            return Err(SnapshotError::UnsupportedVersion(manifest.version));
+        }
+        if manifest.content_hash != manifest.expected_content_hash() {
+            return Err(SnapshotError::ContentHashMismatch);
+        }
+        let listed: u64 = manifest
+            .chunks
+            .iter()
+            .map(|c| u64::from(c.asset_count))
+            .sum();
+        if listed != manifest.asset_count {
+            return Err(SnapshotError::CountMismatch {
+                part: "manifest".to_string(),
+                expected: manifest.asset_count,
+                actual: listed,
+            });
+        }
+
+        verify_hash("header", &manifest.header_hash, header)?;
+        let header: SnapshotHeader = serde_json::from_slice(header)?;
+        if header.version != SNAPSHOT_VERSION {
+            return Err(SnapshotError::UnsupportedVersion(header.version));
+        }
+        if header.collection.policy_id != manifest.policy_id {
+            return Err(SnapshotError::PolicyMismatch {
+                expected: manifest.policy_id,
+                actual: header.collection.policy_id,
+            });
+        }
+
+        Ok(Self {
+            manifest,
+            header,
+            chunks_read: 0,
+            assets_read: 0,
+        })
+    }
+
+    #[must_use]
+    pub fn header(&self) -> &SnapshotHeader {
+        &self.header
+    }
+
+    #[must_use]
+    pub fn manifest(&self) -> &SnapshotManifest {
+        &self.manifest
+    }
+
+    /// Verify and parse the next chunk, in manifest order
+    pub fn read_chunk(&mut self, bytes: &[u8]) -> Result<Vec<AssetV2>, SnapshotError> {
+        let index = self.chunks_read;
+        let info = self
+            .manifest
+            .chunks
+            .get(index)
+            .ok_or(SnapshotError::ChunkCount {
+                expected: self.manifest.chunks.len(),
+                actual: index + 1,
+            })?;
+        let part = format!("chunk {index}");
+        verify_hash(&part, &info.hash, bytes)?;
+
+        let assets = serde_json::Deserializer::from_slice(bytes)
+            .into_iter::<AssetV2>()
+            .collect::<Result<Vec<_>, _>>()?;
+        if assets.len() as u64 != u64::from(info.asset_count) {
+            return Err(SnapshotError::CountMismatch {
+                part,
+                expected: u64::from(info.asset_count),
+                actual: assets.len() as u64,
+            });
+        }
+
+        self.chunks_read += 1;
+        self.assets_read += assets.len() as u64;
+        Ok(assets)
+    }
+
+    /// Confirm every chunk was read and return the header
+    pub fn finish(self) -> Result<SnapshotHeader, SnapshotError> {
+        if self.chunks_read != self.manifest.chunks.len() {
+            return Err(SnapshotError::ChunkCount {
+                expected: self.manifest.chunks.len(),
+                actual: self.chunks_read,
+            });
+        }
+        Ok(self.header)
+    }
+}
+
+fn verify_hash(part: &str, expected: &str, bytes: &[u8]) -> Result<(), SnapshotError> {
+    let actual = blake2b_256_hex(bytes);
+    if actual != expected {
+        return Err(SnapshotError::HashMismatch {
+            part: part.to_string(),
+            expected: expected.to_string(),
+            actual,
+        });
+    }
+    Ok(())
+}
+
+fn content_hash(header_hash: &str, chunks: &[ChunkInfo]) -> String {
+    let mut parts = header_hash.to_string();
+    for chunk in chunks {
+        parts.push('\n');
+        parts.push_str(&chunk.hash);
+    }
+    blake2b_256_hex(parts.as_bytes())
+}
+
+fn blake2b_256_hex(bytes: &[u8]) -> String {
+    let mut hasher = Blake2bVar::new(32).expect("valid output size");
+    hasher.update(bytes);
+    let mut out = [0u8; 32];
+    hasher
+        .finalize_variable(&mut out)
+        .expect("output buffer matches");
+    hex::encode(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::TraitSummary;
+    use std::collections::HashMap;
+
+    const POLICY: &str = "b3dab69f7e6100849434fb1781e34bd12a916557f6231b8d2629b6f6";
+
+    fn snapshot(assets: u32) -> CollectionSnapshot {
+        let collection: CollectionDetails = serde_json::from_value(serde_json::json!({
+            "policy_id": POLICY,
+            "name": "Pirates",
+            "royalty_percentage": 5.0
+        }))
+        .unwrap();
+
+        let assets: Vec<AssetV2> = (0..assets)
+            .map(|n| {
+                let name = format!("Pirate{n}");
+                AssetV2::new(
+                    AssetId::new_unchecked(POLICY.to_string(), hex::encode(&name)),
+                    name,
+                    format!("ipfs://Qm{n}"),
+                    None,
+                    HashMap::from([("hat".to_string(), vec!["tricorn".to_string()])]).into(),
+                    Some(n + 1),
+                    vec![],
+                )
+            })
+            .collect();
+        let rankings = assets
+            .iter()
+            .map(|a| RankEntry {
+                asset_id: a.id.clone(),
+                rank: a.rarity_rank.unwrap(),
+                score: None,
+            })
+            .collect();
+
+        CollectionSnapshot {
+            collection,
+            assets,
+            trait_summary: TraitSummary::default().into(),
+            rankings,
+        }
+    }
+
+    #[test]
+    fn test_chunked_roundtrip() {
+        let original = snapshot(5);
+        let export = original.export(2).unwrap();
+
+        assert_eq!(export.chunks.len(), 3);
+        assert_eq!(export.manifest.asset_count, 5);
+        assert_eq!(export.manifest.chunks[2].asset_count, 1);
+        assert_eq!(export.manifest.policy_id, POLICY);
+
+        let restored =
+            CollectionSnapshot::load(&export.manifest, &export.header, &export.chunks).unwrap();
+        assert_eq!(restored.assets, original.assets);
+        assert_eq!(restored.rankings, original.rankings);
+
+        // Same content, same hash
+        assert_eq!(
+            original.export(2).unwrap().manifest.content_hash,
+            export.manifest.content_hash
+        );
+    }
+
+    #[test]
+    fn test_tampered_chunk_rejected() {
+        let export = snapshot(4).export(2).unwrap();
+
+        let mut chunks = export.chunks.clone();
+        chunks[1] = String::from_utf8(chunks[1].clone())
+            .unwrap()
+            .replace("Pirate3", "Pirate9")
+            .into_bytes();
+        assert!(matches!(
+            CollectionSnapshot::load(&export.manifest, &export.header, &chunks),
+            Err(SnapshotError::HashMismatch { part, .. }) if part == "chunk 1"
+        ));
+
+        assert!(matches!(
+            CollectionSnapshot::load(&export.manifest, &export.header, &export.chunks[..1]),
+            Err(SnapshotError::ChunkCount {
+                expected: 2,
+                actual: 1
+            })
+        ));
+    }
+
+    #[test]
+    fn test_tampered_manifest_rejected() {
+        let export = snapshot(2).export(2).unwrap();
+
+        let mut manifest = export.manifest.clone();
+        manifest.chunks[0].hash = blake2b_256_hex(b"other");
+        assert!(matches!(
+            CollectionSnapshot::load(&manifest, &export.header, &export.chunks),
+            Err(SnapshotError::ContentHashMismatch)
+        ));
+
+        let mut manifest = export.manifest.clone();
+        manifest.version = 2;
+        assert!(matches!(
+            CollectionSnapshot::load(&manifest, &export.header, &export.chunks),
+            Err(SnapshotError::UnsupportedVersion(2))
+        ));
+    }
+
+    #[test]
+    fn test_object_keys() {
+        assert_eq!(
+            SnapshotManifest::chunk_key("snapshots/pirates/", 3),
+            "snapshots/pirates/chunk-00003.ndjson"
+        );
+        assert_eq!(
+            SnapshotManifest::manifest_key("snapshots/pirates"),
+            "snapshots/pirates/manifest.json"
+        );
+    }
+}
@@ -0,0 +1,379 @@
+//! Collection social links, extracted from metadata and canonicalized.
+//!
+//! Projects write links every way imaginable: `@handle`, `twitter.com/x`,
+//! `HTTP://Discord.GG/abc/`, chunked across a list. [`Socials`] normalizes
+//! them to one form so registries can compare and display links without
+//! their own cleanup:
+//!
+//! - URLs get `https://`, a lowercase host and no trailing slash
+//! - Twitter/X links and `@handles` become `https://x.com/{handle}`
+//! - values that aren't links (`"n/a"`, `"TBA"`) are dropped
+//!
+//! ```
+//! use cardano_assets::Socials;
+//!
+//! let socials = Socials::from_raw(
+//!     Some("HTTP://Discord.GG/pirates/"),
+//!     Some("@PiratesCNFT"),
+//!     Some("pirates.io"),
+//! );
+//! assert_eq!(socials.discord.as_deref(), Some("https://discord.gg/pirates"));
+//! assert_eq!(socials.twitter.as_deref(), Some("https://x.com/PiratesCNFT"));
+//! assert_eq!(socials.twitter_handle(), Some("PiratesCNFT"));
+//! assert_eq!(socials.website.as_deref(), Some("https://pirates.io"));
+//! ```
+
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "openapi")]
+use utoipa::ToSchema;
+
+use crate::{AssetMetadata, CollectionSocials, PrimitiveOrList};
+
+const TWITTER_HOSTS: &[&str] = &[
+    "x.com",
+    "www.x.com",
+    "twitter.com",
+    "www.twitter.com",
+    "mobile.twitter.com",
+];
+
+/// Values projects put in the twitter field while they have no account,
+/// compared case-insensitively
+const PLACEHOLDER_HANDLES: &[&str] = &[
+    "tba",
+    "tbd",
+    "soon",
+    "comingsoon",
+    "coming_soon",
+    "none",
+    "null",
+    "nil",
+    "na",
+    "n_a",
+    "undefined",
+    "unknown",
+    "twitter",
+];
+
+/// Canonical social links for a collection
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+pub struct Socials {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub website: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub discord: Option<String>,
+    /// Always `https://x.com/{handle}`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub twitter: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub github: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub medium: Option<String>,
+}
+
+impl Socials {
+    /// Normalize raw discord, twitter and website values
+    pub fn from_raw(discord: Option<&str>, twitter: Option<&str>, website: Option<&str>) -> Self {
+        Self {
+            website: website.and_then(normalize_url),
+            discord: discord.and_then(normalize_url),
+            twitter: twitter
+                .and_then(twitter_handle)
+                .map(|handle| format!("https://x.com/{handle}")),
+            github: None,
+            medium: None,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.website.is_none()
+            && self.discord.is_none()
+            && self.twitter.is_none()
+            && self.github.is_none()
+            && self.medium.is_none()
+    }
+
+    /// Twitter/X handle without the `@`
+    pub fn twitter_handle(&self) -> Option<&str> {
+        self.twitter
+            .as_deref()
+            .and_then(|url| url.strip_prefix("https://x.com/"))
+    }
+
+    /// Fill links missing here from `other`, e.g. a later asset's metadata
+    pub fn merge(&mut self, other: Socials) {
+        for (mine, theirs) in [
+            (&mut self.website, other.website),
+            (&mut self.discord, other.discord),
+            (&mut self.twitter, other.twitter),
+            (&mut self.github, other.github),
+            (&mut self.medium, other.medium),
+        ] {
+            if mine.is_none() {
+                *mine = theirs;
+            }
+        }
+    }
+}
+
+impl From<Socials> for CollectionSocials {
+    fn from(socials: Socials) -> Self {
+        Self {
+            discord: socials.discord,
+            twitter: socials.twitter,
+            website: socials.website,
+        }
+    }
+}
+
+impl From<&CollectionSocials> for Socials {
+    fn from(socials: &CollectionSocials) -> Self {
+        Self::from_raw(
+            socials.discord.as_deref(),
+            socials.twitter.as_deref(),
+            socials.website.as_deref(),
+        )
+    }
+}
+
+/// `https://` URL with a lowercase host and no trailing slash, or `None` if
+/// `raw` doesn't look like a link
+pub fn normalize_url(raw: &str) -> Option<String> {
+    let raw = raw.trim();
+    let rest = match raw.split_once("://") {
+        Some((scheme, rest)) if scheme.eq_ignore_ascii_case("http") => rest,
+        Some((scheme, rest)) if scheme.eq_ignore_ascii_case("https") => rest,
+        Some(_) => return None,
+        None => raw,
+    };
+
+    let host_end = rest.find(['/', '?', '#']).unwrap_or(rest.len());
+    let (host, path) = rest.split_at(host_end);
+    let host = host.to_ascii_lowercase();
+    let valid_host = host.contains('.')
+        && !host.starts_with('.')
+        && !host.ends_with('.')
+        && host
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '-' | ':'));
+    if !valid_host {
+        return None;
+    }
+
+    let path = path.trim_end_matches('/');
+    Some(format!("https://{host}{path}"))
+}
+
+/// Handle from `@handle`, `handle` or a twitter.com / x.com profile URL.
+/// Placeholders such as `TBA` or `none` are rejected.
+pub fn twitter_handle(raw: &str) -> Option<String> {
+    let raw = raw.trim();
+    let handle = match raw.strip_prefix('@') {
+        Some(handle) => handle.to_string(),
+        None if raw.contains('.') || raw.contains('/') => {
+            let url = normalize_url(raw)?;
+            let rest = url.strip_prefix("https://")?;
+            let (host, path) = rest.split_once('/')?;
+            if !TWITTER_HOSTS.contains(&host) {
+                return None;
+            }
+            let handle = path.split(['/', '?', '#']).next()?;
+            handle.trim_start_matches('@').to_string()
+        }
+        None => raw.to_string(),
+    };
+
+    let valid = (1..=15).contains(&handle.len())
+        && handle
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_')
+        && !PLACEHOLDER_HANDLES
+            .iter()
+            .any(|placeholder| handle.eq_ignore_ascii_case(placeholder));
+    valid.then_some(handle)
+}
+
+fn chunked(value: &Option<PrimitiveOrList<String>>) -> Option<String> {
+    value.as_ref().map(PrimitiveOrList::dechunked)
+}
+
+impl AssetMetadata {
+    /// Social links in this asset's metadata, normalized
+    pub fn socials(&self) -> Socials {
+        match self {
+            AssetMetadata::CodifiedTraits {
+                discord,
+                twitter,
+                website,
+                ..
+            }
+            | AssetMetadata::Attributed {
+                discord,
+                twitter,
+                website,
+                ..
+            }
+            | AssetMetadata::AttributeArray {
+                discord,
+                twitter,
+                website,
+                ..
+            }
+            | AssetMetadata::FlattenedMixed {
+                discord,
+                twitter,
+                website,
+                ..
+            } => Socials::from_raw(discord.as_deref(), twitter.as_deref(), website.as_deref()),
+            AssetMetadata::Flattened {
+                discord,
+                twitter,
+                website,
+                github,
+                medium,
+                ..
+            } => Socials {
+                github: chunked(github).as_deref().and_then(normalize_url),
+                medium: chunked(medium).as_deref().and_then(normalize_url),
+                ..Socials::from_raw(
+                    chunked(discord).as_deref(),
+                    chunked(twitter).as_deref(),
+                    chunked(website).as_deref(),
+                )
+            },
+            AssetMetadata::ColonDelimitedAttributes { website, .. } => {
+                Socials::from_raw(None, None, website.as_deref())
+            }
+            AssetMetadata::UnsignedAlgorithms { .. } | AssetMetadata::Untitled { .. } => {
+                Socials::default()
+            }
+            AssetMetadata::Raw { value } => {
+                let field = |keys: &[&str]| crate::raw::string_field(value, keys);
+                Socials {
+                    github: field(&["github", "Github"])
+                        .as_deref()
+                        .and_then(normalize_url),
+                    medium: field(&["medium", "Medium"])
+                        .as_deref()
+                        .and_then(normalize_url),
+                    ..Socials::from_raw(
+                        field(&["discord", "Discord"]).as_deref(),
+                        field(&["twitter", "Twitter"]).as_deref(),
+                        field(&["website", "Website"]).as_deref(),
+                    )
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize_url() {
+        assert_eq!(
+            normalize_url(" http://WWW.Pirates.IO/Crew/ ").as_deref(),
+            Some("https://www.pirates.io/Crew")
+        );
+        assert_eq!(
+            normalize_url("discord.gg/abc").as_deref(),
+            Some("https://discord.gg/abc")
+        );
+        assert_eq!(normalize_url("n/a"), None);
+        assert_eq!(normalize_url("TBA"), None);
+        assert_eq!(normalize_url("ipfs://QmPirates"), None);
+    }
+
+    #[test]
+    fn test_twitter_handle() {
+        for raw in [
+            "@Pirates_CNFT",
+            "Pirates_CNFT",
+            "https://twitter.com/Pirates_CNFT",
+            "http://mobile.twitter.com/Pirates_CNFT/status/1?s=20",
+            "x.com/@Pirates_CNFT/",
+        ] {
+            assert_eq!(
+                twitter_handle(raw).as_deref(),
+                Some("Pirates_CNFT"),
+                "{raw}"
+            );
+        }
+        assert_eq!(twitter_handle("https://pirates.io/Pirates_CNFT"), None);
+        assert_eq!(twitter_handle("not a handle"), None);
+        assert_eq!(twitter_handle("@"), None);
+    }
+
+    #[test]
+    fn test_twitter_handle_rejects_placeholders() {
+        for raw in [
+            "TBA",
+            "tbd",
+            "@none",
+            "None",
+            "soon",
+            "Coming_Soon",
+            "null",
+            "N_A",
+            "https://twitter.com/TBA",
+            "x.com/none",
+        ] {
+            assert_eq!(twitter_handle(raw), None, "{raw}");
+        }
+        // Real handles that merely contain a placeholder word still pass
+        assert_eq!(
+            twitter_handle("@SoonPirates").as_deref(),
+            Some("SoonPirates")
+        );
+        assert_eq!(
+            Socials::from_raw(None, Some("TBA"), Some("pirates.io")).twitter,
+            None
+        );
+    }
+
+    #[test]
+    fn test_socials_from_flattened_metadata() {
+        let json = r#"{
+            "name": "Pirate #1",
+            "image": "ipfs://QmPirate",
+            "Twitter": ["https://twitter.com/", "PiratesCNFT"],
+            "Website": "PIRATES.IO/",
+            "github": "https://GitHub.com/pirates",
+            "Hat": "Tricorn"
+        }"#;
+        let metadata: AssetMetadata = serde_json::from_str(json).unwrap();
+        assert!(matches!(metadata, AssetMetadata::Flattened { .. }));
+
+        let socials = metadata.socials();
+        assert_eq!(socials.twitter_handle(), Some("PiratesCNFT"));
+        assert_eq!(socials.website.as_deref(), Some("https://pirates.io"));
+        assert_eq!(
+            socials.github.as_deref(),
+            Some("https://github.com/pirates")
+        );
+        assert_eq!(socials.discord, None);
+    }
+
+    #[test]
+    fn test_merge_and_collection_socials() {
+        let mut socials = Socials::from_raw(None, Some("@pirates"), None);
+        socials.merge(Socials::from_raw(
+            Some("discord.gg/pirates"),
+            Some("@other"),
+            None,
+        ));
+        assert_eq!(socials.twitter_handle(), Some("pirates"));
+        assert_eq!(
+            socials.discord.as_deref(),
+            Some("https://discord.gg/pirates")
+        );
+        assert!(!socials.is_empty());
+
+        let collection: CollectionSocials = socials.clone().into();
+        assert_eq!(Socials::from(&collection), socials);
+    }
+}
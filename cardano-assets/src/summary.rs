@@ -0,0 +1,383 @@
+//! Incremental [`TraitSummary`] maintenance.
+//!
+//! Rebuilding a summary from every asset on each burn or metadata fix is
+//! slow for large policies. [`TraitSummary::remove_asset`] undoes an
+//! [`add_asset`](TraitSummary::add_asset), and [`TraitSummary::apply_diff`]
+//! moves one asset's counts from its old traits to its new ones.
+//!
+//! Both check every decrement before changing anything: a summary that would
+//! go negative is out of sync with its assets, so the update is refused with
+//! a [`TraitSummaryError`] and the summary left as it was (rebuild it).
+
+use std::collections::HashMap;
+use std::fmt;
+
+use crate::{Asset, Overrides, QuantityWeighting, TraitSummary, Traits};
+
+/// An incremental update that doesn't match what the summary counted
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TraitSummaryError {
+    /// Removing from a summary with no assets
+    Empty,
+    /// The summary holds fewer of this value than is being removed
+    NotCounted { trait_name: String, value: String },
+}
+
+impl fmt::Display for TraitSummaryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Empty => f.write_str("Trait summary has no assets to remove"),
+            Self::NotCounted { trait_name, value } => {
+                write!(f, "Trait summary has no {trait_name}: {value} to remove")
+            }
+        }
+    }
+}
+
+impl std::error::Error for TraitSummaryError {}
+
+type Counts = HashMap<String, HashMap<String, u32>>;
+type Delta<'a> = HashMap<(&'a str, &'a str), i64>;
+
+/// Change in each `(trait, value)` count going from `before` to `after`
+fn delta<'a>(before: &'a Traits, after: &'a Traits) -> Delta<'a> {
+    let mut delta = Delta::new();
+    for (traits, step) in [(before, -1), (after, 1)] {
+        for (name, values) in traits {
+            for value in values {
+                *delta.entry((name.as_str(), value.as_str())).or_default() += step;
+            }
+        }
+    }
+    delta.retain(|_, change| *change != 0);
+    delta
+}
+
+fn check(counts: &Counts, delta: &Delta) -> Result<(), TraitSummaryError> {
+    for (&(name, value), &change) in delta {
+        let have = counts
+            .get(name)
+            .and_then(|values| values.get(value))
+            .copied()
+            .unwrap_or(0);
+        if i64::from(have) + change < 0 {
+            return Err(TraitSummaryError::NotCounted {
+                trait_name: name.to_string(),
+                value: value.to_string(),
+            });
+        }
+    }
+    Ok(())
+}
+
+/// Apply a [`check`]ed delta, dropping values (and traits) that reach zero
+fn apply(counts: &mut Counts, delta: &Delta) {
+    for (&(name, value), &change) in delta {
+        let values = counts.entry(name.to_string()).or_default();
+        let count = values.entry(value.to_string()).or_default();
+        *count = (i64::from(*count) + change) as u32;
+        if *count == 0 {
+            values.remove(value);
+        }
+        if values.is_empty() {
+            counts.remove(name);
+        }
+    }
+}
+
+impl TraitSummary {
+    /// Undo [`add_asset`](Self::add_asset), e.g. when the asset is burned
+    pub fn remove_asset(&mut self, asset: &Asset) -> Result<(), TraitSummaryError> {
+        self.remove_asset_weighted(asset, QuantityWeighting::PerAsset)
+    }
+
+    /// Undo [`add_asset_weighted`](Self::add_asset_weighted) with the same
+    /// `weighting`
+    pub fn remove_asset_weighted(
+        &mut self,
+        asset: &Asset,
+        weighting: QuantityWeighting,
+    ) -> Result<(), TraitSummaryError> {
+        let weight = weighting.weight(asset);
+        if self.count < weight {
+            return Err(TraitSummaryError::Empty);
+        }
+        let none = Traits::default();
+        let mut delta = delta(&asset.traits, &none);
+        for change in delta.values_mut() {
+            *change *= i64::from(weight);
+        }
+        check(&self.traits, &delta)?;
+        if let Some(original) = &self.original_traits {
+            check(original, &delta)?;
+        }
+
+        apply(&mut self.traits, &delta);
+        if let Some(original) = &mut self.original_traits {
+            apply(original, &delta);
+        }
+        self.count -= weight;
+        Ok(())
+    }
+
+    /// Undo [`add_asset_with_overrides`](Self::add_asset_with_overrides)
+    pub fn remove_asset_with_overrides(
+        &mut self,
+        asset: &Asset,
+        asset_name_hex: &str,
+        overrides: &Overrides,
+    ) -> Result<(), TraitSummaryError> {
+        self.remove_asset_with_overrides_weighted(
+            asset,
+            asset_name_hex,
+            overrides,
+            QuantityWeighting::PerAsset,
+        )
+    }
+
+    /// Undo [`add_asset_with_overrides_weighted`](Self::add_asset_with_overrides_weighted)
+    /// with the same `weighting`
+    pub fn remove_asset_with_overrides_weighted(
+        &mut self,
+        asset: &Asset,
+        asset_name_hex: &str,
+        overrides: &Overrides,
+        weighting: QuantityWeighting,
+    ) -> Result<(), TraitSummaryError> {
+        let weight = weighting.weight(asset);
+        if self.count < weight {
+            return Err(TraitSummaryError::Empty);
+        }
+        let none = Traits::default();
+        let corrected = overrides.apply_traits(&asset.traits, asset_name_hex);
+        let mut delta_corrected = delta(&corrected, &none);
+        let mut delta_original = delta(&asset.traits, &none);
+        for change in delta_corrected
+            .values_mut()
+            .chain(delta_original.values_mut())
+        {
+            *change *= i64::from(weight);
+        }
+        check(&self.traits, &delta_corrected)?;
+        if let Some(original) = &self.original_traits {
+            check(original, &delta_original)?;
+        }
+
+        apply(&mut self.traits, &delta_corrected);
+        if let Some(original) = &mut self.original_traits {
+            apply(original, &delta_original);
+        }
+        if corrected != asset.traits {
+            self.overridden = self.overridden.saturating_sub(1);
+        }
+        self.count -= weight;
+        Ok(())
+    }
+
+    /// Move one asset's counts from `before` to `after`, e.g. when its
+    /// metadata is corrected. The asset count is unchanged.
+    pub fn apply_diff(&mut self, before: &Traits, after: &Traits) -> Result<(), TraitSummaryError> {
+        let delta = delta(before, after);
+        check(&self.traits, &delta)?;
+        if let Some(original) = &self.original_traits {
+            check(original, &delta)?;
+        }
+
+        apply(&mut self.traits, &delta);
+        if let Some(original) = &mut self.original_traits {
+            apply(original, &delta);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{AssetMetadata, PolicyId};
+    use test_utils::test_case;
+
+    fn asset(pairs: &[(&str, &[&str])]) -> Asset {
+        Asset {
+            name: "Test".to_string(),
+            image: String::new(),
+            media_type: None,
+            traits: Traits::from_map(
+                pairs
+                    .iter()
+                    .map(|(k, v)| (k.to_string(), v.iter().map(|s| s.to_string()).collect()))
+                    .collect(),
+            ),
+            rarity_rank: None,
+            quantity: 1,
+            tags: vec![],
+        }
+    }
+
+    fn summary_of(assets: &[Asset]) -> TraitSummary {
+        let mut summary = TraitSummary::default();
+        for asset in assets {
+            summary.add_asset(asset);
+        }
+        summary
+    }
+
+    #[test]
+    fn test_remove_asset_drops_zero_counts() {
+        let pirate = asset(&[("Hat", &["Tricorn"]), ("Eyes", &["Patch"])]);
+        let crew = asset(&[("Hat", &["Tricorn"]), ("Tattoos", &["Anchor", "Rose"])]);
+        let mut summary = summary_of(&[pirate.clone(), crew.clone()]);
+
+        summary.remove_asset(&crew).unwrap();
+        assert_eq!(summary.count, 1);
+        assert_eq!(summary.traits["Hat"]["Tricorn"], 1);
+        assert!(!summary.traits.contains_key("Tattoos"));
+
+        summary.remove_asset(&pirate).unwrap();
+        assert_eq!(summary.count, 0);
+        assert!(summary.traits.is_empty());
+        assert_eq!(summary.remove_asset(&pirate), Err(TraitSummaryError::Empty));
+    }
+
+    #[test]
+    fn test_remove_unknown_asset_leaves_summary_unchanged() {
+        let pirate = asset(&[("Hat", &["Tricorn"])]);
+        let mut summary = summary_of(&[pirate]);
+
+        // Hat is counted, Eyes isn't: nothing may change
+        let stranger = asset(&[("Hat", &["Tricorn"]), ("Eyes", &["Laser"])]);
+        assert_eq!(
+            summary.remove_asset(&stranger),
+            Err(TraitSummaryError::NotCounted {
+                trait_name: "Eyes".to_string(),
+                value: "Laser".to_string(),
+            })
+        );
+        assert_eq!(summary.count, 1);
+        assert_eq!(summary.traits["Hat"]["Tricorn"], 1);
+    }
+
+    #[test]
+    fn test_apply_diff_matches_rebuild() {
+        let before = asset(&[("Hat", &["Tricorn"]), ("Eyes", &["Pacth"])]);
+        let after = asset(&[("Hat", &["Tricorn"]), ("Eyes", &["Patch"])]);
+        let other = asset(&[("Eyes", &["Patch"])]);
+
+        let mut summary = summary_of(&[before.clone(), other.clone()]);
+        summary.apply_diff(&before.traits, &after.traits).unwrap();
+
+        let rebuilt = summary_of(&[after.clone(), other]);
+        assert_eq!(summary.traits, rebuilt.traits);
+        assert_eq!(summary.count, rebuilt.count);
+
+        // The typo is gone, so applying the same fix again is refused
+        assert!(summary.apply_diff(&before.traits, &after.traits).is_err());
+        assert_eq!(summary.traits, rebuilt.traits);
+    }
+
+    #[test]
+    fn test_counts_never_go_negative() {
+        // Deterministic pseudo-random adds and removes over a small pool,
+        // checked against a rebuild after every step
+        let pool: Vec<Asset> = (0..6)
+            .map(|i| {
+                let hat = ["Tricorn", "Bandana", "None"][i % 3];
+                let tattoos: &[&str] = if i % 2 == 0 {
+                    &["Anchor", "Anchor"]
+                } else {
+                    &["Rose"]
+                };
+                asset(&[("Hat", &[hat]), ("Tattoos", tattoos)])
+            })
+            .collect();
+        let ghost = asset(&[("Hat", &["Crown"])]);
+        let mut held: Vec<usize> = Vec::new();
+        let mut summary = TraitSummary::default();
+        let mut seed: u32 = 0x2414;
+
+        for step in 0..500 {
+            seed = seed.wrapping_mul(1_103_515_245).wrapping_add(12_345);
+            let pick = (seed >> 16) as usize;
+            if held.is_empty() || seed & 0x100 == 0 {
+                summary.add_asset(&pool[pick % pool.len()]);
+                held.push(pick % pool.len());
+            } else {
+                let removed = held.swap_remove(pick % held.len());
+                summary.remove_asset(&pool[removed]).unwrap();
+            }
+            if step % 50 == 0 {
+                assert!(summary.remove_asset(&ghost).is_err());
+            }
+
+            let expected = summary_of(&held.iter().map(|&h| pool[h].clone()).collect::<Vec<_>>());
+            assert_eq!(summary.traits, expected.traits);
+            assert_eq!(summary.count, expected.count);
+            assert!(summary
+                .traits
+                .values()
+                .flat_map(HashMap::values)
+                .all(|&c| c > 0));
+        }
+    }
+
+    /// Bankopoly cards are minted as editions under one asset name
+    fn bankcard(quantity: u64) -> Asset {
+        let metadata: AssetMetadata =
+            serde_json::from_str(test_case!("bankcard2500.json")).unwrap();
+        Asset::from(metadata).with_quantity(quantity)
+    }
+
+    #[test]
+    fn test_weighted_semi_fungibles() {
+        let partner = bankcard(25);
+        let pirate = asset(&[("Card Type", &["Founder"])]);
+
+        let mut per_asset = TraitSummary::default();
+        let mut per_unit = TraitSummary::default();
+        for asset in [&partner, &pirate] {
+            per_asset.add_asset(asset);
+            per_unit.add_asset_weighted(asset, QuantityWeighting::PerUnit);
+        }
+
+        assert_eq!(per_asset.count, 2);
+        assert_eq!(per_asset.traits["Card Type"]["Partner"], 1);
+        assert_eq!(per_unit.count, 26);
+        assert_eq!(per_unit.traits["Card Type"]["Partner"], 25);
+        assert_eq!(per_unit.traits["Card Type"]["Founder"], 1);
+
+        per_unit
+            .remove_asset_weighted(&partner, QuantityWeighting::PerUnit)
+            .unwrap();
+        assert_eq!(per_unit.count, 1);
+        assert!(!per_unit.traits["Card Type"].contains_key("Partner"));
+        assert_eq!(
+            per_unit.remove_asset_weighted(&partner, QuantityWeighting::PerUnit),
+            Err(TraitSummaryError::Empty)
+        );
+    }
+
+    #[test]
+    fn test_zero_weight_removal_is_a_no_op() {
+        // A zero-quantity asset adds no counts under per-unit weighting, so
+        // removing it succeeds even from an empty summary
+        let empty = bankcard(0);
+        let overrides = Overrides::new(PolicyId::new_unchecked("pirates"));
+        let mut summary = TraitSummary::default();
+        summary.add_asset_weighted(&empty, QuantityWeighting::PerUnit);
+        assert_eq!(summary.count, 0);
+
+        summary
+            .remove_asset_weighted(&empty, QuantityWeighting::PerUnit)
+            .unwrap();
+        summary
+            .remove_asset_with_overrides_weighted(
+                &empty,
+                &hex::encode(&empty.name),
+                &overrides,
+                QuantityWeighting::PerUnit,
+            )
+            .unwrap();
+        assert_eq!(summary.count, 0);
+        assert!(summary.traits.is_empty());
+    }
+}
@@ -1,6 +1,17 @@
-//! Mint supply — how many copies of a master may be minted.
+//! Mint supply — how many copies of a master may be minted, and how many are
+//! actually in circulation.
+//!
+//! - [`MintSupply`] — the configured ceiling for a master (launchpad side).
+//! - [`SupplyLedger`] — on-chain circulating supply, folded from mint/burn
+//!   events (utxorpc, maestro mint history, ...).
 
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+use crate::AssetId;
+
+#[cfg(feature = "openapi")]
+use utoipa::ToSchema;
 
 /// How many copies of a master may be minted — the per-row supply ceiling
 /// (e.g. a launchpad's `collection_assets.max_supply` column, or a mint
@@ -69,6 +80,304 @@ impl<'de> Deserialize<'de> for MintSupply {
     }
 }
 
+// ─── Supply ledger ───────────────────────────────────────────────────────────
+
+/// A single mint (`quantity > 0`) or burn (`quantity < 0`) of one asset.
+///
+/// Build these from whichever indexer you are consuming — the ledger doesn't
+/// care where they came from. `slot` is informational (first/last seen) and is
+/// not used for ordering; feed events in chain order.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+pub struct MintEvent {
+    pub asset_id: AssetId,
+    pub quantity: i64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub slot: Option<u64>,
+}
+
+impl MintEvent {
+    pub fn new(asset_id: AssetId, quantity: i64) -> Self {
+        Self {
+            asset_id,
+            quantity,
+            slot: None,
+        }
+    }
+
+    pub fn at_slot(mut self, slot: u64) -> Self {
+        self.slot = Some(slot);
+        self
+    }
+}
+
+/// What a [`MintEvent`] did to an asset's supply.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SupplyChange {
+    /// First mint of this asset.
+    Minted,
+    /// Further mint while copies are still circulating.
+    Increased,
+    /// Mint after the asset had been fully burned.
+    Reminted,
+    /// Partial burn; copies remain.
+    Burned,
+    /// Burn that took circulating supply to zero.
+    FullyBurned,
+    /// Zero-quantity event; nothing changed.
+    Unchanged,
+}
+
+/// Circulating-supply history for a single asset.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+pub struct AssetSupply {
+    /// Total ever minted.
+    pub minted: u64,
+    /// Total ever burned.
+    pub burned: u64,
+    /// Currently in circulation (`minted - burned`, floored at zero).
+    pub circulating: u64,
+    /// Highest circulating supply ever observed.
+    pub peak_circulating: u64,
+    /// Times the asset was minted again after being fully burned.
+    pub remints: u32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub first_slot: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_slot: Option<u64>,
+}
+
+impl AssetSupply {
+    /// At most one copy has ever existed at a time — treat as an NFT.
+    ///
+    /// A 1-of-1 that was burned and reminted is still unique; an asset that
+    /// ever had two copies circulating is not, even if one was later burned.
+    #[must_use]
+    pub fn is_unique(&self) -> bool {
+        self.peak_circulating <= 1
+    }
+
+    /// Fully burned (and not reminted since).
+    #[must_use]
+    pub fn is_burned(&self) -> bool {
+        self.minted > 0 && self.circulating == 0
+    }
+
+    fn apply(&mut self, quantity: i64, slot: Option<u64>) -> SupplyChange {
+        if let Some(slot) = slot {
+            self.first_slot.get_or_insert(slot);
+            self.last_slot = Some(slot);
+        }
+
+        let amount = quantity.unsigned_abs();
+        if quantity > 0 {
+            let change = if self.minted == 0 {
+                SupplyChange::Minted
+            } else if self.circulating == 0 {
+                self.remints += 1;
+                SupplyChange::Reminted
+            } else {
+                SupplyChange::Increased
+            };
+            self.minted = self.minted.saturating_add(amount);
+            self.circulating = self.circulating.saturating_add(amount);
+            self.peak_circulating = self.peak_circulating.max(self.circulating);
+            change
+        } else if quantity < 0 {
+            self.burned = self.burned.saturating_add(amount);
+            // Over-burns mean we missed a mint; floor at zero rather than wrap
+            self.circulating = self.circulating.saturating_sub(amount);
+            if self.circulating == 0 {
+                SupplyChange::FullyBurned
+            } else {
+                SupplyChange::Burned
+            }
+        } else {
+            SupplyChange::Unchanged
+        }
+    }
+}
+
+/// Aggregate supply across every asset under one policy.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+pub struct PolicySupply {
+    /// Assets with at least one copy circulating.
+    pub live_assets: usize,
+    /// Assets ever minted, including fully burned ones.
+    pub total_assets: usize,
+    /// Sum of circulating supply across assets.
+    pub circulating: u64,
+    pub minted: u64,
+    pub burned: u64,
+}
+
+/// Per-asset and per-policy circulating supply, folded from mint/burn events.
+///
+/// # Examples
+///
+/// ```
+/// use cardano_assets::{AssetId, MintEvent, SupplyChange, SupplyLedger};
+///
+/// let id = AssetId::new(
+///     "b3dab69f7e6100849434fb1781e34bd12a916557f6231b8d2629b6f6".to_string(),
+///     "50697261746531303836".to_string(),
+/// )
+/// .unwrap();
+///
+/// let mut ledger = SupplyLedger::new();
+/// assert_eq!(ledger.apply(&MintEvent::new(id.clone(), 1)), SupplyChange::Minted);
+/// assert_eq!(ledger.apply(&MintEvent::new(id.clone(), -1)), SupplyChange::FullyBurned);
+/// assert_eq!(ledger.apply(&MintEvent::new(id.clone(), 1)), SupplyChange::Reminted);
+///
+/// assert_eq!(ledger.circulating(&id), 1);
+/// assert!(ledger.is_unique(&id));
+/// ```
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SupplyLedger {
+    /// Keyed by concatenated asset id on the wire; JSON map keys must be strings
+    #[serde(with = "concatenated_keys")]
+    assets: BTreeMap<AssetId, AssetSupply>,
+}
+
+mod concatenated_keys {
+    use std::collections::BTreeMap;
+
+    use serde::de::Error;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    use super::AssetSupply;
+    use crate::AssetId;
+
+    pub fn serialize<S: Serializer>(
+        assets: &BTreeMap<AssetId, AssetSupply>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        let keyed: BTreeMap<String, &AssetSupply> = assets
+            .iter()
+            .map(|(id, supply)| (id.concatenated(), supply))
+            .collect();
+        keyed.serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<BTreeMap<AssetId, AssetSupply>, D::Error> {
+        BTreeMap::<String, AssetSupply>::deserialize(deserializer)?
+            .into_iter()
+            .map(|(key, supply)| {
+                AssetId::parse_concatenated(&key)
+                    .map(|id| (id, supply))
+                    .map_err(|e| D::Error::custom(format!("Invalid AssetId key {key}: {e}")))
+            })
+            .collect()
+    }
+}
+
+impl SupplyLedger {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Apply one mint or burn.
+    pub fn apply(&mut self, event: &MintEvent) -> SupplyChange {
+        self.record(&event.asset_id, event.quantity, event.slot)
+    }
+
+    /// Apply a mint (`quantity > 0`) or burn (`quantity < 0`) without building
+    /// a [`MintEvent`].
+    pub fn record(&mut self, asset_id: &AssetId, quantity: i64, slot: Option<u64>) -> SupplyChange {
+        if quantity == 0 {
+            return SupplyChange::Unchanged;
+        }
+        self.assets
+            .entry(asset_id.clone())
+            .or_default()
+            .apply(quantity, slot)
+    }
+
+    /// Apply a batch of events in order.
+    pub fn extend<'a>(&mut self, events: impl IntoIterator<Item = &'a MintEvent>) {
+        for event in events {
+            self.apply(event);
+        }
+    }
+
+    /// Supply history for an asset, if it has ever been minted or burned.
+    #[must_use]
+    pub fn asset(&self, asset_id: &AssetId) -> Option<&AssetSupply> {
+        self.assets.get(asset_id)
+    }
+
+    /// Current circulating supply (zero for unknown assets).
+    #[must_use]
+    pub fn circulating(&self, asset_id: &AssetId) -> u64 {
+        self.asset(asset_id).map_or(0, |a| a.circulating)
+    }
+
+    /// Whether the asset should be handled as an NFT.
+    ///
+    /// Unknown assets are not unique — there is nothing to decide on yet.
+    #[must_use]
+    pub fn is_unique(&self, asset_id: &AssetId) -> bool {
+        self.asset(asset_id).is_some_and(AssetSupply::is_unique)
+    }
+
+    /// Whether every asset ever minted under `policy_id` is unique.
+    ///
+    /// `false` for policies with no recorded events.
+    #[must_use]
+    pub fn is_unique_policy(&self, policy_id: &str) -> bool {
+        let mut assets = self.policy_assets(policy_id).peekable();
+        assets.peek().is_some() && assets.all(|(_, supply)| supply.is_unique())
+    }
+
+    /// Assets that were minted again after a full burn.
+    pub fn remints(&self) -> impl Iterator<Item = (&AssetId, &AssetSupply)> {
+        self.assets.iter().filter(|(_, supply)| supply.remints > 0)
+    }
+
+    /// All assets under `policy_id`.
+    pub fn policy_assets<'a>(
+        &'a self,
+        policy_id: &'a str,
+    ) -> impl Iterator<Item = (&'a AssetId, &'a AssetSupply)> + 'a {
+        self.assets
+            .iter()
+            .filter(move |(id, _)| id.policy_id == policy_id)
+    }
+
+    /// Aggregate supply for `policy_id`.
+    #[must_use]
+    pub fn policy(&self, policy_id: &str) -> PolicySupply {
+        self.policy_assets(policy_id)
+            .fold(PolicySupply::default(), |mut acc, (_, supply)| {
+                acc.total_assets += 1;
+                if supply.circulating > 0 {
+                    acc.live_assets += 1;
+                }
+                acc.circulating = acc.circulating.saturating_add(supply.circulating);
+                acc.minted = acc.minted.saturating_add(supply.minted);
+                acc.burned = acc.burned.saturating_add(supply.burned);
+                acc
+            })
+    }
+
+    /// Every tracked asset with its supply.
+    pub fn iter(&self) -> impl Iterator<Item = (&AssetId, &AssetSupply)> {
+        self.assets.iter()
+    }
+
+    pub fn len(&self) -> usize {
+        self.assets.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.assets.is_empty()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -119,4 +428,99 @@ mod tests {
         assert_eq!(MintSupply::from_db(Some(50)), MintSupply::Quota(50));
         assert_eq!(MintSupply::from_db(None), MintSupply::Uncapped);
     }
+
+    fn asset(name_hex: &str) -> AssetId {
+        AssetId::new_unchecked(
+            "b3dab69f7e6100849434fb1781e34bd12a916557f6231b8d2629b6f6".to_string(),
+            name_hex.to_string(),
+        )
+    }
+
+    #[test]
+    fn ledger_tracks_mint_burn_remint() {
+        let id = asset("01");
+        let mut ledger = SupplyLedger::new();
+
+        assert_eq!(ledger.record(&id, 1, Some(10)), SupplyChange::Minted);
+        assert_eq!(ledger.record(&id, -1, Some(20)), SupplyChange::FullyBurned);
+        assert!(ledger.asset(&id).unwrap().is_burned());
+        assert_eq!(ledger.record(&id, 1, Some(30)), SupplyChange::Reminted);
+
+        let supply = ledger.asset(&id).unwrap();
+        assert_eq!(supply.minted, 2);
+        assert_eq!(supply.burned, 1);
+        assert_eq!(supply.circulating, 1);
+        assert_eq!(supply.remints, 1);
+        assert_eq!(supply.first_slot, Some(10));
+        assert_eq!(supply.last_slot, Some(30));
+        assert!(ledger.is_unique(&id));
+        assert_eq!(ledger.remints().count(), 1);
+    }
+
+    #[test]
+    fn ledger_uniqueness_uses_peak_supply() {
+        let id = asset("02");
+        let mut ledger = SupplyLedger::new();
+
+        assert_eq!(ledger.record(&id, 1, None), SupplyChange::Minted);
+        assert_eq!(ledger.record(&id, 1, None), SupplyChange::Increased);
+        assert_eq!(ledger.record(&id, -1, None), SupplyChange::Burned);
+        assert_eq!(ledger.circulating(&id), 1);
+        assert!(!ledger.is_unique(&id));
+        assert!(!ledger.is_unique(&asset("ff")));
+    }
+
+    #[test]
+    fn ledger_overburn_floors_at_zero() {
+        let id = asset("03");
+        let mut ledger = SupplyLedger::new();
+        ledger.record(&id, 5, None);
+        assert_eq!(ledger.record(&id, -7, None), SupplyChange::FullyBurned);
+        assert_eq!(ledger.circulating(&id), 0);
+        assert_eq!(ledger.record(&id, 0, None), SupplyChange::Unchanged);
+    }
+
+    #[test]
+    fn ledger_policy_aggregate() {
+        let mut ledger = SupplyLedger::new();
+        ledger.extend(&[
+            MintEvent::new(asset("01"), 1),
+            MintEvent::new(asset("02"), 1),
+            MintEvent::new(asset("02"), -1),
+            MintEvent::new(
+                AssetId::new_unchecked("aa".repeat(28), "01".to_string()),
+                1_000,
+            ),
+        ]);
+
+        let policy = ledger.policy("b3dab69f7e6100849434fb1781e34bd12a916557f6231b8d2629b6f6");
+        assert_eq!(policy.total_assets, 2);
+        assert_eq!(policy.live_assets, 1);
+        assert_eq!(policy.circulating, 1);
+        assert_eq!(policy.minted, 2);
+        assert_eq!(policy.burned, 1);
+        assert!(ledger.is_unique_policy("b3dab69f7e6100849434fb1781e34bd12a916557f6231b8d2629b6f6"));
+        assert!(!ledger.is_unique_policy(&"aa".repeat(28)));
+        assert!(!ledger.is_unique_policy(&"bb".repeat(28)));
+    }
+
+    #[test]
+    fn ledger_serde_round_trip() {
+        let mut ledger = SupplyLedger::new();
+        ledger.record(&asset("01"), 1, Some(10));
+        ledger.record(&asset("02"), 5, Some(11));
+        ledger.record(&asset("02"), -2, Some(12));
+
+        let json = serde_json::to_value(&ledger).unwrap();
+        assert_eq!(
+            json["assets"]["b3dab69f7e6100849434fb1781e34bd12a916557f6231b8d2629b6f602"]
+                ["circulating"],
+            3
+        );
+
+        let back: SupplyLedger = serde_json::from_value(json).unwrap();
+        assert_eq!(back.len(), 2);
+        assert_eq!(back.asset(&asset("02")), ledger.asset(&asset("02")));
+        assert!(back.is_unique(&asset("01")));
+    }
 }
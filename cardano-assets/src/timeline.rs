@@ -0,0 +1,349 @@
+//! Per-epoch mint timeline for a collection.
+//!
+//! [`EpochTimeline`] folds mint and burn events (e.g. maestro `MintTx`
+//! timestamps) into epoch buckets, producing the supply growth curve and the
+//! first/last mint of every trait value for mint-out progress dashboards.
+//!
+//! ```
+//! use cardano_assets::{AssetId, EpochTimeline, Network, Traits};
+//!
+//! let asset = |name: &str| {
+//!     AssetId::new_unchecked(
+//!         "b3dab69f7e6100849434fb1781e34bd12a916557f6231b8d2629b6f6".to_string(),
+//!         hex::encode(name),
+//!     )
+//! };
+//! let mut gold = Traits::new();
+//! gold.insert_single("Background".to_string(), "Gold".to_string());
+//!
+//! // Mainnet epoch 500 starts at 1_722_203_091
+//! let mut timeline = EpochTimeline::new(Network::Mainnet);
+//! timeline.record(&asset("Pirate1"), 1, 1_722_203_091, Some(&gold));
+//! timeline.record(&asset("Pirate2"), 1, 1_723_383_091, None);
+//!
+//! let curve = timeline.supply_curve();
+//! assert_eq!(curve.len(), 3);
+//! assert_eq!(curve[2].epoch, 502);
+//! assert_eq!(curve[2].total_assets, 2);
+//!
+//! let span = timeline.trait_value("Background", "Gold").unwrap();
+//! assert_eq!(span.first_epoch, 500);
+//! assert_eq!(timeline.mint_progress(10), 0.2);
+//! ```
+
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+
+use crate::{AssetId, Network, Traits};
+
+#[cfg(feature = "openapi")]
+use utoipa::ToSchema;
+
+/// Mint activity in one epoch, with running totals up to the end of it
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+pub struct EpochPoint {
+    pub epoch: u64,
+    /// Quantity minted during the epoch
+    pub minted: u64,
+    /// Quantity burned during the epoch
+    pub burned: u64,
+    /// Assets minted for the first time during the epoch
+    pub new_assets: usize,
+    /// Circulating supply at the end of the epoch
+    pub total_supply: u64,
+    /// Distinct assets minted up to the end of the epoch
+    pub total_assets: usize,
+}
+
+/// When a trait value was minted, across every asset carrying it
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+pub struct TraitMintSpan {
+    pub first_epoch: u64,
+    pub last_epoch: u64,
+    /// Unix timestamp (seconds) of the first mint
+    pub first_mint: u64,
+    /// Unix timestamp (seconds) of the latest mint
+    pub last_mint: u64,
+    /// Distinct assets minted with this value
+    pub assets: usize,
+}
+
+impl TraitMintSpan {
+    fn new(timestamp: u64, epoch: u64) -> Self {
+        Self {
+            first_epoch: epoch,
+            last_epoch: epoch,
+            first_mint: timestamp,
+            last_mint: timestamp,
+            assets: 0,
+        }
+    }
+
+    fn observe(&mut self, timestamp: u64, epoch: u64) {
+        if timestamp < self.first_mint {
+            self.first_mint = timestamp;
+            self.first_epoch = epoch;
+        }
+        if timestamp > self.last_mint {
+            self.last_mint = timestamp;
+            self.last_epoch = epoch;
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+struct EpochActivity {
+    minted: u64,
+    burned: u64,
+}
+
+/// Mint and burn events grouped by epoch.
+///
+/// Events may be recorded in any order; totals are accumulated when the curve
+/// is read. Timestamps are unix seconds.
+#[derive(Debug, Clone)]
+pub struct EpochTimeline {
+    network: Network,
+    epochs: BTreeMap<u64, EpochActivity>,
+    /// Earliest mint timestamp per asset
+    first_mints: HashMap<AssetId, u64>,
+    /// Trait key -> value -> span
+    traits: BTreeMap<String, BTreeMap<String, TraitMintSpan>>,
+}
+
+impl EpochTimeline {
+    pub fn new(network: Network) -> Self {
+        Self {
+            network,
+            epochs: BTreeMap::new(),
+            first_mints: HashMap::new(),
+            traits: BTreeMap::new(),
+        }
+    }
+
+    /// Record a mint (`quantity > 0`) or burn (`quantity < 0`) at `timestamp`.
+    ///
+    /// `traits` are the minted asset's traits; each asset counts once per
+    /// trait value however many times it is minted. Burns and zero-quantity
+    /// events don't touch trait spans.
+    pub fn record(
+        &mut self,
+        asset_id: &AssetId,
+        quantity: i64,
+        timestamp: u64,
+        traits: Option<&Traits>,
+    ) {
+        if quantity == 0 {
+            return;
+        }
+
+        let epoch = self.network.epoch_at(timestamp);
+        let activity = self.epochs.entry(epoch).or_default();
+        let amount = quantity.unsigned_abs();
+        if quantity < 0 {
+            activity.burned = activity.burned.saturating_add(amount);
+            return;
+        }
+        activity.minted = activity.minted.saturating_add(amount);
+
+        let new_asset = match self.first_mints.get_mut(asset_id) {
+            Some(first) => {
+                *first = (*first).min(timestamp);
+                false
+            }
+            None => {
+                self.first_mints.insert(asset_id.clone(), timestamp);
+                true
+            }
+        };
+
+        for (key, values) in traits.into_iter().flat_map(Traits::iter) {
+            let spans = self.traits.entry(key.clone()).or_default();
+            for value in values {
+                let span = spans
+                    .entry(value.clone())
+                    .or_insert_with(|| TraitMintSpan::new(timestamp, epoch));
+                span.observe(timestamp, epoch);
+                if new_asset {
+                    span.assets += 1;
+                }
+            }
+        }
+    }
+
+    /// One point per epoch from the first to the last recorded event,
+    /// including quiet epochs in between so the curve has no gaps
+    pub fn supply_curve(&self) -> Vec<EpochPoint> {
+        let (Some(&first), Some(&last)) = (self.epochs.keys().next(), self.epochs.keys().last())
+        else {
+            return Vec::new();
+        };
+
+        let mut new_assets: BTreeMap<u64, usize> = BTreeMap::new();
+        for &timestamp in self.first_mints.values() {
+            *new_assets
+                .entry(self.network.epoch_at(timestamp))
+                .or_default() += 1;
+        }
+
+        let mut total_supply = 0u64;
+        let mut total_assets = 0usize;
+        (first..=last)
+            .map(|epoch| {
+                let activity = self.epochs.get(&epoch).cloned().unwrap_or_default();
+                let new_assets = new_assets.get(&epoch).copied().unwrap_or(0);
+                // Over-burns mean we missed a mint; floor at zero rather than wrap
+                total_supply = total_supply
+                    .saturating_add(activity.minted)
+                    .saturating_sub(activity.burned);
+                total_assets += new_assets;
+                EpochPoint {
+                    epoch,
+                    minted: activity.minted,
+                    burned: activity.burned,
+                    new_assets,
+                    total_supply,
+                    total_assets,
+                }
+            })
+            .collect()
+    }
+
+    /// Mint span of a single trait value
+    #[must_use]
+    pub fn trait_value(&self, key: &str, value: &str) -> Option<&TraitMintSpan> {
+        self.traits.get(key)?.get(value)
+    }
+
+    /// Every trait key with its values' mint spans, sorted by key and value
+    pub fn traits(&self) -> impl Iterator<Item = (&String, &BTreeMap<String, TraitMintSpan>)> {
+        self.traits.iter()
+    }
+
+    /// Distinct assets minted so far
+    #[must_use]
+    pub fn total_assets(&self) -> usize {
+        self.first_mints.len()
+    }
+
+    /// Share of a `collection_size`-asset collection minted so far, capped at
+    /// 1.0. Zero for an empty collection.
+    #[must_use]
+    pub fn mint_progress(&self, collection_size: usize) -> f64 {
+        if collection_size == 0 {
+            return 0.0;
+        }
+        (self.total_assets() as f64 / collection_size as f64).min(1.0)
+    }
+
+    /// First and last epoch with any recorded activity
+    #[must_use]
+    pub fn epoch_range(&self) -> Option<(u64, u64)> {
+        Some((*self.epochs.keys().next()?, *self.epochs.keys().last()?))
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.epochs.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EPOCH_500: u64 = 1_722_203_091;
+    const EPOCH_SECS: u64 = 432_000;
+
+    fn asset(name_hex: &str) -> AssetId {
+        AssetId::new_unchecked(
+            "b3dab69f7e6100849434fb1781e34bd12a916557f6231b8d2629b6f6".to_string(),
+            name_hex.to_string(),
+        )
+    }
+
+    fn traits(pairs: &[(&str, &str)]) -> Traits {
+        let mut traits = Traits::new();
+        for (key, value) in pairs {
+            traits.insert_single(key.to_string(), value.to_string());
+        }
+        traits
+    }
+
+    #[test]
+    fn curve_accumulates_and_fills_gaps() {
+        let mut timeline = EpochTimeline::new(Network::Mainnet);
+        timeline.record(&asset("01"), 1, EPOCH_500 + 10, None);
+        timeline.record(&asset("02"), 1, EPOCH_500 + 20, None);
+        timeline.record(&asset("03"), 1, EPOCH_500 + 3 * EPOCH_SECS, None);
+        timeline.record(&asset("02"), -1, EPOCH_500 + 3 * EPOCH_SECS + 5, None);
+
+        let curve = timeline.supply_curve();
+        assert_eq!(curve.len(), 4);
+        assert_eq!(curve[0].epoch, 500);
+        assert_eq!(curve[0].new_assets, 2);
+        assert_eq!(curve[0].total_supply, 2);
+        assert_eq!(
+            curve[1],
+            EpochPoint {
+                epoch: 501,
+                total_supply: 2,
+                total_assets: 2,
+                ..Default::default()
+            }
+        );
+        assert_eq!(curve[3].minted, 1);
+        assert_eq!(curve[3].burned, 1);
+        assert_eq!(curve[3].total_supply, 2);
+        assert_eq!(curve[3].total_assets, 3);
+        assert_eq!(timeline.epoch_range(), Some((500, 503)));
+    }
+
+    #[test]
+    fn out_of_order_events_match_chain_order() {
+        let mut timeline = EpochTimeline::new(Network::Mainnet);
+        let gold = traits(&[("Background", "Gold")]);
+        timeline.record(&asset("01"), 1, EPOCH_500 + 2 * EPOCH_SECS, Some(&gold));
+        timeline.record(&asset("02"), 1, EPOCH_500, Some(&gold));
+        timeline.record(&asset("01"), 1, EPOCH_500 + EPOCH_SECS, Some(&gold));
+
+        let span = timeline.trait_value("Background", "Gold").unwrap();
+        assert_eq!(span.first_epoch, 500);
+        assert_eq!(span.last_epoch, 502);
+        assert_eq!(span.first_mint, EPOCH_500);
+        assert_eq!(span.assets, 2);
+
+        let curve = timeline.supply_curve();
+        assert_eq!(curve[1].new_assets, 1);
+        assert_eq!(curve[2].new_assets, 0);
+        assert_eq!(curve[2].total_supply, 3);
+    }
+
+    #[test]
+    fn burns_skip_traits_and_progress_caps() {
+        let mut timeline = EpochTimeline::new(Network::Preview);
+        let red = traits(&[("Color", "Red")]);
+        timeline.record(&asset("01"), -1, 1_700_000_000, Some(&red));
+        timeline.record(&asset("01"), 0, 1_700_000_000, Some(&red));
+
+        assert!(timeline.trait_value("Color", "Red").is_none());
+        assert_eq!(timeline.total_assets(), 0);
+        assert_eq!(timeline.supply_curve()[0].total_supply, 0);
+
+        timeline.record(&asset("01"), 1, 1_700_000_000, Some(&red));
+        timeline.record(&asset("02"), 1, 1_700_000_000, None);
+        assert_eq!(timeline.mint_progress(4), 0.5);
+        assert_eq!(timeline.mint_progress(1), 1.0);
+        assert_eq!(timeline.mint_progress(0), 0.0);
+        assert_eq!(timeline.traits().count(), 1);
+    }
+
+    #[test]
+    fn empty_timeline() {
+        let timeline = EpochTimeline::new(Network::Mainnet);
+        assert!(timeline.is_empty());
+        assert!(timeline.supply_curve().is_empty());
+        assert_eq!(timeline.epoch_range(), None);
+    }
+}
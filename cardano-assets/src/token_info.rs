@@ -0,0 +1,243 @@
+//! Display metadata for fungible tokens.
+//!
+//! Everything else in this crate assumes NFTs. Fungible tokens carry their
+//! metadata in one of two places:
+//!
+//! - the Cardano token registry (CIP-26), whose entries wrap every property
+//!   as `{ "value": ..., "sequenceNumber": ..., "signatures": [...] }` and
+//!   hold the logo as base64 PNG — see [`RegistryEntry`];
+//! - a CIP-68 `333` token's reference datum, whose metadata map is flat —
+//!   see [`TokenInfo::from_cip68_metadata`] and, behind the `cip68`
+//!   feature, [`decode_cip68_ft_datum`](crate::cip68::decode_cip68_ft_datum).
+//!
+//! Both become a [`TokenInfo`], which formats raw quantities with the
+//! token's decimals and ticker.
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// Where a [`TokenInfo`] came from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum TokenInfoSource {
+    /// Cardano token registry (CIP-26)
+    Registry,
+    /// CIP-68 `333` reference datum
+    Cip68,
+}
+
+/// Display metadata for a fungible token.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct TokenInfo {
+    pub name: String,
+    pub ticker: Option<String>,
+    /// Digits after the decimal point; raw quantities are in the smallest unit
+    #[serde(default)]
+    pub decimals: u8,
+    pub description: Option<String>,
+    /// Logo URI: `ipfs://`, `https://` or a `data:` URI
+    pub logo: Option<String>,
+    pub url: Option<String>,
+    pub source: TokenInfoSource,
+}
+
+impl TokenInfo {
+    /// `quantity` in whole tokens, e.g. `1234567` with 6 decimals is
+    /// `"1.234567"`; trailing zeros are dropped
+    #[must_use]
+    pub fn format_quantity(&self, quantity: u128) -> String {
+        let decimals = u32::from(self.decimals);
+        let Some(scale) = 10u128.checked_pow(decimals) else {
+            return format!("0.{quantity:0>width$}", width = decimals as usize)
+                .trim_end_matches('0')
+                .trim_end_matches('.')
+                .to_string();
+        };
+        let whole = quantity / scale;
+        let fraction = quantity % scale;
+        if fraction == 0 {
+            return whole.to_string();
+        }
+        let fraction = format!("{fraction:0>width$}", width = decimals as usize);
+        format!("{whole}.{}", fraction.trim_end_matches('0'))
+    }
+
+    /// [`format_quantity`](Self::format_quantity) followed by the ticker
+    /// (or name), e.g. `"1.5 SUNDAE"`
+    #[must_use]
+    pub fn format_amount(&self, quantity: u128) -> String {
+        let symbol = self.ticker.as_deref().unwrap_or(&self.name);
+        format!("{} {symbol}", self.format_quantity(quantity))
+    }
+
+    /// From a CIP-68 `333` metadata map rendered to JSON
+    pub fn from_cip68_metadata(metadata: Value) -> Result<Self, serde_json::Error> {
+        let metadata: Cip68FtMetadata = serde_json::from_value(metadata)?;
+        Ok(Self {
+            name: metadata.name,
+            ticker: metadata.ticker,
+            decimals: metadata.decimals,
+            description: metadata.description,
+            logo: metadata.logo.map(UriChunks::join),
+            url: metadata.url,
+            source: TokenInfoSource::Cip68,
+        })
+    }
+}
+
+/// CIP-68 FT metadata map (`name` and `description` are required by the
+/// spec, but `description` is often missing in the wild)
+#[derive(Deserialize)]
+struct Cip68FtMetadata {
+    name: String,
+    #[serde(default)]
+    description: Option<String>,
+    #[serde(default)]
+    ticker: Option<String>,
+    #[serde(default)]
+    url: Option<String>,
+    #[serde(default)]
+    decimals: u8,
+    #[serde(default)]
+    logo: Option<UriChunks>,
+}
+
+/// A URI, possibly split into chunks to fit metadata size limits
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum UriChunks {
+    Whole(String),
+    Chunks(Vec<String>),
+}
+
+impl UriChunks {
+    fn join(self) -> String {
+        match self {
+            UriChunks::Whole(uri) => uri,
+            UriChunks::Chunks(chunks) => chunks.concat(),
+        }
+    }
+}
+
+/// One signed property of a registry entry; signatures aren't checked
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RegistryProperty<T> {
+    pub value: T,
+    #[serde(default)]
+    pub sequence_number: u32,
+}
+
+/// A Cardano token registry (CIP-26) entry, as served by the registry API
+/// (`/metadata/{subject}`) or stored in the registry repository
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RegistryEntry {
+    /// Policy ID followed by the hex asset name
+    pub subject: String,
+    pub name: RegistryProperty<String>,
+    pub description: RegistryProperty<String>,
+    #[serde(default)]
+    pub ticker: Option<RegistryProperty<String>>,
+    #[serde(default)]
+    pub decimals: Option<RegistryProperty<u8>>,
+    /// Base64-encoded PNG
+    #[serde(default)]
+    pub logo: Option<RegistryProperty<String>>,
+    #[serde(default)]
+    pub url: Option<RegistryProperty<String>>,
+}
+
+impl From<RegistryEntry> for TokenInfo {
+    fn from(entry: RegistryEntry) -> Self {
+        Self {
+            name: entry.name.value,
+            ticker: entry.ticker.map(|t| t.value),
+            decimals: entry.decimals.map_or(0, |d| d.value),
+            description: Some(entry.description.value),
+            logo: entry
+                .logo
+                .map(|logo| format!("data:image/png;base64,{}", logo.value)),
+            url: entry.url.map(|u| u.value),
+            source: TokenInfoSource::Registry,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn sundae() -> TokenInfo {
+        let entry: RegistryEntry = serde_json::from_value(json!({
+            "subject": "9a9693a9a37912a5097918f97918d15240c92ab729a0b7c4aa144d7753554e444145",
+            "name": { "value": "SUNDAE", "sequenceNumber": 0, "signatures": [] },
+            "description": { "value": "The official token of SundaeSwap Labs", "sequenceNumber": 0 },
+            "ticker": { "value": "SUNDAE", "sequenceNumber": 0 },
+            "decimals": { "value": 6, "sequenceNumber": 0 },
+            "logo": { "value": "iVBORw0KGgo=", "sequenceNumber": 0 },
+            "url": { "value": "https://sundaeswap.finance", "sequenceNumber": 0 }
+        }))
+        .unwrap();
+        entry.into()
+    }
+
+    #[test]
+    fn test_registry_entry() {
+        let info = sundae();
+        assert_eq!(info.ticker.as_deref(), Some("SUNDAE"));
+        assert_eq!(info.decimals, 6);
+        assert_eq!(
+            info.logo.as_deref(),
+            Some("data:image/png;base64,iVBORw0KGgo=")
+        );
+        assert_eq!(info.source, TokenInfoSource::Registry);
+    }
+
+    #[test]
+    fn test_format_quantity() {
+        let info = sundae();
+        assert_eq!(info.format_quantity(1_234_567), "1.234567");
+        assert_eq!(info.format_quantity(1_500_000), "1.5");
+        assert_eq!(info.format_quantity(42_000_000), "42");
+        assert_eq!(info.format_quantity(5), "0.000005");
+        assert_eq!(info.format_amount(1_500_000), "1.5 SUNDAE");
+
+        let whole = TokenInfo {
+            decimals: 0,
+            ticker: None,
+            ..info.clone()
+        };
+        assert_eq!(whole.format_amount(1_000), "1000 SUNDAE");
+
+        let tiny = TokenInfo {
+            decimals: 40,
+            ..info
+        };
+        assert_eq!(tiny.format_quantity(25), format!("0.{}25", "0".repeat(38)));
+    }
+
+    #[test]
+    fn test_cip68_metadata() {
+        let info = TokenInfo::from_cip68_metadata(json!({
+            "name": "Indy",
+            "ticker": "INDY",
+            "decimals": 6,
+            "logo": ["ipfs://QmSfqtMhjqeU6cncYWpMXco", "QQVrzxsaap2SgRzmhkvXZC9"],
+            "version": 1
+        }))
+        .unwrap();
+        assert_eq!(info.name, "Indy");
+        assert_eq!(info.format_amount(2_500_000), "2.5 INDY");
+        assert_eq!(
+            info.logo.as_deref(),
+            Some("ipfs://QmSfqtMhjqeU6cncYWpMXcoQQVrzxsaap2SgRzmhkvXZC9")
+        );
+        assert_eq!(info.description, None);
+        assert_eq!(info.source, TokenInfoSource::Cip68);
+
+        assert!(TokenInfo::from_cip68_metadata(json!({ "ticker": "X" })).is_err());
+    }
+}
@@ -4,7 +4,7 @@
 //! including CIP-25 metadata parsing using JSON conversion for robust compatibility with various
 //! NFT metadata formats.
 
-use crate::{Asset, AssetId, AssetMetadata as CardanoAssetMetadata, AssetV2, Traits};
+use crate::{Asset, AssetId, AssetMetadata as CardanoAssetMetadata, AssetV2, MintEvent, Traits};
 use serde_json::Value;
 use tracing::debug;
 use utxorpc_spec::utxorpc::v1alpha::cardano as u5c;
@@ -110,6 +110,40 @@ pub fn extract_mint_assets_from_utxorpc_tx(tx: &u5c::Tx) -> Vec<AssetV2> {
     assets
 }
 
+/// Mint and burn events for every asset in a UTxORPC transaction's mint field
+///
+/// Feed the result into a [`SupplyLedger`](crate::SupplyLedger). Unlike
+/// [`extract_mint_assets_from_utxorpc_tx`] this keeps burns (negative
+/// quantities) and uses the real (possibly empty) asset name, so fungible
+/// tokens are keyed the same way they appear on chain.
+pub fn mint_events_from_utxorpc_tx(tx: &u5c::Tx, slot: Option<u64>) -> Vec<MintEvent> {
+    let mut events = Vec::new();
+
+    for multiasset in &tx.mint {
+        let policy_id = hex::encode(&multiasset.policy_id);
+
+        for asset in &multiasset.assets {
+            let quantity = match &asset.quantity {
+                Some(u5c::asset::Quantity::MintCoin(u5c::BigInt {
+                    big_int: Some(u5c::big_int::BigInt::Int(n)),
+                })) => *n,
+                other => {
+                    debug!("Skipping unsupported mint quantity: {:?}", other);
+                    continue;
+                }
+            };
+
+            events.push(MintEvent {
+                asset_id: AssetId::new_unchecked(policy_id.clone(), hex::encode(&asset.name)),
+                quantity,
+                slot,
+            });
+        }
+    }
+
+    events
+}
+
 /// Extract metadata for a specific asset from UTxORPC transaction auxiliary data
 ///
 /// This function extracts CIP-25 metadata from the transaction's auxiliary data
@@ -360,6 +394,43 @@ mod tests {
         assert!(!asset.id.policy_id().is_empty());
         assert!(!asset.id.asset_name_hex().is_empty());
     }
+
+    #[test]
+    fn test_mint_events_include_burns() {
+        let quantity = |n: i64| {
+            Some(u5c::asset::Quantity::MintCoin(u5c::BigInt {
+                big_int: Some(u5c::big_int::BigInt::Int(n)),
+            }))
+        };
+        let tx = u5c::Tx {
+            mint: vec![u5c::Multiasset {
+                policy_id: vec![0xab; 28].into(),
+                redeemer: None,
+                assets: vec![
+                    u5c::Asset {
+                        name: b"Minted".to_vec().into(),
+                        quantity: quantity(1),
+                    },
+                    u5c::Asset {
+                        name: b"Burned".to_vec().into(),
+                        quantity: quantity(-1),
+                    },
+                ],
+            }],
+            ..Default::default()
+        };
+
+        let events = mint_events_from_utxorpc_tx(&tx, Some(42));
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[0].asset_id.asset_name(), "Minted");
+        assert_eq!(events[0].quantity, 1);
+        assert_eq!(events[1].quantity, -1);
+        assert!(events.iter().all(|e| e.slot == Some(42)));
+
+        let mut ledger = crate::SupplyLedger::new();
+        ledger.extend(&events);
+        assert_eq!(ledger.policy(&"ab".repeat(28)).live_assets, 1);
+    }
 }
 
 // Integration tests with real CBOR data using pallas-utxorpc
@@ -0,0 +1,123 @@
+//! Holder snapshot diffing for whale alerts
+//!
+//! Compares two policy snapshots from [`CnftApi::get_for_policy`](crate::CnftApi::get_for_policy)
+//! by `owner_stake_key` and reports ownership changes.
+//!
+//! Assets are matched by `encoded_name`. An empty `owner_stake_key` means
+//! cnft.tools couldn't resolve the holder (e.g. the asset sits in a script
+//! address), so those assets are left out of moves and holder counts.
+
+use crate::CnftAsset;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+
+/// An ownership change between two snapshots
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum HolderEvent {
+    /// An asset changed wallets
+    AssetMoved {
+        encoded_name: String,
+        name: String,
+        from: String,
+        to: String,
+    },
+    /// A wallet gained at least the configured number of assets
+    Accumulated {
+        stake_key: String,
+        gained: u32,
+        holding: u32,
+    },
+    /// A wallet that held assets holds none
+    Exited {
+        stake_key: String,
+        previously_held: u32,
+    },
+}
+
+/// Count assets per known holder
+pub fn holder_counts(assets: &[CnftAsset]) -> HashMap<&str, u32> {
+    let mut counts = HashMap::new();
+    for asset in assets {
+        if !asset.owner_stake_key.is_empty() {
+            *counts.entry(asset.owner_stake_key.as_str()).or_default() += 1;
+        }
+    }
+    counts
+}
+
+/// Diff two snapshots of the same policy
+///
+/// `min_accumulated` is the net gain a wallet needs before an
+/// [`HolderEvent::Accumulated`] is reported. Events are ordered moves first
+/// (by asset), then accumulations (largest gain first), then exits (largest
+/// previous holding first).
+pub fn diff_holders(
+    before: &[CnftAsset],
+    after: &[CnftAsset],
+    min_accumulated: u32,
+) -> Vec<HolderEvent> {
+    let mut events = Vec::new();
+
+    let previous_owners: HashMap<&str, &str> = before
+        .iter()
+        .filter(|a| !a.owner_stake_key.is_empty())
+        .map(|a| (a.encoded_name.as_str(), a.owner_stake_key.as_str()))
+        .collect();
+
+    let mut moves: BTreeMap<&str, HolderEvent> = BTreeMap::new();
+    for asset in after.iter().filter(|a| !a.owner_stake_key.is_empty()) {
+        let Some(from) = previous_owners.get(asset.encoded_name.as_str()) else {
+            continue;
+        };
+        if *from != asset.owner_stake_key {
+            moves.insert(
+                &asset.encoded_name,
+                HolderEvent::AssetMoved {
+                    encoded_name: asset.encoded_name.clone(),
+                    name: asset.name.clone(),
+                    from: from.to_string(),
+                    to: asset.owner_stake_key.clone(),
+                },
+            );
+        }
+    }
+    events.extend(moves.into_values());
+
+    let before_counts = holder_counts(before);
+    let after_counts = holder_counts(after);
+
+    let mut accumulated: Vec<(&str, u32, u32)> = after_counts
+        .iter()
+        .filter_map(|(stake_key, &holding)| {
+            let previous = before_counts.get(stake_key).copied().unwrap_or_default();
+            let gained = holding.saturating_sub(previous);
+            (gained > 0 && gained >= min_accumulated).then_some((*stake_key, gained, holding))
+        })
+        .collect();
+    accumulated.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(b.0)));
+    events.extend(accumulated.into_iter().map(|(stake_key, gained, holding)| {
+        HolderEvent::Accumulated {
+            stake_key: stake_key.to_string(),
+            gained,
+            holding,
+        }
+    }));
+
+    let mut exited: Vec<(&str, u32)> = before_counts
+        .iter()
+        .filter(|(stake_key, _)| !after_counts.contains_key(*stake_key))
+        .map(|(stake_key, &held)| (*stake_key, held))
+        .collect();
+    exited.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(b.0)));
+    events.extend(
+        exited
+            .into_iter()
+            .map(|(stake_key, previously_held)| HolderEvent::Exited {
+                stake_key: stake_key.to_string(),
+                previously_held,
+            }),
+    );
+
+    events
+}
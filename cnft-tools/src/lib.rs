@@ -1,9 +1,13 @@
 mod error;
+mod holders;
+mod sync;
 mod test;
 
 pub use error::*;
+pub use holders::{diff_holders, holder_counts, HolderEvent};
+pub use sync::{content_hash, PolicySync};
 
-use http_client::HttpClient;
+use http_client::{path_segment, BaseClient, HttpClient};
 use serde::de::{MapAccess, Visitor};
 use serde::{Deserialize, Deserializer, Serialize};
 use std::collections::HashMap;
@@ -57,28 +61,90 @@ impl PartialEq for CnftAsset {
 pub struct AssetRarity(pub String, pub u32);
 
 pub struct CnftApi {
-    client: HttpClient,
+    api: BaseClient,
 }
 
 impl Default for CnftApi {
+    // `HttpClient` already sends `Accept: application/json`. Don't add a
+    // Content-Type: on wasm it makes browser GETs preflight, which fails
     fn default() -> Self {
         Self {
-            client: HttpClient::new()
-                .with_header("Accept", "application/json")
-                .with_header("Content-Type", "application/json"),
+            api: BaseClient::with_client(BASE_URL, HttpClient::new()),
         }
     }
 }
 
 impl CnftApi {
+    /// Use a preconfigured [`HttpClient`] (e.g. one replaying test fixtures)
+    pub fn with_http_client(mut self, client: HttpClient) -> Self {
+        self.api = self.api.with_http_client(client);
+        self
+    }
+
+    /// Authenticate requests with a bearer API key
+    pub fn with_api_key(mut self, api_key: &str) -> Self {
+        self.api = self
+            .api
+            .with_header("Authorization", &format!("Bearer {api_key}"));
+        self
+    }
+
+    /// Point at another host, e.g. a caching proxy (`https://` is assumed
+    /// when no scheme is given)
+    pub fn with_base_url(mut self, base_url: impl Into<String>) -> Self {
+        self.api = self.api.with_base_url(&base_url.into());
+        self
+    }
+
+    /// Create a CnftApi from a worker environment
+    ///
+    /// Both settings are optional, so an unconfigured worker gets the public API:
+    /// - CNFT_TOOLS_API_KEY: bearer API key (secrets store or env secret)
+    /// - CNFT_TOOLS_BASE_URL: base URL override (env var)
+    #[cfg(feature = "worker")]
+    pub async fn for_env(env: &worker_stack::worker::Env) -> worker_stack::worker::Result<Self> {
+        let mut api = Self::default();
+
+        match worker_utils::secrets::get_secret(env, "CNFT_TOOLS_API_KEY").await {
+            Ok(api_key) => api = api.with_api_key(&api_key),
+            Err(_) => tracing::debug!("[cnft-tools] no CNFT_TOOLS_API_KEY, using public API"),
+        }
+
+        if let Ok(base_url) = env.var("CNFT_TOOLS_BASE_URL") {
+            api = api.with_base_url(base_url.to_string());
+        }
+
+        Ok(api)
+    }
+
     pub fn extract_rarity(asset: &CnftAsset) -> AssetRarity {
         AssetRarity(asset.encoded_name.clone(), asset.rarity_rank)
     }
 
     pub async fn get_for_policy(&self, policy_id: &str) -> Result<Vec<CnftAsset>, CnftError> {
-        let url = format!("https://{BASE_URL}/{policy_id}");
-        tracing::info!("[cnft-tools] requesting {}", url);
-        self.client.get(&url).await.map_err(CnftError::Request)
+        let path = path_segment(policy_id);
+        tracing::info!("[cnft-tools] requesting {}", self.api.url(&path));
+        self.api.get_path(&path).await.map_err(CnftError::Request)
+    }
+
+    /// [`get_for_policy`](Self::get_for_policy), reporting
+    /// [`PolicySync::Unchanged`] when the dump's [`content_hash`] matches
+    /// `previous_hash` from the last run
+    pub async fn sync_policy(
+        &self,
+        policy_id: &str,
+        previous_hash: Option<&str>,
+    ) -> Result<PolicySync, CnftError> {
+        let assets = self.get_for_policy(policy_id).await?;
+        let content_hash = content_hash(&assets);
+        if previous_hash == Some(content_hash.as_str()) {
+            tracing::info!("[cnft-tools] {policy_id} unchanged since last sync");
+            return Ok(PolicySync::Unchanged);
+        }
+        Ok(PolicySync::Changed {
+            content_hash,
+            assets,
+        })
     }
 }
 
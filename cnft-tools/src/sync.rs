@@ -0,0 +1,74 @@
+//! Change detection for policy dumps
+//!
+//! cnft.tools has no modification-time filter, so every sync downloads the
+//! whole policy. [`content_hash`] fingerprints a dump independently of asset
+//! and trait order, and [`CnftApi::sync_policy`](crate::CnftApi::sync_policy)
+//! compares it with the hash stored by the previous run, so a cron can skip
+//! re-scoring and re-writing collections that haven't changed.
+
+use crate::CnftAsset;
+use sha2::{Digest, Sha256};
+
+/// Outcome of [`CnftApi::sync_policy`](crate::CnftApi::sync_policy)
+#[derive(Debug, Clone)]
+pub enum PolicySync {
+    /// The dump matches the previous hash; nothing to process
+    Unchanged,
+    /// New or changed content; store `content_hash` for the next run
+    Changed {
+        content_hash: String,
+        assets: Vec<CnftAsset>,
+    },
+}
+
+/// Hex SHA-256 over every field of every asset, in a canonical order
+/// (assets by `encoded_name`, traits by name)
+pub fn content_hash(assets: &[CnftAsset]) -> String {
+    let mut sorted: Vec<&CnftAsset> = assets.iter().collect();
+    sorted.sort_by(|a, b| a.encoded_name.cmp(&b.encoded_name));
+
+    let mut hasher = Sha256::new();
+    for asset in sorted {
+        hash_str(&mut hasher, &asset.encoded_name);
+        hash_str(&mut hasher, &asset.asset_id);
+        hash_opt(&mut hasher, asset.asset_name.as_deref());
+        hash_str(&mut hasher, &asset.name);
+        hash_opt(&mut hasher, asset.icon_url.as_deref());
+        hash_opt(&mut hasher, asset.build_type.as_deref());
+        hash_str(&mut hasher, &asset.owner_stake_key);
+        hasher.update(asset.rarity_rank.to_le_bytes());
+        hash_opt(
+            &mut hasher,
+            asset.trait_count.map(|c| c.to_string()).as_deref(),
+        );
+        hash_opt(&mut hasher, asset.on_sale.map(|s| s.to_string()).as_deref());
+
+        let mut traits: Vec<_> = asset.traits.iter().collect();
+        traits.sort();
+        hasher.update((traits.len() as u64).to_le_bytes());
+        for (name, values) in traits {
+            hash_str(&mut hasher, name);
+            hasher.update((values.len() as u64).to_le_bytes());
+            for value in values {
+                hash_str(&mut hasher, value);
+            }
+        }
+    }
+    hex::encode(hasher.finalize())
+}
+
+/// Length-prefixed, so adjacent fields can't run into each other
+fn hash_str(hasher: &mut Sha256, value: &str) {
+    hasher.update((value.len() as u64).to_le_bytes());
+    hasher.update(value.as_bytes());
+}
+
+fn hash_opt(hasher: &mut Sha256, value: Option<&str>) {
+    match value {
+        Some(value) => {
+            hasher.update([1]);
+            hash_str(hasher, value);
+        }
+        None => hasher.update([0]),
+    }
+}
@@ -2,7 +2,7 @@
 mod tests {
     #![allow(clippy::assertions_on_constants)]
 
-    use crate::{CnftApi, CnftAsset};
+    use crate::{content_hash, diff_holders, holder_counts, CnftApi, CnftAsset, HolderEvent};
 
     use std::collections::HashMap;
     use test_utils::test_case;
@@ -165,6 +165,112 @@ mod tests {
         assert!(!asset.traits.contains_key("Eyes")); // null trait value skipped
     }
 
+    fn owned(encoded_name: &str, owner: &str) -> CnftAsset {
+        CnftAsset {
+            on_sale: None,
+            asset_name: None,
+            asset_id: encoded_name.to_string(),
+            name: format!("Pirate {encoded_name}"),
+            icon_url: None,
+            trait_count: None,
+            encoded_name: encoded_name.to_string(),
+            build_type: None,
+            rarity_rank: 1,
+            owner_stake_key: owner.to_string(),
+            traits: HashMap::new(),
+        }
+    }
+
+    #[test]
+    fn test_diff_holders() {
+        let before = vec![
+            owned("01", "stake_alice"),
+            owned("02", "stake_alice"),
+            owned("03", "stake_bob"),
+            owned("04", "stake_carol"),
+            owned("05", ""),
+        ];
+        let after = vec![
+            owned("01", "stake_whale"),
+            owned("02", "stake_whale"),
+            owned("03", "stake_bob"),
+            owned("04", "stake_whale"),
+            owned("05", "stake_bob"),
+        ];
+
+        let counts = holder_counts(&before);
+        assert_eq!(counts.get("stake_alice"), Some(&2));
+        assert_eq!(counts.len(), 3);
+
+        let events = diff_holders(&before, &after, 2);
+        assert_eq!(
+            events,
+            vec![
+                HolderEvent::AssetMoved {
+                    encoded_name: "01".into(),
+                    name: "Pirate 01".into(),
+                    from: "stake_alice".into(),
+                    to: "stake_whale".into(),
+                },
+                HolderEvent::AssetMoved {
+                    encoded_name: "02".into(),
+                    name: "Pirate 02".into(),
+                    from: "stake_alice".into(),
+                    to: "stake_whale".into(),
+                },
+                HolderEvent::AssetMoved {
+                    encoded_name: "04".into(),
+                    name: "Pirate 04".into(),
+                    from: "stake_carol".into(),
+                    to: "stake_whale".into(),
+                },
+                HolderEvent::Accumulated {
+                    stake_key: "stake_whale".into(),
+                    gained: 3,
+                    holding: 3,
+                },
+                HolderEvent::Exited {
+                    stake_key: "stake_alice".into(),
+                    previously_held: 2,
+                },
+                HolderEvent::Exited {
+                    stake_key: "stake_carol".into(),
+                    previously_held: 1,
+                },
+            ]
+        );
+
+        // Bob's +1 from an unresolved holder only shows with a lower threshold
+        let events = diff_holders(&before, &after, 1);
+        assert!(events.contains(&HolderEvent::Accumulated {
+            stake_key: "stake_bob".into(),
+            gained: 1,
+            holding: 2,
+        }));
+
+        assert!(diff_holders(&after, &after, 1).is_empty());
+    }
+
+    #[test]
+    fn test_content_hash() {
+        let assets: Vec<CnftAsset> = serde_json::from_str(test_case!("blackflag.json")).unwrap();
+        let hash = content_hash(&assets);
+        assert_eq!(hash.len(), 64);
+
+        // Independent of the order the API returns assets in
+        let mut reversed = assets.clone();
+        reversed.reverse();
+        assert_eq!(content_hash(&reversed), hash);
+
+        let mut sold = assets.clone();
+        sold[0].owner_stake_key = "stake_bob".into();
+        assert_ne!(content_hash(&sold), hash);
+
+        let mut reranked = assets;
+        reranked[0].rarity_rank += 1;
+        assert_ne!(content_hash(&reranked), hash);
+    }
+
     #[tokio::test]
     async fn test_encounter() {
         worker_utils::init_tracing(Some(Level::DEBUG));
@@ -179,4 +285,26 @@ mod tests {
             Err(err) => panic!("failed to call microversus api: {err:?}"),
         }
     }
+
+    #[tokio::test]
+    async fn test_get_for_policy_replay() {
+        // Replays resources/fixtures/cnft-tools (first three Black Flag
+        // assets); refresh with HTTP_FIXTURES=record
+        let api = CnftApi::default().with_http_client(test_utils::fixture_client!("cnft-tools"));
+
+        let assets = api
+            .get_for_policy("b3dab69f7e6100849434fb1781e34bd12a916557f6231b8d2629b6f6")
+            .await
+            .unwrap();
+        assert_eq!(assets.len(), 3);
+        assert_eq!(assets[0].name, "Skeleton King");
+        assert_eq!(assets[0].rarity_rank, 1);
+        assert_eq!(assets[0].encoded_name, "506972617465343035");
+
+        // No fixture is recorded for this policy
+        assert!(api
+            .get_for_policy("43206de9e07fbd36ce6c109b3d34637727233c58a0b38f1da00a9ccf")
+            .await
+            .is_err());
+    }
 }
@@ -0,0 +1,50 @@
+#[cfg(target_arch = "wasm32")]
+mod wasm_tests {
+    use cnft_tools::{CnftApi, CnftAsset};
+    use wasm_bindgen_test::*;
+
+    wasm_bindgen_test_configure!(run_in_browser);
+
+    #[wasm_bindgen_test]
+    fn test_wasm_deserialize() {
+        let asset: CnftAsset = serde_json::from_str(include_str!(
+            "../resources/test/asset_in_list_response.json"
+        ))
+        .unwrap();
+
+        assert_eq!(asset.encoded_name, "506972617465333736");
+        assert_eq!(asset.rarity_rank, 59);
+        assert_eq!(asset.traits.len(), 9);
+    }
+
+    /// Serve the recorded cnft.tools dump from a blob URL. Blob fetches
+    /// ignore the fragment, so the policy path appended after `#` is
+    /// harmless.
+    fn fixture_base_url() -> String {
+        let fixture: serde_json::Value = serde_json::from_str(include_str!(
+            "../resources/fixtures/cnft-tools/get_api_cnft_tools_api_external_b3dab69f7e6100849434fb1781e34bd12a916557f6231b8d2629_9e7be807978b6208.json"
+        ))
+        .unwrap();
+        let body = js_sys::Array::of1(&fixture["body"].as_str().unwrap().into());
+        let options = web_sys::BlobPropertyBag::new();
+        options.set_type("application/json");
+        let blob = web_sys::Blob::new_with_str_sequence_and_options(&body, &options).unwrap();
+        let url = web_sys::Url::create_object_url_with_blob(&blob).unwrap();
+        format!("{url}#")
+    }
+
+    // Runs the default client's GET through the browser's fetch and decodes
+    // the replayed dump
+    #[wasm_bindgen_test]
+    async fn test_wasm_get_for_policy() {
+        let assets = CnftApi::default()
+            .with_base_url(fixture_base_url())
+            .get_for_policy("b3dab69f7e6100849434fb1781e34bd12a916557f6231b8d2629b6f6")
+            .await
+            .expect("fixture request failed");
+
+        assert_eq!(assets.len(), 3);
+        assert_eq!(assets[0].name, "Skeleton King");
+        assert_eq!(assets[0].rarity_rank, 1);
+    }
+}
@@ -24,6 +24,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         content: Some("Hello from discord-client native example!".to_string()),
         embeds: None,
         attachments: None,
+        components: None,
     };
     let response = client.send_message(&channel_id, &simple_message).await?;
     println!("Message sent! ID: {}", response.id);
@@ -33,6 +34,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     let edit = discord_client::DiscordMessageEdit {
         content: Some("Hello from discord-client (edited)!".to_string()),
         embeds: None,
+        components: None,
     };
     let response_id = response.id.to_string();
     let edited = client
@@ -54,6 +56,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         content: Some("Check out this embed!".to_string()),
         embeds: Some(vec![embed]),
         attachments: None,
+        components: None,
     };
     let response = client.send_message(&channel_id, &embed_message).await?;
     println!("Embed message sent! ID: {}", response.id);
@@ -105,6 +108,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         content: Some("Here's an image attachment!".to_string()),
         embeds: None,
         attachments: Some(vec![attachment]),
+        components: None,
     };
     let attachment_response = client
         .send_message(&channel_id, &attachment_message)
@@ -120,6 +124,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     let edit_keep_attachment = discord_client::DiscordMessageEdit {
         content: Some("Updated text; attachment should still be visible.".to_string()),
         embeds: None,
+        components: None,
     };
     let attachment_response_id = attachment_response.id.to_string();
     let edited_keep = client
@@ -173,6 +178,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     let edit_with_new = discord_client::DiscordMessageEdit {
         content: Some("Content updated during attachment add".to_string()),
         embeds: Some(vec![embed2]),
+        components: None,
     };
 
     let edited_with_new = client
@@ -0,0 +1,330 @@
+//! Attachment helpers for building [`AttachmentInput`]s from remote URLs
+//!
+//! Workers frequently want to attach NFT art served from IPFS gateways or R2.
+//! [`AttachmentInput::from_url`] downloads the file via `http-client`, checks it
+//! against the upload limit for the target guild's boost tier, and (with the
+//! `image-resize` feature) downscales oversized images to fit.
+//!
+//! On Workers, [`R2Attachment`] attaches an object straight from an R2 bucket
+//! binding via `WasmDiscordClient::send_message_with_r2`. The object body is
+//! streamed into a JS `Blob` for the multipart form and never copied into
+//! wasm memory, which grows to fit the largest file and never shrinks.
+
+use crate::{AttachmentInput, DiscordError};
+use http_client::{HttpClient, HttpError};
+use tracing::debug;
+
+const MB: usize = 1024 * 1024;
+
+/// Guild premium (boost) tier, which determines the maximum upload size
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum BoostTier {
+    #[default]
+    None,
+    Tier1,
+    Tier2,
+    Tier3,
+}
+
+impl BoostTier {
+    /// Maximum upload size in bytes for a single file in a guild at this tier
+    pub fn max_upload_bytes(&self) -> usize {
+        match self {
+            BoostTier::None | BoostTier::Tier1 => 8 * MB,
+            BoostTier::Tier2 => 50 * MB,
+            BoostTier::Tier3 => 100 * MB,
+        }
+    }
+
+    /// Map Discord's `premium_tier` guild field (0-3) to a boost tier
+    pub fn from_premium_tier(tier: u8) -> Self {
+        match tier {
+            0 => BoostTier::None,
+            1 => BoostTier::Tier1,
+            2 => BoostTier::Tier2,
+            _ => BoostTier::Tier3,
+        }
+    }
+}
+
+impl AttachmentInput {
+    /// Download `url` and wrap it as an attachment, using the unboosted size limit
+    pub async fn from_url(url: &str, client: &HttpClient) -> Result<Self, DiscordError> {
+        Self::from_url_with_tier(url, client, BoostTier::None).await
+    }
+
+    /// Download `url` and wrap it as an attachment sized for the given boost tier
+    ///
+    /// Oversized files are rejected with [`DiscordError::AttachmentTooLarge`]
+    /// unless the `image-resize` feature is enabled and the file is a still
+    /// image that can be re-encoded under the limit.
+    pub async fn from_url_with_tier(
+        url: &str,
+        client: &HttpClient,
+        tier: BoostTier,
+    ) -> Result<Self, DiscordError> {
+        let response = client
+            .get_bytes_with_details(url)
+            .await
+            .map_err(|e| match e {
+                HttpError::Status { code, .. } => {
+                    DiscordError::AttachmentDownload(format!("{url} returned status {code}"))
+                }
+                other => DiscordError::AttachmentDownload(format!("{url}: {other}")),
+            })?;
+
+        if response.data.is_empty() {
+            return Err(DiscordError::InvalidAttachment(format!(
+                "{url} returned an empty body"
+            )));
+        }
+
+        let extension = response
+            .get_header("content-type")
+            .and_then(|ct| extension_for_content_type(ct))
+            .or_else(|| extension_from_url(url))
+            .ok_or_else(|| {
+                DiscordError::InvalidAttachment(format!("Unable to determine file type for {url}"))
+            })?;
+
+        let filename = format!("{}.{extension}", file_stem_from_url(url));
+        debug!(
+            "📥 Downloaded attachment {filename} ({} bytes)",
+            response.data.len()
+        );
+
+        let (file_data, filename) = fit_to_limit(response.data, filename, tier.max_upload_bytes())?;
+
+        Ok(Self {
+            id: "0".to_string(),
+            filename,
+            description: None,
+            file_data,
+        })
+    }
+
+    /// Set the attachment id used for `attachment://` references
+    pub fn with_id(mut self, id: impl Into<String>) -> Self {
+        self.id = id.into();
+        self
+    }
+
+    /// Set the alt-text description shown by Discord clients
+    pub fn with_description(mut self, description: impl Into<String>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+}
+
+/// An R2 object to attach with `WasmDiscordClient::send_message_with_r2`
+///
+/// The attachment id is its `files[n]` index in the upload, so it's assigned
+/// when the message is sent.
+#[cfg(feature = "wasm")]
+#[derive(Debug, Clone)]
+pub struct R2Attachment {
+    pub key: String,
+    /// Defaults to the key's last segment, with an extension from the
+    /// object's content type if the key has none
+    pub filename: Option<String>,
+    pub description: Option<String>,
+}
+
+#[cfg(feature = "wasm")]
+impl R2Attachment {
+    pub fn new(key: impl Into<String>) -> Self {
+        Self {
+            key: key.into(),
+            filename: None,
+            description: None,
+        }
+    }
+
+    pub fn with_filename(mut self, filename: impl Into<String>) -> Self {
+        self.filename = Some(filename.into());
+        self
+    }
+
+    /// Set the alt-text description shown by Discord clients
+    pub fn with_description(mut self, description: impl Into<String>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+
+    /// Stream the object into an untyped JS `Blob`, returning it with the
+    /// attachment metadata for `payload_json` under id `index`
+    pub(crate) async fn fetch(
+        &self,
+        bucket: &worker_stack::worker::Bucket,
+        tier: BoostTier,
+        index: usize,
+    ) -> Result<(worker_stack::web_sys::Blob, AttachmentInput), DiscordError> {
+        use worker_stack::wasm_bindgen::{JsCast, JsValue};
+        use worker_stack::wasm_bindgen_futures::JsFuture;
+        use worker_stack::web_sys::{Blob, Response};
+        use worker_stack::worker::ResponseBody;
+
+        let key = &self.key;
+        let object = bucket.get(key).execute().await?.ok_or_else(|| {
+            DiscordError::AttachmentDownload(format!("R2 object {key} not found"))
+        })?;
+
+        let size = object.size() as usize;
+        let limit = tier.max_upload_bytes();
+        if size > limit {
+            return Err(DiscordError::AttachmentTooLarge { size, limit });
+        }
+
+        let filename = match &self.filename {
+            Some(filename) => filename.clone(),
+            None => {
+                let extension = object
+                    .http_metadata()
+                    .content_type
+                    .as_deref()
+                    .and_then(extension_for_content_type)
+                    .or_else(|| extension_from_url(key))
+                    .ok_or_else(|| {
+                        DiscordError::InvalidAttachment(format!(
+                            "Unable to determine file type for R2 object {key}"
+                        ))
+                    })?;
+                format!("{}.{extension}", file_stem_from_url(key))
+            }
+        };
+
+        let empty = || DiscordError::InvalidAttachment(format!("R2 object {key} is empty"));
+        let ResponseBody::Stream(stream) = object.body().ok_or_else(empty)?.response_body()? else {
+            return Err(empty());
+        };
+        let js_error =
+            |e: JsValue| DiscordError::Gloo(format!("Failed to read R2 object {key}: {e:?}"));
+        let response = Response::new_with_opt_readable_stream(Some(&stream)).map_err(js_error)?;
+        let blob: Blob = JsFuture::from(response.blob().map_err(js_error)?)
+            .await
+            .map_err(js_error)?
+            .unchecked_into();
+        if blob.size() == 0.0 {
+            return Err(empty());
+        }
+
+        debug!("📥 Streamed R2 attachment {filename} ({size} bytes)");
+
+        let metadata = AttachmentInput {
+            id: index.to_string(),
+            filename,
+            description: self.description.clone(),
+            file_data: Vec::new(),
+        };
+        Ok((blob, metadata))
+    }
+}
+
+#[cfg(not(feature = "image-resize"))]
+fn fit_to_limit(
+    data: Vec<u8>,
+    filename: String,
+    limit: usize,
+) -> Result<(Vec<u8>, String), DiscordError> {
+    if data.len() > limit {
+        return Err(DiscordError::AttachmentTooLarge {
+            size: data.len(),
+            limit,
+        });
+    }
+    Ok((data, filename))
+}
+
+#[cfg(feature = "image-resize")]
+fn fit_to_limit(
+    data: Vec<u8>,
+    filename: String,
+    limit: usize,
+) -> Result<(Vec<u8>, String), DiscordError> {
+    use image::codecs::jpeg::JpegEncoder;
+    use image::imageops::FilterType;
+
+    const MAX_ATTEMPTS: usize = 4;
+    const JPEG_QUALITY: u8 = 85;
+
+    let size = data.len();
+    if size <= limit {
+        return Ok((data, filename));
+    }
+
+    // Animated GIFs would lose their frames when re-encoded
+    if filename.ends_with(".gif") {
+        return Err(DiscordError::AttachmentTooLarge { size, limit });
+    }
+
+    let img = image::load_from_memory(&data).map_err(|e| {
+        DiscordError::InvalidAttachment(format!("Unable to decode oversized image: {e}"))
+    })?;
+
+    let mut scale = ((limit as f64 / size as f64).sqrt() * 0.9).min(1.0);
+    for _ in 0..MAX_ATTEMPTS {
+        let width = ((img.width() as f64 * scale) as u32).max(1);
+        let height = ((img.height() as f64 * scale) as u32).max(1);
+        // JPEG has no alpha channel, so flatten to RGB before encoding
+        let resized = img.resize(width, height, FilterType::Lanczos3).to_rgb8();
+
+        let mut buf = Vec::new();
+        JpegEncoder::new_with_quality(&mut buf, JPEG_QUALITY)
+            .encode_image(&resized)
+            .map_err(|e| DiscordError::InvalidAttachment(format!("Re-encode failed: {e}")))?;
+
+        if buf.len() <= limit {
+            debug!(
+                "🗜️ Resized attachment to {width}x{height} ({} -> {} bytes)",
+                size,
+                buf.len()
+            );
+            let stem = filename.rsplit_once('.').map_or(&*filename, |(s, _)| s);
+            return Ok((buf, format!("{stem}.jpg")));
+        }
+
+        scale *= 0.75;
+    }
+
+    Err(DiscordError::AttachmentTooLarge { size, limit })
+}
+
+fn extension_for_content_type(content_type: &str) -> Option<&'static str> {
+    let mime = content_type.split(';').next()?.trim();
+    match mime.to_ascii_lowercase().as_str() {
+        "image/png" => Some("png"),
+        "image/jpeg" | "image/jpg" => Some("jpg"),
+        "image/gif" => Some("gif"),
+        "image/webp" => Some("webp"),
+        _ => None,
+    }
+}
+
+fn extension_from_url(url: &str) -> Option<&'static str> {
+    let path = url.split(['?', '#']).next()?;
+    let (_, ext) = path.rsplit('/').next()?.rsplit_once('.')?;
+    match ext.to_ascii_lowercase().as_str() {
+        "png" => Some("png"),
+        "jpg" | "jpeg" => Some("jpg"),
+        "gif" => Some("gif"),
+        "webp" => Some("webp"),
+        _ => None,
+    }
+}
+
+fn file_stem_from_url(url: &str) -> String {
+    let path = url.split(['?', '#']).next().unwrap_or(url);
+    let last = path.trim_end_matches('/').rsplit('/').next().unwrap_or("");
+    let stem = last.rsplit_once('.').map_or(last, |(stem, _)| stem);
+    let cleaned: String = stem
+        .chars()
+        .filter(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_'))
+        .take(64)
+        .collect();
+
+    if cleaned.is_empty() {
+        "attachment".to_string()
+    } else {
+        cleaned
+    }
+}
@@ -0,0 +1,313 @@
+//! Guild audit log retrieval
+//!
+//! Moderation workers see a member's roles change and want to know who did
+//! it. [`DiscordClient::get_guild_audit_log`](crate::DiscordClient::get_guild_audit_log)
+//! fetches entries (the bot needs `VIEW_AUDIT_LOG`), and
+//! [`AuditLogEntry::action`] decodes the role, member and message actions:
+//!
+//! ```ignore
+//! use discord_client::audit_log::{AuditAction, AuditLogActionType, AuditLogQuery};
+//!
+//! let query = AuditLogQuery::new()
+//!     .action_type(AuditLogActionType::MemberRoleUpdate)
+//!     .limit(10);
+//! for entry in client.get_guild_audit_log(guild_id, &query).await? {
+//!     if let Some(AuditAction::MemberRoleUpdate { member_id, added, .. }) = entry.action() {
+//!         // entry.user_id granted `added` to member_id
+//!     }
+//! }
+//! ```
+
+use serde::Deserialize;
+use serde_json::Value;
+
+/// Audit log action types for role, member and message moderation
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[repr(u16)]
+pub enum AuditLogActionType {
+    MemberKick = 20,
+    MemberBanAdd = 22,
+    MemberBanRemove = 23,
+    MemberUpdate = 24,
+    MemberRoleUpdate = 25,
+    RoleCreate = 30,
+    RoleUpdate = 31,
+    RoleDelete = 32,
+    MessageDelete = 72,
+    MessageBulkDelete = 73,
+    MessagePin = 74,
+    MessageUnpin = 75,
+}
+
+impl AuditLogActionType {
+    const ALL: [Self; 12] = [
+        Self::MemberKick,
+        Self::MemberBanAdd,
+        Self::MemberBanRemove,
+        Self::MemberUpdate,
+        Self::MemberRoleUpdate,
+        Self::RoleCreate,
+        Self::RoleUpdate,
+        Self::RoleDelete,
+        Self::MessageDelete,
+        Self::MessageBulkDelete,
+        Self::MessagePin,
+        Self::MessageUnpin,
+    ];
+
+    /// `None` for the action types not covered here
+    pub fn from_code(code: u16) -> Option<Self> {
+        Self::ALL.into_iter().find(|t| *t as u16 == code)
+    }
+}
+
+/// Filters for `GET /guilds/{guild.id}/audit-logs`
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AuditLogQuery {
+    pub action_type: Option<AuditLogActionType>,
+    /// Only entries made by this user
+    pub user_id: Option<String>,
+    /// Only entries older than this entry id
+    pub before: Option<String>,
+    /// 1-100, Discord's default is 50
+    pub limit: Option<u8>,
+}
+
+impl AuditLogQuery {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn action_type(mut self, action_type: AuditLogActionType) -> Self {
+        self.action_type = Some(action_type);
+        self
+    }
+
+    pub fn user_id(mut self, user_id: impl Into<String>) -> Self {
+        self.user_id = Some(user_id.into());
+        self
+    }
+
+    pub fn before(mut self, entry_id: impl Into<String>) -> Self {
+        self.before = Some(entry_id.into());
+        self
+    }
+
+    pub fn limit(mut self, limit: u8) -> Self {
+        self.limit = Some(limit.clamp(1, 100));
+        self
+    }
+
+    /// Query string including the leading `?`, or empty without filters
+    pub fn to_query_string(&self) -> String {
+        let mut params = Vec::new();
+        if let Some(action_type) = self.action_type {
+            params.push(format!("action_type={}", action_type as u16));
+        }
+        if let Some(user_id) = &self.user_id {
+            params.push(format!("user_id={user_id}"));
+        }
+        if let Some(before) = &self.before {
+            params.push(format!("before={before}"));
+        }
+        if let Some(limit) = self.limit {
+            params.push(format!("limit={limit}"));
+        }
+        if params.is_empty() {
+            String::new()
+        } else {
+            format!("?{}", params.join("&"))
+        }
+    }
+}
+
+/// A change to one field of the entry's target
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct AuditLogChange {
+    pub key: String,
+    #[serde(default)]
+    pub old_value: Option<Value>,
+    #[serde(default)]
+    pub new_value: Option<Value>,
+}
+
+/// Extra details some action types carry
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+pub struct AuditLogOptions {
+    #[serde(default)]
+    pub channel_id: Option<String>,
+    #[serde(default)]
+    pub message_id: Option<String>,
+    /// Number of messages deleted (sent as a string)
+    #[serde(default)]
+    pub count: Option<String>,
+}
+
+/// One audit log entry, as Discord returns it
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct AuditLogEntry {
+    pub id: String,
+    /// Who performed the action
+    #[serde(default)]
+    pub user_id: Option<String>,
+    /// What it was performed on (user, role, ...)
+    #[serde(default)]
+    pub target_id: Option<String>,
+    pub action_type: u16,
+    #[serde(default)]
+    pub changes: Vec<AuditLogChange>,
+    #[serde(default)]
+    pub options: Option<AuditLogOptions>,
+    #[serde(default)]
+    pub reason: Option<String>,
+}
+
+/// `GET /guilds/{guild.id}/audit-logs` response; only the entries are kept
+#[cfg(any(feature = "native", feature = "wasm"))]
+#[derive(Deserialize)]
+pub(crate) struct AuditLogResponse {
+    pub audit_log_entries: Vec<AuditLogEntry>,
+}
+
+/// A role as listed in `$add` / `$remove` changes
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct AuditRole {
+    pub id: String,
+    pub name: String,
+}
+
+/// Typed view of the role, member and message actions
+#[derive(Debug, Clone, PartialEq)]
+pub enum AuditAction {
+    MemberKick {
+        member_id: String,
+    },
+    MemberBanAdd {
+        member_id: String,
+    },
+    MemberBanRemove {
+        member_id: String,
+    },
+    /// Nickname, timeout and similar changes; see `changes`
+    MemberUpdate {
+        member_id: String,
+        changes: Vec<AuditLogChange>,
+    },
+    MemberRoleUpdate {
+        member_id: String,
+        added: Vec<AuditRole>,
+        removed: Vec<AuditRole>,
+    },
+    RoleCreate {
+        role_id: String,
+        changes: Vec<AuditLogChange>,
+    },
+    RoleUpdate {
+        role_id: String,
+        changes: Vec<AuditLogChange>,
+    },
+    RoleDelete {
+        role_id: String,
+        changes: Vec<AuditLogChange>,
+    },
+    /// Another user's message deleted by a moderator
+    MessageDelete {
+        author_id: String,
+        channel_id: Option<String>,
+        count: u32,
+    },
+    MessageBulkDelete {
+        channel_id: Option<String>,
+        count: u32,
+    },
+    MessagePin {
+        channel_id: Option<String>,
+        message_id: Option<String>,
+    },
+    MessageUnpin {
+        channel_id: Option<String>,
+        message_id: Option<String>,
+    },
+}
+
+impl AuditLogEntry {
+    pub fn action_type(&self) -> Option<AuditLogActionType> {
+        AuditLogActionType::from_code(self.action_type)
+    }
+
+    /// Decode the entry; `None` for other action types or entries without
+    /// the target they should have
+    pub fn action(&self) -> Option<AuditAction> {
+        use AuditLogActionType as T;
+
+        let options = self.options.clone().unwrap_or_default();
+        let count = options
+            .count
+            .as_deref()
+            .and_then(|c| c.parse().ok())
+            .unwrap_or(1);
+        let target = || self.target_id.clone();
+        let changes = || self.changes.clone();
+
+        Some(match self.action_type()? {
+            T::MemberKick => AuditAction::MemberKick {
+                member_id: target()?,
+            },
+            T::MemberBanAdd => AuditAction::MemberBanAdd {
+                member_id: target()?,
+            },
+            T::MemberBanRemove => AuditAction::MemberBanRemove {
+                member_id: target()?,
+            },
+            T::MemberUpdate => AuditAction::MemberUpdate {
+                member_id: target()?,
+                changes: changes(),
+            },
+            T::MemberRoleUpdate => AuditAction::MemberRoleUpdate {
+                member_id: target()?,
+                added: self.roles("$add"),
+                removed: self.roles("$remove"),
+            },
+            T::RoleCreate => AuditAction::RoleCreate {
+                role_id: target()?,
+                changes: changes(),
+            },
+            T::RoleUpdate => AuditAction::RoleUpdate {
+                role_id: target()?,
+                changes: changes(),
+            },
+            T::RoleDelete => AuditAction::RoleDelete {
+                role_id: target()?,
+                changes: changes(),
+            },
+            T::MessageDelete => AuditAction::MessageDelete {
+                author_id: target()?,
+                channel_id: options.channel_id,
+                count,
+            },
+            T::MessageBulkDelete => AuditAction::MessageBulkDelete {
+                channel_id: options.channel_id.or_else(target),
+                count,
+            },
+            T::MessagePin => AuditAction::MessagePin {
+                channel_id: options.channel_id,
+                message_id: options.message_id,
+            },
+            T::MessageUnpin => AuditAction::MessageUnpin {
+                channel_id: options.channel_id,
+                message_id: options.message_id,
+            },
+        })
+    }
+
+    /// Roles listed under a `$add` / `$remove` change
+    fn roles(&self, key: &str) -> Vec<AuditRole> {
+        self.changes
+            .iter()
+            .filter(|change| change.key == key)
+            .filter_map(|change| change.new_value.clone())
+            .filter_map(|value| serde_json::from_value::<Vec<AuditRole>>(value).ok())
+            .flatten()
+            .collect()
+    }
+}
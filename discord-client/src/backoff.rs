@@ -0,0 +1,179 @@
+//! Sending with retries on rate limits.
+//!
+//! [`send_with_backoff`] retries a send that hits a 429, waiting at least
+//! as long as Discord asks and backing off exponentially between attempts.
+//! A global rate limit is recorded for the whole isolate, so every send that
+//! goes through this helper waits it out instead of each one tripping it
+//! again. Once the attempt or wait budget in [`BackoffPolicy`] runs out the
+//! send fails with [`DiscordError::RetriesExhausted`].
+//!
+//! ```ignore
+//! use discord_client::{send_with_backoff, BackoffPolicy};
+//!
+//! let message = send_with_backoff(&client, &channel_id, &announcement, &BackoffPolicy::default()).await?;
+//! ```
+//!
+//! Queue consumers that would rather re-enqueue than sleep should use
+//! [`ScheduledSender`](crate::ScheduledSender) instead.
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+
+#[cfg(any(feature = "native", all(feature = "wasm", target_arch = "wasm32")))]
+use twilight_model::channel::Message;
+
+#[cfg(any(feature = "native", all(feature = "wasm", target_arch = "wasm32")))]
+use crate::{DiscordError, DiscordMessage, DynDiscordClient};
+
+/// Unix milliseconds until which Discord's global rate limit applies
+static GLOBAL_LIMIT_UNTIL_MS: AtomicU64 = AtomicU64::new(0);
+
+/// Retry limits for [`send_with_backoff`]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BackoffPolicy {
+    /// Attempts in total, including the first
+    pub max_attempts: u32,
+    /// Wait before the first retry
+    pub initial_delay: Duration,
+    /// Growth of the wait per retry
+    pub multiplier: f64,
+    /// Cap on the backoff wait; Discord's `retry_after` is always honored
+    pub max_delay: Duration,
+    /// Cap on the total time spent waiting across all retries
+    pub max_total_wait: Duration,
+}
+
+impl Default for BackoffPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 5,
+            initial_delay: Duration::from_millis(500),
+            multiplier: 2.0,
+            max_delay: Duration::from_secs(30),
+            max_total_wait: Duration::from_secs(60),
+        }
+    }
+}
+
+impl BackoffPolicy {
+    /// Wait before retry number `retry` (1-based) after Discord asked for
+    /// `retry_after` seconds
+    pub fn delay_for(&self, retry: u32, retry_after: f64) -> Duration {
+        let exponent = retry.saturating_sub(1).min(i32::MAX as u32) as i32;
+        let backoff = self
+            .initial_delay
+            .mul_f64(self.multiplier.max(1.0).powi(exponent).min(1e6))
+            .min(self.max_delay);
+        backoff.max(Duration::from_secs_f64(retry_after.max(0.0)))
+    }
+}
+
+/// When the isolate-wide global rate limit lifts, if it's still in force at
+/// `now_ms`
+pub fn global_rate_limit_until(now_ms: u64) -> Option<u64> {
+    let until = GLOBAL_LIMIT_UNTIL_MS.load(Ordering::Relaxed);
+    (until > now_ms).then_some(until)
+}
+
+/// Record a global rate limit lasting until `until_ms`. Earlier times never
+/// shorten one already recorded.
+pub fn note_global_rate_limit(until_ms: u64) {
+    GLOBAL_LIMIT_UNTIL_MS.fetch_max(until_ms, Ordering::Relaxed);
+}
+
+/// Send `message`, retrying rate-limited attempts under `policy`.
+///
+/// Errors other than [`DiscordError::RateLimited`] are returned at once.
+#[cfg(any(feature = "native", all(feature = "wasm", target_arch = "wasm32")))]
+pub async fn send_with_backoff(
+    client: &dyn DynDiscordClient,
+    channel_id: &str,
+    message: &DiscordMessage,
+    policy: &BackoffPolicy,
+) -> Result<Message, DiscordError> {
+    let mut waited = Duration::ZERO;
+    let mut attempts = 0;
+
+    loop {
+        // Sit out a global limit another send already ran into
+        let now_ms = clock::now_ms();
+        if let Some(until_ms) = global_rate_limit_until(now_ms) {
+            let wait = Duration::from_millis(until_ms - now_ms);
+            if waited + wait > policy.max_total_wait {
+                return Err(DiscordError::RetriesExhausted {
+                    attempts,
+                    waited_ms: waited.as_millis() as u64,
+                    last: Box::new(DiscordError::RateLimited {
+                        retry_after: wait.as_secs_f64(),
+                        global: true,
+                    }),
+                });
+            }
+            clock::sleep(wait).await;
+            waited += wait;
+        }
+
+        attempts += 1;
+        let (retry_after, global) = match client.send_message(channel_id, message).await {
+            Err(DiscordError::RateLimited {
+                retry_after,
+                global,
+            }) => (retry_after, global),
+            result => return result,
+        };
+
+        let delay = policy.delay_for(attempts, retry_after);
+        if global {
+            note_global_rate_limit(clock::now_ms() + delay.as_millis() as u64);
+        }
+        if attempts >= policy.max_attempts || waited + delay > policy.max_total_wait {
+            return Err(DiscordError::RetriesExhausted {
+                attempts,
+                waited_ms: waited.as_millis() as u64,
+                last: Box::new(DiscordError::RateLimited {
+                    retry_after,
+                    global,
+                }),
+            });
+        }
+
+        tracing::warn!(
+            "Rate limited sending to {channel_id} (global: {global}), retrying in {:.2}s",
+            delay.as_secs_f64()
+        );
+        if !global {
+            clock::sleep(delay).await;
+            waited += delay;
+        }
+    }
+}
+
+#[cfg(all(feature = "wasm", target_arch = "wasm32"))]
+mod clock {
+    use std::time::Duration;
+
+    use worker_stack::worker::{Date, Delay};
+
+    pub(super) fn now_ms() -> u64 {
+        Date::now().as_millis()
+    }
+
+    pub(super) async fn sleep(duration: Duration) {
+        Delay::from(duration).await;
+    }
+}
+
+#[cfg(all(feature = "native", not(all(feature = "wasm", target_arch = "wasm32"))))]
+mod clock {
+    use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+    pub(super) fn now_ms() -> u64 {
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |d| d.as_millis() as u64)
+    }
+
+    pub(super) async fn sleep(duration: Duration) {
+        tokio::time::sleep(duration).await;
+    }
+}
@@ -0,0 +1,742 @@
+//! Message components (buttons and select menus) and component interactions
+//!
+//! Outgoing messages carry up to five [`ActionRow`]s, each holding either up
+//! to five [`Button`]s or a single [`SelectMenu`]. When a user clicks one,
+//! Discord posts a `MESSAGE_COMPONENT` interaction to the app's endpoint;
+//! [`ComponentInteraction::parse`] extracts what was clicked and a
+//! [`ComponentRouter`] dispatches on the `custom_id`.
+//!
+//! Custom ids follow an `action:arg:arg` convention (see [`CustomId`]) so the
+//! action and its arguments survive the round trip without server-side state:
+//!
+//! ```
+//! use discord_client::components::{ActionRow, Button, ComponentRouter, CustomId};
+//!
+//! let policy = "b3dab69f7e6100849434fb1781e34bd12a916557f6231b8d2629b6f6";
+//! let row = ActionRow::new()
+//!     .button(Button::success(CustomId::new("watch").arg(policy), "Watch"))
+//!     .button(Button::secondary(CustomId::new("mute").arg(policy), "Mute collection"));
+//! assert_eq!(row.len(), 2);
+//!
+//! let router = ComponentRouter::new()
+//!     .on("watch", |_, args| format!("watching {}", args[0]))
+//!     .on("mute", |_, args| format!("muted {}", args[0]));
+//! # let _ = router;
+//! ```
+
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::collections::HashMap;
+
+use crate::{DiscordError, IntegrationType, InteractionContext};
+
+/// Maximum action rows per message
+pub const MAX_ACTION_ROWS: usize = 5;
+/// Maximum buttons per action row
+pub const MAX_BUTTONS_PER_ROW: usize = 5;
+/// Maximum options in a select menu
+pub const MAX_SELECT_OPTIONS: usize = 25;
+/// Maximum length of a `custom_id`
+pub const MAX_CUSTOM_ID_LEN: usize = 100;
+
+const TYPE_ACTION_ROW: u8 = 1;
+const TYPE_BUTTON: u8 = 2;
+const TYPE_STRING_SELECT: u8 = 3;
+
+/// Visual style of a [`Button`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ButtonStyle {
+    Primary,
+    Secondary,
+    Success,
+    Danger,
+    /// Opens a URL instead of sending an interaction
+    Link,
+}
+
+impl ButtonStyle {
+    fn code(self) -> u8 {
+        match self {
+            ButtonStyle::Primary => 1,
+            ButtonStyle::Secondary => 2,
+            ButtonStyle::Success => 3,
+            ButtonStyle::Danger => 4,
+            ButtonStyle::Link => 5,
+        }
+    }
+
+    fn from_code(code: u8) -> Option<Self> {
+        Some(match code {
+            1 => ButtonStyle::Primary,
+            2 => ButtonStyle::Secondary,
+            3 => ButtonStyle::Success,
+            4 => ButtonStyle::Danger,
+            5 => ButtonStyle::Link,
+            _ => return None,
+        })
+    }
+}
+
+/// A clickable button
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Button {
+    pub style: ButtonStyle,
+    pub label: Option<String>,
+    /// Set for every style except [`ButtonStyle::Link`]
+    pub custom_id: Option<String>,
+    /// Set only for [`ButtonStyle::Link`]
+    pub url: Option<String>,
+    /// Unicode emoji shown before the label
+    pub emoji: Option<String>,
+    pub disabled: bool,
+}
+
+impl Button {
+    fn interactive(style: ButtonStyle, custom_id: impl Into<String>, label: &str) -> Self {
+        Self {
+            style,
+            label: Some(label.to_string()),
+            custom_id: Some(custom_id.into()),
+            url: None,
+            emoji: None,
+            disabled: false,
+        }
+    }
+
+    pub fn primary(custom_id: impl Into<String>, label: &str) -> Self {
+        Self::interactive(ButtonStyle::Primary, custom_id, label)
+    }
+
+    pub fn secondary(custom_id: impl Into<String>, label: &str) -> Self {
+        Self::interactive(ButtonStyle::Secondary, custom_id, label)
+    }
+
+    pub fn success(custom_id: impl Into<String>, label: &str) -> Self {
+        Self::interactive(ButtonStyle::Success, custom_id, label)
+    }
+
+    pub fn danger(custom_id: impl Into<String>, label: &str) -> Self {
+        Self::interactive(ButtonStyle::Danger, custom_id, label)
+    }
+
+    /// A button that opens `url` (e.g. a marketplace listing) without an interaction
+    pub fn link(url: &str, label: &str) -> Self {
+        Self {
+            style: ButtonStyle::Link,
+            label: Some(label.to_string()),
+            custom_id: None,
+            url: Some(url.to_string()),
+            emoji: None,
+            disabled: false,
+        }
+    }
+
+    pub fn emoji(mut self, emoji: &str) -> Self {
+        self.emoji = Some(emoji.to_string());
+        self
+    }
+
+    pub fn disabled(mut self, disabled: bool) -> Self {
+        self.disabled = disabled;
+        self
+    }
+}
+
+/// One choice in a [`SelectMenu`]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SelectOption {
+    pub label: String,
+    pub value: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub default: bool,
+}
+
+impl SelectOption {
+    pub fn new(label: &str, value: &str) -> Self {
+        Self {
+            label: label.to_string(),
+            value: value.to_string(),
+            description: None,
+            default: false,
+        }
+    }
+
+    pub fn description(mut self, description: &str) -> Self {
+        self.description = Some(description.to_string());
+        self
+    }
+
+    /// Pre-select this option
+    pub fn selected(mut self) -> Self {
+        self.default = true;
+        self
+    }
+}
+
+/// A dropdown of string options
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SelectMenu {
+    pub custom_id: String,
+    pub options: Vec<SelectOption>,
+    pub placeholder: Option<String>,
+    pub min_values: Option<u8>,
+    pub max_values: Option<u8>,
+    pub disabled: bool,
+}
+
+impl SelectMenu {
+    pub fn new(custom_id: impl Into<String>) -> Self {
+        Self {
+            custom_id: custom_id.into(),
+            options: Vec::new(),
+            placeholder: None,
+            min_values: None,
+            max_values: None,
+            disabled: false,
+        }
+    }
+
+    pub fn option(mut self, option: SelectOption) -> Self {
+        self.options.push(option);
+        self
+    }
+
+    pub fn placeholder(mut self, placeholder: &str) -> Self {
+        self.placeholder = Some(placeholder.to_string());
+        self
+    }
+
+    /// Allow selecting between `min` and `max` options (Discord defaults to exactly one)
+    pub fn values(mut self, min: u8, max: u8) -> Self {
+        self.min_values = Some(min);
+        self.max_values = Some(max);
+        self
+    }
+
+    pub fn disabled(mut self, disabled: bool) -> Self {
+        self.disabled = disabled;
+        self
+    }
+}
+
+/// A component that can sit inside an [`ActionRow`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Component {
+    Button(Button),
+    SelectMenu(SelectMenu),
+}
+
+impl Component {
+    fn custom_id(&self) -> Option<&str> {
+        match self {
+            Component::Button(button) => button.custom_id.as_deref(),
+            Component::SelectMenu(menu) => Some(&menu.custom_id),
+        }
+    }
+}
+
+/// A horizontal row of components on a message
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(into = "RawComponent", try_from = "RawComponent")]
+pub struct ActionRow {
+    pub components: Vec<Component>,
+}
+
+impl ActionRow {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn button(mut self, button: Button) -> Self {
+        self.components.push(Component::Button(button));
+        self
+    }
+
+    /// A row holding a single select menu (menus can't share a row)
+    pub fn select_menu(menu: SelectMenu) -> Self {
+        Self {
+            components: vec![Component::SelectMenu(menu)],
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.components.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.components.is_empty()
+    }
+}
+
+/// Check rows against Discord's component limits before sending
+///
+/// Discord rejects the whole message with an opaque `50035` error when any
+/// limit is exceeded, so this reports which rule was broken instead.
+pub fn validate_components(rows: &[ActionRow]) -> Result<(), DiscordError> {
+    let invalid = |msg: String| Err(DiscordError::InvalidComponent(msg));
+
+    if rows.len() > MAX_ACTION_ROWS {
+        return invalid(format!(
+            "{} action rows exceeds the limit of {MAX_ACTION_ROWS}",
+            rows.len()
+        ));
+    }
+
+    let mut seen_ids = std::collections::HashSet::new();
+    for (index, row) in rows.iter().enumerate() {
+        if row.is_empty() {
+            return invalid(format!("action row {index} is empty"));
+        }
+
+        let menus = row
+            .components
+            .iter()
+            .filter(|c| matches!(c, Component::SelectMenu(_)))
+            .count();
+        if menus > 0 && row.len() > 1 {
+            return invalid(format!(
+                "action row {index} mixes a select menu with other components"
+            ));
+        }
+        if row.len() > MAX_BUTTONS_PER_ROW {
+            return invalid(format!(
+                "action row {index} has {} buttons, limit is {MAX_BUTTONS_PER_ROW}",
+                row.len()
+            ));
+        }
+
+        for component in &row.components {
+            if let Component::Button(button) = component {
+                let is_link = button.style == ButtonStyle::Link;
+                if is_link != button.url.is_some() || is_link == button.custom_id.is_some() {
+                    return invalid(format!(
+                        "button in row {index} needs a url if and only if it is a link button"
+                    ));
+                }
+            }
+            if let Component::SelectMenu(menu) = component {
+                if menu.options.is_empty() || menu.options.len() > MAX_SELECT_OPTIONS {
+                    return invalid(format!(
+                        "select menu '{}' needs 1-{MAX_SELECT_OPTIONS} options",
+                        menu.custom_id
+                    ));
+                }
+            }
+            if let Some(custom_id) = component.custom_id() {
+                if custom_id.is_empty() || custom_id.len() > MAX_CUSTOM_ID_LEN {
+                    return invalid(format!(
+                        "custom_id '{custom_id}' must be 1-{MAX_CUSTOM_ID_LEN} characters"
+                    ));
+                }
+                if !seen_ids.insert(custom_id) {
+                    return invalid(format!("duplicate custom_id '{custom_id}'"));
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Wire format shared by every component type (discriminated by `type`)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct RawComponent {
+    #[serde(rename = "type")]
+    kind: u8,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    components: Vec<RawComponent>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    style: Option<u8>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    label: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    custom_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    url: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    emoji: Option<RawEmoji>,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    disabled: bool,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    options: Vec<SelectOption>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    placeholder: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    min_values: Option<u8>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    max_values: Option<u8>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct RawEmoji {
+    name: String,
+}
+
+impl From<Component> for RawComponent {
+    fn from(component: Component) -> Self {
+        match component {
+            Component::Button(button) => RawComponent {
+                kind: TYPE_BUTTON,
+                style: Some(button.style.code()),
+                label: button.label,
+                custom_id: button.custom_id,
+                url: button.url,
+                emoji: button.emoji.map(|name| RawEmoji { name }),
+                disabled: button.disabled,
+                ..Default::default()
+            },
+            Component::SelectMenu(menu) => RawComponent {
+                kind: TYPE_STRING_SELECT,
+                custom_id: Some(menu.custom_id),
+                options: menu.options,
+                placeholder: menu.placeholder,
+                min_values: menu.min_values,
+                max_values: menu.max_values,
+                disabled: menu.disabled,
+                ..Default::default()
+            },
+        }
+    }
+}
+
+impl TryFrom<RawComponent> for Component {
+    type Error = String;
+
+    fn try_from(raw: RawComponent) -> Result<Self, Self::Error> {
+        match raw.kind {
+            TYPE_BUTTON => Ok(Component::Button(Button {
+                style: raw
+                    .style
+                    .and_then(ButtonStyle::from_code)
+                    .ok_or_else(|| format!("unknown button style {:?}", raw.style))?,
+                label: raw.label,
+                custom_id: raw.custom_id,
+                url: raw.url,
+                emoji: raw.emoji.map(|e| e.name),
+                disabled: raw.disabled,
+            })),
+            TYPE_STRING_SELECT => Ok(Component::SelectMenu(SelectMenu {
+                custom_id: raw.custom_id.ok_or("select menu without custom_id")?,
+                options: raw.options,
+                placeholder: raw.placeholder,
+                min_values: raw.min_values,
+                max_values: raw.max_values,
+                disabled: raw.disabled,
+            })),
+            other => Err(format!("unsupported component type {other}")),
+        }
+    }
+}
+
+impl From<ActionRow> for RawComponent {
+    fn from(row: ActionRow) -> Self {
+        RawComponent {
+            kind: TYPE_ACTION_ROW,
+            components: row.components.into_iter().map(Into::into).collect(),
+            ..Default::default()
+        }
+    }
+}
+
+impl TryFrom<RawComponent> for ActionRow {
+    type Error = String;
+
+    fn try_from(raw: RawComponent) -> Result<Self, Self::Error> {
+        if raw.kind != TYPE_ACTION_ROW {
+            return Err(format!(
+                "expected action row, got component type {}",
+                raw.kind
+            ));
+        }
+        let components = raw
+            .components
+            .into_iter()
+            .map(Component::try_from)
+            .collect::<Result<_, _>>()?;
+        Ok(ActionRow { components })
+    }
+}
+
+/// Builder/parser for `action:arg:arg` custom ids
+///
+/// Arguments must not contain `:`; policy ids, asset ids and snowflakes are
+/// all safe.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CustomId {
+    pub action: String,
+    pub args: Vec<String>,
+}
+
+impl CustomId {
+    pub const SEPARATOR: char = ':';
+
+    pub fn new(action: &str) -> Self {
+        Self {
+            action: action.to_string(),
+            args: Vec::new(),
+        }
+    }
+
+    pub fn arg(mut self, arg: impl ToString) -> Self {
+        self.args.push(arg.to_string());
+        self
+    }
+
+    pub fn parse(custom_id: &str) -> Self {
+        let mut parts = custom_id.split(Self::SEPARATOR);
+        let action = parts.next().unwrap_or_default().to_string();
+        Self {
+            action,
+            args: parts.map(str::to_string).collect(),
+        }
+    }
+}
+
+impl std::fmt::Display for CustomId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.action)?;
+        for arg in &self.args {
+            write!(f, "{}{arg}", Self::SEPARATOR)?;
+        }
+        Ok(())
+    }
+}
+
+impl From<CustomId> for String {
+    fn from(id: CustomId) -> Self {
+        id.to_string()
+    }
+}
+
+/// Interaction type Discord uses for button clicks and menu selections
+const INTERACTION_MESSAGE_COMPONENT: u8 = 3;
+
+#[derive(Deserialize)]
+struct RawInteraction {
+    id: String,
+    application_id: String,
+    #[serde(rename = "type")]
+    kind: u8,
+    token: String,
+    data: Option<RawInteractionData>,
+    guild_id: Option<String>,
+    channel_id: Option<String>,
+    member: Option<RawMember>,
+    user: Option<RawUser>,
+    message: Option<RawMessageRef>,
+    context: Option<InteractionContext>,
+    /// Integration type (as a string key) to the installing guild or user id
+    #[serde(default)]
+    authorizing_integration_owners: HashMap<String, String>,
+}
+
+#[derive(Deserialize)]
+struct RawInteractionData {
+    custom_id: String,
+    component_type: u8,
+    #[serde(default)]
+    values: Vec<String>,
+}
+
+#[derive(Deserialize)]
+struct RawMember {
+    user: RawUser,
+}
+
+#[derive(Deserialize)]
+struct RawUser {
+    id: String,
+}
+
+#[derive(Deserialize)]
+struct RawMessageRef {
+    id: String,
+}
+
+/// A button click or select-menu choice, parsed from an interaction payload
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ComponentInteraction {
+    pub id: String,
+    pub application_id: String,
+    /// Token for follow-up webhooks (valid for 15 minutes)
+    pub token: String,
+    pub custom_id: String,
+    /// `2` for buttons, `3` for string selects
+    pub component_type: u8,
+    /// Selected option values (empty for buttons)
+    pub values: Vec<String>,
+    pub guild_id: Option<String>,
+    pub channel_id: Option<String>,
+    /// Clicking user (from `member.user` in guilds, `user` in DMs)
+    pub user_id: Option<String>,
+    /// Message the component is attached to
+    pub message_id: Option<String>,
+    /// Where the component was clicked (absent on older payloads)
+    pub context: Option<InteractionContext>,
+    /// Installations that authorized this interaction
+    pub integration_types: Vec<IntegrationType>,
+}
+
+impl ComponentInteraction {
+    /// Parse a raw interaction body
+    ///
+    /// Returns [`DiscordError::InvalidComponent`] for other interaction types
+    /// (pings, slash commands), so callers can fall through to other handlers.
+    pub fn parse(body: &str) -> Result<Self, DiscordError> {
+        let raw: RawInteraction = serde_json::from_str(body)?;
+
+        if raw.kind != INTERACTION_MESSAGE_COMPONENT {
+            return Err(DiscordError::InvalidComponent(format!(
+                "interaction type {} is not a message component",
+                raw.kind
+            )));
+        }
+        let data = raw.data.ok_or_else(|| {
+            DiscordError::InvalidComponent("component interaction without data".to_string())
+        })?;
+
+        let mut integration_types: Vec<IntegrationType> = raw
+            .authorizing_integration_owners
+            .keys()
+            .filter_map(|key| key.parse::<u8>().ok())
+            .filter_map(|key| IntegrationType::try_from(key).ok())
+            .collect();
+        integration_types.sort_by_key(|kind| u8::from(*kind));
+
+        Ok(Self {
+            id: raw.id,
+            application_id: raw.application_id,
+            token: raw.token,
+            custom_id: data.custom_id,
+            component_type: data.component_type,
+            values: data.values,
+            guild_id: raw.guild_id,
+            channel_id: raw.channel_id,
+            user_id: raw.member.map(|m| m.user).or(raw.user).map(|u| u.id),
+            message_id: raw.message.map(|m| m.id),
+            context: raw.context,
+            integration_types,
+        })
+    }
+
+    /// Triggered through a user install only, so the bot may not be in the
+    /// guild or channel and can't post there outside the interaction response
+    pub fn is_user_install(&self) -> bool {
+        self.integration_types
+            .contains(&IntegrationType::UserInstall)
+            && !self
+                .integration_types
+                .contains(&IntegrationType::GuildInstall)
+    }
+
+    /// The custom id split into action and arguments
+    pub fn custom_id_parts(&self) -> CustomId {
+        CustomId::parse(&self.custom_id)
+    }
+}
+
+type Handler<'a, R> = Box<dyn Fn(&ComponentInteraction, &[String]) -> R + 'a>;
+
+/// Dispatches component interactions to handlers by custom id action
+///
+/// `R` is whatever the handlers produce — typically a [`ComponentResponse`],
+/// or a boxed future of one for async handlers.
+pub struct ComponentRouter<'a, R> {
+    routes: Vec<(String, Handler<'a, R>)>,
+}
+
+impl<R> Default for ComponentRouter<'_, R> {
+    fn default() -> Self {
+        Self { routes: Vec::new() }
+    }
+}
+
+impl<'a, R> ComponentRouter<'a, R> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register a handler for custom ids whose action is `action`
+    ///
+    /// The handler receives the interaction and the custom id's arguments.
+    pub fn on(
+        mut self,
+        action: &str,
+        handler: impl Fn(&ComponentInteraction, &[String]) -> R + 'a,
+    ) -> Self {
+        self.routes.push((action.to_string(), Box::new(handler)));
+        self
+    }
+
+    /// Run the handler for this interaction's action, if one is registered
+    pub fn route(&self, interaction: &ComponentInteraction) -> Option<R> {
+        let id = interaction.custom_id_parts();
+        self.routes
+            .iter()
+            .find(|(action, _)| *action == id.action)
+            .map(|(_, handler)| handler(interaction, &id.args))
+    }
+}
+
+/// Flag for replies only the clicking user can see
+const FLAG_EPHEMERAL: u64 = 1 << 6;
+
+/// Reply to a component interaction
+#[derive(Debug, Clone, PartialEq)]
+pub enum ComponentResponse {
+    /// Post a new message in the channel
+    Message {
+        content: String,
+        ephemeral: bool,
+        components: Vec<ActionRow>,
+    },
+    /// Edit the message the component is attached to
+    UpdateMessage {
+        content: Option<String>,
+        components: Vec<ActionRow>,
+    },
+    /// Acknowledge now and edit the message later via the interaction token
+    DeferredUpdate,
+}
+
+impl ComponentResponse {
+    /// A reply only the clicking user sees (e.g. "Muted Pirates")
+    pub fn ephemeral(content: &str) -> Self {
+        ComponentResponse::Message {
+            content: content.to_string(),
+            ephemeral: true,
+            components: Vec::new(),
+        }
+    }
+
+    /// The interaction response body to return from the endpoint
+    pub fn to_json(&self) -> serde_json::Value {
+        match self {
+            ComponentResponse::Message {
+                content,
+                ephemeral,
+                components,
+            } => json!({
+                "type": 4,
+                "data": {
+                    "content": content,
+                    "flags": if *ephemeral { FLAG_EPHEMERAL } else { 0 },
+                    "components": components,
+                }
+            }),
+            ComponentResponse::UpdateMessage {
+                content,
+                components,
+            } => {
+                let mut data = json!({ "components": components });
+                if let Some(content) = content {
+                    data["content"] = json!(content);
+                }
+                json!({ "type": 7, "data": data })
+            }
+            ComponentResponse::DeferredUpdate => json!({ "type": 6 }),
+        }
+    }
+}
@@ -0,0 +1,202 @@
+//! Object-safe client interface for code shared between native and wasm bots.
+//!
+//! [`DiscordClient`] names its futures with associated types, so it can't be
+//! used as `dyn DiscordClient`. [`DynDiscordClient`] has the same operations
+//! returning boxed futures and is implemented for every `DiscordClient`, so
+//! shared notify logic can take `&dyn DynDiscordClient` (or hold a
+//! `Box<dyn DynDiscordClient>`) and be handed a `NativeDiscordClient` or a
+//! `WasmDiscordClient` depending on the target.
+//!
+//! The methods share their names with [`DiscordClient`]; import only one of
+//! the two traits in a module that calls them on a concrete client.
+//!
+//! ```ignore
+//! use discord_client::{DiscordError, DiscordMessage, DynDiscordClient};
+//!
+//! async fn alert_holders(
+//!     client: &dyn DynDiscordClient,
+//!     user_ids: &[String],
+//!     message: &DiscordMessage,
+//! ) -> Result<(), DiscordError> {
+//!     for user_id in user_ids {
+//!         match client.send_dm(user_id, message).await {
+//!             Ok(_) | Err(DiscordError::CannotDm(_)) => {}
+//!             Err(e) => return Err(e),
+//!         }
+//!     }
+//!     Ok(())
+//! }
+//! ```
+
+use core::future::Future;
+use core::pin::Pin;
+use twilight_model::channel::{Channel, Message};
+use twilight_model::guild::Emoji as GuildEmoji;
+
+use crate::audit_log::{AuditLogEntry, AuditLogQuery};
+use crate::permissions::ChannelPermissions;
+use crate::{
+    AttachmentInput, DiscordClient, DiscordError, DiscordMessage, DiscordMessageEdit, Emoji,
+};
+
+/// Boxed future returned by [`DynDiscordClient`] methods
+///
+/// Not `Send`, as wasm clients hold JS values across awaits.
+pub type DiscordFuture<'a, T> = Pin<Box<dyn Future<Output = Result<T, DiscordError>> + 'a>>;
+
+/// Object-safe counterpart of [`DiscordClient`]
+pub trait DynDiscordClient {
+    /// Send a message to a Discord channel with optional attachments
+    fn send_message<'a>(
+        &'a self,
+        channel_id: &'a str,
+        message: &'a DiscordMessage,
+    ) -> DiscordFuture<'a, Message>;
+
+    /// Edit an existing message (content/embeds). Omit fields to leave unchanged.
+    fn edit_message<'a>(
+        &'a self,
+        channel_id: &'a str,
+        message_id: &'a str,
+        edit: &'a DiscordMessageEdit,
+    ) -> DiscordFuture<'a, Message>;
+
+    /// Edit a message and add new file attachments
+    fn edit_message_with_attachments<'a>(
+        &'a self,
+        channel_id: &'a str,
+        message_id: &'a str,
+        edit: &'a DiscordMessageEdit,
+        attachments: &'a [AttachmentInput],
+    ) -> DiscordFuture<'a, Message>;
+
+    /// Open (or fetch the existing) DM channel with a user
+    fn create_dm_channel<'a>(&'a self, user_id: &'a str) -> DiscordFuture<'a, Channel>;
+
+    /// Send a direct message to a user
+    fn send_dm<'a>(
+        &'a self,
+        user_id: &'a str,
+        message: &'a DiscordMessage,
+    ) -> DiscordFuture<'a, Message>;
+
+    /// React to a message as the bot
+    fn create_reaction<'a>(
+        &'a self,
+        channel_id: &'a str,
+        message_id: &'a str,
+        emoji: &'a Emoji,
+    ) -> DiscordFuture<'a, ()>;
+
+    /// Remove the bot's own reaction from a message
+    fn delete_own_reaction<'a>(
+        &'a self,
+        channel_id: &'a str,
+        message_id: &'a str,
+        emoji: &'a Emoji,
+    ) -> DiscordFuture<'a, ()>;
+
+    /// List a guild's custom emoji
+    fn list_guild_emojis<'a>(&'a self, guild_id: &'a str) -> DiscordFuture<'a, Vec<GuildEmoji>>;
+
+    /// The bot's effective permissions in a channel
+    fn get_channel_permissions<'a>(
+        &'a self,
+        channel_id: &'a str,
+    ) -> DiscordFuture<'a, ChannelPermissions>;
+
+    /// Recent audit log entries for a guild, newest first
+    fn get_guild_audit_log<'a>(
+        &'a self,
+        guild_id: &'a str,
+        query: &'a AuditLogQuery,
+    ) -> DiscordFuture<'a, Vec<AuditLogEntry>>;
+}
+
+impl<C: DiscordClient> DynDiscordClient for C {
+    fn send_message<'a>(
+        &'a self,
+        channel_id: &'a str,
+        message: &'a DiscordMessage,
+    ) -> DiscordFuture<'a, Message> {
+        Box::pin(DiscordClient::send_message(self, channel_id, message))
+    }
+
+    fn edit_message<'a>(
+        &'a self,
+        channel_id: &'a str,
+        message_id: &'a str,
+        edit: &'a DiscordMessageEdit,
+    ) -> DiscordFuture<'a, Message> {
+        Box::pin(DiscordClient::edit_message(
+            self, channel_id, message_id, edit,
+        ))
+    }
+
+    fn edit_message_with_attachments<'a>(
+        &'a self,
+        channel_id: &'a str,
+        message_id: &'a str,
+        edit: &'a DiscordMessageEdit,
+        attachments: &'a [AttachmentInput],
+    ) -> DiscordFuture<'a, Message> {
+        Box::pin(DiscordClient::edit_message_with_attachments(
+            self,
+            channel_id,
+            message_id,
+            edit,
+            attachments,
+        ))
+    }
+
+    // Optional operations already return a `DiscordFuture`
+
+    fn create_dm_channel<'a>(&'a self, user_id: &'a str) -> DiscordFuture<'a, Channel> {
+        DiscordClient::create_dm_channel(self, user_id)
+    }
+
+    fn send_dm<'a>(
+        &'a self,
+        user_id: &'a str,
+        message: &'a DiscordMessage,
+    ) -> DiscordFuture<'a, Message> {
+        DiscordClient::send_dm(self, user_id, message)
+    }
+
+    fn create_reaction<'a>(
+        &'a self,
+        channel_id: &'a str,
+        message_id: &'a str,
+        emoji: &'a Emoji,
+    ) -> DiscordFuture<'a, ()> {
+        DiscordClient::create_reaction(self, channel_id, message_id, emoji)
+    }
+
+    fn delete_own_reaction<'a>(
+        &'a self,
+        channel_id: &'a str,
+        message_id: &'a str,
+        emoji: &'a Emoji,
+    ) -> DiscordFuture<'a, ()> {
+        DiscordClient::delete_own_reaction(self, channel_id, message_id, emoji)
+    }
+
+    fn list_guild_emojis<'a>(&'a self, guild_id: &'a str) -> DiscordFuture<'a, Vec<GuildEmoji>> {
+        DiscordClient::list_guild_emojis(self, guild_id)
+    }
+
+    fn get_channel_permissions<'a>(
+        &'a self,
+        channel_id: &'a str,
+    ) -> DiscordFuture<'a, ChannelPermissions> {
+        DiscordClient::get_channel_permissions(self, channel_id)
+    }
+
+    fn get_guild_audit_log<'a>(
+        &'a self,
+        guild_id: &'a str,
+        query: &'a AuditLogQuery,
+    ) -> DiscordFuture<'a, Vec<AuditLogEntry>> {
+        DiscordClient::get_guild_audit_log(self, guild_id, query)
+    }
+}
@@ -0,0 +1,152 @@
+//! Emoji for reactions and message text
+//!
+//! Discord refers to emoji differently depending on where they're used:
+//! message content and embeds take the `<:name:id>` markdown form, while the
+//! reaction endpoints take a URL path segment (`name:id` for custom emoji,
+//! the percent-encoded character for unicode). [`Emoji`] converts between
+//! them, including from guild emoji returned by
+//! [`DiscordClient::list_guild_emojis`](crate::DiscordClient::list_guild_emojis).
+//!
+//! ```
+//! use discord_client::emoji::Emoji;
+//!
+//! let fire = Emoji::unicode("🔥");
+//! assert_eq!(fire.to_string(), "🔥");
+//! assert_eq!(fire.reaction_path(), "%F0%9F%94%A5");
+//!
+//! let pepe: Emoji = "<a:pepe_dance:1234567890>".parse().unwrap();
+//! assert_eq!(pepe.to_string(), "<a:pepe_dance:1234567890>");
+//! assert_eq!(pepe.reaction_path(), "pepe_dance:1234567890");
+//! ```
+
+use std::fmt;
+use std::str::FromStr;
+
+use twilight_model::guild::Emoji as GuildEmoji;
+
+use crate::DiscordError;
+
+/// A unicode or custom (guild) emoji
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Emoji {
+    Unicode(String),
+    Custom {
+        name: String,
+        id: u64,
+        animated: bool,
+    },
+}
+
+impl Emoji {
+    pub fn unicode(emoji: &str) -> Self {
+        Emoji::Unicode(emoji.to_string())
+    }
+
+    pub fn custom(name: &str, id: u64) -> Self {
+        Emoji::Custom {
+            name: name.to_string(),
+            id,
+            animated: false,
+        }
+    }
+
+    pub fn animated(name: &str, id: u64) -> Self {
+        Emoji::Custom {
+            name: name.to_string(),
+            id,
+            animated: true,
+        }
+    }
+
+    /// Path segment for the reaction endpoints
+    pub fn reaction_path(&self) -> String {
+        match self {
+            Emoji::Unicode(emoji) => percent_encode(emoji),
+            Emoji::Custom { name, id, .. } => format!("{}:{id}", percent_encode(name)),
+        }
+    }
+}
+
+/// Message markdown: the character itself, or `<:name:id>` / `<a:name:id>`
+impl fmt::Display for Emoji {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Emoji::Unicode(emoji) => f.write_str(emoji),
+            Emoji::Custom { name, id, animated } => {
+                let prefix = if *animated { "a" } else { "" };
+                write!(f, "<{prefix}:{name}:{id}>")
+            }
+        }
+    }
+}
+
+/// Parses message markdown (`<:name:id>`, `<a:name:id>`), the reaction form
+/// (`name:id`), or anything else as a unicode emoji
+impl FromStr for Emoji {
+    type Err = DiscordError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        if s.is_empty() {
+            return Err(DiscordError::InvalidEmoji("empty emoji".to_string()));
+        }
+
+        let (animated, body) = match s.strip_prefix('<').and_then(|s| s.strip_suffix('>')) {
+            Some(inner) => match inner.strip_prefix("a:") {
+                Some(body) => (true, body),
+                None => (false, inner.strip_prefix(':').unwrap_or(inner)),
+            },
+            None if s.contains(':') => (false, s),
+            None => return Ok(Emoji::unicode(s)),
+        };
+
+        let (name, id) = body
+            .rsplit_once(':')
+            .ok_or_else(|| DiscordError::InvalidEmoji(s.to_string()))?;
+        let id = id
+            .parse()
+            .map_err(|_| DiscordError::InvalidEmoji(s.to_string()))?;
+        if name.is_empty() {
+            return Err(DiscordError::InvalidEmoji(s.to_string()));
+        }
+
+        Ok(Emoji::Custom {
+            name: name.to_string(),
+            id,
+            animated,
+        })
+    }
+}
+
+impl From<&GuildEmoji> for Emoji {
+    fn from(emoji: &GuildEmoji) -> Self {
+        Emoji::Custom {
+            name: emoji.name.clone(),
+            id: emoji.id.get(),
+            animated: emoji.animated,
+        }
+    }
+}
+
+/// Markdown for a guild emoji, for use in message content and embeds
+pub fn format_emoji(emoji: &GuildEmoji) -> String {
+    Emoji::from(emoji).to_string()
+}
+
+/// Find a guild emoji by name (case-sensitive, as Discord matches them)
+pub fn find_emoji<'a>(emojis: &'a [GuildEmoji], name: &str) -> Option<&'a GuildEmoji> {
+    emojis.iter().find(|emoji| emoji.name == name)
+}
+
+/// Percent-encode everything outside RFC 3986 unreserved characters
+fn percent_encode(value: &str) -> String {
+    let mut encoded = String::with_capacity(value.len() * 3);
+    for byte in value.bytes() {
+        if byte.is_ascii_alphanumeric() || matches!(byte, b'-' | b'.' | b'_' | b'~') {
+            encoded.push(byte as char);
+        } else {
+            encoded.push_str(&format!("%{byte:02X}"));
+        }
+    }
+    encoded
+}
@@ -8,6 +8,15 @@ mod wasm;
 #[cfg(feature = "wasm")]
 use worker_stack::worker;
 
+pub mod attachment;
+pub mod audit_log;
+pub mod backoff;
+pub mod components;
+pub mod dynamic;
+pub mod emoji;
+pub mod locale;
+pub mod permissions;
+pub mod scheduler;
 pub mod types;
 
 #[cfg(feature = "native")]
@@ -15,6 +24,25 @@ pub use native::*;
 #[cfg(feature = "wasm")]
 pub use wasm::*;
 
+pub use attachment::BoostTier;
+#[cfg(feature = "wasm")]
+pub use attachment::R2Attachment;
+pub use audit_log::{
+    AuditAction, AuditLogActionType, AuditLogChange, AuditLogEntry, AuditLogOptions, AuditLogQuery,
+    AuditRole,
+};
+#[cfg(any(feature = "native", all(feature = "wasm", target_arch = "wasm32")))]
+pub use backoff::send_with_backoff;
+pub use backoff::{global_rate_limit_until, note_global_rate_limit, BackoffPolicy};
+pub use components::{
+    validate_components, ActionRow, Button, ButtonStyle, ComponentInteraction, ComponentResponse,
+    ComponentRouter, CustomId, SelectMenu, SelectOption,
+};
+pub use dynamic::{DiscordFuture, DynDiscordClient};
+pub use emoji::Emoji;
+pub use locale::{Locale, LocaleConfig, Template, TemplateArgs, Templates};
+pub use permissions::{preflight_check, ChannelPermissions};
+pub use scheduler::{DrainReport, ScheduledMessage, ScheduledSender, SchedulerConfig};
 pub use types::*;
 
 pub mod compat;
@@ -35,9 +63,38 @@ pub enum DiscordError {
     #[error("Invalid attachment: {0}")]
     InvalidAttachment(String),
 
+    #[error("Attachment too large: {size} bytes exceeds {limit} byte limit")]
+    AttachmentTooLarge { size: usize, limit: usize },
+
+    #[error("Attachment download failed: {0}")]
+    AttachmentDownload(String),
+
+    #[error("Invalid component: {0}")]
+    InvalidComponent(String),
+
     #[error("Configuration error: {0}")]
     Config(String),
 
+    #[error("Cannot DM user: {0}")]
+    CannotDm(String),
+
+    #[error("Invalid emoji: {0}")]
+    InvalidEmoji(String),
+
+    #[error("Not supported by this client: {0}")]
+    Unsupported(String),
+
+    #[error("Invalid template: {0}")]
+    InvalidTemplate(String),
+
+    #[error("Gave up after {attempts} attempts and {waited_ms}ms waiting: {last}")]
+    RetriesExhausted {
+        attempts: u32,
+        waited_ms: u64,
+        #[source]
+        last: Box<DiscordError>,
+    },
+
     #[cfg(feature = "native")]
     #[error("Reqwest error: {0}")]
     Reqwest(#[from] reqwest::Error),
@@ -50,3 +107,39 @@ pub enum DiscordError {
     #[error("Worker error: {0}")]
     Worker(#[from] worker::Error),
 }
+
+impl DiscordError {
+    /// Classify a non-2xx, non-429 response
+    pub(crate) fn from_api_response(status: u16, body: &str) -> Self {
+        match serde_json::from_str::<DiscordApiError>(body) {
+            Ok(api_error) if api_error.code == ERROR_CANNOT_SEND_TO_USER => {
+                DiscordError::CannotDm(api_error.message)
+            }
+            _ => DiscordError::Request(format!("Discord API error {status}: {body}")),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_api_response_maps_cannot_dm() {
+        let body = r#"{"message": "Cannot send messages to this user", "code": 50007}"#;
+        assert!(matches!(
+            DiscordError::from_api_response(403, body),
+            DiscordError::CannotDm(ref message) if message == "Cannot send messages to this user"
+        ));
+
+        // Any other code, or a body that isn't a Discord error, stays a
+        // plain request failure
+        let missing_access = r#"{"message": "Missing Access", "code": 50001}"#;
+        for body in [missing_access, "<html>bad gateway</html>"] {
+            assert!(matches!(
+                DiscordError::from_api_response(403, body),
+                DiscordError::Request(ref message) if message.contains(body)
+            ));
+        }
+    }
+}
@@ -0,0 +1,462 @@
+//! Localized notification text
+//!
+//! Communities are configured with a [`Locale`] per guild or channel
+//! ([`LocaleConfig`]), and message text is rendered from per-locale string
+//! tables ([`Templates`]) rather than hard-coded English. Templates use a
+//! small subset of ICU MessageFormat: `{name}` placeholders and
+//! `{name, plural, ...}` selection, where `#` stands for the count.
+//!
+//! ```
+//! use discord_client::locale::{Locale, TemplateArgs, Templates};
+//!
+//! let templates = Templates::default();
+//! let args = TemplateArgs::new().count("count", 1);
+//! assert_eq!(
+//!     templates.render(Locale::En, "scheduler.summary.sales", &args).unwrap(),
+//!     "**1 new sale**"
+//! );
+//!
+//! let args = TemplateArgs::new().count("count", 3);
+//! assert_eq!(
+//!     templates.render(Locale::Es, "scheduler.summary.sales", &args).unwrap(),
+//!     "**3 nuevas ventas**"
+//! );
+//! ```
+
+use std::collections::HashMap;
+use std::fmt;
+use std::iter::Peekable;
+use std::str::{Chars, FromStr};
+
+use serde::{Deserialize, Serialize};
+
+use crate::DiscordError;
+
+/// Languages notification text is available in
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Locale {
+    #[default]
+    En,
+    Ja,
+    Es,
+}
+
+impl Locale {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Locale::En => "en",
+            Locale::Ja => "ja",
+            Locale::Es => "es",
+        }
+    }
+
+    /// CLDR plural category of `n` (integers only)
+    pub fn plural_category(&self, n: u64) -> PluralCategory {
+        match self {
+            Locale::En | Locale::Es if n == 1 => PluralCategory::One,
+            Locale::En | Locale::Es | Locale::Ja => PluralCategory::Other,
+        }
+    }
+}
+
+impl fmt::Display for Locale {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Parses a language tag by its primary subtag, so Discord's `en-US`,
+/// `es-419` and `ja` all resolve
+impl FromStr for Locale {
+    type Err = DiscordError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let language = s.split(['-', '_']).next().unwrap_or_default();
+        match language.to_ascii_lowercase().as_str() {
+            "en" => Ok(Locale::En),
+            "ja" => Ok(Locale::Ja),
+            "es" => Ok(Locale::Es),
+            _ => Err(DiscordError::Config(format!("Unsupported locale: {s}"))),
+        }
+    }
+}
+
+/// CLDR plural categories used by the supported locales
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PluralCategory {
+    One,
+    Other,
+}
+
+/// Which locale each guild and channel gets; a channel setting wins over
+/// its guild's
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LocaleConfig {
+    #[serde(default)]
+    pub default: Locale,
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub guilds: HashMap<String, Locale>,
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub channels: HashMap<String, Locale>,
+}
+
+impl LocaleConfig {
+    pub fn new(default: Locale) -> Self {
+        Self {
+            default,
+            ..Self::default()
+        }
+    }
+
+    pub fn with_guild(mut self, guild_id: impl Into<String>, locale: Locale) -> Self {
+        self.guilds.insert(guild_id.into(), locale);
+        self
+    }
+
+    pub fn with_channel(mut self, channel_id: impl Into<String>, locale: Locale) -> Self {
+        self.channels.insert(channel_id.into(), locale);
+        self
+    }
+
+    pub fn locale_for(&self, channel_id: &str, guild_id: Option<&str>) -> Locale {
+        self.channels
+            .get(channel_id)
+            .or_else(|| guild_id.and_then(|id| self.guilds.get(id)))
+            .copied()
+            .unwrap_or(self.default)
+    }
+}
+
+/// A parsed message template
+#[derive(Debug, Clone, PartialEq)]
+pub struct Template(Vec<Part>);
+
+#[derive(Debug, Clone, PartialEq)]
+enum Part {
+    Text(String),
+    Arg(String),
+    /// `#` inside a plural arm
+    Count(String),
+    Plural {
+        arg: String,
+        arms: Vec<(Selector, Vec<Part>)>,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Selector {
+    Exact(u64),
+    Category(PluralCategory),
+    Other,
+}
+
+impl Template {
+    /// Parse `{name}` placeholders and `{name, plural, =0 {..} one {..} other {..}}`
+    /// selections. Every plural needs an `other` arm.
+    pub fn parse(source: &str) -> Result<Self, DiscordError> {
+        let mut chars = source.chars().peekable();
+        let parts = parse_parts(&mut chars, None).and_then(|parts| match chars.next() {
+            None => Ok(parts),
+            Some(c) => Err(format!("unexpected '{c}'")),
+        });
+        parts
+            .map(Template)
+            .map_err(|reason| DiscordError::InvalidTemplate(format!("{source}: {reason}")))
+    }
+
+    /// Render for `locale`; placeholders without an argument are left as-is
+    pub fn render(&self, locale: Locale, args: &TemplateArgs) -> String {
+        let mut out = String::new();
+        render_parts(&self.0, locale, args, &mut out);
+        out
+    }
+}
+
+impl FromStr for Template {
+    type Err = DiscordError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Template::parse(s)
+    }
+}
+
+/// Parse until `}` (left unconsumed) or the end; `plural_arg` is the
+/// argument `#` refers to inside a plural arm
+fn parse_parts(chars: &mut Peekable<Chars>, plural_arg: Option<&str>) -> Result<Vec<Part>, String> {
+    let mut parts = Vec::new();
+    let mut text = String::new();
+
+    while let Some(&c) = chars.peek() {
+        match c {
+            '}' => break,
+            '{' => {
+                chars.next();
+                if !text.is_empty() {
+                    parts.push(Part::Text(std::mem::take(&mut text)));
+                }
+                parts.push(parse_argument(chars)?);
+            }
+            '#' if plural_arg.is_some() => {
+                chars.next();
+                if !text.is_empty() {
+                    parts.push(Part::Text(std::mem::take(&mut text)));
+                }
+                parts.push(Part::Count(plural_arg.unwrap_or_default().to_string()));
+            }
+            c => {
+                chars.next();
+                text.push(c);
+            }
+        }
+    }
+
+    if !text.is_empty() {
+        parts.push(Part::Text(text));
+    }
+    Ok(parts)
+}
+
+/// Parse the rest of an argument after its opening `{`
+fn parse_argument(chars: &mut Peekable<Chars>) -> Result<Part, String> {
+    let name = take_until(chars, &[',', '}']);
+    let name = name.trim().to_string();
+    if name.is_empty() {
+        return Err("empty argument name".to_string());
+    }
+
+    match chars.next() {
+        Some('}') => return Ok(Part::Arg(name)),
+        Some(',') => {}
+        _ => return Err(format!("unclosed {{{name}")),
+    }
+
+    let kind = take_until(chars, &[',', '}']);
+    if kind.trim() != "plural" || chars.next() != Some(',') {
+        return Err(format!("unsupported format '{}' for {name}", kind.trim()));
+    }
+
+    let mut arms = Vec::new();
+    loop {
+        skip_whitespace(chars);
+        match chars.next() {
+            Some('}') => break,
+            Some(c) => {
+                let selector = format!("{c}{}", take_until(chars, &['{', '}']));
+                let selector = match selector.trim() {
+                    "other" => Selector::Other,
+                    "one" => Selector::Category(PluralCategory::One),
+                    exact if exact.starts_with('=') => exact[1..]
+                        .parse()
+                        .map(Selector::Exact)
+                        .map_err(|_| format!("bad selector '{exact}'"))?,
+                    other => return Err(format!("unknown plural selector '{other}'")),
+                };
+                if chars.next() != Some('{') {
+                    return Err(format!("missing message for a {name} selector"));
+                }
+                let body = parse_parts(chars, Some(&name))?;
+                if chars.next() != Some('}') {
+                    return Err(format!("unclosed plural arm in {name}"));
+                }
+                arms.push((selector, body));
+            }
+            None => return Err(format!("unclosed {{{name}")),
+        }
+    }
+
+    if !arms
+        .iter()
+        .any(|(selector, _)| *selector == Selector::Other)
+    {
+        return Err(format!("plural {name} has no 'other' arm"));
+    }
+    Ok(Part::Plural { arg: name, arms })
+}
+
+fn take_until(chars: &mut Peekable<Chars>, stops: &[char]) -> String {
+    let mut out = String::new();
+    while let Some(&c) = chars.peek() {
+        if stops.contains(&c) {
+            break;
+        }
+        out.push(c);
+        chars.next();
+    }
+    out
+}
+
+fn skip_whitespace(chars: &mut Peekable<Chars>) {
+    while chars.peek().is_some_and(|c| c.is_whitespace()) {
+        chars.next();
+    }
+}
+
+fn render_parts(parts: &[Part], locale: Locale, args: &TemplateArgs, out: &mut String) {
+    for part in parts {
+        match part {
+            Part::Text(text) => out.push_str(text),
+            Part::Arg(name) => match args.get(name) {
+                Some(value) => out.push_str(&value.to_string()),
+                None => {
+                    out.push('{');
+                    out.push_str(name);
+                    out.push('}');
+                }
+            },
+            Part::Count(name) => match args.get(name) {
+                Some(value) => out.push_str(&value.to_string()),
+                None => out.push('#'),
+            },
+            Part::Plural { arg, arms } => {
+                let n = match args.get(arg) {
+                    Some(TemplateArg::Count(n)) => Some(*n),
+                    _ => None,
+                };
+                let arm = n
+                    .and_then(|n| {
+                        let category = locale.plural_category(n);
+                        arms.iter()
+                            .find(|(selector, _)| *selector == Selector::Exact(n))
+                            .or_else(|| {
+                                arms.iter()
+                                    .find(|(selector, _)| *selector == Selector::Category(category))
+                            })
+                    })
+                    .or_else(|| {
+                        arms.iter()
+                            .find(|(selector, _)| *selector == Selector::Other)
+                    });
+                if let Some((_, body)) = arm {
+                    render_parts(body, locale, args, out);
+                }
+            }
+        }
+    }
+}
+
+/// A value substituted into a [`Template`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TemplateArg {
+    Text(String),
+    Count(u64),
+}
+
+impl fmt::Display for TemplateArg {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TemplateArg::Text(text) => f.write_str(text),
+            TemplateArg::Count(n) => write!(f, "{n}"),
+        }
+    }
+}
+
+/// Named arguments for [`Template::render`]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TemplateArgs(HashMap<String, TemplateArg>);
+
+impl TemplateArgs {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn text(mut self, name: &str, value: impl Into<String>) -> Self {
+        self.0
+            .insert(name.to_string(), TemplateArg::Text(value.into()));
+        self
+    }
+
+    /// A number plural selections can branch on
+    pub fn count(mut self, name: &str, n: u64) -> Self {
+        self.0.insert(name.to_string(), TemplateArg::Count(n));
+        self
+    }
+
+    pub fn get(&self, name: &str) -> Option<&TemplateArg> {
+        self.0.get(name)
+    }
+}
+
+/// Built-in strings: `(key, en, ja, es)`
+const BUILTIN: &[(&str, &str, &str, &str)] = &[
+    (
+        "scheduler.summary",
+        "**{count} new {label}**",
+        "**{label}: 新着{count}件**",
+        "**{label}: {count} nuevos**",
+    ),
+    (
+        "scheduler.summary.sales",
+        "**{count, plural, one {# new sale} other {# new sales}}**",
+        "**新しい販売{count}件**",
+        "**{count, plural, one {# nueva venta} other {# nuevas ventas}}**",
+    ),
+    (
+        "scheduler.summary.listings",
+        "**{count, plural, one {# new listing} other {# new listings}}**",
+        "**新しい出品{count}件**",
+        "**{count, plural, one {# nuevo listado} other {# nuevos listados}}**",
+    ),
+    (
+        "scheduler.summary.mints",
+        "**{count, plural, one {# new mint} other {# new mints}}**",
+        "**新しいミント{count}件**",
+        "**{count, plural, one {# nuevo minteo} other {# nuevos minteos}}**",
+    ),
+];
+
+/// Per-locale string tables, keyed by message id
+///
+/// [`Default`] holds the built-in strings; start from [`Templates::empty`]
+/// to supply every string yourself.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Templates {
+    tables: HashMap<Locale, HashMap<String, Template>>,
+}
+
+impl Default for Templates {
+    fn default() -> Self {
+        let mut templates = Self::empty();
+        for (key, en, ja, es) in BUILTIN {
+            for (locale, source) in [(Locale::En, en), (Locale::Ja, ja), (Locale::Es, es)] {
+                let template = Template::parse(source).expect("built-in templates parse");
+                templates.insert(locale, key, template);
+            }
+        }
+        templates
+    }
+}
+
+impl Templates {
+    pub fn empty() -> Self {
+        Self {
+            tables: HashMap::new(),
+        }
+    }
+
+    pub fn insert(&mut self, locale: Locale, key: &str, template: Template) {
+        self.tables
+            .entry(locale)
+            .or_default()
+            .insert(key.to_string(), template);
+    }
+
+    /// Parse and add `source`, e.g. when loading a string table from config
+    pub fn with(mut self, locale: Locale, key: &str, source: &str) -> Result<Self, DiscordError> {
+        self.insert(locale, key, Template::parse(source)?);
+        Ok(self)
+    }
+
+    /// The template for `key`, falling back to English
+    pub fn get(&self, locale: Locale, key: &str) -> Option<&Template> {
+        self.tables
+            .get(&locale)
+            .and_then(|table| table.get(key))
+            .or_else(|| self.tables.get(&Locale::En)?.get(key))
+    }
+
+    pub fn render(&self, locale: Locale, key: &str, args: &TemplateArgs) -> Option<String> {
+        self.get(locale, key)
+            .map(|template| template.render(locale, args))
+    }
+}
@@ -1,12 +1,16 @@
+use crate::audit_log::{AuditLogEntry, AuditLogQuery, AuditLogResponse};
+use crate::permissions::{fetch_channel_permissions, ChannelPermissions};
 use crate::{
-    AttachmentInput, DiscordClient, DiscordError, DiscordMessage, DiscordRateLimitResponse,
-    BASE_URL,
+    AttachmentInput, DiscordClient, DiscordError, DiscordFuture, DiscordMessage,
+    DiscordRateLimitResponse, Emoji, BASE_URL,
 };
 use core::future::Future;
 use core::pin::Pin;
 use reqwest::multipart;
+use serde::de::DeserializeOwned;
 use tracing::{debug, error, info, warn};
-use twilight_model::channel::Message;
+use twilight_model::channel::{Channel, Message};
+use twilight_model::guild::Emoji as GuildEmoji;
 
 /// Native Discord bot client using reqwest (for augminted-bots)
 pub struct NativeDiscordClient {
@@ -43,6 +47,10 @@ impl DiscordClient for NativeDiscordClient {
         message: &'a DiscordMessage,
     ) -> Self::SendMessageFut<'a> {
         Box::pin(async move {
+            if let Some(rows) = &message.components {
+                crate::validate_components(rows)?;
+            }
+
             info!("🔗 Sending Discord message with native client");
 
             let url = format!("{BASE_URL}/channels/{}/messages", channel_id);
@@ -68,7 +76,7 @@ impl DiscordClient for NativeDiscordClient {
                 .send()
                 .await?;
 
-            self.handle_message_response(response).await
+            self.handle_response(response).await
         })
     }
 
@@ -79,6 +87,10 @@ impl DiscordClient for NativeDiscordClient {
         edit: &'a crate::DiscordMessageEdit,
     ) -> Self::EditMessageFut<'a> {
         Box::pin(async move {
+            if let Some(rows) = &edit.components {
+                crate::validate_components(rows)?;
+            }
+
             info!("✏️ Editing Discord message (native)");
             let url = format!(
                 "https://discord.com/api/v10/channels/{}/messages/{}",
@@ -94,7 +106,7 @@ impl DiscordClient for NativeDiscordClient {
                 .send()
                 .await?;
 
-            self.handle_message_response(response).await
+            self.handle_response(response).await
         })
     }
 
@@ -139,12 +151,129 @@ impl DiscordClient for NativeDiscordClient {
                 .send()
                 .await?;
 
-            self.handle_message_response(response).await
+            self.handle_response(response).await
+        })
+    }
+
+    fn create_dm_channel<'a>(&'a self, user_id: &'a str) -> DiscordFuture<'a, Channel> {
+        Box::pin(async move {
+            debug!("📨 Opening DM channel with user {user_id}");
+            let response = self
+                .client
+                .post(format!("{BASE_URL}/users/@me/channels"))
+                .header("Authorization", format!("Bot {}", self.bot_token))
+                .header("User-Agent", "defrag-discord-client/1.0")
+                .json(&serde_json::json!({ "recipient_id": user_id }))
+                .send()
+                .await?;
+
+            self.handle_response(response).await
+        })
+    }
+
+    fn create_reaction<'a>(
+        &'a self,
+        channel_id: &'a str,
+        message_id: &'a str,
+        emoji: &'a Emoji,
+    ) -> DiscordFuture<'a, ()> {
+        Box::pin(async move {
+            debug!("👍 Reacting with {emoji} to message {message_id}");
+            let response = self
+                .client
+                .put(reaction_url(channel_id, message_id, emoji))
+                .header("Authorization", format!("Bot {}", self.bot_token))
+                .header("User-Agent", "defrag-discord-client/1.0")
+                .header(reqwest::header::CONTENT_LENGTH, 0)
+                .send()
+                .await?;
+
+            self.check_response(response).await.map(|_| ())
         })
     }
+
+    fn delete_own_reaction<'a>(
+        &'a self,
+        channel_id: &'a str,
+        message_id: &'a str,
+        emoji: &'a Emoji,
+    ) -> DiscordFuture<'a, ()> {
+        Box::pin(async move {
+            debug!("🗑️ Removing {emoji} reaction from message {message_id}");
+            let response = self
+                .client
+                .delete(reaction_url(channel_id, message_id, emoji))
+                .header("Authorization", format!("Bot {}", self.bot_token))
+                .header("User-Agent", "defrag-discord-client/1.0")
+                .send()
+                .await?;
+
+            self.check_response(response).await.map(|_| ())
+        })
+    }
+
+    fn list_guild_emojis<'a>(&'a self, guild_id: &'a str) -> DiscordFuture<'a, Vec<GuildEmoji>> {
+        Box::pin(async move {
+            let response = self
+                .client
+                .get(format!("{BASE_URL}/guilds/{guild_id}/emojis"))
+                .header("Authorization", format!("Bot {}", self.bot_token))
+                .header("User-Agent", "defrag-discord-client/1.0")
+                .send()
+                .await?;
+
+            self.handle_response(response).await
+        })
+    }
+
+    fn get_channel_permissions<'a>(
+        &'a self,
+        channel_id: &'a str,
+    ) -> DiscordFuture<'a, ChannelPermissions> {
+        Box::pin(async move {
+            debug!("🔐 Resolving permissions in channel {channel_id}");
+            fetch_channel_permissions(channel_id, |url| self.get_text(url)).await
+        })
+    }
+
+    fn get_guild_audit_log<'a>(
+        &'a self,
+        guild_id: &'a str,
+        query: &'a AuditLogQuery,
+    ) -> DiscordFuture<'a, Vec<AuditLogEntry>> {
+        Box::pin(async move {
+            debug!("📜 Fetching audit log for guild {guild_id}");
+            let url = format!(
+                "{BASE_URL}/guilds/{guild_id}/audit-logs{}",
+                query.to_query_string()
+            );
+            let response: AuditLogResponse = serde_json::from_str(&self.get_text(url).await?)?;
+            Ok(response.audit_log_entries)
+        })
+    }
+}
+
+fn reaction_url(channel_id: &str, message_id: &str, emoji: &Emoji) -> String {
+    format!(
+        "{BASE_URL}/channels/{channel_id}/messages/{message_id}/reactions/{}/@me",
+        emoji.reaction_path()
+    )
 }
 
 impl NativeDiscordClient {
+    /// Authorized GET, returning the body of a successful response
+    async fn get_text(&self, url: String) -> Result<String, DiscordError> {
+        let response = self
+            .client
+            .get(url)
+            .header("Authorization", format!("Bot {}", self.bot_token))
+            .header("User-Agent", "defrag-discord-client/1.0")
+            .send()
+            .await?;
+
+        Ok(self.check_response(response).await?.text().await?)
+    }
+
     async fn send_multipart_message(
         &self,
         url: &str,
@@ -179,19 +308,28 @@ impl NativeDiscordClient {
             .send()
             .await?;
 
-        self.handle_message_response(response).await
+        self.handle_response(response).await
     }
 
-    async fn handle_message_response(
+    async fn handle_response<T: DeserializeOwned>(
         &self,
         response: reqwest::Response,
-    ) -> Result<Message, DiscordError> {
+    ) -> Result<T, DiscordError> {
+        let response = self.check_response(response).await?;
+        Ok(response.json().await?)
+    }
+
+    /// Turn rate limits and error statuses into errors, passing successful
+    /// responses through unread (some endpoints reply 204 with no body)
+    async fn check_response(
+        &self,
+        response: reqwest::Response,
+    ) -> Result<reqwest::Response, DiscordError> {
         let status = response.status();
 
         if response.status().is_success() {
-            info!("✅ Discord message sent successfully");
-            let message_response: Message = response.json().await?;
-            Ok(message_response)
+            info!("✅ Discord request succeeded");
+            Ok(response)
         } else if status == 429 {
             match response.json::<DiscordRateLimitResponse>().await {
                 Ok(rate_limit) => {
@@ -215,10 +353,10 @@ impl NativeDiscordClient {
         } else {
             let error_text = response.text().await.unwrap_or_default();
             error!("❌ Discord API error {}: {}", status, error_text);
-            Err(DiscordError::Request(format!(
-                "Discord API error {}: {}",
-                status, error_text
-            )))
+            Err(DiscordError::from_api_response(
+                status.as_u16(),
+                &error_text,
+            ))
         }
     }
 }
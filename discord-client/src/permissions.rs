@@ -0,0 +1,228 @@
+//! Channel permission checks before sending
+//!
+//! A bot without `SEND_MESSAGES` in a channel gets a 403 on every send, and
+//! one without `EMBED_LINKS` has its embeds silently dropped. Either is easy
+//! to miss in worker logs. [`preflight_check`] works out the bot's effective
+//! permissions in the channel and returns what the message needs but the bot
+//! lacks, so a worker can alert the server's admins instead.
+//!
+//! ```ignore
+//! use discord_client::permissions::{permission_names, preflight_check};
+//!
+//! let missing = preflight_check(client, &message, channel_id).await?;
+//! if !missing.is_empty() {
+//!     alert_admins(format!("Missing {}", permission_names(missing).join(", ")));
+//! }
+//! ```
+//!
+//! Permissions are resolved the way Discord documents it: `@everyone` and
+//! the bot's roles, then channel overwrites for `@everyone`, the roles and
+//! the bot itself. Threads use their parent channel's overwrites. Guild
+//! ownership isn't considered, as bots don't own the guilds they post in.
+
+use twilight_model::channel::permission_overwrite::{PermissionOverwrite, PermissionOverwriteType};
+use twilight_model::guild::Permissions;
+use twilight_model::id::marker::{GuildMarker, RoleMarker, UserMarker};
+use twilight_model::id::Id;
+
+use crate::{DiscordError, DiscordMessage, DynDiscordClient};
+
+/// The bot's effective permissions in one channel
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ChannelPermissions {
+    pub permissions: Permissions,
+    /// Thread channels need `SEND_MESSAGES_IN_THREADS` rather than
+    /// `SEND_MESSAGES`
+    pub thread: bool,
+}
+
+impl ChannelPermissions {
+    /// DM channels, which aren't permission-gated
+    pub fn direct() -> Self {
+        Self {
+            permissions: Permissions::all(),
+            thread: false,
+        }
+    }
+
+    /// Permissions `message` needs here that the bot doesn't have
+    pub fn missing_for(&self, message: &DiscordMessage) -> Permissions {
+        required_permissions(message, self.thread).difference(self.permissions)
+    }
+}
+
+/// Permissions needed to send `message`
+pub fn required_permissions(message: &DiscordMessage, thread: bool) -> Permissions {
+    let mut required = Permissions::VIEW_CHANNEL | send_permission(thread);
+    if message.embeds.as_ref().is_some_and(|e| !e.is_empty()) {
+        required |= Permissions::EMBED_LINKS;
+    }
+    if message.attachments.as_ref().is_some_and(|a| !a.is_empty()) {
+        required |= Permissions::ATTACH_FILES;
+    }
+    required
+}
+
+/// Flag names, e.g. `["SEND_MESSAGES", "EMBED_LINKS"]`, for alerts
+pub fn permission_names(permissions: Permissions) -> Vec<&'static str> {
+    permissions.iter_names().map(|(name, _)| name).collect()
+}
+
+fn send_permission(thread: bool) -> Permissions {
+    if thread {
+        Permissions::SEND_MESSAGES_IN_THREADS
+    } else {
+        Permissions::SEND_MESSAGES
+    }
+}
+
+/// Effective permissions of `user_id` in a guild channel.
+///
+/// `roles` are the guild's roles (including `@everyone`, whose id is the
+/// guild id) and `overwrites` the channel's, or the parent's for a thread.
+pub fn compute_permissions(
+    guild_id: Id<GuildMarker>,
+    user_id: Id<UserMarker>,
+    member_roles: &[Id<RoleMarker>],
+    roles: &[(Id<RoleMarker>, Permissions)],
+    overwrites: &[PermissionOverwrite],
+    thread: bool,
+) -> Permissions {
+    let everyone = guild_id.cast::<RoleMarker>();
+    let mut permissions = roles
+        .iter()
+        .filter(|(id, _)| *id == everyone || member_roles.contains(id))
+        .fold(Permissions::empty(), |acc, (_, role)| acc | *role);
+    if permissions.contains(Permissions::ADMINISTRATOR) {
+        return Permissions::all();
+    }
+
+    let role_overwrite = |o: &&PermissionOverwrite| o.kind == PermissionOverwriteType::Role;
+    if let Some(o) = overwrites
+        .iter()
+        .filter(role_overwrite)
+        .find(|o| o.id.cast() == everyone)
+    {
+        permissions = (permissions - o.deny) | o.allow;
+    }
+    let (allow, deny) = overwrites
+        .iter()
+        .filter(role_overwrite)
+        .filter(|o| member_roles.contains(&o.id.cast()))
+        .fold((Permissions::empty(), Permissions::empty()), |(a, d), o| {
+            (a | o.allow, d | o.deny)
+        });
+    permissions = (permissions - deny) | allow;
+    if let Some(o) = overwrites
+        .iter()
+        .find(|o| o.kind == PermissionOverwriteType::Member && o.id.cast() == user_id)
+    {
+        permissions = (permissions - o.deny) | o.allow;
+    }
+
+    // Implicit denials: nothing without VIEW_CHANNEL, and no message extras
+    // without the permission to send
+    if !permissions.contains(Permissions::VIEW_CHANNEL) {
+        return Permissions::empty();
+    }
+    if !permissions.contains(send_permission(thread)) {
+        permissions -= Permissions::SEND_TTS_MESSAGES
+            | Permissions::MENTION_EVERYONE
+            | Permissions::EMBED_LINKS
+            | Permissions::ATTACH_FILES;
+    }
+    permissions
+}
+
+/// Permissions `message` needs in `channel_id` that the bot doesn't have;
+/// empty when it can be sent as-is
+pub async fn preflight_check(
+    client: &dyn DynDiscordClient,
+    message: &DiscordMessage,
+    channel_id: &str,
+) -> Result<Permissions, DiscordError> {
+    Ok(client
+        .get_channel_permissions(channel_id)
+        .await?
+        .missing_for(message))
+}
+
+#[cfg(any(feature = "native", feature = "wasm"))]
+pub(crate) use fetch::fetch_channel_permissions;
+
+#[cfg(any(feature = "native", feature = "wasm"))]
+mod fetch {
+    use core::future::Future;
+    use serde::Deserialize;
+    use twilight_model::channel::Channel;
+    use twilight_model::guild::Permissions;
+    use twilight_model::id::marker::{RoleMarker, UserMarker};
+    use twilight_model::id::Id;
+
+    use super::{compute_permissions, ChannelPermissions};
+    use crate::{DiscordError, BASE_URL};
+
+    #[derive(Deserialize)]
+    struct CurrentUser {
+        id: Id<UserMarker>,
+    }
+
+    #[derive(Deserialize)]
+    struct GuildMember {
+        roles: Vec<Id<RoleMarker>>,
+    }
+
+    #[derive(Deserialize)]
+    struct GuildRole {
+        id: Id<RoleMarker>,
+        permissions: Permissions,
+    }
+
+    /// Fetch what [`compute_permissions`] needs with `get` (an authorized
+    /// GET returning the response body) and resolve the bot's permissions
+    pub(crate) async fn fetch_channel_permissions<F, Fut>(
+        channel_id: &str,
+        get: F,
+    ) -> Result<ChannelPermissions, DiscordError>
+    where
+        F: Fn(String) -> Fut,
+        Fut: Future<Output = Result<String, DiscordError>>,
+    {
+        let channel: Channel =
+            serde_json::from_str(&get(format!("{BASE_URL}/channels/{channel_id}")).await?)?;
+        let Some(guild_id) = channel.guild_id else {
+            return Ok(ChannelPermissions::direct());
+        };
+
+        let thread = channel.kind.is_thread();
+        let overwrites = match (thread, channel.parent_id) {
+            (true, Some(parent_id)) => {
+                let parent: Channel =
+                    serde_json::from_str(&get(format!("{BASE_URL}/channels/{parent_id}")).await?)?;
+                parent.permission_overwrites
+            }
+            _ => channel.permission_overwrites,
+        }
+        .unwrap_or_default();
+
+        let me: CurrentUser = serde_json::from_str(&get(format!("{BASE_URL}/users/@me")).await?)?;
+        let member: GuildMember = serde_json::from_str(
+            &get(format!("{BASE_URL}/guilds/{guild_id}/members/{}", me.id)).await?,
+        )?;
+        let roles: Vec<GuildRole> =
+            serde_json::from_str(&get(format!("{BASE_URL}/guilds/{guild_id}/roles")).await?)?;
+        let roles: Vec<_> = roles.into_iter().map(|r| (r.id, r.permissions)).collect();
+
+        Ok(ChannelPermissions {
+            permissions: compute_permissions(
+                guild_id,
+                me.id,
+                &member.roles,
+                &roles,
+                &overwrites,
+                thread,
+            ),
+            thread,
+        })
+    }
+}
@@ -0,0 +1,367 @@
+//! Throttled, coalescing message sending for announcement channels.
+//!
+//! During volume spikes a sales bot can produce dozens of announcements a
+//! minute for the same channel. [`ScheduledSender`] holds messages until
+//! they're due, folds messages sharing a coalesce key into one summary
+//! ("5 new sales"), and sends at most one message per channel per interval.
+//! Summary lines are rendered in each channel's [`Locale`] (see
+//! [`with_locales`](ScheduledSender::with_locales)). Messages with
+//! attachments are never folded. Messages that can't go out yet are handed
+//! back from [`drain`](ScheduledSender::drain), so a queue consumer can
+//! re-enqueue them with a delay instead of sleeping.
+//!
+//! ```ignore
+//! use discord_client::{DynDiscordClient, ScheduledMessage, ScheduledSender};
+//!
+//! let mut sender = ScheduledSender::default();
+//! for (channel_id, message) in sales {
+//!     sender.push(ScheduledMessage::new(channel_id, message).coalesce(policy_id, "sales"));
+//! }
+//! let report = sender.drain(client, now_ms).await;
+//! for deferred in report.deferred {
+//!     // re-enqueue with a delay of `not_before_ms - now_ms`
+//! }
+//! ```
+
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::locale::{Locale, LocaleConfig, TemplateArgs, Templates};
+use crate::{DiscordError, DiscordMessage, DynDiscordClient};
+
+/// Discord's limit on embeds per message
+const MAX_EMBEDS: usize = 10;
+
+/// Groups messages that may be folded into one summary
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Coalesce {
+    pub key: String,
+    /// Plural noun for the summary line, e.g. `sales`. Labels with a
+    /// `scheduler.summary.<label>` template are localized.
+    pub label: String,
+}
+
+/// A message waiting to be sent
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScheduledMessage {
+    pub channel_id: String,
+    /// Guild the channel belongs to, for per-guild [`LocaleConfig`]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub guild_id: Option<String>,
+    pub message: DiscordMessage,
+    /// Unix milliseconds before which the message must not be sent
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub not_before_ms: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub coalesce: Option<Coalesce>,
+}
+
+impl ScheduledMessage {
+    pub fn new(channel_id: impl Into<String>, message: DiscordMessage) -> Self {
+        Self {
+            channel_id: channel_id.into(),
+            guild_id: None,
+            message,
+            not_before_ms: None,
+            coalesce: None,
+        }
+    }
+
+    pub fn in_guild(mut self, guild_id: impl Into<String>) -> Self {
+        self.guild_id = Some(guild_id.into());
+        self
+    }
+
+    /// Hold the message until `not_before_ms`
+    pub fn not_before(mut self, not_before_ms: u64) -> Self {
+        self.not_before_ms = Some(not_before_ms);
+        self
+    }
+
+    /// Allow folding with other due messages for the same channel and key
+    pub fn coalesce(mut self, key: impl Into<String>, label: impl Into<String>) -> Self {
+        self.coalesce = Some(Coalesce {
+            key: key.into(),
+            label: label.into(),
+        });
+        self
+    }
+
+    fn is_due(&self, now_ms: u64) -> bool {
+        self.not_before_ms.is_none_or(|at| at <= now_ms)
+    }
+
+    /// Attachments can't be merged into a summary, so those messages go
+    /// out on their own
+    fn can_fold(&self) -> bool {
+        self.message
+            .attachments
+            .as_ref()
+            .is_none_or(|a| a.is_empty())
+    }
+}
+
+/// Throttling and coalescing limits for [`ScheduledSender`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SchedulerConfig {
+    /// Minimum gap between two sends to the same channel
+    pub channel_interval_ms: u64,
+    /// Due messages sharing a coalesce key are folded once there are at
+    /// least this many
+    pub coalesce_threshold: usize,
+    /// Cap on sends per [`drain`](ScheduledSender::drain) call
+    pub max_sends_per_drain: usize,
+}
+
+impl Default for SchedulerConfig {
+    fn default() -> Self {
+        Self {
+            channel_interval_ms: 2_000,
+            coalesce_threshold: 3,
+            max_sends_per_drain: 25,
+        }
+    }
+}
+
+/// Outcome of one [`drain`](ScheduledSender::drain)
+#[derive(Debug, Default)]
+pub struct DrainReport {
+    /// Messages delivered (a coalesced summary counts once)
+    pub sent: usize,
+    /// Original messages folded into summaries
+    pub coalesced: usize,
+    /// Messages not sent yet, with `not_before_ms` set to when they may go
+    pub deferred: Vec<ScheduledMessage>,
+    /// Channels whose sends failed with something other than a rate limit
+    pub failed: Vec<(String, DiscordError)>,
+}
+
+/// Queue of outbound messages drained under per-channel rate limits
+#[derive(Debug, Default)]
+pub struct ScheduledSender {
+    config: SchedulerConfig,
+    queue: Vec<ScheduledMessage>,
+    last_sent_ms: HashMap<String, u64>,
+    locales: LocaleConfig,
+    templates: Templates,
+}
+
+impl ScheduledSender {
+    pub fn new(config: SchedulerConfig) -> Self {
+        Self {
+            config,
+            queue: Vec::new(),
+            last_sent_ms: HashMap::new(),
+            locales: LocaleConfig::default(),
+            templates: Templates::default(),
+        }
+    }
+
+    /// Pick the summary language per guild/channel
+    pub fn with_locales(mut self, locales: LocaleConfig) -> Self {
+        self.locales = locales;
+        self
+    }
+
+    /// Replace the built-in summary strings
+    pub fn with_templates(mut self, templates: Templates) -> Self {
+        self.templates = templates;
+        self
+    }
+
+    pub fn push(&mut self, message: ScheduledMessage) {
+        self.queue.push(message);
+    }
+
+    pub fn extend(&mut self, messages: impl IntoIterator<Item = ScheduledMessage>) {
+        self.queue.extend(messages);
+    }
+
+    pub fn len(&self) -> usize {
+        self.queue.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.queue.is_empty()
+    }
+
+    /// Earliest time anything queued may be sent
+    pub fn next_due_ms(&self) -> Option<u64> {
+        self.queue
+            .iter()
+            .map(|m| {
+                let channel_ready = self
+                    .last_sent_ms
+                    .get(&m.channel_id)
+                    .map_or(0, |last| last + self.config.channel_interval_ms);
+                m.not_before_ms.unwrap_or(0).max(channel_ready)
+            })
+            .min()
+    }
+
+    /// Record a send made outside the scheduler, so the channel interval
+    /// still applies
+    pub fn mark_sent(&mut self, channel_id: impl Into<String>, at_ms: u64) {
+        self.last_sent_ms.insert(channel_id.into(), at_ms);
+    }
+
+    /// Remove the messages that may be sent at `now_ms`, one per channel,
+    /// coalescing where the threshold is met.
+    ///
+    /// Returns `(message, folded_count)`; `folded_count` is 1 for a message
+    /// sent as-is, which keeps its coalesce key. Summaries carry none.
+    pub fn take_due(&mut self, now_ms: u64) -> Vec<(ScheduledMessage, usize)> {
+        let mut due: Vec<(ScheduledMessage, usize)> = Vec::new();
+        let mut remaining = Vec::with_capacity(self.queue.len());
+        let mut queue = std::mem::take(&mut self.queue).into_iter();
+
+        while let Some(next) = queue.next() {
+            let channel_free = self
+                .last_sent_ms
+                .get(&next.channel_id)
+                .is_none_or(|last| now_ms >= last + self.config.channel_interval_ms);
+            let channel_taken = due.iter().any(|(m, _)| m.channel_id == next.channel_id);
+            if !next.is_due(now_ms)
+                || !channel_free
+                || channel_taken
+                || due.len() >= self.config.max_sends_per_drain
+            {
+                remaining.push(next);
+                continue;
+            }
+
+            let Some(coalesce) = next.coalesce.clone().filter(|_| next.can_fold()) else {
+                due.push((next, 1));
+                continue;
+            };
+
+            // Gather every due message for this channel and key
+            let (group, rest): (Vec<_>, Vec<_>) = queue.by_ref().partition(|m| {
+                m.channel_id == next.channel_id
+                    && m.coalesce.as_ref() == Some(&coalesce)
+                    && m.is_due(now_ms)
+                    && m.can_fold()
+            });
+            queue = rest.into_iter();
+
+            let channel_id = next.channel_id.clone();
+            let guild_id = next.guild_id.clone();
+            let mut group: Vec<ScheduledMessage> = std::iter::once(next).chain(group).collect();
+            if group.len() < self.config.coalesce_threshold {
+                // Below the threshold: send the first now, keep the rest queued
+                let first = group.remove(0);
+                remaining.extend(group);
+                due.push((first, 1));
+            } else {
+                let count = group.len();
+                let locale = self.locales.locale_for(&channel_id, guild_id.as_deref());
+                let content = self.summary_line(locale, &coalesce.label, count);
+                let message = summarize(content, group.into_iter().map(|m| m.message));
+                let summary = ScheduledMessage {
+                    guild_id,
+                    ..ScheduledMessage::new(channel_id, message)
+                };
+                due.push((summary, count));
+            }
+        }
+
+        self.queue = remaining;
+        due
+    }
+
+    fn summary_line(&self, locale: Locale, label: &str, count: usize) -> String {
+        let args = TemplateArgs::new()
+            .count("count", count as u64)
+            .text("label", label);
+        self.templates
+            .render(locale, &format!("scheduler.summary.{label}"), &args)
+            .or_else(|| self.templates.render(locale, "scheduler.summary", &args))
+            .unwrap_or_else(|| format!("**{count} new {label}**"))
+    }
+
+    /// Send everything due at `now_ms` and hand back the rest.
+    ///
+    /// A rate-limited message is deferred by its `retry_after`; a global rate
+    /// limit defers everything still due. Other errors are reported in
+    /// [`DrainReport::failed`] and the message is dropped.
+    pub async fn drain(&mut self, client: &dyn DynDiscordClient, now_ms: u64) -> DrainReport {
+        let mut report = DrainReport::default();
+        let mut global_retry_at: Option<u64> = None;
+
+        for (scheduled, count) in self.take_due(now_ms) {
+            if let Some(retry_at) = global_retry_at {
+                report.deferred.push(scheduled.not_before(retry_at));
+                continue;
+            }
+
+            let channel_id = scheduled.channel_id.clone();
+            match client.send_message(&channel_id, &scheduled.message).await {
+                Ok(_) => {
+                    report.sent += 1;
+                    if count > 1 {
+                        report.coalesced += count;
+                    }
+                    self.last_sent_ms.insert(channel_id, now_ms);
+                }
+                Err(DiscordError::RateLimited {
+                    retry_after,
+                    global,
+                }) => {
+                    let retry_at = now_ms + (retry_after * 1000.0).ceil() as u64;
+                    tracing::warn!(
+                        "Rate limited sending to {channel_id}, deferring {retry_after:.2}s"
+                    );
+                    if global {
+                        global_retry_at = Some(retry_at);
+                    }
+                    report.deferred.push(scheduled.not_before(retry_at));
+                }
+                Err(e) => {
+                    tracing::warn!("Failed to send scheduled message to {channel_id}: {e}");
+                    report.failed.push((channel_id, e));
+                }
+            }
+        }
+
+        // Whatever is still queued goes back to the caller with its due time
+        let next_allowed: HashMap<String, u64> = self
+            .last_sent_ms
+            .iter()
+            .map(|(channel, last)| (channel.clone(), last + self.config.channel_interval_ms))
+            .collect();
+        for mut message in std::mem::take(&mut self.queue) {
+            let channel_ready = next_allowed.get(&message.channel_id).copied().unwrap_or(0);
+            let not_before = message
+                .not_before_ms
+                .unwrap_or(0)
+                .max(channel_ready)
+                .max(global_retry_at.unwrap_or(0))
+                .max(now_ms);
+            message.not_before_ms = Some(not_before);
+            report.deferred.push(message);
+        }
+
+        report
+    }
+}
+
+/// One message standing in for `messages`: a count line plus as many of
+/// their embeds as fit
+fn summarize(
+    content: String,
+    messages: impl IntoIterator<Item = DiscordMessage>,
+) -> DiscordMessage {
+    let embeds: Vec<_> = messages
+        .into_iter()
+        .flat_map(|m| m.embeds.unwrap_or_default())
+        .take(MAX_EMBEDS)
+        .collect();
+
+    DiscordMessage {
+        content: Some(content),
+        embeds: (!embeds.is_empty()).then_some(embeds),
+        attachments: None,
+        components: None,
+    }
+}
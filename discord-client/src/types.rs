@@ -1,7 +1,13 @@
 use core::future::Future;
 use serde::{Deserialize, Serialize};
 use twilight_model::channel::message::embed::Embed as TwEmbed;
-use twilight_model::channel::Message;
+use twilight_model::channel::{Channel, Message};
+use twilight_model::guild::Emoji as GuildEmoji;
+
+use crate::audit_log::{AuditLogEntry, AuditLogQuery};
+use crate::components::ActionRow;
+use crate::permissions::ChannelPermissions;
+use crate::{DiscordError, DiscordFuture, Emoji};
 
 /// Outbound message payload with optional attachments.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -9,6 +15,9 @@ pub struct DiscordMessage {
     pub content: Option<String>,
     pub embeds: Option<Vec<TwEmbed>>, // Twilight embed types
     pub attachments: Option<Vec<AttachmentInput>>, // For multipart file uploads
+    /// Buttons / select menus (see [`crate::components`])
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub components: Option<Vec<ActionRow>>,
 }
 
 /// Attachment input for file uploads (binary data is not serialized to JSON).
@@ -29,9 +38,90 @@ pub struct DiscordRateLimitResponse {
     pub global: bool,
 }
 
+/// Error body Discord returns alongside non-2xx statuses
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DiscordApiError {
+    pub code: u32,
+    pub message: String,
+}
+
+/// JSON error code for "Cannot send messages to this user" (DMs closed,
+/// bot blocked, or no shared guild)
+pub const ERROR_CANNOT_SEND_TO_USER: u32 = 50007;
+
+/// Where an interaction was triggered
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(try_from = "u8", into = "u8")]
+pub enum InteractionContext {
+    /// A guild channel
+    Guild,
+    /// The DM with the app's bot user
+    BotDm,
+    /// Group DMs and DMs other than the bot's (user-installed apps only)
+    PrivateChannel,
+}
+
+impl TryFrom<u8> for InteractionContext {
+    type Error = String;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(InteractionContext::Guild),
+            1 => Ok(InteractionContext::BotDm),
+            2 => Ok(InteractionContext::PrivateChannel),
+            other => Err(format!("unknown interaction context {other}")),
+        }
+    }
+}
+
+impl From<InteractionContext> for u8 {
+    fn from(context: InteractionContext) -> Self {
+        match context {
+            InteractionContext::Guild => 0,
+            InteractionContext::BotDm => 1,
+            InteractionContext::PrivateChannel => 2,
+        }
+    }
+}
+
+/// How the app was installed for the user or guild an interaction came from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(try_from = "u8", into = "u8")]
+pub enum IntegrationType {
+    GuildInstall,
+    UserInstall,
+}
+
+impl TryFrom<u8> for IntegrationType {
+    type Error = String;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(IntegrationType::GuildInstall),
+            1 => Ok(IntegrationType::UserInstall),
+            other => Err(format!("unknown integration type {other}")),
+        }
+    }
+}
+
+impl From<IntegrationType> for u8 {
+    fn from(kind: IntegrationType) -> Self {
+        match kind {
+            IntegrationType::GuildInstall => 0,
+            IntegrationType::UserInstall => 1,
+        }
+    }
+}
+
 // Response type: leverage Twilight message model
 
 /// Common interface for Discord bot API operations
+///
+/// The message operations name their futures with associated types.
+/// Operations added since return a boxed [`DiscordFuture`] and have a default
+/// body failing with [`DiscordError::Unsupported`]: associated types can't
+/// have defaults, so a new named future would break every existing
+/// implementor, test mocks included. Clients override the ones they support.
 pub trait DiscordClient {
     /// Future type for `send_message` (avoids async fn in traits)
     type SendMessageFut<'a>: Future<Output = Result<Message, crate::DiscordError>> + 'a
@@ -73,9 +163,81 @@ pub trait DiscordClient {
         attachments: &'a [AttachmentInput],
     ) -> Self::EditMessageWithAttachmentsFut<'a>;
 
+    /// Open (or fetch the existing) DM channel with a user
+    fn create_dm_channel<'a>(&'a self, _user_id: &'a str) -> DiscordFuture<'a, Channel> {
+        unsupported("create_dm_channel")
+    }
+
+    /// Send a direct message to a user
+    ///
+    /// Fails with [`DiscordError::CannotDm`](crate::DiscordError::CannotDm)
+    /// when the user doesn't accept DMs from the bot; callers alerting many
+    /// users should skip that user rather than abort.
+    fn send_dm<'a>(
+        &'a self,
+        user_id: &'a str,
+        message: &'a DiscordMessage,
+    ) -> DiscordFuture<'a, Message> {
+        Box::pin(async move {
+            let channel = self.create_dm_channel(user_id).await?;
+            let channel_id = channel.id.to_string();
+            self.send_message(&channel_id, message).await
+        })
+    }
+
+    /// React to a message as the bot
+    ///
+    /// Custom emoji must be from a guild the bot is in. Fails with
+    /// [`DiscordError::Unsupported`]
+    /// unless the client implements it.
+    fn create_reaction<'a>(
+        &'a self,
+        _channel_id: &'a str,
+        _message_id: &'a str,
+        _emoji: &'a Emoji,
+    ) -> DiscordFuture<'a, ()> {
+        unsupported("create_reaction")
+    }
+
+    /// Remove the bot's own reaction from a message
+    fn delete_own_reaction<'a>(
+        &'a self,
+        _channel_id: &'a str,
+        _message_id: &'a str,
+        _emoji: &'a Emoji,
+    ) -> DiscordFuture<'a, ()> {
+        unsupported("delete_own_reaction")
+    }
+
+    /// List a guild's custom emoji
+    fn list_guild_emojis<'a>(&'a self, _guild_id: &'a str) -> DiscordFuture<'a, Vec<GuildEmoji>> {
+        unsupported("list_guild_emojis")
+    }
+
+    /// The bot's effective permissions in a channel (see
+    /// [`preflight_check`](crate::permissions::preflight_check))
+    fn get_channel_permissions<'a>(
+        &'a self,
+        _channel_id: &'a str,
+    ) -> DiscordFuture<'a, ChannelPermissions> {
+        unsupported("get_channel_permissions")
+    }
+
+    /// Recent audit log entries for a guild, newest first (needs
+    /// `VIEW_AUDIT_LOG`; see [`crate::audit_log`])
+    fn get_guild_audit_log<'a>(
+        &'a self,
+        _guild_id: &'a str,
+        _query: &'a AuditLogQuery,
+    ) -> DiscordFuture<'a, Vec<AuditLogEntry>> {
+        unsupported("get_guild_audit_log")
+    }
+
     /// Validate attachment data before sending
     fn validate_attachment(data: &[u8], filename: &str) -> Result<(), crate::DiscordError> {
-        const MAX_FILE_SIZE: usize = 8 * 1024 * 1024; // 8MB Discord limit
+        // The guild's boost tier isn't known here, so assume an unboosted guild;
+        // `AttachmentInput::from_url_with_tier` applies higher tier limits.
+        let max_file_size = crate::BoostTier::default().max_upload_bytes();
 
         if data.is_empty() {
             return Err(crate::DiscordError::InvalidAttachment(
@@ -83,11 +245,11 @@ pub trait DiscordClient {
             ));
         }
 
-        if data.len() > MAX_FILE_SIZE {
-            return Err(crate::DiscordError::InvalidAttachment(format!(
-                "File too large: {}KB > 8MB limit",
-                data.len() / 1024
-            )));
+        if data.len() > max_file_size {
+            return Err(crate::DiscordError::AttachmentTooLarge {
+                size: data.len(),
+                limit: max_file_size,
+            });
         }
 
         let valid_extensions = ["png", "jpg", "jpeg", "gif", "webp"];
@@ -116,9 +278,18 @@ pub trait DiscordClient {
     }
 }
 
+/// Default body for optional [`DiscordClient`] operations
+fn unsupported<'a, T: 'a>(operation: &str) -> DiscordFuture<'a, T> {
+    let error = DiscordError::Unsupported(operation.to_string());
+    Box::pin(core::future::ready(Err(error)))
+}
+
 /// Payload for editing a message. Attachments are intentionally omitted to avoid accidental removal.
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct DiscordMessageEdit {
     pub content: Option<String>,
     pub embeds: Option<Vec<TwEmbed>>,
+    /// Replaces the message's components; `Some(vec![])` removes them
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub components: Option<Vec<ActionRow>>,
 }
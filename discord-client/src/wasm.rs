@@ -1,15 +1,21 @@
+use crate::attachment::R2Attachment;
+use crate::audit_log::{AuditLogEntry, AuditLogQuery, AuditLogResponse};
+use crate::permissions::{fetch_channel_permissions, ChannelPermissions};
 use crate::{
-    AttachmentInput, DiscordClient, DiscordError, DiscordMessage, DiscordRateLimitResponse,
-    BASE_URL,
+    AttachmentInput, BoostTier, DiscordClient, DiscordError, DiscordFuture, DiscordMessage,
+    DiscordRateLimitResponse, Emoji, BASE_URL,
 };
 use core::future::Future;
 use core::pin::Pin;
 use gloo_net::http::Request;
+use serde::de::DeserializeOwned;
 use tracing::{error, info, warn};
-use twilight_model::channel::Message;
+use twilight_model::channel::{Channel, Message};
+use twilight_model::guild::Emoji as GuildEmoji;
 use worker_stack::js_sys;
 use worker_stack::wasm_bindgen::JsValue;
 use worker_stack::web_sys::{Blob, BlobPropertyBag, FormData};
+use worker_stack::worker::Bucket;
 
 /// WASM Discord bot client using gloo-net (for cnft.dev-workers)
 pub struct WasmDiscordClient {
@@ -42,6 +48,10 @@ impl DiscordClient for WasmDiscordClient {
         message: &'a DiscordMessage,
     ) -> Self::SendMessageFut<'a> {
         Box::pin(async move {
+            if let Some(rows) = &message.components {
+                crate::validate_components(rows)?;
+            }
+
             info!("🔗 Sending Discord message with WASM client");
             let url = format!("{BASE_URL}/channels/{channel_id}/messages",);
 
@@ -69,7 +79,7 @@ impl DiscordClient for WasmDiscordClient {
                 .await
                 .map_err(|e| DiscordError::Gloo(format!("Request failed: {e:?}")))?;
 
-            self.handle_message_response(response).await
+            self.handle_response(response).await
         })
     }
 
@@ -80,6 +90,10 @@ impl DiscordClient for WasmDiscordClient {
         edit: &'a crate::DiscordMessageEdit,
     ) -> Self::EditMessageFut<'a> {
         Box::pin(async move {
+            if let Some(rows) = &edit.components {
+                crate::validate_components(rows)?;
+            }
+
             info!("✏️ Editing Discord message (WASM)");
             let url = format!(
                 "https://discord.com/api/v10/channels/{}/messages/{}",
@@ -98,7 +112,7 @@ impl DiscordClient for WasmDiscordClient {
                 .await
                 .map_err(|e| DiscordError::Gloo(format!("Edit request failed: {e:?}")))?;
 
-            self.handle_message_response(response).await
+            self.handle_response(response).await
         })
     }
 
@@ -165,12 +179,218 @@ impl DiscordClient for WasmDiscordClient {
                 .await
                 .map_err(|e| DiscordError::Gloo(format!("Multipart edit request failed: {e:?}")))?;
 
-            self.handle_message_response(response).await
+            self.handle_response(response).await
+        })
+    }
+
+    fn create_dm_channel<'a>(&'a self, user_id: &'a str) -> DiscordFuture<'a, Channel> {
+        Box::pin(async move {
+            info!("📨 Opening DM channel with user {user_id}");
+            let request = Request::post(&format!("{BASE_URL}/users/@me/channels"))
+                .header("Authorization", &format!("Bot {}", self.bot_token))
+                .header("User-Agent", "defrag-discord-client/1.0")
+                .header("Content-Type", "application/json")
+                .json(&serde_json::json!({ "recipient_id": user_id }))
+                .map_err(|e| DiscordError::Gloo(format!("DM request creation failed: {e:?}")))?;
+
+            let response = request
+                .send()
+                .await
+                .map_err(|e| DiscordError::Gloo(format!("DM request failed: {e:?}")))?;
+
+            self.handle_response(response).await
+        })
+    }
+
+    fn create_reaction<'a>(
+        &'a self,
+        channel_id: &'a str,
+        message_id: &'a str,
+        emoji: &'a Emoji,
+    ) -> DiscordFuture<'a, ()> {
+        Box::pin(async move {
+            info!("👍 Reacting with {emoji} to message {message_id}");
+            let response = Request::put(&reaction_url(channel_id, message_id, emoji))
+                .header("Authorization", &format!("Bot {}", self.bot_token))
+                .header("User-Agent", "defrag-discord-client/1.0")
+                .send()
+                .await
+                .map_err(|e| DiscordError::Gloo(format!("Reaction request failed: {e:?}")))?;
+
+            Self::check_response(response).await.map(|_| ())
+        })
+    }
+
+    fn delete_own_reaction<'a>(
+        &'a self,
+        channel_id: &'a str,
+        message_id: &'a str,
+        emoji: &'a Emoji,
+    ) -> DiscordFuture<'a, ()> {
+        Box::pin(async move {
+            info!("🗑️ Removing {emoji} reaction from message {message_id}");
+            let response = Request::delete(&reaction_url(channel_id, message_id, emoji))
+                .header("Authorization", &format!("Bot {}", self.bot_token))
+                .header("User-Agent", "defrag-discord-client/1.0")
+                .send()
+                .await
+                .map_err(|e| {
+                    DiscordError::Gloo(format!("Delete reaction request failed: {e:?}"))
+                })?;
+
+            Self::check_response(response).await.map(|_| ())
+        })
+    }
+
+    fn list_guild_emojis<'a>(&'a self, guild_id: &'a str) -> DiscordFuture<'a, Vec<GuildEmoji>> {
+        Box::pin(async move {
+            let response = Request::get(&format!("{BASE_URL}/guilds/{guild_id}/emojis"))
+                .header("Authorization", &format!("Bot {}", self.bot_token))
+                .header("User-Agent", "defrag-discord-client/1.0")
+                .send()
+                .await
+                .map_err(|e| DiscordError::Gloo(format!("Emoji list request failed: {e:?}")))?;
+
+            self.handle_response(response).await
+        })
+    }
+
+    fn get_channel_permissions<'a>(
+        &'a self,
+        channel_id: &'a str,
+    ) -> DiscordFuture<'a, ChannelPermissions> {
+        Box::pin(async move {
+            info!("🔐 Resolving permissions in channel {channel_id}");
+            fetch_channel_permissions(channel_id, |url| self.get_text(url)).await
+        })
+    }
+
+    fn get_guild_audit_log<'a>(
+        &'a self,
+        guild_id: &'a str,
+        query: &'a AuditLogQuery,
+    ) -> DiscordFuture<'a, Vec<AuditLogEntry>> {
+        Box::pin(async move {
+            info!("📜 Fetching audit log for guild {guild_id}");
+            let url = format!(
+                "{BASE_URL}/guilds/{guild_id}/audit-logs{}",
+                query.to_query_string()
+            );
+            let response: AuditLogResponse = serde_json::from_str(&self.get_text(url).await?)?;
+            Ok(response.audit_log_entries)
         })
     }
 }
 
+fn reaction_url(channel_id: &str, message_id: &str, emoji: &Emoji) -> String {
+    format!(
+        "{BASE_URL}/channels/{channel_id}/messages/{message_id}/reactions/{}/@me",
+        emoji.reaction_path()
+    )
+}
+
 impl WasmDiscordClient {
+    /// Authorized GET, returning the body of a successful response
+    async fn get_text(&self, url: String) -> Result<String, DiscordError> {
+        let response = Request::get(&url)
+            .header("Authorization", &format!("Bot {}", self.bot_token))
+            .header("User-Agent", "defrag-discord-client/1.0")
+            .send()
+            .await
+            .map_err(|e| DiscordError::Gloo(format!("GET {url} failed: {e:?}")))?;
+
+        Self::check_response(response)
+            .await?
+            .text()
+            .await
+            .map_err(|e| DiscordError::Gloo(format!("Failed to get response text: {e:?}")))
+    }
+
+    /// Post `message` to a webhook URL
+    /// (`https://discord.com/api/webhooks/{id}/{token}`)
+    ///
+    /// The token in the URL authorizes the request, so no bot token is
+    /// needed. Attachments aren't uploaded and are rejected.
+    pub async fn execute_webhook(
+        webhook_url: &str,
+        message: &DiscordMessage,
+    ) -> Result<(), DiscordError> {
+        if message.attachments.as_ref().is_some_and(|a| !a.is_empty()) {
+            return Err(DiscordError::InvalidAttachment(
+                "webhook messages can't carry attachments".to_string(),
+            ));
+        }
+        if let Some(rows) = &message.components {
+            crate::validate_components(rows)?;
+        }
+
+        let response = Request::post(webhook_url)
+            .header("User-Agent", "defrag-discord-client/1.0")
+            .header("Content-Type", "application/json")
+            .json(message)
+            .map_err(|e| DiscordError::Gloo(format!("Request creation failed: {e:?}")))?
+            .send()
+            .await
+            .map_err(|e| DiscordError::Gloo(format!("Webhook request failed: {e:?}")))?;
+
+        Self::check_response(response).await.map(|_| ())
+    }
+
+    /// Send a message with files attached straight from an R2 bucket
+    ///
+    /// Any in-memory `message.attachments` are sent first. Each object is
+    /// checked against `tier`'s upload limit before it is read; see
+    /// [`R2Attachment`] for how the body reaches the form.
+    pub async fn send_message_with_r2(
+        &self,
+        channel_id: &str,
+        message: &DiscordMessage,
+        bucket: &Bucket,
+        attachments: &[R2Attachment],
+        tier: BoostTier,
+    ) -> Result<Message, DiscordError> {
+        if let Some(rows) = &message.components {
+            crate::validate_components(rows)?;
+        }
+
+        info!(
+            "📎 Sending Discord message with {} R2 attachments",
+            attachments.len()
+        );
+        let url = format!("{BASE_URL}/channels/{channel_id}/messages");
+        let mut metadata = message.attachments.clone().unwrap_or_default();
+        let form_data = Self::attachment_form(&metadata)?;
+
+        for attachment in attachments {
+            let (body, input) = attachment.fetch(bucket, tier, metadata.len()).await?;
+            let content_type = Self::get_content_type(&input.filename);
+            if content_type == "application/octet-stream" {
+                return Err(DiscordError::InvalidAttachment(format!(
+                    "Unsupported file type: {}",
+                    input.filename
+                )));
+            }
+
+            // Re-wrapping shares the streamed bytes; it only sets the part's type
+            let blob_options = BlobPropertyBag::new();
+            blob_options.set_type(content_type);
+            let blob =
+                Blob::new_with_blob_sequence_and_options(&js_sys::Array::of1(&body), &blob_options)
+                    .map_err(|e| {
+                        error!("❌ Failed to create Blob for {}: {e:?}", attachment.key);
+                        DiscordError::Gloo(format!("Failed to create Blob for {}", attachment.key))
+                    })?;
+            Self::append_file(&form_data, metadata.len(), &blob, &input.filename)?;
+            metadata.push(input);
+        }
+
+        let message = DiscordMessage {
+            attachments: Some(metadata),
+            ..message.clone()
+        };
+        self.post_multipart(&url, form_data, &message).await
+    }
+
     async fn send_multipart_message(
         &self,
         url: &str,
@@ -182,6 +402,12 @@ impl WasmDiscordClient {
             attachments.len()
         );
 
+        let form_data = Self::attachment_form(attachments)?;
+        self.post_multipart(url, form_data, message).await
+    }
+
+    /// FormData holding `attachments` as `files[n]` parts
+    fn attachment_form(attachments: &[AttachmentInput]) -> Result<FormData, DiscordError> {
         // Create FormData for multipart request
         let form_data = FormData::new().map_err(|e| {
             error!("❌ Failed to create FormData: {e:?}");
@@ -211,19 +437,33 @@ impl WasmDiscordClient {
                 DiscordError::Gloo(format!("Failed to create Blob for attachment {index}"))
             })?;
 
-            // Add file to form data
-            form_data
-                .append_with_blob_and_filename(
-                    &format!("files[{index}]"),
-                    &blob,
-                    &attachment.filename,
-                )
-                .map_err(|e| {
-                    error!("❌ Failed to append file {index} to FormData: {e:?}");
-                    DiscordError::Gloo(format!("Failed to append file {index}"))
-                })?;
+            Self::append_file(&form_data, index, &blob, &attachment.filename)?;
         }
 
+        Ok(form_data)
+    }
+
+    fn append_file(
+        form_data: &FormData,
+        index: usize,
+        blob: &Blob,
+        filename: &str,
+    ) -> Result<(), DiscordError> {
+        form_data
+            .append_with_blob_and_filename(&format!("files[{index}]"), blob, filename)
+            .map_err(|e| {
+                error!("❌ Failed to append file {index} to FormData: {e:?}");
+                DiscordError::Gloo(format!("Failed to append file {index}"))
+            })
+    }
+
+    /// POST `form_data` with `message` as its `payload_json`
+    async fn post_multipart(
+        &self,
+        url: &str,
+        form_data: FormData,
+        message: &DiscordMessage,
+    ) -> Result<Message, DiscordError> {
         // Add JSON payload
         let payload = serde_json::to_string(message).map_err(|e| {
             error!("❌ Failed to serialize message: {e:?}");
@@ -251,25 +491,33 @@ impl WasmDiscordClient {
             DiscordError::Gloo(format!("Multipart request failed: {e:?}"))
         })?;
 
-        self.handle_message_response(response).await
+        self.handle_response(response).await
     }
 
-    async fn handle_message_response(
+    async fn handle_response<T: DeserializeOwned>(
         &self,
         response: gloo_net::http::Response,
-    ) -> Result<Message, DiscordError> {
+    ) -> Result<T, DiscordError> {
+        let response = Self::check_response(response).await?;
+        let response_text = response
+            .text()
+            .await
+            .map_err(|e| DiscordError::Gloo(format!("Failed to get response text: {e:?}")))?;
+
+        serde_json::from_str(&response_text)
+            .map_err(|e| DiscordError::Gloo(format!("Failed to parse response JSON: {e:?}")))
+    }
+
+    /// Turn rate limits and error statuses into errors, passing successful
+    /// responses through unread (some endpoints reply 204 with no body)
+    async fn check_response(
+        response: gloo_net::http::Response,
+    ) -> Result<gloo_net::http::Response, DiscordError> {
         let status = response.status();
 
         if response.ok() {
-            info!("✅ Discord message sent successfully");
-            let response_text = response
-                .text()
-                .await
-                .map_err(|e| DiscordError::Gloo(format!("Failed to get response text: {e:?}")))?;
-
-            let message_response: Message = serde_json::from_str(&response_text)
-                .map_err(|e| DiscordError::Gloo(format!("Failed to parse response JSON: {e:?}")))?;
-            Ok(message_response)
+            info!("✅ Discord request succeeded");
+            Ok(response)
         } else if status == 429 {
             match response.json::<DiscordRateLimitResponse>().await {
                 Ok(rate_limit) => {
@@ -293,10 +541,7 @@ impl WasmDiscordClient {
         } else {
             let error_text = response.text().await.unwrap_or_default();
             error!("❌ Discord API error {}: {}", status, error_text);
-            Err(DiscordError::Request(format!(
-                "Discord API error {}: {}",
-                status, error_text
-            )))
+            Err(DiscordError::from_api_response(status, &error_text))
         }
     }
 }
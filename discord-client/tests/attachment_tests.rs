@@ -0,0 +1,138 @@
+#![cfg(feature = "native")]
+
+use discord_client::{
+    AttachmentInput, BoostTier, DiscordClient, DiscordError, NativeDiscordClient,
+};
+use http_client::HttpClient;
+use std::io::{Read, Write};
+use std::net::TcpListener;
+
+const MB: usize = 1024 * 1024;
+
+/// Serve one HTTP response on a local port, returning its base URL
+fn serve_once(status: &str, content_type: &str, body: Vec<u8>) -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let port = listener.local_addr().unwrap().port();
+    let head = format!(
+        "HTTP/1.1 {status}\r\nContent-Type: {content_type}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+        body.len()
+    );
+    std::thread::spawn(move || {
+        let (mut stream, _) = listener.accept().unwrap();
+        let mut request = [0u8; 4096];
+        let _ = stream.read(&mut request);
+        stream.write_all(head.as_bytes()).unwrap();
+        stream.write_all(&body).unwrap();
+    });
+    format!("http://127.0.0.1:{port}")
+}
+
+fn client() -> HttpClient {
+    HttpClient::builder().no_proxy().build().unwrap()
+}
+
+#[test]
+fn test_boost_tier_limits() {
+    assert_eq!(BoostTier::default(), BoostTier::None);
+    assert_eq!(BoostTier::None.max_upload_bytes(), 8 * MB);
+    assert_eq!(BoostTier::Tier1.max_upload_bytes(), 8 * MB);
+    assert_eq!(BoostTier::Tier2.max_upload_bytes(), 50 * MB);
+    assert_eq!(BoostTier::Tier3.max_upload_bytes(), 100 * MB);
+
+    assert_eq!(BoostTier::from_premium_tier(0), BoostTier::None);
+    assert_eq!(BoostTier::from_premium_tier(2), BoostTier::Tier2);
+    assert_eq!(BoostTier::from_premium_tier(7), BoostTier::Tier3);
+}
+
+#[test]
+fn test_validate_attachment_uses_unboosted_limit() {
+    let validate = <NativeDiscordClient as DiscordClient>::validate_attachment;
+
+    assert!(validate(&[1; 1024], "art.png").is_ok());
+    assert!(validate(&vec![1; 8 * MB], "art.png").is_ok());
+    match validate(&vec![1; 8 * MB + 1], "art.png") {
+        Err(DiscordError::AttachmentTooLarge { limit, .. }) => assert_eq!(limit, 8 * MB),
+        other => panic!("expected AttachmentTooLarge, got {other:?}"),
+    }
+    assert!(matches!(
+        validate(&[], "art.png"),
+        Err(DiscordError::InvalidAttachment(_))
+    ));
+    assert!(matches!(
+        validate(&[1; 16], "art.svg"),
+        Err(DiscordError::InvalidAttachment(_))
+    ));
+}
+
+#[tokio::test]
+async fn test_from_url_names_file_from_content_type() {
+    let base = serve_once("200 OK", "image/png", vec![0x89, b'P', b'N', b'G']);
+    let url = format!("{base}/ipfs/QmPirate.bin?download=1");
+
+    let attachment = AttachmentInput::from_url(&url, &client()).await.unwrap();
+    assert_eq!(attachment.filename, "QmPirate.png");
+    assert_eq!(attachment.file_data.len(), 4);
+    assert_eq!(attachment.id, "0");
+
+    let attachment = attachment.with_id("2").with_description("Pirate #2");
+    assert_eq!(attachment.id, "2");
+    assert_eq!(attachment.description.as_deref(), Some("Pirate #2"));
+}
+
+#[tokio::test]
+async fn test_from_url_falls_back_to_url_extension() {
+    let base = serve_once("200 OK", "application/octet-stream", vec![1, 2, 3]);
+    let url = format!("{base}/art/frog.webp");
+
+    let attachment = AttachmentInput::from_url(&url, &client()).await.unwrap();
+    assert_eq!(attachment.filename, "frog.webp");
+}
+
+#[tokio::test]
+async fn test_from_url_errors() {
+    let base = serve_once("404 Not Found", "text/plain", b"missing".to_vec());
+    let err = AttachmentInput::from_url(&format!("{base}/a.png"), &client())
+        .await
+        .unwrap_err();
+    assert!(
+        matches!(err, DiscordError::AttachmentDownload(_)),
+        "{err:?}"
+    );
+
+    let base = serve_once("200 OK", "image/png", Vec::new());
+    let err = AttachmentInput::from_url(&format!("{base}/a.png"), &client())
+        .await
+        .unwrap_err();
+    assert!(matches!(err, DiscordError::InvalidAttachment(_)), "{err:?}");
+
+    let base = serve_once("200 OK", "text/html", b"<html>".to_vec());
+    let err = AttachmentInput::from_url(&format!("{base}/page"), &client())
+        .await
+        .unwrap_err();
+    assert!(matches!(err, DiscordError::InvalidAttachment(_)), "{err:?}");
+}
+
+#[cfg(not(feature = "image-resize"))]
+#[tokio::test]
+async fn test_from_url_enforces_tier_limit() {
+    let base = serve_once("200 OK", "image/gif", vec![0; 8 * MB + 1]);
+    let err = AttachmentInput::from_url(&format!("{base}/big.gif"), &client())
+        .await
+        .unwrap_err();
+    match err {
+        DiscordError::AttachmentTooLarge { size, limit } => {
+            assert_eq!((size, limit), (8 * MB + 1, 8 * MB))
+        }
+        other => panic!("expected AttachmentTooLarge, got {other:?}"),
+    }
+
+    let base = serve_once("200 OK", "image/gif", vec![0; 8 * MB + 1]);
+    let attachment = AttachmentInput::from_url_with_tier(
+        &format!("{base}/big.gif"),
+        &client(),
+        BoostTier::Tier2,
+    )
+    .await
+    .unwrap();
+    assert_eq!(attachment.file_data.len(), 8 * MB + 1);
+}
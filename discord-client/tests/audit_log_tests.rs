@@ -0,0 +1,92 @@
+use discord_client::{AuditAction, AuditLogActionType, AuditLogEntry, AuditLogQuery, AuditRole};
+
+fn entries() -> Vec<AuditLogEntry> {
+    serde_json::from_value(serde_json::json!([
+        {
+            "id": "1200000000000000003",
+            "user_id": "900000000000000001",
+            "target_id": "900000000000000042",
+            "action_type": 25,
+            "changes": [
+                { "key": "$add", "new_value": [{ "id": "800000000000000007", "name": "Holder" }] },
+                { "key": "$remove", "new_value": [{ "id": "800000000000000008", "name": "Unverified" }] }
+            ],
+            "reason": "Verified wallet"
+        },
+        {
+            "id": "1200000000000000002",
+            "user_id": "900000000000000001",
+            "target_id": "900000000000000043",
+            "action_type": 72,
+            "options": { "channel_id": "700000000000000001", "count": "3" }
+        },
+        {
+            "id": "1200000000000000001",
+            "user_id": "900000000000000001",
+            "target_id": "800000000000000009",
+            "action_type": 30,
+            "changes": [{ "key": "name", "new_value": "Whale" }]
+        },
+        {
+            "id": "1200000000000000000",
+            "user_id": "900000000000000001",
+            "target_id": "600000000000000001",
+            "action_type": 10
+        }
+    ]))
+    .unwrap()
+}
+
+#[test]
+fn test_query_string() {
+    assert_eq!(AuditLogQuery::new().to_query_string(), "");
+    assert_eq!(
+        AuditLogQuery::new()
+            .action_type(AuditLogActionType::MemberRoleUpdate)
+            .user_id("900000000000000001")
+            .before("1200000000000000003")
+            .limit(150)
+            .to_query_string(),
+        "?action_type=25&user_id=900000000000000001&before=1200000000000000003&limit=100"
+    );
+}
+
+#[test]
+fn test_typed_actions() {
+    let entries = entries();
+
+    assert_eq!(
+        entries[0].action(),
+        Some(AuditAction::MemberRoleUpdate {
+            member_id: "900000000000000042".to_string(),
+            added: vec![AuditRole {
+                id: "800000000000000007".to_string(),
+                name: "Holder".to_string(),
+            }],
+            removed: vec![AuditRole {
+                id: "800000000000000008".to_string(),
+                name: "Unverified".to_string(),
+            }],
+        })
+    );
+    assert_eq!(entries[0].reason.as_deref(), Some("Verified wallet"));
+
+    assert_eq!(
+        entries[1].action(),
+        Some(AuditAction::MessageDelete {
+            author_id: "900000000000000043".to_string(),
+            channel_id: Some("700000000000000001".to_string()),
+            count: 3,
+        })
+    );
+
+    let Some(AuditAction::RoleCreate { role_id, changes }) = entries[2].action() else {
+        panic!("expected a role creation");
+    };
+    assert_eq!(role_id, "800000000000000009");
+    assert_eq!(changes[0].key, "name");
+
+    // Channel creation isn't one of the typed actions
+    assert_eq!(entries[3].action_type(), None);
+    assert_eq!(entries[3].action(), None);
+}
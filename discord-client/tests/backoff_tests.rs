@@ -0,0 +1,82 @@
+use std::time::Duration;
+
+use discord_client::{send_with_backoff, BackoffPolicy, DiscordError};
+
+mod common;
+use common::{announcement, quick_policy, RateLimitedClient};
+
+#[test]
+fn test_delay_grows_and_honors_retry_after() {
+    let policy = BackoffPolicy {
+        initial_delay: Duration::from_millis(100),
+        max_delay: Duration::from_millis(500),
+        ..BackoffPolicy::default()
+    };
+
+    assert_eq!(policy.delay_for(1, 0.0), Duration::from_millis(100));
+    assert_eq!(policy.delay_for(2, 0.0), Duration::from_millis(200));
+    assert_eq!(policy.delay_for(3, 0.0), Duration::from_millis(400));
+    assert_eq!(policy.delay_for(4, 0.0), Duration::from_millis(500));
+    assert_eq!(policy.delay_for(u32::MAX, 0.0), Duration::from_millis(500));
+    // Discord's retry_after wins over both the backoff and its cap
+    assert_eq!(policy.delay_for(1, 2.5), Duration::from_millis(2_500));
+}
+
+#[tokio::test]
+async fn test_retries_until_send_goes_through() {
+    let client = RateLimitedClient {
+        limited_sends: 2,
+        retry_after: 0.005,
+        ..Default::default()
+    };
+
+    let result = send_with_backoff(&client, "mints", &announcement(), &quick_policy()).await;
+    assert!(matches!(result, Err(DiscordError::Request(ref e)) if e == "delivered"));
+    assert_eq!(client.sends.borrow().len(), 3);
+}
+
+#[tokio::test]
+async fn test_gives_up_after_max_attempts() {
+    let client = RateLimitedClient {
+        limited_sends: usize::MAX,
+        retry_after: 0.001,
+        ..Default::default()
+    };
+
+    let err = send_with_backoff(&client, "mints", &announcement(), &quick_policy())
+        .await
+        .unwrap_err();
+    match err {
+        DiscordError::RetriesExhausted {
+            attempts,
+            waited_ms,
+            last,
+        } => {
+            assert_eq!(attempts, 4);
+            assert!(waited_ms >= 7, "waited {waited_ms}ms");
+            assert!(matches!(
+                *last,
+                DiscordError::RateLimited { global: false, .. }
+            ));
+        }
+        other => panic!("expected RetriesExhausted, got {other:?}"),
+    }
+    assert_eq!(client.sends.borrow().len(), 4);
+}
+
+#[tokio::test]
+async fn test_gives_up_when_wait_exceeds_budget() {
+    let client = RateLimitedClient {
+        limited_sends: usize::MAX,
+        retry_after: 30.0,
+        ..Default::default()
+    };
+
+    let err = send_with_backoff(&client, "mints", &announcement(), &quick_policy())
+        .await
+        .unwrap_err();
+    assert!(matches!(
+        err,
+        DiscordError::RetriesExhausted { attempts: 1, .. }
+    ));
+}
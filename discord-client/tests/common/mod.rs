@@ -0,0 +1,216 @@
+//! Mock clients and helpers shared by the integration tests
+#![allow(dead_code)]
+
+use std::cell::RefCell;
+use std::future::{ready, Ready};
+use std::time::Duration;
+
+use discord_client::{
+    AttachmentInput, AuditLogEntry, AuditLogQuery, BackoffPolicy, ChannelPermissions,
+    DiscordClient, DiscordError, DiscordFuture, DiscordMessage, DiscordMessageEdit, Emoji,
+};
+use twilight_model::channel::{Channel, Message};
+use twilight_model::guild::{Emoji as GuildEmoji, Permissions};
+
+/// Rate limits the first `limited_sends` sends, recording the channels tried
+#[derive(Default)]
+pub struct RateLimitedClient {
+    pub limited_sends: usize,
+    pub retry_after: f64,
+    pub global: bool,
+    pub sends: RefCell<Vec<String>>,
+}
+
+impl RateLimitedClient {
+    /// Rate limits every send for `retry_after` seconds
+    pub fn always(retry_after: f64, global: bool) -> Self {
+        Self {
+            limited_sends: usize::MAX,
+            retry_after,
+            global,
+            ..Default::default()
+        }
+    }
+
+    fn unsupported<T>() -> Ready<Result<T, DiscordError>> {
+        ready(Err(DiscordError::Request("unsupported".to_string())))
+    }
+}
+
+impl DiscordClient for RateLimitedClient {
+    type SendMessageFut<'a> = Ready<Result<Message, DiscordError>>;
+    type EditMessageFut<'a> = Ready<Result<Message, DiscordError>>;
+    type EditMessageWithAttachmentsFut<'a> = Ready<Result<Message, DiscordError>>;
+
+    fn send_message<'a>(
+        &'a self,
+        channel_id: &'a str,
+        _message: &'a DiscordMessage,
+    ) -> Self::SendMessageFut<'a> {
+        let mut sends = self.sends.borrow_mut();
+        sends.push(channel_id.to_string());
+        if sends.len() > self.limited_sends {
+            // Building a twilight Message is verbose; any other error ends
+            // the retries just the same
+            return ready(Err(DiscordError::Request("delivered".to_string())));
+        }
+        ready(Err(DiscordError::RateLimited {
+            retry_after: self.retry_after,
+            global: self.global,
+        }))
+    }
+
+    fn edit_message<'a>(
+        &'a self,
+        _channel_id: &'a str,
+        _message_id: &'a str,
+        _edit: &'a DiscordMessageEdit,
+    ) -> Self::EditMessageFut<'a> {
+        Self::unsupported()
+    }
+
+    fn edit_message_with_attachments<'a>(
+        &'a self,
+        _channel_id: &'a str,
+        _message_id: &'a str,
+        _edit: &'a DiscordMessageEdit,
+        _attachments: &'a [AttachmentInput],
+    ) -> Self::EditMessageWithAttachmentsFut<'a> {
+        Self::unsupported()
+    }
+}
+
+/// Records calls and fails every request, so no Discord payloads are needed
+#[derive(Default)]
+pub struct RecordingClient {
+    pub calls: RefCell<Vec<String>>,
+}
+
+impl RecordingClient {
+    fn record<T>(&self, call: String) -> Ready<Result<T, DiscordError>> {
+        self.calls.borrow_mut().push(call.clone());
+        ready(Err(DiscordError::Request(call)))
+    }
+}
+
+impl DiscordClient for RecordingClient {
+    type SendMessageFut<'a> = Ready<Result<Message, DiscordError>>;
+    type EditMessageFut<'a> = Ready<Result<Message, DiscordError>>;
+    type EditMessageWithAttachmentsFut<'a> = Ready<Result<Message, DiscordError>>;
+
+    fn send_message<'a>(
+        &'a self,
+        channel_id: &'a str,
+        _message: &'a DiscordMessage,
+    ) -> Self::SendMessageFut<'a> {
+        self.record(format!("send_message {channel_id}"))
+    }
+
+    fn edit_message<'a>(
+        &'a self,
+        channel_id: &'a str,
+        message_id: &'a str,
+        _edit: &'a DiscordMessageEdit,
+    ) -> Self::EditMessageFut<'a> {
+        self.record(format!("edit_message {channel_id}/{message_id}"))
+    }
+
+    fn edit_message_with_attachments<'a>(
+        &'a self,
+        channel_id: &'a str,
+        message_id: &'a str,
+        _edit: &'a DiscordMessageEdit,
+        attachments: &'a [AttachmentInput],
+    ) -> Self::EditMessageWithAttachmentsFut<'a> {
+        self.record(format!(
+            "edit_message_with_attachments {channel_id}/{message_id} ({})",
+            attachments.len()
+        ))
+    }
+
+    fn create_dm_channel<'a>(&'a self, user_id: &'a str) -> DiscordFuture<'a, Channel> {
+        Box::pin(self.record(format!("create_dm_channel {user_id}")))
+    }
+
+    fn send_dm<'a>(
+        &'a self,
+        user_id: &'a str,
+        _message: &'a DiscordMessage,
+    ) -> DiscordFuture<'a, Message> {
+        self.calls.borrow_mut().push(format!("send_dm {user_id}"));
+        Box::pin(ready(Err(DiscordError::CannotDm(user_id.to_string()))))
+    }
+
+    fn create_reaction<'a>(
+        &'a self,
+        channel_id: &'a str,
+        message_id: &'a str,
+        emoji: &'a Emoji,
+    ) -> DiscordFuture<'a, ()> {
+        self.calls.borrow_mut().push(format!(
+            "create_reaction {channel_id}/{message_id} {}",
+            emoji.reaction_path()
+        ));
+        Box::pin(ready(Ok(())))
+    }
+
+    fn delete_own_reaction<'a>(
+        &'a self,
+        channel_id: &'a str,
+        message_id: &'a str,
+        emoji: &'a Emoji,
+    ) -> DiscordFuture<'a, ()> {
+        Box::pin(self.record(format!(
+            "delete_own_reaction {channel_id}/{message_id} {emoji}"
+        )))
+    }
+
+    fn list_guild_emojis<'a>(&'a self, guild_id: &'a str) -> DiscordFuture<'a, Vec<GuildEmoji>> {
+        Box::pin(self.record(format!("list_guild_emojis {guild_id}")))
+    }
+
+    /// Can send, but not embed or attach
+    fn get_channel_permissions<'a>(
+        &'a self,
+        channel_id: &'a str,
+    ) -> DiscordFuture<'a, ChannelPermissions> {
+        self.calls
+            .borrow_mut()
+            .push(format!("get_channel_permissions {channel_id}"));
+        Box::pin(ready(Ok(ChannelPermissions {
+            permissions: Permissions::VIEW_CHANNEL | Permissions::SEND_MESSAGES,
+            thread: false,
+        })))
+    }
+
+    fn get_guild_audit_log<'a>(
+        &'a self,
+        guild_id: &'a str,
+        query: &'a AuditLogQuery,
+    ) -> DiscordFuture<'a, Vec<AuditLogEntry>> {
+        Box::pin(self.record(format!(
+            "get_guild_audit_log {guild_id}{}",
+            query.to_query_string()
+        )))
+    }
+}
+
+pub fn announcement() -> DiscordMessage {
+    DiscordMessage {
+        content: Some("mint is live".to_string()),
+        embeds: None,
+        attachments: None,
+        components: None,
+    }
+}
+
+/// Short waits so retry tests finish quickly
+pub fn quick_policy() -> BackoffPolicy {
+    BackoffPolicy {
+        max_attempts: 4,
+        initial_delay: Duration::from_millis(1),
+        multiplier: 2.0,
+        max_delay: Duration::from_millis(10),
+        max_total_wait: Duration::from_secs(5),
+    }
+}
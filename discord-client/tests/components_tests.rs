@@ -0,0 +1,206 @@
+use discord_client::{
+    validate_components, ActionRow, Button, ComponentInteraction, ComponentResponse,
+    ComponentRouter, CustomId, DiscordApiError, DiscordError, DiscordMessage, IntegrationType,
+    InteractionContext, SelectMenu, SelectOption, ERROR_CANNOT_SEND_TO_USER,
+};
+use serde_json::json;
+
+const POLICY: &str = "b3dab69f7e6100849434fb1781e34bd12a916557f6231b8d2629b6f6";
+
+#[test]
+fn test_components_serialize_to_discord_format() {
+    let message = DiscordMessage {
+        content: Some("New listing".to_string()),
+        embeds: None,
+        attachments: None,
+        components: Some(vec![
+            ActionRow::new()
+                .button(Button::link("https://www.jpg.store", "Buy"))
+                .button(Button::success(CustomId::new("watch").arg(POLICY), "Watch").emoji("👀")),
+            ActionRow::select_menu(
+                SelectMenu::new("mute")
+                    .placeholder("Mute for...")
+                    .option(SelectOption::new("1 hour", "3600"))
+                    .option(SelectOption::new("1 day", "86400").selected()),
+            ),
+        ]),
+    };
+
+    let value = serde_json::to_value(&message).unwrap();
+    assert_eq!(
+        value["components"],
+        json!([
+            {
+                "type": 1,
+                "components": [
+                    { "type": 2, "style": 5, "label": "Buy", "url": "https://www.jpg.store" },
+                    {
+                        "type": 2,
+                        "style": 3,
+                        "label": "Watch",
+                        "custom_id": format!("watch:{POLICY}"),
+                        "emoji": { "name": "👀" }
+                    }
+                ]
+            },
+            {
+                "type": 1,
+                "components": [{
+                    "type": 3,
+                    "custom_id": "mute",
+                    "placeholder": "Mute for...",
+                    "options": [
+                        { "label": "1 hour", "value": "3600" },
+                        { "label": "1 day", "value": "86400", "default": true }
+                    ]
+                }]
+            }
+        ])
+    );
+
+    // Round-trips through the wire format
+    let parsed: DiscordMessage = serde_json::from_value(value).unwrap();
+    assert_eq!(parsed.components, message.components);
+}
+
+#[test]
+fn test_messages_without_components_omit_field() {
+    let message = DiscordMessage {
+        content: Some("hi".to_string()),
+        embeds: None,
+        attachments: None,
+        components: None,
+    };
+    let value = serde_json::to_value(&message).unwrap();
+    assert!(value.get("components").is_none());
+}
+
+#[test]
+fn test_validate_components() {
+    let ok = vec![ActionRow::new().button(Button::primary("a", "A"))];
+    assert!(validate_components(&ok).is_ok());
+
+    let too_many_buttons = vec![(0..6).fold(ActionRow::new(), |row, i| {
+        row.button(Button::primary(format!("b{i}"), "B"))
+    })];
+    assert!(matches!(
+        validate_components(&too_many_buttons),
+        Err(DiscordError::InvalidComponent(_))
+    ));
+
+    let duplicate_ids = vec![
+        ActionRow::new().button(Button::primary("same", "A")),
+        ActionRow::new().button(Button::danger("same", "B")),
+    ];
+    assert!(validate_components(&duplicate_ids).is_err());
+
+    let empty_menu = vec![ActionRow::select_menu(SelectMenu::new("menu"))];
+    assert!(validate_components(&empty_menu).is_err());
+
+    let long_id = vec![ActionRow::new().button(Button::primary("x".repeat(101), "A"))];
+    assert!(validate_components(&long_id).is_err());
+
+    let too_many_rows = vec![ActionRow::new().button(Button::link("https://a.b", "L")); 6];
+    assert!(validate_components(&too_many_rows).is_err());
+}
+
+#[test]
+fn test_parse_and_route_component_interaction() {
+    let body = json!({
+        "id": "1100",
+        "application_id": "2200",
+        "type": 3,
+        "token": "interaction-token",
+        "guild_id": "3300",
+        "channel_id": "4400",
+        "member": { "user": { "id": "5500", "username": "pirate" } },
+        "message": { "id": "6600" },
+        "data": { "custom_id": format!("mute:{POLICY}:86400"), "component_type": 3, "values": ["86400"] }
+    })
+    .to_string();
+
+    let interaction = ComponentInteraction::parse(&body).unwrap();
+    assert_eq!(interaction.user_id.as_deref(), Some("5500"));
+    assert_eq!(interaction.message_id.as_deref(), Some("6600"));
+    assert_eq!(interaction.values, vec!["86400"]);
+    assert_eq!(interaction.context, None);
+    assert!(!interaction.is_user_install());
+
+    let router = ComponentRouter::new()
+        .on("watch", |_, _| ComponentResponse::DeferredUpdate)
+        .on("mute", |interaction, args| {
+            assert_eq!(args[0], POLICY);
+            ComponentResponse::ephemeral(&format!("Muted for {}s", interaction.values[0]))
+        });
+
+    let response = router.route(&interaction).unwrap();
+    assert_eq!(
+        response.to_json(),
+        json!({
+            "type": 4,
+            "data": { "content": "Muted for 86400s", "flags": 64, "components": [] }
+        })
+    );
+
+    let unknown = ComponentInteraction {
+        custom_id: "buy:123".to_string(),
+        ..interaction
+    };
+    assert!(router.route(&unknown).is_none());
+}
+
+#[test]
+fn test_parse_user_install_context() {
+    let body = json!({
+        "id": "1100",
+        "application_id": "2200",
+        "type": 3,
+        "token": "interaction-token",
+        "channel_id": "4400",
+        "user": { "id": "5500" },
+        "context": 2,
+        "authorizing_integration_owners": { "1": "5500" },
+        "data": { "custom_id": "watch", "component_type": 2 }
+    })
+    .to_string();
+
+    let interaction = ComponentInteraction::parse(&body).unwrap();
+    assert_eq!(interaction.user_id.as_deref(), Some("5500"));
+    assert_eq!(
+        interaction.context,
+        Some(InteractionContext::PrivateChannel)
+    );
+    assert_eq!(
+        interaction.integration_types,
+        vec![IntegrationType::UserInstall]
+    );
+    assert!(interaction.is_user_install());
+}
+
+#[test]
+fn test_api_error_and_context_wire_format() {
+    let body = json!({ "code": ERROR_CANNOT_SEND_TO_USER, "message": "Cannot send messages to this user" });
+    let parsed: DiscordApiError = serde_json::from_value(body).unwrap();
+    assert_eq!(parsed.code, 50007);
+    assert_eq!(
+        serde_json::to_value(InteractionContext::BotDm).unwrap(),
+        json!(1)
+    );
+}
+
+#[test]
+fn test_parse_rejects_other_interaction_types() {
+    let ping = json!({ "id": "1", "application_id": "2", "type": 1, "token": "t" }).to_string();
+    assert!(matches!(
+        ComponentInteraction::parse(&ping),
+        Err(DiscordError::InvalidComponent(_))
+    ));
+}
+
+#[test]
+fn test_custom_id_roundtrip() {
+    let id = CustomId::new("watch").arg(POLICY).arg(42);
+    let encoded = id.to_string();
+    assert_eq!(encoded, format!("watch:{POLICY}:42"));
+    assert_eq!(CustomId::parse(&encoded), id);
+}
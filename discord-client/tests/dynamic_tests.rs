@@ -0,0 +1,138 @@
+use discord_client::{
+    preflight_check, AuditLogQuery, DiscordError, DiscordMessage, DiscordMessageEdit,
+    DynDiscordClient, Emoji,
+};
+use twilight_model::guild::Permissions;
+use twilight_util::builder::embed::EmbedBuilder;
+
+mod common;
+use common::{RateLimitedClient, RecordingClient};
+
+/// Shared logic written against the trait object, as a bot would
+async fn alert_holders(
+    client: &dyn DynDiscordClient,
+    user_ids: &[&str],
+    message: &DiscordMessage,
+) -> Result<usize, DiscordError> {
+    let mut skipped = 0;
+    for user_id in user_ids {
+        match client.send_dm(user_id, message).await {
+            Ok(_) => {}
+            Err(DiscordError::CannotDm(_)) => skipped += 1,
+            Err(e) => return Err(e),
+        }
+    }
+    Ok(skipped)
+}
+
+fn message() -> DiscordMessage {
+    DiscordMessage {
+        content: Some("Floor moved".to_string()),
+        embeds: None,
+        attachments: None,
+        components: None,
+    }
+}
+
+#[tokio::test]
+async fn test_dyn_client_dispatches_to_concrete_client() {
+    let client = RecordingClient::default();
+    let dyn_client: &dyn DynDiscordClient = &client;
+    let edit = DiscordMessageEdit::default();
+
+    let err = dyn_client
+        .send_message("123", &message())
+        .await
+        .unwrap_err();
+    assert!(matches!(err, DiscordError::Request(ref call) if call == "send_message 123"));
+
+    assert!(dyn_client.edit_message("123", "456", &edit).await.is_err());
+    assert!(dyn_client
+        .edit_message_with_attachments("123", "456", &edit, &[])
+        .await
+        .is_err());
+    assert!(dyn_client.create_dm_channel("789").await.is_err());
+    dyn_client
+        .create_reaction("123", "456", &Emoji::custom("gm", 42))
+        .await
+        .unwrap();
+    assert!(dyn_client
+        .delete_own_reaction("123", "456", &Emoji::unicode("🔥"))
+        .await
+        .is_err());
+    assert!(dyn_client.list_guild_emojis("999").await.is_err());
+    assert!(dyn_client.get_channel_permissions("123").await.is_ok());
+    assert!(dyn_client
+        .get_guild_audit_log("999", &AuditLogQuery::new().limit(5))
+        .await
+        .is_err());
+
+    assert_eq!(
+        client.calls.borrow().as_slice(),
+        [
+            "send_message 123",
+            "edit_message 123/456",
+            "edit_message_with_attachments 123/456 (0)",
+            "create_dm_channel 789",
+            "create_reaction 123/456 gm:42",
+            "delete_own_reaction 123/456 🔥",
+            "list_guild_emojis 999",
+            "get_channel_permissions 123",
+            "get_guild_audit_log 999?limit=5",
+        ]
+    );
+}
+
+#[tokio::test]
+async fn test_boxed_dyn_client_in_shared_logic() {
+    let client: Box<dyn DynDiscordClient> = Box::new(RecordingClient::default());
+
+    let skipped = alert_holders(client.as_ref(), &["1", "2"], &message())
+        .await
+        .unwrap();
+    assert_eq!(skipped, 2);
+}
+
+#[tokio::test]
+async fn test_send_dm_defaults_through_create_dm_channel() {
+    // RateLimitedClient implements neither DM method
+    let client = RateLimitedClient::default();
+    let dyn_client: &dyn DynDiscordClient = &client;
+
+    let err = dyn_client.send_dm("789", &message()).await.unwrap_err();
+    assert!(matches!(err, DiscordError::Unsupported(ref op) if op == "create_dm_channel"));
+    assert!(client.sends.borrow().is_empty());
+}
+
+#[tokio::test]
+async fn test_audit_log_defaults_to_unsupported() {
+    let client = RateLimitedClient::default();
+    let dyn_client: &dyn DynDiscordClient = &client;
+
+    let err = dyn_client
+        .get_guild_audit_log("999", &AuditLogQuery::new())
+        .await
+        .unwrap_err();
+    assert!(matches!(err, DiscordError::Unsupported(ref op) if op == "get_guild_audit_log"));
+}
+
+#[tokio::test]
+async fn test_preflight_check_reports_missing_embed_links() {
+    let client = RecordingClient::default();
+
+    let missing = preflight_check(&client, &message(), "123").await.unwrap();
+    assert!(missing.is_empty());
+
+    let with_embed = DiscordMessage {
+        embeds: Some(vec![EmbedBuilder::new().title("Sale").build()]),
+        ..message()
+    };
+    let missing = preflight_check(&client, &with_embed, "123").await.unwrap();
+    assert_eq!(missing, Permissions::EMBED_LINKS);
+
+    // Clients without permission lookups can't be preflighted
+    let err = preflight_check(&RateLimitedClient::default(), &message(), "123")
+        .await
+        .unwrap_err();
+    assert!(matches!(err, DiscordError::Unsupported(ref op) if op == "get_channel_permissions"));
+}
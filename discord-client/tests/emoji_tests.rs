@@ -0,0 +1,131 @@
+use discord_client::emoji::{find_emoji, format_emoji};
+use discord_client::{DiscordClient, DiscordError, Emoji};
+use twilight_model::guild::Emoji as GuildEmoji;
+
+mod common;
+use common::RateLimitedClient;
+
+fn guild_emojis() -> Vec<GuildEmoji> {
+    serde_json::from_value(serde_json::json!([
+        {
+            "id": "1100000000000000001",
+            "name": "floor_up",
+            "animated": false,
+            "available": true,
+            "managed": false,
+            "require_colons": true,
+            "roles": []
+        },
+        {
+            "id": "1100000000000000002",
+            "name": "wagmi",
+            "animated": true,
+            "available": true,
+            "managed": false,
+            "require_colons": true,
+            "roles": []
+        }
+    ]))
+    .unwrap()
+}
+
+#[test]
+fn test_emoji_markdown() {
+    assert_eq!(Emoji::unicode("🚀").to_string(), "🚀");
+    assert_eq!(Emoji::custom("gm", 42).to_string(), "<:gm:42>");
+    assert_eq!(Emoji::animated("gm", 42).to_string(), "<a:gm:42>");
+}
+
+#[test]
+fn test_emoji_reaction_path() {
+    assert_eq!(Emoji::unicode("👍").reaction_path(), "%F0%9F%91%8D");
+    assert_eq!(Emoji::animated("gm", 42).reaction_path(), "gm:42");
+}
+
+#[test]
+fn test_emoji_parse() {
+    assert_eq!(
+        "<:gm:42>".parse::<Emoji>().unwrap(),
+        Emoji::custom("gm", 42)
+    );
+    assert_eq!(
+        "<a:gm:42>".parse::<Emoji>().unwrap(),
+        Emoji::animated("gm", 42)
+    );
+    assert_eq!("gm:42".parse::<Emoji>().unwrap(), Emoji::custom("gm", 42));
+    assert_eq!(" 🔥 ".parse::<Emoji>().unwrap(), Emoji::unicode("🔥"));
+
+    for invalid in ["", "<:gm:abc>", "<::42>", "gm:"] {
+        assert!(
+            matches!(invalid.parse::<Emoji>(), Err(DiscordError::InvalidEmoji(_))),
+            "{invalid:?} should be rejected"
+        );
+    }
+}
+
+#[test]
+fn test_guild_emoji_helpers() {
+    let emojis = guild_emojis();
+
+    let floor_up = find_emoji(&emojis, "floor_up").unwrap();
+    assert_eq!(format_emoji(floor_up), "<:floor_up:1100000000000000001>");
+
+    let wagmi = Emoji::from(find_emoji(&emojis, "wagmi").unwrap());
+    assert_eq!(wagmi.to_string(), "<a:wagmi:1100000000000000002>");
+    assert_eq!(wagmi.reaction_path(), "wagmi:1100000000000000002");
+
+    assert!(find_emoji(&emojis, "Floor_Up").is_none());
+}
+
+#[test]
+fn test_emoji_from_str_edge_cases() {
+    // Display output parses back to the same emoji
+    for emoji in [
+        Emoji::unicode("🏴\u{200d}☠\u{fe0f}"),
+        Emoji::custom("floor_up", 1_100_000_000_000_000_001),
+        Emoji::animated("wagmi", 1_100_000_000_000_000_002),
+    ] {
+        assert_eq!(emoji.to_string().parse::<Emoji>().unwrap(), emoji);
+    }
+
+    for invalid in [
+        "   ",
+        "<:42>",
+        "<a:gm:>",
+        "gm:-1",
+        "gm:18446744073709551616",
+    ] {
+        assert!(
+            matches!(invalid.parse::<Emoji>(), Err(DiscordError::InvalidEmoji(_))),
+            "{invalid:?} should be rejected"
+        );
+    }
+}
+
+#[tokio::test]
+async fn test_emoji_methods_default_to_unsupported() {
+    // RateLimitedClient implements none of the emoji methods
+    let client = RateLimitedClient::default();
+    let emoji = Emoji::unicode("🔥");
+
+    let results = [
+        client.create_reaction("123", "456", &emoji).await.err(),
+        client.delete_own_reaction("123", "456", &emoji).await.err(),
+        client.list_guild_emojis("999").await.err(),
+    ];
+    let operations: Vec<_> = results
+        .iter()
+        .map(|err| match err {
+            Some(DiscordError::Unsupported(operation)) => operation.as_str(),
+            other => panic!("expected Unsupported, got {other:?}"),
+        })
+        .collect();
+    assert_eq!(
+        operations,
+        [
+            "create_reaction",
+            "delete_own_reaction",
+            "list_guild_emojis"
+        ]
+    );
+}
@@ -0,0 +1,53 @@
+//! The global rate limit is a process-wide static, so this test runs in its
+//! own binary rather than alongside the other backoff tests.
+
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use discord_client::{global_rate_limit_until, send_with_backoff, BackoffPolicy, DiscordError};
+
+mod common;
+use common::{announcement, quick_policy, RateLimitedClient};
+
+fn now_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_millis() as u64
+}
+
+#[tokio::test]
+async fn test_global_limit_is_shared() {
+    let limited = RateLimitedClient::always(0.05, true);
+    let policy = BackoffPolicy {
+        max_attempts: 1,
+        ..quick_policy()
+    };
+    let err = send_with_backoff(&limited, "mints", &announcement(), &policy)
+        .await
+        .unwrap_err();
+    assert!(matches!(
+        err,
+        DiscordError::RetriesExhausted { attempts: 1, .. }
+    ));
+    assert!(global_rate_limit_until(now_ms()).is_some());
+
+    // Another client in the isolate holds off rather than sending into it
+    let other = RateLimitedClient::default();
+    let policy = BackoffPolicy {
+        max_total_wait: Duration::ZERO,
+        ..quick_policy()
+    };
+    let err = send_with_backoff(&other, "sales", &announcement(), &policy)
+        .await
+        .unwrap_err();
+    assert!(matches!(
+        err,
+        DiscordError::RetriesExhausted { attempts: 0, .. }
+    ));
+    assert!(other.sends.borrow().is_empty());
+
+    // Once the limit lifts it goes through
+    let result = send_with_backoff(&other, "sales", &announcement(), &quick_policy()).await;
+    assert!(matches!(result, Err(DiscordError::Request(ref e)) if e == "delivered"));
+    assert_eq!(other.sends.borrow().len(), 1);
+}
@@ -27,6 +27,7 @@ async fn test_native_json_message() {
         content: Some("Test message from discord-client native".to_string()),
         embeds: None,
         attachments: None,
+        components: None,
     };
 
     let result = client.send_message(&channel_id, &message).await;
@@ -64,6 +65,7 @@ async fn test_native_message_with_embed() {
         content: Some("Message with embed".to_string()),
         embeds: Some(vec![embed]),
         attachments: None,
+        components: None,
     };
 
     let result = client.send_message(&channel_id, &message).await;
@@ -116,6 +118,7 @@ async fn test_native_message_with_attachment() {
         content: Some("Message with attachment".to_string()),
         embeds: None,
         attachments: Some(vec![attachment]),
+        components: None,
     };
 
     let result = client.send_message(&channel_id, &message).await;
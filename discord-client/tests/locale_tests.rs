@@ -0,0 +1,95 @@
+use discord_client::locale::{Locale, LocaleConfig, Template, TemplateArgs, Templates};
+use discord_client::DiscordError;
+
+#[test]
+fn test_locale_parsing() {
+    assert_eq!("en-US".parse::<Locale>().unwrap(), Locale::En);
+    assert_eq!("es-419".parse::<Locale>().unwrap(), Locale::Es);
+    assert_eq!("JA".parse::<Locale>().unwrap(), Locale::Ja);
+    assert!(matches!(
+        "fr".parse::<Locale>(),
+        Err(DiscordError::Config(_))
+    ));
+    assert_eq!(serde_json::to_string(&Locale::Ja).unwrap(), "\"ja\"");
+}
+
+#[test]
+fn test_locale_config_precedence() {
+    let config: LocaleConfig = serde_json::from_value(serde_json::json!({
+        "default": "en",
+        "guilds": { "g1": "ja" },
+        "channels": { "c1": "es" }
+    }))
+    .unwrap();
+
+    assert_eq!(config.locale_for("c1", Some("g1")), Locale::Es);
+    assert_eq!(config.locale_for("c2", Some("g1")), Locale::Ja);
+    assert_eq!(config.locale_for("c2", None), Locale::En);
+    assert_eq!(LocaleConfig::default().locale_for("c1", None), Locale::En);
+}
+
+#[test]
+fn test_template_plurals() {
+    let template: Template =
+        "{who} made {count, plural, =0 {no sales} one {# sale} other {# sales}}"
+            .parse()
+            .unwrap();
+    let render = |locale, n| {
+        template.render(
+            locale,
+            &TemplateArgs::new().text("who", "Pirates").count("count", n),
+        )
+    };
+
+    assert_eq!(render(Locale::En, 0), "Pirates made no sales");
+    assert_eq!(render(Locale::En, 1), "Pirates made 1 sale");
+    assert_eq!(render(Locale::En, 12), "Pirates made 12 sales");
+    // Japanese has no singular
+    assert_eq!(render(Locale::Ja, 1), "Pirates made 1 sales");
+
+    // Missing arguments are left visible rather than dropped
+    assert_eq!(
+        template.render(Locale::En, &TemplateArgs::new()),
+        "{who} made # sales"
+    );
+}
+
+#[test]
+fn test_template_errors() {
+    for source in [
+        "{count, plural, one {# sale}}",
+        "{count, select, a {x} other {y}}",
+        "{count, plural, few {x} other {y}}",
+        "{count",
+        "{}",
+        "stray }",
+    ] {
+        assert!(
+            matches!(
+                Template::parse(source),
+                Err(DiscordError::InvalidTemplate(_))
+            ),
+            "{source} should not parse"
+        );
+    }
+}
+
+#[test]
+fn test_templates_fall_back_to_english() {
+    let templates = Templates::empty()
+        .with(Locale::En, "floor", "Floor: {price} ₳")
+        .unwrap()
+        .with(Locale::Ja, "floor", "フロア: {price} ₳")
+        .unwrap();
+    let args = TemplateArgs::new().text("price", "42");
+
+    assert_eq!(
+        templates.render(Locale::Ja, "floor", &args).unwrap(),
+        "フロア: 42 ₳"
+    );
+    assert_eq!(
+        templates.render(Locale::Es, "floor", &args).unwrap(),
+        "Floor: 42 ₳"
+    );
+    assert_eq!(templates.render(Locale::En, "missing", &args), None);
+}
@@ -0,0 +1,159 @@
+use discord_client::permissions::{
+    compute_permissions, permission_names, required_permissions, ChannelPermissions,
+};
+use discord_client::{AttachmentInput, DiscordMessage};
+use twilight_model::channel::permission_overwrite::{PermissionOverwrite, PermissionOverwriteType};
+use twilight_model::guild::Permissions;
+use twilight_model::id::marker::{GuildMarker, RoleMarker, UserMarker};
+use twilight_model::id::Id;
+use twilight_util::builder::embed::EmbedBuilder;
+
+const GUILD: Id<GuildMarker> = Id::new(100);
+const BOT: Id<UserMarker> = Id::new(200);
+const BOT_ROLE: Id<RoleMarker> = Id::new(300);
+const OTHER_ROLE: Id<RoleMarker> = Id::new(301);
+
+fn everyone() -> Id<RoleMarker> {
+    GUILD.cast()
+}
+
+fn text_permissions() -> Permissions {
+    Permissions::VIEW_CHANNEL
+        | Permissions::SEND_MESSAGES
+        | Permissions::EMBED_LINKS
+        | Permissions::ATTACH_FILES
+}
+
+fn roles() -> Vec<(Id<RoleMarker>, Permissions)> {
+    vec![
+        (everyone(), text_permissions()),
+        (BOT_ROLE, Permissions::ADD_REACTIONS),
+        (OTHER_ROLE, Permissions::MANAGE_MESSAGES),
+    ]
+}
+
+fn role_overwrite(
+    id: Id<RoleMarker>,
+    allow: Permissions,
+    deny: Permissions,
+) -> PermissionOverwrite {
+    PermissionOverwrite {
+        allow,
+        deny,
+        id: id.cast(),
+        kind: PermissionOverwriteType::Role,
+    }
+}
+
+fn resolve(overwrites: &[PermissionOverwrite]) -> Permissions {
+    compute_permissions(GUILD, BOT, &[BOT_ROLE], &roles(), overwrites, false)
+}
+
+fn message() -> DiscordMessage {
+    DiscordMessage {
+        content: Some("New sale".to_string()),
+        embeds: Some(vec![EmbedBuilder::new().title("Pirate #1086").build()]),
+        attachments: None,
+        components: None,
+    }
+}
+
+#[test]
+fn test_roles_without_overwrites() {
+    let permissions = resolve(&[]);
+    assert!(permissions.contains(text_permissions() | Permissions::ADD_REACTIONS));
+    // Not one of the bot's roles
+    assert!(!permissions.contains(Permissions::MANAGE_MESSAGES));
+}
+
+#[test]
+fn test_overwrite_order() {
+    // @everyone loses SEND_MESSAGES, the bot's role gets it back, and a
+    // member overwrite takes EMBED_LINKS away again
+    let overwrites = [
+        role_overwrite(everyone(), Permissions::empty(), Permissions::SEND_MESSAGES),
+        role_overwrite(BOT_ROLE, Permissions::SEND_MESSAGES, Permissions::empty()),
+        PermissionOverwrite {
+            allow: Permissions::empty(),
+            deny: Permissions::EMBED_LINKS,
+            id: BOT.cast(),
+            kind: PermissionOverwriteType::Member,
+        },
+        // Other roles' overwrites don't apply
+        role_overwrite(OTHER_ROLE, Permissions::empty(), Permissions::VIEW_CHANNEL),
+    ];
+    let permissions = resolve(&overwrites);
+    assert!(permissions.contains(Permissions::VIEW_CHANNEL | Permissions::SEND_MESSAGES));
+    assert!(!permissions.contains(Permissions::EMBED_LINKS));
+}
+
+#[test]
+fn test_implicit_denials() {
+    let hidden = [role_overwrite(
+        everyone(),
+        Permissions::empty(),
+        Permissions::VIEW_CHANNEL,
+    )];
+    assert!(resolve(&hidden).is_empty());
+
+    // Read-only channel: embeds and files go with SEND_MESSAGES
+    let read_only = [role_overwrite(
+        everyone(),
+        Permissions::empty(),
+        Permissions::SEND_MESSAGES,
+    )];
+    let permissions = resolve(&read_only);
+    assert!(permissions.contains(Permissions::VIEW_CHANNEL));
+    assert!(!permissions.intersects(Permissions::EMBED_LINKS | Permissions::ATTACH_FILES));
+}
+
+#[test]
+fn test_administrator_bypasses_overwrites() {
+    let roles = vec![(everyone(), Permissions::ADMINISTRATOR)];
+    let hidden = [role_overwrite(
+        everyone(),
+        Permissions::empty(),
+        Permissions::VIEW_CHANNEL,
+    )];
+    assert_eq!(
+        compute_permissions(GUILD, BOT, &[], &roles, &hidden, false),
+        Permissions::all()
+    );
+}
+
+#[test]
+fn test_missing_for_message() {
+    let message = message();
+    assert_eq!(
+        required_permissions(&message, false),
+        Permissions::VIEW_CHANNEL | Permissions::SEND_MESSAGES | Permissions::EMBED_LINKS
+    );
+
+    let channel = ChannelPermissions {
+        permissions: Permissions::VIEW_CHANNEL,
+        thread: false,
+    };
+    let missing = channel.missing_for(&message);
+    assert_eq!(permission_names(missing), ["SEND_MESSAGES", "EMBED_LINKS"]);
+
+    let with_file = DiscordMessage {
+        attachments: Some(vec![AttachmentInput {
+            id: "0".to_string(),
+            filename: "sale.png".to_string(),
+            description: None,
+            file_data: vec![1],
+        }]),
+        ..message
+    };
+    let thread = ChannelPermissions {
+        permissions: text_permissions(),
+        thread: true,
+    };
+    assert_eq!(
+        thread.missing_for(&with_file),
+        Permissions::SEND_MESSAGES_IN_THREADS
+    );
+    assert!(ChannelPermissions::direct()
+        .missing_for(&with_file)
+        .is_empty());
+}
@@ -0,0 +1,173 @@
+use discord_client::{
+    AttachmentInput, DiscordMessage, Locale, LocaleConfig, ScheduledMessage, ScheduledSender,
+    SchedulerConfig,
+};
+
+mod common;
+use common::RateLimitedClient;
+
+fn sale(content: &str) -> DiscordMessage {
+    DiscordMessage {
+        content: Some(content.to_string()),
+        embeds: None,
+        attachments: None,
+        components: None,
+    }
+}
+
+fn contents(due: &[(ScheduledMessage, usize)]) -> Vec<(&str, &str, usize)> {
+    due.iter()
+        .map(|(m, count)| {
+            (
+                m.channel_id.as_str(),
+                m.message.content.as_deref().unwrap_or_default(),
+                *count,
+            )
+        })
+        .collect()
+}
+
+#[test]
+fn test_take_due_coalesces_and_throttles() {
+    let mut sender = ScheduledSender::new(SchedulerConfig {
+        channel_interval_ms: 1_000,
+        coalesce_threshold: 3,
+        max_sends_per_drain: 10,
+    });
+    for n in 0..4 {
+        sender.push(
+            ScheduledMessage::new("sales", sale(&format!("sale {n}"))).coalesce("pirates", "sales"),
+        );
+    }
+    sender.push(ScheduledMessage::new("sales", sale("listing")));
+    sender.push(ScheduledMessage::new("alerts", sale("later")).not_before(5_000));
+    sender.push(ScheduledMessage::new("alerts", sale("now")));
+
+    let due = sender.take_due(0);
+    assert_eq!(
+        contents(&due),
+        [("sales", "**4 new sales**", 4), ("alerts", "now", 1)]
+    );
+    assert_eq!(sender.len(), 2);
+
+    // Each channel waits out its interval before the next send
+    sender.mark_sent("sales", 0);
+    sender.mark_sent("alerts", 0);
+    assert!(sender.take_due(500).is_empty());
+    assert_eq!(sender.next_due_ms(), Some(1_000));
+    assert_eq!(contents(&sender.take_due(1_000)), [("sales", "listing", 1)]);
+    assert_eq!(contents(&sender.take_due(5_000)), [("alerts", "later", 1)]);
+    assert!(sender.is_empty());
+}
+
+#[test]
+fn test_take_due_below_threshold_sends_one_at_a_time() {
+    let mut sender = ScheduledSender::default();
+    sender.push(ScheduledMessage::new("sales", sale("a")).coalesce("pirates", "sales"));
+    sender.push(ScheduledMessage::new("sales", sale("b")).coalesce("pirates", "sales"));
+
+    assert_eq!(contents(&sender.take_due(0)), [("sales", "a", 1)]);
+    assert_eq!(sender.len(), 1);
+}
+
+#[test]
+fn test_take_due_never_folds_attachments() {
+    let mut sender = ScheduledSender::default();
+    let mut with_file = sale("with file");
+    with_file.attachments = Some(vec![AttachmentInput {
+        id: "0".to_string(),
+        filename: "pirate.png".to_string(),
+        description: None,
+        file_data: vec![1, 2, 3],
+    }]);
+    sender.push(ScheduledMessage::new("sales", with_file).coalesce("pirates", "sales"));
+    for n in 0..3 {
+        sender.push(
+            ScheduledMessage::new("sales", sale(&format!("sale {n}"))).coalesce("pirates", "sales"),
+        );
+    }
+
+    let due = sender.take_due(0);
+    assert_eq!(contents(&due), [("sales", "with file", 1)]);
+    assert_eq!(due[0].0.message.attachments.as_ref().map(Vec::len), Some(1));
+
+    sender.mark_sent("sales", 0);
+    assert_eq!(
+        contents(&sender.take_due(10_000)),
+        [("sales", "**3 new sales**", 3)]
+    );
+}
+
+#[test]
+fn test_summary_uses_channel_locale() {
+    let locales = LocaleConfig::new(Locale::En)
+        .with_guild("jp-guild", Locale::Ja)
+        .with_channel("ventas", Locale::Es);
+    let mut sender = ScheduledSender::default().with_locales(locales);
+    for channel in ["ventas", "jp-sales", "en-sales"] {
+        for n in 0..3 {
+            let mut message = ScheduledMessage::new(channel, sale(&format!("sale {n}")))
+                .coalesce("pirates", "sales");
+            if channel != "en-sales" {
+                message = message.in_guild("jp-guild");
+            }
+            sender.push(message);
+        }
+    }
+    for _ in 0..3 {
+        sender.push(ScheduledMessage::new("offers", sale("o")).coalesce("pirates", "offers"));
+    }
+
+    assert_eq!(
+        contents(&sender.take_due(0)),
+        [
+            ("ventas", "**3 nuevas ventas**", 3),
+            ("jp-sales", "**新しい販売3件**", 3),
+            ("en-sales", "**3 new sales**", 3),
+            ("offers", "**3 new offers**", 3),
+        ]
+    );
+}
+
+#[tokio::test]
+async fn test_drain_defers_rate_limited_messages() {
+    let client = RateLimitedClient::always(1.5, true);
+    let mut sender = ScheduledSender::default();
+    sender.push(ScheduledMessage::new("sales", sale("a")));
+    sender.push(ScheduledMessage::new("alerts", sale("b")));
+    sender.push(ScheduledMessage::new("alerts", sale("c")));
+
+    let report = sender.drain(&client, 10_000).await;
+    assert_eq!(report.sent, 0);
+    assert!(report.failed.is_empty());
+    // A global limit stops the drain after the first attempt
+    assert_eq!(client.sends.borrow().as_slice(), ["sales"]);
+    assert_eq!(report.deferred.len(), 3);
+    assert!(report
+        .deferred
+        .iter()
+        .all(|m| m.not_before_ms == Some(11_500)));
+    assert!(sender.is_empty());
+}
+
+#[tokio::test]
+async fn test_drain_keeps_coalesce_on_deferred_messages() {
+    let client = RateLimitedClient::always(1.5, false);
+    let mut sender = ScheduledSender::default();
+    sender.push(
+        ScheduledMessage::new("sales", sale("a"))
+            .in_guild("pirates-guild")
+            .coalesce("pirates", "sales"),
+    );
+
+    let report = sender.drain(&client, 10_000).await;
+    let [deferred] = report.deferred.as_slice() else {
+        panic!("expected one deferred message");
+    };
+    assert_eq!(deferred.not_before_ms, Some(11_500));
+    assert_eq!(deferred.guild_id.as_deref(), Some("pirates-guild"));
+    assert_eq!(
+        deferred.coalesce.as_ref().map(|c| c.key.as_str()),
+        Some("pirates")
+    );
+}
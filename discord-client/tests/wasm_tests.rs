@@ -59,6 +59,7 @@ mod wasm_tests {
             content: Some("Test message from discord-client WASM".to_string()),
             embeds: None,
             attachments: None,
+            components: None,
         };
 
         let result = client.send_message(&channel_id, &message).await;
@@ -124,6 +125,7 @@ mod wasm_tests {
             content: Some("WASM message with embed".to_string()),
             embeds: Some(vec![embed]),
             attachments: None,
+            components: None,
         };
 
         let result = client.send_message(&channel_id, &message).await;
@@ -204,6 +206,7 @@ mod wasm_tests {
             content: Some("WASM message with attachment".to_string()),
             embeds: None,
             attachments: Some(vec![attachment]),
+            components: None,
         };
 
         let result = client.send_message(&channel_id, &message).await;
@@ -47,7 +47,7 @@ impl FilebaseApi {
     /// Make a request and return the parsed body.
     ///
     /// Uses `request_with_details` under the hood so that non-2xx responses
-    /// surface as `HttpError::HttpStatus` with headers intact — callers can
+    /// surface as `HttpError::Status` with headers intact — callers can
     /// inspect `Retry-After` on 429 via `HttpError::retry_after_seconds()`.
     pub async fn request<T: Serialize, R: DeserializeOwned>(
         &self,
@@ -0,0 +1,269 @@
+//! An [`HttpClient`] bound to one API's base URL
+//!
+//! API clients used to `format!` full URLs, which let double slashes and
+//! unescaped or dangling query strings slip through. [`BaseClient`] joins
+//! paths onto the base with exactly one `/`, and [`Query`] and
+//! [`path_segment`] percent-encode whatever the caller interpolates:
+//!
+//! ```
+//! use http_client::{path_segment, BaseClient, Query};
+//!
+//! let api = BaseClient::new("mainnet.gomaestro-api.org/v1/");
+//! assert_eq!(
+//!     api.url(&format!("/assets/{}", path_segment("abc def"))),
+//!     "https://mainnet.gomaestro-api.org/v1/assets/abc%20def"
+//! );
+//!
+//! let query = Query::new().push("count", 100).push_opt("cursor", None::<&str>);
+//! assert_eq!(
+//!     api.url_with_query("policy/abc/accounts", &query),
+//!     "https://mainnet.gomaestro-api.org/v1/policy/abc/accounts?count=100"
+//! );
+//! ```
+
+use std::fmt::{self, Write};
+
+use serde::{de::DeserializeOwned, Serialize};
+
+use crate::{HttpClient, HttpError, HttpMethod, ResponseDetails};
+
+/// An [`HttpClient`] that takes paths relative to a base URL
+#[derive(Clone)]
+pub struct BaseClient {
+    client: HttpClient,
+    base_url: String,
+}
+
+impl BaseClient {
+    /// Bind a default [`HttpClient`] to `base_url`
+    ///
+    /// `https://` is assumed when the base has no scheme, and trailing
+    /// slashes are dropped.
+    pub fn new(base_url: &str) -> Self {
+        Self::with_client(base_url, HttpClient::new())
+    }
+
+    /// Bind a preconfigured client (headers, fixtures, single-flight)
+    pub fn with_client(base_url: &str, client: HttpClient) -> Self {
+        let base_url = base_url.trim_end_matches('/');
+        let base_url = if base_url.contains("://") {
+            base_url.to_string()
+        } else {
+            format!("https://{base_url}")
+        };
+        Self { client, base_url }
+    }
+
+    /// Swap the underlying client, keeping the base URL
+    pub fn with_http_client(mut self, client: HttpClient) -> Self {
+        self.client = client;
+        self
+    }
+
+    /// Point at another base URL, keeping the client
+    pub fn with_base_url(self, base_url: &str) -> Self {
+        Self::with_client(base_url, self.client)
+    }
+
+    /// Add a default header to the underlying client
+    pub fn with_header(mut self, key: &str, value: &str) -> Self {
+        self.client = self.client.with_header(key, value);
+        self
+    }
+
+    /// Normalized base URL, with scheme and without a trailing slash
+    pub fn base_url(&self) -> &str {
+        &self.base_url
+    }
+
+    pub fn client(&self) -> &HttpClient {
+        &self.client
+    }
+
+    /// Full URL for `path`; leading slashes on `path` are optional
+    pub fn url(&self, path: &str) -> String {
+        let path = path.trim_start_matches('/');
+        if path.is_empty() {
+            self.base_url.clone()
+        } else {
+            format!("{}/{path}", self.base_url)
+        }
+    }
+
+    /// Full URL for `path` with `query` appended; an empty query adds nothing
+    pub fn url_with_query(&self, path: &str, query: &Query) -> String {
+        let url = self.url(path);
+        if query.is_empty() {
+            url
+        } else {
+            let separator = if url.contains('?') { '&' } else { '?' };
+            format!("{url}{separator}{query}")
+        }
+    }
+
+    /// Path relative to the base, if `url` is one of this client's URLs
+    pub fn path_of<'a>(&self, url: &'a str) -> Option<&'a str> {
+        url.strip_prefix(self.base_url.as_str())
+    }
+
+    pub async fn get_path<R: DeserializeOwned>(&self, path: &str) -> Result<R, HttpError> {
+        self.client.get(&self.url(path)).await
+    }
+
+    pub async fn get_path_with_query<R: DeserializeOwned>(
+        &self,
+        path: &str,
+        query: &Query,
+    ) -> Result<R, HttpError> {
+        self.client.get(&self.url_with_query(path, query)).await
+    }
+
+    pub async fn get_path_with_details<R: DeserializeOwned>(
+        &self,
+        path: &str,
+    ) -> Result<ResponseDetails<R>, HttpError> {
+        self.client.get_with_details(&self.url(path)).await
+    }
+
+    pub async fn post_path<T: Serialize, R: DeserializeOwned>(
+        &self,
+        path: &str,
+        body: &T,
+    ) -> Result<R, HttpError> {
+        self.client.post(&self.url(path), body).await
+    }
+
+    pub async fn post_path_with_details<T: Serialize, R: DeserializeOwned>(
+        &self,
+        path: &str,
+        body: &T,
+    ) -> Result<ResponseDetails<R>, HttpError> {
+        self.client.post_with_details(&self.url(path), body).await
+    }
+
+    /// See [`HttpClient::request_text_with_details`]
+    pub async fn request_path_text_with_details<T: Serialize>(
+        &self,
+        method: HttpMethod,
+        path: &str,
+        body: Option<&T>,
+    ) -> Result<ResponseDetails<String>, HttpError> {
+        self.client
+            .request_text_with_details(method, &self.url(path), body)
+            .await
+    }
+}
+
+/// Query string parameters, percent-encoded when displayed
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Query(Vec<(String, String)>);
+
+impl Query {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn push(mut self, key: &str, value: impl fmt::Display) -> Self {
+        self.0.push((key.to_string(), value.to_string()));
+        self
+    }
+
+    /// Push `value` if present
+    pub fn push_opt(self, key: &str, value: Option<impl fmt::Display>) -> Self {
+        match value {
+            Some(value) => self.push(key, value),
+            None => self,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl fmt::Display for Query {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, (key, value)) in self.0.iter().enumerate() {
+            if i > 0 {
+                f.write_char('&')?;
+            }
+            write!(f, "{}={}", path_segment(key), path_segment(value))?;
+        }
+        Ok(())
+    }
+}
+
+/// Percent-encode `s` for use as one path segment or query component
+///
+/// Everything but RFC 3986 unreserved characters is escaped, including `/`.
+pub fn path_segment(s: &str) -> String {
+    let mut encoded = String::with_capacity(s.len());
+    for byte in s.bytes() {
+        if byte.is_ascii_alphanumeric() || matches!(byte, b'-' | b'.' | b'_' | b'~') {
+            encoded.push(byte as char);
+        } else {
+            let _ = write!(encoded, "%{byte:02X}");
+        }
+    }
+    encoded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_url_joining() {
+        for base in [
+            "api.example.com/v1",
+            "api.example.com/v1/",
+            "https://api.example.com/v1//",
+        ] {
+            let api = BaseClient::new(base);
+            assert_eq!(api.base_url(), "https://api.example.com/v1");
+            assert_eq!(api.url("/assets/1"), "https://api.example.com/v1/assets/1");
+            assert_eq!(api.url("assets/1"), "https://api.example.com/v1/assets/1");
+            assert_eq!(api.url(""), "https://api.example.com/v1");
+        }
+        assert_eq!(
+            BaseClient::new("http://localhost:8080").url("/health"),
+            "http://localhost:8080/health"
+        );
+    }
+
+    #[test]
+    fn test_query_encoding() {
+        let api = BaseClient::new("api.example.com");
+        let query = Query::new()
+            .push("cursor", "a b&c=d/e")
+            .push_opt("count", Some(100))
+            .push_opt("order", None::<&str>);
+        assert_eq!(query.to_string(), "cursor=a%20b%26c%3Dd%2Fe&count=100");
+        assert_eq!(
+            api.url_with_query("/search?q=x", &query),
+            "https://api.example.com/search?q=x&cursor=a%20b%26c%3Dd%2Fe&count=100"
+        );
+        assert_eq!(
+            api.url_with_query("/search", &Query::new()),
+            "https://api.example.com/search"
+        );
+    }
+
+    #[test]
+    fn test_path_segment() {
+        assert_eq!(path_segment("addr1qx-_.~"), "addr1qx-_.~");
+        assert_eq!(path_segment("../admin"), "..%2Fadmin");
+        assert_eq!(path_segment("Pirate #1"), "Pirate%20%231");
+        assert_eq!(path_segment("é"), "%C3%A9");
+    }
+
+    #[test]
+    fn test_path_of() {
+        let api = BaseClient::new("api.example.com/v1");
+        assert_eq!(
+            api.path_of("https://api.example.com/v1/assets/1"),
+            Some("/assets/1")
+        );
+        assert_eq!(api.path_of("https://other.example.com/assets/1"), None);
+    }
+}
@@ -0,0 +1,147 @@
+//! Cloudflare edge cache options for GETs made from Workers
+//!
+//! Idempotent fetches (asset metadata, IPFS gateways) can be served from the
+//! colo's cache instead of the origin. [`CacheOptions`] carries the fetch
+//! `cache` mode and the Workers-only `cf` request properties:
+//!
+//! ```
+//! use http_client::{CacheOptions, HttpClient};
+//!
+//! let gateway = CacheOptions::new()
+//!     .ttl(86_400)
+//!     .ttl_for_status("404", 60)
+//!     .cache_everything();
+//! let client = HttpClient::new().with_cache(gateway);
+//! ```
+//!
+//! The options only apply to GETs, and only on wasm; the native backend
+//! accepts and ignores them so the same code runs in tests and CLIs.
+
+use std::collections::BTreeMap;
+
+use serde::Serialize;
+use serde_json::Value;
+
+/// fetch `cache` mode; Workers supports only these two
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CacheMode {
+    /// Skip the cache entirely, reading and writing
+    NoStore,
+    /// Revalidate with the origin before using a cached response
+    NoCache,
+}
+
+impl CacheMode {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CacheMode::NoStore => "no-store",
+            CacheMode::NoCache => "no-cache",
+        }
+    }
+}
+
+/// Per-request cache settings, sent as the `cf` request properties
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CacheOptions {
+    #[serde(skip)]
+    mode: Option<CacheMode>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    cache_ttl: Option<u32>,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    cache_everything: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    cache_key: Option<String>,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    cache_ttl_by_status: BTreeMap<String, i32>,
+    #[serde(flatten)]
+    extra: serde_json::Map<String, Value>,
+}
+
+impl CacheOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the fetch `cache` mode
+    pub fn mode(mut self, mode: CacheMode) -> Self {
+        self.mode = Some(mode);
+        self
+    }
+
+    /// Cache responses for `seconds`, overriding the origin's headers
+    /// (`cf.cacheTtl`)
+    pub fn ttl(mut self, seconds: u32) -> Self {
+        self.cache_ttl = Some(seconds);
+        self
+    }
+
+    /// Cache responses with status in `statuses` (e.g. `"200-299"`, `"404"`)
+    /// for `seconds`; zero or negative means don't cache them
+    /// (`cf.cacheTtlByStatus`)
+    pub fn ttl_for_status(mut self, statuses: &str, seconds: i32) -> Self {
+        self.cache_ttl_by_status
+            .insert(statuses.to_string(), seconds);
+        self
+    }
+
+    /// Cache every content type, not just static assets — needed for JSON
+    /// (`cf.cacheEverything`)
+    pub fn cache_everything(mut self) -> Self {
+        self.cache_everything = true;
+        self
+    }
+
+    /// Cache under `key` instead of the URL, e.g. to share entries between
+    /// IPFS gateways (`cf.cacheKey`)
+    pub fn cache_key(mut self, key: &str) -> Self {
+        self.cache_key = Some(key.to_string());
+        self
+    }
+
+    /// Set any other `cf` request property (`polish`, `resolveOverride`, ...)
+    pub fn cf(mut self, key: &str, value: impl Into<Value>) -> Self {
+        self.extra.insert(key.to_string(), value.into());
+        self
+    }
+
+    pub fn cache_mode(&self) -> Option<CacheMode> {
+        self.mode
+    }
+
+    /// The `cf` object as sent with the request
+    pub fn cf_properties(&self) -> Value {
+        serde_json::to_value(self).unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_cf_properties() {
+        let options = CacheOptions::new()
+            .ttl(3600)
+            .ttl_for_status("200-299", 86_400)
+            .ttl_for_status("500-599", 0)
+            .cache_everything()
+            .cache_key("ipfs:bafy")
+            .cf("polish", "lossless")
+            .mode(CacheMode::NoCache);
+
+        assert_eq!(
+            options.cf_properties(),
+            json!({
+                "cacheTtl": 3600,
+                "cacheEverything": true,
+                "cacheKey": "ipfs:bafy",
+                "cacheTtlByStatus": {"200-299": 86400, "500-599": 0},
+                "polish": "lossless",
+            })
+        );
+        assert_eq!(options.cache_mode().map(|m| m.as_str()), Some("no-cache"));
+        assert_eq!(CacheOptions::new().cf_properties(), json!({}));
+    }
+}
@@ -0,0 +1,234 @@
+//! Response size guards and body decompression
+//!
+//! The native backend negotiates and decodes through reqwest's `gzip`,
+//! `deflate` and `brotli` features. On wasm, bodies are read as raw bytes and
+//! decoded here, with pure-Rust decoders so they compile for `wasm32`.
+//!
+//! Some runtimes (including Workers' `fetch`) already decode the body but
+//! leave `Content-Encoding` in place, so gzip and zlib bodies are only decoded
+//! when they carry the format's magic bytes, and raw deflate or brotli bodies
+//! that are already valid UTF-8 are passed through as-is.
+
+use crate::HttpError;
+
+/// Default cap on a (decompressed) response body: 64 MiB
+pub const DEFAULT_MAX_RESPONSE_BYTES: u64 = 64 * 1024 * 1024;
+
+/// `Accept-Encoding` value for the enabled decoders, if any
+#[cfg_attr(not(target_arch = "wasm32"), allow(dead_code))]
+pub(crate) fn accept_encoding() -> Option<String> {
+    let encodings: Vec<&str> = [
+        (cfg!(feature = "gzip"), "gzip"),
+        (cfg!(feature = "deflate"), "deflate"),
+        (cfg!(feature = "brotli"), "br"),
+    ]
+    .into_iter()
+    .filter_map(|(enabled, name)| enabled.then_some(name))
+    .collect();
+
+    (!encodings.is_empty()).then(|| encodings.join(", "))
+}
+
+/// Reject a response whose declared `Content-Length` exceeds `limit`
+pub(crate) fn check_content_length(length: Option<u64>, limit: u64) -> Result<(), HttpError> {
+    match length {
+        Some(size) if size > limit => Err(HttpError::ResponseTooLarge {
+            limit,
+            size: Some(size),
+        }),
+        _ => Ok(()),
+    }
+}
+
+/// Reject a body that has grown past `limit`
+pub(crate) fn check_body_size(size: usize, limit: u64) -> Result<(), HttpError> {
+    if size as u64 > limit {
+        return Err(HttpError::ResponseTooLarge { limit, size: None });
+    }
+    Ok(())
+}
+
+/// Decode a response body, if this client sent an `Accept-Encoding`
+///
+/// Without one the runtime negotiated the encoding itself and has already
+/// decoded the body, so `Content-Encoding` is left over and is ignored.
+#[cfg_attr(not(target_arch = "wasm32"), allow(dead_code))]
+pub(crate) fn decode_response(
+    content_encoding: Option<&str>,
+    body: Vec<u8>,
+    limit: u64,
+) -> Result<Vec<u8>, HttpError> {
+    match accept_encoding() {
+        Some(_) => decode(content_encoding, body, limit),
+        None => Ok(body),
+    }
+}
+
+/// Decode `body` according to a `Content-Encoding` header value
+///
+/// Multiple codings are undone in reverse order of application. The decoded
+/// size is capped at `limit` so a small compressed body can't expand without
+/// bound.
+#[cfg_attr(not(target_arch = "wasm32"), allow(dead_code))]
+pub(crate) fn decode(
+    content_encoding: Option<&str>,
+    body: Vec<u8>,
+    limit: u64,
+) -> Result<Vec<u8>, HttpError> {
+    let Some(content_encoding) = content_encoding else {
+        return Ok(body);
+    };
+
+    let mut body = body;
+    for coding in content_encoding.rsplit(',').map(str::trim) {
+        body = match coding.to_ascii_lowercase().as_str() {
+            "" | "identity" => body,
+            #[cfg(feature = "gzip")]
+            "gzip" | "x-gzip" if body.starts_with(&[0x1f, 0x8b]) => {
+                read_limited(flate2::read::MultiGzDecoder::new(body.as_slice()), limit)?
+            }
+            #[cfg(feature = "deflate")]
+            "deflate" if is_zlib(&body) => {
+                read_limited(flate2::read::ZlibDecoder::new(body.as_slice()), limit)?
+            }
+            // Compressed streams are effectively never valid UTF-8
+            #[cfg(any(feature = "deflate", feature = "brotli"))]
+            "deflate" | "br" if std::str::from_utf8(&body).is_ok() => body,
+            #[cfg(feature = "deflate")]
+            "deflate" => read_limited(flate2::read::DeflateDecoder::new(body.as_slice()), limit)?,
+            #[cfg(feature = "brotli")]
+            "br" => read_limited(
+                brotli_decompressor::Decompressor::new(body.as_slice(), 4096),
+                limit,
+            )?,
+            // Already decoded upstream (no magic bytes)
+            #[cfg(feature = "gzip")]
+            "gzip" | "x-gzip" => body,
+            other => {
+                return Err(HttpError::Decompression(format!(
+                    "unsupported content-encoding: {other}"
+                )))
+            }
+        };
+    }
+
+    Ok(body)
+}
+
+/// zlib header: deflate method and a valid header checksum
+#[cfg(feature = "deflate")]
+fn is_zlib(body: &[u8]) -> bool {
+    match body {
+        [cmf, flg, ..] => cmf & 0x0f == 8 && ((u16::from(*cmf) << 8) | u16::from(*flg)) % 31 == 0,
+        _ => false,
+    }
+}
+
+#[cfg(any(feature = "gzip", feature = "deflate", feature = "brotli"))]
+fn read_limited(reader: impl std::io::Read, limit: u64) -> Result<Vec<u8>, HttpError> {
+    use std::io::Read;
+
+    let mut decoded = Vec::new();
+    reader
+        .take(limit.saturating_add(1))
+        .read_to_end(&mut decoded)
+        .map_err(|e| HttpError::Decompression(e.to_string()))?;
+    check_body_size(decoded.len(), limit)?;
+    Ok(decoded)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const JSON: &[u8] =
+        br#"{"policy_id":"b3dab69f7e6100849434fb1781e34bd12a916557f6231b8d2629b6f6"}"#;
+
+    #[test]
+    fn test_identity_and_missing_encoding() {
+        assert_eq!(decode(None, JSON.to_vec(), 1024).unwrap(), JSON);
+        assert_eq!(decode(Some("identity"), JSON.to_vec(), 1024).unwrap(), JSON);
+    }
+
+    #[test]
+    fn test_content_length_guard() {
+        assert!(check_content_length(None, 10).is_ok());
+        assert!(check_content_length(Some(10), 10).is_ok());
+        assert!(matches!(
+            check_content_length(Some(11), 10),
+            Err(HttpError::ResponseTooLarge {
+                limit: 10,
+                size: Some(11)
+            })
+        ));
+    }
+
+    #[cfg(not(any(feature = "gzip", feature = "deflate", feature = "brotli")))]
+    #[test]
+    fn test_no_decoders() {
+        assert_eq!(accept_encoding(), None);
+        assert!(matches!(
+            decode(Some("gzip"), JSON.to_vec(), 1024),
+            Err(HttpError::Decompression(_))
+        ));
+        // Nothing was asked for, so the runtime already decoded the body
+        for encoding in ["gzip", "x-gzip", "deflate", "br"] {
+            assert_eq!(
+                decode_response(Some(encoding), JSON.to_vec(), 1024).unwrap(),
+                JSON
+            );
+        }
+    }
+
+    #[cfg(feature = "gzip")]
+    #[test]
+    fn test_gzip() {
+        use flate2::{write::GzEncoder, Compression};
+        use std::io::Write;
+
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(JSON).unwrap();
+        let compressed = encoder.finish().unwrap();
+
+        assert!(accept_encoding().unwrap().contains("gzip"));
+        assert_eq!(
+            decode(Some("gzip"), compressed.clone(), 1024).unwrap(),
+            JSON
+        );
+        // Already decoded by the runtime
+        assert_eq!(decode(Some("gzip"), JSON.to_vec(), 1024).unwrap(), JSON);
+        // Decoded size is capped
+        assert!(matches!(
+            decode(Some("gzip"), compressed, 16),
+            Err(HttpError::ResponseTooLarge { limit: 16, .. })
+        ));
+    }
+
+    #[cfg(feature = "deflate")]
+    #[test]
+    fn test_deflate() {
+        use flate2::{write::DeflateEncoder, write::ZlibEncoder, Compression};
+        use std::io::Write;
+
+        let mut zlib = ZlibEncoder::new(Vec::new(), Compression::default());
+        zlib.write_all(JSON).unwrap();
+        assert_eq!(
+            decode(Some("deflate"), zlib.finish().unwrap(), 1024).unwrap(),
+            JSON
+        );
+
+        let mut raw = DeflateEncoder::new(Vec::new(), Compression::default());
+        raw.write_all(JSON).unwrap();
+        assert_eq!(
+            decode(Some("deflate"), raw.finish().unwrap(), 1024).unwrap(),
+            JSON
+        );
+    }
+
+    #[cfg(feature = "brotli")]
+    #[test]
+    fn test_brotli_passthrough_when_already_decoded() {
+        assert!(accept_encoding().unwrap().contains("br"));
+        assert_eq!(decode(Some("br"), JSON.to_vec(), 1024).unwrap(), JSON);
+    }
+}
@@ -1,84 +1,183 @@
 use std::{collections::HashMap, fmt};
 
+/// Errors from [`HttpClient`](crate::HttpClient), the same on native and wasm
+///
+/// Backend errors (reqwest, gloo-net) are classified into these variants so
+/// callers can branch on the failure without matching error strings.
 #[derive(Debug)]
 pub enum HttpError {
-    #[cfg(not(target_arch = "wasm32"))]
-    Reqwest(reqwest::Error),
-    #[cfg(target_arch = "wasm32")]
-    Gloo(gloo_net::Error),
-    Serialization(serde_json::Error),
-    Custom(String),
-    /// HTTP error with response details (status code, headers, body)
-    /// Useful for handling rate limits and other non-2xx responses
-    HttpStatus {
-        status_code: u16,
+    /// Non-2xx response, with headers for rate limit handling
+    Status {
+        code: u16,
         headers: HashMap<String, String>,
         body: String,
     },
+    /// No response within the request timeout
+    Timeout(String),
+    /// Connection, DNS, TLS or fetch failure before a response arrived
+    Network(String),
+    /// JSON request or response body couldn't be (de)serialized
+    Decode { source: serde_json::Error },
+    /// Invalid client or request configuration (URL, proxy, certificate)
+    Builder(String),
+    /// Response body exceeded the client's size limit. `size` is the declared
+    /// `Content-Length` when the response was rejected before reading
+    ResponseTooLarge { limit: u64, size: Option<u64> },
+    /// Response body couldn't be decoded per its `Content-Encoding`
+    Decompression(String),
+    /// Fixture file missing or unwritable
+    Fixture(String),
 }
 
 impl fmt::Display for HttpError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
-            #[cfg(not(target_arch = "wasm32"))]
-            HttpError::Reqwest(e) => write!(f, "HTTP request error: {e}"),
-            #[cfg(target_arch = "wasm32")]
-            HttpError::Gloo(e) => write!(f, "HTTP request error: {e}"),
-            HttpError::Serialization(e) => write!(f, "JSON serialization error: {e}"),
-            HttpError::Custom(e) => write!(f, "Custom HTTP error: {e}"),
-            HttpError::HttpStatus { status_code, .. } => {
-                write!(f, "HTTP request failed with status: {status_code}")
-            }
+            HttpError::Status { code, .. } => write!(f, "HTTP request failed with status: {code}"),
+            HttpError::Timeout(e) => write!(f, "HTTP request timed out: {e}"),
+            HttpError::Network(e) => write!(f, "HTTP request error: {e}"),
+            HttpError::Decode { source } => write!(f, "JSON decode error: {source}"),
+            HttpError::Builder(e) => write!(f, "Invalid HTTP request: {e}"),
+            HttpError::ResponseTooLarge { limit, size } => match size {
+                Some(size) => write!(f, "Response of {size} bytes exceeds limit of {limit}"),
+                None => write!(f, "Response exceeds limit of {limit} bytes"),
+            },
+            HttpError::Decompression(e) => write!(f, "Response decompression error: {e}"),
+            HttpError::Fixture(e) => write!(f, "Fixture error: {e}"),
         }
     }
 }
 
-impl std::error::Error for HttpError {}
+impl std::error::Error for HttpError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            HttpError::Decode { source } => Some(source),
+            _ => None,
+        }
+    }
+}
 
 impl HttpError {
-    /// Extract retry-after header value (in seconds) if this is an HttpStatus error
+    /// Extract retry-after header value (in seconds) if this is a Status error
     pub fn retry_after_seconds(&self) -> Option<u64> {
+        self.headers()?
+            .iter()
+            .find(|(k, _)| k.eq_ignore_ascii_case("retry-after"))
+            .and_then(|(_, v)| v.trim().parse::<u64>().ok())
+    }
+
+    /// Get status code if this is a Status error
+    pub fn status_code(&self) -> Option<u16> {
         match self {
-            HttpError::HttpStatus { headers, .. } => headers
-                .get("retry-after")
-                .and_then(|v| v.parse::<u64>().ok()),
+            HttpError::Status { code, .. } => Some(*code),
             _ => None,
         }
     }
 
-    /// Get status code if this is an HttpStatus error
-    pub fn status_code(&self) -> Option<u16> {
+    /// Get all headers if this is a Status error
+    pub fn headers(&self) -> Option<&HashMap<String, String>> {
         match self {
-            HttpError::HttpStatus { status_code, .. } => Some(*status_code),
+            HttpError::Status { headers, .. } => Some(headers),
             _ => None,
         }
     }
 
-    /// Get all headers if this is an HttpStatus error
-    pub fn headers(&self) -> Option<&HashMap<String, String>> {
+    /// Get the response body if this is a Status error
+    pub fn body(&self) -> Option<&str> {
         match self {
-            HttpError::HttpStatus { headers, .. } => Some(headers),
+            HttpError::Status { body, .. } => Some(body),
             _ => None,
         }
     }
+
+    /// Whether the same request may succeed if retried: timeouts, network
+    /// failures, 408, 429 and 5xx
+    pub fn is_transient(&self) -> bool {
+        match self {
+            HttpError::Timeout(_) | HttpError::Network(_) => true,
+            HttpError::Status { code, .. } => matches!(code, 408 | 429 | 500..=599),
+            _ => false,
+        }
+    }
 }
 
 #[cfg(not(target_arch = "wasm32"))]
 impl From<reqwest::Error> for HttpError {
     fn from(e: reqwest::Error) -> Self {
-        HttpError::Reqwest(e)
+        if e.is_timeout() {
+            HttpError::Timeout(e.to_string())
+        } else if e.is_builder() {
+            HttpError::Builder(e.to_string())
+        } else if e.is_decode() {
+            HttpError::Decompression(e.to_string())
+        } else if let Some(status) = e.status() {
+            HttpError::Status {
+                code: status.as_u16(),
+                headers: HashMap::new(),
+                body: String::new(),
+            }
+        } else {
+            HttpError::Network(e.to_string())
+        }
     }
 }
 
 #[cfg(target_arch = "wasm32")]
 impl From<gloo_net::Error> for HttpError {
     fn from(e: gloo_net::Error) -> Self {
-        HttpError::Gloo(e)
+        match e {
+            gloo_net::Error::SerdeError(source) => HttpError::Decode { source },
+            // fetch rejects with these DOMException names when aborted by a timeout signal
+            gloo_net::Error::JsError(js)
+                if js.name == "TimeoutError" || js.name == "AbortError" =>
+            {
+                HttpError::Timeout(js.message)
+            }
+            other => HttpError::Network(other.to_string()),
+        }
     }
 }
 
 impl From<serde_json::Error> for HttpError {
-    fn from(e: serde_json::Error) -> Self {
-        HttpError::Serialization(e)
+    fn from(source: serde_json::Error) -> Self {
+        HttpError::Decode { source }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn status(code: u16, headers: &[(&str, &str)]) -> HttpError {
+        HttpError::Status {
+            code,
+            headers: headers
+                .iter()
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect(),
+            body: "{\"error\":\"slow down\"}".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_status_accessors() {
+        let err = status(429, &[("Retry-After", "30")]);
+        assert_eq!(err.status_code(), Some(429));
+        assert_eq!(err.retry_after_seconds(), Some(30));
+        assert_eq!(err.body(), Some("{\"error\":\"slow down\"}"));
+        assert!(err.is_transient());
+
+        assert!(!status(404, &[]).is_transient());
+        assert!(status(503, &[]).is_transient());
+        assert_eq!(HttpError::Timeout("30s".to_string()).status_code(), None);
+    }
+
+    #[test]
+    fn test_decode_source() {
+        use std::error::Error;
+
+        let err: HttpError = serde_json::from_str::<u64>("\"nope\"").unwrap_err().into();
+        assert!(matches!(err, HttpError::Decode { .. }));
+        assert!(err.source().is_some());
+        assert!(!err.is_transient());
     }
 }
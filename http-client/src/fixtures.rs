@@ -0,0 +1,205 @@
+//! Record/replay HTTP fixtures for deterministic tests (native only)
+//!
+//! With a [`FixtureMode`] attached via [`HttpClient::with_fixtures`], JSON and
+//! text requests are either:
+//!
+//! - **Recorded** — sent for real, with the response (status, headers, body)
+//!   written to a JSON fixture file, or
+//! - **Replayed** — served from the fixture file without touching the network.
+//!   A missing fixture is an error rather than a silent live request.
+//!
+//! Fixture files are named from the method, URL path, and a stable hash of the
+//! method, full URL, and request body, so distinct requests never collide and
+//! re-recording overwrites in place. Binary downloads
+//! ([`HttpClient::get_bytes_with_details`]) bypass fixtures.
+//!
+//! [`HttpClient::with_fixtures`]: crate::HttpClient::with_fixtures
+//! [`HttpClient::get_bytes_with_details`]: crate::HttpClient::get_bytes_with_details
+
+use crate::{HttpError, HttpMethod, ResponseDetails};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use tracing::debug;
+
+/// Whether fixtures are captured from live responses or served from disk
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FixtureMode {
+    /// Send requests for real and write responses into this directory
+    Record(PathBuf),
+    /// Serve responses from this directory, failing on missing fixtures
+    Replay(PathBuf),
+}
+
+impl FixtureMode {
+    pub fn dir(&self) -> &Path {
+        match self {
+            FixtureMode::Record(dir) | FixtureMode::Replay(dir) => dir,
+        }
+    }
+}
+
+/// On-disk fixture format
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Fixture {
+    pub method: String,
+    pub url: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub request_body: Option<serde_json::Value>,
+    pub status_code: u16,
+    #[serde(default)]
+    pub headers: HashMap<String, String>,
+    pub body: String,
+}
+
+impl Fixture {
+    fn into_details(self) -> ResponseDetails<String> {
+        ResponseDetails {
+            data: self.body,
+            status_code: self.status_code,
+            headers: self.headers,
+        }
+    }
+}
+
+/// File name for a request: `<method>_<path-slug>_<hash>.json`
+pub fn fixture_file_name(method: &HttpMethod, url: &str, body: Option<&str>) -> String {
+    let path = url
+        .split_once("://")
+        .map_or(url, |(_, rest)| rest)
+        .split(['?', '#'])
+        .next()
+        .unwrap_or("");
+    let slug: String = path
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect::<String>()
+        .split('_')
+        .filter(|s| !s.is_empty())
+        .collect::<Vec<_>>()
+        .join("_");
+    let slug: String = slug.chars().take(80).collect();
+
+    let method = format!("{method:?}");
+    let hash = fnv1a64(&[
+        method.as_bytes(),
+        url.as_bytes(),
+        body.unwrap_or("").as_bytes(),
+    ]);
+
+    format!("{}_{slug}_{hash:016x}.json", method.to_lowercase())
+}
+
+/// FNV-1a, used instead of `DefaultHasher` so names stay stable across Rust releases
+fn fnv1a64(parts: &[&[u8]]) -> u64 {
+    let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+    for part in parts {
+        for byte in *part {
+            hash ^= u64::from(*byte);
+            hash = hash.wrapping_mul(0x0000_0100_0000_01b3);
+        }
+        // Separator so ("ab", "c") and ("a", "bc") hash differently
+        hash ^= 0xff;
+        hash = hash.wrapping_mul(0x0000_0100_0000_01b3);
+    }
+    hash
+}
+
+pub(crate) async fn request_text<T: Serialize>(
+    mode: &FixtureMode,
+    client: &reqwest::Client,
+    default_headers: &HashMap<String, String>,
+    method: HttpMethod,
+    url: &str,
+    body: Option<&T>,
+    max_response_bytes: u64,
+) -> Result<ResponseDetails<String>, HttpError> {
+    let body_json = body.map(serde_json::to_value).transpose()?;
+    let body_string = body_json.as_ref().map(|v| v.to_string());
+    let path = mode
+        .dir()
+        .join(fixture_file_name(&method, url, body_string.as_deref()));
+
+    match mode {
+        FixtureMode::Replay(_) => {
+            debug!("Replaying fixture {}", path.display());
+            let contents = std::fs::read_to_string(&path).map_err(|e| {
+                HttpError::Fixture(format!(
+                    "Missing fixture for {method:?} {url} ({}): {e}. Re-run with fixtures in record mode.",
+                    path.display()
+                ))
+            })?;
+            let fixture: Fixture = serde_json::from_str(&contents)?;
+            Ok(fixture.into_details())
+        }
+        FixtureMode::Record(dir) => {
+            let method_name = format!("{method:?}");
+            let details = crate::native::make_request_text_with_details(
+                client,
+                default_headers,
+                method,
+                url,
+                body,
+                max_response_bytes,
+            )
+            .await?;
+
+            let fixture = Fixture {
+                method: method_name,
+                url: url.to_string(),
+                request_body: body_json,
+                status_code: details.status_code,
+                headers: details.headers.clone(),
+                body: details.data.clone(),
+            };
+
+            std::fs::create_dir_all(dir)
+                .and_then(|_| {
+                    let json =
+                        serde_json::to_string_pretty(&fixture).map_err(std::io::Error::other)?;
+                    std::fs::write(&path, json)
+                })
+                .map_err(|e| {
+                    HttpError::Fixture(format!("Failed to write fixture {}: {e}", path.display()))
+                })?;
+            debug!("Recorded fixture {}", path.display());
+
+            Ok(details)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fixture_file_name_is_stable() {
+        let name = fixture_file_name(
+            &HttpMethod::GET,
+            "https://mainnet.gomaestro-api.org/v1/epochs/current",
+            None,
+        );
+        assert!(name.starts_with("get_mainnet_gomaestro_api_org_v1_epochs_current_"));
+        assert!(name.ends_with(".json"));
+        assert_eq!(
+            name,
+            fixture_file_name(
+                &HttpMethod::GET,
+                "https://mainnet.gomaestro-api.org/v1/epochs/current",
+                None,
+            )
+        );
+    }
+
+    #[test]
+    fn test_fixture_file_name_distinguishes_query_and_body() {
+        let base = "https://example.com/assets";
+        let a = fixture_file_name(&HttpMethod::GET, &format!("{base}?page=1"), None);
+        let b = fixture_file_name(&HttpMethod::GET, &format!("{base}?page=2"), None);
+        let c = fixture_file_name(&HttpMethod::POST, base, Some(r#"{"limit":1}"#));
+        let d = fixture_file_name(&HttpMethod::POST, base, Some(r#"{"limit":2}"#));
+        assert_ne!(a, b);
+        assert_ne!(c, d);
+    }
+}
@@ -1,9 +1,28 @@
 use serde::{de::DeserializeOwned, Serialize};
 use std::collections::HashMap;
-use tracing::debug;
+use tracing::Instrument;
 
+mod base;
+mod builder;
+mod cache;
+mod decompress;
 mod error;
+mod single_flight;
+mod trace;
+pub use base::{path_segment, BaseClient, Query};
+pub use builder::HttpClientBuilder;
+pub use cache::{CacheMode, CacheOptions};
+pub use decompress::DEFAULT_MAX_RESPONSE_BYTES;
 pub use error::*;
+pub use single_flight::SingleFlightStats;
+use single_flight::{flight_key, SingleFlight};
+pub use trace::Redaction;
+use trace::RequestTrace;
+
+#[cfg(all(feature = "fixtures", not(target_arch = "wasm32")))]
+pub mod fixtures;
+#[cfg(all(feature = "fixtures", not(target_arch = "wasm32")))]
+pub use fixtures::FixtureMode;
 
 /// Response with parsed data and metadata (headers, status)
 #[derive(Debug)]
@@ -48,6 +67,9 @@ mod native;
 #[cfg(target_arch = "wasm32")]
 mod wasm;
 
+#[cfg(all(test, not(target_arch = "wasm32")))]
+mod test_support;
+
 #[derive(Debug, Clone)]
 pub enum HttpMethod {
     GET,
@@ -75,6 +97,13 @@ pub struct HttpClient {
     #[cfg(not(target_arch = "wasm32"))]
     inner: reqwest::Client,
     default_headers: HashMap<String, String>,
+    max_response_bytes: u64,
+    redaction: Redaction,
+    single_flight: Option<SingleFlight>,
+    #[cfg(target_arch = "wasm32")]
+    cache: Option<CacheOptions>,
+    #[cfg(all(feature = "fixtures", not(target_arch = "wasm32")))]
+    fixtures: Option<FixtureMode>,
 }
 
 impl HttpClient {
@@ -83,9 +112,21 @@ impl HttpClient {
             #[cfg(not(target_arch = "wasm32"))]
             inner: reqwest::Client::new(),
             default_headers: HashMap::new(),
+            max_response_bytes: DEFAULT_MAX_RESPONSE_BYTES,
+            redaction: Redaction::default(),
+            single_flight: None,
+            #[cfg(target_arch = "wasm32")]
+            cache: None,
+            #[cfg(all(feature = "fixtures", not(target_arch = "wasm32")))]
+            fixtures: None,
         }
     }
 
+    /// Start building a client with proxy, root CA, or DNS override options
+    pub fn builder() -> HttpClientBuilder {
+        HttpClientBuilder::new()
+    }
+
     pub fn with_bearer_token(token: String) -> Self {
         let mut client = Self::new();
         client
@@ -112,6 +153,139 @@ impl HttpClient {
         self.with_header("User-Agent", user_agent)
     }
 
+    /// Cap response bodies at `bytes` (after decompression)
+    ///
+    /// Larger responses fail with [`HttpError::ResponseTooLarge`]. Defaults to
+    /// [`DEFAULT_MAX_RESPONSE_BYTES`].
+    pub fn with_max_response_bytes(mut self, bytes: u64) -> Self {
+        self.max_response_bytes = bytes;
+        self
+    }
+
+    /// Replace the rules used to mask headers, query parameters and bodies
+    /// in request logs
+    pub fn with_redaction(mut self, redaction: Redaction) -> Self {
+        self.redaction = redaction;
+        self
+    }
+
+    /// Coalesce concurrent GETs for the same URL and headers into one
+    /// upstream request
+    ///
+    /// Clones of the client share in-flight requests; see
+    /// [`single_flight_stats`](Self::single_flight_stats) for hit counts.
+    pub fn with_single_flight(mut self) -> Self {
+        self.single_flight = Some(SingleFlight::default());
+        self
+    }
+
+    /// Upstream and deduplicated GET counts, if single-flight is enabled
+    pub fn single_flight_stats(&self) -> Option<SingleFlightStats> {
+        self.single_flight.as_ref().map(SingleFlight::stats)
+    }
+
+    /// Apply Cloudflare edge cache settings to GETs (wasm only)
+    ///
+    /// Natively the options are ignored. For a single request use
+    /// [`get_cached`](Self::get_cached) or
+    /// [`get_bytes_cached`](Self::get_bytes_cached) instead.
+    pub fn with_cache(self, options: CacheOptions) -> Self {
+        #[cfg(target_arch = "wasm32")]
+        {
+            Self {
+                cache: Some(options),
+                ..self
+            }
+        }
+
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            let _ = options;
+            self
+        }
+    }
+
+    /// The cache settings, if set and `method` is a GET
+    #[cfg(target_arch = "wasm32")]
+    fn cache_for(&self, method: &HttpMethod) -> Option<&CacheOptions> {
+        match method {
+            HttpMethod::GET => self.cache.as_ref(),
+            _ => None,
+        }
+    }
+
+    /// The single-flight layer and flight key, if enabled and `method` is a GET
+    fn coalesce(&self, method: &HttpMethod, url: &str) -> Option<(&SingleFlight, String)> {
+        match method {
+            HttpMethod::GET => self
+                .single_flight
+                .as_ref()
+                .map(|flight| (flight, flight_key(method, url, &self.default_headers))),
+            _ => None,
+        }
+    }
+
+    /// Open the span for a request and log it under the redaction rules
+    fn trace<T: Serialize>(
+        &self,
+        method: &HttpMethod,
+        url: &str,
+        body: Option<&T>,
+    ) -> RequestTrace {
+        RequestTrace::start(&self.redaction, method, url, &self.default_headers, body)
+    }
+
+    /// Record responses to, or replay them from, JSON fixture files
+    #[cfg(all(feature = "fixtures", not(target_arch = "wasm32")))]
+    pub fn with_fixtures(mut self, mode: FixtureMode) -> Self {
+        self.fixtures = Some(mode);
+        self
+    }
+
+    /// Text response routed through the fixture layer, if one is attached
+    #[cfg(all(feature = "fixtures", not(target_arch = "wasm32")))]
+    async fn fixture_text<T: Serialize>(
+        &self,
+        method: &HttpMethod,
+        url: &str,
+        body: Option<&T>,
+    ) -> Option<Result<ResponseDetails<String>, HttpError>> {
+        let mode = self.fixtures.as_ref()?;
+        let details = fixtures::request_text(
+            mode,
+            &self.inner,
+            &self.default_headers,
+            method.clone(),
+            url,
+            body,
+            self.max_response_bytes,
+        )
+        .await;
+        if let Ok(details) = &details {
+            trace::record_status(details.status_code);
+        }
+        Some(details)
+    }
+
+    /// Parse a fixture or single-flight text response the way the live JSON
+    /// paths do
+    fn parse_text_details<R: DeserializeOwned>(
+        details: ResponseDetails<String>,
+    ) -> Result<ResponseDetails<R>, HttpError> {
+        if !(200..300).contains(&details.status_code) {
+            return Err(HttpError::Status {
+                code: details.status_code,
+                headers: details.headers,
+                body: details.data,
+            });
+        }
+        Ok(ResponseDetails {
+            data: serde_json::from_str(&details.data)?,
+            status_code: details.status_code,
+            headers: details.headers,
+        })
+    }
+
     /// Generic request method that handles serialization, headers, and logging
     pub async fn request<T: Serialize, R: DeserializeOwned>(
         &self,
@@ -119,26 +293,47 @@ impl HttpClient {
         url: &str,
         body: Option<&T>,
     ) -> Result<R, HttpError> {
-        if let Some(data) = body {
-            debug!(
-                "{:?} request to: {}, body: {}",
-                method,
-                url,
-                serde_json::to_string(data).unwrap_or_else(|_| "serialization failed".to_string())
-            );
-        } else {
-            debug!("{:?} request to: {} (no body)", method, url);
-        }
+        let trace = self.trace(&method, url, body);
+        let result = async {
+            if let Some((flight, key)) = self.coalesce(&method, url) {
+                let details = flight.run(&key, || self.send_text(method, url, body)).await;
+                return Self::parse_text_details(details?).map(|details| details.data);
+            }
+
+            #[cfg(all(feature = "fixtures", not(target_arch = "wasm32")))]
+            if let Some(details) = self.fixture_text(&method, url, body).await {
+                return Self::parse_text_details(details?).map(|details| details.data);
+            }
 
-        #[cfg(not(target_arch = "wasm32"))]
-        {
-            native::make_request(&self.inner, &self.default_headers, method, url, body).await
-        }
-
-        #[cfg(target_arch = "wasm32")]
-        {
-            wasm::make_request(&self.default_headers, method, url, body).await
+            #[cfg(not(target_arch = "wasm32"))]
+            {
+                native::make_request(
+                    &self.inner,
+                    &self.default_headers,
+                    method,
+                    url,
+                    body,
+                    self.max_response_bytes,
+                )
+                .await
+            }
+
+            #[cfg(target_arch = "wasm32")]
+            {
+                wasm::make_request(
+                    &self.default_headers,
+                    self.cache_for(&method),
+                    method,
+                    url,
+                    body,
+                    self.max_response_bytes,
+                )
+                .await
+            }
         }
+        .instrument(trace.span())
+        .await;
+        trace.finish(result)
     }
 
     /// Convenience method for GET requests
@@ -146,6 +341,16 @@ impl HttpClient {
         self.request::<(), R>(HttpMethod::GET, url, None).await
     }
 
+    /// GET with Cloudflare edge cache settings for this request only; see
+    /// [`with_cache`](Self::with_cache)
+    pub async fn get_cached<R: DeserializeOwned>(
+        &self,
+        url: &str,
+        options: &CacheOptions,
+    ) -> Result<R, HttpError> {
+        self.clone().with_cache(options.clone()).get(url).await
+    }
+
     /// Convenience method for POST requests with JSON body
     pub async fn post<T: Serialize, R: DeserializeOwned>(
         &self,
@@ -186,18 +391,47 @@ impl HttpClient {
         url: &str,
         body: Option<&T>,
     ) -> Result<ResponseDetails<R>, HttpError> {
-        debug!("{:?} request with details to: {}", method, url);
+        let trace = self.trace(&method, url, body);
+        let result = async {
+            if let Some((flight, key)) = self.coalesce(&method, url) {
+                let details = flight.run(&key, || self.send_text(method, url, body)).await;
+                return Self::parse_text_details(details?);
+            }
+
+            #[cfg(all(feature = "fixtures", not(target_arch = "wasm32")))]
+            if let Some(details) = self.fixture_text(&method, url, body).await {
+                return Self::parse_text_details(details?);
+            }
 
-        #[cfg(not(target_arch = "wasm32"))]
-        {
-            native::make_request_with_details(&self.inner, &self.default_headers, method, url, body)
+            #[cfg(not(target_arch = "wasm32"))]
+            {
+                native::make_request_with_details(
+                    &self.inner,
+                    &self.default_headers,
+                    method,
+                    url,
+                    body,
+                    self.max_response_bytes,
+                )
                 .await
+            }
+
+            #[cfg(target_arch = "wasm32")]
+            {
+                wasm::make_request_with_details(
+                    &self.default_headers,
+                    self.cache_for(&method),
+                    method,
+                    url,
+                    body,
+                    self.max_response_bytes,
+                )
+                .await
+            }
         }
-
-        #[cfg(target_arch = "wasm32")]
-        {
-            wasm::make_request_with_details(&self.default_headers, method, url, body).await
-        }
+        .instrument(trace.span())
+        .await;
+        trace.finish(result)
     }
 
     /// Convenience method for POST with response details
@@ -227,7 +461,29 @@ impl HttpClient {
         url: &str,
         body: Option<&T>,
     ) -> Result<ResponseDetails<String>, HttpError> {
-        debug!("{method:?} request for text with details to: {url}");
+        let trace = self.trace(&method, url, body);
+        let result = async {
+            match self.coalesce(&method, url) {
+                Some((flight, key)) => flight.run(&key, || self.send_text(method, url, body)).await,
+                None => self.send_text(method, url, body).await,
+            }
+        }
+        .instrument(trace.span())
+        .await;
+        trace.finish(result)
+    }
+
+    /// Send a request and return the raw text response, without tracing
+    async fn send_text<T: Serialize>(
+        &self,
+        method: HttpMethod,
+        url: &str,
+        body: Option<&T>,
+    ) -> Result<ResponseDetails<String>, HttpError> {
+        #[cfg(all(feature = "fixtures", not(target_arch = "wasm32")))]
+        if let Some(details) = self.fixture_text(&method, url, body).await {
+            return details;
+        }
 
         #[cfg(not(target_arch = "wasm32"))]
         {
@@ -237,15 +493,75 @@ impl HttpClient {
                 method,
                 url,
                 body,
+                self.max_response_bytes,
             )
             .await
         }
 
         #[cfg(target_arch = "wasm32")]
         {
-            wasm::make_request_text_with_details(&self.default_headers, method, url, body).await
+            wasm::make_request_text_with_details(
+                &self.default_headers,
+                self.cache_for(&method),
+                method,
+                url,
+                body,
+                self.max_response_bytes,
+            )
+            .await
         }
     }
+
+    /// GET a binary resource (images, archives) and return the raw bytes with metadata
+    ///
+    /// Non-2xx responses are returned as [`HttpError::Status`].
+    pub async fn get_bytes_with_details(
+        &self,
+        url: &str,
+    ) -> Result<ResponseDetails<Vec<u8>>, HttpError> {
+        let trace = self.trace::<()>(&HttpMethod::GET, url, None);
+        let result = async {
+            #[cfg(not(target_arch = "wasm32"))]
+            {
+                native::make_bytes_request_with_details(
+                    &self.inner,
+                    &self.default_headers,
+                    &self.redaction,
+                    url,
+                    self.max_response_bytes,
+                )
+                .await
+            }
+
+            #[cfg(target_arch = "wasm32")]
+            {
+                wasm::make_bytes_request_with_details(
+                    &self.default_headers,
+                    self.cache_for(&HttpMethod::GET),
+                    &self.redaction,
+                    url,
+                    self.max_response_bytes,
+                )
+                .await
+            }
+        }
+        .instrument(trace.span())
+        .await;
+        trace.finish(result)
+    }
+
+    /// [`get_bytes_with_details`](Self::get_bytes_with_details) with
+    /// Cloudflare edge cache settings for this request only
+    pub async fn get_bytes_cached(
+        &self,
+        url: &str,
+        options: &CacheOptions,
+    ) -> Result<ResponseDetails<Vec<u8>>, HttpError> {
+        self.clone()
+            .with_cache(options.clone())
+            .get_bytes_with_details(url)
+            .await
+    }
 }
 
 impl Default for HttpClient {
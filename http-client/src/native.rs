@@ -1,15 +1,51 @@
-use crate::{HttpError, HttpMethod, ResponseDetails};
+use crate::decompress::{check_body_size, check_content_length};
+use crate::trace::record_status;
+use crate::{builder::ProxyConfig, HttpError, HttpMethod, Redaction, ResponseDetails};
 use reqwest::header::{ACCEPT, CONTENT_TYPE};
 use serde::{de::DeserializeOwned, Serialize};
 use std::collections::HashMap;
+use std::net::SocketAddr;
 use tracing::debug;
 
+pub(crate) fn build_client(
+    proxy: Option<&ProxyConfig>,
+    no_proxy: bool,
+    root_certificates: &[Vec<u8>],
+    dns_overrides: &[(String, SocketAddr)],
+    redaction: &Redaction,
+) -> Result<reqwest::Client, HttpError> {
+    let mut builder = reqwest::Client::builder();
+
+    if no_proxy {
+        builder = builder.no_proxy();
+    } else if let Some(config) = proxy {
+        let mut proxy = reqwest::Proxy::all(&config.url)?.no_proxy(reqwest::NoProxy::from_env());
+        if let Some((username, password)) = &config.basic_auth {
+            proxy = proxy.basic_auth(username, password);
+        }
+        debug!("Using HTTP proxy: {}", redaction.url(&config.url));
+        builder = builder.proxy(proxy);
+    }
+
+    for pem in root_certificates {
+        builder = builder.add_root_certificate(reqwest::Certificate::from_pem(pem)?);
+    }
+
+    for (host, addr) in dns_overrides {
+        debug!("Resolving {host} to {addr}");
+        builder = builder.resolve(host, *addr);
+    }
+
+    builder.build().map_err(HttpError::from)
+}
+
 pub(crate) async fn make_request<T: Serialize, R: DeserializeOwned>(
     client: &reqwest::Client,
     default_headers: &HashMap<String, String>,
     method: HttpMethod,
     url: &str,
     body: Option<&T>,
+    max_response_bytes: u64,
 ) -> Result<R, HttpError> {
     let mut builder = client
         .request(method.to_reqwest(), url)
@@ -26,10 +62,23 @@ pub(crate) async fn make_request<T: Serialize, R: DeserializeOwned>(
         builder = builder.json(body_data);
     }
 
-    let response = builder.send().await?.error_for_status()?;
+    let response = builder.send().await?;
+    record_status(response.status().as_u16());
     debug!("Got response from API: {:?}", response.status());
 
-    response.json::<R>().await.map_err(HttpError::from)
+    if !response.status().is_success() {
+        let code = response.status().as_u16();
+        let headers = response_headers(&response);
+        let body = read_text(response, max_response_bytes).await?;
+        return Err(HttpError::Status {
+            code,
+            headers,
+            body,
+        });
+    }
+
+    let body = read_body(response, max_response_bytes).await?;
+    serde_json::from_slice(&body).map_err(HttpError::from)
 }
 
 pub(crate) async fn make_request_with_details<T: Serialize, R: DeserializeOwned>(
@@ -38,6 +87,7 @@ pub(crate) async fn make_request_with_details<T: Serialize, R: DeserializeOwned>
     method: HttpMethod,
     url: &str,
     body: Option<&T>,
+    max_response_bytes: u64,
 ) -> Result<ResponseDetails<R>, HttpError> {
     let mut builder = client
         .request(method.to_reqwest(), url)
@@ -55,29 +105,24 @@ pub(crate) async fn make_request_with_details<T: Serialize, R: DeserializeOwned>
     }
 
     let response = builder.send().await?;
+    record_status(response.status().as_u16());
     let status_code = response.status().as_u16();
 
-    // Extract headers - native extracts ALL headers
-    let mut headers = HashMap::new();
-    for (key, value) in response.headers() {
-        if let Ok(value_str) = value.to_str() {
-            headers.insert(key.as_str().to_string(), value_str.to_string());
-        }
-    }
+    let headers = response_headers(&response);
 
     // Check status before parsing body
     if !response.status().is_success() {
         // Get response body as text for error details
-        let body = response.text().await?;
-        return Err(HttpError::HttpStatus {
-            status_code,
+        let body = read_text(response, max_response_bytes).await?;
+        return Err(HttpError::Status {
+            code: status_code,
             headers,
             body,
         });
     }
 
     // Parse JSON body
-    let data = response.json::<R>().await?;
+    let data = serde_json::from_slice(&read_body(response, max_response_bytes).await?)?;
     debug!("Got response from API: status {status_code}");
 
     Ok(ResponseDetails {
@@ -93,6 +138,7 @@ pub(crate) async fn make_request_text_with_details<T: Serialize>(
     method: HttpMethod,
     url: &str,
     body: Option<&T>,
+    max_response_bytes: u64,
 ) -> Result<ResponseDetails<String>, HttpError> {
     let mut builder = client
         .request(method.to_reqwest(), url)
@@ -110,18 +156,13 @@ pub(crate) async fn make_request_text_with_details<T: Serialize>(
     }
 
     let response = builder.send().await?;
+    record_status(response.status().as_u16());
     let status_code = response.status().as_u16();
 
-    // Extract headers - native extracts ALL headers
-    let mut headers = HashMap::new();
-    for (key, value) in response.headers() {
-        if let Ok(value_str) = value.to_str() {
-            headers.insert(key.as_str().to_string(), value_str.to_string());
-        }
-    }
+    let headers = response_headers(&response);
 
     // Get raw text body without checking status first (for custom error handling)
-    let data = response.text().await?;
+    let data = read_text(response, max_response_bytes).await?;
     debug!("Got text response from API: status {status_code}");
 
     Ok(ResponseDetails {
@@ -130,3 +171,79 @@ pub(crate) async fn make_request_text_with_details<T: Serialize>(
         headers,
     })
 }
+
+pub(crate) async fn make_bytes_request_with_details(
+    client: &reqwest::Client,
+    default_headers: &HashMap<String, String>,
+    redaction: &Redaction,
+    url: &str,
+    max_response_bytes: u64,
+) -> Result<ResponseDetails<Vec<u8>>, HttpError> {
+    let mut builder = client.get(url);
+
+    // Add default headers
+    for (key, value) in default_headers {
+        builder = builder.header(key, value);
+    }
+
+    let response = builder.send().await?;
+    record_status(response.status().as_u16());
+    let status_code = response.status().as_u16();
+
+    let headers = response_headers(&response);
+
+    if !response.status().is_success() {
+        let body = read_text(response, max_response_bytes).await?;
+        return Err(HttpError::Status {
+            code: status_code,
+            headers,
+            body,
+        });
+    }
+
+    let data = read_body(response, max_response_bytes).await?;
+    debug!(
+        "Got {} bytes from {}: status {status_code}",
+        data.len(),
+        redaction.url(url)
+    );
+
+    Ok(ResponseDetails {
+        data,
+        status_code,
+        headers,
+    })
+}
+
+/// Native extracts ALL headers
+fn response_headers(response: &reqwest::Response) -> HashMap<String, String> {
+    response
+        .headers()
+        .iter()
+        .filter_map(|(key, value)| {
+            value
+                .to_str()
+                .ok()
+                .map(|v| (key.as_str().to_string(), v.to_string()))
+        })
+        .collect()
+}
+
+/// Read a (decompressed) body, failing once it passes `limit` bytes
+async fn read_body(mut response: reqwest::Response, limit: u64) -> Result<Vec<u8>, HttpError> {
+    // With a decoder enabled reqwest reports no length for encoded bodies, so
+    // the running check below is what bounds those
+    check_content_length(response.content_length(), limit)?;
+
+    let mut body = Vec::new();
+    while let Some(chunk) = response.chunk().await? {
+        body.extend_from_slice(&chunk);
+        check_body_size(body.len(), limit)?;
+    }
+    Ok(body)
+}
+
+async fn read_text(response: reqwest::Response, limit: u64) -> Result<String, HttpError> {
+    let body = read_body(response, limit).await?;
+    Ok(String::from_utf8_lossy(&body).into_owned())
+}
@@ -0,0 +1,390 @@
+//! Coalescing of identical in-flight GETs
+//!
+//! With [`HttpClient::with_single_flight`](crate::HttpClient::with_single_flight),
+//! a GET issued while another GET for the same URL and headers is still in
+//! flight waits for that request instead of sending its own, and gets a copy
+//! of its response. Requests with different headers (e.g. another
+//! `Authorization`) never share a response. Nothing is cached: once the first request completes, the next
+//! GET goes upstream again.
+//!
+//! Waiting uses plain wakers rather than a runtime primitive, so the same
+//! code coalesces requests on tokio and inside a Worker's single-threaded
+//! executor. If the request being waited on is dropped before it completes,
+//! each waiter sends its own request.
+
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::future::Future;
+use std::hash::{Hash, Hasher};
+use std::pin::Pin;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+use std::task::{Context, Poll, Waker};
+
+use tracing::{debug, Span};
+
+use crate::trace::record_status;
+use crate::{HttpError, HttpMethod, ResponseDetails};
+
+type TextResult = Result<ResponseDetails<String>, HttpError>;
+
+/// Counters for a client's single-flight layer
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SingleFlightStats {
+    /// GETs sent upstream
+    pub upstream: u64,
+    /// GETs answered by joining one already in flight
+    pub deduplicated: u64,
+}
+
+/// In-flight GETs by [`flight_key`], shared by clones of a client
+#[derive(Clone, Default)]
+pub(crate) struct SingleFlight {
+    flights: Arc<Mutex<HashMap<String, Arc<Flight>>>>,
+    upstream: Arc<AtomicU64>,
+    deduplicated: Arc<AtomicU64>,
+}
+
+#[derive(Default)]
+struct Flight {
+    state: Mutex<FlightState>,
+}
+
+enum FlightState {
+    Pending(Vec<Waker>),
+    Done(TextResult),
+    Abandoned,
+}
+
+impl Default for FlightState {
+    fn default() -> Self {
+        FlightState::Pending(Vec::new())
+    }
+}
+
+impl SingleFlight {
+    pub(crate) fn stats(&self) -> SingleFlightStats {
+        SingleFlightStats {
+            upstream: self.upstream.load(Ordering::Relaxed),
+            deduplicated: self.deduplicated.load(Ordering::Relaxed),
+        }
+    }
+
+    /// Run `fetch` for `key`, or wait for the request already in flight
+    pub(crate) async fn run<F, Fut>(&self, key: &str, fetch: F) -> TextResult
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = TextResult>,
+    {
+        let (flight, leader) = {
+            let mut flights = lock(&self.flights);
+            match flights.get(key) {
+                Some(flight) => (flight.clone(), false),
+                None => {
+                    let flight = Arc::new(Flight::default());
+                    flights.insert(key.to_string(), flight.clone());
+                    (flight, true)
+                }
+            }
+        };
+
+        if !leader {
+            self.deduplicated.fetch_add(1, Ordering::Relaxed);
+            Span::current().record("deduplicated", true);
+            debug!("Joined in-flight request");
+            if let Some(result) = (Wait { flight: &flight }).await {
+                if let Ok(details) = &result {
+                    record_status(details.status_code);
+                }
+                return result;
+            }
+            debug!("In-flight request was dropped, sending our own");
+            return fetch().await;
+        }
+
+        self.upstream.fetch_add(1, Ordering::Relaxed);
+        let landing = Landing {
+            flights: &self.flights,
+            key,
+            flight: &flight,
+        };
+        let result = fetch().await;
+        landing.complete(&result);
+        result
+    }
+}
+
+/// Key identifying requests that may share a response
+///
+/// Headers are hashed, ignoring name case and order, so credentials aren't
+/// kept in the map.
+pub(crate) fn flight_key(
+    method: &HttpMethod,
+    url: &str,
+    headers: &HashMap<String, String>,
+) -> String {
+    let mut headers: Vec<_> = headers
+        .iter()
+        .map(|(name, value)| (name.to_ascii_lowercase(), value))
+        .collect();
+    headers.sort();
+    let mut hasher = DefaultHasher::new();
+    headers.hash(&mut hasher);
+    format!("{method:?} {url} {:016x}", hasher.finish())
+}
+
+/// Settles a leader's flight, abandoning it if the leader is dropped early
+struct Landing<'a> {
+    flights: &'a Mutex<HashMap<String, Arc<Flight>>>,
+    key: &'a str,
+    flight: &'a Arc<Flight>,
+}
+
+impl Landing<'_> {
+    fn complete(self, result: &TextResult) {
+        self.settle(FlightState::Done(duplicate_result(result)));
+    }
+
+    fn settle(&self, outcome: FlightState) {
+        {
+            let mut flights = lock(self.flights);
+            if flights
+                .get(self.key)
+                .is_some_and(|f| Arc::ptr_eq(f, self.flight))
+            {
+                flights.remove(self.key);
+            }
+        }
+
+        let mut state = lock(&self.flight.state);
+        if let FlightState::Pending(wakers) = std::mem::replace(&mut *state, outcome) {
+            wakers.into_iter().for_each(Waker::wake);
+        }
+    }
+}
+
+impl Drop for Landing<'_> {
+    fn drop(&mut self) {
+        let pending = matches!(*lock(&self.flight.state), FlightState::Pending(_));
+        if pending {
+            self.settle(FlightState::Abandoned);
+        }
+    }
+}
+
+/// Resolves with a copy of the flight's result, or `None` if abandoned
+struct Wait<'a> {
+    flight: &'a Flight,
+}
+
+impl Future for Wait<'_> {
+    type Output = Option<TextResult>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut state = lock(&self.flight.state);
+        match &mut *state {
+            FlightState::Pending(wakers) => {
+                if !wakers.iter().any(|w| w.will_wake(cx.waker())) {
+                    wakers.push(cx.waker().clone());
+                }
+                Poll::Pending
+            }
+            FlightState::Done(result) => Poll::Ready(Some(duplicate_result(result))),
+            FlightState::Abandoned => Poll::Ready(None),
+        }
+    }
+}
+
+/// Lock ignoring poisoning; the guarded maps stay consistent across panics
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+fn duplicate_result(result: &TextResult) -> TextResult {
+    match result {
+        Ok(details) => Ok(ResponseDetails {
+            data: details.data.clone(),
+            status_code: details.status_code,
+            headers: details.headers.clone(),
+        }),
+        Err(e) => Err(duplicate_error(e)),
+    }
+}
+
+/// `HttpError` holds a `serde_json::Error`, so it can't derive `Clone`
+fn duplicate_error(error: &HttpError) -> HttpError {
+    match error {
+        HttpError::Status {
+            code,
+            headers,
+            body,
+        } => HttpError::Status {
+            code: *code,
+            headers: headers.clone(),
+            body: body.clone(),
+        },
+        HttpError::Timeout(e) => HttpError::Timeout(e.clone()),
+        HttpError::Network(e) => HttpError::Network(e.clone()),
+        HttpError::Decode { source } => HttpError::Decode {
+            source: serde::de::Error::custom(source),
+        },
+        HttpError::Builder(e) => HttpError::Builder(e.clone()),
+        HttpError::ResponseTooLarge { limit, size } => HttpError::ResponseTooLarge {
+            limit: *limit,
+            size: *size,
+        },
+        HttpError::Decompression(e) => HttpError::Decompression(e.clone()),
+        HttpError::Fixture(e) => HttpError::Fixture(e.clone()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::AtomicBool;
+    use std::task::Wake;
+
+    const URL: &str = "https://mainnet.gomaestro-api.org/v1/assets/abc";
+
+    #[derive(Default)]
+    struct CountingWaker(AtomicU64);
+
+    impl Wake for CountingWaker {
+        fn wake(self: Arc<Self>) {
+            self.0.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    fn ok(body: &str) -> TextResult {
+        Ok(ResponseDetails {
+            data: body.to_string(),
+            status_code: 200,
+            headers: HashMap::new(),
+        })
+    }
+
+    /// Pending until `open` is set
+    async fn gated(open: Arc<AtomicBool>, body: &str) -> TextResult {
+        std::future::poll_fn(|_| {
+            if open.load(Ordering::Relaxed) {
+                Poll::Ready(())
+            } else {
+                Poll::Pending
+            }
+        })
+        .await;
+        ok(body)
+    }
+
+    #[test]
+    fn test_concurrent_gets_share_one_request() {
+        let flights = SingleFlight::default();
+        let open = Arc::new(AtomicBool::new(false));
+        let counter = Arc::new(CountingWaker::default());
+        let waker = Waker::from(counter.clone());
+        let mut cx = Context::from_waker(&waker);
+
+        let mut leader = Box::pin(flights.run(URL, || gated(open.clone(), "{\"asset\":1}")));
+        let mut follower = Box::pin(flights.run(URL, || async { panic!("follower fetched") }));
+        assert!(leader.as_mut().poll(&mut cx).is_pending());
+        assert!(follower.as_mut().poll(&mut cx).is_pending());
+
+        open.store(true, Ordering::Relaxed);
+        let Poll::Ready(Ok(first)) = leader.as_mut().poll(&mut cx) else {
+            panic!("leader should complete");
+        };
+        assert_eq!(counter.0.load(Ordering::Relaxed), 1);
+        let Poll::Ready(Ok(second)) = follower.as_mut().poll(&mut cx) else {
+            panic!("follower should share the leader's response");
+        };
+        assert_eq!(first.data, second.data);
+        assert_eq!(
+            flights.stats(),
+            SingleFlightStats {
+                upstream: 1,
+                deduplicated: 1,
+            }
+        );
+
+        // Completed requests aren't cached
+        let mut later = Box::pin(flights.run(URL, || async { ok("{\"asset\":2}") }));
+        let Poll::Ready(Ok(fresh)) = later.as_mut().poll(&mut cx) else {
+            panic!("new request should go upstream");
+        };
+        assert_eq!(fresh.data, "{\"asset\":2}");
+        assert_eq!(flights.stats().upstream, 2);
+    }
+
+    #[test]
+    fn test_different_headers_dont_share() {
+        let flights = SingleFlight::default();
+        let open = Arc::new(AtomicBool::new(false));
+        let waker = Waker::from(Arc::new(CountingWaker::default()));
+        let mut cx = Context::from_waker(&waker);
+
+        let alice = HashMap::from([("Authorization".to_string(), "Bearer alice".to_string())]);
+        let bob = HashMap::from([("Authorization".to_string(), "Bearer bob".to_string())]);
+        let alice_key = flight_key(&HttpMethod::GET, URL, &alice);
+        let bob_key = flight_key(&HttpMethod::GET, URL, &bob);
+        assert_ne!(alice_key, bob_key);
+        assert_ne!(alice_key, flight_key(&HttpMethod::POST, URL, &alice));
+        let alice_again =
+            HashMap::from([("authorization".to_string(), "Bearer alice".to_string())]);
+        assert_eq!(alice_key, flight_key(&HttpMethod::GET, URL, &alice_again));
+
+        let mut first = Box::pin(flights.run(&alice_key, || gated(open.clone(), "alice")));
+        let mut second = Box::pin(flights.run(&bob_key, || gated(open.clone(), "bob")));
+        assert!(first.as_mut().poll(&mut cx).is_pending());
+        assert!(second.as_mut().poll(&mut cx).is_pending());
+
+        open.store(true, Ordering::Relaxed);
+        let Poll::Ready(Ok(first)) = first.as_mut().poll(&mut cx) else {
+            panic!("first request should complete");
+        };
+        let Poll::Ready(Ok(second)) = second.as_mut().poll(&mut cx) else {
+            panic!("second request should complete");
+        };
+        assert_eq!(first.data, "alice");
+        assert_eq!(second.data, "bob");
+        assert_eq!(
+            flights.stats(),
+            SingleFlightStats {
+                upstream: 2,
+                deduplicated: 0,
+            }
+        );
+    }
+
+    #[test]
+    fn test_dropped_leader_releases_waiters() {
+        let flights = SingleFlight::default();
+        let waker = Waker::from(Arc::new(CountingWaker::default()));
+        let mut cx = Context::from_waker(&waker);
+
+        let mut leader =
+            Box::pin(flights.run(URL, || gated(Arc::new(AtomicBool::new(false)), "never")));
+        let mut follower = Box::pin(flights.run(URL, || async { ok("own") }));
+        assert!(leader.as_mut().poll(&mut cx).is_pending());
+        assert!(follower.as_mut().poll(&mut cx).is_pending());
+
+        drop(leader);
+        let Poll::Ready(Ok(details)) = follower.as_mut().poll(&mut cx) else {
+            panic!("follower should fall back to its own request");
+        };
+        assert_eq!(details.data, "own");
+    }
+
+    #[test]
+    fn test_errors_are_shared() {
+        let error = duplicate_error(&HttpError::Status {
+            code: 429,
+            headers: HashMap::from([("retry-after".to_string(), "2".to_string())]),
+            body: String::new(),
+        });
+        assert_eq!(error.status_code(), Some(429));
+        assert_eq!(error.retry_after_seconds(), Some(2));
+
+        let decode: HttpError = serde_json::from_str::<u64>("\"x\"").unwrap_err().into();
+        assert_eq!(duplicate_error(&decode).to_string(), decode.to_string());
+    }
+}
@@ -1,81 +1,55 @@
-use crate::{HttpError, HttpMethod, ResponseDetails};
-use gloo_net::http::Request;
+use crate::decompress::{accept_encoding, check_body_size, check_content_length, decode_response};
+use crate::trace::record_status;
+use crate::{CacheOptions, HttpError, HttpMethod, Redaction, ResponseDetails};
+use gloo_net::http::{Request, RequestBuilder, Response};
 use serde::{de::DeserializeOwned, Serialize};
 use std::collections::HashMap;
 use tracing::debug;
+use worker_stack::wasm_bindgen::JsValue;
+use worker_stack::{js_sys, web_sys};
 
 pub(crate) async fn make_request<T: Serialize, R: DeserializeOwned>(
     default_headers: &HashMap<String, String>,
+    cache: Option<&CacheOptions>,
     method: HttpMethod,
     url: &str,
     body: Option<&T>,
+    max_response_bytes: u64,
 ) -> Result<R, HttpError> {
-    // Create request using the appropriate static method
-    let mut request = match method {
-        HttpMethod::GET => Request::get(url),
-        HttpMethod::POST => Request::post(url),
-        HttpMethod::PUT => Request::put(url),
-        HttpMethod::DELETE => Request::delete(url),
-        HttpMethod::PATCH => Request::patch(url),
-    };
-
-    // Add default headers
-    for (key, value) in default_headers {
-        request = request.header(key, value);
-    }
-
-    // Set content type for JSON
-    request = request.header("Content-Type", "application/json");
-    request = request.header("Accept", "application/json");
-
-    // Add body if present and send request
-    let response = if let Some(body_data) = body {
-        request.json(body_data)?.send().await?
-    } else {
-        request.send().await?
-    };
+    let request = build_request(default_headers, method, url);
+    let response = send(request, body, cache).await?;
+    record_status(response.status());
     debug!("Got response from API: {}", response.status());
 
     if !response.ok() {
-        return Err(HttpError::Custom(format!(
-            "HTTP request failed with status: {}",
-            response.status()
-        )));
+        let code = response.status();
+        let mut headers = HashMap::new();
+        if let Some(retry_after) = response.headers().get("retry-after") {
+            headers.insert("retry-after".to_string(), retry_after);
+        }
+        let body = read_text(&response, max_response_bytes).await?;
+        return Err(HttpError::Status {
+            code,
+            headers,
+            body,
+        });
     }
 
-    response.json::<R>().await.map_err(HttpError::from)
+    let body = read_body(&response, max_response_bytes).await?;
+    serde_json::from_slice(&body).map_err(HttpError::from)
 }
 
 pub(crate) async fn make_request_with_details<T: Serialize, R: DeserializeOwned>(
     default_headers: &HashMap<String, String>,
+    cache: Option<&CacheOptions>,
     method: HttpMethod,
     url: &str,
     body: Option<&T>,
+    max_response_bytes: u64,
 ) -> Result<ResponseDetails<R>, HttpError> {
-    // Create request using the appropriate static method
-    let mut request = match method {
-        HttpMethod::GET => Request::get(url),
-        HttpMethod::POST => Request::post(url),
-        HttpMethod::PUT => Request::put(url),
-        HttpMethod::DELETE => Request::delete(url),
-        HttpMethod::PATCH => Request::patch(url),
-    };
-
-    // Add default headers
-    for (key, value) in default_headers {
-        request = request.header(key, value);
-    }
-
-    // Set content type for JSON
-    request = request.header("Content-Type", "application/json");
-    request = request.header("Accept", "application/json");
-
-    // Add body if present and send request
-    let response = if let Some(body_data) = body {
-        request.json(body_data)?.send().await?
-    } else {
-        request.send().await?
-    };
+    let request = build_request(default_headers, method, url);
+    let response = send(request, body, cache).await?;
+    record_status(response.status());
 
     let status_code = response.status();
 
@@ -96,6 +70,8 @@ pub(crate) async fn make_request_with_details<T: Serialize, R: DeserializeOwned>
         "x-ratelimit-remaining-tokens",
         "x-ratelimit-reset-tokens",
         "content-type",
+        "content-encoding",
+        "content-length",
         "date",
     ];
 
@@ -108,16 +84,16 @@ pub(crate) async fn make_request_with_details<T: Serialize, R: DeserializeOwned>
     // Check status before parsing
     if !response.ok() {
         // Get response body as text for error details
-        let body = response.text().await?;
-        return Err(HttpError::HttpStatus {
-            status_code,
+        let body = read_text(&response, max_response_bytes).await?;
+        return Err(HttpError::Status {
+            code: status_code,
             headers,
             body,
         });
     }
 
     // Parse JSON body
-    let data = response.json::<R>().await?;
+    let data = serde_json::from_slice(&read_body(&response, max_response_bytes).await?)?;
     debug!("Got response from API: status {status_code}");
 
     Ok(ResponseDetails {
@@ -129,34 +105,15 @@ pub(crate) async fn make_request_with_details<T: Serialize, R: DeserializeOwned>
 
 pub(crate) async fn make_request_text_with_details<T: Serialize>(
     default_headers: &HashMap<String, String>,
+    cache: Option<&CacheOptions>,
     method: HttpMethod,
     url: &str,
     body: Option<&T>,
+    max_response_bytes: u64,
 ) -> Result<ResponseDetails<String>, HttpError> {
-    // Create request using the appropriate static method
-    let mut request = match method {
-        HttpMethod::GET => Request::get(url),
-        HttpMethod::POST => Request::post(url),
-        HttpMethod::PUT => Request::put(url),
-        HttpMethod::DELETE => Request::delete(url),
-        HttpMethod::PATCH => Request::patch(url),
-    };
-
-    // Add default headers
-    for (key, value) in default_headers {
-        request = request.header(key, value);
-    }
-
-    // Set content type for JSON
-    request = request.header("Content-Type", "application/json");
-    request = request.header("Accept", "application/json");
-
-    // Add body if present and send request
-    let response = if let Some(body_data) = body {
-        request.json(body_data)?.send().await?
-    } else {
-        request.send().await?
-    };
+    let request = build_request(default_headers, method, url);
+    let response = send(request, body, cache).await?;
+    record_status(response.status());
 
     let status_code = response.status();
 
@@ -176,6 +133,8 @@ pub(crate) async fn make_request_text_with_details<T: Serialize>(
         "x-ratelimit-remaining-tokens",
         "x-ratelimit-reset-tokens",
         "content-type",
+        "content-encoding",
+        "content-length",
         "date",
     ];
 
@@ -186,7 +145,7 @@ pub(crate) async fn make_request_text_with_details<T: Serialize>(
     }
 
     // Get raw text body without checking status first (for custom error handling)
-    let data = response.text().await?;
+    let data = read_text(&response, max_response_bytes).await?;
     debug!("Got text response from API: status {status_code}");
 
     Ok(ResponseDetails {
@@ -195,3 +154,150 @@ pub(crate) async fn make_request_text_with_details<T: Serialize>(
         headers,
     })
 }
+
+pub(crate) async fn make_bytes_request_with_details(
+    default_headers: &HashMap<String, String>,
+    cache: Option<&CacheOptions>,
+    redaction: &Redaction,
+    url: &str,
+    max_response_bytes: u64,
+) -> Result<ResponseDetails<Vec<u8>>, HttpError> {
+    let mut request = Request::get(url);
+
+    // Add default headers
+    for (key, value) in default_headers {
+        request = request.header(key, value);
+    }
+    request = with_accept_encoding(request);
+
+    let response = send::<()>(request, None, cache).await?;
+    record_status(response.status());
+    let status_code = response.status();
+
+    let mut headers = HashMap::new();
+    let response_headers = response.headers();
+
+    let header_names = vec![
+        "retry-after",
+        "content-type",
+        "content-encoding",
+        "content-length",
+        "content-disposition",
+        "date",
+    ];
+
+    for header_name in header_names {
+        if let Some(value) = response_headers.get(header_name) {
+            headers.insert(header_name.to_string(), value);
+        }
+    }
+
+    if !response.ok() {
+        let body = read_text(&response, max_response_bytes).await?;
+        return Err(HttpError::Status {
+            code: status_code,
+            headers,
+            body,
+        });
+    }
+
+    let data = read_body(&response, max_response_bytes).await?;
+    debug!(
+        "Got {} bytes from {}: status {status_code}",
+        data.len(),
+        redaction.url(url)
+    );
+
+    Ok(ResponseDetails {
+        data,
+        status_code,
+        headers,
+    })
+}
+
+fn build_request(
+    default_headers: &HashMap<String, String>,
+    method: HttpMethod,
+    url: &str,
+) -> RequestBuilder {
+    // Create request using the appropriate static method
+    let mut request = match method {
+        HttpMethod::GET => Request::get(url),
+        HttpMethod::POST => Request::post(url),
+        HttpMethod::PUT => Request::put(url),
+        HttpMethod::DELETE => Request::delete(url),
+        HttpMethod::PATCH => Request::patch(url),
+    };
+
+    // Add default headers
+    for (key, value) in default_headers {
+        request = request.header(key, value);
+    }
+
+    // No Content-Type here: `json()` sets it when there is a body, and on a
+    // bodiless GET it turns a simple CORS request into a preflighted one that
+    // many public APIs reject
+    request = request.header("Accept", "application/json");
+    with_accept_encoding(request)
+}
+
+/// Attach the body, if any, and send with the cache settings applied
+async fn send<T: Serialize>(
+    request: RequestBuilder,
+    body: Option<&T>,
+    cache: Option<&CacheOptions>,
+) -> Result<Response, HttpError> {
+    let request = match body {
+        Some(body_data) => request.json(body_data)?,
+        None => request.build()?,
+    };
+    let request = match cache {
+        Some(cache) => with_cache(request, cache)?,
+        None => request,
+    };
+    Ok(request.send().await?)
+}
+
+/// Copy `request` with the fetch `cache` mode and the Workers `cf`
+/// properties set — neither is reachable through gloo-net's builder
+fn with_cache(request: Request, cache: &CacheOptions) -> Result<Request, HttpError> {
+    let init = web_sys::RequestInit::new();
+    if let Some(mode) = cache.cache_mode() {
+        js_sys::Reflect::set(&init, &"cache".into(), &mode.as_str().into()).map_err(js_error)?;
+    }
+    let cf = js_sys::JSON::parse(&cache.cf_properties().to_string()).map_err(js_error)?;
+    js_sys::Reflect::set(&init, &"cf".into(), &cf).map_err(js_error)?;
+
+    let request: web_sys::Request = request.into();
+    let request = web_sys::Request::new_with_request_and_init(&request, &init).map_err(js_error)?;
+    Ok(Request::from(request))
+}
+
+fn js_error(e: JsValue) -> HttpError {
+    HttpError::Builder(format!("{e:?}"))
+}
+
+fn with_accept_encoding(request: RequestBuilder) -> RequestBuilder {
+    match accept_encoding() {
+        Some(encodings) => request.header("Accept-Encoding", &encodings),
+        None => request,
+    }
+}
+
+/// Read and decode the body, enforcing `limit` before and after decompression
+async fn read_body(response: &Response, limit: u64) -> Result<Vec<u8>, HttpError> {
+    let headers = response.headers();
+    let content_length = headers
+        .get("content-length")
+        .and_then(|v| v.parse::<u64>().ok());
+    check_content_length(content_length, limit)?;
+
+    let raw = response.binary().await?;
+    check_body_size(raw.len(), limit)?;
+    decode_response(headers.get("content-encoding").as_deref(), raw, limit)
+}
+
+async fn read_text(response: &Response, limit: u64) -> Result<String, HttpError> {
+    let body = read_body(response, limit).await?;
+    Ok(String::from_utf8_lossy(&body).into_owned())
+}
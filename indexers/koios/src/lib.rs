@@ -591,12 +591,9 @@ impl KupoApi {
 
         match self.client.get::<Vec<KupoAssetMatch>>(&url).await {
             Ok(result) => Ok(result),
-            Err(HttpError::Custom(msg)) if msg.starts_with("HTTP request failed with status:") => {
-                // Extract status code from error message
-                let status_str = msg.replace("HTTP request failed with status: ", "");
-                let status = status_str.parse::<u16>().unwrap_or(500);
-                error!("Kupo API error: {} {}", status, msg);
-                Err(KoiosError::KoiosResponse { status, body: msg })
+            Err(HttpError::Status { code, body, .. }) => {
+                error!("Kupo API error: {} {}", code, body);
+                Err(KoiosError::KoiosResponse { status: code, body })
             }
             Err(e) => Err(KoiosError::Http(e)),
         }
@@ -749,6 +746,19 @@ impl KoiosApi {
         &self,
         hashes: &[String],
     ) -> Result<Vec<KoiosTransaction>, KoiosError> {
+        self.tx_info(hashes).await
+    }
+
+    /// Unparsed [`get_tx_details`](Self::get_tx_details) response body (a
+    /// list of [`KoiosTransaction`]), e.g. for archiving
+    pub async fn get_tx_details_body(
+        &self,
+        hashes: &[String],
+    ) -> Result<serde_json::Value, KoiosError> {
+        self.tx_info(hashes).await
+    }
+
+    async fn tx_info<R: DeserializeOwned>(&self, hashes: &[String]) -> Result<R, KoiosError> {
         let url = format!("{}/tx_info?order=block_height.desc", self.base_url);
         self.post_json(
             &url,
@@ -959,12 +969,9 @@ impl KoiosApi {
 
         match self.client.get::<R>(&final_url).await {
             Ok(result) => Ok(result),
-            Err(HttpError::Custom(msg)) if msg.starts_with("HTTP request failed with status:") => {
-                // Extract status code from error message
-                let status_str = msg.replace("HTTP request failed with status: ", "");
-                let status = status_str.parse::<u16>().unwrap_or(500);
-                error!("Koios API error: {} {}", status, msg);
-                Err(KoiosError::KoiosResponse { status, body: msg })
+            Err(HttpError::Status { code, body, .. }) => {
+                error!("Koios API error: {} {}", code, body);
+                Err(KoiosError::KoiosResponse { status: code, body })
             }
             Err(e) => Err(KoiosError::Http(e)),
         }
@@ -997,12 +1004,9 @@ impl KoiosApi {
 
         match self.client.post::<T, R>(&final_url, body).await {
             Ok(result) => Ok(result),
-            Err(HttpError::Custom(msg)) if msg.starts_with("HTTP request failed with status:") => {
-                // Extract status code from error message
-                let status_str = msg.replace("HTTP request failed with status: ", "");
-                let status = status_str.parse::<u16>().unwrap_or(500);
-                error!("Koios API error: {} {}", status, msg);
-                Err(KoiosError::KoiosResponse { status, body: msg })
+            Err(HttpError::Status { code, body, .. }) => {
+                error!("Koios API error: {} {}", code, body);
+                Err(KoiosError::KoiosResponse { status: code, body })
             }
             Err(e) => Err(KoiosError::Http(e)),
         }
@@ -0,0 +1,201 @@
+//! Request counting and credit budgets
+//!
+//! Maestro bills in credits per request. Every [`MaestroApi`](crate::MaestroApi)
+//! counts the requests it sends (retries included) per endpoint and
+//! estimates the credits they cost; read the totals with
+//! [`MaestroApi::usage`](crate::MaestroApi::usage) or observe each request
+//! through [`RequestBudget::on_request`]. With a limit set, a request that
+//! would exceed it fails with [`MaestroError::BudgetExceeded`] before it is
+//! sent.
+//!
+//! ```ignore
+//! let budget = RequestBudget::new()
+//!     .with_costs(CreditCosts::default().with_cost("/txmanager", 10))
+//!     .with_limit(500)
+//!     .on_request(|event| debug!("{} -> {} credits", event.endpoint, event.total_credits));
+//! let maestro = MaestroApi::for_network(api_key, Network::Mainnet).with_budget(budget);
+//! ```
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::sync::{Arc, Mutex, PoisonError};
+
+use tracing::warn;
+
+use crate::MaestroError;
+
+/// Estimated credits per request, by endpoint prefix
+///
+/// The default of one credit per request suits most read endpoints; set
+/// your plan's prices for the heavier ones with [`with_cost`](Self::with_cost).
+#[derive(Debug, Clone)]
+pub struct CreditCosts {
+    default_cost: u64,
+    /// `(prefix, cost)`; the longest matching prefix wins
+    overrides: Vec<(String, u64)>,
+}
+
+impl Default for CreditCosts {
+    fn default() -> Self {
+        Self {
+            default_cost: 1,
+            overrides: Vec::new(),
+        }
+    }
+}
+
+impl CreditCosts {
+    pub fn with_default_cost(mut self, cost: u64) -> Self {
+        self.default_cost = cost;
+        self
+    }
+
+    /// Charge `cost` for endpoints starting with `prefix`, e.g. `/assets`
+    pub fn with_cost(mut self, prefix: impl Into<String>, cost: u64) -> Self {
+        self.overrides.push((prefix.into(), cost));
+        self
+    }
+
+    pub fn cost(&self, endpoint: &str) -> u64 {
+        self.overrides
+            .iter()
+            .filter(|(prefix, _)| endpoint.starts_with(prefix.as_str()))
+            .max_by_key(|(prefix, _)| prefix.len())
+            .map_or(self.default_cost, |(_, cost)| *cost)
+    }
+}
+
+/// Requests and credits for one endpoint
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct EndpointUsage {
+    pub requests: u64,
+    pub credits: u64,
+}
+
+/// Requests sent and credits spent since the budget was created or reset
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct UsageReport {
+    pub requests: u64,
+    pub credits: u64,
+    /// Keyed by endpoint template, e.g. `/assets/{}/txs`
+    pub by_endpoint: BTreeMap<String, EndpointUsage>,
+}
+
+/// Passed to the [`RequestBudget::on_request`] hook after each request is
+/// counted
+#[derive(Debug, Clone, Copy)]
+pub struct UsageEvent<'a> {
+    pub endpoint: &'a str,
+    pub credits: u64,
+    pub total_requests: u64,
+    pub total_credits: u64,
+}
+
+type UsageHook = Arc<dyn Fn(&UsageEvent<'_>) + Send + Sync>;
+
+/// Usage counters and an optional credit limit; clones share the counters
+#[derive(Clone, Default)]
+pub struct RequestBudget {
+    costs: CreditCosts,
+    limit: Option<u64>,
+    hook: Option<UsageHook>,
+    usage: Arc<Mutex<UsageReport>>,
+}
+
+impl fmt::Debug for RequestBudget {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RequestBudget")
+            .field("costs", &self.costs)
+            .field("limit", &self.limit)
+            .field("usage", &self.usage())
+            .finish_non_exhaustive()
+    }
+}
+
+impl RequestBudget {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_costs(mut self, costs: CreditCosts) -> Self {
+        self.costs = costs;
+        self
+    }
+
+    /// Fail requests that would take the credit total past `credits`
+    pub fn with_limit(mut self, credits: u64) -> Self {
+        self.limit = Some(credits);
+        self
+    }
+
+    /// Call `hook` after every counted request, e.g. to feed a metric
+    pub fn on_request(mut self, hook: impl Fn(&UsageEvent<'_>) + Send + Sync + 'static) -> Self {
+        self.hook = Some(Arc::new(hook));
+        self
+    }
+
+    pub fn usage(&self) -> UsageReport {
+        self.usage
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone()
+    }
+
+    /// Start counting from zero, e.g. at the start of an invocation
+    pub fn reset(&self) {
+        *self.usage.lock().unwrap_or_else(PoisonError::into_inner) = UsageReport::default();
+    }
+
+    /// Count a request to `endpoint`, or refuse it if it would exceed the limit
+    pub(crate) fn charge(&self, endpoint: &str) -> Result<(), MaestroError> {
+        let credits = self.costs.cost(endpoint);
+        let (total_requests, total_credits) = {
+            let mut usage = self.usage.lock().unwrap_or_else(PoisonError::into_inner);
+            if let Some(limit) = self.limit {
+                if usage.credits + credits > limit {
+                    warn!(
+                        "Maestro budget of {limit} credits exhausted ({} used), refusing {endpoint}",
+                        usage.credits
+                    );
+                    return Err(MaestroError::BudgetExceeded {
+                        endpoint: endpoint.to_string(),
+                        used: usage.credits,
+                        limit,
+                    });
+                }
+            }
+            usage.requests += 1;
+            usage.credits += credits;
+            let entry = usage.by_endpoint.entry(endpoint.to_string()).or_default();
+            entry.requests += 1;
+            entry.credits += credits;
+            (usage.requests, usage.credits)
+        };
+
+        if let Some(hook) = &self.hook {
+            hook(&UsageEvent {
+                endpoint,
+                credits,
+                total_requests,
+                total_credits,
+            });
+        }
+        Ok(())
+    }
+}
+
+/// Endpoint template for a request path: query dropped, and segments that
+/// look like identifiers (hashes, heights, addresses) replaced with `{}`
+pub fn endpoint_key(path: &str) -> String {
+    let path = path.split('?').next().unwrap_or_default();
+    let mut key = String::new();
+    for segment in path.split('/').filter(|segment| !segment.is_empty()) {
+        key.push('/');
+        if segment.len() >= 40 || segment.bytes().any(|b| b.is_ascii_digit()) {
+            key.push_str("{}");
+        } else {
+            key.push_str(segment);
+        }
+    }
+    key
+}
@@ -1,26 +1,48 @@
 #![allow(clippy::match_like_matches_macro)]
 
 use async_stream::stream;
+pub use cardano_assets::Network;
 use cardano_assets::{
-    asset_from_metadata_value, Asset, AssetMetadata, AssetMetadata68, AssetWithId, ExtractedCid,
-    MetadataKind, NftPurpose,
+    asset_from_metadata_value, Asset, AssetId, AssetMetadata, AssetMetadata68, AssetWithId,
+    ExtractedCid, MetadataKind, MintEvent, NftPurpose, PolicyId, Traits,
 };
 use chrono::Utc;
 use futures_core::stream::Stream;
-use http_client::HttpClient;
+use http_client::{path_segment, BaseClient, HttpClient, Query};
 use serde::{Deserialize, Deserializer, Serialize};
 use serde_json::Value;
+use std::collections::{HashMap, HashSet};
+use std::future::poll_fn;
+use std::pin::pin;
 use std::str::FromStr;
 use std::{error::Error, fmt};
 use tracing::warn;
 use worker_stack::worker;
 
+mod budget;
 mod test;
 
+pub use budget::{
+    endpoint_key, CreditCosts, EndpointUsage, RequestBudget, UsageEvent, UsageReport,
+};
+
 pub type BlockfrostAsset = serde_json::Map<String, Value>;
 
 const BASE_URL_MAINNET: &str = "mainnet.gomaestro-api.org/v1";
 const BASE_URL_PREPROD: &str = "preprod.gomaestro-api.org/v1";
+const BASE_URL_PREVIEW: &str = "preview.gomaestro-api.org/v1";
+
+/// Default number of in-flight requests for [`MaestroApi::get_assets_bulk`]
+pub const BULK_CONCURRENCY: usize = 8;
+
+/// Maestro base URL (host + version, no scheme) for a network
+pub fn base_url_for(network: Network) -> &'static str {
+    match network {
+        Network::Mainnet => BASE_URL_MAINNET,
+        Network::Preprod => BASE_URL_PREPROD,
+        Network::Preview => BASE_URL_PREVIEW,
+    }
+}
 
 #[derive(Debug, Default)]
 pub enum MaestroError {
@@ -31,6 +53,18 @@ pub enum MaestroError {
         retry_after: Option<u64>,
     },
     Deserialization(String),
+    /// Sending a request to `endpoint` would take the [`RequestBudget`]
+    /// past its credit limit
+    BudgetExceeded {
+        endpoint: String,
+        used: u64,
+        limit: u64,
+    },
+    /// A paginated endpoint handed back a cursor it had already returned,
+    /// which would otherwise loop forever
+    RepeatedCursor {
+        cursor: String,
+    },
     #[default]
     Unknown,
 }
@@ -50,6 +84,20 @@ impl fmt::Display for MaestroError {
                 None => write!(f, "Maestro rate limit exceeded"),
             },
             Self::Deserialization(input) => write!(f, "Maestro deserialization failure: {input}"),
+            Self::BudgetExceeded {
+                endpoint,
+                used,
+                limit,
+            } => write!(
+                f,
+                "Maestro budget exceeded: {used} of {limit} credits used, refusing {endpoint}"
+            ),
+            Self::RepeatedCursor { cursor } => {
+                write!(
+                    f,
+                    "Maestro returned cursor {cursor} twice, stopping pagination"
+                )
+            }
             Self::Unknown => write!(f, "Unknown Maestro error"),
         }
     }
@@ -200,6 +248,22 @@ struct AssetResult {
     asset_standards: AssetStandards,
 }
 
+impl AssetResult {
+    /// Units in circulation, when Maestro reports a parseable supply
+    fn supply(&self) -> Option<u64> {
+        self.total_supply.as_deref().and_then(|s| s.parse().ok())
+    }
+
+    /// The asset's metadata as an [`Asset`], carrying its supply as the quantity
+    fn to_asset(&self) -> Option<Asset> {
+        let asset = Asset::try_from(self.asset_standards.clone()).ok()?;
+        Some(match self.supply() {
+            Some(supply) => asset.with_quantity(supply),
+            None => asset,
+        })
+    }
+}
+
 #[derive(Deserialize, Debug)]
 struct AssetAccountsResponse {
     data: Vec<AccountQuantity>,
@@ -218,6 +282,45 @@ struct PolicyAccountsResponse {
     next_cursor: Option<String>,
 }
 
+/// One page from [`MaestroApi::owners_for_policy_pages`]
+#[derive(Debug)]
+pub struct PolicyOwnersPage {
+    pub owners: Vec<PolicyAssetOwner>,
+    /// Cursor for the next page, or `None` if this was the last page.
+    /// Persist it to resume after this page.
+    pub next_cursor: Option<String>,
+}
+
+/// Tracks the cursors a pagination loop has followed so a repeated one
+/// ends the loop with [`MaestroError::RepeatedCursor`]
+#[derive(Debug, Default)]
+pub(crate) struct CursorGuard {
+    seen: HashSet<String>,
+}
+
+impl CursorGuard {
+    /// Start from `cursor`, e.g. one restored from a checkpoint
+    pub(crate) fn starting_at(cursor: Option<&str>) -> Self {
+        Self {
+            seen: cursor.map(str::to_string).into_iter().collect(),
+        }
+    }
+
+    /// The cursor to follow next, `None` once pagination is done
+    pub(crate) fn advance(&mut self, next: Option<String>) -> Result<Option<String>, MaestroError> {
+        match next {
+            Some(cursor) if !cursor.is_empty() => {
+                if self.seen.insert(cursor.clone()) {
+                    Ok(Some(cursor))
+                } else {
+                    Err(MaestroError::RepeatedCursor { cursor })
+                }
+            }
+            _ => Ok(None),
+        }
+    }
+}
+
 #[derive(Deserialize, Debug)]
 pub struct PolicyAssetOwner {
     pub account: String,
@@ -279,6 +382,90 @@ pub struct PolicyTransactionPage {
     pub next_cursor: Option<String>,
 }
 
+/// A CIP-68 reference NFT involved in a policy transaction. Moving the
+/// reference token is how its datum (the user token's metadata) is replaced.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReferenceTouch {
+    /// Reference token (label 100) asset name, hex
+    pub reference_asset_name: String,
+    /// User token (label 222, 333 or 444) asset name the datum describes, hex
+    pub user_asset_name: String,
+    /// Latest transaction involving the reference token
+    pub tx_hash: String,
+    pub slot: u64,
+}
+
+/// Reference tokens involved in `transactions`, each with its latest
+/// transaction, ordered by slot
+///
+/// The user token is whichever of the 222, 333 or 444 tokens sharing the
+/// reference token's name appears in `transactions`; label 222 if none does.
+pub fn reference_touches(transactions: &[PolicyTransaction]) -> Vec<ReferenceTouch> {
+    let mut latest: HashMap<&str, &PolicyTransaction> = HashMap::new();
+    let mut seen: HashSet<&str> = HashSet::new();
+    for tx in transactions {
+        for asset_name in &tx.assets {
+            seen.insert(asset_name);
+            if !asset_name.starts_with(cip67_prefix::REFERENCE_NFT) {
+                continue;
+            }
+            let entry = latest.entry(asset_name).or_insert(tx);
+            if tx.slot > entry.slot {
+                *entry = tx;
+            }
+        }
+    }
+
+    let mut touches: Vec<_> = latest
+        .into_iter()
+        .map(|(asset_name, tx)| {
+            let name = &asset_name[cip67_prefix::REFERENCE_NFT.len()..];
+            let user_asset_name = cip67_prefix::USER_TOKENS
+                .iter()
+                .map(|prefix| format!("{prefix}{name}"))
+                .find(|user| seen.contains(user.as_str()))
+                .unwrap_or_else(|| format!("{}{name}", cip67_prefix::USER_NFT));
+            ReferenceTouch {
+                reference_asset_name: asset_name.to_string(),
+                user_asset_name,
+                tx_hash: tx.tx_hash.clone(),
+                slot: tx.slot,
+            }
+        })
+        .collect();
+    touches
+        .sort_by(|a, b| (a.slot, &a.reference_asset_name).cmp(&(b.slot, &b.reference_asset_name)));
+    touches
+}
+
+/// Current metadata of a reference token touched since the requested slot
+#[derive(Debug)]
+pub struct ReferenceDatumUpdate {
+    pub touch: ReferenceTouch,
+    /// Asset built from the current datum, `None` if it doesn't parse
+    pub asset: Option<Asset>,
+}
+
+impl ReferenceDatumUpdate {
+    /// Whether the current datum's traits differ from `stored`, i.e. the
+    /// asset needs re-scoring
+    pub fn traits_changed(&self, stored: &Traits) -> bool {
+        self.asset
+            .as_ref()
+            .is_some_and(|asset| asset.traits != *stored)
+    }
+}
+
+/// Result of [`MaestroApi::get_recent_datum_updates`]
+#[derive(Debug)]
+pub struct DatumUpdates {
+    pub updates: Vec<ReferenceDatumUpdate>,
+    /// Tokens whose metadata couldn't be fetched; retry them next poll
+    pub failed: Vec<(ReferenceTouch, MaestroError)>,
+    /// Pass `last_updated.block_slot` as `since_slot` on the next poll
+    pub last_updated: LastUpdated,
+}
+
 /// CIP-67 asset name prefixes for on-chain token classification.
 mod cip67_prefix {
     /// CIP-68 Reference NFT (label 100)
@@ -289,6 +476,8 @@ mod cip67_prefix {
     pub const FUNGIBLE_TOKEN: &str = "0014df10";
     /// CIP-68 Rich Fungible Token / Semi-Fungible (label 444)
     pub const RICH_FUNGIBLE: &str = "001bc280";
+    /// User token labels a reference token can describe
+    pub const USER_TOKENS: [&str; 3] = [USER_NFT, FUNGIBLE_TOKEN, RICH_FUNGIBLE];
 }
 
 /// Result of classifying a policy's assets.
@@ -374,15 +563,7 @@ impl PolicyAssetsResponse {
         }
 
         // 3. Supply + asset count heuristics
-        let supplies: Vec<u64> = self
-            .data
-            .iter()
-            .filter_map(|a| {
-                a.total_supply
-                    .as_deref()
-                    .and_then(|s| s.parse::<u64>().ok())
-            })
-            .collect();
+        let supplies: Vec<u64> = self.data.iter().filter_map(AssetResult::supply).collect();
 
         let max_supply = supplies.iter().copied().max().unwrap_or(0);
 
@@ -429,6 +610,16 @@ struct AssetInfoResponse {
     data: DetailedAssetInfo,
 }
 
+/// Result of [`MaestroApi::get_assets_bulk`]
+///
+/// Per-asset failures don't abort the batch; they're returned in `failed` so
+/// an import can retry just those ids later.
+#[derive(Debug, Default)]
+pub struct BulkAssetInfo {
+    pub assets: HashMap<AssetId, DetailedAssetInfo>,
+    pub failed: Vec<(AssetId, MaestroError)>,
+}
+
 #[derive(Deserialize, Debug)]
 pub struct DetailedAssetInfo {
     pub asset_name: String,
@@ -442,7 +633,7 @@ impl TryFrom<DetailedAssetInfo> for Asset {
     type Error = MaestroError;
 
     fn try_from(value: DetailedAssetInfo) -> Result<Self, Self::Error> {
-        Asset::try_from(value.asset_standards)
+        Asset::try_from(value.asset_standards).map(|asset| asset.with_quantity(value.total_supply))
     }
 }
 
@@ -455,6 +646,23 @@ pub struct MintTx {
     pub amount: String,
 }
 
+impl MintTx {
+    /// As a [`SupplyLedger`](cardano_assets::SupplyLedger) event; burns have a
+    /// negative `amount`
+    pub fn to_event(&self, asset_id: &AssetId) -> Result<MintEvent, MaestroError> {
+        let quantity = self.amount.parse::<i64>().map_err(|e| {
+            MaestroError::Deserialization(format!("Invalid mint amount {}: {e}", self.amount))
+        })?;
+        Ok(MintEvent::new(asset_id.clone(), quantity).at_slot(self.slot))
+    }
+}
+
+#[derive(Deserialize, Debug)]
+struct AssetMintsResponse {
+    data: Vec<MintTx>,
+    next_cursor: Option<String>,
+}
+
 // Transaction data structures for tx-classifier
 #[derive(Deserialize, Debug)]
 struct TransactionResponse {
@@ -481,8 +689,8 @@ pub struct DatumData {
 }
 
 #[derive(Deserialize, Debug)]
-struct DatumsByHashesResponse {
-    data: std::collections::HashMap<String, DatumData>,
+pub struct DatumsByHashesResponse {
+    pub data: std::collections::HashMap<String, DatumData>,
 }
 
 /// One resolved output from `POST /transactions/outputs`.
@@ -839,6 +1047,120 @@ pub struct BlockInfo {
     pub block_slot: u64,
 }
 
+#[derive(Deserialize, Debug)]
+struct ChainTipResponse {
+    data: ChainTip,
+}
+
+/// Latest block on Maestro's view of the chain
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct ChainTip {
+    pub block_hash: String,
+    #[serde(with = "wasm_safe_serde::u64_required")]
+    pub height: u64,
+    #[serde(with = "wasm_safe_serde::u64_required")]
+    pub slot: u64,
+}
+
+#[derive(Deserialize, Debug)]
+struct BlockResponse {
+    data: Block,
+}
+
+/// A block and the hashes of the transactions in it
+#[derive(Deserialize, Debug, Clone)]
+pub struct Block {
+    pub hash: String,
+    #[serde(with = "wasm_safe_serde::u64_required")]
+    pub height: u64,
+    #[serde(with = "wasm_safe_serde::u64_required")]
+    pub absolute_slot: u64,
+    pub epoch: u32,
+    pub epoch_slot: u32,
+    /// UTC timestamp, e.g. `2024-07-29 20:38:11`
+    pub timestamp: String,
+    #[serde(default)]
+    pub tx_hashes: Vec<String>,
+    /// `None` only for the genesis block
+    pub previous_block: Option<String>,
+    #[serde(default)]
+    pub block_producer: Option<String>,
+    #[serde(default)]
+    pub era: Option<String>,
+}
+
+/// Block lookup key for [`MaestroApi::get_block`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BlockRef {
+    Hash(String),
+    Height(u64),
+}
+
+impl fmt::Display for BlockRef {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Hash(hash) => write!(f, "{hash}"),
+            Self::Height(height) => write!(f, "{height}"),
+        }
+    }
+}
+
+impl From<u64> for BlockRef {
+    fn from(height: u64) -> Self {
+        Self::Height(height)
+    }
+}
+
+impl From<&str> for BlockRef {
+    fn from(hash: &str) -> Self {
+        Self::Hash(hash.to_string())
+    }
+}
+
+impl From<String> for BlockRef {
+    fn from(hash: String) -> Self {
+        Self::Hash(hash)
+    }
+}
+
+/// Polling behaviour for [`MaestroApi::poll_new_blocks_with`]
+///
+/// Mainnet produces a block every ~20s on average, with long gaps being
+/// common. The interval starts at `min_interval_ms`, grows by half after every
+/// poll that finds nothing new (capped at `max_interval_ms`), and drops back
+/// to the minimum as soon as a block arrives. Errors also back off.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BlockPollConfig {
+    pub min_interval_ms: u32,
+    pub max_interval_ms: u32,
+    /// Most blocks fetched to catch up from `since_slot` on the first poll or
+    /// after a long pause; older blocks are skipped with a warning
+    pub max_catch_up: u32,
+}
+
+impl Default for BlockPollConfig {
+    fn default() -> Self {
+        Self {
+            min_interval_ms: 5_000,
+            max_interval_ms: 60_000,
+            max_catch_up: 20,
+        }
+    }
+}
+
+impl BlockPollConfig {
+    /// Delay before the poll after one that waited `current_ms`
+    pub fn next_interval(&self, current_ms: u32, found_blocks: bool) -> u32 {
+        if found_blocks {
+            self.min_interval_ms
+        } else {
+            current_ms
+                .saturating_add(current_ms / 2)
+                .clamp(self.min_interval_ms, self.max_interval_ms)
+        }
+    }
+}
+
 #[allow(dead_code)]
 #[derive(Deserialize, Debug)]
 struct PaymentCredential {
@@ -857,6 +1179,56 @@ struct StakingCredential {
     pointer: Option<serde_json::Value>,
 }
 
+// ─── Stake account history types ───────────────────────────────────────
+
+#[derive(Deserialize, Debug)]
+struct AccountHistoryResponse {
+    data: Vec<AccountEpochStake>,
+    next_cursor: Option<String>,
+}
+
+/// Delegation snapshot of a stake account for one epoch.
+#[derive(Deserialize, Debug, Clone)]
+pub struct AccountEpochStake {
+    pub epoch_no: u32,
+    /// Lovelace counted towards the pool's stake in this epoch
+    #[serde(deserialize_with = "deserialize_u64_string")]
+    pub active_stake: u64,
+    /// Pool the account was delegated to, `None` if undelegated
+    pub pool_id: Option<String>,
+}
+
+#[derive(Deserialize, Debug)]
+struct AccountRewardsResponse {
+    data: Vec<AccountReward>,
+    next_cursor: Option<String>,
+}
+
+/// A reward paid to a stake account.
+#[derive(Deserialize, Debug, Clone)]
+pub struct AccountReward {
+    /// Epoch the reward was earned in
+    pub earned_epoch: u32,
+    /// Epoch from which the reward can be withdrawn
+    pub spendable_epoch: u32,
+    /// Reward in lovelace
+    #[serde(deserialize_with = "deserialize_u64_string")]
+    pub amount: u64,
+    /// Pool that paid the reward, `None` for non-pool rewards (refunds, treasury)
+    pub pool_id: Option<String>,
+    /// Reward source as reported by Maestro (`member`, `leader`, `refund`, ...)
+    #[serde(rename = "type")]
+    pub kind: String,
+}
+
+/// Ascending, max page size, plus the cursor for follow-up pages
+fn account_page_query(cursor: Option<&str>) -> Query {
+    Query::new()
+        .push("order", "asc")
+        .push("count", 100)
+        .push_opt("cursor", cursor)
+}
+
 pub enum EpochTarget {
     Current,
     Specific(u32),
@@ -989,80 +1361,246 @@ pub struct AdditionalUtxo {
 }
 
 pub struct MaestroApi {
-    client: HttpClient,
+    api: BaseClient,
     #[allow(dead_code)] // read in submit_transaction behind cfg(feature = "transactions")
     api_key: String,
-    base_url: String,
+    budget: RequestBudget,
 }
 
 impl MaestroApi {
     /// Create a new MaestroApi instance with the provided API key and base URL
     pub fn new(api_key: String, base_url: String) -> Self {
         Self {
-            client: HttpClient::new().with_header("api-key", &api_key),
-            api_key: api_key.clone(),
-            base_url,
+            api: BaseClient::new(&base_url).with_header("api-key", &api_key),
+            api_key,
+            budget: RequestBudget::default(),
         }
     }
 
+    /// Create a MaestroApi instance pointed at the given network's endpoint
+    pub fn for_network(api_key: String, network: Network) -> Self {
+        Self::new(api_key, base_url_for(network).to_string())
+    }
+
+    /// Use a preconfigured [`HttpClient`] (e.g. one replaying test fixtures).
+    ///
+    /// The replacement client must carry its own `api-key` header if it talks
+    /// to the live API.
+    pub fn with_http_client(mut self, client: HttpClient) -> Self {
+        self.api = self.api.with_http_client(client);
+        self
+    }
+
+    /// Count requests against `budget` instead of a fresh, unlimited one
+    pub fn with_budget(mut self, budget: RequestBudget) -> Self {
+        self.budget = budget;
+        self
+    }
+
+    /// Requests and estimated credits so far (see [`RequestBudget`])
+    pub fn usage(&self) -> UsageReport {
+        self.budget.usage()
+    }
+
+    /// Count a request to `url` against the budget
+    fn charge(&self, url: &str) -> Result<(), MaestroError> {
+        let path = self.api.path_of(url).unwrap_or(url);
+        self.budget.charge(&endpoint_key(path))
+    }
+
     #[deprecated(note = "use for_env_with_network instead")]
     pub async fn for_env(env: &worker::Env) -> worker::Result<Self> {
         let api_key = worker_utils::secrets::get_secret(env, "MAESTRO_API_KEY").await?;
         Ok(Self {
-            client: HttpClient::new().with_header("api-key", &api_key),
-            api_key: api_key.clone(),
-            base_url: BASE_URL_MAINNET.to_string(),
+            api: BaseClient::new(BASE_URL_MAINNET).with_header("api-key", &api_key),
+            api_key,
+            budget: RequestBudget::default(),
         })
     }
 
     /// Create MaestroApi from environment with network selection
     ///
-    /// Selects the correct API key and base URL for the network:
+    /// `network` is a chain id such as `cardano:mainnet` or `cardano:preprod`
+    /// (`cardano:testnet` is treated as preprod). Selects the API key and base URL:
     /// - Mainnet: MAESTRO_API_KEY_MAINNET with mainnet.gomaestro-api.org
-    /// - Testnet: MAESTRO_API_KEY_TESTNET with preview.gomaestro-api.org
+    /// - Preprod / Preview: MAESTRO_API_KEY_TESTNET with the matching gomaestro-api.org host
     pub async fn for_env_with_network(env: &worker::Env, network: &str) -> worker::Result<Self> {
-        let (secret_name, base_url) = match network {
-            "cardano:mainnet" => ("MAESTRO_API_KEY_MAINNET", BASE_URL_MAINNET),
-            "cardano:testnet" | "cardano:preview" | "cardano:preprod" => {
-                ("MAESTRO_API_KEY_TESTNET", BASE_URL_PREPROD)
-            }
-            _ => {
-                return Err(worker::Error::RustError(format!(
-                    "Unsupported network for Maestro: {network}"
-                )))
-            }
+        let network = Network::from_str(network).map_err(|_| {
+            worker::Error::RustError(format!("Unsupported network for Maestro: {network}"))
+        })?;
+        Self::for_env_network(env, network).await
+    }
+
+    /// Typed variant of [`for_env_with_network`](Self::for_env_with_network)
+    pub async fn for_env_network(env: &worker::Env, network: Network) -> worker::Result<Self> {
+        let secret_name = if network.is_mainnet() {
+            "MAESTRO_API_KEY_MAINNET"
+        } else {
+            "MAESTRO_API_KEY_TESTNET"
         };
 
         let api_key = worker_utils::secrets::get_secret(env, secret_name).await?;
 
-        Ok(Self {
-            client: HttpClient::new().with_header("api-key", &api_key),
-            api_key: api_key.clone(),
-            base_url: base_url.to_string(),
-        })
+        Ok(Self::for_network(api_key, network))
     }
 
     pub async fn get_epoch(&self, target: EpochTarget) -> Result<EpochDetails, MaestroError> {
         let url = match target {
-            EpochTarget::Current => format!("https://{}/epochs/current", self.base_url),
-            EpochTarget::Specific(epoch) => format!("https://{}/epochs/{epoch}", self.base_url),
+            EpochTarget::Current => self.api.url("/epochs/current"),
+            EpochTarget::Specific(epoch) => self.api.url(&format!("/epochs/{epoch}")),
         };
 
         let response: EpochResponse = self.get_url(url).await?;
         Ok(response.data)
     }
 
+    /// Latest block Maestro has indexed
+    pub async fn get_chain_tip(&self) -> Result<ChainTip, MaestroError> {
+        let url = self.api.url("/chain-tip");
+        let response: ChainTipResponse = self.get_url(url).await?;
+        Ok(response.data)
+    }
+
+    /// Get a block by hash or height
+    pub async fn get_block(&self, block: impl Into<BlockRef>) -> Result<Block, MaestroError> {
+        let block = path_segment(&block.into().to_string());
+        let url = self.api.url(&format!("/blocks/{block}"));
+        let response: BlockResponse = self.get_url(url).await?;
+        Ok(response.data)
+    }
+
+    /// Follow the chain, yielding every block after `since_slot` in order.
+    ///
+    /// Uses [`BlockPollConfig::default`]; see [`poll_new_blocks_with`](Self::poll_new_blocks_with).
+    pub fn poll_new_blocks(
+        &self,
+        since_slot: u64,
+    ) -> impl Stream<Item = Result<Block, MaestroError>> + '_ {
+        self.poll_new_blocks_with(since_slot, BlockPollConfig::default())
+    }
+
+    /// Follow the chain, yielding every block after `since_slot` in order.
+    ///
+    /// A lightweight alternative to running Oura: filter each block's
+    /// `tx_hashes` (or fetch the transactions) for the policies of interest.
+    /// The stream never ends; request errors are yielded and polling carries
+    /// on after backing off. Rollbacks aren't replayed — a block whose
+    /// `previous_block` doesn't match the last one yielded is logged and
+    /// followed anyway.
+    pub fn poll_new_blocks_with(
+        &self,
+        since_slot: u64,
+        config: BlockPollConfig,
+    ) -> impl Stream<Item = Result<Block, MaestroError>> + '_ {
+        stream! {
+            let mut last: Option<(u64, String)> = None;
+            let mut interval = config.min_interval_ms;
+
+            loop {
+                let mut found_blocks = false;
+
+                match self.get_chain_tip().await {
+                    Err(e) => yield Err(e),
+                    Ok(tip) if tip.slot <= since_slot => {}
+                    Ok(tip) => {
+                        let pending = match &last {
+                            Some((height, _)) => self.blocks_after_height(*height, &tip, config).await,
+                            None => self.blocks_after_slot(since_slot, &tip, config).await,
+                        };
+
+                        match pending {
+                            Ok(blocks) => {
+                                for block in blocks {
+                                    if let Some((_, hash)) = &last {
+                                        if block.previous_block.as_deref() != Some(hash.as_str()) {
+                                            warn!(
+                                                "Block {} at height {} doesn't follow {hash}, possible rollback",
+                                                block.hash, block.height
+                                            );
+                                        }
+                                    }
+                                    found_blocks = true;
+                                    last = Some((block.height, block.hash.clone()));
+                                    yield Ok(block);
+                                }
+                            }
+                            Err(e) => yield Err(e),
+                        }
+                    }
+                }
+
+                interval = config.next_interval(interval, found_blocks);
+                worker_utils::sleep::sleep(interval as i32).await;
+            }
+        }
+    }
+
+    /// Blocks above `height` up to `tip`, oldest first
+    async fn blocks_after_height(
+        &self,
+        height: u64,
+        tip: &ChainTip,
+        config: BlockPollConfig,
+    ) -> Result<Vec<Block>, MaestroError> {
+        let mut from = height + 1;
+        let limit = u64::from(config.max_catch_up.max(1));
+        if tip.height >= from + limit {
+            warn!(
+                "Skipping {} blocks to catch up with the chain tip",
+                tip.height + 1 - from - limit
+            );
+            from = tip.height + 1 - limit;
+        }
+
+        let mut blocks = Vec::new();
+        for height in from..=tip.height {
+            blocks.push(self.get_block(height).await?);
+        }
+        Ok(blocks)
+    }
+
+    /// Blocks after `slot` up to `tip`, oldest first, walking back from the tip
+    async fn blocks_after_slot(
+        &self,
+        slot: u64,
+        tip: &ChainTip,
+        config: BlockPollConfig,
+    ) -> Result<Vec<Block>, MaestroError> {
+        let mut blocks = Vec::new();
+        let mut next = Some(BlockRef::Hash(tip.block_hash.clone()));
+
+        while let Some(block_ref) = next.take() {
+            let block = self.get_block(block_ref).await?;
+            if block.absolute_slot <= slot {
+                break;
+            }
+            if blocks.len() >= config.max_catch_up.max(1) as usize {
+                warn!(
+                    "More than {} blocks since slot {slot}, skipping older blocks",
+                    config.max_catch_up
+                );
+                break;
+            }
+            next = block.previous_block.clone().map(BlockRef::Hash);
+            blocks.push(block);
+        }
+
+        blocks.reverse();
+        Ok(blocks)
+    }
+
     /// Get current protocol parameters
     /// Essential for fee calculation, min UTxO values, and transaction building
     pub async fn get_protocol_parameters(&self) -> Result<ProtocolParameters, MaestroError> {
-        let url = format!("https://{}/protocol-parameters", self.base_url);
+        let url = self.api.url("/protocol-parameters");
         let response: ProtocolParametersResponse = self.get_url(url).await?;
         Ok(response.data)
     }
 
     /// Get transaction details by hash
     pub async fn get_transaction(&self, tx_hash: &str) -> Result<TransactionDetails, MaestroError> {
-        let url = format!("https://{}/transactions/{tx_hash}", self.base_url);
+        let tx_hash = path_segment(tx_hash);
+        let url = self.api.url(&format!("/transactions/{tx_hash}"));
         let response: TransactionResponse = self.get_url(url).await?;
         Ok(response.data)
     }
@@ -1072,7 +1610,8 @@ impl MaestroApi {
         &self,
         tx_hash: &str,
     ) -> Result<TransactionUtxos, MaestroError> {
-        let url = format!("https://{}/transactions/{tx_hash}/utxos", self.base_url);
+        let tx_hash = path_segment(tx_hash);
+        let url = self.api.url(&format!("/transactions/{tx_hash}/utxos"));
         let response: TransactionUtxosResponse = self.get_url(url).await?;
         Ok(response.data)
     }
@@ -1080,7 +1619,8 @@ impl MaestroApi {
     /// Get transaction CBOR data (for faster parsing)
     /// Returns hex-encoded CBOR that can be parsed directly with pallas
     pub async fn get_transaction_cbor(&self, tx_hash: &str) -> Result<String, MaestroError> {
-        let url = format!("https://{}/transactions/{tx_hash}/cbor", self.base_url);
+        let tx_hash = path_segment(tx_hash);
+        let url = self.api.url(&format!("/transactions/{tx_hash}/cbor"));
         let response: TransactionCborResponse = self.get_url(url).await?;
         Ok(response.data)
     }
@@ -1088,7 +1628,8 @@ impl MaestroApi {
     /// Get a single datum by its hash
     /// Returns CBOR bytes and optional JSON representation
     pub async fn get_datum_by_hash(&self, datum_hash: &str) -> Result<DatumData, MaestroError> {
-        let url = format!("https://{}/datums/{datum_hash}", self.base_url);
+        let datum_hash = path_segment(datum_hash);
+        let url = self.api.url(&format!("/datums/{datum_hash}"));
         let response: DatumByHashResponse = self.get_url(url).await?;
         Ok(response.data)
     }
@@ -1099,12 +1640,28 @@ impl MaestroApi {
         &self,
         datum_hashes: &[&str],
     ) -> Result<std::collections::HashMap<String, DatumData>, MaestroError> {
-        let url = format!("https://{}/datums", self.base_url);
+        let response: DatumsByHashesResponse = self.post_datums(datum_hashes).await?;
+        Ok(response.data)
+    }
+
+    /// Unparsed [`get_datums_by_hashes`](Self::get_datums_by_hashes) response
+    /// body (a [`DatumsByHashesResponse`]), e.g. for archiving
+    pub async fn get_datums_by_hashes_body(
+        &self,
+        datum_hashes: &[&str],
+    ) -> Result<serde_json::Value, MaestroError> {
+        self.post_datums(datum_hashes).await
+    }
+
+    async fn post_datums<T: serde::de::DeserializeOwned>(
+        &self,
+        datum_hashes: &[&str],
+    ) -> Result<T, MaestroError> {
+        let url = self.api.url("/datums");
         let body = serde_json::to_value(datum_hashes).map_err(|e| {
             MaestroError::Deserialization(format!("Failed to serialize datum hashes: {e}"))
         })?;
-        let response: DatumsByHashesResponse = self.post_url(url, &body).await?;
-        Ok(response.data)
+        self.post_url(url, &body).await
     }
 
     /// Resolve transaction outputs by `"tx_hash#index"` reference in one
@@ -1116,10 +1673,10 @@ impl MaestroApi {
         refs: &[String],
         allow_spent: bool,
     ) -> Result<Vec<ResolvedTxOut>, MaestroError> {
-        let url = format!(
-            "https://{}/transactions/outputs?resolve_datums=true&allow_spent={allow_spent}",
-            self.base_url
-        );
+        let query = Query::new()
+            .push("resolve_datums", true)
+            .push("allow_spent", allow_spent);
+        let url = self.api.url_with_query("/transactions/outputs", &query);
         let body = serde_json::to_value(refs).map_err(|e| {
             MaestroError::Deserialization(format!("Failed to serialize output refs: {e}"))
         })?;
@@ -1133,15 +1690,29 @@ impl MaestroApi {
         &self,
         tx_hash: &str,
     ) -> Result<CompleteTransactionDetails, MaestroError> {
-        let url = format!("https://{}/transactions/{tx_hash}", self.base_url);
+        let tx_hash = path_segment(tx_hash);
+        let url = self.api.url(&format!("/transactions/{tx_hash}"));
         let response: CompleteTransactionResponse = self.get_url(url).await?;
         Ok(response.data)
     }
 
+    /// Unparsed [`get_complete_transaction`](Self::get_complete_transaction)
+    /// response body (a [`CompleteTransactionResponse`]), e.g. for archiving
+    #[cfg(feature = "transactions")]
+    pub async fn get_complete_transaction_body(
+        &self,
+        tx_hash: &str,
+    ) -> Result<serde_json::Value, MaestroError> {
+        let tx_hash = path_segment(tx_hash);
+        let url = self.api.url(&format!("/transactions/{tx_hash}"));
+        self.get_url(url).await
+    }
+
     /// Get first page of UTxOs at a specific address (for wallet operations and transaction building)
     #[cfg(feature = "transactions")]
     pub async fn get_address_utxos(&self, address: &str) -> Result<Vec<AddressUtxo>, MaestroError> {
-        let url = format!("https://{}/addresses/{address}/utxos", self.base_url);
+        let address = path_segment(address);
+        let url = self.api.url(&format!("/addresses/{address}/utxos"));
         let response: AddressUtxosResponse = self.get_url(url).await?;
         Ok(response.data)
     }
@@ -1152,18 +1723,15 @@ impl MaestroApi {
         &self,
         address: &str,
     ) -> Result<Vec<AddressUtxo>, MaestroError> {
+        let address = path_segment(address);
         let mut all_utxos = Vec::new();
         let mut cursor: Option<String> = None;
 
         loop {
-            let querystring = match &cursor {
-                Some(c) => format!("?cursor={c}"),
-                None => String::new(),
-            };
-            let url = format!(
-                "https://{}/addresses/{address}/utxos{querystring}",
-                self.base_url
-            );
+            let query = Query::new().push_opt("cursor", cursor.as_ref());
+            let url = self
+                .api
+                .url_with_query(&format!("/addresses/{address}/utxos"), &query);
             let response: AddressUtxosResponse = self.get_url(url).await?;
             all_utxos.extend(response.data);
 
@@ -1183,18 +1751,15 @@ impl MaestroApi {
         &self,
         credential: &str,
     ) -> Result<Vec<AddressUtxo>, MaestroError> {
+        let credential = path_segment(credential);
         let mut all_utxos = Vec::new();
         let mut cursor: Option<String> = None;
 
         loop {
-            let querystring = match &cursor {
-                Some(c) => format!("?cursor={c}"),
-                None => String::new(),
-            };
-            let url = format!(
-                "https://{}/addresses/cred/{credential}/utxos{querystring}",
-                self.base_url
-            );
+            let query = Query::new().push_opt("cursor", cursor.as_ref());
+            let url = self
+                .api
+                .url_with_query(&format!("/addresses/cred/{credential}/utxos"), &query);
             let response: AddressUtxosResponse = self.get_url(url).await?;
             all_utxos.extend(response.data);
 
@@ -1216,7 +1781,8 @@ impl MaestroApi {
     pub async fn submit_transaction(&self, tx_cbor_hex: &str) -> Result<String, MaestroError> {
         use worker_stack::worker;
 
-        let url = format!("https://{}/txmanager", self.base_url);
+        let url = self.api.url("/txmanager");
+        self.charge(&url)?;
 
         // Decode hex to bytes for CBOR submission
         let tx_bytes = hex::decode(tx_cbor_hex)
@@ -1230,12 +1796,12 @@ impl MaestroApi {
         headers
             .set("Content-Type", "application/cbor")
             .map_err(|e| {
-                MaestroError::Http(http_client::HttpError::Custom(format!(
+                MaestroError::Http(http_client::HttpError::Builder(format!(
                     "Failed to set header: {e:?}"
                 )))
             })?;
         headers.set("api-key", api_key).map_err(|e| {
-            MaestroError::Http(http_client::HttpError::Custom(format!(
+            MaestroError::Http(http_client::HttpError::Builder(format!(
                 "Failed to set header: {e:?}"
             )))
         })?;
@@ -1246,30 +1812,31 @@ impl MaestroApi {
         init.body = Some(tx_bytes.into());
 
         let request = worker::Request::new_with_init(&url, &init).map_err(|e| {
-            MaestroError::Http(http_client::HttpError::Custom(format!(
+            MaestroError::Http(http_client::HttpError::Builder(format!(
                 "Failed to create request: {e:?}"
             )))
         })?;
 
         let mut response = worker::Fetch::Request(request).send().await.map_err(|e| {
-            MaestroError::Http(http_client::HttpError::Custom(format!(
+            MaestroError::Http(http_client::HttpError::Network(format!(
                 "Fetch failed: {e:?}"
             )))
         })?;
 
         // Check for 202 Accepted status
         if response.status_code() != 202 {
-            let error_text = response.text().await.unwrap_or_default();
-            return Err(MaestroError::Http(http_client::HttpError::Custom(format!(
-                "Transaction submission failed (status {}): {}",
-                response.status_code(),
-                error_text
-            ))));
+            let code = response.status_code();
+            let body = response.text().await.unwrap_or_default();
+            return Err(MaestroError::Http(http_client::HttpError::Status {
+                code,
+                headers: HashMap::new(),
+                body,
+            }));
         }
 
         // Response body is the transaction hash as plain text
         let tx_hash = response.text().await.map_err(|e| {
-            MaestroError::Http(http_client::HttpError::Custom(format!(
+            MaestroError::Http(http_client::HttpError::Network(format!(
                 "Failed to read response: {e:?}"
             )))
         })?;
@@ -1286,7 +1853,7 @@ impl MaestroApi {
         tx_cbor_hex: &str,
         additional_utxos: Option<&[AdditionalUtxo]>,
     ) -> Result<Vec<EvaluateRedeemerResult>, MaestroError> {
-        let url = format!("https://{}/transactions/evaluate", self.base_url);
+        let url = self.api.url("/transactions/evaluate");
 
         let mut body = serde_json::Map::new();
         body.insert(
@@ -1311,7 +1878,8 @@ impl MaestroApi {
         &self,
         tx_hash: &str,
     ) -> Result<TransactionState, MaestroError> {
-        let url = format!("https://{}/txmanager/{tx_hash}/state", self.base_url);
+        let tx_hash = path_segment(tx_hash);
+        let url = self.api.url(&format!("/txmanager/{tx_hash}/state"));
         let response: TransactionStateResponse = self.get_url(url).await?;
         Ok(response.data)
     }
@@ -1324,23 +1892,8 @@ impl MaestroApi {
         page: Option<u32>,
         count: Option<u32>,
     ) -> Result<TransactionHistory, MaestroError> {
-        let mut query_params = Vec::new();
-
-        if let Some(p) = page {
-            query_params.push(format!("page={p}"));
-        }
-
-        if let Some(c) = count {
-            query_params.push(format!("count={c}"));
-        }
-
-        let query_string = if query_params.is_empty() {
-            String::new()
-        } else {
-            format!("?{}", query_params.join("&"))
-        };
-
-        let url = format!("https://{}/txmanager/history{query_string}", self.base_url);
+        let query = Query::new().push_opt("page", page).push_opt("count", count);
+        let url = self.api.url_with_query("/txmanager/history", &query);
         let response: TransactionHistoryResponse = self.get_url(url).await?;
         Ok(response.data)
     }
@@ -1351,37 +1904,21 @@ impl MaestroApi {
     /// Use `cursor` to paginate through results.
     pub async fn get_policy_transactions(
         &self,
-        policy_id: &str,
+        policy_id: &PolicyId,
         from_slot: Option<u64>,
         count: Option<u32>,
         order: Option<&str>,
         cursor: Option<&str>,
     ) -> Result<PolicyTransactionPage, MaestroError> {
-        let mut query_params = Vec::new();
-
-        if let Some(slot) = from_slot {
-            query_params.push(format!("from={slot}"));
-        }
-        if let Some(c) = count {
-            query_params.push(format!("count={c}"));
-        }
-        if let Some(o) = order {
-            query_params.push(format!("order={o}"));
-        }
-        if let Some(c) = cursor {
-            query_params.push(format!("cursor={c}"));
-        }
-
-        let querystring = if query_params.is_empty() {
-            String::new()
-        } else {
-            format!("?{}", query_params.join("&"))
-        };
-
-        let url = format!(
-            "https://{}/policy/{policy_id}/transactions{querystring}",
-            self.base_url
-        );
+        let policy_id = path_segment(policy_id.as_str());
+        let query = Query::new()
+            .push_opt("from", from_slot)
+            .push_opt("count", count)
+            .push_opt("order", order)
+            .push_opt("cursor", cursor);
+        let url = self
+            .api
+            .url_with_query(&format!("/policy/{policy_id}/transactions"), &query);
 
         let response: PolicyTransactionsResponse = self.get_url(url).await?;
         Ok(PolicyTransactionPage {
@@ -1398,7 +1935,7 @@ impl MaestroApi {
     /// to avoid re-processing.
     pub async fn get_all_policy_transactions(
         &self,
-        policy_id: &str,
+        policy_id: &PolicyId,
         from_slot: Option<u64>,
     ) -> Result<(Vec<PolicyTransaction>, LastUpdated), MaestroError> {
         let mut all_txs = Vec::new();
@@ -1430,12 +1967,63 @@ impl MaestroApi {
         Ok((all_txs, last_updated))
     }
 
+    /// CIP-68 reference datums that may have changed at or after `since_slot`.
+    ///
+    /// Every reference token moved in the policy's transactions since then
+    /// (including at mint) has its user token's metadata fetched again, with
+    /// at most [`BULK_CONCURRENCY`] requests in flight. Check each against
+    /// the stored traits with [`ReferenceDatumUpdate::traits_changed`].
+    pub async fn get_recent_datum_updates(
+        &self,
+        policy_id: &PolicyId,
+        since_slot: u64,
+    ) -> Result<DatumUpdates, MaestroError> {
+        let (transactions, last_updated) = self
+            .get_all_policy_transactions(policy_id, Some(since_slot))
+            .await?;
+
+        let results = worker_utils::join_bounded(
+            reference_touches(&transactions)
+                .into_iter()
+                .map(|touch| async move {
+                    let result = self.get_detailed(&touch.user_asset_name, policy_id).await;
+                    (touch, result)
+                }),
+            BULK_CONCURRENCY,
+        )
+        .await;
+
+        let mut output = DatumUpdates {
+            updates: Vec::new(),
+            failed: Vec::new(),
+            last_updated,
+        };
+        for (touch, result) in results {
+            match result {
+                Ok(info) => output.updates.push(ReferenceDatumUpdate {
+                    asset: Asset::try_from(info).ok(),
+                    touch,
+                }),
+                Err(err) => {
+                    warn!(
+                        "Failed to fetch datum for {policy_id}{}: {err}",
+                        touch.reference_asset_name
+                    );
+                    output.failed.push((touch, err));
+                }
+            }
+        }
+
+        Ok(output)
+    }
+
     /// Resolve a payment address to its associated stake key
     pub async fn resolve_address_to_stake_key(
         &self,
         address: &str,
     ) -> Result<Option<String>, MaestroError> {
-        let url = format!("https://{}/addresses/{address}/decode", self.base_url);
+        let address = path_segment(address);
+        let url = self.api.url(&format!("/addresses/{address}/decode"));
 
         match self.get_url::<AddressDecodeResponse>(url).await {
             Ok(response) => {
@@ -1482,14 +2070,19 @@ impl MaestroApi {
     pub async fn get_account_assets_for_policy(
         &self,
         stake_address: &str,
-        policy_id: &str,
+        policy_id: &PolicyId,
     ) -> Result<Vec<AssetHolding>, MaestroError> {
         let mut all_assets = Vec::new();
         let mut cursor: Option<String> = None;
 
         loop {
             let page = self
-                .get_account_assets_page(stake_address, cursor.clone(), Some(policy_id), None)
+                .get_account_assets_page(
+                    stake_address,
+                    cursor.clone(),
+                    Some(policy_id.as_str()),
+                    None,
+                )
                 .await?;
             all_assets.extend(page.data);
 
@@ -1510,34 +2103,107 @@ impl MaestroApi {
         policy_id: Option<&str>,
         count: Option<u32>,
     ) -> Result<AccountAssetsResponse, MaestroError> {
-        let mut query_params = Vec::new();
+        let stake_address = path_segment(stake_address);
+        let query = Query::new()
+            .push_opt("policy", policy_id)
+            .push_opt("cursor", cursor)
+            .push("count", count.unwrap_or(100));
+        let url = self
+            .api
+            .url_with_query(&format!("/accounts/{stake_address}/assets"), &query);
+
+        self.get_url(url).await
+    }
+
+    /// Get the per-epoch delegation history of a stake address, oldest epoch first
+    pub async fn get_account_history(
+        &self,
+        stake_address: &str,
+    ) -> Result<Vec<AccountEpochStake>, MaestroError> {
+        let stake_address = path_segment(stake_address);
+        let mut history = Vec::new();
+        let mut cursor: Option<String> = None;
+
+        loop {
+            let url = self.api.url_with_query(
+                &format!("/accounts/{stake_address}/history"),
+                &account_page_query(cursor.as_deref()),
+            );
+            let page: AccountHistoryResponse = self.get_url(url).await?;
+            history.extend(page.data);
 
-        if let Some(policy) = policy_id {
-            query_params.push(format!("policy={policy}"));
+            if page.next_cursor.is_none() {
+                break;
+            }
+            cursor = page.next_cursor;
         }
 
-        if let Some(c) = cursor {
-            query_params.push(format!("cursor={c}"));
+        Ok(history)
+    }
+
+    /// Get all rewards earned by a stake address, oldest epoch first
+    pub async fn get_account_rewards(
+        &self,
+        stake_address: &str,
+    ) -> Result<Vec<AccountReward>, MaestroError> {
+        let stake_address = path_segment(stake_address);
+        let mut rewards = Vec::new();
+        let mut cursor: Option<String> = None;
+
+        loop {
+            let url = self.api.url_with_query(
+                &format!("/accounts/{stake_address}/rewards"),
+                &account_page_query(cursor.as_deref()),
+            );
+            let page: AccountRewardsResponse = self.get_url(url).await?;
+            rewards.extend(page.data);
+
+            if page.next_cursor.is_none() {
+                break;
+            }
+            cursor = page.next_cursor;
         }
 
-        let count = count.unwrap_or(100);
-        query_params.push(format!("count={count}"));
+        Ok(rewards)
+    }
 
-        let querystring = if query_params.is_empty() {
-            String::new()
-        } else {
-            format!("?{}", query_params.join("&"))
-        };
+    /// Every mint and burn of an asset, oldest first
+    pub async fn get_asset_mints(&self, asset_id: &AssetId) -> Result<Vec<MintTx>, MaestroError> {
+        let asset = path_segment(&asset_id.concatenated());
+        let mut mints = Vec::new();
+        let mut cursor: Option<String> = None;
 
-        let url = format!(
-            "https://{}/accounts/{stake_address}/assets{querystring}",
-            self.base_url
-        );
+        loop {
+            let url = self.api.url_with_query(
+                &format!("/assets/{asset}/mints"),
+                &account_page_query(cursor.as_deref()),
+            );
+            let page: AssetMintsResponse = self.get_url(url).await?;
+            mints.extend(page.data);
 
-        self.get_url(url).await
+            if page.next_cursor.is_none() {
+                break;
+            }
+            cursor = page.next_cursor;
+        }
+
+        Ok(mints)
+    }
+
+    /// Mint history of an asset as events to feed a
+    /// [`SupplyLedger`](cardano_assets::SupplyLedger)
+    pub async fn get_mint_events(
+        &self,
+        asset_id: &AssetId,
+    ) -> Result<Vec<MintEvent>, MaestroError> {
+        self.get_asset_mints(asset_id)
+            .await?
+            .iter()
+            .map(|mint| mint.to_event(asset_id))
+            .collect()
     }
 
-    pub async fn get(&self, id: &str, policy_id: &str) -> Result<Asset, MaestroError> {
+    pub async fn get(&self, id: &str, policy_id: &PolicyId) -> Result<Asset, MaestroError> {
         self.get_detailed(id, policy_id)
             .await
             .and_then(Asset::try_from)
@@ -1546,14 +2212,68 @@ impl MaestroApi {
     pub async fn get_detailed(
         &self,
         id: &str,
-        policy_id: &str,
+        policy_id: &PolicyId,
     ) -> Result<DetailedAssetInfo, MaestroError> {
-        let url = format!("https://{}/assets/{policy_id}{id}", self.base_url);
-        let response: AssetInfoResponse = self.get_url(url.clone()).await?;
+        self.get_asset_info(&format!("{policy_id}{id}")).await
+    }
+
+    /// `/assets/{unit}`, for a policy id and hex asset name already joined
+    async fn get_asset_info(&self, unit: &str) -> Result<DetailedAssetInfo, MaestroError> {
+        let unit = path_segment(unit);
+        let url = self.api.url(&format!("/assets/{unit}"));
+        let response: AssetInfoResponse = self.get_url(url).await?;
         Ok(response.data)
     }
 
-    pub async fn get_all_assets(&self, policy_id: &str) -> Result<Vec<AssetWithId>, MaestroError> {
+    /// Fetch detailed info for many assets with bounded concurrency.
+    ///
+    /// Maestro has no multi-asset info endpoint, so this issues one
+    /// `/assets/{id}` request per (deduplicated) id, keeping at most
+    /// [`BULK_CONCURRENCY`] in flight. Rate limits are retried per request as
+    /// in every other call.
+    pub async fn get_assets_bulk(&self, ids: &[AssetId]) -> BulkAssetInfo {
+        self.get_assets_bulk_with_concurrency(ids, BULK_CONCURRENCY)
+            .await
+    }
+
+    /// [`get_assets_bulk`](Self::get_assets_bulk) with an explicit concurrency limit
+    pub async fn get_assets_bulk_with_concurrency(
+        &self,
+        ids: &[AssetId],
+        concurrency: usize,
+    ) -> BulkAssetInfo {
+        let mut seen = HashSet::new();
+        let unique: Vec<&AssetId> = ids.iter().filter(|id| seen.insert(*id)).collect();
+
+        let results = worker_utils::join_bounded(
+            unique.into_iter().map(|id| async move {
+                let result = self.get_asset_info(&id.concatenated()).await;
+                (id.clone(), result)
+            }),
+            concurrency,
+        )
+        .await;
+
+        let mut output = BulkAssetInfo::default();
+        for (id, result) in results {
+            match result {
+                Ok(info) => {
+                    output.assets.insert(id, info);
+                }
+                Err(err) => {
+                    warn!("Failed to fetch asset {id}: {err}");
+                    output.failed.push((id, err));
+                }
+            }
+        }
+
+        output
+    }
+
+    pub async fn get_all_assets(
+        &self,
+        policy_id: &PolicyId,
+    ) -> Result<Vec<AssetWithId>, MaestroError> {
         let mut output: Vec<AssetWithId> = Vec::new();
         let mut cursor: Option<String> = None;
 
@@ -1572,7 +2292,7 @@ impl MaestroApi {
 
     pub async fn get_asset_page(
         &self,
-        policy_id: &str,
+        policy_id: &PolicyId,
         cursor: &Option<String>,
     ) -> Result<(Vec<AssetWithId>, Option<String>), MaestroError> {
         self.get_asset_page_with_count(policy_id, cursor, None)
@@ -1581,7 +2301,7 @@ impl MaestroApi {
 
     pub async fn get_asset_page_with_count(
         &self,
-        policy_id: &str,
+        policy_id: &PolicyId,
         cursor: &Option<String>,
         count: Option<u32>,
     ) -> Result<(Vec<AssetWithId>, Option<String>), MaestroError> {
@@ -1595,7 +2315,7 @@ impl MaestroApi {
     /// Returns `Some(PolicyClassification)` only on the first page (no cursor).
     pub async fn get_asset_page_classified(
         &self,
-        policy_id: &str,
+        policy_id: &PolicyId,
         cursor: &Option<String>,
         count: Option<u32>,
     ) -> Result<
@@ -1615,14 +2335,12 @@ impl MaestroApi {
         let assets: Vec<_> = page
             .get_importable_nfts()
             .iter()
-            .filter_map(|d| match Asset::try_from(d.asset_standards.clone()) {
-                Ok(asset) => {
-                    // Capture the full CID set (image + files[]) from the
-                    // rich metadata before the flatten to `Asset` drops it.
-                    let cids = d.asset_standards.extract_cids();
-                    Some(AssetWithId::new(d.asset_name.clone(), asset, cids))
-                }
-                Err(_) => None,
+            .filter_map(|d| {
+                let asset = d.to_asset()?;
+                // Capture the full CID set (image + files[]) from the
+                // rich metadata before the flatten to `Asset` drops it.
+                let cids = d.asset_standards.extract_cids();
+                Some(AssetWithId::new(d.asset_name.clone(), asset, cids))
             })
             .collect();
 
@@ -1631,40 +2349,78 @@ impl MaestroApi {
 
     pub async fn get_all_owners_for_policy(
         &self,
-        policy_id: &str,
+        policy_id: &PolicyId,
     ) -> Result<Vec<PolicyAssetOwner>, MaestroError> {
         let mut output: Vec<PolicyAssetOwner> = Vec::new();
-        let mut cursor: Option<String> = None;
-
-        loop {
-            let page = self.get_accounts(policy_id, cursor).await?;
-            output.extend(page.data);
-            if page.next_cursor.is_none() {
-                break;
-            }
+        let mut pages = pin!(self.owners_for_policy_pages(policy_id, None));
 
-            cursor = page.next_cursor;
+        while let Some(page) = poll_fn(|cx| pages.as_mut().poll_next(cx)).await {
+            output.extend(page?.owners);
         }
 
         Ok(output)
     }
 
+    /// Page through a policy's owners, starting after `cursor`.
+    ///
+    /// For walks that may outlive the worker: persist each page's
+    /// `next_cursor` once the page is handled, and pass the last one saved
+    /// back in to resume. The stream ends after the last page or the first
+    /// error, including [`MaestroError::RepeatedCursor`].
+    pub fn owners_for_policy_pages<'a>(
+        &'a self,
+        policy_id: &'a PolicyId,
+        cursor: Option<String>,
+    ) -> impl Stream<Item = Result<PolicyOwnersPage, MaestroError>> + 'a {
+        stream! {
+            let mut guard = CursorGuard::starting_at(cursor.as_deref());
+            let mut cursor = cursor;
+
+            loop {
+                let page = match self.get_accounts(policy_id, cursor).await {
+                    Ok(page) => page,
+                    Err(e) => {
+                        yield Err(e);
+                        break;
+                    }
+                };
+                let next_cursor = match guard.advance(page.next_cursor) {
+                    Ok(next_cursor) => next_cursor,
+                    Err(e) => {
+                        yield Err(e);
+                        break;
+                    }
+                };
+
+                yield Ok(PolicyOwnersPage {
+                    owners: page.data,
+                    next_cursor: next_cursor.clone(),
+                });
+
+                match next_cursor {
+                    Some(next_cursor) => cursor = Some(next_cursor),
+                    None => break,
+                }
+            }
+        }
+    }
+
     pub async fn get_all_owners_for_asset(
         &self,
-        policy_id: &str,
+        policy_id: &PolicyId,
         asset_id: &str,
     ) -> Result<Vec<AccountQuantity>, MaestroError> {
         let mut output: Vec<AccountQuantity> = Vec::new();
+        let mut guard = CursorGuard::default();
         let mut cursor: Option<String> = None;
 
         loop {
             let page = self.get_asset_accounts(policy_id, asset_id, cursor).await?;
             output.extend(page.data);
-            if page.next_cursor.is_none() {
+            cursor = guard.advance(page.next_cursor)?;
+            if cursor.is_none() {
                 break;
             }
-
-            cursor = page.next_cursor;
         }
 
         Ok(output)
@@ -1673,14 +2429,14 @@ impl MaestroApi {
     #[allow(clippy::needless_lifetimes)]
     pub async fn get_asset_stream<'a>(
         &'a self,
-        policy_id: &'a str,
+        policy_id: &'a PolicyId,
     ) -> impl Stream<Item = Asset> + 'a {
         let mut cursor: Option<String> = None;
 
         stream! {
             while let Ok(page) = self.get_assets(policy_id, cursor, None).await {
                 for data in &page.data {
-                    if let Ok(result) = Asset::try_from(data.asset_standards.clone()) {
+                    if let Some(result) = data.to_asset() {
                         yield result;
                     }
                 }
@@ -1696,59 +2452,47 @@ impl MaestroApi {
 
     async fn get_assets(
         &self,
-        policy_id: &str,
+        policy_id: &PolicyId,
         cursor: Option<String>,
         count: Option<u32>,
     ) -> Result<PolicyAssetsResponse, MaestroError> {
-        let mut query_params = Vec::new();
-
-        if let Some(c) = cursor {
-            query_params.push(format!("cursor={c}"));
-        }
-
-        if let Some(n) = count {
-            query_params.push(format!("count={n}"));
-        }
-
-        let querystring = if query_params.is_empty() {
-            String::new()
-        } else {
-            format!("?{}", query_params.join("&"))
-        };
-
-        let url = format!(
-            "https://{}/policy/{policy_id}/assets{querystring}",
-            self.base_url
-        );
+        let policy_id = path_segment(policy_id.as_str());
+        let query = Query::new()
+            .push_opt("cursor", cursor)
+            .push_opt("count", count);
+        let url = self
+            .api
+            .url_with_query(&format!("/policy/{policy_id}/assets"), &query);
 
         self.get_url(url).await
     }
 
     async fn get_accounts(
         &self,
-        policy_id: &str,
+        policy_id: &PolicyId,
         cursor: Option<String>,
     ) -> Result<PolicyAccountsResponse, MaestroError> {
-        let querystring = cursor.map_or(String::new(), |c| format!("cursor={c}"));
-        let url = format!(
-            "https://{}/policy/{policy_id}/accounts?count=100&{querystring}",
-            self.base_url
-        );
+        let policy_id = path_segment(policy_id.as_str());
+        let query = Query::new().push("count", 100).push_opt("cursor", cursor);
+        let url = self
+            .api
+            .url_with_query(&format!("/policy/{policy_id}/accounts"), &query);
 
         self.get_url(url).await
     }
 
     async fn get_asset_accounts(
         &self,
-        policy_id: &str,
+        policy_id: &PolicyId,
         asset_id: &str,
         cursor: Option<String>,
     ) -> Result<AssetAccountsResponse, MaestroError> {
-        let querystring = cursor.map_or(String::new(), |c| format!("cursor={c}"));
-        let url = format!(
-            "https://{}/policy/{policy_id}{asset_id}/accounts?count=100&{querystring}",
-            self.base_url
-        );
+        let policy_id = path_segment(policy_id.as_str());
+        let asset_id = path_segment(asset_id);
+        let query = Query::new().push("count", 100).push_opt("cursor", cursor);
+        let url = self
+            .api
+            .url_with_query(&format!("/policy/{policy_id}{asset_id}/accounts"), &query);
 
         self.get_url(url).await
     }
@@ -1781,9 +2525,11 @@ impl MaestroApi {
         let mut attempt = 0;
 
         loop {
+            self.charge(url)?;
             // Request text with details to get raw body, headers, and perform custom retry logic
             let response_details = self
-                .client
+                .api
+                .client()
                 .request_text_with_details(HttpMethod::GET, url, None::<&()>)
                 .await?;
 
@@ -1827,10 +2573,11 @@ impl MaestroApi {
                 }
                 _ => {
                     // Other HTTP errors - propagate immediately
-                    return Err(MaestroError::Http(http_client::HttpError::Custom(format!(
-                        "HTTP error {}: {}",
-                        response_details.status_code, response_details.data
-                    ))));
+                    return Err(MaestroError::Http(http_client::HttpError::Status {
+                        code: response_details.status_code,
+                        headers: response_details.headers,
+                        body: response_details.data,
+                    }));
                 }
             }
         }
@@ -1851,8 +2598,10 @@ impl MaestroApi {
         const MAX_RETRIES: u32 = 3;
         let mut attempt = 0;
         let response_details = loop {
+            self.charge(&url)?;
             let details = self
-                .client
+                .api
+                .client()
                 .request_text_with_details(HttpMethod::POST, &url, Some(body))
                 .await?;
             if details.status_code == 429 && attempt < MAX_RETRIES {
@@ -1885,10 +2634,11 @@ impl MaestroApi {
                 let retry_after = response_details.retry_after_seconds();
                 Err(MaestroError::RateLimit { retry_after })
             }
-            _ => Err(MaestroError::Http(http_client::HttpError::Custom(format!(
-                "HTTP error {}: {}",
-                response_details.status_code, response_details.data
-            )))),
+            _ => Err(MaestroError::Http(http_client::HttpError::Status {
+                code: response_details.status_code,
+                headers: response_details.headers,
+                body: response_details.data,
+            })),
         }
     }
 }
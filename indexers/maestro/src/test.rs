@@ -563,13 +563,14 @@ mod tests {
                     ("Collectible", "None"),
                 ])
                 .into_traits();
-                let asset: Asset = deserialized.data.asset_standards.try_into().unwrap();
+                assert_eq!(deserialized.data.total_supply, 4);
+                let asset: Asset = deserialized.data.try_into().unwrap();
                 assert_eq!(asset.name, "WavyApe387");
                 assert_eq!(
                     asset.image,
                     "ipfs://QmTXJg2xbRD7mzxzasRzbmVkFL5bXpZuav4tWbey8WkPwb"
                 );
-                assert_eq!(deserialized.data.total_supply, 4);
+                assert_eq!(asset.quantity, 4);
                 assert_eq!(asset.traits, test_traits);
             }
             Err(err) => {
@@ -1062,10 +1063,254 @@ mod tests {
                         asset.err()
                     );
                 }
+
+                // Multi-edition assets keep their supply as the quantity
+                let edition = importable
+                    .iter()
+                    .find(|nft| nft.total_supply.as_deref() == Some("2"))
+                    .unwrap();
+                assert_eq!(edition.to_asset().unwrap().quantity, 2);
             }
             Err(err) => {
                 panic!("failed decoding Old Money assets: {err:?}");
             }
         }
     }
+
+    #[test]
+    fn test_deserialize_account_history() {
+        let history: AccountHistoryResponse =
+            serde_json::from_str(&test_case!("account_history.json")).unwrap();
+        assert!(history.next_cursor.is_none());
+        assert_eq!(history.data.len(), 3);
+        assert_eq!(history.data[0].epoch_no, 480);
+        assert_eq!(history.data[1].active_stake, 4_825_114_802);
+        assert_eq!(
+            history.data[1].pool_id.as_deref(),
+            Some("pool1pu5jlj4q9w9jlxeu370a3c9myx47md5j5m2str0naunn2q3lkdy")
+        );
+        assert!(history.data[2].pool_id.is_none());
+    }
+
+    #[test]
+    fn test_deserialize_account_rewards() {
+        let rewards: AccountRewardsResponse =
+            serde_json::from_str(&test_case!("account_rewards.json")).unwrap();
+        assert_eq!(rewards.next_cursor.as_deref(), Some("ZXBvY2g6NDgx"));
+        assert_eq!(rewards.data.len(), 3);
+        assert_eq!(rewards.data[0].earned_epoch, 479);
+        assert_eq!(rewards.data[0].spendable_epoch, 481);
+        assert_eq!(rewards.data[1].amount, 2_113_068);
+        assert_eq!(rewards.data[1].kind, "member");
+        assert_eq!(rewards.data[2].kind, "refund");
+        assert!(rewards.data[2].pool_id.is_none());
+    }
+
+    #[test]
+    fn test_deserialize_chain_tip() {
+        let tip: ChainTipResponse = serde_json::from_str(&test_case!("chain_tip.json")).unwrap();
+        assert_eq!(tip.data.height, 10_655_411);
+        assert_eq!(tip.data.slot, 131_942_595);
+    }
+
+    #[test]
+    fn test_deserialize_block() {
+        let block: BlockResponse = serde_json::from_str(&test_case!("block_info.json")).unwrap();
+        assert_eq!(block.data.height, 10_655_411);
+        assert_eq!(block.data.absolute_slot, 131_942_595);
+        assert_eq!(block.data.epoch, 503);
+        assert_eq!(block.data.epoch_slot, 9_795);
+        assert_eq!(block.data.tx_hashes.len(), 2);
+        assert_eq!(block.data.era.as_deref(), Some("babbage"));
+        assert!(block.data.previous_block.is_some());
+    }
+
+    #[test]
+    fn test_block_poll_interval() {
+        let config = BlockPollConfig::default();
+        assert_eq!(config.next_interval(5_000, false), 7_500);
+        assert_eq!(config.next_interval(50_000, false), 60_000);
+        assert_eq!(config.next_interval(60_000, true), 5_000);
+        assert_eq!(BlockRef::from(42).to_string(), "42");
+    }
+
+    #[test]
+    fn test_reference_touches() {
+        let tx = |hash: &str, slot: u64, assets: &[&str]| PolicyTransaction {
+            tx_hash: hash.to_string(),
+            slot,
+            assets: assets.iter().map(|a| a.to_string()).collect(),
+        };
+        let transactions = vec![
+            tx(
+                "b",
+                20,
+                &["000643b04261745069673031", "000de1404261745069673032"],
+            ),
+            tx("a", 10, &["000643b04261745069673031"]),
+            tx("c", 15, &["000643b04261745069673033"]),
+        ];
+
+        let touches = reference_touches(&transactions);
+        assert_eq!(touches.len(), 2);
+        assert_eq!(touches[0].tx_hash, "c");
+        assert_eq!(touches[0].user_asset_name, "000de1404261745069673033");
+        assert_eq!(touches[1].reference_asset_name, "000643b04261745069673031");
+        assert_eq!(touches[1].user_asset_name, "000de1404261745069673031");
+        assert_eq!((touches[1].tx_hash.as_str(), touches[1].slot), ("b", 20));
+
+        // 333 and 444 user tokens share the label 100 reference token
+        let transactions = vec![
+            tx("d", 30, &["000643b04d656c64", "0014df104d656c64"]),
+            tx("e", 40, &["001bc2804469616d6f6e64"]),
+            tx("f", 50, &["000643b04469616d6f6e64"]),
+        ];
+        let touches = reference_touches(&transactions);
+        assert_eq!(touches.len(), 2);
+        assert_eq!(touches[0].user_asset_name, "0014df104d656c64");
+        assert_eq!(touches[1].user_asset_name, "001bc2804469616d6f6e64");
+        assert_eq!(touches[1].tx_hash, "f");
+    }
+
+    #[test]
+    fn test_cursor_guard() {
+        let mut guard = CursorGuard::starting_at(Some("a"));
+        assert_eq!(
+            guard.advance(Some("b".to_string())).unwrap(),
+            Some("b".to_string())
+        );
+        assert_eq!(guard.advance(Some(String::new())).unwrap(), None);
+        assert_eq!(guard.advance(None).unwrap(), None);
+
+        match guard.advance(Some("a".to_string())) {
+            Err(MaestroError::RepeatedCursor { cursor }) => assert_eq!(cursor, "a"),
+            other => panic!("expected RepeatedCursor, got {other:?}"),
+        }
+        assert!(guard.advance(Some("b".to_string())).is_err());
+    }
+
+    #[test]
+    fn test_request_budget() {
+        assert_eq!(
+            endpoint_key("/assets/000de1404261745069673031/txs?count=100&order=desc"),
+            "/assets/{}/txs"
+        );
+        assert_eq!(endpoint_key("/blocks/latest"), "/blocks/latest");
+
+        let seen = std::sync::Arc::new(std::sync::atomic::AtomicU64::new(0));
+        let hook_seen = seen.clone();
+        let budget = RequestBudget::new()
+            .with_costs(CreditCosts::default().with_cost("/txmanager", 10))
+            .with_limit(12)
+            .on_request(move |event| {
+                hook_seen.store(event.total_credits, std::sync::atomic::Ordering::Relaxed)
+            });
+
+        budget.charge("/txmanager").unwrap();
+        budget.charge("/assets/{}/txs").unwrap();
+        budget.charge("/assets/{}/txs").unwrap();
+        assert_eq!(seen.load(std::sync::atomic::Ordering::Relaxed), 12);
+
+        let usage = budget.usage();
+        assert_eq!((usage.requests, usage.credits), (3, 12));
+        assert_eq!(
+            usage.by_endpoint["/assets/{}/txs"],
+            EndpointUsage {
+                requests: 2,
+                credits: 2,
+            }
+        );
+
+        match budget.charge("/blocks/latest") {
+            Err(MaestroError::BudgetExceeded { used, limit, .. }) => {
+                assert_eq!((used, limit), (12, 12))
+            }
+            other => panic!("expected BudgetExceeded, got {other:?}"),
+        }
+        assert_eq!(budget.usage().requests, 3);
+
+        budget.reset();
+        assert_eq!(budget.usage(), UsageReport::default());
+        budget.charge("/blocks/latest").unwrap();
+    }
+
+    #[test]
+    fn test_mint_events_feed_supply_ledger() {
+        let info = serde_json::from_str::<AssetInfoResponse>(&test_case!("wavy_dupe.json"))
+            .unwrap()
+            .data;
+        let id = AssetId::new_unchecked("aa".repeat(28), info.asset_name.clone());
+        let first = info.first_mint_tx.to_event(&id).unwrap();
+        assert_eq!((first.quantity, first.slot), (1, Some(153705850)));
+
+        // A later burn of the same asset
+        let page: AssetMintsResponse = serde_json::from_str(
+            r#"{"data": [{"tx_hash": "aa", "slot": 153705851, "timestamp": "2025-04-21 21:49:18", "amount": "-1"}], "next_cursor": null}"#,
+        )
+        .unwrap();
+        let burn = page.data[0].to_event(&id).unwrap();
+        assert_eq!(burn.quantity, -1);
+
+        let mut ledger = cardano_assets::SupplyLedger::new();
+        ledger.extend([&first, &burn]);
+        assert!(ledger.asset(&id).unwrap().is_burned());
+
+        let bad = MintTx {
+            amount: "lots".to_string(),
+            ..page.data.into_iter().next().unwrap()
+        };
+        assert!(matches!(
+            bad.to_event(&id),
+            Err(MaestroError::Deserialization(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_get_asset_replay() {
+        // Replays resources/fixtures/maestro; refresh with HTTP_FIXTURES=record
+        // and a real MAESTRO_API_KEY
+        let api = MaestroApi::new("test".into(), "mainnet.gomaestro-api.org/v1".into())
+            .with_http_client(test_utils::fixture_client!("maestro"));
+
+        let blackflag: PolicyId = "b3dab69f7e6100849434fb1781e34bd12a916557f6231b8d2629b6f6"
+            .parse()
+            .unwrap();
+
+        let asset = api.get("50697261746531", &blackflag).await.unwrap();
+        assert_eq!(asset.name, "Pirate #1");
+        assert_eq!(
+            asset.image,
+            "ipfs://QmWhLmt9BXdxdK6VeaZHWyLkeHfszwyRqgbXeiUyPimMaR"
+        );
+        assert_eq!(api.usage().requests, 1);
+
+        // Missing fixtures fail instead of reaching the live API
+        assert!(matches!(
+            api.get("50697261746532", &blackflag).await,
+            Err(MaestroError::Http(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_get_assets_bulk_dedupes() {
+        let api = MaestroApi::new("test".into(), "mainnet.gomaestro-api.org/v1".into())
+            .with_http_client(test_utils::fixture_client!("maestro"));
+
+        let blackflag = "b3dab69f7e6100849434fb1781e34bd12a916557f6231b8d2629b6f6";
+        let pirate = |name: &str| AssetId::new(blackflag.to_string(), name.to_string()).unwrap();
+        let ids = [
+            pirate("50697261746531"),
+            pirate("50697261746532"),
+            pirate("50697261746531"),
+            pirate("50697261746532"),
+        ];
+
+        // One request per distinct id; #2 has no fixture so it fails
+        let bulk = api.get_assets_bulk_with_concurrency(&ids, 2).await;
+        assert_eq!(api.usage().requests, 2);
+        assert_eq!(bulk.assets.len(), 1);
+        assert_eq!(bulk.assets[&ids[0]].asset_name, "50697261746531");
+        assert_eq!(bulk.failed.len(), 1);
+        assert_eq!(bulk.failed[0].0, ids[1]);
+    }
 }
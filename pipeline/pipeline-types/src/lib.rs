@@ -44,7 +44,7 @@ impl PricedAsset {
 
 impl std::fmt::Display for PricedAsset {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}", self.asset.asset_name())
+        write!(f, "{}", self.asset.display_name())
     }
 }
 
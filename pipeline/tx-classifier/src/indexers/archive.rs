@@ -0,0 +1,333 @@
+//! Indexer response archive for deterministic reprocessing
+//!
+//! When a classifier bug is fixed, historical transactions need to be
+//! re-classified against exactly the data seen the first time — indexers
+//! backfill datums, re-resolve metadata and occasionally change shape. With
+//! an archive attached, [`IndexerPool`](super::IndexerPool) either:
+//!
+//! - **Records** — fetches live and writes each raw response body to R2,
+//!   keyed by provider, endpoint and params, or
+//! - **Replays** — serves response bodies from R2 without touching the
+//!   indexer. A missing entry is an error rather than a silent live
+//!   request, so a back-test can't quietly mix archived and fresh data.
+//!
+//! Bodies are archived before they are converted to [`RawTxData`], so a
+//! replay re-runs the provider conversion along with the classifier.
+//!
+//! Configure from the environment with `INDEXER_ARCHIVE_MODE`
+//! (`record` / `replay`) and an `INDEXER_ARCHIVE` R2 binding.
+
+use std::future::Future;
+
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use serde_json::Value;
+use tracing::{debug, warn};
+use transactions::RawTxData;
+use worker::{Bucket, Env};
+
+use super::{IndexerPool, IndexerProvider};
+use crate::TxClassifierError;
+
+/// R2 binding the archive is stored in
+pub const ARCHIVE_BUCKET_BINDING: &str = "INDEXER_ARCHIVE";
+/// Env var selecting the archive mode
+pub const ARCHIVE_MODE_VAR: &str = "INDEXER_ARCHIVE_MODE";
+
+/// Whether responses are captured from the indexer or served from R2
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ArchiveMode {
+    Record,
+    Replay,
+}
+
+impl ArchiveMode {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "record" => Some(Self::Record),
+            "replay" => Some(Self::Replay),
+            _ => None,
+        }
+    }
+}
+
+/// Stored form of one archived response
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArchivedResponse {
+    pub provider: String,
+    pub endpoint: String,
+    pub params: String,
+    /// Unix milliseconds when the response was recorded
+    pub recorded_at: u64,
+    /// Response body as the indexer returned it
+    pub data: Value,
+}
+
+/// R2 key for a response: `indexer-archive/<provider>/<endpoint>/<params>.json`
+///
+/// Params are kept readable (tx hashes, asset ids) with anything outside
+/// `[A-Za-z0-9._-]` replaced, so keys can be listed and inspected by prefix.
+pub fn archive_key(provider: IndexerProvider, endpoint: &str, params: &str) -> String {
+    let clean = |value: &str| -> String {
+        value
+            .chars()
+            .map(|c| {
+                if c.is_ascii_alphanumeric() || matches!(c, '.' | '_' | '-') {
+                    c
+                } else {
+                    '_'
+                }
+            })
+            .collect()
+    };
+    let provider = format!("{provider:?}").to_lowercase();
+    format!(
+        "indexer-archive/{provider}/{}/{}.json",
+        clean(endpoint),
+        clean(params)
+    )
+}
+
+/// Indexer responses recorded to, or replayed from, an R2 bucket
+pub struct ResponseArchive {
+    bucket: Bucket,
+    mode: ArchiveMode,
+}
+
+impl ResponseArchive {
+    pub fn new(bucket: Bucket, mode: ArchiveMode) -> Self {
+        Self { bucket, mode }
+    }
+
+    /// Archive configured by `INDEXER_ARCHIVE_MODE`, or `None` when unset
+    pub fn from_env(env: &Env) -> Result<Option<Self>, TxClassifierError> {
+        let Some(value) = env.var(ARCHIVE_MODE_VAR).ok().map(|v| v.to_string()) else {
+            return Ok(None);
+        };
+        if value.trim().is_empty() || value.eq_ignore_ascii_case("off") {
+            return Ok(None);
+        }
+        let mode = ArchiveMode::parse(&value).ok_or_else(|| {
+            TxClassifierError::ClassificationFailed(format!(
+                "Invalid {ARCHIVE_MODE_VAR} '{value}' (expected record, replay or off)"
+            ))
+        })?;
+        let bucket = env.bucket(ARCHIVE_BUCKET_BINDING)?;
+        Ok(Some(Self::new(bucket, mode)))
+    }
+
+    pub fn mode(&self) -> ArchiveMode {
+        self.mode
+    }
+
+    /// Archived response body for `(endpoint, params)`, if recorded
+    pub async fn load(
+        &self,
+        provider: IndexerProvider,
+        endpoint: &str,
+        params: &str,
+    ) -> Result<Option<Value>, TxClassifierError> {
+        let key = archive_key(provider, endpoint, params);
+        let Some(object) = self.bucket.get(&key).execute().await? else {
+            return Ok(None);
+        };
+        let Some(body) = object.body() else {
+            return Ok(None);
+        };
+        let text = body.text().await?;
+        let archived: ArchivedResponse = serde_json::from_str(&text).map_err(|e| {
+            TxClassifierError::ClassificationFailed(format!("Corrupt archive entry {key}: {e}"))
+        })?;
+        debug!("Replayed {key}");
+        Ok(Some(archived.data))
+    }
+
+    /// Write a live response body to the archive, replacing any earlier recording
+    pub async fn store(
+        &self,
+        provider: IndexerProvider,
+        endpoint: &str,
+        params: &str,
+        data: Value,
+    ) -> Result<(), TxClassifierError> {
+        let key = archive_key(provider, endpoint, params);
+        let archived = ArchivedResponse {
+            provider: format!("{provider:?}").to_lowercase(),
+            endpoint: endpoint.to_string(),
+            params: params.to_string(),
+            recorded_at: worker::Date::now().as_millis(),
+            data,
+        };
+        let json = serde_json::to_string(&archived).map_err(|e| {
+            TxClassifierError::ClassificationFailed(format!("Failed to encode {key}: {e}"))
+        })?;
+        self.bucket.put(&key, json.into_bytes()).execute().await?;
+        debug!("Recorded {key}");
+        Ok(())
+    }
+
+    /// Response body for `(endpoint, params)`: replayed from the archive, or
+    /// fetched live with `fetch` and recorded
+    pub async fn body<F, Fut>(
+        &self,
+        provider: IndexerProvider,
+        endpoint: &str,
+        params: &str,
+        fetch: F,
+    ) -> Result<Value, TxClassifierError>
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = Result<Value, TxClassifierError>>,
+    {
+        match self.mode {
+            ArchiveMode::Replay => self.load(provider, endpoint, params).await?.ok_or_else(|| {
+                TxClassifierError::ClassificationFailed(format!(
+                    "No archived response for {}",
+                    archive_key(provider, endpoint, params)
+                ))
+            }),
+            ArchiveMode::Record => {
+                let body = fetch().await?;
+                if let Err(e) = self.store(provider, endpoint, params, body.clone()).await {
+                    warn!("Failed to archive {endpoint} {params}: {e}");
+                }
+                Ok(body)
+            }
+        }
+    }
+}
+
+impl IndexerPool {
+    /// Fetch a transaction through `archive`, converting the archived bodies
+    /// exactly as a live fetch would
+    pub(super) async fn fetch_archived_transaction(
+        &self,
+        archive: &ResponseArchive,
+        tx_hash: &str,
+    ) -> Result<RawTxData, TxClassifierError> {
+        let provider = self.provider;
+        match provider {
+            IndexerProvider::Maestro => {
+                let body = archive
+                    .body(provider, "transactions", tx_hash, || async {
+                        Ok(self.maestro.get_complete_transaction_body(tx_hash).await?)
+                    })
+                    .await?;
+                let response: ::maestro::CompleteTransactionResponse =
+                    parse_body(body, "transactions", tx_hash)?;
+                let mut raw_tx =
+                    super::maestro::convert_complete_transaction_to_raw_data(&response.data)?;
+
+                let unresolved = super::maestro::resolve_datums_from_metadata(&mut raw_tx);
+                if unresolved.is_empty() {
+                    return Ok(raw_tx);
+                }
+                let hash_refs: Vec<&str> = unresolved.iter().map(String::as_str).collect();
+                let datums = archive
+                    .body(provider, "datums", tx_hash, || async {
+                        Ok(self.maestro.get_datums_by_hashes_body(&hash_refs).await?)
+                    })
+                    .await
+                    .and_then(|body| {
+                        parse_body::<::maestro::DatumsByHashesResponse>(body, "datums", tx_hash)
+                    });
+                match datums {
+                    Ok(response) => super::maestro::apply_maestro_datums(
+                        &mut raw_tx,
+                        response.data,
+                        unresolved.len(),
+                    ),
+                    Err(e) => warn!("Failed to resolve datums by hash: {e}"),
+                }
+                Ok(raw_tx)
+            }
+            IndexerProvider::Koios => {
+                let body = archive
+                    .body(provider, "tx_info", tx_hash, || async {
+                        self.koios
+                            .get_tx_details_body(&[tx_hash.to_string()])
+                            .await
+                            .map_err(|e| {
+                                TxClassifierError::ClassificationFailed(format!(
+                                    "Koios tx_info failed: {e}"
+                                ))
+                            })
+                    })
+                    .await?;
+                let txs: Vec<::koios::koios_transaction::KoiosTransaction> =
+                    parse_body(body, "tx_info", tx_hash)?;
+                let tx = txs
+                    .into_iter()
+                    .next()
+                    .ok_or_else(|| TxClassifierError::TransactionNotFound(tx_hash.to_string()))?;
+                super::koios::convert_koios_tx_to_raw_data(&tx)
+            }
+        }
+    }
+}
+
+fn parse_body<T: DeserializeOwned>(
+    body: Value,
+    endpoint: &str,
+    params: &str,
+) -> Result<T, TxClassifierError> {
+    serde_json::from_value(body).map_err(|e| {
+        TxClassifierError::ClassificationFailed(format!(
+            "Failed to parse {endpoint} response for {params}: {e}"
+        ))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_archive_key() {
+        let hash = "a2b2c2adf0ebd1ee2f7d3bc0b4c3bbcb8e0efb1e9f6bc0fa6c5b49d91e4b2b7d";
+        assert_eq!(
+            archive_key(IndexerProvider::Maestro, "tx", hash),
+            format!("indexer-archive/maestro/tx/{hash}.json")
+        );
+        assert_eq!(
+            archive_key(IndexerProvider::Koios, "assets/info", "policy?x=1"),
+            "indexer-archive/koios/assets_info/policy_x_1.json"
+        );
+    }
+
+    #[test]
+    fn test_archived_body_replays_through_conversion() {
+        let hash = "13b3285b302ff3dd0406cfc059345ec2100f41874ace62b33698c88769dc9157";
+        let archived = ArchivedResponse {
+            provider: "maestro".to_string(),
+            endpoint: "transactions".to_string(),
+            params: hash.to_string(),
+            recorded_at: 0,
+            data: serde_json::from_str(test_utils::test_case!(
+                "tx_13b3285b302ff3dd0406cfc059345ec2100f41874ace62b33698c88769dc9157.json"
+            ))
+            .unwrap(),
+        };
+        let stored: ArchivedResponse =
+            serde_json::from_str(&serde_json::to_string(&archived).unwrap()).unwrap();
+
+        let response: ::maestro::CompleteTransactionResponse =
+            parse_body(stored.data, "transactions", hash).unwrap();
+        let raw_tx =
+            super::super::maestro::convert_complete_transaction_to_raw_data(&response.data)
+                .unwrap();
+        assert_eq!(raw_tx.tx_hash, hash);
+        assert!(parse_body::<::maestro::CompleteTransactionResponse>(
+            Value::Null,
+            "transactions",
+            hash
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_archive_mode_parse() {
+        assert_eq!(ArchiveMode::parse(" Record "), Some(ArchiveMode::Record));
+        assert_eq!(ArchiveMode::parse("replay"), Some(ArchiveMode::Replay));
+        assert_eq!(ArchiveMode::parse("live"), None);
+    }
+}
@@ -292,6 +292,26 @@ fn datum_needs_resolution(datum: &TxDatum) -> bool {
 /// datums from metadata (free, no API call), then falls back to Maestro's batch datum
 /// endpoint for any remaining unresolved datums.
 async fn enrich_missing_datum_content(maestro: &MaestroApi, raw_tx: &mut RawTxData) {
+    let unresolved_hashes = resolve_datums_from_metadata(raw_tx);
+    if unresolved_hashes.is_empty() {
+        return;
+    }
+
+    // Step 2: Fall back to Maestro batch datum endpoint for remaining unresolved datums
+    debug!(
+        "Falling back to Maestro API for {} remaining unresolved datum(s)",
+        unresolved_hashes.len()
+    );
+    let hash_refs: Vec<&str> = unresolved_hashes.iter().map(|s| s.as_str()).collect();
+    match maestro.get_datums_by_hashes(&hash_refs).await {
+        Ok(resolved) => apply_maestro_datums(raw_tx, resolved, unresolved_hashes.len()),
+        Err(e) => warn!("Failed to resolve datums by hash: {e}"),
+    }
+}
+
+/// Step 1 of datum enrichment: apply datums published in tx metadata,
+/// returning the hashes that still need Maestro's `/datums` endpoint
+pub(crate) fn resolve_datums_from_metadata(raw_tx: &mut RawTxData) -> Vec<String> {
     // Collect all datum hashes that need resolution from inputs, outputs, and reference inputs
     let mut unresolved_hashes: Vec<String> = Vec::new();
 
@@ -320,7 +340,7 @@ async fn enrich_missing_datum_content(maestro: &MaestroApi, raw_tx: &mut RawTxDa
     }
 
     if unresolved_hashes.is_empty() {
-        return;
+        return unresolved_hashes;
     }
 
     debug!(
@@ -329,7 +349,7 @@ async fn enrich_missing_datum_content(maestro: &MaestroApi, raw_tx: &mut RawTxDa
         raw_tx.tx_hash
     );
 
-    // Step 1: Try extracting datums from tx metadata (free, no API call needed)
+    // Try extracting datums from tx metadata (free, no API call needed)
     let metadata_datums = extract_datums_from_metadata(raw_tx);
     if !metadata_datums.is_empty() {
         debug!(
@@ -342,28 +362,18 @@ async fn enrich_missing_datum_content(maestro: &MaestroApi, raw_tx: &mut RawTxDa
         unresolved_hashes.retain(|hash| !metadata_datums.contains_key(hash));
     }
 
-    if unresolved_hashes.is_empty() {
-        return;
-    }
-
-    // Step 2: Fall back to Maestro batch datum endpoint for remaining unresolved datums
-    debug!(
-        "Falling back to Maestro API for {} remaining unresolved datum(s)",
-        unresolved_hashes.len()
-    );
-    let hash_refs: Vec<&str> = unresolved_hashes.iter().map(|s| s.as_str()).collect();
-    let resolved = match maestro.get_datums_by_hashes(&hash_refs).await {
-        Ok(data) => data,
-        Err(e) => {
-            warn!("Failed to resolve datums by hash: {e}");
-            return;
-        }
-    };
+    unresolved_hashes
+}
 
+/// Step 2 of datum enrichment: apply datums returned by Maestro's `/datums` endpoint
+pub(crate) fn apply_maestro_datums(
+    raw_tx: &mut RawTxData,
+    resolved: std::collections::HashMap<String, maestro::DatumData>,
+    requested: usize,
+) {
     debug!(
-        "Resolved {}/{} datums from Maestro API",
-        resolved.len(),
-        unresolved_hashes.len()
+        "Resolved {}/{requested} datums from Maestro API",
+        resolved.len()
     );
 
     // Convert Maestro DatumData to CBOR hex strings for uniform application
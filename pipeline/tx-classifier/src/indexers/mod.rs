@@ -4,6 +4,7 @@ use tracing::{info, warn};
 use worker::Env;
 
 // Re-export submodules
+pub mod archive;
 pub mod koios;
 pub mod maestro;
 pub mod webhook_blockfrost;
@@ -12,6 +13,8 @@ pub mod webhook_oura;
 // Re-export public types from transactions crate
 pub use transactions::{MintOperation, RawTxData, TxDatum, TxInput, TxOutput};
 
+pub use archive::{ArchiveMode, ResponseArchive};
+
 // Re-export webhook types
 pub use webhook_blockfrost::*;
 pub use webhook_oura::*;
@@ -29,6 +32,7 @@ pub struct IndexerPool {
     maestro: maestro::MaestroApi,
     koios: ::koios::KoiosApi,
     provider: IndexerProvider,
+    archive: Option<ResponseArchive>,
 }
 
 /// Returns true if the given Shelley-style address has a *script* payment credential.
@@ -97,12 +101,18 @@ impl IndexerPool {
             }
         };
 
+        let archive = ResponseArchive::from_env(env)?;
+        if let Some(archive) = &archive {
+            info!("Indexer responses archived to R2 ({:?})", archive.mode());
+        }
+
         info!("✅ Indexer pool initialized (provider: {provider:?}, network: {network})");
 
         Ok(Self {
             maestro,
             koios,
             provider,
+            archive,
         })
     }
 
@@ -113,6 +123,7 @@ impl IndexerPool {
             maestro,
             koios: ::koios::KoiosApi::default(),
             provider: IndexerProvider::Maestro,
+            archive: None,
         }
     }
 
@@ -126,6 +137,7 @@ impl IndexerPool {
             maestro,
             koios: ::koios::KoiosApi::default(),
             provider: IndexerProvider::Maestro,
+            archive: None,
         }
     }
 
@@ -134,8 +146,22 @@ impl IndexerPool {
         self.provider
     }
 
-    /// Get transaction data from the active provider (Maestro or Koios).
+    /// Record responses to, or replay them from, an R2 archive
+    pub fn with_archive(mut self, archive: ResponseArchive) -> Self {
+        self.archive = Some(archive);
+        self
+    }
+
+    /// Get transaction data, going through the archive if one is attached.
     pub async fn get_transaction(&self, tx_hash: &str) -> Result<RawTxData, TxClassifierError> {
+        match &self.archive {
+            Some(archive) => self.fetch_archived_transaction(archive, tx_hash).await,
+            None => self.fetch_transaction(tx_hash).await,
+        }
+    }
+
+    /// Get transaction data from the active provider (Maestro or Koios).
+    async fn fetch_transaction(&self, tx_hash: &str) -> Result<RawTxData, TxClassifierError> {
         info!(
             "Fetching transaction data for {tx_hash} via {:?}",
             self.provider
@@ -1,11 +1,28 @@
-use tx_insights::{AssetSaleKind, TxInsight};
+use std::collections::BTreeSet;
+
+use cardano_assets::PolicyId;
+use tx_insights::{AssetSaleKind, MintContext, TxInsight};
 
 use crate::{TxClassification, TxType};
 
 impl From<TxType> for Option<TxInsight> {
     fn from(value: TxType) -> Self {
         match value {
-            TxType::Mint { assets, .. } => Some(TxInsight::Mint {
+            TxType::Mint {
+                assets,
+                total_lovelace,
+                launchpad,
+                policy_script,
+                ..
+            } => Some(TxInsight::Mint {
+                // Without a payment there's nothing to tell the phase from
+                context: total_lovelace.map(|total| {
+                    let context = MintContext::from_payment(Some(total), assets.len(), launchpad);
+                    match policy_script {
+                        Some(script) => context.with_policy_script(script),
+                        None => context,
+                    }
+                }),
                 assets: assets.into_iter().map(|a| a.into()).collect(),
             }),
             TxType::Sale {
@@ -19,6 +36,29 @@ impl From<TxType> for Option<TxInsight> {
                 seller,
                 buyer,
                 price_lovelace: asset.price_lovelace.unwrap_or_default(),
+                price_token: None,
+                seller_stake: None,
+                buyer_stake: None,
+            }),
+            TxType::AuctionBid {
+                asset,
+                bidder,
+                amount_lovelace,
+                ..
+            } => Some(TxInsight::AuctionBid {
+                asset: asset.into(),
+                bidder,
+                amount_lovelace,
+            }),
+            TxType::AuctionSettle {
+                asset,
+                winner,
+                amount_lovelace,
+                ..
+            } => Some(TxInsight::AuctionSettled {
+                asset: asset.into(),
+                winner,
+                amount_lovelace,
             }),
             _ => None,
         }
@@ -27,6 +67,9 @@ impl From<TxType> for Option<TxInsight> {
 
 impl From<TxClassification> for Vec<TxInsight> {
     fn from(classification: TxClassification) -> Self {
+        if classification.is_unknown() {
+            return unclassified(&classification).into_iter().collect();
+        }
         classification
             .tx_types
             .into_iter()
@@ -34,3 +77,54 @@ impl From<TxClassification> for Vec<TxInsight> {
             .collect()
     }
 }
+
+/// Raw hints for a tx no rule matched, if it moved any native tokens
+fn unclassified(classification: &TxClassification) -> Option<TxInsight> {
+    let policies: BTreeSet<&String> = classification
+        .assets
+        .iter()
+        .filter_map(|op| op.policy_id())
+        .collect();
+    if policies.is_empty() {
+        return None;
+    }
+
+    let script_hashes: BTreeSet<String> = classification
+        .context
+        .scripts
+        .iter()
+        .filter_map(|script| {
+            // The indexers record executed script hashes; addresses are
+            // reduced to their payment script hash
+            if script.len() == 56 && hex::decode(script).is_ok() {
+                Some(script.clone())
+            } else {
+                script_hash(script)
+            }
+        })
+        .collect();
+
+    Some(TxInsight::Unclassified {
+        policies: policies
+            .into_iter()
+            .filter_map(|policy_id| PolicyId::new(policy_id).ok())
+            .collect(),
+        script_hashes: script_hashes.into_iter().collect(),
+        hint: (!classification.context.notes.is_empty())
+            .then(|| classification.context.notes.join("; ")),
+    })
+}
+
+/// Payment script hash of a script address
+pub(crate) fn script_hash(address: &str) -> Option<String> {
+    let bytes = pallas_addresses::Address::from_bech32(address)
+        .ok()?
+        .to_vec();
+    // Script-payment types per CIP-19: 1, 3, 5 and 7; the credential follows
+    // the header byte
+    let addr_type = bytes.first()? >> 4;
+    if !matches!(addr_type, 1 | 3 | 5 | 7) {
+        return None;
+    }
+    bytes.get(1..29).map(hex::encode)
+}
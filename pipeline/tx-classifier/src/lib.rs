@@ -433,6 +433,22 @@ impl TxTypeMatcher for DexLiquidityRemove {
     }
 }
 
+/// Type marker for AuctionBid transactions
+pub struct AuctionBid;
+impl TxTypeMatcher for AuctionBid {
+    fn matches(tx_type: &TxType) -> bool {
+        matches!(tx_type, TxType::AuctionBid { .. })
+    }
+}
+
+/// Type marker for AuctionSettle transactions
+pub struct AuctionSettle;
+impl TxTypeMatcher for AuctionSettle {
+    fn matches(tx_type: &TxType) -> bool {
+        matches!(tx_type, TxType::AuctionSettle { .. })
+    }
+}
+
 /// Type marker for Unknown transactions
 pub struct Unknown;
 impl TxTypeMatcher for Unknown {
@@ -642,6 +658,8 @@ impl TxClassification {
                 TxType::DexSwap { .. } => "DEX Swap".to_string(),
                 TxType::DexLiquidityAdd { .. } => "DEX Liquidity Add".to_string(),
                 TxType::DexLiquidityRemove { .. } => "DEX Liquidity Remove".to_string(),
+                TxType::AuctionBid { .. } => "Auction Bid".to_string(),
+                TxType::AuctionSettle { .. } => "Auction Settle".to_string(),
                 TxType::Unknown => "Unknown".to_string(),
             })
             .collect();
@@ -770,6 +788,12 @@ pub enum TxType {
         total_lovelace: Option<u64>, // Total lovelace spent on minting (excluding tx fee)
         minter: String,       // Address receiving the primary assets
         mint_type: MintType,  // Type of mint operation
+        /// Launchpad the mint was paid to, e.g. `JPG.store`
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        launchpad: Option<String>,
+        /// Minting policy kind, when the source had redeemer data
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        policy_script: Option<tx_insights::PolicyScriptType>,
     },
 
     /// Asset burning transaction
@@ -944,6 +968,26 @@ pub enum TxType {
         provider: String,
     },
 
+    /// Bid placed on an on-chain auction contract
+    AuctionBid {
+        asset: AssetId,
+        bidder: String,
+        /// Lovelace locked with the lot after the bid (the standing bid)
+        amount_lovelace: u64,
+        auction_contract: String,
+        auction_label: String,
+    },
+
+    /// Auction closed: lot released from the contract to the winning bidder
+    AuctionSettle {
+        asset: AssetId,
+        winner: String,
+        /// Lovelace that was locked with the lot (the winning bid)
+        amount_lovelace: u64,
+        auction_contract: String,
+        auction_label: String,
+    },
+
     /// Unknown/unclassified transaction
     Unknown,
 }
@@ -964,7 +1008,7 @@ impl std::fmt::Display for TxType {
                 ..
             } => {
                 let ada_amount = *offer_lovelace as f64 / 1_000_000.0;
-                let name = asset.asset_name();
+                let name = asset.display_name();
                 write!(f, "{name} offer accepted for ₳{ada_amount:.2} to {buyer}")
             }
             Self::Mint {
@@ -1113,7 +1157,7 @@ impl std::fmt::Display for TxType {
                 let ada_amount = *offer_lovelace as f64 / 1_000_000.0;
                 match asset {
                     Some(asset) => {
-                        let name = asset.asset_name();
+                        let name = asset.display_name();
                         write!(f, "Offer of ₳{ada_amount:.2} for {name} by {bidder}")
                     }
                     None => write!(f, "Collection offer of ₳{ada_amount:.2} by {bidder}"),
@@ -1282,7 +1326,7 @@ impl std::fmt::Display for TxType {
                 };
 
                 if assets.len() == 1 {
-                    write!(f, "{} unlisted by {seller}", assets[0].asset_name())
+                    write!(f, "{} unlisted by {seller}", assets[0].display_name())
                 } else {
                     write!(
                         f,
@@ -1303,22 +1347,13 @@ impl std::fmt::Display for TxType {
                         format!("₳{ada_amount:.2}")
                     }
                     OperationPayload::NativeToken {
-                        policy_id: _,
+                        policy_id,
                         encoded_name,
                         amount,
                     } => {
-                        // Try to decode asset name
-                        let asset_name = if encoded_name.is_empty() {
-                            String::new()
-                        } else {
-                            match hex::decode(encoded_name) {
-                                Ok(bytes) => match String::from_utf8(bytes) {
-                                    Ok(decoded) => decoded,
-                                    Err(_) => encoded_name.clone(),
-                                },
-                                Err(_) => encoded_name.clone(),
-                            }
-                        };
+                        let asset_name =
+                            AssetId::new_unchecked(policy_id.clone(), encoded_name.clone())
+                                .display_name();
                         format!("{amount} {asset_name}")
                     }
                 };
@@ -1329,22 +1364,13 @@ impl std::fmt::Display for TxType {
                         format!("₳{ada_amount:.2}")
                     }
                     OperationPayload::NativeToken {
-                        policy_id: _,
+                        policy_id,
                         encoded_name,
                         amount,
                     } => {
-                        // Try to decode asset name
-                        let asset_name = if encoded_name.is_empty() {
-                            String::new()
-                        } else {
-                            match hex::decode(encoded_name) {
-                                Ok(bytes) => match String::from_utf8(bytes) {
-                                    Ok(decoded) => decoded,
-                                    Err(_) => encoded_name.clone(),
-                                },
-                                Err(_) => encoded_name.clone(),
-                            }
-                        };
+                        let asset_name =
+                            AssetId::new_unchecked(policy_id.clone(), encoded_name.clone())
+                                .display_name();
                         format!("{amount} {asset_name}")
                     }
                 };
@@ -1366,14 +1392,14 @@ impl std::fmt::Display for TxType {
                     let ada_amount = *amount_a as f64 / 1_000_000.0;
                     format!("₳{ada_amount:.2}")
                 } else {
-                    format!("{amount_a} {}", asset_a.asset_name())
+                    format!("{amount_a} {}", asset_a.display_name())
                 };
 
                 let b_display = if asset_b.policy_id.is_empty() {
                     let ada_amount = *amount_b as f64 / 1_000_000.0;
                     format!("₳{ada_amount:.2}")
                 } else {
-                    format!("{amount_b} {}", asset_b.asset_name())
+                    format!("{amount_b} {}", asset_b.display_name())
                 };
 
                 write!(
@@ -1393,18 +1419,46 @@ impl std::fmt::Display for TxType {
                     let ada_amount = *amount_a as f64 / 1_000_000.0;
                     format!("₳{ada_amount:.2}")
                 } else {
-                    format!("{amount_a} {}", asset_a.asset_name())
+                    format!("{amount_a} {}", asset_a.display_name())
                 };
 
                 let b_display = if asset_b.policy_id.is_empty() {
                     let ada_amount = *amount_b as f64 / 1_000_000.0;
                     format!("₳{ada_amount:.2}")
                 } else {
-                    format!("{amount_b} {}", asset_b.asset_name())
+                    format!("{amount_b} {}", asset_b.display_name())
                 };
 
                 write!(f, "Liquidity removed from {dex_platform}: {a_display} + {b_display} by {provider}")
             }
+            Self::AuctionBid {
+                asset,
+                bidder,
+                amount_lovelace,
+                auction_label,
+                ..
+            } => {
+                let ada_amount = *amount_lovelace as f64 / 1_000_000.0;
+                let name = asset.display_name();
+                write!(
+                    f,
+                    "Bid of ₳{ada_amount:.2} on {name} by {bidder} in {auction_label} auction"
+                )
+            }
+            Self::AuctionSettle {
+                asset,
+                winner,
+                amount_lovelace,
+                auction_label,
+                ..
+            } => {
+                let ada_amount = *amount_lovelace as f64 / 1_000_000.0;
+                let name = asset.display_name();
+                write!(
+                    f,
+                    "{name} won for ₳{ada_amount:.2} by {winner} in {auction_label} auction"
+                )
+            }
             Self::Unknown => write!(f, "Unknown transaction type"),
         }
     }
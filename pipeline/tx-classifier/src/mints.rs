@@ -150,6 +150,53 @@ pub(crate) fn detect_utxo_mint_type(
     crate::MintType::Unknown
 }
 
+/// Launchpad paid by the tx, from minter addresses in the registry
+pub(crate) fn detect_launchpad(tx_data: &crate::RawTxData) -> Option<String> {
+    use crate::registry::{lookup_address, AddressCategory, ScriptCategory};
+
+    tx_data
+        .outputs
+        .iter()
+        .find_map(|output| match lookup_address(&output.address) {
+            Some(AddressCategory::Script(ScriptCategory::Minter(minter))) => {
+                Some(minter.to_string())
+            }
+            _ => None,
+        })
+}
+
+/// Minting policy kind from the tx's redeemers: a mint redeemer means a
+/// Plutus policy, none means a native script. Native timelocks can't be told
+/// apart without the script itself, and sources without redeemer data give
+/// `None`.
+pub(crate) fn detect_policy_script(
+    tx_data: &crate::RawTxData,
+) -> Option<tx_insights::PolicyScriptType> {
+    use serde_json::Value;
+    use tx_insights::PolicyScriptType;
+
+    let has_mint_redeemer = match tx_data.redeemers.as_ref()? {
+        // Maestro groups redeemers by purpose
+        Value::Object(groups) => groups
+            .get("mints")
+            .and_then(Value::as_array)
+            .is_some_and(|mints| !mints.is_empty()),
+        // Koios lists plutus contracts with each redeemer's purpose
+        Value::Array(contracts) if !contracts.is_empty() => contracts.iter().any(|contract| {
+            contract
+                .pointer("/input/redeemer/purpose")
+                .and_then(Value::as_str)
+                == Some("mint")
+        }),
+        _ => return None,
+    };
+    Some(if has_mint_redeemer {
+        PolicyScriptType::Plutus
+    } else {
+        PolicyScriptType::Native
+    })
+}
+
 /// Separate minted assets into primary assets and reference assets based on mint type
 /// For CIP-68: UserNfts (primary) and ReferenceNfts (reference)
 /// For others: All assets are primary, no reference assets
@@ -0,0 +1,194 @@
+//! On-chain auction pattern detection
+//!
+//! Auction contracts are registered in the address registry as
+//! `ScriptCategory::Auction`. The lot sits at the contract alongside the
+//! standing bid: a bid spends the lot and re-locks it with more lovelace, and
+//! settlement releases it to the winner while the seller is paid out.
+//!
+//! No mainnet auction contracts are registered yet. Contracts that aren't in
+//! the registry can be checked directly with [`detect_auction_bids`] and
+//! [`detect_auction_settlements`], passing a label lookup for them.
+
+use super::{PatternContext, PatternDetectionResult};
+use crate::registry::{lookup_address, AddressCategory, ScriptCategory};
+use crate::*;
+use pipeline_types::AssetId;
+use tracing::debug;
+use transactions::RawTxData;
+
+/// Detect auction bids and settlements
+pub fn detect_auction_wrapper(context: &PatternContext) -> PatternDetectionResult {
+    let mut transactions = Vec::new();
+
+    transactions.extend(detect_auction_bids(context.raw_tx_data, auction_label));
+    transactions.extend(detect_auction_settlements(
+        context.raw_tx_data,
+        auction_label,
+    ));
+
+    PatternDetectionResult { transactions }
+}
+
+/// Registry label for an auction contract address
+fn auction_label(address: &str) -> Option<&'static str> {
+    match lookup_address(address) {
+        Some(AddressCategory::Script(ScriptCategory::Auction { label })) => Some(label),
+        _ => None,
+    }
+}
+
+/// Single-quantity native tokens held by a UTxO (the auction lots)
+fn lots(assets: &std::collections::HashMap<String, u64>) -> impl Iterator<Item = &String> {
+    assets
+        .iter()
+        .filter(|(unit, qty)| unit.as_str() != "lovelace" && **qty == 1)
+        .map(|(unit, _)| unit)
+}
+
+/// Bids: a lot is spent from an auction contract and re-locked at the same
+/// contract with more lovelace, funded by a wallet (the bidder). The reported
+/// amount is the lovelace now locked with the lot, i.e. the new standing bid.
+pub fn detect_auction_bids(
+    raw: &RawTxData,
+    label_for: impl Fn(&str) -> Option<&'static str>,
+) -> Vec<(TxType, f64)> {
+    let mut bids = Vec::new();
+
+    for input in &raw.inputs {
+        let Some(label) = label_for(&input.address) else {
+            continue;
+        };
+
+        for unit in lots(&input.assets) {
+            let Some(relocked) = raw
+                .outputs
+                .iter()
+                .find(|o| o.address == input.address && o.assets.get(unit) == Some(&1))
+            else {
+                continue;
+            };
+
+            if relocked.amount_lovelace <= input.amount_lovelace {
+                continue;
+            }
+
+            let Ok(asset) = AssetId::parse_concatenated(unit) else {
+                continue;
+            };
+
+            // Without a funding wallet this is a contract-only update, not a bid
+            let Some(bidder) = largest_wallet_input(raw, &label_for) else {
+                continue;
+            };
+
+            debug!(
+                "Detected auction bid on {} by {} ({} lovelace) at {}",
+                unit, bidder, relocked.amount_lovelace, label
+            );
+
+            bids.push((
+                TxType::AuctionBid {
+                    asset,
+                    bidder,
+                    amount_lovelace: relocked.amount_lovelace,
+                    auction_contract: input.address.clone(),
+                    auction_label: label.to_string(),
+                },
+                0.85,
+            ));
+        }
+    }
+
+    bids
+}
+
+/// Settlements: a lot leaves an auction contract for a wallet (the winner)
+/// while a different wallet (the seller) nets at least half the lovelace that
+/// was locked with the lot. Change returned to a wallet that funded the
+/// transaction isn't a payout, so reclaims of unsold lots are skipped. The reported amount is the lovelace that was
+/// locked with the lot, i.e. the winning bid.
+pub fn detect_auction_settlements(
+    raw: &RawTxData,
+    label_for: impl Fn(&str) -> Option<&'static str>,
+) -> Vec<(TxType, f64)> {
+    let mut settlements = Vec::new();
+    let is_wallet = |address: &str| label_for(address).is_none() && !is_script_address(address);
+
+    for input in &raw.inputs {
+        let Some(label) = label_for(&input.address) else {
+            continue;
+        };
+
+        for unit in lots(&input.assets) {
+            let Some(released) = raw
+                .outputs
+                .iter()
+                .find(|o| o.assets.get(unit) == Some(&1) && is_wallet(&o.address))
+            else {
+                continue;
+            };
+
+            let winner = released.address.clone();
+            let seller_paid = raw.outputs.iter().any(|o| {
+                o.address != winner
+                    && is_wallet(&o.address)
+                    && o.assets.is_empty()
+                    && net_lovelace(raw, &o.address) >= (input.amount_lovelace / 2) as i64
+            });
+            if !seller_paid {
+                continue;
+            }
+
+            let Ok(asset) = AssetId::parse_concatenated(unit) else {
+                continue;
+            };
+
+            debug!(
+                "Detected auction settlement of {} to {} ({} lovelace) at {}",
+                unit, winner, input.amount_lovelace, label
+            );
+
+            settlements.push((
+                TxType::AuctionSettle {
+                    asset,
+                    winner,
+                    amount_lovelace: input.amount_lovelace,
+                    auction_contract: input.address.clone(),
+                    auction_label: label.to_string(),
+                },
+                0.8,
+            ));
+        }
+    }
+
+    settlements
+}
+
+/// Wallet (non-script) input contributing the most lovelace — the bidder
+fn largest_wallet_input(
+    raw: &RawTxData,
+    label_for: &impl Fn(&str) -> Option<&'static str>,
+) -> Option<String> {
+    raw.inputs
+        .iter()
+        .filter(|i| label_for(&i.address).is_none() && !is_script_address(&i.address))
+        .max_by_key(|i| i.amount_lovelace)
+        .map(|i| i.address.clone())
+}
+
+/// Lovelace `address` receives in the outputs less what it spends in the inputs
+fn net_lovelace(raw: &RawTxData, address: &str) -> i64 {
+    let received: u64 = raw
+        .outputs
+        .iter()
+        .filter(|o| o.address == address)
+        .map(|o| o.amount_lovelace)
+        .sum();
+    let spent: u64 = raw
+        .inputs
+        .iter()
+        .filter(|i| i.address == address)
+        .map(|i| i.amount_lovelace)
+        .sum();
+    received as i64 - spent as i64
+}
@@ -88,6 +88,7 @@ fn detect_utxo_mint(context: &PatternContext) -> Vec<(TxType, f64)> {
     // Analyze mint type using CIP-68 detection logic from rules.rs
     let mint_type = crate::mints::detect_utxo_mint_type(&minted_assets, raw_tx_data);
     debug!("Detected mint type: {:?}", mint_type);
+    let policy_script = crate::mints::detect_policy_script(raw_tx_data);
 
     // Check for minter addresses in the registry and ADA flows to minters
     let mut minter_operations: HashMap<crate::registry::Minter, Vec<(String, String)>> =
@@ -179,6 +180,8 @@ fn detect_utxo_mint(context: &PatternContext) -> Vec<(TxType, f64)> {
                 total_lovelace: Some(actual_mint_cost),
                 minter: buyer,
                 mint_type: mint_type.clone(),
+                launchpad: Some(minter.to_string()),
+                policy_script,
             };
 
             // High confidence for marketplace mints with clear ADA payment
@@ -234,6 +237,8 @@ fn detect_utxo_mint(context: &PatternContext) -> Vec<(TxType, f64)> {
                 total_lovelace: Some(ada_to_minter),
                 minter: buyer,
                 mint_type: mint_type.clone(),
+                launchpad: Some(minter.to_string()),
+                policy_script,
             };
 
             debug!(
@@ -294,6 +299,8 @@ fn detect_utxo_mint(context: &PatternContext) -> Vec<(TxType, f64)> {
                 total_lovelace,
                 minter: minter_address,
                 mint_type: local_mint_type,
+                launchpad: None,
+                policy_script,
             };
             results.push((tx_type, 0.75));
         } else {
@@ -322,6 +329,8 @@ fn detect_utxo_mint(context: &PatternContext) -> Vec<(TxType, f64)> {
                     total_lovelace,
                     minter: minter_address,
                     mint_type: local_mint_type.clone(),
+                    launchpad: None,
+                    policy_script,
                 };
                 results.push((tx_type, 0.70));
             }
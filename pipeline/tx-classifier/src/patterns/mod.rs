@@ -3,6 +3,7 @@
 //! This module contains specialized pattern detectors for different types of Cardano transactions.
 //! Each sub-module focuses on a specific transaction type for better maintainability.
 
+pub mod auctions;
 pub mod dex;
 pub mod extractors;
 pub mod listings;
@@ -15,6 +16,7 @@ pub mod transfers;
 pub mod utils;
 
 // Re-export main types and functions for backward compatibility
+pub use auctions::*;
 pub use dex::*;
 pub use extractors::*;
 pub use listings::*;
@@ -129,6 +131,13 @@ static PATTERNS: Lazy<Vec<TransactionPattern>> = Lazy::new(|| {
             detect_fn: detect_transfers_wrapper,
             confidence_threshold: 0.5,
         },
+        // Auction patterns
+        TransactionPattern {
+            name: "auction_rule".to_string(),
+            description: "Detect bids and settlements on registered auction contracts".to_string(),
+            detect_fn: detect_auction_wrapper,
+            confidence_threshold: 0.75,
+        },
         // DEX patterns
         TransactionPattern {
             name: "dex_swap_rule".to_string(),
@@ -1173,6 +1173,8 @@ impl ClassificationRule for MintRule {
                 total_lovelace: total_mint_cost,
                 minter: minter_address,
                 mint_type,
+                launchpad: crate::mints::detect_launchpad(tx_data),
+                policy_script: crate::mints::detect_policy_script(tx_data),
             },
             confidence,
         ))
@@ -75,6 +75,39 @@ mod unit_tests {
         assert_eq!(policy_id.len(), POLICY_ID_LENGTH); // Policy ID should be expected length
         assert!(!asset_name.is_empty()); // Asset name should be decoded
     }
+
+    #[test]
+    fn test_script_hash_cip19_headers() {
+        use crate::insights::script_hash;
+
+        // Type 0: key payment, key stake
+        assert_eq!(script_hash("addr1q9797cl22rjxawzmuvawjvk90dfgc9rmkm7xutddp606eflawvndpy9pqhr3246yeyusa45jhz34wrzk0282d2srpscs3nm83x"), None);
+        // Type 1: script payment, key stake
+        assert_eq!(
+            script_hash("addr1zxnk7racqx3f7kg7npc4weggmpdskheu8pm57egr9av0mtvasazx8r5xwqtnfjsfrnat3h6yrycd2hfm9qpg7d0hf50s7x4y79").as_deref(),
+            Some("a76f0fb801a29f591e9871576508d85b0b5f3c38774f65032f58fdad")
+        );
+        // Type 3: script payment, script stake
+        assert_eq!(
+            script_hash("addr1xxgx3far7qygq0k6epa0zcvcvrevmn0ypsnfsue94nsn3tfvjel5h55fgjcxgchp830r7h2l5msrlpt8262r3nvr8eks2utwdd").as_deref(),
+            Some("9068a7a3f008803edac87af1619860f2cdcde40c26987325ace138ad")
+        );
+        // Type 6: key enterprise
+        assert_eq!(
+            script_hash("addr1v87m5srrtx52s8jdragjl8wle0eq57dzv2n62nxh3nx65dq0edwwu"),
+            None
+        );
+        // Type 7: script enterprise
+        assert_eq!(
+            script_hash("addr1w8p79rpkcdz8x9d6tft0x0dx5mwuzac2sa4gm8cvkw5hcnqst2ctf").as_deref(),
+            Some("c3e28c36c3447315ba5a56f33da6a6ddc1770a876a8d9f0cb3a97c4c")
+        );
+        // Not an address
+        assert_eq!(
+            script_hash("9068a7a3f008803edac87af1619860f2cdcde40c26987325ace138ad"),
+            None
+        );
+    }
 }
 
 /// Integration tests that require external dependencies
@@ -184,6 +217,77 @@ mod integration_tests {
         assert_eq!(mint_report, vec!["1 asset minted (CIP-25) for ₳52.24 by addr1q9c7f4we6cja8qvlc63ycep97xdxcv563upew7yvjpp5e0l4fr9rh39dpgmzl234njvxfpnah654jxuwzlgnqejnnkwqm0v2v2"]);
     }
 
+    #[test]
+    fn test_ancestors_mint_insight_context() {
+        use test_utils::test_case;
+        use tx_insights::{MintPhase, PolicyScriptType, TxInsight};
+
+        let complete_tx = load_tx(test_case!("txs/ancestors_mint.json"));
+        let (classification, _) = classify_tx(&complete_tx).unwrap();
+
+        let insights: Vec<TxInsight> = classification.into();
+        let Some(TxInsight::Mint {
+            context: Some(context),
+            assets,
+        }) = insights.into_iter().next()
+        else {
+            panic!("Expected a mint insight with context");
+        };
+
+        // Paid to the JPG.store minter, no mint redeemer
+        assert_eq!(assets.len(), 1);
+        assert_eq!(context.phase, MintPhase::PublicSale);
+        assert_eq!(context.launchpad.as_deref(), Some("JPG.store"));
+        assert_eq!(context.policy_script, Some(PolicyScriptType::Native));
+        assert_eq!(context.price_per_asset_lovelace, Some(52_236_825));
+    }
+
+    #[test]
+    fn test_unclassified_insight() {
+        use test_utils::test_case;
+        use tx_insights::TxInsight;
+
+        let complete_tx = load_tx(test_case!(
+            "txs/4c6d9ab5758bda791aedbf655cac78fac626e88637718a694635481782d93c8d.json"
+        ));
+        let (mut classification, _) = classify_tx(&complete_tx).unwrap();
+
+        // Pretend no rule matched; the executed script hash comes from the
+        // indexer, the address is reduced to its payment script hash
+        classification.tx_types = vec![TxType::Unknown];
+        classification.context.scripts.push(
+            "addr1zxnk7racqx3f7kg7npc4weggmpdskheu8pm57egr9av0mtvasazx8r5xwqtnfjsfrnat3h6yrycd2hfm9qpg7d0hf50s7x4y79".to_string(),
+        );
+        classification.context.notes = vec!["near miss".to_string(), "another".to_string()];
+
+        let insights: Vec<TxInsight> = classification.clone().into();
+        let [TxInsight::Unclassified {
+            policies,
+            script_hashes,
+            hint,
+        }] = insights.as_slice()
+        else {
+            panic!("Expected a single unclassified insight, got {insights:?}");
+        };
+        assert_eq!(
+            policies.iter().map(|p| p.as_str()).collect::<Vec<_>>(),
+            vec!["3cf8489b12ded9346708bed263307b362ce813636f92bddfd46e02ec"]
+        );
+        assert_eq!(
+            script_hashes,
+            &vec![
+                "9068a7a3f008803edac87af1619860f2cdcde40c26987325ace138ad".to_string(),
+                "a76f0fb801a29f591e9871576508d85b0b5f3c38774f65032f58fdad".to_string(),
+            ]
+        );
+        assert_eq!(hint.as_deref(), Some("near miss; another"));
+
+        // No native tokens moved, nothing worth surfacing
+        classification.assets.retain(|op| op.policy_id().is_none());
+        let insights: Vec<TxInsight> = classification.into();
+        assert!(insights.is_empty());
+    }
+
     // CBOR transaction parsing tests removed - transaction classification from CBOR not supported
     // Datum parsing from CBOR is still supported via decoder crate
 
@@ -258,9 +362,9 @@ mod integration_tests {
 
         // This transaction should detect 3 individual sales at ₳21.08 each
         assert_eq!(sales_report, vec![
-            "000de1404e696b65766572736530373833 sold for ₳24.00 to addr1q9c7f4we6cja8qvlc63ycep97xdxcv563upew7yvjpp5e0l4fr9rh39dpgmzl234njvxfpnah654jxuwzlgnqejnnkwqm0v2v2",
-            "000de1404e696b65766572736532363531 sold for ₳24.00 to addr1q9c7f4we6cja8qvlc63ycep97xdxcv563upew7yvjpp5e0l4fr9rh39dpgmzl234njvxfpnah654jxuwzlgnqejnnkwqm0v2v2",
-            "000de1404e696b65766572736533383137 sold for ₳24.00 to addr1q9c7f4we6cja8qvlc63ycep97xdxcv563upew7yvjpp5e0l4fr9rh39dpgmzl234njvxfpnah654jxuwzlgnqejnnkwqm0v2v2"
+            "Nikeverse0783 sold for ₳24.00 to addr1q9c7f4we6cja8qvlc63ycep97xdxcv563upew7yvjpp5e0l4fr9rh39dpgmzl234njvxfpnah654jxuwzlgnqejnnkwqm0v2v2",
+            "Nikeverse2651 sold for ₳24.00 to addr1q9c7f4we6cja8qvlc63ycep97xdxcv563upew7yvjpp5e0l4fr9rh39dpgmzl234njvxfpnah654jxuwzlgnqejnnkwqm0v2v2",
+            "Nikeverse3817 sold for ₳24.00 to addr1q9c7f4we6cja8qvlc63ycep97xdxcv563upew7yvjpp5e0l4fr9rh39dpgmzl234njvxfpnah654jxuwzlgnqejnnkwqm0v2v2"
         ]);
 
         // Also verify no false positive offer accepts
@@ -314,16 +418,16 @@ mod integration_tests {
 
         // This transaction should detect 3 individual sales at ₳21.08 each
         assert_eq!(sales_report, vec![
-            "000de1404e696b65766572736530323635 sold for ₳28.00 to addr1q9c7f4we6cja8qvlc63ycep97xdxcv563upew7yvjpp5e0l4fr9rh39dpgmzl234njvxfpnah654jxuwzlgnqejnnkwqm0v2v2",
-            "000de1404e696b65766572736531393535 sold for ₳28.00 to addr1q9c7f4we6cja8qvlc63ycep97xdxcv563upew7yvjpp5e0l4fr9rh39dpgmzl234njvxfpnah654jxuwzlgnqejnnkwqm0v2v2",
-            "000de1404e696b65766572736532313632 sold for ₳28.00 to addr1q9c7f4we6cja8qvlc63ycep97xdxcv563upew7yvjpp5e0l4fr9rh39dpgmzl234njvxfpnah654jxuwzlgnqejnnkwqm0v2v2",
-            "000de1404e696b65766572736532323930 sold for ₳28.00 to addr1q9c7f4we6cja8qvlc63ycep97xdxcv563upew7yvjpp5e0l4fr9rh39dpgmzl234njvxfpnah654jxuwzlgnqejnnkwqm0v2v2",
-            "000de1404e696b65766572736532333639 sold for ₳28.00 to addr1q9c7f4we6cja8qvlc63ycep97xdxcv563upew7yvjpp5e0l4fr9rh39dpgmzl234njvxfpnah654jxuwzlgnqejnnkwqm0v2v2",
-            "000de1404e696b65766572736533313732 sold for ₳28.00 to addr1q9c7f4we6cja8qvlc63ycep97xdxcv563upew7yvjpp5e0l4fr9rh39dpgmzl234njvxfpnah654jxuwzlgnqejnnkwqm0v2v2",
-            "000de1404e696b65766572736533323931 sold for ₳28.00 to addr1q9c7f4we6cja8qvlc63ycep97xdxcv563upew7yvjpp5e0l4fr9rh39dpgmzl234njvxfpnah654jxuwzlgnqejnnkwqm0v2v2",
-            "000de1404e696b65766572736533393138 sold for ₳28.00 to addr1q9c7f4we6cja8qvlc63ycep97xdxcv563upew7yvjpp5e0l4fr9rh39dpgmzl234njvxfpnah654jxuwzlgnqejnnkwqm0v2v2",
-            "000de1404e696b65766572736534343638 sold for ₳28.00 to addr1q9c7f4we6cja8qvlc63ycep97xdxcv563upew7yvjpp5e0l4fr9rh39dpgmzl234njvxfpnah654jxuwzlgnqejnnkwqm0v2v2",
-            "000de1404e696b65766572736534383737 sold for ₳28.00 to addr1q9c7f4we6cja8qvlc63ycep97xdxcv563upew7yvjpp5e0l4fr9rh39dpgmzl234njvxfpnah654jxuwzlgnqejnnkwqm0v2v2"
+            "Nikeverse0265 sold for ₳28.00 to addr1q9c7f4we6cja8qvlc63ycep97xdxcv563upew7yvjpp5e0l4fr9rh39dpgmzl234njvxfpnah654jxuwzlgnqejnnkwqm0v2v2",
+            "Nikeverse1955 sold for ₳28.00 to addr1q9c7f4we6cja8qvlc63ycep97xdxcv563upew7yvjpp5e0l4fr9rh39dpgmzl234njvxfpnah654jxuwzlgnqejnnkwqm0v2v2",
+            "Nikeverse2162 sold for ₳28.00 to addr1q9c7f4we6cja8qvlc63ycep97xdxcv563upew7yvjpp5e0l4fr9rh39dpgmzl234njvxfpnah654jxuwzlgnqejnnkwqm0v2v2",
+            "Nikeverse2290 sold for ₳28.00 to addr1q9c7f4we6cja8qvlc63ycep97xdxcv563upew7yvjpp5e0l4fr9rh39dpgmzl234njvxfpnah654jxuwzlgnqejnnkwqm0v2v2",
+            "Nikeverse2369 sold for ₳28.00 to addr1q9c7f4we6cja8qvlc63ycep97xdxcv563upew7yvjpp5e0l4fr9rh39dpgmzl234njvxfpnah654jxuwzlgnqejnnkwqm0v2v2",
+            "Nikeverse3172 sold for ₳28.00 to addr1q9c7f4we6cja8qvlc63ycep97xdxcv563upew7yvjpp5e0l4fr9rh39dpgmzl234njvxfpnah654jxuwzlgnqejnnkwqm0v2v2",
+            "Nikeverse3291 sold for ₳28.00 to addr1q9c7f4we6cja8qvlc63ycep97xdxcv563upew7yvjpp5e0l4fr9rh39dpgmzl234njvxfpnah654jxuwzlgnqejnnkwqm0v2v2",
+            "Nikeverse3918 sold for ₳28.00 to addr1q9c7f4we6cja8qvlc63ycep97xdxcv563upew7yvjpp5e0l4fr9rh39dpgmzl234njvxfpnah654jxuwzlgnqejnnkwqm0v2v2",
+            "Nikeverse4468 sold for ₳28.00 to addr1q9c7f4we6cja8qvlc63ycep97xdxcv563upew7yvjpp5e0l4fr9rh39dpgmzl234njvxfpnah654jxuwzlgnqejnnkwqm0v2v2",
+            "Nikeverse4877 sold for ₳28.00 to addr1q9c7f4we6cja8qvlc63ycep97xdxcv563upew7yvjpp5e0l4fr9rh39dpgmzl234njvxfpnah654jxuwzlgnqejnnkwqm0v2v2"
         ]);
 
         // Also verify no false positive offer accepts
@@ -1258,3 +1362,86 @@ mod compound_transaction_tests {
         ]);
     }
 }
+
+/// No auction contract has on-chain history in the snapshots yet, so these run
+/// the auction detectors over real marketplace transactions, treating the
+/// marketplace contract as the auction: a purchase has the same shape as a
+/// settlement, and delists and price updates must not match.
+#[cfg(test)]
+mod auction_tests {
+    use crate::patterns::{detect_auction_bids, detect_auction_settlements};
+    use crate::tests::{classify_tx, load_tx};
+    use crate::TxType;
+    use test_utils::test_case;
+
+    /// Label the listing contracts seen in the snapshots as auctions
+    fn as_auction(address: &str) -> Option<&'static str> {
+        [
+            "addr1x8rjw3pawl0kelu4mj3c8x20fsczf5pl744s9mxz9v8n7e",
+            "addr1zxnk7racqx3f7kg7npc4weggm",
+            "addr1zxgx3far7qygq0k6epa0zcvcv",
+        ]
+        .iter()
+        .any(|prefix| address.starts_with(prefix))
+        .then_some("Test")
+    }
+
+    #[test]
+    fn test_purchase_shaped_settlement() {
+        let (_, raw) = classify_tx(&load_tx(test_case!("txs/blackflag_sale_wayup.json"))).unwrap();
+
+        let settlements = detect_auction_settlements(&raw, as_auction);
+        assert_eq!(settlements.len(), 1);
+        let (
+            TxType::AuctionSettle {
+                asset,
+                winner,
+                amount_lovelace,
+                auction_label,
+                ..
+            },
+            _,
+        ) = &settlements[0]
+        else {
+            panic!("expected a settlement, got {settlements:?}");
+        };
+        assert_eq!(
+            asset.policy_id(),
+            "b3dab69f7e6100849434fb1781e34bd12a916557f6231b8d2629b6f6"
+        );
+        assert_eq!(winner, "addr1q9xw2fj2x9tuvpf26yflx6sczxmdcazsej5wcll7u0css8q5nq6h4vqfvzfracm5fn3pssk596qt0046ua5jeveq3wjs0y2wgs");
+        assert_eq!(*amount_lovelace, 1_310_240);
+        assert_eq!(auction_label, "Test");
+
+        assert!(detect_auction_bids(&raw, as_auction).is_empty());
+    }
+
+    #[test]
+    fn test_bundle_purchase_settles_each_lot() {
+        let (_, raw) = classify_tx(&load_tx(test_case!("txs/nikeverse_sale.json"))).unwrap();
+
+        let settlements = detect_auction_settlements(&raw, as_auction);
+        assert_eq!(settlements.len(), 3);
+        assert!(settlements.iter().all(|(tx, _)| matches!(
+            tx,
+            TxType::AuctionSettle { winner, .. }
+                if winner == "addr1q9c7f4we6cja8qvlc63ycep97xdxcv563upew7yvjpp5e0l4fr9rh39dpgmzl234njvxfpnah654jxuwzlgnqejnnkwqm0v2v2"
+        )));
+    }
+
+    #[test]
+    fn test_reclaim_is_not_a_settlement() {
+        // The lot and the change both go back to the seller: nobody is paid
+        let (_, raw) = classify_tx(&load_tx(test_case!("txs/kwic_unlisting.json"))).unwrap();
+        assert!(detect_auction_settlements(&raw, as_auction).is_empty());
+        assert!(detect_auction_bids(&raw, as_auction).is_empty());
+    }
+
+    #[test]
+    fn test_relock_without_more_lovelace_is_not_a_bid() {
+        let (_, raw) =
+            classify_tx(&load_tx(test_case!("txs/ancestors_price_update.json"))).unwrap();
+        assert!(detect_auction_bids(&raw, as_auction).is_empty());
+        assert!(detect_auction_settlements(&raw, as_auction).is_empty());
+    }
+}
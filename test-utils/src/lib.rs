@@ -36,6 +36,58 @@ macro_rules! load_test_resource {
     }};
 }
 
+/// Build a fixture-backed [`http_client::HttpClient`] for the named API.
+///
+/// Fixtures live in `<crate>/resources/fixtures/<name>/`. Requests are replayed
+/// from disk by default; set `HTTP_FIXTURES=record` (with real credentials in
+/// the environment) to hit the live API and refresh the fixture files.
+///
+/// # Example
+/// ```ignore
+/// use test_utils::fixture_client;
+///
+/// let client = fixture_client!("maestro").with_header("api-key", "test");
+/// let api = maestro::MaestroApi::new("test".into(), "mainnet.gomaestro-api.org/v1".into())
+///     .with_http_client(client);
+/// ```
+#[cfg(all(feature = "http-fixtures", not(target_arch = "wasm32")))]
+#[macro_export]
+macro_rules! fixture_client {
+    ($name:expr) => {{
+        $crate::http_fixtures::HttpClient::new().with_fixtures($crate::http_fixtures::fixture_mode(
+            env!("CARGO_MANIFEST_DIR"),
+            $name,
+        ))
+    }};
+}
+
+#[cfg(all(feature = "http-fixtures", not(target_arch = "wasm32")))]
+pub mod http_fixtures {
+    pub use http_client::fixtures::{fixture_file_name, Fixture};
+    pub use http_client::{FixtureMode, HttpClient};
+    use std::path::PathBuf;
+
+    /// Environment variable that switches fixtures into record mode
+    pub const MODE_ENV: &str = "HTTP_FIXTURES";
+
+    /// Directory holding fixtures for `name` within a crate
+    pub fn fixture_dir(manifest_dir: &str, name: &str) -> PathBuf {
+        let mut path = PathBuf::from(manifest_dir);
+        path.push("resources/fixtures");
+        path.push(name);
+        path
+    }
+
+    /// Replay unless `HTTP_FIXTURES=record` is set
+    pub fn fixture_mode(manifest_dir: &str, name: &str) -> FixtureMode {
+        let dir = fixture_dir(manifest_dir, name);
+        match std::env::var(MODE_ENV).as_deref() {
+            Ok("record") => FixtureMode::Record(dir),
+            _ => FixtureMode::Replay(dir),
+        }
+    }
+}
+
 pub fn init_test_tracing() {
     // Use a simple formatting subscriber for local dev/test logs.
     let subscriber = tracing_subscriber::fmt()
@@ -0,0 +1,116 @@
+//! Building [`TxInsight::Airdrop`](crate::TxInsight::Airdrop)
+//!
+//! Classifier guidelines: a transaction is an airdrop when at least
+//! [`AIRDROP_MIN_RECIPIENTS`] distinct addresses receive fungible tokens of
+//! one policy without paying for them. Pass only the outputs that count:
+//!
+//! - leave out the sender's change output and anything sent back to an input
+//!   address;
+//! - skip the transaction altogether if it mints the tokens (that's a
+//!   [`Mint`](crate::TxInsight::Mint)) or spends a marketplace or DEX script.
+
+use std::collections::HashMap;
+
+use cardano_assets::PolicyId;
+
+use crate::TxInsight;
+
+/// Fewest distinct recipients for a transfer to be announced as an airdrop
+pub const AIRDROP_MIN_RECIPIENTS: usize = 10;
+
+/// Recipients kept in `sample_recipients`, largest amounts first
+pub const AIRDROP_SAMPLE_SIZE: usize = 5;
+
+impl TxInsight {
+    /// An [`Airdrop`](TxInsight::Airdrop) from `(address, quantity)` outputs
+    /// of `policy_id`'s tokens, or `None` below [`AIRDROP_MIN_RECIPIENTS`]
+    pub fn airdrop(
+        policy_id: PolicyId,
+        outputs: impl IntoIterator<Item = (String, u64)>,
+    ) -> Option<Self> {
+        let mut received: HashMap<String, u64> = HashMap::new();
+        for (address, quantity) in outputs {
+            if quantity > 0 {
+                let total = received.entry(address).or_default();
+                *total = total.saturating_add(quantity);
+            }
+        }
+        if received.len() < AIRDROP_MIN_RECIPIENTS {
+            return None;
+        }
+
+        let total_quantity = received
+            .values()
+            .fold(0u64, |sum, quantity| sum.saturating_add(*quantity));
+        let recipients_count = received.len() as u32;
+
+        let mut recipients: Vec<_> = received.into_iter().collect();
+        recipients.sort_by(|(a, qa), (b, qb)| qb.cmp(qa).then_with(|| a.cmp(b)));
+        let sample_recipients = recipients
+            .into_iter()
+            .take(AIRDROP_SAMPLE_SIZE)
+            .map(|(address, _)| address)
+            .collect();
+
+        Some(TxInsight::Airdrop {
+            policy_id,
+            recipients_count,
+            total_quantity,
+            sample_recipients,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const POLICY: &str = "b3dab69f7e6100849434fb1781e34bd12a916557f6231b8d2629b6f6";
+
+    fn outputs(count: usize) -> Vec<(String, u64)> {
+        (0..count)
+            .map(|i| (format!("addr1holder{i:02}"), 1_000 + i as u64))
+            .collect()
+    }
+
+    #[test]
+    fn test_too_few_recipients() {
+        let policy_id = PolicyId::new(POLICY).unwrap();
+        assert!(TxInsight::airdrop(policy_id.clone(), outputs(9)).is_none());
+
+        // Two outputs to one address count once
+        let mut repeated = outputs(9);
+        repeated.push(("addr1holder00".to_string(), 5));
+        assert!(TxInsight::airdrop(policy_id, repeated).is_none());
+    }
+
+    #[test]
+    fn test_airdrop_totals_and_sample() {
+        let policy_id = PolicyId::new(POLICY).unwrap();
+        let mut outputs = outputs(12);
+        outputs.push(("addr1holder00".to_string(), 10_000));
+        outputs.push(("addr1empty".to_string(), 0));
+
+        let Some(TxInsight::Airdrop {
+            recipients_count,
+            total_quantity,
+            sample_recipients,
+            ..
+        }) = TxInsight::airdrop(policy_id, outputs)
+        else {
+            panic!("expected an airdrop");
+        };
+        assert_eq!(recipients_count, 12);
+        assert_eq!(total_quantity, (1_000..1_012).sum::<u64>() + 10_000);
+        assert_eq!(
+            sample_recipients,
+            [
+                "addr1holder00",
+                "addr1holder11",
+                "addr1holder10",
+                "addr1holder09",
+                "addr1holder08"
+            ]
+        );
+    }
+}
@@ -0,0 +1,404 @@
+//! Counterparty tagging for analysed transactions
+//!
+//! Insights carry raw bech32 addresses for sellers, buyers, bidders and so
+//! on. [`AnalysedTx::tag_counterparties`] resolves each of them against a
+//! [`CounterpartyResolver`] and records the matches in
+//! [`AnalysedTx::counterparties`], so notifications can show
+//! "JPG.store escrow" or "$pirate" instead of `addr1xyz…`.
+//!
+//! [`CounterpartyRegistry`] covers addresses a worker knows about (community
+//! handles, burn wallets, treasury wallets). With the `known-addresses`
+//! feature, `address_registry::SmartContractRegistry` resolves the curated
+//! marketplace, exchange and contract addresses. Resolvers compose as a
+//! tuple, first match wins:
+//!
+//! ```
+//! use tx_insights::{CounterpartyRegistry, CounterpartyResolver};
+//!
+//! let handles = CounterpartyRegistry::new().with_handle("addr1alice", "alice");
+//! let burns = CounterpartyRegistry::new().with_burn("addr1burn");
+//! let resolver = (handles, burns);
+//!
+//! assert_eq!(resolver.resolve("addr1alice").unwrap().to_string(), "$alice");
+//! assert_eq!(resolver.resolve("addr1burn").unwrap().to_string(), "Burn address");
+//! assert!(resolver.resolve("addr1bob").is_none());
+//! ```
+
+use std::collections::HashMap;
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "openapi")]
+use utoipa::ToSchema;
+
+use crate::{AnalysedTx, TxInsight};
+
+/// What kind of party sits behind an address
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum CounterpartyKind {
+    /// Marketplace escrow, offer or fee wallet
+    Marketplace,
+    /// Centralised exchange wallet or DEX contract
+    Exchange,
+    /// Address that can never spend what it receives
+    Burn,
+    /// Other known smart contract (staking, vesting, auctions, ...)
+    Contract,
+    /// Wallet registered by a community member
+    Community,
+}
+
+/// A human-readable name for an address
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+pub struct CounterpartyTag {
+    pub kind: CounterpartyKind,
+    /// Display label, e.g. `JPG.store escrow` or `$pirate`
+    pub label: String,
+}
+
+impl CounterpartyTag {
+    pub fn new(kind: CounterpartyKind, label: impl Into<String>) -> Self {
+        Self {
+            kind,
+            label: label.into(),
+        }
+    }
+
+    pub fn marketplace(label: impl Into<String>) -> Self {
+        Self::new(CounterpartyKind::Marketplace, label)
+    }
+
+    pub fn exchange(label: impl Into<String>) -> Self {
+        Self::new(CounterpartyKind::Exchange, label)
+    }
+
+    pub fn burn() -> Self {
+        Self::new(CounterpartyKind::Burn, "Burn address")
+    }
+
+    pub fn contract(label: impl Into<String>) -> Self {
+        Self::new(CounterpartyKind::Contract, label)
+    }
+
+    /// An ADA handle, shown with its `$` prefix
+    pub fn handle(handle: &str) -> Self {
+        Self::new(
+            CounterpartyKind::Community,
+            format!("${}", handle.trim_start_matches('$')),
+        )
+    }
+}
+
+impl fmt::Display for CounterpartyTag {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.label)
+    }
+}
+
+/// Looks up the tag for an address
+pub trait CounterpartyResolver {
+    /// `None` if the address is unknown to this resolver
+    fn resolve(&self, address: &str) -> Option<CounterpartyTag>;
+}
+
+impl<R: CounterpartyResolver + ?Sized> CounterpartyResolver for &R {
+    fn resolve(&self, address: &str) -> Option<CounterpartyTag> {
+        (**self).resolve(address)
+    }
+}
+
+impl<R: CounterpartyResolver + ?Sized> CounterpartyResolver for Box<R> {
+    fn resolve(&self, address: &str) -> Option<CounterpartyTag> {
+        (**self).resolve(address)
+    }
+}
+
+impl<A: CounterpartyResolver, B: CounterpartyResolver> CounterpartyResolver for (A, B) {
+    fn resolve(&self, address: &str) -> Option<CounterpartyTag> {
+        self.0.resolve(address).or_else(|| self.1.resolve(address))
+    }
+}
+
+impl<R: CounterpartyResolver> CounterpartyResolver for [R] {
+    fn resolve(&self, address: &str) -> Option<CounterpartyTag> {
+        self.iter().find_map(|resolver| resolver.resolve(address))
+    }
+}
+
+impl<R: CounterpartyResolver> CounterpartyResolver for Vec<R> {
+    fn resolve(&self, address: &str) -> Option<CounterpartyTag> {
+        self.as_slice().resolve(address)
+    }
+}
+
+/// Runtime address → tag table
+///
+/// Exact addresses are checked before prefixes; prefixes match script
+/// addresses whose staking credential varies per user.
+#[derive(Debug, Clone, Default)]
+pub struct CounterpartyRegistry {
+    addresses: HashMap<String, CounterpartyTag>,
+    prefixes: Vec<(String, CounterpartyTag)>,
+}
+
+impl CounterpartyRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn insert(&mut self, address: impl Into<String>, tag: CounterpartyTag) {
+        self.addresses.insert(address.into(), tag);
+    }
+
+    pub fn insert_prefix(&mut self, prefix: impl Into<String>, tag: CounterpartyTag) {
+        self.prefixes.push((prefix.into(), tag));
+    }
+
+    pub fn with_address(mut self, address: impl Into<String>, tag: CounterpartyTag) -> Self {
+        self.insert(address, tag);
+        self
+    }
+
+    pub fn with_prefix(mut self, prefix: impl Into<String>, tag: CounterpartyTag) -> Self {
+        self.insert_prefix(prefix, tag);
+        self
+    }
+
+    pub fn with_handle(self, address: impl Into<String>, handle: &str) -> Self {
+        self.with_address(address, CounterpartyTag::handle(handle))
+    }
+
+    pub fn with_burn(self, address: impl Into<String>) -> Self {
+        self.with_address(address, CounterpartyTag::burn())
+    }
+
+    pub fn len(&self) -> usize {
+        self.addresses.len() + self.prefixes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.addresses.is_empty() && self.prefixes.is_empty()
+    }
+}
+
+impl CounterpartyResolver for CounterpartyRegistry {
+    fn resolve(&self, address: &str) -> Option<CounterpartyTag> {
+        if let Some(tag) = self.addresses.get(address) {
+            return Some(tag.clone());
+        }
+        self.prefixes
+            .iter()
+            .find(|(prefix, _)| address.starts_with(prefix.as_str()))
+            .map(|(_, tag)| tag.clone())
+    }
+}
+
+#[cfg(feature = "known-addresses")]
+mod known {
+    use address_registry::{
+        AddressCategory, AddressLookup, MarketplacePurpose, ScriptCategory, SmartContractRegistry,
+    };
+
+    use super::{CounterpartyResolver, CounterpartyTag};
+
+    impl CounterpartyTag {
+        /// Tag for a curated `address_registry` category
+        pub fn from_category(category: &AddressCategory) -> Option<Self> {
+            match category {
+                AddressCategory::Unknown => None,
+                AddressCategory::Marketplace(marketplace) => {
+                    Some(CounterpartyTag::marketplace(marketplace.to_string()))
+                }
+                AddressCategory::Script(script) => match script {
+                    ScriptCategory::Unknown => None,
+                    ScriptCategory::Marketplace {
+                        marketplace,
+                        purpose,
+                        ..
+                    } => {
+                        let role = match purpose {
+                            MarketplacePurpose::Sale => " escrow",
+                            MarketplacePurpose::Offer => " offers",
+                            MarketplacePurpose::Fee => " fees",
+                            MarketplacePurpose::Unknown => "",
+                        };
+                        Some(CounterpartyTag::marketplace(format!("{marketplace}{role}")))
+                    }
+                    ScriptCategory::Exchange { label } => Some(CounterpartyTag::exchange(*label)),
+                    other => Some(CounterpartyTag::contract(other.to_string())),
+                },
+            }
+        }
+    }
+
+    impl CounterpartyResolver for SmartContractRegistry {
+        fn resolve(&self, address: &str) -> Option<CounterpartyTag> {
+            self.lookup(address)
+                .and_then(CounterpartyTag::from_category)
+        }
+    }
+}
+
+impl TxInsight {
+    /// Wallet addresses of the parties involved in this insight
+    pub fn counterparty_addresses(&self) -> Vec<&str> {
+        match self {
+            TxInsight::Mint { .. }
+            | TxInsight::DexTrade { .. }
+            | TxInsight::Unclassified { .. }
+            | TxInsight::Unknown => Vec::new(),
+            TxInsight::OfferCreate { seller, .. } | TxInsight::Listing { seller, .. } => {
+                vec![seller.as_str()]
+            }
+            TxInsight::Sale { seller, buyer, .. } => vec![seller.as_str(), buyer.as_str()],
+            TxInsight::AuctionBid { bidder, .. } => vec![bidder.as_str()],
+            TxInsight::AuctionSettled { winner, .. } => vec![winner.as_str()],
+            TxInsight::ListForRent { lender, .. } => vec![lender.as_str()],
+            TxInsight::RentStarted { lender, renter, .. }
+            | TxInsight::RentEnded { lender, renter, .. } => vec![lender.as_str(), renter.as_str()],
+            TxInsight::Airdrop {
+                sample_recipients, ..
+            } => sample_recipients.iter().map(String::as_str).collect(),
+        }
+    }
+}
+
+impl AnalysedTx {
+    /// Resolve every counterparty address in the insights, replacing any
+    /// earlier tags. Returns the number of addresses tagged.
+    pub fn tag_counterparties<R: CounterpartyResolver + ?Sized>(&mut self, resolver: &R) -> usize {
+        let mut counterparties = HashMap::new();
+        for address in self
+            .insights
+            .iter()
+            .flat_map(|insight| insight.counterparty_addresses())
+        {
+            if !counterparties.contains_key(address) {
+                if let Some(tag) = resolver.resolve(address) {
+                    counterparties.insert(address.to_string(), tag);
+                }
+            }
+        }
+        self.counterparties = counterparties;
+        self.counterparties.len()
+    }
+
+    /// Tag for `address`, if it was resolved
+    pub fn counterparty(&self, address: &str) -> Option<&CounterpartyTag> {
+        self.counterparties.get(address)
+    }
+
+    /// Display name for `address`: its tag label, or the shortened address
+    pub fn counterparty_label(&self, address: &str) -> String {
+        match self.counterparty(address) {
+            Some(tag) => tag.label.clone(),
+            None => short_address(address),
+        }
+    }
+}
+
+/// `addr1qxy…k3j9` style abbreviation for display
+pub fn short_address(address: &str) -> String {
+    let chars: Vec<char> = address.chars().collect();
+    if chars.len() <= 16 {
+        return address.to_string();
+    }
+    let head: String = chars[..9].iter().collect();
+    let tail: String = chars[chars.len() - 4..].iter().collect();
+    format!("{head}…{tail}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{AssetSaleKind, TxAsset};
+    use cardano_assets::AssetId;
+
+    const SELLER: &str = "addr1q9seller0000000000000000000000000000000000000000000000000000000";
+    const ESCROW: &str = "addr1zxescrow00000000000000000000000000000000000000000000000000000000";
+
+    fn sale(seller: &str, buyer: &str) -> TxInsight {
+        TxInsight::Sale {
+            asset: TxAsset {
+                id: AssetId::parse_concatenated(
+                    "b3dab69f7e6100849434fb1781e34bd12a916557f6231b8d2629b6f650697261746531303836",
+                )
+                .unwrap(),
+                qty: 1,
+                traits: None,
+                name: None,
+                image: None,
+                rarity_rank: None,
+            },
+            kind: AssetSaleKind::Standard,
+            seller: seller.to_string(),
+            buyer: buyer.to_string(),
+            price_lovelace: 125_000_000,
+            price_token: None,
+            seller_stake: None,
+            buyer_stake: None,
+        }
+    }
+
+    #[test]
+    fn test_registry_exact_and_prefix() {
+        let registry = CounterpartyRegistry::new()
+            .with_prefix(
+                "addr1zxescrow",
+                CounterpartyTag::marketplace("JPG.store escrow"),
+            )
+            .with_address(ESCROW, CounterpartyTag::contract("Exact wins"));
+
+        assert_eq!(registry.resolve(ESCROW).unwrap().label, "Exact wins");
+        assert_eq!(
+            registry.resolve("addr1zxescrow11").unwrap().kind,
+            CounterpartyKind::Marketplace
+        );
+        assert!(registry.resolve(SELLER).is_none());
+        assert_eq!(CounterpartyTag::handle("$pirate").label, "$pirate");
+    }
+
+    #[test]
+    fn test_tag_counterparties() {
+        let mut tx = AnalysedTx {
+            hash: "tx123".to_string(),
+            insights: vec![sale(SELLER, ESCROW), sale(SELLER, "addr1unknown")],
+            counterparties: HashMap::new(),
+        };
+        let resolver = vec![
+            CounterpartyRegistry::new().with_handle(SELLER, "pirate"),
+            CounterpartyRegistry::new()
+                .with_address(ESCROW, CounterpartyTag::marketplace("JPG.store escrow")),
+        ];
+
+        assert_eq!(tx.tag_counterparties(&resolver), 2);
+        assert_eq!(tx.counterparty_label(SELLER), "$pirate");
+        assert_eq!(tx.counterparty_label(ESCROW), "JPG.store escrow");
+        assert_eq!(tx.counterparty_label("addr1unknown"), "addr1unknown");
+        assert_eq!(short_address(SELLER), "addr1q9se…0000");
+
+        let json = serde_json::to_string(&tx).unwrap();
+        assert!(json.contains("\"kind\":\"community\""));
+        let back: AnalysedTx = serde_json::from_str(&json).unwrap();
+        assert_eq!(back.counterparty(ESCROW), tx.counterparty(ESCROW));
+    }
+
+    #[cfg(feature = "known-addresses")]
+    #[test]
+    fn test_known_addresses() {
+        let registry = address_registry::SmartContractRegistry::new();
+        let tag = registry
+            .resolve("addr1w8p79rpkcdz8x9d6tft0x0dx5mwuzac2sa4gm8cvkw5hcnqst2ctf")
+            .unwrap();
+        assert_eq!(tag, CounterpartyTag::exchange("Minswap"));
+
+        let tag = registry
+            .resolve("addr1x8rjw3pawl0kelu4mj3c8x20fsczf5pl744s9mxz9v8n7efvjel5h55fgjcxgchp830r7h2l5msrlpt8262r3nvr8ekstg4qrx")
+            .unwrap();
+        assert_eq!(tag.to_string(), "JPG.store escrow");
+    }
+}
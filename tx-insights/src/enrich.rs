@@ -0,0 +1,302 @@
+//! Asset enrichment for analysed transactions
+//!
+//! Classifiers emit [`TxAsset`]s with just an id and quantity. Before a
+//! notification goes out, [`AnalysedTx::enrich_assets`] fills in name, image,
+//! traits and rarity from an [`AssetEnricher`] backed by whatever metadata
+//! source the worker has (maestro, anvil, a KV cache, ...).
+//!
+//! Enrichment is best effort: assets the enricher can't resolve keep their
+//! bare id, and failures are reported in the [`EnrichOutcome`] rather than
+//! failing the whole transaction.
+
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::sync::Mutex;
+
+use async_trait::async_trait;
+
+use crate::{AnalysedTx, TxAsset};
+
+/// Metadata resolved for a single asset
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct EnrichedAsset {
+    pub name: Option<String>,
+    pub image: Option<String>,
+    pub traits: HashMap<String, Vec<String>>,
+    pub rarity_rank: Option<u32>,
+}
+
+/// Resolves asset metadata by concatenated asset id (policy id + asset name hex)
+///
+/// `?Send` so implementations can wrap wasm HTTP clients.
+#[async_trait(?Send)]
+pub trait AssetEnricher {
+    type Error: fmt::Display;
+
+    /// `Ok(None)` if the asset is unknown to this source
+    async fn enrich(&self, asset_id: &str) -> Result<Option<EnrichedAsset>, Self::Error>;
+}
+
+/// Result of [`AnalysedTx::enrich_assets`] and
+/// [`AnalysedTx::resolve_stakes`]
+#[derive(Debug)]
+pub struct EnrichOutcome<E> {
+    /// Distinct ids (asset ids, or addresses for stake resolution) that
+    /// were resolved
+    pub enriched: usize,
+    /// Distinct ids the source didn't know
+    pub not_found: Vec<String>,
+    pub failed: Vec<(String, E)>,
+}
+
+impl<E> EnrichOutcome<E> {
+    pub fn is_complete(&self) -> bool {
+        self.not_found.is_empty() && self.failed.is_empty()
+    }
+}
+
+impl TxAsset {
+    /// Fill fields the classifier left empty; existing values are kept
+    pub fn apply_enrichment(&mut self, enriched: &EnrichedAsset) {
+        if self.name.is_none() {
+            self.name.clone_from(&enriched.name);
+        }
+        if self.image.is_none() {
+            self.image.clone_from(&enriched.image);
+        }
+        if self.rarity_rank.is_none() {
+            self.rarity_rank = enriched.rarity_rank;
+        }
+        let has_traits = self.traits.as_ref().is_some_and(|t| !t.is_empty());
+        if !has_traits && !enriched.traits.is_empty() {
+            self.traits = Some(enriched.traits.clone());
+        }
+    }
+}
+
+impl AnalysedTx {
+    /// Resolve every distinct asset in the insights, at most `concurrency`
+    /// lookups in flight at once
+    pub async fn enrich_assets<E: AssetEnricher + ?Sized>(
+        &mut self,
+        enricher: &E,
+        concurrency: usize,
+    ) -> EnrichOutcome<E::Error> {
+        let mut seen = HashSet::new();
+        let ids: Vec<String> = self
+            .insights
+            .iter()
+            .flat_map(|insight| insight.assets())
+            .filter(|asset| seen.insert(&asset.id))
+            .map(|asset| asset.id.concatenated())
+            .collect();
+
+        let results = worker_utils::join_bounded(
+            ids.into_iter().map(|id| async move {
+                let result = enricher.enrich(&id).await;
+                (id, result)
+            }),
+            concurrency,
+        )
+        .await;
+
+        let mut resolved = HashMap::new();
+        let mut outcome = EnrichOutcome {
+            enriched: 0,
+            not_found: Vec::new(),
+            failed: Vec::new(),
+        };
+        for (id, result) in results {
+            match result {
+                Ok(Some(enriched)) => {
+                    resolved.insert(id, enriched);
+                }
+                Ok(None) => outcome.not_found.push(id),
+                Err(e) => outcome.failed.push((id, e)),
+            }
+        }
+        outcome.enriched = resolved.len();
+
+        for asset in self
+            .insights
+            .iter_mut()
+            .flat_map(|insight| insight.assets_mut())
+        {
+            if let Some(enriched) = resolved.get(&asset.id.concatenated()) {
+                asset.apply_enrichment(enriched);
+            }
+        }
+
+        outcome
+    }
+}
+
+/// Memoizes another enricher's hits and misses
+///
+/// Errors aren't cached, so a transient failure is retried on the next
+/// lookup. Share one instance across transactions in a batch so repeat
+/// assets (sweeps, bundle sales) are resolved once.
+pub struct CachingEnricher<E> {
+    inner: E,
+    cache: Mutex<HashMap<String, Option<EnrichedAsset>>>,
+}
+
+impl<E> CachingEnricher<E> {
+    pub fn new(inner: E) -> Self {
+        Self {
+            inner,
+            cache: Mutex::new(HashMap::new()),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.cache
+            .lock()
+            .map(|cache| cache.len())
+            .unwrap_or_default()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+#[async_trait(?Send)]
+impl<E: AssetEnricher> AssetEnricher for CachingEnricher<E> {
+    type Error = E::Error;
+
+    async fn enrich(&self, asset_id: &str) -> Result<Option<EnrichedAsset>, Self::Error> {
+        if let Some(cached) = self
+            .cache
+            .lock()
+            .ok()
+            .and_then(|cache| cache.get(asset_id).cloned())
+        {
+            return Ok(cached);
+        }
+
+        let result = self.inner.enrich(asset_id).await?;
+        if let Ok(mut cache) = self.cache.lock() {
+            cache.insert(asset_id.to_string(), result.clone());
+        }
+        Ok(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{AssetSaleKind, TxInsight};
+    use cardano_assets::AssetId;
+    use std::cell::Cell;
+
+    const PIRATE: &str =
+        "b3dab69f7e6100849434fb1781e34bd12a916557f6231b8d2629b6f650697261746531303836";
+    const UNKNOWN: &str = "b3dab69f7e6100849434fb1781e34bd12a916557f6231b8d2629b6f6ff";
+    const BROKEN: &str = "b3dab69f7e6100849434fb1781e34bd12a916557f6231b8d2629b6f600";
+
+    struct StaticEnricher {
+        calls: Cell<usize>,
+    }
+
+    #[async_trait(?Send)]
+    impl AssetEnricher for StaticEnricher {
+        type Error = String;
+
+        async fn enrich(&self, asset_id: &str) -> Result<Option<EnrichedAsset>, String> {
+            self.calls.set(self.calls.get() + 1);
+            match asset_id {
+                PIRATE => Ok(Some(EnrichedAsset {
+                    name: Some("Pirate1086".to_string()),
+                    image: Some("ipfs://QmPirate".to_string()),
+                    traits: HashMap::from([("hat".to_string(), vec!["tricorn".to_string()])]),
+                    rarity_rank: Some(42),
+                })),
+                BROKEN => Err("metadata source unavailable".to_string()),
+                _ => Ok(None),
+            }
+        }
+    }
+
+    fn asset(id: &str) -> TxAsset {
+        TxAsset {
+            id: AssetId::parse_concatenated(id).unwrap(),
+            qty: 1,
+            traits: None,
+            name: None,
+            image: None,
+            rarity_rank: None,
+        }
+    }
+
+    #[test]
+    fn test_enrich_assets() {
+        let mut tx = AnalysedTx {
+            hash: "tx123".to_string(),
+            insights: vec![
+                TxInsight::Mint {
+                    assets: vec![asset(PIRATE), asset(UNKNOWN), asset(BROKEN)],
+                    context: None,
+                },
+                TxInsight::Sale {
+                    asset: TxAsset {
+                        name: Some("Classifier name".to_string()),
+                        ..asset(PIRATE)
+                    },
+                    kind: AssetSaleKind::Standard,
+                    seller: "addr1seller".to_string(),
+                    buyer: "addr1buyer".to_string(),
+                    price_lovelace: 125_000_000,
+                    price_token: None,
+                    seller_stake: None,
+                    buyer_stake: None,
+                },
+            ],
+            counterparties: HashMap::new(),
+        };
+
+        let enricher = StaticEnricher {
+            calls: Cell::new(0),
+        };
+        let outcome = futures::executor::block_on(tx.enrich_assets(&enricher, 2));
+
+        // Repeated assets are looked up once
+        assert_eq!(enricher.calls.get(), 3);
+        assert_eq!(outcome.enriched, 1);
+        assert_eq!(outcome.not_found, vec![UNKNOWN.to_string()]);
+        assert_eq!(outcome.failed.len(), 1);
+        assert!(!outcome.is_complete());
+
+        let TxInsight::Mint { assets, .. } = &tx.insights[0] else {
+            panic!("Wrong variant");
+        };
+        assert_eq!(assets[0].name.as_deref(), Some("Pirate1086"));
+        assert_eq!(assets[0].rarity_rank, Some(42));
+        assert_eq!(assets[0].traits.as_ref().unwrap()["hat"], vec!["tricorn"]);
+        assert!(assets[1].name.is_none() && assets[1].traits.is_none());
+
+        // Classifier-provided fields win
+        let sale_asset = tx.insights[1].assets()[0];
+        assert_eq!(sale_asset.name.as_deref(), Some("Classifier name"));
+        assert_eq!(sale_asset.image.as_deref(), Some("ipfs://QmPirate"));
+    }
+
+    #[test]
+    fn test_caching_enricher() {
+        let enricher = CachingEnricher::new(StaticEnricher {
+            calls: Cell::new(0),
+        });
+
+        futures::executor::block_on(async {
+            for _ in 0..2 {
+                assert!(enricher.enrich(PIRATE).await.unwrap().is_some());
+                assert!(enricher.enrich(UNKNOWN).await.unwrap().is_none());
+                assert!(enricher.enrich(BROKEN).await.is_err());
+            }
+        });
+
+        // Hits and misses cached, errors retried
+        assert_eq!(enricher.inner.calls.get(), 4);
+        assert_eq!(enricher.len(), 2);
+    }
+}
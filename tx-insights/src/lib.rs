@@ -3,35 +3,108 @@
 
 use std::collections::HashMap;
 
-use cardano_assets::AssetId;
+use cardano_assets::{AssetId, AssetIdError, PolicyId};
 pub use serde::{Deserialize, Serialize};
 pub use wasm_safe_serde;
 
+#[cfg(feature = "openapi")]
+use utoipa::ToSchema;
+
+mod airdrop;
+mod counterparty;
+#[cfg(feature = "enrich")]
+mod enrich;
+mod mint;
+mod price;
+#[cfg(feature = "enrich")]
+mod stake;
+
+pub use airdrop::{AIRDROP_MIN_RECIPIENTS, AIRDROP_SAMPLE_SIZE};
+pub use counterparty::{
+    short_address, CounterpartyKind, CounterpartyRegistry, CounterpartyResolver, CounterpartyTag,
+};
+
+pub use mint::{MintContext, MintPhase, PolicyScriptType};
+pub use price::{Price, TokenPrice};
+
+#[cfg(feature = "enrich")]
+pub use enrich::{AssetEnricher, CachingEnricher, EnrichOutcome, EnrichedAsset};
+#[cfg(feature = "enrich")]
+pub use stake::StakeResolver;
+
+#[cfg(feature = "serde_compat")]
+pub use cardano_assets::serde_compat::CamelCase;
+
 #[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
 pub struct AnalysedTx {
     pub hash: String,
     pub insights: Vec<TxInsight>,
+    /// Tags for known counterparty addresses, filled in by
+    /// [`AnalysedTx::tag_counterparties`]
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub counterparties: HashMap<String, CounterpartyTag>,
 }
 
+/// A notable event detected in a transaction
+///
+/// Internally tagged by `type`; lovelace amounts above JS's safe integer range
+/// are sent as strings:
+///
+/// ```
+/// use tx_insights::TxInsight;
+///
+/// let json = r#"{
+///     "type": "sale",
+///     "asset": {
+///         "id": "b3dab69f7e6100849434fb1781e34bd12a916557f6231b8d2629b6f650697261746531303836",
+///         "qty": 1
+///     },
+///     "kind": "standard",
+///     "seller": "addr1seller",
+///     "buyer": "addr1buyer",
+///     "price_lovelace": 125000000
+/// }"#;
+///
+/// let insight: TxInsight = serde_json::from_str(json).unwrap();
+/// assert!(matches!(insight, TxInsight::Sale { price_lovelace: 125_000_000, .. }));
+/// ```
 #[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
 #[serde(tag = "type", rename_all = "snake_case")]
 pub enum TxInsight {
     Mint {
         assets: Vec<TxAsset>,
+        /// Payment and policy details, when the classifier could work them out
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        context: Option<MintContext>,
     },
     OfferCreate {
-        policy_id: String,
+        #[cfg_attr(feature = "serde_compat", serde(alias = "policyId"))]
+        policy_id: PolicyId,
         seller: String,
+        #[cfg_attr(feature = "serde_compat", serde(alias = "offerType"))]
         offer_type: TxOfferType,
         #[serde(with = "wasm_safe_serde::u64_required")]
+        #[cfg_attr(feature = "serde_compat", serde(alias = "priceLovelace"))]
         price_lovelace: u64,
+        /// Set when the offer is made in a native token; `price_lovelace`
+        /// is then 0 unless the marketplace also quotes ADA
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        #[cfg_attr(feature = "serde_compat", serde(alias = "priceToken"))]
+        price_token: Option<TokenPrice>,
     },
     Listing {
         asset: TxAsset,
         action: ListingAction,
         seller: String,
         #[serde(with = "wasm_safe_serde::u64_required")]
+        #[cfg_attr(feature = "serde_compat", serde(alias = "priceLovelace"))]
         price_lovelace: u64,
+        /// Set when listed in a native token; see `OfferCreate`
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        #[cfg_attr(feature = "serde_compat", serde(alias = "priceToken"))]
+        price_token: Option<TokenPrice>,
     },
     Sale {
         asset: TxAsset,
@@ -39,40 +112,257 @@ pub enum TxInsight {
         seller: String,
         buyer: String,
         #[serde(with = "wasm_safe_serde::u64_required")]
+        #[cfg_attr(feature = "serde_compat", serde(alias = "priceLovelace"))]
         price_lovelace: u64,
+        /// Set when settled in a native token; see `OfferCreate`
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        #[cfg_attr(feature = "serde_compat", serde(alias = "priceToken"))]
+        price_token: Option<TokenPrice>,
+        /// Stake address behind `seller`, filled in by stake resolution
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        #[cfg_attr(feature = "serde_compat", serde(alias = "sellerStake"))]
+        seller_stake: Option<String>,
+        /// Stake address behind `buyer`, filled in by stake resolution
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        #[cfg_attr(feature = "serde_compat", serde(alias = "buyerStake"))]
+        buyer_stake: Option<String>,
     },
     DexTrade {
         asset: TxAsset,
     },
+    /// A bid placed on an on-chain auction
+    AuctionBid {
+        asset: TxAsset,
+        bidder: String,
+        #[serde(with = "wasm_safe_serde::u64_required")]
+        #[cfg_attr(feature = "serde_compat", serde(alias = "amountLovelace"))]
+        amount_lovelace: u64,
+    },
+    /// An auction closed and the lot was released to the winning bidder
+    AuctionSettled {
+        asset: TxAsset,
+        winner: String,
+        #[serde(with = "wasm_safe_serde::u64_required")]
+        #[cfg_attr(feature = "serde_compat", serde(alias = "amountLovelace"))]
+        amount_lovelace: u64,
+    },
+    /// An asset listed on an NFT rental / lending market
+    ListForRent {
+        asset: TxAsset,
+        lender: String,
+        /// Asking price for the full rental term
+        #[serde(with = "wasm_safe_serde::u64_required")]
+        #[cfg_attr(feature = "serde_compat", serde(alias = "priceLovelace"))]
+        price_lovelace: u64,
+        /// Longest term the lender allows, when the listing sets one
+        #[serde(default, with = "wasm_safe_serde::u64_option")]
+        #[cfg_attr(feature = "serde_compat", serde(alias = "maxDurationSecs"))]
+        max_duration_secs: Option<u64>,
+    },
+    /// A listed asset was rented out
+    RentStarted {
+        asset: TxAsset,
+        lender: String,
+        renter: String,
+        #[serde(with = "wasm_safe_serde::u64_required")]
+        #[cfg_attr(feature = "serde_compat", serde(alias = "priceLovelace"))]
+        price_lovelace: u64,
+        /// Length of the rental term
+        #[serde(with = "wasm_safe_serde::u64_required")]
+        #[cfg_attr(feature = "serde_compat", serde(alias = "durationSecs"))]
+        duration_secs: u64,
+    },
+    /// A rental term ended and the asset went back to the lender
+    RentEnded {
+        asset: TxAsset,
+        lender: String,
+        renter: String,
+    },
+    /// Fungible tokens of one policy sent to many wallets at once. See
+    /// [`TxInsight::airdrop`] for when classifiers should emit this.
+    Airdrop {
+        #[cfg_attr(feature = "serde_compat", serde(alias = "policyId"))]
+        policy_id: PolicyId,
+        #[cfg_attr(feature = "serde_compat", serde(alias = "recipientsCount"))]
+        recipients_count: u32,
+        /// Sum over all recipients, in the token's smallest unit
+        #[serde(with = "wasm_safe_serde::u64_required")]
+        #[cfg_attr(feature = "serde_compat", serde(alias = "totalQuantity"))]
+        total_quantity: u64,
+        /// The largest recipients, at most [`AIRDROP_SAMPLE_SIZE`]
+        #[cfg_attr(feature = "serde_compat", serde(alias = "sampleRecipients"))]
+        sample_recipients: Vec<String>,
+    },
+    /// A tx touching watched policies that no classifier could categorize,
+    /// kept so misses can be logged and aggregated
+    Unclassified {
+        #[serde(default)]
+        policies: Vec<PolicyId>,
+        /// Hex hashes of the scripts the tx spends from or pays to
+        #[serde(default)]
+        #[cfg_attr(feature = "serde_compat", serde(alias = "scriptHashes"))]
+        script_hashes: Vec<String>,
+        /// Whatever the classifier could tell, e.g. the rules that nearly matched
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        hint: Option<String>,
+    },
+    /// An insight type added after this build; its payload is dropped
+    #[serde(other)]
+    Unknown,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
 pub struct TxAsset {
-    pub id: String, // policy_id + asset_hex concatenated
+    /// Read in either `AssetId` form, written concatenated for older consumers.
+    /// Policy-only tokens have an empty asset name.
+    #[serde(
+        serialize_with = "serialize_concatenated",
+        deserialize_with = "deserialize_asset_id"
+    )]
+    #[cfg_attr(feature = "openapi", schema(value_type = String))]
+    pub id: AssetId,
     #[serde(with = "wasm_safe_serde::u64_required")]
     pub qty: u64,
     #[serde(default)]
     pub traits: Option<HashMap<String, Vec<String>>>,
+    /// Display name, filled in by enrichment
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// Image URL (usually `ipfs://`), filled in by enrichment
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub image: Option<String>,
+    /// Rarity rank within the collection (1 = rarest), filled in by enrichment
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "serde_compat", serde(alias = "rarityRank"))]
+    pub rarity_rank: Option<u32>,
+}
+
+impl TxAsset {
+    pub fn policy_id(&self) -> PolicyId {
+        // Policy hex was validated when the id was read
+        PolicyId::new_unchecked(self.id.policy_id())
+    }
+
+    /// Asset name decoded as UTF-8, or the hex if it isn't valid UTF-8
+    pub fn asset_name(&self) -> String {
+        self.id.asset_name()
+    }
+
+    pub fn asset_name_hex(&self) -> &str {
+        self.id.asset_name_hex()
+    }
+}
+
+fn serialize_concatenated<S: serde::Serializer>(
+    id: &AssetId,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    serializer.serialize_str(&id.concatenated())
+}
+
+/// Any `AssetId` wire form, but unlike `AssetId`'s own impl an empty asset
+/// name is accepted, as String ids always were
+fn deserialize_asset_id<'de, D: serde::Deserializer<'de>>(
+    deserializer: D,
+) -> Result<AssetId, D::Error> {
+    use serde::de::Error;
+
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Repr {
+        Structured {
+            policy_id: String,
+            asset_name_hex: String,
+        },
+        String(String),
+    }
+
+    let (policy_id, asset_name_hex) = match Repr::deserialize(deserializer)? {
+        Repr::Structured {
+            policy_id,
+            asset_name_hex,
+        } => (policy_id, asset_name_hex),
+        Repr::String(s) => match s.split_once(['.', ':']) {
+            Some((policy_id, name)) => (policy_id.to_string(), name.to_string()),
+            None if s.len() >= 56 && s.is_char_boundary(56) => {
+                (s[..56].to_string(), s[56..].to_string())
+            }
+            None => return Err(D::Error::custom(format!("Invalid AssetId string: {s}"))),
+        },
+    };
+    match AssetId::new(policy_id.clone(), asset_name_hex.clone()) {
+        Ok(id) => Ok(id),
+        Err(AssetIdError::EmptyAssetName) => Ok(AssetId::new_unchecked(policy_id, asset_name_hex)),
+        Err(e) => Err(D::Error::custom(format!("Invalid AssetId: {e}"))),
+    }
 }
 
 impl From<AssetId> for TxAsset {
     fn from(value: AssetId) -> Self {
         Self {
-            id: value.concatenated(),
+            id: value,
             qty: 1,
             traits: None,
+            name: None,
+            image: None,
+            rarity_rank: None,
+        }
+    }
+}
+
+impl TxInsight {
+    /// Assets referenced by this insight
+    pub fn assets(&self) -> Vec<&TxAsset> {
+        match self {
+            TxInsight::Mint { assets, .. } => assets.iter().collect(),
+            TxInsight::OfferCreate { .. }
+            | TxInsight::Airdrop { .. }
+            | TxInsight::Unclassified { .. }
+            | TxInsight::Unknown => Vec::new(),
+            TxInsight::Listing { asset, .. }
+            | TxInsight::Sale { asset, .. }
+            | TxInsight::DexTrade { asset }
+            | TxInsight::AuctionBid { asset, .. }
+            | TxInsight::AuctionSettled { asset, .. }
+            | TxInsight::ListForRent { asset, .. }
+            | TxInsight::RentStarted { asset, .. }
+            | TxInsight::RentEnded { asset, .. } => vec![asset],
+        }
+    }
+
+    pub fn assets_mut(&mut self) -> Vec<&mut TxAsset> {
+        match self {
+            TxInsight::Mint { assets, .. } => assets.iter_mut().collect(),
+            TxInsight::OfferCreate { .. }
+            | TxInsight::Airdrop { .. }
+            | TxInsight::Unclassified { .. }
+            | TxInsight::Unknown => Vec::new(),
+            TxInsight::Listing { asset, .. }
+            | TxInsight::Sale { asset, .. }
+            | TxInsight::DexTrade { asset }
+            | TxInsight::AuctionBid { asset, .. }
+            | TxInsight::AuctionSettled { asset, .. }
+            | TxInsight::ListForRent { asset, .. }
+            | TxInsight::RentStarted { asset, .. }
+            | TxInsight::RentEnded { asset, .. } => vec![asset],
         }
     }
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
 #[serde(tag = "type", rename_all = "snake_case")]
 pub enum TxOfferType {
     Collection,
-    Asset { asset_hex: String },
+    Asset {
+        #[cfg_attr(feature = "serde_compat", serde(alias = "assetHex"))]
+        asset_hex: String,
+    },
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
 #[serde(rename_all = "snake_case")]
 pub enum ListingAction {
     Create,
@@ -80,6 +370,7 @@ pub enum ListingAction {
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
 #[serde(rename_all = "snake_case")]
 pub enum AssetSaleKind {
     Standard,
@@ -90,20 +381,33 @@ pub enum AssetSaleKind {
 mod tests {
     use super::*;
 
+    const UNIT: &str =
+        "b3dab69f7e6100849434fb1781e34bd12a916557f6231b8d2629b6f650697261746531303836";
+
+    fn unit() -> AssetId {
+        AssetId::parse_concatenated(UNIT).unwrap()
+    }
+
     #[test]
     fn test_large_price_serialization() {
         let large_price = 15_000_000_000_000_000_u64; // > MAX_SAFE_JS_INTEGER (9007199254740991)
 
         let insight = TxInsight::Sale {
             asset: TxAsset {
-                id: "policy123.asset456".to_string(),
+                id: unit(),
                 qty: 1,
                 traits: None,
+                name: None,
+                image: None,
+                rarity_rank: None,
             },
             kind: AssetSaleKind::Standard,
             seller: "addr1seller".to_string(),
             buyer: "addr1buyer".to_string(),
             price_lovelace: large_price,
+            price_token: None,
+            seller_stake: None,
+            buyer_stake: None,
         };
 
         let json = serde_json::to_string(&insight).expect("Should serialize");
@@ -126,17 +430,240 @@ mod tests {
             hash: "tx123".to_string(),
             insights: vec![TxInsight::Mint {
                 assets: vec![TxAsset {
-                    id: "policy.asset".to_string(),
+                    id: unit(),
                     qty: 1000,
                     traits: Some(HashMap::from([(
                         "color".to_string(),
                         vec!["red".to_string(), "blue".to_string()],
                     )])),
+                    name: None,
+                    image: None,
+                    rarity_rank: None,
                 }],
+                context: None,
             }],
+            counterparties: HashMap::new(),
         };
 
         let json = serde_json::to_string(&tx).expect("Should serialize");
         let _deserialized: AnalysedTx = serde_json::from_str(&json).expect("Should deserialize");
     }
+
+    #[test]
+    fn test_auction_insight_serialization() {
+        let insight = TxInsight::AuctionSettled {
+            asset: TxAsset {
+                id: unit(),
+                qty: 1,
+                traits: None,
+                name: None,
+                image: None,
+                rarity_rank: None,
+            },
+            winner: "addr1winner".to_string(),
+            amount_lovelace: 250_000_000,
+        };
+
+        let json = serde_json::to_string(&insight).expect("Should serialize");
+        assert!(json.contains("\"type\":\"auction_settled\""));
+
+        let deserialized: TxInsight = serde_json::from_str(&json).expect("Should deserialize");
+        match deserialized {
+            TxInsight::AuctionSettled {
+                winner,
+                amount_lovelace,
+                ..
+            } => {
+                assert_eq!(winner, "addr1winner");
+                assert_eq!(amount_lovelace, 250_000_000);
+            }
+            _ => panic!("Wrong variant"),
+        }
+    }
+
+    #[test]
+    fn test_offer_create_policy_id() {
+        let policy = "b3dab69f7e6100849434fb1781e34bd12a916557f6231b8d2629b6f6";
+        let json = format!(
+            r#"{{"type":"offer_create","policy_id":"{policy}","seller":"addr1buyer","offer_type":{{"type":"collection"}},"price_lovelace":50000000}}"#
+        );
+        let insight: TxInsight = serde_json::from_str(&json).expect("Should deserialize");
+        assert!(
+            matches!(insight, TxInsight::OfferCreate { ref policy_id, .. } if policy_id == policy)
+        );
+
+        // An asset unit in the policy field is rejected rather than passed on
+        let unit_json = json.replace(policy, &format!("{policy}50697261746531303836"));
+        assert!(serde_json::from_str::<TxInsight>(&unit_json).is_err());
+
+        let asset_id = AssetId::new(policy.to_string(), "5069726174653130".to_string()).unwrap();
+        let asset = TxAsset::from(asset_id);
+        assert_eq!(asset.policy_id(), policy);
+        assert_eq!(asset.asset_name(), "Pirate10");
+    }
+
+    #[test]
+    fn test_rental_insight_serialization() {
+        let asset = TxAsset {
+            id: unit(),
+            qty: 1,
+            traits: None,
+            name: None,
+            image: None,
+            rarity_rank: None,
+        };
+
+        let listing = TxInsight::ListForRent {
+            asset: asset.clone(),
+            lender: "addr1lender".to_string(),
+            price_lovelace: 20_000_000,
+            max_duration_secs: None,
+        };
+        let json = serde_json::to_string(&listing).expect("Should serialize");
+        assert!(json.contains("\"type\":\"list_for_rent\""));
+
+        let json = r#"{
+            "type": "rent_started",
+            "asset": { "id": "b3dab69f7e6100849434fb1781e34bd12a916557f6231b8d2629b6f650697261746531303836", "qty": 1 },
+            "lender": "addr1lender",
+            "renter": "addr1renter",
+            "price_lovelace": 20000000,
+            "duration_secs": "604800"
+        }"#;
+        let started: TxInsight = serde_json::from_str(json).expect("Should deserialize");
+        match &started {
+            TxInsight::RentStarted {
+                renter,
+                duration_secs,
+                ..
+            } => {
+                assert_eq!(renter, "addr1renter");
+                assert_eq!(*duration_secs, 604_800);
+            }
+            _ => panic!("Wrong variant"),
+        }
+        assert_eq!(started.assets().len(), 1);
+
+        let ended = TxInsight::RentEnded {
+            asset,
+            lender: "addr1lender".to_string(),
+            renter: "addr1renter".to_string(),
+        };
+        let json = serde_json::to_string(&ended).expect("Should serialize");
+        let deserialized: TxInsight = serde_json::from_str(&json).expect("Should deserialize");
+        assert!(matches!(deserialized, TxInsight::RentEnded { .. }));
+    }
+
+    #[test]
+    fn test_unclassified_and_unknown_insights() {
+        let policy = &UNIT[..56];
+        let json = format!(
+            r#"{{"type":"unclassified","policies":["{policy}"],"script_hashes":["e1317b152faac13426e6a83e06ff88a4d62cce3c1634ab0a5ec13309"]}}"#
+        );
+        let insight: TxInsight = serde_json::from_str(&json).expect("Should deserialize");
+        match &insight {
+            TxInsight::Unclassified {
+                policies,
+                script_hashes,
+                hint,
+            } => {
+                assert_eq!(policies.len(), 1);
+                assert_eq!(script_hashes.len(), 1);
+                assert!(hint.is_none());
+            }
+            _ => panic!("Wrong variant"),
+        }
+        assert!(insight.assets().is_empty());
+
+        // Insight types from newer producers still decode, so the rest of
+        // the tx isn't lost
+        let tx: AnalysedTx = serde_json::from_str(
+            r#"{"hash":"tx123","insights":[{"type":"stake_delegation","pool":"pool1abc"},{"type":"dex_trade","asset":{"id":"b3dab69f7e6100849434fb1781e34bd12a916557f6231b8d2629b6f650697261746531303836","qty":1}}]}"#,
+        )
+        .expect("Should deserialize");
+        assert!(matches!(tx.insights[0], TxInsight::Unknown));
+        assert!(matches!(tx.insights[1], TxInsight::DexTrade { .. }));
+    }
+
+    #[test]
+    fn test_asset_id_wire_forms() {
+        let policy = &UNIT[..56];
+        for id in [
+            serde_json::json!(UNIT),
+            serde_json::json!(format!("{policy}.50697261746531303836")),
+            serde_json::json!({ "policy_id": policy, "asset_name_hex": "50697261746531303836" }),
+        ] {
+            let asset: TxAsset =
+                serde_json::from_value(serde_json::json!({ "id": id, "qty": 1 })).unwrap();
+            assert_eq!(asset.id, unit());
+            assert_eq!(serde_json::to_value(&asset).unwrap()["id"], UNIT);
+        }
+
+        let asset = TxAsset::from(unit());
+        assert_eq!(asset.policy_id(), policy);
+        assert_eq!(asset.asset_name(), "Pirate1086");
+        assert_eq!(asset.asset_name_hex(), "50697261746531303836");
+
+        // Policy-only tokens
+        let asset: TxAsset =
+            serde_json::from_value(serde_json::json!({ "id": policy, "qty": 1_000 })).unwrap();
+        assert_eq!(asset.policy_id(), policy);
+        assert_eq!(asset.asset_name_hex(), "");
+        assert_eq!(serde_json::to_value(&asset).unwrap()["id"], policy);
+
+        let bad = serde_json::json!({ "id": "policy123asset456", "qty": 1 });
+        assert!(serde_json::from_value::<TxAsset>(bad).is_err());
+    }
+
+    #[test]
+    fn test_token_denominated_price() {
+        let iusd = AssetId::parse_concatenated(
+            "f66d78b4a3cb3d37afa0ec36461e51ecbde00f26c8f0a68f94b6988069555344",
+        )
+        .unwrap();
+        let json = serde_json::json!({
+            "type": "listing",
+            "asset": { "id": UNIT, "qty": 1, "traits": null },
+            "action": "create",
+            "seller": "addr1seller",
+            "price_lovelace": 0,
+            "price_token": { "asset_id": iusd.concatenated(), "quantity": 12_500_000, "decimals": 6 }
+        });
+        let insight: TxInsight = serde_json::from_value(json.clone()).unwrap();
+
+        let price = insight.price().unwrap();
+        let token = price.token().unwrap();
+        assert_eq!(token.asset_id, iusd);
+        assert_eq!(token.display_amount(), "12.5");
+        assert_eq!(price.lovelace(), None);
+        assert_eq!(serde_json::to_value(&insight).unwrap(), json);
+
+        // ADA prices are unchanged on the wire
+        let ada = TxInsight::Sale {
+            asset: TxAsset::from(unit()),
+            kind: AssetSaleKind::Standard,
+            seller: "addr1seller".to_string(),
+            buyer: "addr1buyer".to_string(),
+            price_lovelace: 125_000_000,
+            price_token: None,
+            seller_stake: None,
+            buyer_stake: None,
+        };
+        assert_eq!(ada.price(), Some(Price::Lovelace(125_000_000)));
+        assert!(serde_json::to_value(&ada)
+            .unwrap()
+            .get("price_token")
+            .is_none());
+        assert_eq!(TxInsight::Unknown.price(), None);
+
+        assert_eq!(
+            TokenPrice::new(iusd.clone(), 7, 6).display_amount(),
+            "0.000007"
+        );
+        assert_eq!(
+            TokenPrice::new(iusd.clone(), 3_000_000, 6).display_amount(),
+            "3"
+        );
+        assert_eq!(TokenPrice::new(iusd, 42, 0).display_amount(), "42");
+    }
 }
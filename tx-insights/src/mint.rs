@@ -0,0 +1,171 @@
+//! Context for [`TxInsight::Mint`](crate::TxInsight::Mint)
+//!
+//! A bare list of minted assets can't tell a public sale from a team
+//! airdrop. [`MintContext`] records what the classifier could work out
+//! about the mint — who was paid, how much per asset, what kind of policy —
+//! and [`MintPhase`] condenses that into the label a notification shows.
+
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "openapi")]
+use utoipa::ToSchema;
+
+/// How a mint was paid for
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum MintPhase {
+    /// Paid to a known launchpad
+    PublicSale,
+    /// Paid, but not through a known launchpad (project-run mint page)
+    DirectSale,
+    /// Nothing paid beyond fees and min-UTxO: team mints and giveaways
+    Airdrop,
+    #[default]
+    Unknown,
+}
+
+impl fmt::Display for MintPhase {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MintPhase::PublicSale => write!(f, "Public mint"),
+            MintPhase::DirectSale => write!(f, "Direct mint"),
+            MintPhase::Airdrop => write!(f, "Airdrop"),
+            MintPhase::Unknown => write!(f, "Mint"),
+        }
+    }
+}
+
+/// Kind of script behind the minting policy
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum PolicyScriptType {
+    /// Native script with no time lock; can mint forever
+    Native,
+    /// Native script that locks after a slot
+    NativeTimelocked,
+    Plutus,
+}
+
+/// What the classifier knows about a mint
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+pub struct MintContext {
+    #[serde(default)]
+    pub phase: MintPhase,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "serde_compat", serde(alias = "policyScript"))]
+    pub policy_script: Option<PolicyScriptType>,
+    /// Launchpad that received the payment, e.g. `JPG.store`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub launchpad: Option<String>,
+    #[serde(
+        default,
+        with = "wasm_safe_serde::u64_option",
+        skip_serializing_if = "Option::is_none"
+    )]
+    #[cfg_attr(feature = "serde_compat", serde(alias = "pricePerAssetLovelace"))]
+    pub price_per_asset_lovelace: Option<u64>,
+    /// Estimated assets left to mint, when the collection size is known
+    #[serde(
+        default,
+        with = "wasm_safe_serde::u64_option",
+        skip_serializing_if = "Option::is_none"
+    )]
+    #[cfg_attr(feature = "serde_compat", serde(alias = "remainingSupply"))]
+    pub remaining_supply: Option<u64>,
+}
+
+impl MintContext {
+    /// Payments at or below this per asset are min-UTxO and fees, not a price
+    pub const AIRDROP_THRESHOLD_LOVELACE: u64 = 2_500_000;
+
+    /// Context from what the minter paid for `asset_count` assets, and the
+    /// launchpad it was paid to (if any)
+    pub fn from_payment(
+        total_lovelace: Option<u64>,
+        asset_count: usize,
+        launchpad: Option<String>,
+    ) -> Self {
+        let price_per_asset_lovelace = total_lovelace
+            .filter(|_| asset_count > 0)
+            .map(|total| total / asset_count as u64);
+        let mut context = Self {
+            launchpad,
+            price_per_asset_lovelace,
+            ..Default::default()
+        };
+        context.phase = context.infer_phase();
+        context
+    }
+
+    /// Phase implied by the payment details
+    pub fn infer_phase(&self) -> MintPhase {
+        match (self.price_per_asset_lovelace, &self.launchpad) {
+            (Some(price), _) if price <= Self::AIRDROP_THRESHOLD_LOVELACE => MintPhase::Airdrop,
+            (_, Some(_)) => MintPhase::PublicSale,
+            (Some(_), None) => MintPhase::DirectSale,
+            (None, None) => MintPhase::Unknown,
+        }
+    }
+
+    pub fn with_policy_script(mut self, script: PolicyScriptType) -> Self {
+        self.policy_script = Some(script);
+        self
+    }
+
+    /// Remaining supply from the collection size and assets minted so far
+    pub fn with_supply(mut self, total_supply: u64, minted: u64) -> Self {
+        self.remaining_supply = Some(total_supply.saturating_sub(minted));
+        self
+    }
+
+    pub fn is_primary_sale(&self) -> bool {
+        self.phase == MintPhase::PublicSale
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_phase_inference() {
+        let public = MintContext::from_payment(Some(90_000_000), 2, Some("JPG.store".into()));
+        assert_eq!(public.phase, MintPhase::PublicSale);
+        assert_eq!(public.price_per_asset_lovelace, Some(45_000_000));
+        assert!(public.is_primary_sale());
+
+        let direct = MintContext::from_payment(Some(45_000_000), 1, None);
+        assert_eq!(direct.phase, MintPhase::DirectSale);
+
+        let airdrop = MintContext::from_payment(Some(3_000_000), 2, Some("JPG.store".into()));
+        assert_eq!(airdrop.phase, MintPhase::Airdrop);
+        assert_eq!(airdrop.phase.to_string(), "Airdrop");
+
+        let unknown = MintContext::from_payment(None, 0, None);
+        assert_eq!(unknown.phase, MintPhase::Unknown);
+        assert_eq!(unknown.with_supply(100, 120).remaining_supply, Some(0));
+    }
+
+    #[test]
+    fn test_mint_context_serialization() {
+        let context = MintContext::from_payment(Some(45_000_000), 1, None)
+            .with_policy_script(PolicyScriptType::NativeTimelocked)
+            .with_supply(10_000, 2_500);
+        let json = serde_json::to_string(&context).unwrap();
+        assert!(json.contains("\"phase\":\"direct_sale\""));
+        assert!(json.contains("\"policy_script\":\"native_timelocked\""));
+        assert!(!json.contains("launchpad"));
+
+        let back: MintContext = serde_json::from_str(&json).unwrap();
+        assert_eq!(back, context);
+        assert_eq!(
+            serde_json::from_str::<MintContext>("{}").unwrap(),
+            MintContext::default()
+        );
+    }
+}
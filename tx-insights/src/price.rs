@@ -0,0 +1,128 @@
+//! Prices settled in native tokens
+//!
+//! Most marketplaces settle in ADA, but some take stablecoins or project
+//! tokens. Those sales carry a [`TokenPrice`] next to `price_lovelace`
+//! rather than being converted at classification time; use
+//! [`TxInsight::price`] to get whichever applies.
+
+use cardano_assets::AssetId;
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "openapi")]
+use utoipa::ToSchema;
+
+use crate::{deserialize_asset_id, serialize_concatenated, TxInsight};
+
+/// An amount of a native token
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+pub struct TokenPrice {
+    #[serde(
+        serialize_with = "serialize_concatenated",
+        deserialize_with = "deserialize_asset_id"
+    )]
+    #[cfg_attr(feature = "serde_compat", serde(alias = "assetId"))]
+    #[cfg_attr(feature = "openapi", schema(value_type = String))]
+    pub asset_id: AssetId,
+    /// In the token's smallest unit
+    #[serde(with = "wasm_safe_serde::u64_required")]
+    pub quantity: u64,
+    /// Decimal places from the token registry, for display
+    #[serde(default)]
+    pub decimals: u8,
+}
+
+impl TokenPrice {
+    pub fn new(asset_id: AssetId, quantity: u64, decimals: u8) -> Self {
+        Self {
+            asset_id,
+            quantity,
+            decimals,
+        }
+    }
+
+    /// `quantity` with `decimals` applied, e.g. `12.5` for 12_500_000 at 6
+    /// decimals; trailing zeros are dropped
+    pub fn display_amount(&self) -> String {
+        if self.decimals == 0 {
+            return self.quantity.to_string();
+        }
+        let digits = format!(
+            "{:0>width$}",
+            self.quantity,
+            width = self.decimals as usize + 1
+        );
+        let (whole, fraction) = digits.split_at(digits.len() - self.decimals as usize);
+        match fraction.trim_end_matches('0') {
+            "" => whole.to_string(),
+            fraction => format!("{whole}.{fraction}"),
+        }
+    }
+}
+
+/// What an insight's price was paid in
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum Price {
+    Lovelace(#[serde(with = "wasm_safe_serde::u64_required")] u64),
+    Token(TokenPrice),
+}
+
+impl Price {
+    pub fn lovelace(&self) -> Option<u64> {
+        match self {
+            Price::Lovelace(lovelace) => Some(*lovelace),
+            Price::Token(_) => None,
+        }
+    }
+
+    pub fn token(&self) -> Option<&TokenPrice> {
+        match self {
+            Price::Lovelace(_) => None,
+            Price::Token(token) => Some(token),
+        }
+    }
+}
+
+impl TxInsight {
+    /// The amount paid or asked, in the token it settles in
+    pub fn price(&self) -> Option<Price> {
+        match self {
+            TxInsight::OfferCreate {
+                price_lovelace,
+                price_token,
+                ..
+            }
+            | TxInsight::Listing {
+                price_lovelace,
+                price_token,
+                ..
+            }
+            | TxInsight::Sale {
+                price_lovelace,
+                price_token,
+                ..
+            } => Some(match price_token {
+                Some(token) => Price::Token(token.clone()),
+                None => Price::Lovelace(*price_lovelace),
+            }),
+            TxInsight::AuctionBid {
+                amount_lovelace, ..
+            }
+            | TxInsight::AuctionSettled {
+                amount_lovelace, ..
+            } => Some(Price::Lovelace(*amount_lovelace)),
+            TxInsight::ListForRent { price_lovelace, .. }
+            | TxInsight::RentStarted { price_lovelace, .. } => {
+                Some(Price::Lovelace(*price_lovelace))
+            }
+            TxInsight::Mint { .. }
+            | TxInsight::DexTrade { .. }
+            | TxInsight::RentEnded { .. }
+            | TxInsight::Airdrop { .. }
+            | TxInsight::Unclassified { .. }
+            | TxInsight::Unknown => None,
+        }
+    }
+}
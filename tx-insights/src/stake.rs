@@ -0,0 +1,202 @@
+//! Stake address resolution for sales
+//!
+//! Sales carry the payment addresses the classifier saw, but holder roles
+//! and leaderboards are keyed on stake addresses. [`AnalysedTx::resolve_stakes`]
+//! fills in `seller_stake` and `buyer_stake` from a [`StakeResolver`] backed
+//! by whatever indexer the worker has (maestro, blockfrost, a KV cache, ...).
+
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+
+use async_trait::async_trait;
+
+use crate::{AnalysedTx, EnrichOutcome, TxInsight};
+
+/// Looks up the stake address a payment address delegates with
+///
+/// `?Send` so implementations can wrap wasm HTTP clients.
+#[async_trait(?Send)]
+pub trait StakeResolver {
+    type Error: fmt::Display;
+
+    /// `Ok(None)` if the address has no stake part (enterprise or script
+    /// addresses) or is unknown to this source
+    async fn stake_address(&self, address: &str) -> Result<Option<String>, Self::Error>;
+}
+
+impl AnalysedTx {
+    /// Resolve the stake address of every sale party still missing one, at
+    /// most `concurrency` lookups in flight at once
+    pub async fn resolve_stakes<R: StakeResolver + ?Sized>(
+        &mut self,
+        resolver: &R,
+        concurrency: usize,
+    ) -> EnrichOutcome<R::Error> {
+        let mut seen = HashSet::new();
+        let addresses: Vec<String> = self
+            .insights
+            .iter()
+            .flat_map(|insight| match insight {
+                TxInsight::Sale {
+                    seller,
+                    buyer,
+                    seller_stake,
+                    buyer_stake,
+                    ..
+                } => [
+                    seller_stake.is_none().then_some(seller),
+                    buyer_stake.is_none().then_some(buyer),
+                ],
+                _ => [None, None],
+            })
+            .flatten()
+            .filter(|address| seen.insert(*address))
+            .cloned()
+            .collect();
+
+        let results = worker_utils::join_bounded(
+            addresses.into_iter().map(|address| async move {
+                let result = resolver.stake_address(&address).await;
+                (address, result)
+            }),
+            concurrency,
+        )
+        .await;
+
+        let mut resolved = HashMap::new();
+        let mut outcome = EnrichOutcome {
+            enriched: 0,
+            not_found: Vec::new(),
+            failed: Vec::new(),
+        };
+        for (address, result) in results {
+            match result {
+                Ok(Some(stake)) => {
+                    resolved.insert(address, stake);
+                }
+                Ok(None) => outcome.not_found.push(address),
+                Err(e) => outcome.failed.push((address, e)),
+            }
+        }
+        outcome.enriched = resolved.len();
+
+        for insight in &mut self.insights {
+            if let TxInsight::Sale {
+                seller,
+                buyer,
+                seller_stake,
+                buyer_stake,
+                ..
+            } = insight
+            {
+                if seller_stake.is_none() {
+                    *seller_stake = resolved.get(seller.as_str()).cloned();
+                }
+                if buyer_stake.is_none() {
+                    *buyer_stake = resolved.get(buyer.as_str()).cloned();
+                }
+            }
+        }
+
+        outcome
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{AssetSaleKind, TxAsset};
+    use cardano_assets::AssetId;
+    use std::cell::Cell;
+
+    const SELLER: &str = "addr1qseller";
+    const BUYER: &str = "addr1qbuyer";
+    const ENTERPRISE: &str = "addr1vnostake";
+    const BROKEN: &str = "addr1qbroken";
+
+    struct StaticResolver {
+        calls: Cell<usize>,
+    }
+
+    #[async_trait(?Send)]
+    impl StakeResolver for StaticResolver {
+        type Error = String;
+
+        async fn stake_address(&self, address: &str) -> Result<Option<String>, String> {
+            self.calls.set(self.calls.get() + 1);
+            match address {
+                SELLER => Ok(Some("stake1seller".to_string())),
+                BUYER => Ok(Some("stake1buyer".to_string())),
+                BROKEN => Err("indexer unavailable".to_string()),
+                _ => Ok(None),
+            }
+        }
+    }
+
+    fn sale(seller: &str, buyer: &str, buyer_stake: Option<&str>) -> TxInsight {
+        TxInsight::Sale {
+            asset: TxAsset::from(AssetId::new_unchecked(
+                "b3dab69f7e6100849434fb1781e34bd12a916557f6231b8d2629b6f6".to_string(),
+                "50697261746531303836".to_string(),
+            )),
+            kind: AssetSaleKind::Standard,
+            seller: seller.to_string(),
+            buyer: buyer.to_string(),
+            price_lovelace: 125_000_000,
+            price_token: None,
+            seller_stake: None,
+            buyer_stake: buyer_stake.map(str::to_string),
+        }
+    }
+
+    fn stakes(insight: &TxInsight) -> (Option<&str>, Option<&str>) {
+        let TxInsight::Sale {
+            seller_stake,
+            buyer_stake,
+            ..
+        } = insight
+        else {
+            panic!("Wrong variant");
+        };
+        (seller_stake.as_deref(), buyer_stake.as_deref())
+    }
+
+    #[test]
+    fn test_resolve_stakes() {
+        let mut tx = AnalysedTx {
+            hash: "tx123".to_string(),
+            insights: vec![
+                sale(SELLER, BUYER, None),
+                // Sweep from the same buyer, and a stake already known
+                sale(ENTERPRISE, BUYER, Some("stake1known")),
+                sale(BROKEN, SELLER, None),
+            ],
+            counterparties: HashMap::new(),
+        };
+
+        let resolver = StaticResolver {
+            calls: Cell::new(0),
+        };
+        let outcome = futures::executor::block_on(tx.resolve_stakes(&resolver, 2));
+
+        // Repeated addresses are looked up once
+        assert_eq!(resolver.calls.get(), 4);
+        assert_eq!(outcome.enriched, 2);
+        assert_eq!(outcome.not_found, vec![ENTERPRISE.to_string()]);
+        assert_eq!(outcome.failed.len(), 1);
+
+        assert_eq!(
+            stakes(&tx.insights[0]),
+            (Some("stake1seller"), Some("stake1buyer"))
+        );
+        assert_eq!(stakes(&tx.insights[1]), (None, Some("stake1known")));
+        assert_eq!(stakes(&tx.insights[2]), (None, Some("stake1seller")));
+
+        let json = serde_json::to_value(&tx.insights[0]).unwrap();
+        assert_eq!(json["seller_stake"], "stake1seller");
+        assert!(serde_json::to_value(&tx.insights[2])
+            .unwrap()
+            .get("seller_stake")
+            .is_none());
+    }
+}
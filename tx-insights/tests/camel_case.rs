@@ -0,0 +1,197 @@
+//! Both JSON shapes of the `serde_compat` feature, pinned.
+//!
+//! Run with `cargo test -p tx_insights --features serde_compat`.
+#![cfg(feature = "serde_compat")]
+
+use std::collections::HashMap;
+
+use cardano_assets::{AssetId, PolicyId};
+use serde_json::{json, Value};
+use tx_insights::{
+    AnalysedTx, AssetSaleKind, CamelCase, CounterpartyTag, MintContext, MintPhase,
+    PolicyScriptType, TxAsset, TxInsight, TxOfferType,
+};
+
+const POLICY: &str = "b3dab69f7e6100849434fb1781e34bd12a916557f6231b8d2629b6f6";
+const UNIT: &str = "b3dab69f7e6100849434fb1781e34bd12a916557f6231b8d2629b6f650697261746531303836";
+
+fn sample() -> AnalysedTx {
+    let asset = TxAsset {
+        id: AssetId::parse_concatenated(UNIT).unwrap(),
+        qty: 1,
+        traits: Some(HashMap::from([(
+            "eye_patch".to_string(),
+            vec!["Gold".to_string()],
+        )])),
+        name: None,
+        image: None,
+        rarity_rank: Some(7),
+    };
+
+    AnalysedTx {
+        hash: "6c3ef6a0".to_string(),
+        insights: vec![
+            TxInsight::Mint {
+                assets: vec![asset.clone()],
+                context: Some(MintContext {
+                    phase: MintPhase::PublicSale,
+                    policy_script: Some(PolicyScriptType::NativeTimelocked),
+                    launchpad: None,
+                    price_per_asset_lovelace: Some(45_000_000),
+                    remaining_supply: None,
+                }),
+            },
+            TxInsight::OfferCreate {
+                policy_id: PolicyId::new(POLICY).unwrap(),
+                seller: "addr1seller".to_string(),
+                offer_type: TxOfferType::Asset {
+                    asset_hex: "5069726174653130383".to_string(),
+                },
+                price_lovelace: 125_000_000,
+                price_token: None,
+            },
+            TxInsight::Sale {
+                asset,
+                kind: AssetSaleKind::AcceptOffer,
+                seller: "addr1seller".to_string(),
+                buyer: "addr1buyer".to_string(),
+                price_lovelace: 125_000_000,
+                price_token: None,
+                seller_stake: None,
+                buyer_stake: None,
+            },
+        ],
+        counterparties: HashMap::from([(
+            "addr1_escrow".to_string(),
+            CounterpartyTag::marketplace("JPG.store escrow"),
+        )]),
+    }
+}
+
+fn snake_case() -> Value {
+    json!({
+        "hash": "6c3ef6a0",
+        "insights": [
+            {
+                "type": "mint",
+                "assets": [{
+                    "id": UNIT,
+                    "qty": 1,
+                    "traits": { "eye_patch": ["Gold"] },
+                    "rarity_rank": 7
+                }],
+                "context": {
+                    "phase": "public_sale",
+                    "policy_script": "native_timelocked",
+                    "price_per_asset_lovelace": 45_000_000
+                }
+            },
+            {
+                "type": "offer_create",
+                "policy_id": POLICY,
+                "seller": "addr1seller",
+                "offer_type": { "type": "asset", "asset_hex": "5069726174653130383" },
+                "price_lovelace": 125_000_000
+            },
+            {
+                "type": "sale",
+                "asset": {
+                    "id": UNIT,
+                    "qty": 1,
+                    "traits": { "eye_patch": ["Gold"] },
+                    "rarity_rank": 7
+                },
+                "kind": "accept_offer",
+                "seller": "addr1seller",
+                "buyer": "addr1buyer",
+                "price_lovelace": 125_000_000
+            }
+        ],
+        "counterparties": {
+            "addr1_escrow": { "kind": "marketplace", "label": "JPG.store escrow" }
+        }
+    })
+}
+
+fn camel_case() -> Value {
+    json!({
+        "hash": "6c3ef6a0",
+        "insights": [
+            {
+                "type": "mint",
+                "assets": [{
+                    "id": UNIT,
+                    "qty": 1,
+                    "traits": { "eye_patch": ["Gold"] },
+                    "rarityRank": 7
+                }],
+                "context": {
+                    "phase": "public_sale",
+                    "policyScript": "native_timelocked",
+                    "pricePerAssetLovelace": 45_000_000
+                }
+            },
+            {
+                "type": "offer_create",
+                "policyId": POLICY,
+                "seller": "addr1seller",
+                "offerType": { "type": "asset", "assetHex": "5069726174653130383" },
+                "priceLovelace": 125_000_000
+            },
+            {
+                "type": "sale",
+                "asset": {
+                    "id": UNIT,
+                    "qty": 1,
+                    "traits": { "eye_patch": ["Gold"] },
+                    "rarityRank": 7
+                },
+                "kind": "accept_offer",
+                "seller": "addr1seller",
+                "buyer": "addr1buyer",
+                "priceLovelace": 125_000_000
+            }
+        ],
+        "counterparties": {
+            "addr1_escrow": { "kind": "marketplace", "label": "JPG.store escrow" }
+        }
+    })
+}
+
+#[test]
+fn serializes_both_shapes() {
+    let tx = sample();
+    assert_eq!(serde_json::to_value(&tx).unwrap(), snake_case());
+    assert_eq!(serde_json::to_value(CamelCase(&tx)).unwrap(), camel_case());
+}
+
+#[test]
+fn deserializes_both_shapes() {
+    for shape in [snake_case(), camel_case()] {
+        let tx: AnalysedTx = serde_json::from_value(shape).unwrap();
+        // Re-serializing normalizes to snake_case
+        assert_eq!(serde_json::to_value(&tx).unwrap(), snake_case());
+    }
+}
+
+#[test]
+fn camel_case_keeps_large_amounts_as_strings() {
+    let insight = TxInsight::OfferCreate {
+        policy_id: PolicyId::new(POLICY).unwrap(),
+        seller: "addr1seller".to_string(),
+        offer_type: TxOfferType::Collection,
+        price_lovelace: u64::MAX,
+        price_token: None,
+    };
+    let camel = serde_json::to_value(CamelCase(&insight)).unwrap();
+    assert_eq!(camel["priceLovelace"], u64::MAX.to_string());
+
+    let back: TxInsight = serde_json::from_value(camel).unwrap();
+    assert!(matches!(
+        back,
+        TxInsight::OfferCreate {
+            price_lovelace: u64::MAX,
+            ..
+        }
+    ));
+}
@@ -0,0 +1,537 @@
+//! Wire-format guards for [`AnalysedTx`] / [`TxInsight`].
+//!
+//! cnft-dev workers produce these and augminted bots consume them off a
+//! queue, and the two fleets don't deploy in lockstep. Two checks keep the
+//! JSON stable:
+//!
+//! - `wire_snapshot_is_unchanged` serializes a sample of every variant and
+//!   compares it with `resources/test/wire_snapshot.json`. A diff there is a
+//!   wire-format change: make it backward compatible, or regenerate with
+//!   `UPDATE_SNAPSHOTS=1 cargo test -p tx_insights --test wire_format` and
+//!   review the snapshot diff like any other API change.
+//! - the proptests round-trip arbitrary values through JSON text, covering
+//!   u64s beyond JS's safe integer range and every optional field.
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+
+use cardano_assets::{AssetId, PolicyId};
+use proptest::collection::{hash_map, vec};
+use proptest::option;
+use proptest::prelude::*;
+use serde_json::Value;
+use tx_insights::{
+    AnalysedTx, AssetSaleKind, CounterpartyKind, CounterpartyTag, ListingAction, MintContext,
+    MintPhase, PolicyScriptType, TokenPrice, TxAsset, TxInsight, TxOfferType,
+};
+
+const POLICY: &str = "b3dab69f7e6100849434fb1781e34bd12a916557f6231b8d2629b6f6";
+const UNIT: &str = "b3dab69f7e6100849434fb1781e34bd12a916557f6231b8d2629b6f650697261746531303836";
+const IUSD: &str = "f66d78b4a3cb3d37afa0ec36461e51ecbde00f26c8f0a68f94b6988069555344";
+
+fn snapshot_path() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("resources/test/wire_snapshot.json")
+}
+
+/// Wire tag of each variant; exhaustive so a new variant must be added to
+/// [`samples`] (and the snapshot) before this compiles
+fn type_tag(insight: &TxInsight) -> &'static str {
+    match insight {
+        TxInsight::Mint { .. } => "mint",
+        TxInsight::OfferCreate { .. } => "offer_create",
+        TxInsight::Listing { .. } => "listing",
+        TxInsight::Sale { .. } => "sale",
+        TxInsight::DexTrade { .. } => "dex_trade",
+        TxInsight::AuctionBid { .. } => "auction_bid",
+        TxInsight::AuctionSettled { .. } => "auction_settled",
+        TxInsight::ListForRent { .. } => "list_for_rent",
+        TxInsight::RentStarted { .. } => "rent_started",
+        TxInsight::RentEnded { .. } => "rent_ended",
+        TxInsight::Airdrop { .. } => "airdrop",
+        TxInsight::Unclassified { .. } => "unclassified",
+        TxInsight::Unknown => "unknown",
+    }
+}
+
+const ALL_TAGS: &[&str] = &[
+    "mint",
+    "offer_create",
+    "listing",
+    "sale",
+    "dex_trade",
+    "auction_bid",
+    "auction_settled",
+    "list_for_rent",
+    "rent_started",
+    "rent_ended",
+    "airdrop",
+    "unclassified",
+    "unknown",
+];
+
+fn asset() -> TxAsset {
+    TxAsset {
+        id: AssetId::parse_concatenated(UNIT).unwrap(),
+        qty: 1,
+        traits: None,
+        name: None,
+        image: None,
+        rarity_rank: None,
+    }
+}
+
+/// One of every variant, with optional fields both set and unset
+fn samples() -> AnalysedTx {
+    let enriched = TxAsset {
+        traits: Some(HashMap::from([(
+            "Hat".to_string(),
+            vec!["Tricorn".to_string()],
+        )])),
+        name: Some("Pirate #1086".to_string()),
+        image: Some("ipfs://QmPirate".to_string()),
+        rarity_rank: Some(42),
+        ..asset()
+    };
+    let policy_id = PolicyId::new(POLICY).unwrap();
+
+    AnalysedTx {
+        hash: "6c3ef6a0f1a4d0e7b4f5c2b1a9e8d7c6b5a4f3e2d1c0b9a8f7e6d5c4b3a2f1e0".to_string(),
+        insights: vec![
+            TxInsight::Mint {
+                assets: vec![enriched],
+                context: Some(
+                    MintContext::from_payment(Some(45_000_000), 1, Some("JPG.store".into()))
+                        .with_policy_script(PolicyScriptType::NativeTimelocked)
+                        .with_supply(10_000, 2_500),
+                ),
+            },
+            TxInsight::Mint {
+                assets: vec![asset()],
+                context: None,
+            },
+            TxInsight::OfferCreate {
+                policy_id: policy_id.clone(),
+                seller: "addr1offerer".to_string(),
+                offer_type: TxOfferType::Collection,
+                price_lovelace: 50_000_000,
+                price_token: None,
+            },
+            TxInsight::OfferCreate {
+                policy_id,
+                seller: "addr1offerer".to_string(),
+                offer_type: TxOfferType::Asset {
+                    asset_hex: "50697261746531303836".to_string(),
+                },
+                // Above JS's safe integer range: sent as a string
+                price_lovelace: 9_007_199_254_740_993,
+                price_token: None,
+            },
+            TxInsight::Listing {
+                asset: asset(),
+                action: ListingAction::Create,
+                seller: "addr1seller".to_string(),
+                price_lovelace: 125_000_000,
+                price_token: None,
+            },
+            TxInsight::Sale {
+                asset: asset(),
+                kind: AssetSaleKind::AcceptOffer,
+                seller: "addr1seller".to_string(),
+                buyer: "addr1buyer".to_string(),
+                price_lovelace: 125_000_000,
+                price_token: None,
+                seller_stake: None,
+                buyer_stake: None,
+            },
+            // Settled in iUSD
+            TxInsight::Sale {
+                asset: asset(),
+                kind: AssetSaleKind::Standard,
+                seller: "addr1seller".to_string(),
+                buyer: "addr1buyer".to_string(),
+                price_lovelace: 0,
+                price_token: Some(TokenPrice::new(
+                    AssetId::parse_concatenated(IUSD).unwrap(),
+                    250_000_000,
+                    6,
+                )),
+                seller_stake: None,
+                buyer_stake: None,
+            },
+            TxInsight::DexTrade { asset: asset() },
+            TxInsight::AuctionBid {
+                asset: asset(),
+                bidder: "addr1bidder".to_string(),
+                amount_lovelace: 300_000_000,
+            },
+            TxInsight::AuctionSettled {
+                asset: asset(),
+                winner: "addr1winner".to_string(),
+                amount_lovelace: 350_000_000,
+            },
+            TxInsight::ListForRent {
+                asset: asset(),
+                lender: "addr1lender".to_string(),
+                price_lovelace: 20_000_000,
+                max_duration_secs: Some(2_592_000),
+            },
+            TxInsight::ListForRent {
+                asset: asset(),
+                lender: "addr1lender".to_string(),
+                price_lovelace: 20_000_000,
+                max_duration_secs: None,
+            },
+            TxInsight::RentStarted {
+                asset: asset(),
+                lender: "addr1lender".to_string(),
+                renter: "addr1renter".to_string(),
+                price_lovelace: 20_000_000,
+                duration_secs: 604_800,
+            },
+            TxInsight::RentEnded {
+                asset: asset(),
+                lender: "addr1lender".to_string(),
+                renter: "addr1renter".to_string(),
+            },
+            TxInsight::Airdrop {
+                policy_id: PolicyId::new(POLICY).unwrap(),
+                recipients_count: 250,
+                total_quantity: 12_500_000_000_000_000_000,
+                sample_recipients: vec!["addr1holder1".to_string(), "addr1holder2".to_string()],
+            },
+            TxInsight::Unclassified {
+                policies: vec![PolicyId::new(POLICY).unwrap()],
+                script_hashes: vec![
+                    "e1317b152faac13426e6a83e06ff88a4d62cce3c1634ab0a5ec13309".to_string()
+                ],
+                hint: Some("Unknown transaction type".to_string()),
+            },
+            TxInsight::Unclassified {
+                policies: vec![PolicyId::new(POLICY).unwrap()],
+                script_hashes: Vec::new(),
+                hint: None,
+            },
+            TxInsight::Unknown,
+        ],
+        counterparties: HashMap::from([(
+            "addr1seller".to_string(),
+            CounterpartyTag::new(CounterpartyKind::Marketplace, "JPG.store escrow"),
+        )]),
+    }
+}
+
+#[test]
+fn wire_snapshot_is_unchanged() {
+    let samples = samples();
+    let actual = serde_json::to_value(&samples).unwrap();
+
+    if std::env::var_os("UPDATE_SNAPSHOTS").is_some() {
+        let pretty = serde_json::to_string_pretty(&actual).unwrap();
+        fs::write(snapshot_path(), pretty + "\n").unwrap();
+        return;
+    }
+
+    let tags: Vec<&str> = samples.insights.iter().map(type_tag).collect();
+    for tag in ALL_TAGS {
+        assert!(tags.contains(tag), "no sample for `{tag}`");
+    }
+
+    let expected: Value =
+        serde_json::from_str(&fs::read_to_string(snapshot_path()).unwrap()).unwrap();
+    assert_eq!(
+        actual, expected,
+        "TxInsight wire format changed; see the module docs before updating the snapshot"
+    );
+
+    // Payloads already in flight still decode to the same JSON
+    let decoded: AnalysedTx = serde_json::from_value(expected.clone()).unwrap();
+    assert_eq!(serde_json::to_value(&decoded).unwrap(), expected);
+}
+
+fn lovelace() -> impl Strategy<Value = u64> {
+    prop_oneof![0..100_000_000_000u64, any::<u64>()]
+}
+
+fn address() -> impl Strategy<Value = String> {
+    "addr1[a-z0-9]{20,50}"
+}
+
+fn stake_address() -> impl Strategy<Value = String> {
+    "stake1[a-z0-9]{53}"
+}
+
+fn policy_id() -> impl Strategy<Value = PolicyId> {
+    "[0-9a-f]{56}".prop_map(|hex| PolicyId::new(hex).unwrap())
+}
+
+fn tx_asset() -> impl Strategy<Value = TxAsset> {
+    (
+        "[0-9a-f]{56}([0-9a-f]{2}){0,32}",
+        lovelace(),
+        option::of(hash_map("[A-Za-z]{1,8}", vec(".{0,12}", 0..3), 0..4)),
+        option::of(".{0,20}"),
+        option::of("ipfs://[A-Za-z0-9]{8,46}"),
+        option::of(any::<u32>()),
+    )
+        .prop_map(|(id, qty, traits, name, image, rarity_rank)| TxAsset {
+            id: AssetId::new_unchecked(id[..56].to_string(), id[56..].to_string()),
+            qty,
+            traits,
+            name,
+            image,
+            rarity_rank,
+        })
+}
+
+fn token_price() -> impl Strategy<Value = TokenPrice> {
+    ("[0-9a-f]{56}([0-9a-f]{2}){0,32}", lovelace(), any::<u8>()).prop_map(
+        |(id, quantity, decimals)| {
+            TokenPrice::new(
+                AssetId::new_unchecked(id[..56].to_string(), id[56..].to_string()),
+                quantity,
+                decimals,
+            )
+        },
+    )
+}
+
+fn mint_context() -> impl Strategy<Value = MintContext> {
+    (
+        prop_oneof![
+            Just(MintPhase::PublicSale),
+            Just(MintPhase::DirectSale),
+            Just(MintPhase::Airdrop),
+            Just(MintPhase::Unknown),
+        ],
+        option::of(prop_oneof![
+            Just(PolicyScriptType::Native),
+            Just(PolicyScriptType::NativeTimelocked),
+            Just(PolicyScriptType::Plutus),
+        ]),
+        option::of("[A-Za-z. ]{1,16}"),
+        option::of(lovelace()),
+        option::of(lovelace()),
+    )
+        .prop_map(
+            |(phase, policy_script, launchpad, price_per_asset_lovelace, remaining_supply)| {
+                MintContext {
+                    phase,
+                    policy_script,
+                    launchpad,
+                    price_per_asset_lovelace,
+                    remaining_supply,
+                }
+            },
+        )
+}
+
+fn tx_insight() -> impl Strategy<Value = TxInsight> {
+    prop_oneof![
+        (vec(tx_asset(), 0..4), option::of(mint_context()))
+            .prop_map(|(assets, context)| TxInsight::Mint { assets, context })
+            .boxed(),
+        (
+            policy_id(),
+            address(),
+            option::of("([0-9a-f]{2}){0,32}"),
+            lovelace(),
+            option::of(token_price())
+        )
+            .prop_map(
+                |(policy_id, seller, asset_hex, price_lovelace, price_token)| {
+                    TxInsight::OfferCreate {
+                        policy_id,
+                        seller,
+                        offer_type: match asset_hex {
+                            Some(asset_hex) => TxOfferType::Asset { asset_hex },
+                            None => TxOfferType::Collection,
+                        },
+                        price_lovelace,
+                        price_token,
+                    }
+                }
+            )
+            .boxed(),
+        (
+            tx_asset(),
+            any::<bool>(),
+            address(),
+            lovelace(),
+            option::of(token_price())
+        )
+            .prop_map(
+                |(asset, create, seller, price_lovelace, price_token)| TxInsight::Listing {
+                    asset,
+                    action: if create {
+                        ListingAction::Create
+                    } else {
+                        ListingAction::Update
+                    },
+                    seller,
+                    price_lovelace,
+                    price_token,
+                }
+            )
+            .boxed(),
+        (
+            tx_asset(),
+            any::<bool>(),
+            address(),
+            address(),
+            lovelace(),
+            option::of(token_price()),
+            option::of(stake_address()),
+            option::of(stake_address())
+        )
+            .prop_map(
+                |(
+                    asset,
+                    standard,
+                    seller,
+                    buyer,
+                    price_lovelace,
+                    price_token,
+                    seller_stake,
+                    buyer_stake,
+                )| TxInsight::Sale {
+                    asset,
+                    kind: if standard {
+                        AssetSaleKind::Standard
+                    } else {
+                        AssetSaleKind::AcceptOffer
+                    },
+                    seller,
+                    buyer,
+                    price_lovelace,
+                    price_token,
+                    seller_stake,
+                    buyer_stake,
+                }
+            )
+            .boxed(),
+        tx_asset()
+            .prop_map(|asset| TxInsight::DexTrade { asset })
+            .boxed(),
+        (tx_asset(), address(), lovelace())
+            .prop_map(|(asset, bidder, amount_lovelace)| TxInsight::AuctionBid {
+                asset,
+                bidder,
+                amount_lovelace,
+            })
+            .boxed(),
+        (tx_asset(), address(), lovelace())
+            .prop_map(
+                |(asset, winner, amount_lovelace)| TxInsight::AuctionSettled {
+                    asset,
+                    winner,
+                    amount_lovelace,
+                }
+            )
+            .boxed(),
+        (tx_asset(), address(), lovelace(), option::of(lovelace()))
+            .prop_map(
+                |(asset, lender, price_lovelace, max_duration_secs)| TxInsight::ListForRent {
+                    asset,
+                    lender,
+                    price_lovelace,
+                    max_duration_secs,
+                }
+            )
+            .boxed(),
+        (tx_asset(), address(), address(), lovelace(), lovelace())
+            .prop_map(|(asset, lender, renter, price_lovelace, duration_secs)| {
+                TxInsight::RentStarted {
+                    asset,
+                    lender,
+                    renter,
+                    price_lovelace,
+                    duration_secs,
+                }
+            })
+            .boxed(),
+        (tx_asset(), address(), address())
+            .prop_map(|(asset, lender, renter)| TxInsight::RentEnded {
+                asset,
+                lender,
+                renter,
+            })
+            .boxed(),
+        (policy_id(), any::<u32>(), lovelace(), vec(address(), 0..5))
+            .prop_map(
+                |(policy_id, recipients_count, total_quantity, sample_recipients)| {
+                    TxInsight::Airdrop {
+                        policy_id,
+                        recipients_count,
+                        total_quantity,
+                        sample_recipients,
+                    }
+                }
+            )
+            .boxed(),
+        (
+            vec(policy_id(), 0..3),
+            vec("[0-9a-f]{56}", 0..3),
+            option::of(".{0,40}"),
+        )
+            .prop_map(|(policies, script_hashes, hint)| TxInsight::Unclassified {
+                policies,
+                script_hashes,
+                hint,
+            })
+            .boxed(),
+        Just(TxInsight::Unknown).boxed(),
+    ]
+}
+
+fn counterparty_tag() -> impl Strategy<Value = CounterpartyTag> {
+    (
+        prop_oneof![
+            Just(CounterpartyKind::Marketplace),
+            Just(CounterpartyKind::Exchange),
+            Just(CounterpartyKind::Burn),
+            Just(CounterpartyKind::Contract),
+            Just(CounterpartyKind::Community),
+        ],
+        ".{1,24}",
+    )
+        .prop_map(|(kind, label)| CounterpartyTag::new(kind, label))
+}
+
+/// JSON text -> value -> JSON, which must be a fixed point
+fn assert_round_trip<T>(value: &T) -> Result<(), TestCaseError>
+where
+    T: serde::Serialize + serde::de::DeserializeOwned,
+{
+    let json = serde_json::to_value(value).unwrap();
+    let text = serde_json::to_string(value).unwrap();
+    let back: T = serde_json::from_str(&text)
+        .map_err(|e| TestCaseError::fail(format!("{e} decoding {text}")))?;
+    prop_assert_eq!(serde_json::to_value(&back).unwrap(), json);
+    Ok(())
+}
+
+proptest! {
+    #[test]
+    fn tx_insight_round_trips(insight in tx_insight()) {
+        assert_round_trip(&insight)?;
+    }
+
+    #[test]
+    fn analysed_tx_round_trips(
+        hash in "[0-9a-f]{64}",
+        insights in vec(tx_insight(), 0..6),
+        counterparties in hash_map(address(), counterparty_tag(), 0..3),
+    ) {
+        assert_round_trip(&AnalysedTx { hash, insights, counterparties })?;
+    }
+
+    #[test]
+    fn large_lovelace_amounts_are_strings(price_lovelace in 9_007_199_254_740_992u64..) {
+        let json = serde_json::to_value(TxInsight::DexTrade {
+            asset: TxAsset { qty: price_lovelace, ..asset() },
+        })
+        .unwrap();
+        prop_assert_eq!(&json["asset"]["qty"], &Value::String(price_lovelace.to_string()));
+    }
+}
@@ -140,8 +140,10 @@ pub fn DropEditor(
 /// Generate a unique key for a drop (for list rendering)
 fn drop_key(drop: &Drop) -> String {
     match drop {
-        Drop::Tip { token, amount } => format!("tip-{token}-{amount}"),
-        Drop::WalletSend { asset_id, amount } => {
+        Drop::Tip { token, amount, .. } => format!("tip-{token}-{amount}"),
+        Drop::WalletSend {
+            asset_id, amount, ..
+        } => {
             format!("ws-{}-{amount}", asset_id.concatenated())
         }
     }
@@ -150,10 +152,12 @@ fn drop_key(drop: &Drop) -> String {
 /// Format drop display name for AssetCard
 fn drop_display_name(drop: &Drop) -> String {
     match drop {
-        Drop::Tip { token, amount } => {
+        Drop::Tip { token, amount, .. } => {
             format!("{} {}", format_number(*amount), token)
         }
-        Drop::WalletSend { asset_id, amount } => {
+        Drop::WalletSend {
+            asset_id, amount, ..
+        } => {
             let name = asset_id.asset_name();
             if *amount > 1 {
                 format!("{} x{}", name, format_number(*amount as f64))
@@ -0,0 +1,434 @@
+//! Lossless decimal amounts for token quantities
+//!
+//! Fungible tokens with decimals (e.g. 6-decimal stablecoins) lose precision
+//! when round-tripped through `f64`, and large raw quantities exceed
+//! JavaScript's safe integer range. [`Decimal`] stores the raw integer amount
+//! alongside its scale and always serializes as a string (`"1234.567890"`).
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::cmp::Ordering;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::str::FromStr;
+
+/// Maximum supported scale (number of fractional digits)
+pub const MAX_SCALE: u32 = 30;
+
+/// A fixed-point decimal: `amount * 10^-scale`
+///
+/// Equality, ordering and hashing are value-based, so `1.5` and `1.50` compare
+/// equal even though they carry different scales.
+///
+/// # Examples
+///
+/// ```
+/// use wasm_safe_serde::Decimal;
+///
+/// // 1.5 units of a 6-decimal token, from its on-chain quantity
+/// let amount = Decimal::from_base_units(1_500_000, 6);
+/// assert_eq!(amount.to_string(), "1.500000");
+///
+/// let total = amount.checked_add(&"0.25".parse().unwrap()).unwrap();
+/// assert_eq!(total.to_base_units(6), Some(1_750_000));
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Decimal {
+    amount: i128,
+    scale: u32,
+}
+
+/// Error returned when parsing or constructing a [`Decimal`] fails
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DecimalError {
+    /// The input was not a plain decimal string (`-?digits(.digits)?`)
+    InvalidFormat(String),
+    /// The scale exceeds [`MAX_SCALE`]
+    ScaleTooLarge(u32),
+    /// The value does not fit in the underlying integer representation
+    Overflow,
+}
+
+impl fmt::Display for DecimalError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DecimalError::InvalidFormat(s) => write!(f, "invalid decimal: {s}"),
+            DecimalError::ScaleTooLarge(scale) => {
+                write!(f, "decimal scale {scale} exceeds maximum of {MAX_SCALE}")
+            }
+            DecimalError::Overflow => write!(f, "decimal overflow"),
+        }
+    }
+}
+
+impl std::error::Error for DecimalError {}
+
+fn pow10(exp: u32) -> Option<i128> {
+    10i128.checked_pow(exp)
+}
+
+impl Decimal {
+    /// Create a decimal from a raw amount and scale (`amount * 10^-scale`)
+    pub fn new(amount: i128, scale: u32) -> Result<Self, DecimalError> {
+        if scale > MAX_SCALE {
+            return Err(DecimalError::ScaleTooLarge(scale));
+        }
+        Ok(Self { amount, scale })
+    }
+
+    /// Create a decimal from an on-chain token quantity with `decimals` places
+    ///
+    /// Panics if `decimals` exceeds [`MAX_SCALE`]; token registries cap
+    /// decimals well below this.
+    pub fn from_base_units(quantity: u64, decimals: u32) -> Self {
+        Self::new(quantity as i128, decimals).expect("token decimals within MAX_SCALE")
+    }
+
+    /// Zero at scale 0
+    pub const fn zero() -> Self {
+        Self {
+            amount: 0,
+            scale: 0,
+        }
+    }
+
+    /// Raw integer amount
+    pub fn amount(&self) -> i128 {
+        self.amount
+    }
+
+    /// Number of fractional digits
+    pub fn scale(&self) -> u32 {
+        self.scale
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.amount == 0
+    }
+
+    pub fn is_negative(&self) -> bool {
+        self.amount < 0
+    }
+
+    /// Convert back to an on-chain quantity with `decimals` places
+    ///
+    /// Returns `None` if the value is negative, would lose precision, or does
+    /// not fit in a `u64`.
+    pub fn to_base_units(&self, decimals: u32) -> Option<u64> {
+        let rescaled = self.rescale(decimals)?;
+        u64::try_from(rescaled.amount).ok()
+    }
+
+    /// Change the scale without losing precision
+    ///
+    /// Returns `None` if reducing the scale would drop non-zero digits or the
+    /// result overflows.
+    pub fn rescale(&self, scale: u32) -> Option<Self> {
+        if scale > MAX_SCALE {
+            return None;
+        }
+        match scale.cmp(&self.scale) {
+            Ordering::Equal => Some(*self),
+            Ordering::Greater => {
+                let factor = pow10(scale - self.scale)?;
+                Some(Self {
+                    amount: self.amount.checked_mul(factor)?,
+                    scale,
+                })
+            }
+            Ordering::Less => {
+                let factor = pow10(self.scale - scale)?;
+                if self.amount % factor != 0 {
+                    return None;
+                }
+                Some(Self {
+                    amount: self.amount / factor,
+                    scale,
+                })
+            }
+        }
+    }
+
+    /// Reduce the scale to `scale`, rounding half away from zero
+    pub fn round_to(&self, scale: u32) -> Self {
+        if scale >= self.scale {
+            return self.rescale(scale).unwrap_or(*self);
+        }
+        let factor = pow10(self.scale - scale).expect("scale within MAX_SCALE");
+        let quotient = self.amount / factor;
+        let remainder = (self.amount % factor).abs();
+        let amount = if remainder * 2 >= factor {
+            quotient + self.amount.signum()
+        } else {
+            quotient
+        };
+        Self { amount, scale }
+    }
+
+    /// Strip trailing fractional zeros (`1.500` -> `1.5`)
+    pub fn normalize(&self) -> Self {
+        let mut result = *self;
+        while result.scale > 0 && result.amount % 10 == 0 {
+            result.amount /= 10;
+            result.scale -= 1;
+        }
+        result
+    }
+
+    fn aligned(&self, other: &Self) -> Option<(i128, i128, u32)> {
+        let scale = self.scale.max(other.scale);
+        let lhs = self.rescale(scale)?.amount;
+        let rhs = other.rescale(scale)?.amount;
+        Some((lhs, rhs, scale))
+    }
+
+    pub fn checked_add(&self, other: &Self) -> Option<Self> {
+        let (lhs, rhs, scale) = self.aligned(other)?;
+        Some(Self {
+            amount: lhs.checked_add(rhs)?,
+            scale,
+        })
+    }
+
+    pub fn checked_sub(&self, other: &Self) -> Option<Self> {
+        let (lhs, rhs, scale) = self.aligned(other)?;
+        Some(Self {
+            amount: lhs.checked_sub(rhs)?,
+            scale,
+        })
+    }
+
+    pub fn checked_mul(&self, other: &Self) -> Option<Self> {
+        let scale = self.scale.checked_add(other.scale)?;
+        if scale > MAX_SCALE {
+            return None;
+        }
+        Some(Self {
+            amount: self.amount.checked_mul(other.amount)?,
+            scale,
+        })
+    }
+
+    /// Multiply by an integer (e.g. per-winner amount times winner count)
+    pub fn checked_mul_int(&self, factor: i128) -> Option<Self> {
+        Some(Self {
+            amount: self.amount.checked_mul(factor)?,
+            scale: self.scale,
+        })
+    }
+
+    /// Lossy conversion to `f64` for display and charting
+    pub fn to_f64(&self) -> f64 {
+        self.amount as f64 / 10f64.powi(self.scale as i32)
+    }
+
+    /// Convert from `f64`, rounding to `scale` fractional digits
+    pub fn from_f64(value: f64, scale: u32) -> Result<Self, DecimalError> {
+        if !value.is_finite() {
+            return Err(DecimalError::InvalidFormat(value.to_string()));
+        }
+        if scale > MAX_SCALE {
+            return Err(DecimalError::ScaleTooLarge(scale));
+        }
+        format!("{value:.*}", scale as usize).parse()
+    }
+}
+
+impl FromStr for Decimal {
+    type Err = DecimalError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || DecimalError::InvalidFormat(s.to_string());
+        let trimmed = s.trim();
+        let (negative, digits) = match trimmed.strip_prefix('-') {
+            Some(rest) => (true, rest),
+            None => (false, trimmed.strip_prefix('+').unwrap_or(trimmed)),
+        };
+        let (whole, frac) = digits.split_once('.').unwrap_or((digits, ""));
+
+        if whole.is_empty() && frac.is_empty() {
+            return Err(invalid());
+        }
+        if !whole.bytes().all(|b| b.is_ascii_digit()) || !frac.bytes().all(|b| b.is_ascii_digit()) {
+            return Err(invalid());
+        }
+
+        let scale = frac.len() as u32;
+        if scale > MAX_SCALE {
+            return Err(DecimalError::ScaleTooLarge(scale));
+        }
+
+        let mut amount: i128 = 0;
+        for b in whole.bytes().chain(frac.bytes()) {
+            amount = amount
+                .checked_mul(10)
+                .and_then(|a| a.checked_add((b - b'0') as i128))
+                .ok_or(DecimalError::Overflow)?;
+        }
+        if negative {
+            amount = -amount;
+        }
+
+        Ok(Self { amount, scale })
+    }
+}
+
+impl fmt::Display for Decimal {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let sign = if self.amount < 0 { "-" } else { "" };
+        let digits = self.amount.unsigned_abs().to_string();
+        let scale = self.scale as usize;
+
+        if scale == 0 {
+            return write!(f, "{sign}{digits}");
+        }
+
+        let padded = format!("{digits:0>width$}", width = scale + 1);
+        let (whole, frac) = padded.split_at(padded.len() - scale);
+        write!(f, "{sign}{whole}.{frac}")
+    }
+}
+
+impl PartialEq for Decimal {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Decimal {}
+
+impl PartialOrd for Decimal {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Decimal {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match self.aligned(other) {
+            Some((lhs, rhs, _)) => lhs.cmp(&rhs),
+            // Aligning only overflows for huge magnitudes; fall back to the
+            // normalized forms which always align within MAX_SCALE.
+            None => {
+                let (lhs, rhs) = (self.normalize(), other.normalize());
+                match lhs.aligned(&rhs) {
+                    Some((l, r, _)) => l.cmp(&r),
+                    None => lhs.to_f64().total_cmp(&rhs.to_f64()),
+                }
+            }
+        }
+    }
+}
+
+impl Hash for Decimal {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        let normalized = self.normalize();
+        normalized.amount.hash(state);
+        normalized.scale.hash(state);
+    }
+}
+
+impl Serialize for Decimal {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(&self.to_string())
+    }
+}
+
+impl<'de> Deserialize<'de> for Decimal {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        use serde_json::Value;
+        let value = Value::deserialize(deserializer)?;
+
+        match value {
+            Value::String(s) => s.parse().map_err(serde::de::Error::custom),
+            // Accept plain JSON numbers from older payloads; their textual form
+            // is parsed directly so integers never pass through f64.
+            Value::Number(n) => n.to_string().parse().map_err(serde::de::Error::custom),
+            _ => Err(serde::de::Error::custom(
+                "Expected decimal string or number",
+            )),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_and_display_round_trip() {
+        for input in ["0", "1.5", "-0.000001", "123456789.123456", "0.10"] {
+            let decimal: Decimal = input.parse().unwrap();
+            assert_eq!(decimal.to_string(), input);
+        }
+        assert_eq!(".5".parse::<Decimal>().unwrap().to_string(), "0.5");
+        assert!("1e6".parse::<Decimal>().is_err());
+        assert!("".parse::<Decimal>().is_err());
+        assert!("1.2.3".parse::<Decimal>().is_err());
+    }
+
+    #[test]
+    fn test_value_equality_ignores_scale() {
+        let a: Decimal = "1.5".parse().unwrap();
+        let b: Decimal = "1.500".parse().unwrap();
+        assert_eq!(a, b);
+        assert!(a < "1.5001".parse().unwrap());
+        assert_eq!(b.normalize().scale(), 1);
+    }
+
+    #[test]
+    fn test_arithmetic_aligns_scale() {
+        let a = Decimal::from_base_units(1_000_001, 6);
+        let b: Decimal = "2.5".parse().unwrap();
+
+        assert_eq!(a.checked_add(&b).unwrap().to_string(), "3.500001");
+        assert_eq!(b.checked_sub(&a).unwrap().to_string(), "1.499999");
+        assert_eq!(b.checked_mul(&b).unwrap().to_string(), "6.25");
+        assert_eq!(b.checked_mul_int(4).unwrap().normalize().to_string(), "10");
+    }
+
+    #[test]
+    fn test_base_units_lossless() {
+        // 2^53 + 1 cannot be represented exactly as f64
+        let quantity = 9_007_199_254_740_993_u64;
+        let decimal = Decimal::from_base_units(quantity, 6);
+        assert_eq!(decimal.to_base_units(6), Some(quantity));
+        assert_eq!(decimal.to_base_units(8), Some(quantity * 100));
+        assert_eq!(decimal.to_base_units(0), None);
+        assert_eq!("-1".parse::<Decimal>().unwrap().to_base_units(0), None);
+    }
+
+    #[test]
+    fn test_rounding() {
+        let d: Decimal = "1.2345".parse().unwrap();
+        assert_eq!(d.round_to(2).to_string(), "1.23");
+        assert_eq!(d.round_to(3).to_string(), "1.235");
+        assert_eq!(
+            "-1.235".parse::<Decimal>().unwrap().round_to(2).to_string(),
+            "-1.24"
+        );
+        assert_eq!(
+            Decimal::from_f64(0.1 + 0.2, 6).unwrap().to_string(),
+            "0.300000"
+        );
+    }
+
+    #[test]
+    fn test_serde_as_string() {
+        let decimal = Decimal::from_base_units(12_738_606_488_933_375, 6);
+        let json = serde_json::to_string(&decimal).unwrap();
+        assert_eq!(json, "\"12738606488.933375\"");
+
+        let back: Decimal = serde_json::from_str(&json).unwrap();
+        assert_eq!(back, decimal);
+
+        let from_number: Decimal = serde_json::from_str("12738606488933375").unwrap();
+        assert_eq!(from_number.scale(), 0);
+        assert_eq!(from_number.amount(), 12_738_606_488_933_375);
+    }
+}
@@ -3,6 +3,13 @@
 //! This crate provides serialization modules that handle JavaScript's
 //! Number.MAX_SAFE_INTEGER limit (2^53 - 1 = 9007199254740991) by
 //! automatically converting large u64 values to strings.
+//!
+//! It also provides [`Decimal`], a lossless fixed-point amount for fungible
+//! token quantities that always serializes as a string.
+
+mod decimal;
+
+pub use decimal::{Decimal, DecimalError, MAX_SCALE};
 
 /// Serializes u64 values as strings when they exceed JavaScript's safe integer limit
 /// (Number.MAX_SAFE_INTEGER = 2^53 - 1 = 9007199254740991)
@@ -241,6 +248,139 @@ pub mod u64_vec {
     }
 }
 
+/// WASM-safe serialization for Option<Vec<u64>>
+///
+/// Use with `#[serde(default, with = "wasm_safe_serde::u64_vec_option")]`
+pub mod u64_vec_option {
+    use serde::{Deserialize, Deserializer, Serializer};
+    use serde_json::Value;
+
+    const MAX_SAFE_JS_INTEGER: u64 = 9007199254740991;
+
+    pub fn serialize<S>(values: &Option<Vec<u64>>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        use serde::ser::SerializeSeq;
+        let Some(values) = values else {
+            return serializer.serialize_none();
+        };
+        let mut seq = serializer.serialize_seq(Some(values.len()))?;
+        for value in values {
+            if *value > MAX_SAFE_JS_INTEGER {
+                seq.serialize_element(&value.to_string())?;
+            } else {
+                seq.serialize_element(value)?;
+            }
+        }
+        seq.end()
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Option<Vec<u64>>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let Some(values) = Option::<Vec<Value>>::deserialize(deserializer)? else {
+            return Ok(None);
+        };
+        let mut result = Vec::with_capacity(values.len());
+
+        for value in values {
+            let parsed_value = match value {
+                Value::Number(n) => {
+                    if let Some(u) = n.as_u64() {
+                        u
+                    } else {
+                        return Err(serde::de::Error::custom("Invalid number for u64"));
+                    }
+                }
+                Value::String(s) => s
+                    .parse::<u64>()
+                    .map_err(|_| serde::de::Error::custom("Invalid string for u64"))?,
+                _ => return Err(serde::de::Error::custom("Expected number or string")),
+            };
+            result.push(parsed_value);
+        }
+
+        Ok(Some(result))
+    }
+}
+
+/// WASM-safe serialization for HashMap<String, Vec<u64>>, e.g. role ids keyed
+/// by guild
+///
+/// Use with `#[serde(with = "wasm_safe_serde::u64_vec_map")]`
+pub mod u64_vec_map {
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+    use serde_json::{Map, Value};
+    use std::collections::HashMap;
+
+    const MAX_SAFE_JS_INTEGER: u64 = 9007199254740991;
+
+    /// A single u64 written as a number or, above the safe limit, a string
+    struct SafeU64(u64);
+
+    impl Serialize for SafeU64 {
+        fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            S: Serializer,
+        {
+            if self.0 > MAX_SAFE_JS_INTEGER {
+                serializer.serialize_str(&self.0.to_string())
+            } else {
+                serializer.serialize_u64(self.0)
+            }
+        }
+    }
+
+    pub fn serialize<S>(value: &HashMap<String, Vec<u64>>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        use serde::ser::SerializeMap;
+        let mut map = serializer.serialize_map(Some(value.len()))?;
+        for (k, values) in value {
+            let values: Vec<SafeU64> = values.iter().copied().map(SafeU64).collect();
+            map.serialize_entry(k, &values)?;
+        }
+        map.end()
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<HashMap<String, Vec<u64>>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let map = Map::deserialize(deserializer)?;
+        let mut result = HashMap::with_capacity(map.len());
+
+        for (key, value) in map {
+            let Value::Array(values) = value else {
+                return Err(serde::de::Error::custom("Expected array of u64"));
+            };
+            let mut parsed = Vec::with_capacity(values.len());
+            for value in values {
+                let parsed_value = match value {
+                    Value::Number(n) => {
+                        if let Some(u) = n.as_u64() {
+                            u
+                        } else {
+                            return Err(serde::de::Error::custom("Invalid number for u64"));
+                        }
+                    }
+                    Value::String(s) => s
+                        .parse::<u64>()
+                        .map_err(|_| serde::de::Error::custom("Invalid string for u64"))?,
+                    _ => return Err(serde::de::Error::custom("Expected number or string")),
+                };
+                parsed.push(parsed_value);
+            }
+            result.insert(key, parsed);
+        }
+
+        Ok(result)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -372,4 +512,66 @@ mod tests {
         assert_eq!(deserialized.role_ids[1], 123456);
         assert_eq!(deserialized.role_ids[2], 9999999999999999999);
     }
+
+    #[test]
+    fn test_u64_vec_option_serialization() {
+        #[derive(Serialize, Deserialize)]
+        struct TestOptionVec {
+            #[serde(default, with = "u64_vec_option")]
+            role_ids: Option<Vec<u64>>,
+        }
+
+        let large_role_id = 1317858678782820400_u64;
+        let test_data = TestOptionVec {
+            role_ids: Some(vec![large_role_id, 123456]),
+        };
+        let json = serde_json::to_string(&test_data).expect("Should serialize successfully");
+        assert_eq!(
+            json,
+            format!(r#"{{"role_ids":["{large_role_id}",123456]}}"#)
+        );
+
+        let deserialized: TestOptionVec =
+            serde_json::from_str(&json).expect("Should deserialize successfully");
+        assert_eq!(deserialized.role_ids, Some(vec![large_role_id, 123456]));
+
+        let none = TestOptionVec { role_ids: None };
+        let json = serde_json::to_string(&none).expect("Should serialize successfully");
+        assert_eq!(json, r#"{"role_ids":null}"#);
+
+        for json in [r#"{"role_ids":null}"#, "{}"] {
+            let deserialized: TestOptionVec =
+                serde_json::from_str(json).expect("Should deserialize successfully");
+            assert_eq!(deserialized.role_ids, None);
+        }
+
+        assert!(serde_json::from_str::<TestOptionVec>(r#"{"role_ids":[true]}"#).is_err());
+    }
+
+    #[test]
+    fn test_u64_vec_map_serialization() {
+        #[derive(Serialize, Deserialize)]
+        struct TestVecMap {
+            #[serde(with = "u64_vec_map")]
+            roles_by_guild: HashMap<String, Vec<u64>>,
+        }
+
+        let large_role_id = 1317858678782820400_u64;
+        let test_data = TestVecMap {
+            roles_by_guild: HashMap::from([("guild".to_string(), vec![large_role_id, 123456])]),
+        };
+        let json = serde_json::to_string(&test_data).expect("Should serialize successfully");
+        assert_eq!(
+            json,
+            format!(r#"{{"roles_by_guild":{{"guild":["{large_role_id}",123456]}}}}"#)
+        );
+
+        let json = r#"{"roles_by_guild":{"a":["1317858678782820400",1],"b":[]}}"#;
+        let deserialized: TestVecMap =
+            serde_json::from_str(json).expect("Should deserialize successfully");
+        assert_eq!(deserialized.roles_by_guild["a"], vec![large_role_id, 1]);
+        assert!(deserialized.roles_by_guild["b"].is_empty());
+
+        assert!(serde_json::from_str::<TestVecMap>(r#"{"roles_by_guild":{"a":1}}"#).is_err());
+    }
 }
@@ -0,0 +1,60 @@
+//! Compatibility shims across `worker` lines
+//!
+//! Downstream workers pick a line with the `worker-0-7` (default) or
+//! `worker-0-6` feature and code against the items here instead of the
+//! differing upstream APIs, so each worker can move to a new line on its own
+//! schedule:
+//!
+//! ```toml
+//! worker_stack = { workspace = true, default-features = false, features = ["worker-0-6"] }
+//! ```
+//!
+//! Workers that use the `#[event]` / `#[durable_object]` macros still need a
+//! direct `worker` dependency on the same line, since the macros expand to
+//! `::worker` paths.
+
+use crate::worker::{Result, Storage};
+use serde::de::DeserializeOwned;
+
+/// The `worker` line selected at build time as `(major, minor)`
+#[cfg(feature = "worker-0-7")]
+pub const WORKER_LINE: (u32, u32) = (0, 7);
+#[cfg(all(feature = "worker-0-6", not(feature = "worker-0-7")))]
+pub const WORKER_LINE: (u32, u32) = (0, 6);
+
+/// Whether the selected `worker` line is at least `major.minor`
+///
+/// For runtime branches; use the `worker-0-*` features for code that only
+/// compiles on one line.
+pub const fn worker_at_least(major: u32, minor: u32) -> bool {
+    WORKER_LINE.0 > major || (WORKER_LINE.0 == major && WORKER_LINE.1 >= minor)
+}
+
+/// Durable Object trait; must be imported explicitly from 0.7
+pub use crate::worker::DurableObject;
+
+/// Durable Object state, under the name used by the prelude
+pub type DurableObjectState = crate::worker::State;
+
+/// KV bindings, formerly the separate `worker-kv` crate
+pub use crate::worker::kv::{KvError, KvStore};
+
+/// Read a Durable Object storage key, `None` if unset
+#[cfg(feature = "worker-0-7")]
+pub async fn storage_get<T: DeserializeOwned>(storage: &Storage, key: &str) -> Result<Option<T>> {
+    storage.get(key).await
+}
+
+/// Read a Durable Object storage key, `None` if unset
+///
+/// 0.6's `get` reports a missing key as an error, indistinguishable from a
+/// real failure, so presence is checked through `get_multiple` instead.
+#[cfg(all(feature = "worker-0-6", not(feature = "worker-0-7")))]
+pub async fn storage_get<T: DeserializeOwned>(storage: &Storage, key: &str) -> Result<Option<T>> {
+    let values = storage.get_multiple(vec![key]).await?;
+    let value = values.get(&key.into());
+    if value.is_undefined() {
+        return Ok(None);
+    }
+    Ok(Some(serde_wasm_bindgen::from_value(value)?))
+}
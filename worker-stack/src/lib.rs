@@ -5,32 +5,46 @@ pub use wasm_bindgen;
 pub use wasm_bindgen_futures;
 pub use wasm_bindgen_macro;
 pub use web_sys;
+
+// The `worker` line is chosen by feature; the newer line wins if both are on
+#[cfg(feature = "worker-0-7")]
 pub use worker;
+#[cfg(all(feature = "worker-0-6", not(feature = "worker-0-7")))]
+pub use worker_0_6 as worker;
+
+#[cfg(not(any(feature = "worker-0-7", feature = "worker-0-6")))]
+compile_error!("worker_stack needs one of the `worker-0-7` or `worker-0-6` features");
+
+pub mod compat;
+pub mod rpc;
 
 // Re-export worker attribute macros at crate root for ergonomic use
 // This allows: #[worker_stack::event(fetch)] or use worker_stack::event; #[event(fetch)]
-pub use worker::{durable_object, event};
+pub use crate::worker::{durable_object, event};
 
 /// Prelude module that imports commonly used items from the worker ecosystem
 pub mod prelude {
     // Worker core types and traits
-    pub use worker::{
+    pub use crate::worker::{
         console_debug, console_error, console_log, console_warn, durable_object, event, Bucket,
         Context, Date, DateInit, Delay, Env, Error, Request, Response, Result, RouteContext,
         Router,
     };
 
     // Queue support
-    pub use worker::{Message, MessageBatch, Queue};
+    pub use crate::worker::{Message, MessageBatch, Queue};
 
     // D1 database support
-    pub use worker::d1::{D1Database, D1PreparedStatement, D1Result};
+    pub use crate::worker::d1::{D1Database, D1PreparedStatement, D1Result};
 
     // KV store support
-    pub use worker::kv::{KvError, KvStore};
+    pub use crate::worker::kv::{KvError, KvStore};
 
     // Durable Objects support
-    pub use worker::{ObjectNamespace, State as DurableObjectState};
+    pub use crate::worker::{ObjectNamespace, State as DurableObjectState};
+
+    // Items whose shape differs between worker lines
+    pub use crate::compat::{storage_get, DurableObject};
 
     // wasm-bindgen essentials
     pub use wasm_bindgen::prelude::*;
@@ -0,0 +1,402 @@
+//! Typed RPC over Durable Object `fetch`
+//!
+//! Each method is a type implementing [`RpcMethod`], usually declared with
+//! [`rpc_method!`](crate::rpc_method), shared by the calling worker and the
+//! Durable Object:
+//!
+//! ```rust,ignore
+//! use worker_stack::rpc_method;
+//!
+//! rpc_method!(pub GetBalance: BalanceQuery => u64);
+//! // Bump the version when the request or response changes incompatibly
+//! rpc_method!(pub Transfer: TransferRequest => Receipt, version = 2);
+//! ```
+//!
+//! The caller wraps the stub and calls methods by type:
+//!
+//! ```rust,ignore
+//! let stub = RpcStub::new(env.durable_object("LEDGER")?.id_from_name("main")?.get_stub()?);
+//! let balance = stub.call::<GetBalance>(&BalanceQuery { account }).await?;
+//! ```
+//!
+//! and the Durable Object routes on the method name inside `fetch`:
+//!
+//! ```rust,ignore
+//! async fn fetch(&self, req: Request) -> Result<Response> {
+//!     let call = RpcCall::from_request(req).await?;
+//!     match call.method() {
+//!         GetBalance::NAME => call.handle::<GetBalance, _, _, _>(|q| self.balance(q)).await,
+//!         Transfer::NAME => call.handle::<Transfer, _, _, _>(|t| self.transfer(t)).await,
+//!         _ => call.unknown_method(),
+//!     }
+//! }
+//! ```
+//!
+//! Handler errors, unknown methods and version mismatches come back to the
+//! caller as an [`RpcError`] rather than a bare status code.
+
+use std::fmt;
+use std::future::Future;
+
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+
+use crate::wasm_bindgen::JsValue;
+use crate::worker::{Headers, Method, Request, RequestInit, Response, Result, Stub};
+
+/// Header carrying the caller's [`RpcMethod::VERSION`]
+pub const VERSION_HEADER: &str = "x-rpc-version";
+
+/// Calls are routed on the path, so the host is never resolved
+const BASE_URL: &str = "https://rpc.internal";
+
+/// One callable Durable Object method
+pub trait RpcMethod {
+    /// Name the Durable Object routes on; must be unique per object
+    const NAME: &'static str;
+    /// Bumped on incompatible changes to `Request` or `Response`
+    const VERSION: u32 = 1;
+
+    type Request: Serialize + DeserializeOwned;
+    type Response: Serialize + DeserializeOwned;
+}
+
+/// Declare an [`RpcMethod`]: `rpc_method!(pub Name: Request => Response)`,
+/// optionally followed by `, version = N`
+#[macro_export]
+macro_rules! rpc_method {
+    ($vis:vis $name:ident : $request:ty => $response:ty) => {
+        $crate::rpc_method!($vis $name: $request => $response, version = 1);
+    };
+    ($vis:vis $name:ident : $request:ty => $response:ty, version = $version:expr) => {
+        #[derive(Debug, Clone, Copy)]
+        $vis struct $name;
+
+        impl $crate::rpc::RpcMethod for $name {
+            const NAME: &'static str = stringify!($name);
+            const VERSION: u32 = $version;
+            type Request = $request;
+            type Response = $response;
+        }
+    };
+}
+
+/// Why an RPC call failed, as seen by the caller
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum RpcError {
+    /// The Durable Object has no handler for the method
+    UnknownMethod { method: String },
+    /// Caller and Durable Object were built with different method versions
+    VersionMismatch {
+        method: String,
+        caller: u32,
+        handler: u32,
+    },
+    /// The handler ran and returned an error
+    Handler { method: String, message: String },
+    /// A request or response body didn't match the method's types
+    Serialization { method: String, message: String },
+    /// The call never produced an RPC reply
+    Transport { message: String },
+}
+
+impl RpcError {
+    fn status(&self) -> u16 {
+        match self {
+            RpcError::UnknownMethod { .. } => 404,
+            RpcError::VersionMismatch { .. } => 409,
+            RpcError::Serialization { .. } => 400,
+            RpcError::Handler { .. } | RpcError::Transport { .. } => 500,
+        }
+    }
+}
+
+impl fmt::Display for RpcError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RpcError::UnknownMethod { method } => write!(f, "Unknown RPC method {method}"),
+            RpcError::VersionMismatch {
+                method,
+                caller,
+                handler,
+            } => write!(
+                f,
+                "RPC method {method} version mismatch: caller v{caller}, handler v{handler}"
+            ),
+            RpcError::Handler { method, message } => write!(f, "RPC {method} failed: {message}"),
+            RpcError::Serialization { method, message } => {
+                write!(f, "RPC {method} (de)serialization failed: {message}")
+            }
+            RpcError::Transport { message } => write!(f, "RPC transport error: {message}"),
+        }
+    }
+}
+
+impl std::error::Error for RpcError {}
+
+impl From<crate::worker::Error> for RpcError {
+    fn from(e: crate::worker::Error) -> Self {
+        RpcError::Transport {
+            message: e.to_string(),
+        }
+    }
+}
+
+impl From<RpcError> for crate::worker::Error {
+    fn from(e: RpcError) -> Self {
+        crate::worker::Error::RustError(e.to_string())
+    }
+}
+
+/// Body of every RPC response
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum Reply<T> {
+    Ok(T),
+    Err(RpcError),
+}
+
+/// Caller side: a Durable Object stub that speaks RPC
+pub struct RpcStub {
+    stub: Stub,
+}
+
+impl RpcStub {
+    pub fn new(stub: Stub) -> Self {
+        Self { stub }
+    }
+
+    /// Call `M` on the Durable Object
+    pub async fn call<M: RpcMethod>(
+        &self,
+        request: &M::Request,
+    ) -> std::result::Result<M::Response, RpcError> {
+        let body = serde_json::to_string(request).map_err(|e| RpcError::Serialization {
+            method: M::NAME.to_string(),
+            message: e.to_string(),
+        })?;
+
+        let headers = Headers::new();
+        headers.set("content-type", "application/json")?;
+        headers.set(VERSION_HEADER, &M::VERSION.to_string())?;
+        let mut init = RequestInit::new();
+        init.with_method(Method::Post)
+            .with_headers(headers)
+            .with_body(Some(JsValue::from_str(&body)));
+        let request = Request::new_with_init(&format!("{BASE_URL}/{}", M::NAME), &init)?;
+
+        let mut response = self.stub.fetch_with_request(request).await?;
+        let status = response.status_code();
+        let text = response.text().await?;
+        match serde_json::from_str::<Reply<M::Response>>(&text) {
+            Ok(Reply::Ok(response)) => Ok(response),
+            Ok(Reply::Err(e)) => Err(e),
+            Err(e) => Err(RpcError::Transport {
+                message: format!("{} returned {status} without an RPC reply: {e}", M::NAME),
+            }),
+        }
+    }
+}
+
+/// Durable Object side: an incoming RPC call, read from `fetch`'s request
+pub struct RpcCall {
+    method: String,
+    version: Option<u32>,
+    body: String,
+}
+
+impl RpcCall {
+    pub async fn from_request(mut req: Request) -> Result<Self> {
+        let method = req.path().trim_start_matches('/').to_string();
+        let version = req
+            .headers()
+            .get(VERSION_HEADER)?
+            .and_then(|v| v.parse().ok());
+        let body = req.text().await?;
+        Ok(Self {
+            method,
+            version,
+            body,
+        })
+    }
+
+    /// Name of the called method, to match against [`RpcMethod::NAME`]
+    pub fn method(&self) -> &str {
+        &self.method
+    }
+
+    /// Decode the request, run `handler` and encode its result
+    ///
+    /// Calls without a version header are treated as version 1.
+    pub async fn handle<M, F, Fut, E>(self, handler: F) -> Result<Response>
+    where
+        M: RpcMethod,
+        F: FnOnce(M::Request) -> Fut,
+        Fut: Future<Output = std::result::Result<M::Response, E>>,
+        E: fmt::Display,
+    {
+        let caller = self.version.unwrap_or(1);
+        if caller != M::VERSION {
+            return reply::<M::Response>(Err(RpcError::VersionMismatch {
+                method: M::NAME.to_string(),
+                caller,
+                handler: M::VERSION,
+            }));
+        }
+
+        let request = match serde_json::from_str::<M::Request>(&self.body) {
+            Ok(request) => request,
+            Err(e) => {
+                return reply::<M::Response>(Err(RpcError::Serialization {
+                    method: M::NAME.to_string(),
+                    message: e.to_string(),
+                }))
+            }
+        };
+
+        reply(handler(request).await.map_err(|e| RpcError::Handler {
+            method: M::NAME.to_string(),
+            message: e.to_string(),
+        }))
+    }
+
+    /// Reply for a method this object doesn't handle
+    pub fn unknown_method(self) -> Result<Response> {
+        reply::<()>(Err(RpcError::UnknownMethod {
+            method: self.method,
+        }))
+    }
+}
+
+fn reply<T: Serialize>(result: std::result::Result<T, RpcError>) -> Result<Response> {
+    match result {
+        Ok(value) => Response::from_json(&Reply::Ok(value)),
+        Err(e) => {
+            let status = e.status();
+            Ok(Response::from_json(&Reply::<T>::Err(e))?.with_status(status))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn assert_wire<T>(value: &T, expected: serde_json::Value)
+    where
+        T: Serialize + DeserializeOwned + fmt::Debug + PartialEq,
+    {
+        assert_eq!(serde_json::to_value(value).unwrap(), expected);
+        assert_eq!(&serde_json::from_value::<T>(expected).unwrap(), value);
+    }
+
+    #[test]
+    fn test_rpc_error_wire_shape() {
+        assert_wire(
+            &RpcError::UnknownMethod {
+                method: "GetBalance".into(),
+            },
+            json!({ "kind": "unknown_method", "method": "GetBalance" }),
+        );
+        assert_wire(
+            &RpcError::VersionMismatch {
+                method: "Transfer".into(),
+                caller: 1,
+                handler: 2,
+            },
+            json!({ "kind": "version_mismatch", "method": "Transfer", "caller": 1, "handler": 2 }),
+        );
+        assert_wire(
+            &RpcError::Handler {
+                method: "Transfer".into(),
+                message: "insufficient funds".into(),
+            },
+            json!({ "kind": "handler", "method": "Transfer", "message": "insufficient funds" }),
+        );
+        assert_wire(
+            &RpcError::Serialization {
+                method: "Transfer".into(),
+                message: "missing field `to`".into(),
+            },
+            json!({ "kind": "serialization", "method": "Transfer", "message": "missing field `to`" }),
+        );
+        assert_wire(
+            &RpcError::Transport {
+                message: "stub closed".into(),
+            },
+            json!({ "kind": "transport", "message": "stub closed" }),
+        );
+    }
+
+    #[test]
+    fn test_reply_wire_shape() {
+        assert_eq!(
+            serde_json::to_value(Reply::Ok(42u64)).unwrap(),
+            json!({ "ok": 42 })
+        );
+        assert_eq!(
+            serde_json::to_value(Reply::<()>::Ok(())).unwrap(),
+            json!({ "ok": null })
+        );
+        assert_eq!(
+            serde_json::to_value(Reply::<u64>::Err(RpcError::UnknownMethod {
+                method: "Nope".into(),
+            }))
+            .unwrap(),
+            json!({ "err": { "kind": "unknown_method", "method": "Nope" } })
+        );
+
+        let Reply::Ok(value) = serde_json::from_value::<Reply<u64>>(json!({ "ok": 7 })).unwrap()
+        else {
+            panic!("Expected an ok reply");
+        };
+        assert_eq!(value, 7);
+        let Reply::Err(e) = serde_json::from_value::<Reply<u64>>(
+            json!({ "err": { "kind": "transport", "message": "gone" } }),
+        )
+        .unwrap() else {
+            panic!("Expected an err reply");
+        };
+        assert_eq!(
+            e,
+            RpcError::Transport {
+                message: "gone".into()
+            }
+        );
+
+        // Anything else is not an RPC reply
+        assert!(serde_json::from_value::<Reply<u64>>(json!({ "error": "Not Found" })).is_err());
+    }
+
+    #[test]
+    fn test_rpc_error_status() {
+        let method = String::from("M");
+        assert_eq!(
+            RpcError::UnknownMethod {
+                method: method.clone()
+            }
+            .status(),
+            404
+        );
+        assert_eq!(
+            RpcError::VersionMismatch {
+                method: method.clone(),
+                caller: 1,
+                handler: 2
+            }
+            .status(),
+            409
+        );
+        assert_eq!(
+            RpcError::Serialization {
+                method,
+                message: String::new()
+            }
+            .status(),
+            400
+        );
+    }
+}
@@ -0,0 +1,155 @@
+//! Typed access to a worker's wrangler bindings
+//!
+//! Looking bindings up by string (`env.kv("ASSETS_KV")`) at every use site
+//! turns a typo or a binding missing from one environment's wrangler config
+//! into a runtime error deep inside a request. [`bindings!`](crate::bindings!)
+//! declares them once and checks them all up front:
+//!
+//! ```rust,ignore
+//! use worker_utils::bindings;
+//!
+//! bindings! {
+//!     kv ASSETS_KV,
+//!     queue TX_QUEUE,
+//!     do COLLECTION_DO,
+//!     r2 SNAPSHOTS,
+//!     var NETWORK,
+//! }
+//!
+//! #[event(fetch)]
+//! async fn fetch(req: Request, env: Env, _ctx: Context) -> Result<Response> {
+//!     // Errs with "Missing wrangler bindings: queue TX_QUEUE, r2 SNAPSHOTS"
+//!     let bindings = Bindings::from_env(&env)?;
+//!     let cached = bindings.ASSETS_KV.get("pirates").text().await?;
+//!     // ...
+//! }
+//! ```
+//!
+//! This generates `pub struct Bindings` with one field per binding, named and
+//! typed after it. Use `pub struct Name { ... }` inside the macro to pick
+//! another name or visibility.
+//!
+//! | kind    | field type                      |
+//! |---------|---------------------------------|
+//! | `kv`    | [`KvStore`]                     |
+//! | `queue` | [`Queue`]                       |
+//! | `do`    | [`ObjectNamespace`]             |
+//! | `r2`    | [`Bucket`]                      |
+//! | `var`   | [`Var`] (plain text variables)  |
+
+pub use worker_stack::worker::kv::KvStore;
+pub use worker_stack::worker::{Bucket, Env, Error, ObjectNamespace, Queue, Result, Var};
+
+/// Record `name` as missing if `lookup` failed; used by
+/// [`bindings!`](crate::bindings!)
+#[doc(hidden)]
+pub fn check<T>(missing: &mut Vec<String>, kind: &str, name: &str, lookup: Result<T>) -> Option<T> {
+    match lookup {
+        Ok(binding) => Some(binding),
+        Err(e) => {
+            tracing::debug!("Binding {kind} {name} unavailable: {e}");
+            missing.push(format!("{kind} {name}"));
+            None
+        }
+    }
+}
+
+/// Error listing every binding [`check`] couldn't find
+#[doc(hidden)]
+pub fn missing_error(missing: &[String]) -> Error {
+    Error::RustError(format!("Missing wrangler bindings: {}", missing.join(", ")))
+}
+
+/// Declare a worker's bindings as a typed struct; see the [module
+/// docs](crate::bindings)
+#[macro_export]
+macro_rules! bindings {
+    ($vis:vis struct $name:ident { $($kind:tt $binding:ident),* $(,)? }) => {
+        #[allow(non_snake_case)]
+        $vis struct $name {
+            $(pub $binding: $crate::bindings!(@type $kind),)*
+        }
+
+        impl $name {
+            /// Look up every binding, failing with all missing ones listed
+            #[allow(non_snake_case)]
+            pub fn from_env(env: &$crate::bindings::Env) -> $crate::bindings::Result<Self> {
+                let mut missing = Vec::new();
+                $(
+                    let $binding = $crate::bindings::check(
+                        &mut missing,
+                        stringify!($kind),
+                        stringify!($binding),
+                        $crate::bindings!(@lookup $kind, env, stringify!($binding)),
+                    );
+                )*
+                if !missing.is_empty() {
+                    return Err($crate::bindings::missing_error(&missing));
+                }
+                Ok(Self {
+                    $($binding: $binding.expect("checked above"),)*
+                })
+            }
+        }
+    };
+
+    (@type kv) => { $crate::bindings::KvStore };
+    (@type queue) => { $crate::bindings::Queue };
+    (@type do) => { $crate::bindings::ObjectNamespace };
+    (@type r2) => { $crate::bindings::Bucket };
+    (@type var) => { $crate::bindings::Var };
+
+    (@lookup kv, $env:ident, $name:expr) => { $env.kv($name) };
+    (@lookup queue, $env:ident, $name:expr) => { $env.queue($name) };
+    (@lookup do, $env:ident, $name:expr) => { $env.durable_object($name) };
+    (@lookup r2, $env:ident, $name:expr) => { $env.bucket($name) };
+    (@lookup var, $env:ident, $name:expr) => { $env.var($name) };
+
+    ($($kind:tt $binding:ident),* $(,)?) => {
+        $crate::bindings! {
+            pub struct Bindings { $($kind $binding),* }
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(dead_code)]
+
+    use super::*;
+
+    // Expansion only: an Env can't be built outside the workers runtime
+    bindings! {
+        kv ASSETS_KV,
+        queue TX_QUEUE,
+        do COLLECTION_DO,
+        r2 SNAPSHOTS,
+        var NETWORK,
+    }
+
+    bindings! {
+        pub(crate) struct CronBindings { kv CRON_STATE }
+    }
+
+    #[test]
+    fn test_missing_bindings_are_listed() {
+        let mut missing = Vec::new();
+        let found = check(&mut missing, "kv", "ASSETS_KV", Ok(1));
+        let lost: Option<u8> = check(
+            &mut missing,
+            "queue",
+            "TX_QUEUE",
+            Err(Error::RustError("no such binding".to_string())),
+        );
+        assert_eq!(found, Some(1));
+        assert!(lost.is_none());
+        assert_eq!(
+            missing_error(&missing).to_string(),
+            "Missing wrangler bindings: queue TX_QUEUE"
+        );
+
+        // Generated constructors exist with the expected signature
+        let _: fn(&Env) -> Result<Bindings> = Bindings::from_env;
+        let _: fn(&Env) -> Result<CronBindings> = CronBindings::from_env;
+    }
+}
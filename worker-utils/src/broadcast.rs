@@ -0,0 +1,230 @@
+//! WebSocket pub-sub broadcasting for Cloudflare Durable Objects.
+//!
+//! A single DO instance acts as the hub for a live feed (e.g. sales on a
+//! dashboard). [`Broadcaster`] uses the hibernation WebSocket API, so sessions
+//! survive DO eviction: per-session metadata lives in the socket attachment
+//! rather than in memory.
+//!
+//! Every server → client frame is a JSON [`ServerMessage`] envelope, and the
+//! only client → server frames understood are [`ClientMessage`]s, so the
+//! browser side can share these types (or mirror them in TypeScript).
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use worker_utils::broadcast::Broadcaster;
+//! use tx_insights::AnalysedTx;
+//!
+//! // fetch(): upgrade subscribers
+//! if req.headers().get("Upgrade")?.as_deref() == Some("websocket") {
+//!     return Broadcaster::<AnalysedTx>::accept(&self.state);
+//! }
+//!
+//! // on each analysed sale
+//! Broadcaster::broadcast(&self.state, &analysed_tx)?;
+//!
+//! // websocket_message(): keep sessions fresh
+//! Broadcaster::<AnalysedTx>::handle_message(&ws, message)?;
+//!
+//! // alarm(): ping everyone and drop silent sessions
+//! Broadcaster::<AnalysedTx>::heartbeat(&self.state, 90_000)?;
+//! ```
+
+use serde::{Deserialize, Serialize};
+use std::marker::PhantomData;
+use worker_stack::worker::{
+    Response, Result, State, WebSocket, WebSocketIncomingMessage, WebSocketPair,
+};
+
+/// Close code sent to sessions pruned for missing heartbeats
+const CLOSE_IDLE: u16 = 4000;
+
+// ─── Envelopes ───────────────────────────────────────────────────────────────
+
+/// Frames sent from the Durable Object to subscribers.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ServerMessage<T> {
+    /// Sent once after the socket is accepted.
+    Welcome { session_id: String },
+    /// A broadcast payload.
+    Event { sent_at: u64, data: T },
+    /// Heartbeat; clients should answer with [`ClientMessage::Pong`].
+    Ping { ts: u64 },
+    /// Reply to a client-initiated [`ClientMessage::Ping`].
+    Pong { ts: u64 },
+}
+
+/// Frames a subscriber may send.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ClientMessage {
+    /// Answer to a server [`ServerMessage::Ping`].
+    Pong { ts: u64 },
+    /// Client-initiated liveness check.
+    Ping { ts: u64 },
+}
+
+/// Per-session metadata, stored as the WebSocket attachment.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SessionMeta {
+    pub session_id: String,
+    pub connected_at: u64,
+    pub last_seen: u64,
+}
+
+// ─── Broadcaster ─────────────────────────────────────────────────────────────
+
+/// Hibernation-safe WebSocket broadcaster, generic over the event payload.
+pub struct Broadcaster<T: Serialize>(PhantomData<T>);
+
+impl<T: Serialize> Broadcaster<T> {
+    /// Accept a WebSocket upgrade and register the session with the DO.
+    ///
+    /// Return the resulting `101 Switching Protocols` response from `fetch`.
+    pub fn accept(state: &State) -> Result<Response> {
+        let pair = WebSocketPair::new()?;
+        state.accept_web_socket(&pair.server);
+
+        let now = now_ms();
+        let meta = SessionMeta {
+            session_id: format!(
+                "{now:x}-{:08x}",
+                (js_sys::Math::random() * u32::MAX as f64) as u32
+            ),
+            connected_at: now,
+            last_seen: now,
+        };
+        pair.server.serialize_attachment(&meta)?;
+
+        send(
+            &pair.server,
+            &ServerMessage::<T>::Welcome {
+                session_id: meta.session_id,
+            },
+        )?;
+
+        Response::from_websocket(pair.client)
+    }
+
+    /// Send `data` to every connected session.
+    ///
+    /// The payload is serialized once. Returns the number of sessions the
+    /// frame was delivered to; sessions that fail to send are closed.
+    pub fn broadcast(state: &State, data: &T) -> Result<usize> {
+        let frame = serde_json::to_string(&ServerMessage::Event {
+            sent_at: now_ms(),
+            data,
+        })?;
+
+        let mut delivered = 0;
+        for ws in state.get_websockets() {
+            match ws.send_with_str(&frame) {
+                Ok(()) => delivered += 1,
+                Err(e) => {
+                    tracing::warn!("broadcast send failed, closing session: {e}");
+                    let _ = ws.close(Some(1011), Some("send failed"));
+                }
+            }
+        }
+        Ok(delivered)
+    }
+
+    /// Handle an incoming frame from `websocket_message`.
+    ///
+    /// Any frame refreshes the session's `last_seen`; client pings are
+    /// answered with a pong. Unknown frames are ignored.
+    pub fn handle_message(ws: &WebSocket, message: WebSocketIncomingMessage) -> Result<()> {
+        let now = now_ms();
+        if let Some(mut meta) = ws.deserialize_attachment::<SessionMeta>()? {
+            meta.last_seen = now;
+            ws.serialize_attachment(&meta)?;
+        }
+
+        let WebSocketIncomingMessage::String(text) = message else {
+            return Ok(());
+        };
+
+        match serde_json::from_str::<ClientMessage>(&text) {
+            Ok(ClientMessage::Ping { ts }) => send(ws, &ServerMessage::<T>::Pong { ts }),
+            Ok(ClientMessage::Pong { .. }) => Ok(()),
+            Err(_) => {
+                tracing::debug!("ignoring unrecognised client frame");
+                Ok(())
+            }
+        }
+    }
+
+    /// Ping all sessions and close those silent for longer than `max_idle_ms`.
+    ///
+    /// Call from the DO alarm. Returns the number of sessions pruned.
+    pub fn heartbeat(state: &State, max_idle_ms: u64) -> Result<usize> {
+        let now = now_ms();
+        let ping = serde_json::to_string(&ServerMessage::<T>::Ping { ts: now })?;
+
+        let mut pruned = 0;
+        for ws in state.get_websockets() {
+            let last_seen = ws
+                .deserialize_attachment::<SessionMeta>()
+                .ok()
+                .flatten()
+                .map_or(0, |meta| meta.last_seen);
+
+            if now.saturating_sub(last_seen) > max_idle_ms {
+                let _ = ws.close(Some(CLOSE_IDLE), Some("idle"));
+                pruned += 1;
+            } else if ws.send_with_str(&ping).is_err() {
+                let _ = ws.close(Some(1011), Some("send failed"));
+                pruned += 1;
+            }
+        }
+        Ok(pruned)
+    }
+
+    /// Number of sessions currently attached to the DO.
+    pub fn session_count(state: &State) -> usize {
+        state.get_websockets().len()
+    }
+}
+
+fn send<M: Serialize>(ws: &WebSocket, message: &M) -> Result<()> {
+    ws.send_with_str(serde_json::to_string(message)?)
+}
+
+fn now_ms() -> u64 {
+    js_sys::Date::now() as u64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_server_message_envelope() {
+        let event = ServerMessage::Event {
+            sent_at: 1_700_000_000_000,
+            data: serde_json::json!({"tx_hash": "abc"}),
+        };
+        let json = serde_json::to_string(&event).unwrap();
+        assert_eq!(
+            json,
+            r#"{"type":"event","sent_at":1700000000000,"data":{"tx_hash":"abc"}}"#
+        );
+
+        let welcome: ServerMessage<()> =
+            serde_json::from_str(r#"{"type":"welcome","session_id":"s1"}"#).unwrap();
+        assert_eq!(
+            welcome,
+            ServerMessage::Welcome {
+                session_id: "s1".to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn test_client_message_envelope() {
+        let ping: ClientMessage = serde_json::from_str(r#"{"type":"ping","ts":42}"#).unwrap();
+        assert_eq!(ping, ClientMessage::Ping { ts: 42 });
+        assert!(serde_json::from_str::<ClientMessage>(r#"{"type":"subscribe"}"#).is_err());
+    }
+}
@@ -0,0 +1,328 @@
+//! Chunked R2 objects for payloads too large for a single put.
+//!
+//! An object written with [`ChunkedR2Writer`] is stored as numbered parts
+//! plus a JSON [`ChunkManifest`]:
+//!
+//! ```text
+//! {key}/manifest.json
+//! {key}/part-00000
+//! {key}/part-00001
+//! ```
+//!
+//! The manifest is rewritten after every part with `complete: false`, so an
+//! upload cut short by a CPU limit or eviction can be picked up by a later
+//! invocation with [`ChunkedR2Writer::resume`]. [`ChunkedR2Reader`] refuses
+//! incomplete objects and checks each part's size and SHA-256 as it reads.
+//!
+//! ```rust,ignore
+//! use worker_utils::chunked::{ChunkedR2Reader, ChunkedR2Writer};
+//!
+//! let mut writer = ChunkedR2Writer::new(bucket.clone(), "traits/pirates")
+//!     .content_type("application/json")
+//!     .resume()
+//!     .await?;
+//! // Skip whatever an earlier invocation already committed
+//! writer.write(&payload[writer.bytes_written() as usize..]).await?;
+//! writer.finish().await?;
+//!
+//! let reader = ChunkedR2Reader::open(bucket, "traits/pirates").await?;
+//! let payload = reader.expect("written above").read_all().await?;
+//! ```
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use worker_stack::worker::{Bucket, Error, Result};
+
+/// Part size used unless [`ChunkedR2Writer::chunk_size`] says otherwise
+pub const DEFAULT_CHUNK_SIZE: usize = 8 * 1024 * 1024;
+
+/// Key of the manifest for chunked object `key`
+pub fn manifest_key(key: &str) -> String {
+    format!("{key}/manifest.json")
+}
+
+/// Key of part `index` of chunked object `key`
+pub fn part_key(key: &str, index: usize) -> String {
+    format!("{key}/part-{index:05}")
+}
+
+/// Hex SHA-256 of a part
+pub fn checksum(data: &[u8]) -> String {
+    hex::encode(Sha256::digest(data))
+}
+
+/// One stored part
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChunkPart {
+    pub size: u64,
+    pub sha256: String,
+}
+
+/// Layout of a chunked object, stored at [`manifest_key`]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChunkManifest {
+    pub key: String,
+    pub chunk_size: usize,
+    /// Content type of the reassembled object
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub content_type: Option<String>,
+    /// Parts in order; part `i` is stored at [`part_key`]`(key, i)`
+    pub parts: Vec<ChunkPart>,
+    /// False while an upload is in progress (or was interrupted)
+    pub complete: bool,
+}
+
+impl ChunkManifest {
+    pub fn new(key: impl Into<String>, chunk_size: usize) -> Self {
+        Self {
+            key: key.into(),
+            chunk_size,
+            content_type: None,
+            parts: Vec::new(),
+            complete: false,
+        }
+    }
+
+    /// Total bytes across all parts
+    pub fn total_size(&self) -> u64 {
+        self.parts.iter().map(|part| part.size).sum()
+    }
+
+    /// Check `data` against the recorded size and checksum of part `index`
+    pub fn verify_part(&self, index: usize, data: &[u8]) -> Result<()> {
+        let part = self
+            .parts
+            .get(index)
+            .ok_or_else(|| Error::RustError(format!("{} has no part {index}", self.key)))?;
+        if data.len() as u64 != part.size {
+            return Err(Error::RustError(format!(
+                "{} part {index}: expected {} bytes, got {}",
+                self.key,
+                part.size,
+                data.len()
+            )));
+        }
+        let actual = checksum(data);
+        if actual != part.sha256 {
+            return Err(Error::RustError(format!(
+                "{} part {index}: checksum mismatch (expected {}, got {actual})",
+                self.key, part.sha256
+            )));
+        }
+        Ok(())
+    }
+}
+
+async fn load_manifest(bucket: &Bucket, key: &str) -> Result<Option<ChunkManifest>> {
+    let Some(object) = bucket.get(manifest_key(key)).execute().await? else {
+        return Ok(None);
+    };
+    let Some(body) = object.body() else {
+        return Ok(None);
+    };
+    let text = body.text().await?;
+    serde_json::from_str(&text)
+        .map(Some)
+        .map_err(|e| Error::RustError(format!("Corrupt manifest for {key}: {e}")))
+}
+
+/// Writes a chunked object, one part per `chunk_size` bytes
+pub struct ChunkedR2Writer {
+    bucket: Bucket,
+    manifest: ChunkManifest,
+    buffer: Vec<u8>,
+}
+
+impl ChunkedR2Writer {
+    /// Start a new object at `key`, replacing any earlier one on finish
+    pub fn new(bucket: Bucket, key: impl Into<String>) -> Self {
+        Self {
+            bucket,
+            manifest: ChunkManifest::new(key, DEFAULT_CHUNK_SIZE),
+            buffer: Vec::new(),
+        }
+    }
+
+    pub fn chunk_size(mut self, chunk_size: usize) -> Self {
+        self.manifest.chunk_size = chunk_size.max(1);
+        self
+    }
+
+    pub fn content_type(mut self, content_type: impl Into<String>) -> Self {
+        self.manifest.content_type = Some(content_type.into());
+        self
+    }
+
+    /// Continue an interrupted upload of the same key, if there is one.
+    ///
+    /// The stored manifest wins over this writer's settings. Write from
+    /// [`bytes_written`](Self::bytes_written) onwards; anything that was
+    /// buffered but not yet stored when the earlier invocation died is gone.
+    pub async fn resume(mut self) -> Result<Self> {
+        if let Some(pending) = load_manifest(&self.bucket, &self.manifest.key).await? {
+            if !pending.complete {
+                tracing::debug!("Resuming {} at part {}", pending.key, pending.parts.len());
+                self.manifest = pending;
+            }
+        }
+        Ok(self)
+    }
+
+    pub fn key(&self) -> &str {
+        &self.manifest.key
+    }
+
+    /// Bytes committed to R2 so far, i.e. where a resumed writer continues
+    pub fn bytes_written(&self) -> u64 {
+        self.manifest.total_size()
+    }
+
+    /// Buffer `data`, storing each part as it fills
+    pub async fn write(&mut self, data: &[u8]) -> Result<()> {
+        self.buffer.extend_from_slice(data);
+        while self.buffer.len() >= self.manifest.chunk_size {
+            let rest = self.buffer.split_off(self.manifest.chunk_size);
+            let part = std::mem::replace(&mut self.buffer, rest);
+            self.put_part(part).await?;
+        }
+        Ok(())
+    }
+
+    /// Store the last partial part and mark the object complete
+    pub async fn finish(mut self) -> Result<ChunkManifest> {
+        if !self.buffer.is_empty() {
+            let part = std::mem::take(&mut self.buffer);
+            self.put_part(part).await?;
+        }
+        self.manifest.complete = true;
+        self.put_manifest().await?;
+        Ok(self.manifest)
+    }
+
+    async fn put_part(&mut self, data: Vec<u8>) -> Result<()> {
+        let index = self.manifest.parts.len();
+        let part = ChunkPart {
+            size: data.len() as u64,
+            sha256: checksum(&data),
+        };
+        // A part left over from an interrupted attempt is simply overwritten
+        self.bucket
+            .put(part_key(&self.manifest.key, index), data)
+            .execute()
+            .await?;
+        self.manifest.parts.push(part);
+        self.put_manifest().await
+    }
+
+    async fn put_manifest(&self) -> Result<()> {
+        let json = serde_json::to_string(&self.manifest)
+            .map_err(|e| Error::RustError(format!("Failed to encode manifest: {e}")))?;
+        self.bucket
+            .put(manifest_key(&self.manifest.key), json.into_bytes())
+            .execute()
+            .await?;
+        Ok(())
+    }
+}
+
+/// Reads a complete chunked object, verifying each part
+pub struct ChunkedR2Reader {
+    bucket: Bucket,
+    manifest: ChunkManifest,
+}
+
+impl ChunkedR2Reader {
+    /// Open the object at `key`; `None` when nothing was written there.
+    /// Errors if an upload to `key` is still in progress.
+    pub async fn open(bucket: Bucket, key: &str) -> Result<Option<Self>> {
+        let Some(manifest) = load_manifest(&bucket, key).await? else {
+            return Ok(None);
+        };
+        if !manifest.complete {
+            return Err(Error::RustError(format!(
+                "{key} is incomplete ({} parts written)",
+                manifest.parts.len()
+            )));
+        }
+        Ok(Some(Self { bucket, manifest }))
+    }
+
+    pub fn manifest(&self) -> &ChunkManifest {
+        &self.manifest
+    }
+
+    /// Part `index`, checked against the manifest
+    pub async fn read_part(&self, index: usize) -> Result<Vec<u8>> {
+        let key = part_key(&self.manifest.key, index);
+        let data = match self.bucket.get(&key).execute().await? {
+            Some(object) => match object.body() {
+                Some(body) => body.bytes().await?,
+                None => Vec::new(),
+            },
+            None => return Err(Error::RustError(format!("Missing part {key}"))),
+        };
+        self.manifest.verify_part(index, &data)?;
+        Ok(data)
+    }
+
+    /// The whole object, reassembled
+    pub async fn read_all(&self) -> Result<Vec<u8>> {
+        let mut data = Vec::with_capacity(self.manifest.total_size() as usize);
+        for index in 0..self.manifest.parts.len() {
+            data.extend(self.read_part(index).await?);
+        }
+        Ok(data)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn manifest_for(parts: &[&[u8]]) -> ChunkManifest {
+        let mut manifest = ChunkManifest::new("traits/pirates", 4);
+        manifest.parts = parts
+            .iter()
+            .map(|data| ChunkPart {
+                size: data.len() as u64,
+                sha256: checksum(data),
+            })
+            .collect();
+        manifest
+    }
+
+    #[test]
+    fn test_keys() {
+        assert_eq!(
+            manifest_key("traits/pirates"),
+            "traits/pirates/manifest.json"
+        );
+        assert_eq!(part_key("traits/pirates", 12), "traits/pirates/part-00012");
+    }
+
+    #[test]
+    fn test_verify_part() {
+        let manifest = manifest_for(&[b"abcd", b"ef"]);
+        assert_eq!(manifest.total_size(), 6);
+        assert!(manifest.verify_part(0, b"abcd").is_ok());
+        assert!(manifest.verify_part(1, b"ef").is_ok());
+        // wrong size, same size but different bytes, unknown part
+        assert!(manifest.verify_part(1, b"efg").is_err());
+        assert!(manifest.verify_part(1, b"eg").is_err());
+        assert!(manifest.verify_part(2, b"").is_err());
+    }
+
+    #[test]
+    fn test_manifest_round_trip() {
+        let manifest = manifest_for(&[b"abcd"]);
+        let json = serde_json::to_value(&manifest).unwrap();
+        assert_eq!(json["complete"], false);
+        assert!(json.get("content_type").is_none());
+        assert_eq!(
+            json["parts"][0]["sha256"],
+            "88d4266fd4e6338d13b845fcf289579d209c897823b9217da3e161936f031589"
+        );
+        let back: ChunkManifest = serde_json::from_value(json).unwrap();
+        assert_eq!(back, manifest);
+    }
+}
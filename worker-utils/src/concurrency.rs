@@ -0,0 +1,153 @@
+//! Bounded concurrency for `!Send` futures
+//!
+//! Workers run on a single-threaded JS event loop with no tokio, and most
+//! futures there (fetch, KV, D1) aren't `Send`. [`join_bounded`] and
+//! [`for_each_concurrent_bounded`] drive a batch of futures on the current
+//! task with at most `limit` in flight, using only `std`, so they work the
+//! same under `wasm_bindgen_futures` and any native executor.
+
+use std::future::{poll_fn, Future};
+use std::pin::Pin;
+use std::task::Poll;
+
+/// Run `futures` with at most `limit` in flight, returning their outputs in
+/// input order.
+///
+/// Futures are started lazily as earlier ones complete, so a long iterator
+/// (e.g. one request per asset) never has more than `limit` requests open.
+/// A `limit` of zero is treated as one.
+pub async fn join_bounded<I, F>(futures: I, limit: usize) -> Vec<F::Output>
+where
+    I: IntoIterator<Item = F>,
+    F: Future,
+{
+    let limit = limit.max(1);
+    let mut pending = futures.into_iter().enumerate();
+    let mut in_flight: Vec<(usize, Pin<Box<F>>)> = Vec::with_capacity(limit);
+    let mut outputs: Vec<Option<F::Output>> = Vec::new();
+
+    poll_fn(|cx| loop {
+        while in_flight.len() < limit {
+            let Some((index, future)) = pending.next() else {
+                break;
+            };
+            outputs.push(None);
+            in_flight.push((index, Box::pin(future)));
+        }
+
+        if in_flight.is_empty() {
+            return Poll::Ready(());
+        }
+
+        let before = in_flight.len();
+        in_flight.retain_mut(|(index, future)| match future.as_mut().poll(cx) {
+            Poll::Ready(output) => {
+                outputs[*index] = Some(output);
+                false
+            }
+            Poll::Pending => true,
+        });
+
+        // Nothing finished, so nothing new can start until a waker fires
+        if in_flight.len() == before {
+            return Poll::Pending;
+        }
+    })
+    .await;
+
+    outputs
+        .into_iter()
+        .map(|output| output.expect("every future runs to completion"))
+        .collect()
+}
+
+/// Call `f` for every item with at most `limit` of the returned futures in
+/// flight, waiting for all of them to finish
+pub async fn for_each_concurrent_bounded<I, F, Fut>(items: I, limit: usize, f: F)
+where
+    I: IntoIterator,
+    F: FnMut(I::Item) -> Fut,
+    Fut: Future<Output = ()>,
+{
+    join_bounded(items.into_iter().map(f), limit).await;
+}
+
+/// Poll `future` to completion on the current thread, for unit tests of
+/// futures that never wait on real IO
+#[cfg(test)]
+pub(crate) fn block_on<F: Future>(future: F) -> F::Output {
+    let mut future = std::pin::pin!(future);
+    let mut cx = std::task::Context::from_waker(std::task::Waker::noop());
+    loop {
+        if let Poll::Ready(output) = future.as_mut().poll(&mut cx) {
+            return output;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+    use std::task::Context;
+
+    /// Ready on the `n`th poll, recording the peak number in flight
+    struct Yields<'a> {
+        remaining: u32,
+        value: u32,
+        active: &'a Cell<usize>,
+        peak: &'a Cell<usize>,
+        started: bool,
+    }
+
+    impl Future for Yields<'_> {
+        type Output = u32;
+
+        fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<u32> {
+            if !self.started {
+                self.started = true;
+                self.active.set(self.active.get() + 1);
+                self.peak.set(self.peak.get().max(self.active.get()));
+            }
+            if self.remaining == 0 {
+                self.active.set(self.active.get() - 1);
+                Poll::Ready(self.value)
+            } else {
+                self.remaining -= 1;
+                cx.waker().wake_by_ref();
+                Poll::Pending
+            }
+        }
+    }
+
+    #[test]
+    fn test_join_bounded_limits_and_orders() {
+        let active = Cell::new(0);
+        let peak = Cell::new(0);
+        let futures = (0..10).map(|value| Yields {
+            remaining: 10 - value,
+            value,
+            active: &active,
+            peak: &peak,
+            started: false,
+        });
+
+        let outputs = block_on(join_bounded(futures, 3));
+        assert_eq!(outputs, (0..10).collect::<Vec<_>>());
+        assert_eq!(peak.get(), 3);
+        assert_eq!(active.get(), 0);
+    }
+
+    #[test]
+    fn test_for_each_concurrent_bounded() {
+        let total = Cell::new(0);
+        block_on(for_each_concurrent_bounded(1..=4, 0, |n| {
+            let total = &total;
+            async move { total.set(total.get() + n) }
+        }));
+        assert_eq!(total.get(), 10);
+
+        let empty: Vec<u32> = block_on(join_bounded(Vec::<std::future::Ready<u32>>::new(), 2));
+        assert!(empty.is_empty());
+    }
+}
@@ -0,0 +1,216 @@
+//! Request correlation ids for Axum workers
+//!
+//! The [`correlation_id`] middleware gives every incoming request a
+//! correlation id, taken from the caller's `x-correlation-id` header when
+//! present so a request can be followed across workers. The id is recorded on
+//! a `request` span around the handler and echoed back in the response
+//! header. Handlers extract it as a [`CorrelationId`] and pass it on to
+//! outgoing calls:
+//!
+//! ```rust,ignore
+//! use worker_utils::correlation::{correlation_id, CorrelationId};
+//!
+//! async fn refresh(id: CorrelationId, State(state): State<AppState>) -> Response {
+//!     let client = id.apply(HttpClient::new());
+//!     let listings = client.get(&url).await?;
+//!     id.envelope("api", &job).send(&state.queue).await?;
+//!     // ...
+//! }
+//!
+//! let router = Router::new()
+//!     .route("/refresh", post(refresh))
+//!     .layer(axum::middleware::from_fn(correlation_id));
+//! ```
+//!
+//! Queue messages carry the id as their [`Envelope`] `trace_id`, so consumer
+//! logs join up with the request that caused them.
+
+use std::convert::Infallible;
+use std::fmt;
+
+use axum::extract::{FromRequestParts, Request};
+use axum::http::request::Parts;
+use axum::http::{HeaderMap, HeaderName, HeaderValue};
+use axum::middleware::Next;
+use axum::response::Response;
+use http_client::HttpClient;
+use tracing::Instrument;
+
+use crate::envelope::{generate_trace_id, Envelope};
+
+/// Header carrying the correlation id, on requests and responses
+pub const CORRELATION_HEADER: &str = "x-correlation-id";
+
+/// Longest incoming id accepted; longer ones are replaced
+const MAX_ID_LEN: usize = 128;
+
+/// The correlation id of the request being handled
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct CorrelationId(String);
+
+impl CorrelationId {
+    /// A fresh id, in the same format as [`Envelope`] trace ids
+    pub fn generate() -> Self {
+        Self(generate_trace_id())
+    }
+
+    /// The caller's id from `headers`, if it's one we can safely log and echo
+    pub fn from_headers(headers: &HeaderMap) -> Option<Self> {
+        let id = headers.get(CORRELATION_HEADER)?.to_str().ok()?.trim();
+        let valid = !id.is_empty()
+            && id.len() <= MAX_ID_LEN
+            && id
+                .bytes()
+                .all(|b| b.is_ascii_alphanumeric() || matches!(b, b'-' | b'_' | b'.' | b':'));
+        valid.then(|| Self(id.to_string()))
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// Send the id with every request made by `client`
+    pub fn apply(&self, client: HttpClient) -> HttpClient {
+        client.with_header(CORRELATION_HEADER, &self.0)
+    }
+
+    /// Wrap a queue message in this request's trace
+    pub fn envelope<M>(&self, producer: impl Into<String>, message: M) -> Envelope<M> {
+        Envelope::with_trace_id(self.0.clone(), producer, message)
+    }
+}
+
+impl fmt::Display for CorrelationId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+/// Reads the id set by the [`correlation_id`] middleware; without it, the
+/// request header or a fresh id
+impl<S: Send + Sync> FromRequestParts<S> for CorrelationId {
+    type Rejection = Infallible;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        Ok(parts
+            .extensions
+            .get::<CorrelationId>()
+            .cloned()
+            .or_else(|| CorrelationId::from_headers(&parts.headers))
+            .unwrap_or_else(CorrelationId::generate))
+    }
+}
+
+/// Middleware assigning each request a [`CorrelationId`], for
+/// `axum::middleware::from_fn`
+pub async fn correlation_id(mut request: Request, next: Next) -> Response {
+    let id = CorrelationId::from_headers(request.headers()).unwrap_or_else(CorrelationId::generate);
+    let span = tracing::info_span!(
+        "request",
+        correlation_id = %id,
+        method = %request.method(),
+        path = %request.uri().path(),
+    );
+    request.extensions_mut().insert(id.clone());
+
+    let mut response = next.run(request).instrument(span).await;
+    if let Ok(value) = HeaderValue::from_str(id.as_str()) {
+        response
+            .headers_mut()
+            .insert(HeaderName::from_static(CORRELATION_HEADER), value);
+    }
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::body::{to_bytes, Body};
+    use axum::routing::get;
+    use axum::Router;
+    use tower::ServiceExt;
+
+    use super::*;
+    use crate::concurrency::block_on;
+
+    fn headers(value: &str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(CORRELATION_HEADER, HeaderValue::from_str(value).unwrap());
+        headers
+    }
+
+    #[test]
+    fn test_incoming_ids() {
+        assert_eq!(
+            CorrelationId::from_headers(&headers(" 18f3a2b1c00-4a7f2e9b ")),
+            Some(CorrelationId("18f3a2b1c00-4a7f2e9b".to_string()))
+        );
+        assert_eq!(
+            CorrelationId::from_headers(&headers("req_01:abc.def"))
+                .unwrap()
+                .as_str(),
+            "req_01:abc.def"
+        );
+
+        assert_eq!(CorrelationId::from_headers(&HeaderMap::new()), None);
+        assert_eq!(CorrelationId::from_headers(&headers("")), None);
+        assert_eq!(CorrelationId::from_headers(&headers("a b")), None);
+        assert_eq!(CorrelationId::from_headers(&headers("id\"><script>")), None);
+        assert_eq!(
+            CorrelationId::from_headers(&headers(&"a".repeat(129))),
+            None
+        );
+    }
+
+    #[test]
+    fn test_generated_id_is_envelope_trace_id() {
+        let id = CorrelationId::generate();
+        assert!(CorrelationId::from_headers(&headers(id.as_str())).is_some());
+
+        let envelope = id.envelope("api", 42u32);
+        assert_eq!(envelope.trace_id, id.as_str());
+        assert_eq!(envelope.producer, "api");
+    }
+
+    /// Send `request` through a router echoing the extracted id
+    fn roundtrip(request: axum::http::Request<Body>) -> (Option<String>, String) {
+        let router = Router::new()
+            .route("/", get(|id: CorrelationId| async move { id.to_string() }))
+            .layer(axum::middleware::from_fn(correlation_id));
+
+        block_on(async {
+            let response = router.oneshot(request).await.unwrap();
+            let header = response
+                .headers()
+                .get(CORRELATION_HEADER)
+                .map(|v| v.to_str().unwrap().to_string());
+            let body = to_bytes(response.into_body(), 1024).await.unwrap();
+            (header, String::from_utf8(body.to_vec()).unwrap())
+        })
+    }
+
+    #[test]
+    fn test_middleware_echoes_and_extracts_id() {
+        let request = axum::http::Request::get("/")
+            .header(CORRELATION_HEADER, "req_01:abc.def")
+            .body(Body::empty())
+            .unwrap();
+        let (header, extracted) = roundtrip(request);
+        assert_eq!(header.as_deref(), Some("req_01:abc.def"));
+        assert_eq!(extracted, "req_01:abc.def");
+
+        // Missing or unsafe ids are replaced, consistently on both sides
+        for request in [
+            axum::http::Request::get("/").body(Body::empty()).unwrap(),
+            axum::http::Request::get("/")
+                .header(CORRELATION_HEADER, "id\"><script>")
+                .body(Body::empty())
+                .unwrap(),
+        ] {
+            let (header, extracted) = roundtrip(request);
+            let header = header.expect("generated id is echoed");
+            assert_ne!(header, "id\"><script>");
+            assert!(CorrelationId::from_headers(&headers(&header)).is_some());
+            assert_eq!(extracted, header);
+        }
+    }
+}
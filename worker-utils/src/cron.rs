@@ -0,0 +1,564 @@
+//! Named cron tasks with last-run tracking and catch-up.
+//!
+//! [`CronRouter`] replaces a hand-written match on `event.cron()`: tasks are
+//! registered by name against a [`Schedule`], and each dispatch records the
+//! window it ran for in KV. When the platform skips a trigger (deploys,
+//! outages) the next dispatch sees the gap, logs the missed windows and,
+//! depending on the task's [`CatchUp`] policy, runs them before the current one.
+//!
+//! # Usage
+//!
+//! ```rust,ignore
+//! use worker_utils::cron::{CatchUp, CronRouter, Schedule};
+//!
+//! #[event(scheduled, respond_with_errors)]
+//! pub async fn scheduled(event: ScheduledEvent, env: Env, _ctx: ScheduleContext) {
+//!     let kv = env.kv("CRON_STATE").expect("CRON_STATE binding");
+//!     let router = CronRouter::new(&kv)
+//!         .task("sync", Schedule::parse("*/5 * * * *").unwrap(), |_run| sync_data(&env))
+//!         .task_with_catch_up(
+//!             "daily-report",
+//!             Schedule::parse("0 0 * * *").unwrap(),
+//!             CatchUp::Once,
+//!             |run| daily_report(&env, run.scheduled_ms),
+//!         );
+//!     router.dispatch(&event).await;
+//! }
+//! ```
+
+use std::fmt;
+use std::future::Future;
+use std::pin::Pin;
+
+use tracing::Instrument;
+use worker_stack::worker::kv::KvStore;
+use worker_stack::worker::ScheduledEvent;
+
+const DEFAULT_KEY_PREFIX: &str = "cron:last_run:";
+
+/// Appended to a task's last-run key for its last attempted window
+const ATTEMPT_KEY_SUFFIX: &str = ":attempt";
+
+/// How far back missed windows are looked for
+const MAX_LOOKBACK_MINUTES: u64 = 7 * 24 * 60;
+
+const MINUTE_MS: u64 = 60_000;
+
+type TaskResult = Result<(), Box<dyn std::error::Error>>;
+type TaskFuture<'a> = Pin<Box<dyn Future<Output = TaskResult> + 'a>>;
+type TaskHandler<'a> = Box<dyn Fn(CronRun) -> TaskFuture<'a> + 'a>;
+
+// ─── Schedule ────────────────────────────────────────────────────────────────
+
+/// A parsed five-field cron expression (`minute hour day-of-month month day-of-week`)
+///
+/// Supports `*`, numbers, ranges (`1-5`), steps (`*/15`, `0-30/10`), lists
+/// and `JAN`-`DEC` / `SUN`-`SAT` names. As in standard cron, when both
+/// day-of-month and day-of-week are restricted a day matching either fires.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Schedule {
+    source: String,
+    minutes: u64,
+    hours: u32,
+    days_of_month: u32,
+    months: u16,
+    days_of_week: u8,
+    dom_restricted: bool,
+    dow_restricted: bool,
+}
+
+/// Why a cron expression couldn't be parsed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CronParseError {
+    pub expression: String,
+    pub reason: String,
+}
+
+impl fmt::Display for CronParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Invalid cron expression '{}': {}",
+            self.expression, self.reason
+        )
+    }
+}
+
+impl std::error::Error for CronParseError {}
+
+const MONTH_NAMES: [&str; 12] = [
+    "JAN", "FEB", "MAR", "APR", "MAY", "JUN", "JUL", "AUG", "SEP", "OCT", "NOV", "DEC",
+];
+const DAY_NAMES: [&str; 7] = ["SUN", "MON", "TUE", "WED", "THU", "FRI", "SAT"];
+
+impl Schedule {
+    pub fn parse(expression: &str) -> Result<Self, CronParseError> {
+        let err = |reason: String| CronParseError {
+            expression: expression.to_string(),
+            reason,
+        };
+
+        let fields: Vec<&str> = expression.split_whitespace().collect();
+        let [minute, hour, dom, month, dow] = fields.as_slice() else {
+            return Err(err(format!("expected 5 fields, got {}", fields.len())));
+        };
+
+        let minutes = parse_field(minute, 0, 59, &[]).map_err(&err)?;
+        let hours = parse_field(hour, 0, 23, &[]).map_err(&err)?;
+        let days_of_month = parse_field(dom, 1, 31, &[]).map_err(&err)?;
+        let months = parse_field(month, 1, 12, &MONTH_NAMES).map_err(&err)?;
+        // 7 is an alias for Sunday
+        let dow_bits = parse_field(dow, 0, 7, &DAY_NAMES).map_err(&err)?;
+        let days_of_week = ((dow_bits | (dow_bits >> 7)) & 0x7f) as u8;
+
+        Ok(Self {
+            source: fields.join(" "),
+            minutes,
+            hours: hours as u32,
+            days_of_month: days_of_month as u32,
+            months: months as u16,
+            days_of_week,
+            dom_restricted: !dom.starts_with('*'),
+            dow_restricted: !dow.starts_with('*'),
+        })
+    }
+
+    /// The expression, whitespace-normalised, as matched against `event.cron()`
+    pub fn as_str(&self) -> &str {
+        &self.source
+    }
+
+    /// Whether the schedule fires in the minute containing `unix_ms`
+    pub fn matches(&self, unix_ms: u64) -> bool {
+        let minutes_since_epoch = unix_ms / MINUTE_MS;
+        let minute = minutes_since_epoch % 60;
+        let hour = (minutes_since_epoch / 60) % 24;
+        let days = (minutes_since_epoch / (24 * 60)) as i64;
+        let (_, month, day) = civil_from_days(days);
+        // 1970-01-01 was a Thursday
+        let weekday = (days + 4).rem_euclid(7) as u32;
+
+        let dom_match = self.days_of_month & (1 << day) != 0;
+        let dow_match = self.days_of_week & (1 << weekday) != 0;
+        let day_match = match (self.dom_restricted, self.dow_restricted) {
+            (true, true) => dom_match || dow_match,
+            (true, false) => dom_match,
+            (false, true) => dow_match,
+            (false, false) => true,
+        };
+
+        self.minutes & (1 << minute) != 0
+            && self.hours & (1 << hour) != 0
+            && self.months & (1 << month) != 0
+            && day_match
+    }
+
+    /// Fire times strictly between `after_ms` and `before_ms`, oldest first
+    ///
+    /// Only the week before `before_ms` is searched.
+    pub fn occurrences_between(&self, after_ms: u64, before_ms: u64) -> Vec<u64> {
+        let first = (after_ms / MINUTE_MS + 1)
+            .max((before_ms / MINUTE_MS).saturating_sub(MAX_LOOKBACK_MINUTES));
+        let end = before_ms.div_ceil(MINUTE_MS);
+
+        (first..end)
+            .map(|minute| minute * MINUTE_MS)
+            .filter(|&ms| ms < before_ms && self.matches(ms))
+            .collect()
+    }
+}
+
+impl fmt::Display for Schedule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.source)
+    }
+}
+
+/// Parse one field into a bitmask of allowed values
+fn parse_field(field: &str, min: u32, max: u32, names: &[&str]) -> Result<u64, String> {
+    let value = |s: &str| -> Result<u32, String> {
+        let upper = s.to_ascii_uppercase();
+        if let Some(index) = names.iter().position(|name| *name == upper) {
+            // Month names start at 1, day names at 0
+            return Ok(index as u32 + min);
+        }
+        let n: u32 = s.parse().map_err(|_| format!("invalid value '{s}'"))?;
+        if n < min || n > max {
+            return Err(format!("{n} is outside {min}-{max}"));
+        }
+        Ok(n)
+    };
+
+    let mut mask = 0u64;
+    for part in field.split(',') {
+        let (range, step) = match part.split_once('/') {
+            Some((range, step)) => {
+                let step: u32 = step
+                    .parse()
+                    .ok()
+                    .filter(|s| *s > 0)
+                    .ok_or_else(|| format!("invalid step in '{part}'"))?;
+                (range, step)
+            }
+            None => (part, 1),
+        };
+
+        let (start, end) = match range {
+            "*" => (min, max),
+            _ => match range.split_once('-') {
+                Some((start, end)) => (value(start)?, value(end)?),
+                // `5/15` means every 15 starting at 5
+                None if step > 1 => (value(range)?, max),
+                None => {
+                    let n = value(range)?;
+                    (n, n)
+                }
+            },
+        };
+        if start > end {
+            return Err(format!("empty range '{range}'"));
+        }
+
+        for n in (start..=end).step_by(step as usize) {
+            mask |= 1 << n;
+        }
+    }
+    Ok(mask)
+}
+
+/// (year, month, day) for days since 1970-01-01
+fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let day_of_era = z - era * 146_097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let mp = (5 * day_of_year + 2) / 153;
+    let day = (day_of_year - (153 * mp + 2) / 5 + 1) as u32;
+    let month = (if mp < 10 { mp + 3 } else { mp - 9 }) as u32;
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+    (year, month, day)
+}
+
+// ─── Router ──────────────────────────────────────────────────────────────────
+
+/// What to do with windows missed since a task's last run
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CatchUp {
+    /// Log them and only run the current window
+    #[default]
+    Skip,
+    /// Run once for the most recent missed window
+    Once,
+    /// Run each missed window, oldest first, up to `max` of the most recent
+    Each { max: usize },
+}
+
+impl CatchUp {
+    fn select(self, missed: &[u64]) -> &[u64] {
+        let keep = match self {
+            CatchUp::Skip => 0,
+            CatchUp::Once => 1,
+            CatchUp::Each { max } => max,
+        };
+        &missed[missed.len().saturating_sub(keep)..]
+    }
+}
+
+/// The window a task handler is being run for
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CronRun {
+    /// Scheduled fire time in unix milliseconds
+    pub scheduled_ms: u64,
+    /// `true` when running a missed window rather than the current trigger
+    pub catch_up: bool,
+}
+
+struct CronTask<'a> {
+    name: String,
+    schedule: Schedule,
+    catch_up: CatchUp,
+    handler: TaskHandler<'a>,
+}
+
+/// A task's run state as persisted in KV
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+struct RunState {
+    /// Latest window the task ran for, whatever the outcome
+    last_attempt: Option<u64>,
+    /// Latest window the task completed
+    last_success: Option<u64>,
+}
+
+impl RunState {
+    /// Windows of `schedule` before `scheduled_ms` that were never attempted
+    fn missed(&self, schedule: &Schedule, scheduled_ms: u64) -> Vec<u64> {
+        self.last_attempt
+            .map(|last| schedule.occurrences_between(last, scheduled_ms))
+            .unwrap_or_default()
+    }
+
+    fn record(&mut self, scheduled_ms: u64, succeeded: bool) {
+        self.last_attempt = self.last_attempt.max(Some(scheduled_ms));
+        if succeeded {
+            self.last_success = self.last_success.max(Some(scheduled_ms));
+        }
+    }
+
+    /// Whether the latest attempt failed
+    fn failing(&self) -> bool {
+        self.last_attempt.is_some() && self.last_success != self.last_attempt
+    }
+}
+
+/// Dispatches scheduled events to named tasks
+///
+/// Tasks run sequentially in registration order. The last attempted and the
+/// last successful window are recorded separately: missed windows are
+/// counted from the last attempt, so a failed window is logged on the next
+/// dispatch rather than retried on every one.
+pub struct CronRouter<'a> {
+    kv: &'a KvStore,
+    key_prefix: String,
+    tasks: Vec<CronTask<'a>>,
+}
+
+impl<'a> CronRouter<'a> {
+    pub fn new(kv: &'a KvStore) -> Self {
+        Self {
+            kv,
+            key_prefix: DEFAULT_KEY_PREFIX.to_string(),
+            tasks: Vec::new(),
+        }
+    }
+
+    /// Prefix for run-state keys, for sharing a namespace between workers
+    pub fn with_key_prefix(mut self, prefix: &str) -> Self {
+        self.key_prefix = prefix.to_string();
+        self
+    }
+
+    /// Register a task that skips missed windows
+    pub fn task<F, Fut>(self, name: &str, schedule: Schedule, handler: F) -> Self
+    where
+        F: Fn(CronRun) -> Fut + 'a,
+        Fut: Future<Output = TaskResult> + 'a,
+    {
+        self.task_with_catch_up(name, schedule, CatchUp::Skip, handler)
+    }
+
+    /// Register a task that runs missed windows per `catch_up`
+    pub fn task_with_catch_up<F, Fut>(
+        mut self,
+        name: &str,
+        schedule: Schedule,
+        catch_up: CatchUp,
+        handler: F,
+    ) -> Self
+    where
+        F: Fn(CronRun) -> Fut + 'a,
+        Fut: Future<Output = TaskResult> + 'a,
+    {
+        self.tasks.push(CronTask {
+            name: name.to_string(),
+            schedule,
+            catch_up,
+            handler: Box::new(move |run| -> TaskFuture<'a> { Box::pin(handler(run)) }),
+        });
+        self
+    }
+
+    /// Run every task registered for the event's cron expression
+    ///
+    /// Errors are logged rather than returned, as scheduled handlers can't
+    /// report them to the runtime.
+    pub async fn dispatch(&self, event: &ScheduledEvent) {
+        let cron = event.cron();
+        self.dispatch_at(&cron, event.schedule() as u64).await;
+    }
+
+    /// Run every task registered for `cron` as if triggered at `scheduled_ms`
+    pub async fn dispatch_at(&self, cron: &str, scheduled_ms: u64) {
+        let cron = cron.split_whitespace().collect::<Vec<_>>().join(" ");
+        let mut matched = false;
+
+        for task in self.tasks.iter().filter(|t| t.schedule.as_str() == cron) {
+            matched = true;
+            let span = tracing::info_span!("cron_task", task = %task.name, cron = %cron);
+            self.run_task(task, scheduled_ms).instrument(span).await;
+        }
+
+        if !matched {
+            tracing::warn!(cron = %cron, "No task registered for cron schedule");
+        }
+    }
+
+    async fn run_task(&self, task: &CronTask<'a>, scheduled_ms: u64) {
+        let success_key = format!("{}{}", self.key_prefix, task.name);
+        let attempt_key = format!("{success_key}{ATTEMPT_KEY_SUFFIX}");
+        let last_success = self.read_ms(&success_key).await;
+        let mut state = RunState {
+            // Stores written before attempts were tracked only have successes
+            last_attempt: self.read_ms(&attempt_key).await.or(last_success),
+            last_success,
+        };
+
+        if state.failing() {
+            tracing::warn!(
+                last_attempt = ?state.last_attempt,
+                last_success = ?state.last_success,
+                "Last cron run failed"
+            );
+        }
+
+        let missed = state.missed(&task.schedule, scheduled_ms);
+        if !missed.is_empty() {
+            tracing::warn!(
+                missed = missed.len(),
+                last_attempt = ?state.last_attempt,
+                catch_up = ?task.catch_up,
+                "Missed cron windows since last run"
+            );
+        }
+        for &window in task.catch_up.select(&missed) {
+            let succeeded = self.execute(task, window, true).await;
+            state.record(window, succeeded);
+        }
+
+        let succeeded = self.execute(task, scheduled_ms, false).await;
+        state.record(scheduled_ms, succeeded);
+
+        if let Some(last_attempt) = state.last_attempt {
+            self.write_ms(&attempt_key, last_attempt).await;
+        }
+        if state.last_success != last_success {
+            if let Some(last_success) = state.last_success {
+                self.write_ms(&success_key, last_success).await;
+            }
+        }
+    }
+
+    async fn read_ms(&self, key: &str) -> Option<u64> {
+        match self.kv.get(key).text().await {
+            Ok(value) => value.and_then(|v| v.parse::<u64>().ok()),
+            Err(e) => {
+                tracing::warn!(key, error = %e, "Failed to read cron run state");
+                None
+            }
+        }
+    }
+
+    async fn write_ms(&self, key: &str, ms: u64) {
+        let result = match self.kv.put(key, ms.to_string()) {
+            Ok(put) => put.execute().await,
+            Err(e) => Err(e),
+        };
+        if let Err(e) = result {
+            tracing::warn!(key, error = %e, "Failed to record cron run state");
+        }
+    }
+
+    async fn execute(&self, task: &CronTask<'a>, scheduled_ms: u64, catch_up: bool) -> bool {
+        let start = crate::timer_start!();
+        let result = (task.handler)(CronRun {
+            scheduled_ms,
+            catch_up,
+        })
+        .await;
+        let elapsed_ms = crate::timer_elapsed_ms!(start);
+
+        match result {
+            Ok(()) => {
+                tracing::info!(scheduled_ms, catch_up, elapsed_ms, "Cron task completed");
+                true
+            }
+            Err(e) => {
+                tracing::error!(scheduled_ms, catch_up, elapsed_ms, error = %e, "Cron task failed");
+                false
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 2024-04-22 09:41:00 UTC, a Monday
+    const MONDAY_0941: u64 = 1_713_778_860_000;
+
+    #[test]
+    fn test_parse() {
+        let schedule = Schedule::parse("*/15  9-17 * * MON-FRI").unwrap();
+        assert_eq!(schedule.as_str(), "*/15 9-17 * * MON-FRI");
+        assert_eq!(schedule.minutes, 1 | 1 << 15 | 1 << 30 | 1 << 45);
+
+        assert!(Schedule::parse("* * * *").is_err());
+        assert!(Schedule::parse("60 * * * *").is_err());
+        assert!(Schedule::parse("*/0 * * * *").is_err());
+        assert!(Schedule::parse("0 0 * FOO *").is_err());
+    }
+
+    #[test]
+    fn test_matches() {
+        let weekdays = Schedule::parse("41 9 * * 1-5").unwrap();
+        assert!(weekdays.matches(MONDAY_0941));
+        assert!(weekdays.matches(MONDAY_0941 + 59_999));
+        assert!(!weekdays.matches(MONDAY_0941 + MINUTE_MS));
+
+        let sunday = Schedule::parse("41 9 * * 7").unwrap();
+        assert!(!sunday.matches(MONDAY_0941));
+        assert!(sunday.matches(MONDAY_0941 - 24 * 60 * MINUTE_MS));
+
+        // Either restricted day field matches
+        let either = Schedule::parse("41 9 1 APR MON").unwrap();
+        assert!(either.matches(MONDAY_0941));
+        assert!(!Schedule::parse("41 9 1 MAY *")
+            .unwrap()
+            .matches(MONDAY_0941));
+    }
+
+    #[test]
+    fn test_occurrences_between() {
+        let every_5 = Schedule::parse("*/5 * * * *").unwrap();
+        let at_0940 = MONDAY_0941 - MINUTE_MS;
+
+        // No gap: previous run was the previous window
+        assert!(every_5
+            .occurrences_between(at_0940 - 5 * MINUTE_MS, at_0940)
+            .is_empty());
+
+        let missed = every_5.occurrences_between(at_0940 - 20 * MINUTE_MS, at_0940);
+        assert_eq!(
+            missed,
+            vec![
+                at_0940 - 15 * MINUTE_MS,
+                at_0940 - 10 * MINUTE_MS,
+                at_0940 - 5 * MINUTE_MS,
+            ]
+        );
+
+        assert!(CatchUp::Skip.select(&missed).is_empty());
+        assert_eq!(CatchUp::Once.select(&missed), &missed[2..]);
+        assert_eq!(CatchUp::Each { max: 10 }.select(&missed), &missed[..]);
+
+        // A failed window counts as attempted, so it isn't missed next time
+        let mut state = RunState::default();
+        assert!(state.missed(&every_5, at_0940).is_empty());
+        state.record(at_0940 - 5 * MINUTE_MS, true);
+        state.record(at_0940, false);
+        assert!(state.failing());
+        assert_eq!(state.last_success, Some(at_0940 - 5 * MINUTE_MS));
+        assert!(state.missed(&every_5, at_0940 + 5 * MINUTE_MS).is_empty());
+        state.record(at_0940 + 5 * MINUTE_MS, true);
+        assert!(!state.failing());
+
+        // Lookback is bounded
+        let minutely = Schedule::parse("* * * * *").unwrap();
+        assert_eq!(
+            minutely.occurrences_between(0, MONDAY_0941).len() as u64,
+            MAX_LOOKBACK_MINUTES
+        );
+    }
+}
@@ -0,0 +1,228 @@
+//! Queue message envelope with tracing context
+//!
+//! Wrapping queue messages in an [`Envelope`] carries a `trace_id` from the
+//! worker that started a piece of work through every queue hop after it, so
+//! logs from different workers can be joined on one id.
+//!
+//! Producer side:
+//!
+//! ```rust,ignore
+//! // New unit of work: fresh trace id
+//! let trace_id = send_enveloped(&queue, "indexer", &job).await?;
+//!
+//! // Follow-up work: keep the incoming trace id
+//! envelope.child("indexer", next_job).send(&queue).await?;
+//! ```
+//!
+//! Consumer side, every log line inside `process` carries the trace fields:
+//!
+//! ```rust,ignore
+//! for message in batch.messages()? {
+//!     let envelope: Envelope<Job> = message.body().clone();
+//!     envelope.process("notifier", |job| handle(job)).await?;
+//!     message.ack();
+//! }
+//! ```
+
+use serde::{Deserialize, Serialize};
+use std::future::Future;
+use tracing::{Instrument, Span};
+use worker_stack::worker::{Queue, Result};
+
+fn default_schema_version() -> u32 {
+    1
+}
+
+/// A queue message with the context needed to trace it across workers
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct Envelope<M> {
+    pub message: M,
+    /// Shared by every message descended from the same unit of work
+    pub trace_id: String,
+    /// Milliseconds since epoch when the message was enqueued
+    pub produced_at: u64,
+    /// Name of the producing worker
+    pub producer: String,
+    /// Version of the `message` schema, for consumers that must handle
+    /// in-flight messages across deploys
+    #[serde(default = "default_schema_version")]
+    pub schema_version: u32,
+}
+
+impl<M> Envelope<M> {
+    /// Wrap a message that starts a new trace
+    pub fn new(producer: impl Into<String>, message: M) -> Self {
+        Self::with_trace_id(generate_trace_id(), producer, message)
+    }
+
+    /// Wrap a message that continues an existing trace
+    pub fn with_trace_id(
+        trace_id: impl Into<String>,
+        producer: impl Into<String>,
+        message: M,
+    ) -> Self {
+        Self {
+            message,
+            trace_id: trace_id.into(),
+            produced_at: now_ms(),
+            producer: producer.into(),
+            schema_version: default_schema_version(),
+        }
+    }
+
+    /// Wrap a follow-up message in this envelope's trace
+    pub fn child<N>(&self, producer: impl Into<String>, message: N) -> Envelope<N> {
+        Envelope::with_trace_id(self.trace_id.clone(), producer, message)
+    }
+
+    pub fn schema_version(mut self, version: u32) -> Self {
+        self.schema_version = version;
+        self
+    }
+
+    /// Milliseconds between enqueue and now
+    pub fn queue_latency_ms(&self) -> u64 {
+        now_ms().saturating_sub(self.produced_at)
+    }
+
+    /// Span for handling this message in `consumer`
+    pub fn span(&self, consumer: &str) -> Span {
+        tracing::info_span!(
+            "queue_message",
+            trace_id = %self.trace_id,
+            producer = %self.producer,
+            consumer = %consumer,
+            schema_version = self.schema_version,
+            queue_latency_ms = self.queue_latency_ms(),
+        )
+    }
+
+    /// Run `handler` on the message inside this envelope's span
+    pub async fn process<F, Fut, T>(self, consumer: &str, handler: F) -> T
+    where
+        F: FnOnce(M) -> Fut,
+        Fut: Future<Output = T>,
+    {
+        let span = self.span(consumer);
+        async move {
+            tracing::debug!("Processing queued message");
+            handler(self.message).await
+        }
+        .instrument(span)
+        .await
+    }
+}
+
+impl<M: Serialize + Clone> Envelope<M> {
+    /// Send this envelope with [`send_to_queue`](crate::send_to_queue)
+    pub async fn send(&self, queue: &Queue) -> Result<()> {
+        tracing::debug!(
+            trace_id = %self.trace_id,
+            producer = %self.producer,
+            "Enqueueing message"
+        );
+        crate::send_to_queue(queue, self).await
+    }
+}
+
+/// Send `message` in a new trace, returning the trace id for logging
+///
+/// The envelope borrows `message`, so it needn't be `Clone`.
+pub async fn send_enveloped<M>(queue: &Queue, producer: &str, message: &M) -> Result<String>
+where
+    M: Serialize,
+{
+    let envelope = Envelope::new(producer, message);
+    envelope.send(queue).await?;
+    Ok(envelope.trace_id)
+}
+
+/// Generate a trace id
+///
+/// Format: `{timestamp_hex}-{random_hex}` e.g. `18f3a2b1c00-4a7f2e9b`
+pub fn generate_trace_id() -> String {
+    format!("{:x}-{:08x}", now_ms(), random_u32())
+}
+
+#[cfg(target_arch = "wasm32")]
+fn now_ms() -> u64 {
+    js_sys::Date::now() as u64
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn now_ms() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or_default()
+}
+
+#[cfg(target_arch = "wasm32")]
+fn random_u32() -> u32 {
+    (js_sys::Math::random() * u32::MAX as f64) as u32
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn random_u32() -> u32 {
+    use std::hash::{BuildHasher, Hasher};
+    let mut hasher = std::collections::hash_map::RandomState::new().build_hasher();
+    hasher.write_u64(now_ms());
+    hasher.finish() as u32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+    struct Job {
+        policy_id: String,
+    }
+
+    fn job() -> Job {
+        Job {
+            policy_id: "b3dab69f".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_envelope_roundtrip() {
+        let envelope = Envelope::new("indexer", job()).schema_version(2);
+        let json = serde_json::to_string(&envelope).unwrap();
+        let parsed: Envelope<Job> = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed, envelope);
+        assert_eq!(parsed.schema_version, 2);
+    }
+
+    #[test]
+    fn test_schema_version_defaults_to_one() {
+        let parsed: Envelope<Job> = serde_json::from_value(serde_json::json!({
+            "message": { "policy_id": "abc" },
+            "trace_id": "18f3a2b1c00-4a7f2e9b",
+            "produced_at": 1,
+            "producer": "indexer"
+        }))
+        .unwrap();
+        assert_eq!(parsed.schema_version, 1);
+        assert!(parsed.queue_latency_ms() > 0);
+    }
+
+    #[test]
+    fn test_child_keeps_trace_id() {
+        let parent = Envelope::new("indexer", job());
+        let child = parent.child("notifier", 42u32);
+        assert_eq!(child.trace_id, parent.trace_id);
+        assert_eq!(child.producer, "notifier");
+        assert_eq!(child.message, 42);
+
+        assert_ne!(Envelope::new("indexer", job()).trace_id, parent.trace_id);
+    }
+
+    #[test]
+    fn test_trace_id_format() {
+        let id = generate_trace_id();
+        let (ts, rand) = id.split_once('-').unwrap();
+        assert!(u64::from_str_radix(ts, 16).is_ok());
+        assert_eq!(rand.len(), 8);
+    }
+}
@@ -0,0 +1,466 @@
+//! Structured error and panic reporting to a webhook or queue.
+//!
+//! Errors logged with `console.error` vanish once the request ends.
+//! [`ErrorReporter`] turns them into [`ErrorReport`]s carrying the worker
+//! name, version and trace id, and ships them to a [`ReportSink`]: a Discord
+//! webhook for small deployments, or a queue drained by a central error
+//! worker.
+//!
+//! Reports are rate limited per isolate (see [`RateLimit`]) so a failing
+//! dependency produces a handful of reports rather than one per request. The
+//! limiter state is shared by every reporter in the isolate, so building a
+//! reporter per request is fine.
+//!
+//! A panic aborts the wasm instance, so a report can't be sent from the panic
+//! hook. [`ErrorReporter::install_panic_hook`] stashes the panic as JSON on
+//! the JS global object instead: that lives in the isolate rather than wasm
+//! memory, so it survives the module being reinstantiated after the abort.
+//! [`ErrorReporter::flush_panic`] ships it from the next request served by
+//! the same isolate.
+//!
+//! # Usage
+//!
+//! ```rust,ignore
+//! use worker_utils::error_report::{ErrorReporter, ReportSink};
+//!
+//! #[event(fetch)]
+//! async fn fetch(req: Request, env: Env, _ctx: Context) -> Result<Response> {
+//!     ErrorReporter::install_panic_hook("sales-bot", env!("CARGO_PKG_VERSION"));
+//!
+//!     let webhook = env.secret("ERROR_WEBHOOK_URL")?.to_string();
+//!     let reporter = ErrorReporter::new("sales-bot", ReportSink::DiscordWebhook(webhook))
+//!         .with_version(env!("CARGO_PKG_VERSION"));
+//!     reporter.flush_panic().await.ok();
+//!
+//!     match handle(req, &env).await {
+//!         Ok(response) => Ok(response),
+//!         Err(e) => {
+//!             let report = reporter.error(&e).with_context("route", "/sales");
+//!             reporter.send(report).await.ok();
+//!             Err(e)
+//!         }
+//!     }
+//! }
+//! ```
+
+use std::cell::RefCell;
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use std::sync::Once;
+
+use discord_client::compat::twilight::TwEmbedBuilder;
+use discord_client::{DiscordMessage, WasmDiscordClient};
+use js_sys::{JsString, Reflect};
+use serde::{Deserialize, Serialize};
+use worker_stack::worker::{self, Queue};
+
+/// Discord rejects embed descriptions longer than this
+const EMBED_DESCRIPTION_MAX: usize = 4096;
+
+const COLOR_ERROR: u32 = 0xe67e22;
+const COLOR_PANIC: u32 = 0xe74c3c;
+
+/// Property on the JS global object holding the stashed panic report
+const PENDING_PANIC_KEY: &str = "__workerUtilsPendingPanic";
+
+thread_local! {
+    static LIMITER: RefCell<Limiter> = RefCell::new(Limiter::default());
+}
+
+static PANIC_HOOK: Once = Once::new();
+
+// ─── Reports ─────────────────────────────────────────────────────────────────
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReportKind {
+    Error,
+    Panic,
+}
+
+impl fmt::Display for ReportKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ReportKind::Error => write!(f, "Error"),
+            ReportKind::Panic => write!(f, "Panic"),
+        }
+    }
+}
+
+/// One error or panic, as sent to the sink (queue messages are this, as JSON)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ErrorReport {
+    pub kind: ReportKind,
+    pub worker: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub trace_id: Option<String>,
+    pub message: String,
+    /// Source location, for panics
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub location: Option<String>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub context: BTreeMap<String, String>,
+    pub timestamp_ms: u64,
+    /// Reports dropped by the rate limiter since the previous one was sent
+    #[serde(default)]
+    pub suppressed: u32,
+}
+
+impl ErrorReport {
+    pub fn with_trace_id(mut self, trace_id: impl Into<String>) -> Self {
+        self.trace_id = Some(trace_id.into());
+        self
+    }
+
+    /// Attach a key/value pair (route, policy id, queue message id, ...)
+    pub fn with_context(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.context.insert(key.into(), value.into());
+        self
+    }
+
+    /// Identical failures share a fingerprint and are deduplicated
+    fn fingerprint(&self) -> String {
+        format!("{}:{}:{}", self.kind, self.worker, self.message)
+    }
+
+    /// Render as a Discord message with a single embed
+    pub fn to_discord_message(&self) -> DiscordMessage {
+        let mut details = Vec::new();
+        if let Some(version) = &self.version {
+            details.push(format!("**Version:** {version}"));
+        }
+        if let Some(trace_id) = &self.trace_id {
+            details.push(format!("**Trace:** `{trace_id}`"));
+        }
+        if let Some(location) = &self.location {
+            details.push(format!("**Location:** `{location}`"));
+        }
+        for (key, value) in &self.context {
+            details.push(format!("**{key}:** {value}"));
+        }
+        if self.suppressed > 0 {
+            details.push(format!("_{} similar reports suppressed_", self.suppressed));
+        }
+
+        let header = details.join("\n");
+        // Leave room for the header, code fence and separators
+        let budget = EMBED_DESCRIPTION_MAX.saturating_sub(header.chars().count() + 16);
+        let message = truncate(&self.message, budget);
+        let description = if header.is_empty() {
+            format!("```\n{message}\n```")
+        } else {
+            format!("{header}\n```\n{message}\n```")
+        };
+
+        let color = match self.kind {
+            ReportKind::Error => COLOR_ERROR,
+            ReportKind::Panic => COLOR_PANIC,
+        };
+        let embed = TwEmbedBuilder::new()
+            .title(format!("{} in {}", self.kind, self.worker))
+            .description(description)
+            .color(color)
+            .build();
+
+        DiscordMessage {
+            content: None,
+            embeds: Some(vec![embed]),
+            attachments: None,
+            components: None,
+        }
+    }
+}
+
+fn truncate(text: &str, max_chars: usize) -> String {
+    if text.chars().count() <= max_chars {
+        return text.to_string();
+    }
+    let mut truncated: String = text.chars().take(max_chars.saturating_sub(1)).collect();
+    truncated.push('…');
+    truncated
+}
+
+// ─── Rate limiting ───────────────────────────────────────────────────────────
+
+/// Limits on how many reports leave the isolate
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RateLimit {
+    /// Reports sent per window, across all fingerprints
+    pub max_reports: u32,
+    pub window_ms: u64,
+    /// An identical report (same kind, worker and message) is sent at most
+    /// once per this interval
+    pub dedupe_ms: u64,
+}
+
+impl Default for RateLimit {
+    /// 10 reports a minute, identical ones at most every 5 minutes
+    fn default() -> Self {
+        Self {
+            max_reports: 10,
+            window_ms: 60_000,
+            dedupe_ms: 5 * 60_000,
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+struct Limiter {
+    window_start: u64,
+    sent_in_window: u32,
+    last_sent: HashMap<String, u64>,
+    suppressed: u32,
+}
+
+impl Limiter {
+    /// Whether a report may be sent now; on `Some`, the count of reports
+    /// suppressed since the last one sent
+    fn admit(&mut self, limit: &RateLimit, fingerprint: &str, now: u64) -> Option<u32> {
+        if now.saturating_sub(self.window_start) >= limit.window_ms {
+            self.window_start = now;
+            self.sent_in_window = 0;
+            self.last_sent
+                .retain(|_, sent| now.saturating_sub(*sent) < limit.dedupe_ms);
+        }
+
+        let duplicate = self
+            .last_sent
+            .get(fingerprint)
+            .is_some_and(|sent| now.saturating_sub(*sent) < limit.dedupe_ms);
+        if duplicate || self.sent_in_window >= limit.max_reports {
+            self.suppressed += 1;
+            return None;
+        }
+
+        self.sent_in_window += 1;
+        self.last_sent.insert(fingerprint.to_string(), now);
+        Some(std::mem::take(&mut self.suppressed))
+    }
+}
+
+// ─── Reporter ────────────────────────────────────────────────────────────────
+
+/// Where reports are delivered
+pub enum ReportSink {
+    /// Discord webhook URL (`https://discord.com/api/webhooks/{id}/{token}`)
+    DiscordWebhook(String),
+    /// Queue of JSON [`ErrorReport`]s
+    Queue(Queue),
+}
+
+pub struct ErrorReporter {
+    worker: String,
+    version: Option<String>,
+    sink: ReportSink,
+    rate_limit: RateLimit,
+}
+
+impl ErrorReporter {
+    pub fn new(worker: impl Into<String>, sink: ReportSink) -> Self {
+        Self {
+            worker: worker.into(),
+            version: None,
+            sink,
+            rate_limit: RateLimit::default(),
+        }
+    }
+
+    pub fn with_version(mut self, version: impl Into<String>) -> Self {
+        self.version = Some(version.into());
+        self
+    }
+
+    pub fn with_rate_limit(mut self, rate_limit: RateLimit) -> Self {
+        self.rate_limit = rate_limit;
+        self
+    }
+
+    /// Start a report for an error (`worker::Error` or anything `Display`)
+    pub fn error(&self, error: &dyn fmt::Display) -> ErrorReport {
+        ErrorReport {
+            kind: ReportKind::Error,
+            worker: self.worker.clone(),
+            version: self.version.clone(),
+            trace_id: None,
+            message: error.to_string(),
+            location: None,
+            context: BTreeMap::new(),
+            timestamp_ms: now_ms(),
+            suppressed: 0,
+        }
+    }
+
+    /// Send a report unless the rate limiter drops it
+    ///
+    /// Returns whether the report was sent.
+    pub async fn send(&self, mut report: ErrorReport) -> worker::Result<bool> {
+        let fingerprint = report.fingerprint();
+        let admitted = LIMITER.with(|limiter| {
+            limiter
+                .borrow_mut()
+                .admit(&self.rate_limit, &fingerprint, report.timestamp_ms)
+        });
+        let Some(suppressed) = admitted else {
+            tracing::debug!("Error report rate limited: {}", report.message);
+            return Ok(false);
+        };
+        report.suppressed = suppressed;
+
+        match &self.sink {
+            ReportSink::Queue(queue) => crate::send_to_queue(queue, &report).await?,
+            ReportSink::DiscordWebhook(url) => post_webhook(url, &report).await?,
+        }
+        Ok(true)
+    }
+
+    /// Send the panic stashed by [`install_panic_hook`](Self::install_panic_hook),
+    /// if any. Call early in each request.
+    pub async fn flush_panic(&self) -> worker::Result<bool> {
+        let Some(report) = take_pending_panic() else {
+            return Ok(false);
+        };
+        self.send(report).await
+    }
+
+    /// Install a panic hook that logs the panic and stashes it as a report
+    ///
+    /// Chains the console panic hook when the `console_error_panic_hook`
+    /// feature is on. Only the first call in an isolate installs the hook.
+    pub fn install_panic_hook(worker: &str, version: &str) {
+        let worker = worker.to_string();
+        let version = version.to_string();
+        PANIC_HOOK.call_once(move || {
+            std::panic::set_hook(Box::new(move |info| {
+                #[cfg(feature = "console_error_panic_hook")]
+                console_error_panic_hook::hook(info);
+
+                let message = match info.payload().downcast_ref::<&str>() {
+                    Some(s) => s.to_string(),
+                    None => info
+                        .payload()
+                        .downcast_ref::<String>()
+                        .cloned()
+                        .unwrap_or_else(|| "panic".to_string()),
+                };
+                let location = info
+                    .location()
+                    .map(|l| format!("{}:{}:{}", l.file(), l.line(), l.column()));
+                tracing::error!("{worker} panicked: {message}");
+
+                let report = ErrorReport {
+                    kind: ReportKind::Panic,
+                    worker: worker.clone(),
+                    version: Some(version.clone()),
+                    trace_id: None,
+                    message,
+                    location,
+                    context: BTreeMap::new(),
+                    timestamp_ms: now_ms(),
+                    suppressed: 0,
+                };
+                stash_pending_panic(&report);
+            }));
+        });
+    }
+}
+
+async fn post_webhook(url: &str, report: &ErrorReport) -> worker::Result<()> {
+    WasmDiscordClient::execute_webhook(url, &report.to_discord_message())
+        .await
+        .map_err(|e| worker::Error::RustError(format!("Error webhook failed: {e}")))
+}
+
+/// Write the report to the JS global object, which outlives the wasm instance
+fn stash_pending_panic(report: &ErrorReport) {
+    let Ok(json) = serde_json::to_string(report) else {
+        return;
+    };
+    let key = JsString::from(PENDING_PANIC_KEY);
+    let _ = Reflect::set(&js_sys::global(), &key, &JsString::from(json));
+}
+
+fn take_pending_panic() -> Option<ErrorReport> {
+    let global = js_sys::global();
+    let key = JsString::from(PENDING_PANIC_KEY);
+    let json = Reflect::get(&global, &key).ok()?.as_string()?;
+    let _ = Reflect::delete_property(&global, &key);
+    serde_json::from_str(&json).ok()
+}
+
+fn now_ms() -> u64 {
+    js_sys::Date::now() as u64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn report(message: &str) -> ErrorReport {
+        ErrorReport {
+            kind: ReportKind::Error,
+            worker: "sales-bot".to_string(),
+            version: Some("1.2.0".to_string()),
+            trace_id: None,
+            message: message.to_string(),
+            location: None,
+            context: BTreeMap::new(),
+            timestamp_ms: 0,
+            suppressed: 0,
+        }
+    }
+
+    #[test]
+    fn test_limiter_dedupes_and_caps() {
+        let limit = RateLimit {
+            max_reports: 2,
+            window_ms: 1_000,
+            dedupe_ms: 5_000,
+        };
+        let mut limiter = Limiter::default();
+
+        assert_eq!(limiter.admit(&limit, "a", 0), Some(0));
+        assert_eq!(limiter.admit(&limit, "a", 10), None);
+        assert_eq!(limiter.admit(&limit, "b", 20), Some(1));
+        // Window full
+        assert_eq!(limiter.admit(&limit, "c", 30), None);
+
+        // New window: "c" goes out, "a" is still deduped
+        assert_eq!(limiter.admit(&limit, "c", 1_000), Some(1));
+        assert_eq!(limiter.admit(&limit, "a", 1_010), None);
+        assert_eq!(limiter.admit(&limit, "a", 5_000), Some(1));
+    }
+
+    #[test]
+    fn test_report_to_discord_message() {
+        let report = report("KV put failed")
+            .with_trace_id("18f3a2b1c00-4a7f2e")
+            .with_context("route", "/sales");
+        let message = report.to_discord_message();
+        let embed = &message.embeds.unwrap()[0];
+
+        assert_eq!(embed.title.as_deref(), Some("Error in sales-bot"));
+        let description = embed.description.as_deref().unwrap();
+        assert!(description.contains("**Trace:** `18f3a2b1c00-4a7f2e`"));
+        assert!(description.contains("**route:** /sales"));
+        assert!(description.ends_with("```\nKV put failed\n```"));
+    }
+
+    #[test]
+    fn test_long_message_fits_embed() {
+        let report = report(&"x".repeat(10_000));
+        let message = report.to_discord_message();
+        let description = message.embeds.unwrap()[0].description.clone().unwrap();
+        assert!(description.chars().count() <= EMBED_DESCRIPTION_MAX);
+        assert!(description.contains('…'));
+    }
+
+    #[test]
+    fn test_report_serialization() {
+        let json = serde_json::to_value(report("boom").with_context("policy", "abc")).unwrap();
+        assert_eq!(json["kind"], "error");
+        assert_eq!(json["context"]["policy"], "abc");
+        assert!(json.get("trace_id").is_none());
+    }
+}
@@ -0,0 +1,286 @@
+//! Axum extractors for common API parameters
+//!
+//! Rejections are returned as `400`/`401` responses in the same
+//! `{ "success": false, "message": ... }` shape as
+//! [`WorkerResponseExt::error_json`](crate::axum::WorkerResponseExt::error_json),
+//! so handlers can take validated values directly:
+//!
+//! ```rust
+//! use worker_utils::extract::{AuthToken, Pagination, TokenSecret, ValidAssetId};
+//!
+//! struct AdminToken;
+//! impl TokenSecret for AdminToken {
+//!     const NAME: &'static str = "ADMIN_API_TOKEN";
+//! }
+//!
+//! async fn get_asset(ValidAssetId(asset_id): ValidAssetId) -> String {
+//!     asset_id.asset_name()
+//! }
+//!
+//! async fn list_holders(page: Pagination, _auth: AuthToken<AdminToken>) -> String {
+//!     format!("limit={} cursor={:?}", page.limit, page.cursor)
+//! }
+//! ```
+
+use axum::extract::{FromRequestParts, Path, Query};
+use axum::http::request::Parts;
+use axum::http::{header, StatusCode};
+use axum::response::{IntoResponse, Response};
+use cardano_assets::{AssetId, PolicyId};
+use serde::Deserialize;
+use std::marker::PhantomData;
+use worker_stack::worker::Env;
+
+use crate::axum::WorkerResponseExt;
+
+/// Rejection returned by the extractors in this module
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ApiRejection {
+    pub status: StatusCode,
+    pub message: String,
+}
+
+impl ApiRejection {
+    pub fn bad_request(message: impl Into<String>) -> Self {
+        Self {
+            status: StatusCode::BAD_REQUEST,
+            message: message.into(),
+        }
+    }
+
+    pub fn unauthorized(message: impl Into<String>) -> Self {
+        Self {
+            status: StatusCode::UNAUTHORIZED,
+            message: message.into(),
+        }
+    }
+}
+
+impl IntoResponse for ApiRejection {
+    fn into_response(self) -> Response {
+        Response::error_json(&self.message, self.status)
+    }
+}
+
+/// Parse an asset id path segment (`policy.name`, `policy:name` or concatenated)
+///
+/// Useful for routes with several path params, where [`ValidAssetId`] can't be
+/// used directly.
+pub fn parse_asset_id(value: &str) -> Result<AssetId, ApiRejection> {
+    AssetId::parse_smart(value)
+        .map_err(|e| ApiRejection::bad_request(format!("Invalid asset id '{value}': {e}")))
+}
+
+/// Parse a policy id path segment
+pub fn parse_policy_id(value: &str) -> Result<PolicyId, ApiRejection> {
+    value
+        .parse::<PolicyId>()
+        .map_err(|e| ApiRejection::bad_request(format!("Invalid policy id '{value}': {e}")))
+}
+
+/// Extract the single path parameter as a raw string
+async fn single_path_param<S: Send + Sync>(
+    parts: &mut Parts,
+    state: &S,
+) -> Result<String, ApiRejection> {
+    Path::<String>::from_request_parts(parts, state)
+        .await
+        .map(|Path(value)| value)
+        .map_err(|e| ApiRejection::bad_request(e.body_text()))
+}
+
+/// Validated [`AssetId`] from a route with a single path parameter
+/// (e.g. `/assets/{asset_id}`)
+#[derive(Debug, Clone)]
+pub struct ValidAssetId(pub AssetId);
+
+impl<S: Send + Sync> FromRequestParts<S> for ValidAssetId {
+    type Rejection = ApiRejection;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let value = single_path_param(parts, state).await?;
+        parse_asset_id(&value).map(ValidAssetId)
+    }
+}
+
+/// Validated [`PolicyId`] from a route with a single path parameter
+/// (e.g. `/policies/{policy_id}`)
+#[derive(Debug, Clone)]
+pub struct ValidPolicyId(pub PolicyId);
+
+impl<S: Send + Sync> FromRequestParts<S> for ValidPolicyId {
+    type Rejection = ApiRejection;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let value = single_path_param(parts, state).await?;
+        parse_policy_id(&value).map(ValidPolicyId)
+    }
+}
+
+#[derive(Deserialize)]
+struct PaginationQuery {
+    cursor: Option<String>,
+    limit: Option<usize>,
+}
+
+/// Cursor + limit pagination from the query string (`?cursor=...&limit=...`)
+///
+/// A missing limit uses `DEFAULT`; larger limits are capped at `MAX` rather
+/// than rejected. `limit=0` and non-numeric limits are rejected with `400`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Pagination<const DEFAULT: usize = 50, const MAX: usize = 100> {
+    pub cursor: Option<String>,
+    pub limit: usize,
+}
+
+impl<const DEFAULT: usize, const MAX: usize> Pagination<DEFAULT, MAX> {
+    /// Build from raw query values, applying the default and cap
+    pub fn from_parts(cursor: Option<String>, limit: Option<usize>) -> Result<Self, ApiRejection> {
+        let limit = match limit {
+            Some(0) => return Err(ApiRejection::bad_request("limit must be at least 1")),
+            Some(limit) => limit.min(MAX),
+            None => DEFAULT.min(MAX),
+        };
+
+        Ok(Self {
+            cursor: cursor.filter(|c| !c.is_empty()),
+            limit,
+        })
+    }
+}
+
+impl<S: Send + Sync, const DEFAULT: usize, const MAX: usize> FromRequestParts<S>
+    for Pagination<DEFAULT, MAX>
+{
+    type Rejection = ApiRejection;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let Query(query) = Query::<PaginationQuery>::from_request_parts(parts, state)
+            .await
+            .map_err(|e| ApiRejection::bad_request(e.body_text()))?;
+
+        Self::from_parts(query.cursor, query.limit)
+    }
+}
+
+/// Router state that exposes the worker [`Env`]
+pub trait HasEnv {
+    fn env(&self) -> &Env;
+}
+
+/// Names the Env secret an [`AuthToken`] is checked against
+pub trait TokenSecret {
+    const NAME: &'static str;
+}
+
+/// Requires an `Authorization: Bearer <token>` header matching the secret
+/// named by `T`
+///
+/// The secret is read from `env.secret` (falling back to `env.var` for local
+/// dev) on each request, so rotated secrets apply without a redeploy.
+pub struct AuthToken<T: TokenSecret>(PhantomData<T>);
+
+impl<S, T> FromRequestParts<S> for AuthToken<T>
+where
+    S: HasEnv + Send + Sync,
+    T: TokenSecret,
+{
+    type Rejection = ApiRejection;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let provided = bearer_token(parts)
+            .ok_or_else(|| ApiRejection::unauthorized("Missing bearer token"))?;
+
+        let env = state.env();
+        let expected = env
+            .secret(T::NAME)
+            .map(|s| s.to_string())
+            .or_else(|_| env.var(T::NAME).map(|v| v.to_string()))
+            .map_err(|_| {
+                tracing::error!("Auth secret '{}' is not configured", T::NAME);
+                ApiRejection::unauthorized("Authentication is not configured")
+            })?;
+
+        if constant_time_eq(provided.as_bytes(), expected.as_bytes()) {
+            Ok(AuthToken(PhantomData))
+        } else {
+            Err(ApiRejection::unauthorized("Invalid bearer token"))
+        }
+    }
+}
+
+fn bearer_token(parts: &Parts) -> Option<&str> {
+    let value = parts.headers.get(header::AUTHORIZATION)?.to_str().ok()?;
+    let (scheme, token) = value.split_once(' ')?;
+    let token = token.trim();
+    (scheme.eq_ignore_ascii_case("bearer") && !token.is_empty()).then_some(token)
+}
+
+/// Compare without short-circuiting on the first differing byte
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+    a.iter().zip(b).fold(0u8, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::Request;
+
+    const POLICY: &str = "b3dab69f7e6100849434fb1781e34bd12a916557f6231b8d2629b6f6";
+
+    #[test]
+    fn test_pagination_limits() {
+        let page = Pagination::<50, 100>::from_parts(None, None).unwrap();
+        assert_eq!(page.limit, 50);
+        assert_eq!(page.cursor, None);
+
+        let page = Pagination::<50, 100>::from_parts(Some("abc".into()), Some(500)).unwrap();
+        assert_eq!(page.limit, 100);
+        assert_eq!(page.cursor.as_deref(), Some("abc"));
+
+        let page = Pagination::<50, 100>::from_parts(Some(String::new()), Some(10)).unwrap();
+        assert_eq!(page.limit, 10);
+        assert_eq!(page.cursor, None);
+
+        let err = Pagination::<50, 100>::from_parts(None, Some(0)).unwrap_err();
+        assert_eq!(err.status, StatusCode::BAD_REQUEST);
+    }
+
+    #[test]
+    fn test_parse_ids() {
+        let asset = parse_asset_id(&format!("{POLICY}.4d7942617365")).unwrap();
+        assert_eq!(asset.policy_id(), POLICY);
+        assert_eq!(asset.asset_name(), "MyBase");
+
+        assert!(parse_policy_id(POLICY).is_ok());
+
+        let err = parse_asset_id("not-an-asset").unwrap_err();
+        assert_eq!(err.status, StatusCode::BAD_REQUEST);
+        assert!(err.message.contains("not-an-asset"));
+        assert!(parse_policy_id("abc").is_err());
+    }
+
+    #[test]
+    fn test_bearer_token() {
+        let parts = |value: &str| {
+            Request::builder()
+                .header(header::AUTHORIZATION, value)
+                .body(())
+                .unwrap()
+                .into_parts()
+                .0
+        };
+
+        assert_eq!(bearer_token(&parts("Bearer s3cret")), Some("s3cret"));
+        assert_eq!(bearer_token(&parts("bearer s3cret ")), Some("s3cret"));
+        assert_eq!(bearer_token(&parts("Basic s3cret")), None);
+        assert_eq!(bearer_token(&parts("Bearer ")), None);
+
+        assert!(constant_time_eq(b"s3cret", b"s3cret"));
+        assert!(!constant_time_eq(b"s3cret", b"s3creT"));
+        assert!(!constant_time_eq(b"s3cret", b"s3cret!"));
+    }
+}
@@ -0,0 +1,183 @@
+//! Work that should finish after the response is sent
+//!
+//! A Worker invocation ends when its handler returns, dropping any buffered
+//! metrics or batched queue sends that haven't been awaited. Register those
+//! flushes on a [`DeferredFlush`] while handling the request, then hand it
+//! to the runtime with [`wait_until`](DeferredFlush::wait_until) just before
+//! returning; the runtime keeps the invocation alive until every task is
+//! done, without delaying the response.
+//!
+//! ```rust,ignore
+//! use worker_utils::flush::DeferredFlush;
+//!
+//! #[event(fetch)]
+//! async fn fetch(req: Request, env: Env, ctx: Context) -> Result<Response> {
+//!     let flush = DeferredFlush::new();
+//!     let response = handle(req, &env, &flush).await;
+//!     flush.wait_until(&ctx);
+//!     response
+//! }
+//!
+//! async fn handle(req: Request, env: &Env, flush: &DeferredFlush) -> Result<Response> {
+//!     let metrics = Metrics::default();
+//!     // ...
+//!     flush.register("metrics", async move { metrics.send().await });
+//!     Response::ok("done")
+//! }
+//! ```
+//!
+//! Clones share one task list, so components can keep their own handle.
+
+use std::cell::RefCell;
+use std::future::Future;
+use std::pin::Pin;
+use std::rc::Rc;
+
+use tracing::{debug, warn};
+use worker_stack::worker::{Context, Error, Result, ScheduleContext};
+
+use crate::concurrency::join_bounded;
+
+type FlushTask = Pin<Box<dyn Future<Output = Result<()>>>>;
+
+/// Async tasks to run once the handler is done; see the [module docs](self)
+#[derive(Clone, Default)]
+pub struct DeferredFlush {
+    tasks: Rc<RefCell<Vec<(String, FlushTask)>>>,
+}
+
+/// Outcome of [`DeferredFlush::flush`]
+#[derive(Debug, Default)]
+pub struct FlushReport {
+    pub completed: usize,
+    /// Failed tasks by the name they were registered under
+    pub failed: Vec<(String, Error)>,
+}
+
+/// Runtime contexts that can extend an invocation past its handler
+pub trait WaitUntil {
+    fn wait_until<F>(&self, future: F)
+    where
+        F: Future<Output = ()> + 'static;
+}
+
+impl WaitUntil for Context {
+    fn wait_until<F>(&self, future: F)
+    where
+        F: Future<Output = ()> + 'static,
+    {
+        Context::wait_until(self, future)
+    }
+}
+
+impl WaitUntil for ScheduleContext {
+    fn wait_until<F>(&self, future: F)
+    where
+        F: Future<Output = ()> + 'static,
+    {
+        ScheduleContext::wait_until(self, future)
+    }
+}
+
+impl DeferredFlush {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Queue `task`; `name` identifies it in logs and the [`FlushReport`]
+    pub fn register<F>(&self, name: impl Into<String>, task: F)
+    where
+        F: Future<Output = Result<()>> + 'static,
+    {
+        self.tasks.borrow_mut().push((name.into(), Box::pin(task)));
+    }
+
+    /// Number of tasks registered and not yet flushed
+    pub fn pending(&self) -> usize {
+        self.tasks.borrow().len()
+    }
+
+    /// Run every registered task concurrently, logging failures
+    ///
+    /// Tasks registered while flushing are run too.
+    pub async fn flush(&self) -> FlushReport {
+        let mut report = FlushReport::default();
+        loop {
+            let tasks = std::mem::take(&mut *self.tasks.borrow_mut());
+            if tasks.is_empty() {
+                return report;
+            }
+
+            let limit = tasks.len();
+            let (names, futures): (Vec<_>, Vec<_>) = tasks.into_iter().unzip();
+            for (name, result) in names.into_iter().zip(join_bounded(futures, limit).await) {
+                match result {
+                    Ok(()) => {
+                        debug!("Deferred flush {name} completed");
+                        report.completed += 1;
+                    }
+                    Err(e) => {
+                        warn!("Deferred flush {name} failed: {e}");
+                        report.failed.push((name, e));
+                    }
+                }
+            }
+        }
+    }
+
+    /// Hand the flush to the runtime so it completes after the response
+    pub fn wait_until(self, ctx: &impl WaitUntil) {
+        if self.pending() == 0 {
+            return;
+        }
+        ctx.wait_until(async move {
+            self.flush().await;
+        });
+    }
+}
+
+impl Drop for DeferredFlush {
+    fn drop(&mut self) {
+        if Rc::strong_count(&self.tasks) == 1 {
+            let pending = self.pending();
+            if pending > 0 {
+                warn!("DeferredFlush dropped with {pending} tasks that never ran");
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::concurrency::block_on;
+    use std::cell::Cell;
+
+    #[test]
+    fn test_flush_runs_every_task() {
+        let flush = DeferredFlush::new();
+        let sent = Rc::new(Cell::new(0));
+
+        for _ in 0..3 {
+            let sent = sent.clone();
+            flush.register("metrics", async move {
+                sent.set(sent.get() + 1);
+                Ok(())
+            });
+        }
+        let handle = flush.clone();
+        flush.register("notifications", async move {
+            // Registered mid-flush, still run
+            handle.register("late", async { Ok(()) });
+            Err(Error::RustError("queue unavailable".to_string()))
+        });
+        assert_eq!(flush.pending(), 4);
+
+        let report = block_on(flush.flush());
+        assert_eq!(sent.get(), 3);
+        assert_eq!(report.completed, 4);
+        assert_eq!(report.failed.len(), 1);
+        assert_eq!(report.failed[0].0, "notifications");
+        assert_eq!(flush.pending(), 0);
+    }
+}
@@ -8,10 +8,19 @@ use worker_stack::worker::{
 
 mod r2_notification;
 
+pub mod bindings;
+pub mod concurrency;
+pub mod envelope;
+pub mod flush;
 pub mod secrets;
+pub mod seen;
 pub mod sleep;
 pub mod timing;
+pub use concurrency::{for_each_concurrent_bounded, join_bounded};
+pub use envelope::{send_enveloped, Envelope};
+pub use flush::DeferredFlush;
 pub use r2_notification::*;
+pub use seen::SeenSet;
 
 #[cfg(feature = "axum")]
 pub mod axum;
@@ -19,18 +28,36 @@ pub mod axum;
 #[cfg(feature = "axum")]
 pub use axum::*;
 
+#[cfg(feature = "axum")]
+pub mod correlation;
+
+#[cfg(feature = "axum")]
+pub mod extract;
+
 #[cfg(feature = "sse")]
 pub mod sse;
 
 #[cfg(feature = "scheduled")]
 pub mod scheduled;
 
+#[cfg(feature = "scheduled")]
+pub mod cron;
+
 #[cfg(feature = "do-workqueue")]
 pub mod do_workqueue;
 
 #[cfg(feature = "service-binding")]
 pub mod service_binding;
 
+#[cfg(feature = "broadcast")]
+pub mod broadcast;
+
+#[cfg(feature = "error-report")]
+pub mod error_report;
+
+#[cfg(feature = "r2-chunked")]
+pub mod chunked;
+
 pub async fn send_to_queue<M>(queue: &Queue, message: &M) -> Result<()>
 where
     M: Serialize + Clone,
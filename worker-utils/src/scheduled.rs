@@ -3,6 +3,8 @@
 //! Provides a trait and runner for the common pattern of mapping cron expressions
 //! to job enum variants. The `#[event(scheduled)]` macro must live in each worker
 //! (it generates wasm bindings), but this module standardises the dispatch logic.
+//! For named tasks with last-run tracking and catch-up of missed windows, see
+//! [`crate::cron::CronRouter`].
 //!
 //! # Usage
 //!
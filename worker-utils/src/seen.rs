@@ -0,0 +1,422 @@
+//! Dedupe for queue consumers that may see the same message twice.
+//!
+//! Queues deliver at least once, so a redelivered batch would post the same
+//! sale to Discord again. [`SeenSet`] remembers ids (tx hashes, event ids)
+//! for a TTL in KV or Durable Object storage:
+//!
+//! ```rust,ignore
+//! use worker_utils::seen::SeenSet;
+//!
+//! let mut seen = SeenSet::kv(&kv, "sales").ttl(6 * 60 * 60);
+//! for message in batch.messages()? {
+//!     if seen.check_and_insert(&message.body().tx_hash).await? {
+//!         continue;
+//!     }
+//!     notify(message.body()).await?;
+//! }
+//! seen.flush().await?;
+//! ```
+//!
+//! Inserts are buffered until [`flush`](SeenSet::flush), which writes them
+//! in one go; ids inserted earlier in the same invocation are caught from
+//! memory. An id is remembered for at least the TTL.
+//!
+//! The default mode keeps one key per id. High-volume feeds can switch to
+//! [`bloom`](SeenSet::bloom) mode, which keeps a single bloom filter per
+//! TTL window instead: one read and one write per invocation, at the cost
+//! of occasionally reporting an unseen id as seen (never the reverse), and
+//! of last-write-wins between concurrent invocations.
+//!
+//! KV is eventually consistent, so two invocations in different colos can
+//! both miss an id inserted seconds earlier; use a Durable Object when
+//! that matters.
+
+use std::collections::{BTreeMap, HashSet};
+
+use worker_stack::compat::storage_get;
+use worker_stack::worker::kv::KvStore;
+use worker_stack::worker::{Error, Result, Storage};
+
+/// TTL used unless [`SeenSet::ttl`] says otherwise
+pub const DEFAULT_TTL_SECS: u64 = 24 * 60 * 60;
+
+/// Shortest expiration KV accepts
+const KV_MIN_TTL_SECS: u64 = 60;
+
+/// Bytes before the bit array in [`BloomFilter::to_bytes`]
+const BLOOM_HEADER_LEN: usize = 12;
+
+/// Sizing for [`SeenSet::bloom`]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BloomConfig {
+    /// Ids expected per TTL window
+    pub expected_items: usize,
+    /// Chance an unseen id is reported as seen, at `expected_items`
+    pub false_positive_rate: f64,
+}
+
+impl Default for BloomConfig {
+    fn default() -> Self {
+        Self {
+            expected_items: 50_000,
+            false_positive_rate: 0.001,
+        }
+    }
+}
+
+/// Fixed-size bloom filter over string ids
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BloomFilter {
+    hashes: u32,
+    created_ms: u64,
+    bits: Vec<u8>,
+}
+
+impl BloomFilter {
+    /// An empty filter sized for `config`
+    pub fn new(config: BloomConfig, created_ms: u64) -> Self {
+        let n = config.expected_items.max(1) as f64;
+        let p = config.false_positive_rate.clamp(1e-9, 0.5);
+        let ln2 = std::f64::consts::LN_2;
+        let bits = (-n * p.ln() / (ln2 * ln2)).ceil().max(8.0);
+        let hashes = ((bits / n) * ln2).round().clamp(1.0, 32.0) as u32;
+        Self {
+            hashes,
+            created_ms,
+            bits: vec![0; (bits as usize).div_ceil(8)],
+        }
+    }
+
+    pub fn created_ms(&self) -> u64 {
+        self.created_ms
+    }
+
+    /// Whether `id` may have been inserted
+    pub fn contains(&self, id: &str) -> bool {
+        self.positions(id)
+            .all(|bit| self.bits[bit / 8] & (1 << (bit % 8)) != 0)
+    }
+
+    /// Insert `id`, returning whether it may have been inserted before
+    pub fn insert(&mut self, id: &str) -> bool {
+        let mut present = true;
+        for bit in self.positions(id).collect::<Vec<_>>() {
+            let mask = 1 << (bit % 8);
+            present &= self.bits[bit / 8] & mask != 0;
+            self.bits[bit / 8] |= mask;
+        }
+        present
+    }
+
+    /// Hash count, creation time (little endian), then the bit array
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(BLOOM_HEADER_LEN + self.bits.len());
+        bytes.extend_from_slice(&self.hashes.to_le_bytes());
+        bytes.extend_from_slice(&self.created_ms.to_le_bytes());
+        bytes.extend_from_slice(&self.bits);
+        bytes
+    }
+
+    /// Parse [`to_bytes`](Self::to_bytes) output; `None` if malformed
+    pub fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        if bytes.len() <= BLOOM_HEADER_LEN {
+            return None;
+        }
+        let (header, bits) = bytes.split_at(BLOOM_HEADER_LEN);
+        let hashes = u32::from_le_bytes(header[..4].try_into().ok()?);
+        let created_ms = u64::from_le_bytes(header[4..].try_into().ok()?);
+        (hashes > 0).then(|| Self {
+            hashes,
+            created_ms,
+            bits: bits.to_vec(),
+        })
+    }
+
+    /// Bit indices for `id`, by double hashing two FNV-1a variants
+    fn positions(&self, id: &str) -> impl Iterator<Item = usize> {
+        let len = (self.bits.len() * 8) as u64;
+        let h1 = fnv1a(id.as_bytes(), 0xcbf2_9ce4_8422_2325);
+        let h2 = fnv1a(id.as_bytes(), 0x8422_2325_cbf2_9ce4) | 1;
+        (0..self.hashes as u64).map(move |i| (h1.wrapping_add(i.wrapping_mul(h2)) % len) as usize)
+    }
+}
+
+fn fnv1a(bytes: &[u8], seed: u64) -> u64 {
+    bytes.iter().fold(seed, |hash, &byte| {
+        (hash ^ byte as u64).wrapping_mul(0x0000_0100_0000_01b3)
+    })
+}
+
+enum Backend<'a> {
+    Kv(&'a KvStore),
+    DurableObject(&'a Storage),
+}
+
+/// Loaded bloom windows: the current one, and the one before it
+struct BloomWindows {
+    current: BloomFilter,
+    previous: Option<BloomFilter>,
+    rotated: bool,
+}
+
+/// TTL-bounded record of ids already handled; see the [module docs](self)
+pub struct SeenSet<'a> {
+    backend: Backend<'a>,
+    namespace: String,
+    ttl_secs: u64,
+    bloom: Option<BloomConfig>,
+    windows: Option<BloomWindows>,
+    inserted: HashSet<String>,
+    pending: Vec<String>,
+    clock: fn() -> u64,
+}
+
+impl<'a> SeenSet<'a> {
+    /// Ids stored in KV under `seen:{namespace}:`
+    pub fn kv(kv: &'a KvStore, namespace: &str) -> Self {
+        Self::with_backend(Backend::Kv(kv), namespace)
+    }
+
+    /// Ids stored in a Durable Object's storage under `seen:{namespace}:`
+    ///
+    /// Storage has no expiry, so exact-mode entries stay until overwritten;
+    /// expired ones read as unseen.
+    pub fn durable_object(storage: &'a Storage, namespace: &str) -> Self {
+        Self::with_backend(Backend::DurableObject(storage), namespace)
+    }
+
+    fn with_backend(backend: Backend<'a>, namespace: &str) -> Self {
+        Self {
+            backend,
+            namespace: namespace.to_string(),
+            ttl_secs: DEFAULT_TTL_SECS,
+            bloom: None,
+            windows: None,
+            inserted: HashSet::new(),
+            pending: Vec::new(),
+            clock: now_ms,
+        }
+    }
+
+    /// Remember ids for at least `secs`
+    pub fn ttl(mut self, secs: u64) -> Self {
+        self.ttl_secs = secs.max(1);
+        self
+    }
+
+    /// Keep a bloom filter per TTL window instead of a key per id
+    pub fn bloom(mut self, config: BloomConfig) -> Self {
+        self.bloom = Some(config);
+        self
+    }
+
+    /// Read the time (ms since epoch) from `clock` instead of the system clock
+    pub fn clock(mut self, clock: fn() -> u64) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Storage key for `id` in exact mode, or for a bloom window
+    pub fn key(&self, id: &str) -> String {
+        format!("seen:{}:{id}", self.namespace)
+    }
+
+    /// Whether `id` has been seen within the TTL
+    pub async fn contains(&mut self, id: &str) -> Result<bool> {
+        if self.inserted.contains(id) {
+            return Ok(true);
+        }
+        if self.bloom.is_some() {
+            let windows = self.windows().await?;
+            return Ok(windows.current.contains(id)
+                || windows.previous.as_ref().is_some_and(|f| f.contains(id)));
+        }
+
+        let key = self.key(id);
+        Ok(match self.backend {
+            Backend::Kv(kv) => kv.get(&key).text().await?.is_some(),
+            Backend::DurableObject(storage) => storage_get::<u64>(storage, &key)
+                .await?
+                .is_some_and(|expires_ms| expires_ms > (self.clock)()),
+        })
+    }
+
+    /// Record `id`, returning whether it had already been seen.
+    ///
+    /// The insert is buffered until [`flush`](Self::flush).
+    pub async fn check_and_insert(&mut self, id: &str) -> Result<bool> {
+        if self.contains(id).await? {
+            return Ok(true);
+        }
+        if let Some(windows) = self.windows.as_mut() {
+            windows.current.insert(id);
+        }
+        self.inserted.insert(id.to_string());
+        self.pending.push(id.to_string());
+        Ok(false)
+    }
+
+    /// Ids inserted but not yet written
+    pub fn pending(&self) -> usize {
+        self.pending.len()
+    }
+
+    /// Write buffered inserts
+    pub async fn flush(&mut self) -> Result<()> {
+        if self.pending.is_empty() && !self.windows.as_ref().is_some_and(|w| w.rotated) {
+            return Ok(());
+        }
+
+        if self.bloom.is_some() {
+            self.save_windows().await?;
+        } else {
+            let keys: Vec<String> = self.pending.iter().map(|id| self.key(id)).collect();
+            match self.backend {
+                Backend::Kv(kv) => {
+                    let ttl = self.ttl_secs.max(KV_MIN_TTL_SECS);
+                    let puts = keys.iter().map(|key| async move {
+                        kv.put(key, "1")?.expiration_ttl(ttl).execute().await?;
+                        Ok::<_, Error>(())
+                    });
+                    for result in crate::join_bounded(puts, 6).await {
+                        result?;
+                    }
+                }
+                Backend::DurableObject(storage) => {
+                    let expires_ms = (self.clock)() + self.ttl_secs * 1000;
+                    // put_multiple takes at most 128 keys
+                    for chunk in keys.chunks(128) {
+                        let entries: BTreeMap<&str, u64> =
+                            chunk.iter().map(|key| (key.as_str(), expires_ms)).collect();
+                        storage.put_multiple(entries).await?;
+                    }
+                }
+            }
+        }
+
+        self.pending.clear();
+        Ok(())
+    }
+
+    /// Load the bloom windows, rotating out ones older than the TTL
+    async fn windows(&mut self) -> Result<&BloomWindows> {
+        if self.windows.is_none() {
+            let config = self.bloom.unwrap_or_default();
+            let now = (self.clock)();
+            let ttl_ms = self.ttl_secs * 1000;
+            let current = self.load_filter("current").await?;
+            let previous = self.load_filter("previous").await?;
+
+            let windows = match current {
+                Some(current) if now < current.created_ms() + ttl_ms => BloomWindows {
+                    current,
+                    previous: previous.filter(|p| now < p.created_ms() + 2 * ttl_ms),
+                    rotated: false,
+                },
+                // An expired current window becomes the previous one
+                current => BloomWindows {
+                    current: BloomFilter::new(config, now),
+                    previous: current.filter(|c| now < c.created_ms() + 2 * ttl_ms),
+                    rotated: true,
+                },
+            };
+            self.windows = Some(windows);
+        }
+        Ok(self.windows.as_ref().expect("loaded above"))
+    }
+
+    async fn load_filter(&self, window: &str) -> Result<Option<BloomFilter>> {
+        let key = self.key(&format!("bloom:{window}"));
+        let bytes = match self.backend {
+            Backend::Kv(kv) => kv.get(&key).bytes().await?,
+            Backend::DurableObject(storage) => storage_get::<Vec<u8>>(storage, &key).await?,
+        };
+        Ok(bytes.as_deref().and_then(BloomFilter::from_bytes))
+    }
+
+    async fn save_windows(&mut self) -> Result<()> {
+        let Some(windows) = self.windows.as_ref() else {
+            return Ok(());
+        };
+        let mut filters = vec![("current", &windows.current)];
+        if windows.rotated {
+            filters.extend(windows.previous.as_ref().map(|p| ("previous", p)));
+        }
+
+        for (window, filter) in filters {
+            let key = self.key(&format!("bloom:{window}"));
+            let bytes = filter.to_bytes();
+            match self.backend {
+                Backend::Kv(kv) => {
+                    // Each window is read for two TTLs: as current, then previous
+                    let ttl = (2 * self.ttl_secs).max(KV_MIN_TTL_SECS);
+                    kv.put_bytes(&key, &bytes)?
+                        .expiration_ttl(ttl)
+                        .execute()
+                        .await?;
+                }
+                Backend::DurableObject(storage) => storage.put(&key, bytes).await?,
+            }
+        }
+
+        if let Some(windows) = self.windows.as_mut() {
+            windows.rotated = false;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+fn now_ms() -> u64 {
+    js_sys::Date::now() as u64
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn now_ms() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map_or(0, |d| d.as_millis() as u64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bloom_insert_and_contains() {
+        let config = BloomConfig {
+            expected_items: 1_000,
+            false_positive_rate: 0.01,
+        };
+        let mut filter = BloomFilter::new(config, 0);
+        for i in 0..1_000 {
+            filter.insert(&format!("tx{i}"));
+        }
+        assert!((0..1_000).all(|i| filter.contains(&format!("tx{i}"))));
+        assert!(filter.insert("tx0"));
+
+        let false_positives = (0..10_000)
+            .filter(|i| filter.contains(&format!("other{i}")))
+            .count();
+        assert!(false_positives < 300, "{false_positives} false positives");
+    }
+
+    #[test]
+    fn test_default_clock_runs_natively() {
+        assert!(now_ms() > 1_600_000_000_000);
+    }
+
+    #[test]
+    fn test_bloom_bytes_roundtrip() {
+        let mut filter = BloomFilter::new(BloomConfig::default(), 1_700_000_000_000);
+        filter.insert("abc");
+
+        let restored = BloomFilter::from_bytes(&filter.to_bytes()).unwrap();
+        assert_eq!(restored, filter);
+        assert_eq!(restored.created_ms(), 1_700_000_000_000);
+        assert!(restored.contains("abc"));
+
+        assert!(BloomFilter::from_bytes(&[]).is_none());
+        assert!(BloomFilter::from_bytes(&[0; 13]).is_none());
+    }
+}